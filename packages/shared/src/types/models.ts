@@ -14,6 +14,15 @@ export interface ModelConfig {
   providerMappings?: Record<string, string>;
   pricing?: { input: string; output: string; cachedInput?: string; cacheCreation?: string };
   context_length?: number;
+  capabilities?: ModelCapabilities;
+}
+
+export interface ModelCapabilities {
+  vision?: boolean;
+  tools?: boolean;
+  reasoning?: boolean;
+  audio?: boolean;
+  jsonMode?: boolean;
 }
 
 export interface ModelsConfiguration {
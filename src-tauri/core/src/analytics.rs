@@ -1,11 +1,23 @@
 use crate::device_id::get_or_create_device_id;
-use reqwest::Client;
-use serde::Serialize;
+use crate::storage::settings::SettingsRepository;
+use crate::storage::Storage;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
 const API_URL: &str = "https://api.talkcody.com/api/analytics/events";
 
+/// Events that failed to send, one JSON payload per line
+const BUFFER_FILE: &str = "analytics_buffer.jsonl";
+/// Oldest events are dropped beyond this many
+const MAX_BUFFERED_EVENTS: usize = 200;
+
+/// Setting key for the user's analytics opt-out (shared with the frontend)
+pub const ANALYTICS_ENABLED_SETTING: &str = "analytics_enabled";
+
 /// Analytics session information
 #[derive(Debug, Clone)]
 pub struct AnalyticsSession {
@@ -17,14 +29,18 @@ pub struct AnalyticsSession {
 /// State to store analytics session info
 pub struct AnalyticsState {
     pub session: Arc<Mutex<Option<AnalyticsSession>>>,
-    pub client: Client,
+    enabled: Arc<AtomicBool>,
+    endpoint: String,
+    buffer_path: Arc<Mutex<Option<PathBuf>>>,
 }
 
 impl Default for AnalyticsState {
     fn default() -> Self {
         Self {
             session: Arc::new(Mutex::new(None)),
-            client: Client::new(),
+            enabled: Arc::new(AtomicBool::new(true)),
+            endpoint: API_URL.to_string(),
+            buffer_path: Arc::new(Mutex::new(None)),
         }
     }
 }
@@ -33,18 +49,64 @@ impl AnalyticsState {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// State that posts events to a custom endpoint
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Keep the offline event buffer in the app data directory
+    pub fn set_buffer_dir(&self, app_data_dir: &Path) {
+        if let Ok(mut guard) = self.buffer_path.lock() {
+            *guard = Some(app_data_dir.join(BUFFER_FILE));
+        }
+    }
+
+    fn buffer_path(&self) -> Option<PathBuf> {
+        self.buffer_path.lock().ok().and_then(|guard| guard.clone())
+    }
+
+    /// Whether the user allows analytics
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Single gate for every outbound event
+    fn ensure_enabled(&self) -> Result<(), String> {
+        if self.is_enabled() {
+            Ok(())
+        } else {
+            Err("analytics disabled".to_string())
+        }
+    }
+
+    /// Forget the current session without sending session_end
+    pub fn clear_session(&self) {
+        if let Ok(mut guard) = self.session.lock() {
+            *guard = None;
+        }
+    }
 }
 
 impl Clone for AnalyticsState {
     fn clone(&self) -> Self {
         Self {
             session: Arc::clone(&self.session),
-            client: self.client.clone(),
+            enabled: Arc::clone(&self.enabled),
+            endpoint: self.endpoint.clone(),
+            buffer_path: Arc::clone(&self.buffer_path),
         }
     }
 }
 
-#[derive(Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct AnalyticsPayload {
     #[serde(rename = "eventType")]
     event_type: String,
@@ -52,14 +114,124 @@ struct AnalyticsPayload {
     session_id: String,
     #[serde(rename = "deviceId")]
     device_id: String,
-    #[serde(rename = "osName", skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "osName", default, skip_serializing_if = "Option::is_none")]
     os_name: Option<String>,
-    #[serde(rename = "osVersion", skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "osVersion", default, skip_serializing_if = "Option::is_none")]
     os_version: Option<String>,
-    #[serde(rename = "appVersion", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "appVersion",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     app_version: Option<String>,
 }
 
+fn read_buffer(path: &Path) -> Vec<AnalyticsPayload> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+fn write_buffer(path: &Path, events: &[AnalyticsPayload]) -> Result<(), String> {
+    if events.is_empty() {
+        return match std::fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("Failed to clear analytics buffer: {}", e))
+            }
+            _ => Ok(()),
+        };
+    }
+
+    let mut content = String::new();
+    for event in events {
+        let line = serde_json::to_string(event)
+            .map_err(|e| format!("Failed to serialize analytics event: {}", e))?;
+        content.push_str(&line);
+        content.push('\n');
+    }
+
+    let tmp_path = path.with_extension("jsonl.tmp");
+    std::fs::write(&tmp_path, content)
+        .map_err(|e| format!("Failed to write analytics buffer: {}", e))?;
+    std::fs::rename(&tmp_path, path)
+        .map_err(|e| format!("Failed to persist analytics buffer: {}", e))
+}
+
+/// Append events to the offline buffer, keeping only the newest ones
+fn buffer_events(state: &AnalyticsState, events: Vec<AnalyticsPayload>) {
+    if !state.is_enabled() || events.is_empty() {
+        return;
+    }
+    let Some(path) = state.buffer_path() else {
+        return;
+    };
+
+    let mut buffered = read_buffer(&path);
+    buffered.extend(events);
+    if buffered.len() > MAX_BUFFERED_EVENTS {
+        let overflow = buffered.len() - MAX_BUFFERED_EVENTS;
+        buffered.drain(..overflow);
+    }
+    if let Err(e) = write_buffer(&path, &buffered) {
+        log::error!("{}", e);
+    }
+}
+
+/// Take every buffered event, leaving the buffer empty
+fn take_buffered_events(state: &AnalyticsState) -> Vec<AnalyticsPayload> {
+    let Some(path) = state.buffer_path() else {
+        return Vec::new();
+    };
+    let events = read_buffer(&path);
+    if let Err(e) = write_buffer(&path, &[]) {
+        log::error!("{}", e);
+    }
+    events
+}
+
+async fn post_event(state: &AnalyticsState, payload: &AnalyticsPayload) -> Result<(), String> {
+    state.ensure_enabled()?;
+    let response = crate::network_proxy::shared_json_client()
+        .post(&state.endpoint)
+        .json(payload)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if response.status().is_server_error() {
+        return Err(format!("server returned {}", response.status()));
+    }
+    Ok(())
+}
+
+/// Resend buffered events. Events that still fail go back into the buffer.
+/// Returns the number of events delivered.
+pub async fn flush_buffer(state: &AnalyticsState) -> usize {
+    if !state.is_enabled() {
+        return 0;
+    }
+
+    let events = take_buffered_events(state);
+    let mut sent = 0;
+    for (index, event) in events.iter().enumerate() {
+        if let Err(e) = post_event(state, event).await {
+            log::warn!("Analytics buffer flush stopped: {}", e);
+            buffer_events(state, events[index..].to_vec());
+            break;
+        }
+        sent += 1;
+    }
+
+    if sent > 0 {
+        log::info!("Flushed {} buffered analytics events", sent);
+    }
+    sent
+}
+
 fn get_os_name() -> String {
     #[cfg(target_os = "macos")]
     return "macos".to_string();
@@ -80,6 +252,11 @@ pub async fn start_session(
     app_data_dir: &std::path::Path,
     app_version: &str,
 ) {
+    state.set_buffer_dir(app_data_dir);
+    if !state.is_enabled() {
+        log::info!("Analytics disabled, not starting a session");
+        return;
+    }
     let device_id = get_or_create_device_id(app_data_dir);
     let session_id = uuid::Uuid::new_v4().to_string();
 
@@ -114,22 +291,16 @@ pub async fn start_session(
         app_version: Some(app_version.to_string()),
     };
 
-    match state
-        .client
-        .post(API_URL)
-        .json(&payload)
-        .timeout(std::time::Duration::from_secs(10))
-        .send()
-        .await
-    {
-        Ok(response) => {
-            log::info!(
-                "Session start sent successfully, status: {}",
-                response.status()
-            );
+    match post_event(state, &payload).await {
+        Err(_) if !state.is_enabled() => {}
+        Ok(()) => {
+            log::info!("Session start sent successfully");
+            // We are online again; deliver anything left from earlier runs
+            flush_buffer(state).await;
         }
         Err(e) => {
             log::error!("Failed to send session_start: {}", e);
+            buffer_events(state, vec![payload]);
         }
     }
 }
@@ -147,6 +318,12 @@ pub fn send_session_end_sync(state: &AnalyticsState) {
     };
 
     if let Some(session) = session {
+        if state.ensure_enabled().is_err() {
+            log::info!("Analytics disabled, not sending session_end");
+            state.clear_session();
+            return;
+        }
+
         log::info!(
             "Sending session_end event for session_id={}, duration={:?}",
             session.session_id,
@@ -163,21 +340,32 @@ pub fn send_session_end_sync(state: &AnalyticsState) {
         };
 
         // Use blocking request since we're in a sync context during window close
-        let client = reqwest::blocking::Client::new();
+        let mut builder = reqwest::blocking::Client::builder();
+        match crate::network_proxy::current_proxy_config().to_reqwest_proxy() {
+            Ok(Some(proxy)) => builder = builder.proxy(proxy),
+            Ok(None) => {}
+            Err(e) => log::warn!("Ignoring proxy configuration: {}", e),
+        }
+        let client = builder.build().unwrap_or_default();
         match client
-            .post(API_URL)
+            .post(&state.endpoint)
             .json(&payload)
             .timeout(std::time::Duration::from_secs(5))
             .send()
         {
-            Ok(response) => {
+            Ok(response) if !response.status().is_server_error() => {
                 log::info!(
                     "Session end sent successfully, status: {}",
                     response.status()
                 );
             }
+            Ok(response) => {
+                log::error!("Failed to send session_end: status {}", response.status());
+                buffer_events(state, vec![payload]);
+            }
             Err(e) => {
                 log::error!("Failed to send session_end: {}", e);
+                buffer_events(state, vec![payload]);
             }
         }
 
@@ -189,3 +377,225 @@ pub fn send_session_end_sync(state: &AnalyticsState) {
         log::info!("No analytics session to end");
     }
 }
+
+fn parse_enabled(value: Option<Value>) -> bool {
+    match value {
+        Some(Value::Bool(enabled)) => enabled,
+        Some(Value::String(text)) => text != "false",
+        _ => true,
+    }
+}
+
+/// Load the opt-out setting into the analytics state
+pub async fn load_enabled_setting(state: &AnalyticsState, settings: &SettingsRepository) {
+    match settings.get_setting(ANALYTICS_ENABLED_SETTING).await {
+        Ok(value) => state.set_enabled(parse_enabled(value)),
+        Err(e) => log::warn!("Failed to read analytics setting: {}", e),
+    }
+}
+
+#[tauri::command]
+pub fn get_analytics_enabled(state: tauri::State<'_, AnalyticsState>) -> bool {
+    state.is_enabled()
+}
+
+/// Persist the opt-out setting and apply it immediately
+#[tauri::command]
+pub async fn set_analytics_enabled(
+    enabled: bool,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AnalyticsState>,
+    storage: tauri::State<'_, Storage>,
+) -> Result<(), String> {
+    use tauri::Manager;
+
+    storage
+        .settings
+        .set_setting(ANALYTICS_ENABLED_SETTING, &Value::Bool(enabled))
+        .await?;
+
+    let was_enabled = state.is_enabled();
+    state.set_enabled(enabled);
+
+    if !enabled {
+        // Drop anything pending so it is never sent
+        state.clear_session();
+        if let Some(path) = state.buffer_path() {
+            write_buffer(&path, &[])?;
+        }
+    } else if !was_enabled {
+        let app_data_dir = app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+        let app_version = app_handle.package_info().version.to_string();
+        start_session(state.inner(), &app_data_dir, &app_version).await;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use tempfile::TempDir;
+
+    /// Nothing listens on port 1, so every send fails fast
+    const UNREACHABLE_URL: &str = "http://127.0.0.1:1/events";
+
+    fn spawn_counting_server() -> (String, Arc<AtomicUsize>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/events", server.server_addr());
+        let count = Arc::new(AtomicUsize::new(0));
+        let counter = count.clone();
+        std::thread::spawn(move || {
+            for request in server.incoming_requests() {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let _ = request.respond(tiny_http::Response::from_string("ok"));
+            }
+        });
+        (url, count)
+    }
+
+    fn buffered(dir: &TempDir) -> Vec<AnalyticsPayload> {
+        read_buffer(&dir.path().join(BUFFER_FILE))
+    }
+
+    fn payload(event_type: &str) -> AnalyticsPayload {
+        AnalyticsPayload {
+            event_type: event_type.to_string(),
+            session_id: "s".to_string(),
+            device_id: "d".to_string(),
+            os_name: None,
+            os_version: None,
+            app_version: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_send_is_buffered_and_flushed_later() {
+        let dir = TempDir::new().unwrap();
+        let offline = AnalyticsState::with_endpoint(UNREACHABLE_URL);
+        start_session(&offline, dir.path(), "1.0.0").await;
+
+        let events = buffered(&dir);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, "session_start");
+
+        let (url, count) = spawn_counting_server();
+        let online = AnalyticsState::with_endpoint(url);
+        online.set_buffer_dir(dir.path());
+        assert_eq!(flush_buffer(&online).await, 1);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        assert!(buffered(&dir).is_empty());
+        assert!(!dir.path().join(BUFFER_FILE).exists());
+    }
+
+    #[tokio::test]
+    async fn successful_start_flushes_previous_events() {
+        let dir = TempDir::new().unwrap();
+        let offline = AnalyticsState::with_endpoint(UNREACHABLE_URL);
+        offline.set_buffer_dir(dir.path());
+        buffer_events(&offline, vec![payload("session_end")]);
+
+        let (url, count) = spawn_counting_server();
+        let online = AnalyticsState::with_endpoint(url);
+        start_session(&online, dir.path(), "1.0.0").await;
+
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+        assert!(buffered(&dir).is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_flush_keeps_events() {
+        let dir = TempDir::new().unwrap();
+        let state = AnalyticsState::with_endpoint(UNREACHABLE_URL);
+        state.set_buffer_dir(dir.path());
+        buffer_events(&state, vec![payload("a"), payload("b")]);
+
+        assert_eq!(flush_buffer(&state).await, 0);
+        let events = buffered(&dir);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event_type, "a");
+    }
+
+    #[test]
+    fn buffer_is_bounded() {
+        let dir = TempDir::new().unwrap();
+        let state = AnalyticsState::new();
+        state.set_buffer_dir(dir.path());
+        let events = (0..MAX_BUFFERED_EVENTS + 5)
+            .map(|i| payload(&i.to_string()))
+            .collect();
+        buffer_events(&state, events);
+
+        let events = buffered(&dir);
+        assert_eq!(events.len(), MAX_BUFFERED_EVENTS);
+        assert_eq!(events[0].event_type, "5");
+    }
+
+    #[test]
+    fn nothing_is_buffered_when_disabled() {
+        let dir = TempDir::new().unwrap();
+        let state = AnalyticsState::with_endpoint(UNREACHABLE_URL);
+        state.set_buffer_dir(dir.path());
+        state.set_enabled(false);
+        buffer_events(&state, vec![payload("session_start")]);
+        assert!(!dir.path().join(BUFFER_FILE).exists());
+    }
+
+    #[tokio::test]
+    async fn disabled_session_start_sends_nothing() {
+        let dir = TempDir::new().unwrap();
+        let (url, count) = spawn_counting_server();
+        let state = AnalyticsState::with_endpoint(url);
+        state.set_enabled(false);
+
+        start_session(&state, dir.path(), "1.0.0").await;
+        assert_eq!(flush_buffer(&state).await, 0);
+
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+        assert!(state.session.lock().unwrap().is_none());
+        assert!(buffered(&dir).is_empty());
+    }
+
+    #[test]
+    fn disabled_session_end_sends_nothing() {
+        let (url, count) = spawn_counting_server();
+        let state = AnalyticsState::with_endpoint(url);
+        *state.session.lock().unwrap() = Some(AnalyticsSession {
+            device_id: "d".to_string(),
+            session_id: "s".to_string(),
+            start_time: Instant::now(),
+        });
+        state.set_enabled(false);
+
+        send_session_end_sync(&state);
+
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+        assert!(state.session.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn enabled_session_end_is_sent() {
+        let (url, count) = spawn_counting_server();
+        let state = AnalyticsState::with_endpoint(url);
+        *state.session.lock().unwrap() = Some(AnalyticsSession {
+            device_id: "d".to_string(),
+            session_id: "s".to_string(),
+            start_time: Instant::now(),
+        });
+
+        send_session_end_sync(&state);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn parses_enabled_setting() {
+        assert!(parse_enabled(None));
+        assert!(parse_enabled(Some(Value::Bool(true))));
+        assert!(!parse_enabled(Some(Value::Bool(false))));
+        assert!(!parse_enabled(Some(Value::String("false".to_string()))));
+        assert!(parse_enabled(Some(Value::String("true".to_string()))));
+    }
+}
@@ -1,21 +1,33 @@
 // src-tauri/src/background_tasks.rs
 // Background task management for long-running processes
 
+use crate::scheduler::cron_utils::compute_next_run_at;
+use crate::scheduler::types::{ScheduledTaskExecutionPolicy, ScheduledTaskSchedule};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
 use std::path::PathBuf;
+use std::pin::Pin;
 use std::process::Stdio;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Runtime};
 use tokio::fs::{create_dir_all, remove_dir_all, File, OpenOptions};
-use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncWriteExt, BufReader};
 use tokio::process::Child;
-use tokio::sync::{broadcast, Mutex};
+use tokio::sync::{broadcast, watch, Mutex};
 use tokio::time::interval;
 
 pub const DEFAULT_MAX_TIMEOUT_MS: u64 = 7_200_000; // 2 hours
 pub const CLEANUP_DAYS: u64 = 7;
+pub const DEFAULT_MAX_BUFFER_BYTES: usize = 1024 * 1024; // 1MB per stream
+/// Shared with the frontend's MAX_CONCURRENT_TASKS; tasks past it are queued
+pub const DEFAULT_MAX_CONCURRENT_TASKS: usize = 10;
+/// Stdout lines starting with this marker carry a JSON `BackgroundTaskProgress`
+pub const PROGRESS_MARKER: &str = "::progress::";
+/// Longest partial stdout line kept while scanning for progress markers
+const MAX_PROGRESS_LINE_BYTES: usize = 4096;
 
 /// Background task status
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -26,6 +38,10 @@ pub enum BackgroundTaskStatus {
     Failed,
     Killed,
     Timeout,
+    /// Recurring task waiting for its next run
+    Scheduled,
+    /// Waiting for a free concurrency slot
+    Queued,
 }
 
 /// Background task information
@@ -43,6 +59,20 @@ pub struct BackgroundTaskInfo {
     pub error_file: String,
     pub max_timeout_ms: Option<u64>,
     pub is_timed_out: bool,
+    pub priority: i32,
+    /// Next run time for scheduled tasks
+    pub next_run_time: Option<u64>,
+    pub schedule: Option<ScheduledTaskSchedule>,
+}
+
+/// Structured progress reported by a running task
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BackgroundTaskProgress {
+    /// 0-100; clamped when reported
+    pub percent: Option<f64>,
+    pub stage: Option<String>,
+    pub message: Option<String>,
 }
 
 /// Request to spawn a background task
@@ -52,6 +82,40 @@ pub struct SpawnBackgroundTaskRequest {
     pub command: String,
     pub cwd: Option<String>,
     pub max_timeout_ms: Option<u64>,
+    /// Max bytes of stdout/stderr kept in memory per stream (oldest dropped first)
+    #[serde(default)]
+    pub max_buffer_bytes: Option<usize>,
+    /// Higher priority tasks leave the queue first when the concurrency cap is hit
+    #[serde(default)]
+    pub priority: Option<i32>,
+}
+
+/// Request to schedule a recurring background task.
+/// Exactly one of `cron` or `interval_ms` must be set.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleBackgroundTaskRequest {
+    pub command: String,
+    pub cwd: Option<String>,
+    pub max_timeout_ms: Option<u64>,
+    #[serde(default)]
+    pub max_buffer_bytes: Option<usize>,
+    #[serde(default)]
+    pub priority: Option<i32>,
+    /// 5- or 6-field cron expression
+    pub cron: Option<String>,
+    /// IANA timezone for `cron` (defaults to UTC)
+    pub timezone: Option<String>,
+    pub interval_ms: Option<u64>,
+}
+
+/// Response for schedule task
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleBackgroundTaskResponse {
+    pub task_id: String,
+    pub next_run_time: u64,
+    pub schedule: ScheduledTaskSchedule,
 }
 
 /// Response for spawn task
@@ -64,6 +128,8 @@ pub struct SpawnBackgroundTaskResponse {
     pub error_file: String,
     pub success: bool,
     pub error: Option<String>,
+    /// Task is waiting for a concurrency slot; `pid` is 0 until it starts
+    pub queued: bool,
 }
 
 /// Response for task status
@@ -76,6 +142,8 @@ pub struct GetTaskStatusResponse {
     pub running_time_ms: u64,
     pub output_bytes: u64,
     pub error_bytes: u64,
+    /// Latest progress reported by the task
+    pub progress: Option<BackgroundTaskProgress>,
 }
 
 /// Response for incremental output
@@ -88,6 +156,9 @@ pub struct GetIncrementalOutputResponse {
     pub stdout_bytes_read: u64,
     pub stderr_bytes_read: u64,
     pub is_complete: bool,
+    /// Output before the requested offset was dropped from the buffer
+    pub stdout_truncated: bool,
+    pub stderr_truncated: bool,
 }
 
 /// Response for listing tasks
@@ -97,6 +168,8 @@ pub struct ListTasksResponse {
     pub tasks: Vec<BackgroundTaskInfo>,
     pub running_count: usize,
     pub completed_count: usize,
+    pub scheduled_count: usize,
+    pub queued_count: usize,
 }
 
 /// Background task handle with process and output tracking
@@ -109,26 +182,333 @@ struct BackgroundTaskHandle {
     error_file: PathBuf,
     start_time: u64,
     max_timeout_ms: Option<u64>,
-    stdout_bytes_written: Arc<Mutex<u64>>,
-    stderr_bytes_written: Arc<Mutex<u64>>,
+    priority: i32,
+    progress: ProgressReporter,
+    stdout_buffer: Arc<Mutex<OutputRingBuffer>>,
+    stderr_buffer: Arc<Mutex<OutputRingBuffer>>,
     shutdown_tx: Option<broadcast::Sender<()>>,
     is_timed_out: bool,
     exit_code: Option<i32>,
 }
 
-/// Global background task registry
+/// Bounded in-memory output buffer. Offsets are absolute byte positions in the
+/// stream, so cursors stay valid after old bytes are dropped.
+#[derive(Debug)]
+struct OutputRingBuffer {
+    data: VecDeque<u8>,
+    capacity: usize,
+    total_written: u64,
+}
+
+/// Slice of buffered output returned to callers
+#[derive(Debug, PartialEq)]
+struct OutputChunk {
+    content: String,
+    next_offset: u64,
+    truncated: bool,
+}
+
+impl OutputRingBuffer {
+    fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            data: VecDeque::with_capacity(capacity.min(64 * 1024)),
+            capacity,
+            total_written: 0,
+        }
+    }
+
+    fn push(&mut self, bytes: &[u8]) {
+        self.total_written += bytes.len() as u64;
+        let bytes = &bytes[bytes.len().saturating_sub(self.capacity)..];
+        let overflow = (self.data.len() + bytes.len()).saturating_sub(self.capacity);
+        self.data.drain(..overflow);
+        self.data.extend(bytes);
+    }
+
+    /// Absolute offset of the oldest byte still buffered
+    fn start_offset(&self) -> u64 {
+        self.total_written - self.data.len() as u64
+    }
+
+    /// Read up to `max_bytes` starting at `offset`. Offsets older than the buffer
+    /// resume at the oldest retained byte and report `truncated`.
+    fn read_since(&self, offset: u64, max_bytes: usize) -> OutputChunk {
+        let start_offset = self.start_offset();
+        let truncated = offset < start_offset;
+        let from = offset.clamp(start_offset, self.total_written);
+        let skip = (from - start_offset) as usize;
+        let bytes: Vec<u8> = self
+            .data
+            .iter()
+            .skip(skip)
+            .take(max_bytes)
+            .copied()
+            .collect();
+
+        OutputChunk {
+            // Use lossy decoding to handle non-UTF8 output gracefully
+            content: String::from_utf8_lossy(&bytes).to_string(),
+            next_offset: from + bytes.len() as u64,
+            truncated,
+        }
+    }
+
+    /// Last `lines` lines of the buffered output; the cursor moves to the end
+    fn tail_lines(&self, lines: usize) -> OutputChunk {
+        let bytes: Vec<u8> = self.data.iter().copied().collect();
+        // A trailing newline terminates the last line rather than starting a new one
+        let body = bytes.strip_suffix(b"\n").unwrap_or(&bytes);
+        let start = if lines == 0 {
+            bytes.len()
+        } else {
+            body.iter()
+                .enumerate()
+                .rev()
+                .filter(|(_, b)| **b == b'\n')
+                .nth(lines - 1)
+                .map(|(i, _)| i + 1)
+                .unwrap_or(0)
+        };
+        // Dropped bytes may have held earlier lines of the requested window
+        let truncated = start == 0 && self.start_offset() > 0;
+
+        OutputChunk {
+            content: String::from_utf8_lossy(&bytes[start..]).to_string(),
+            next_offset: self.total_written,
+            truncated,
+        }
+    }
+}
+
+/// Receives progress updates as `(task_id, progress)`
+type ProgressSink = Arc<dyn Fn(&str, &BackgroundTaskProgress) + Send + Sync>;
+
+/// Progress sink that emits `background-task-progress-{task_id}` events
+fn app_progress_sink<R: Runtime>(app_handle: AppHandle<R>) -> ProgressSink {
+    Arc::new(move |task_id, progress| {
+        let event_name = format!("background-task-progress-{}", task_id);
+        if let Err(e) = app_handle.emit(&event_name, progress) {
+            log::warn!("Failed to emit progress for task {}: {}", task_id, e);
+        }
+    })
+}
+
+/// Tracks the latest progress of a task and forwards updates to the sink
+#[derive(Clone)]
+struct ProgressReporter {
+    task_id: String,
+    latest: Arc<Mutex<Option<BackgroundTaskProgress>>>,
+    sink: Option<ProgressSink>,
+}
+
+impl ProgressReporter {
+    fn new(task_id: String, sink: Option<ProgressSink>) -> Self {
+        Self {
+            task_id,
+            latest: Arc::new(Mutex::new(None)),
+            sink,
+        }
+    }
+
+    async fn report(&self, mut progress: BackgroundTaskProgress) {
+        progress.percent = progress
+            .percent
+            .filter(|percent| percent.is_finite())
+            .map(|percent| percent.clamp(0.0, 100.0));
+        *self.latest.lock().await = Some(progress.clone());
+        if let Some(sink) = &self.sink {
+            sink(&self.task_id, &progress);
+        }
+    }
+
+    async fn latest(&self) -> Option<BackgroundTaskProgress> {
+        self.latest.lock().await.clone()
+    }
+}
+
+/// Splits raw output into lines and extracts progress marker lines
 #[derive(Default)]
+struct ProgressLineScanner {
+    partial: Vec<u8>,
+}
+
+impl ProgressLineScanner {
+    fn feed(&mut self, bytes: &[u8]) -> Vec<BackgroundTaskProgress> {
+        let mut updates = Vec::new();
+        for &byte in bytes {
+            if byte == b'\n' {
+                if let Some(progress) = parse_progress_line(&String::from_utf8_lossy(&self.partial))
+                {
+                    updates.push(progress);
+                }
+                self.partial.clear();
+            } else if self.partial.len() < MAX_PROGRESS_LINE_BYTES {
+                self.partial.push(byte);
+            }
+        }
+        updates
+    }
+}
+
+/// Parse a `::progress::{json}` line
+fn parse_progress_line(line: &str) -> Option<BackgroundTaskProgress> {
+    let payload = line.trim().strip_prefix(PROGRESS_MARKER)?;
+    match serde_json::from_str(payload.trim()) {
+        Ok(progress) => Some(progress),
+        Err(e) => {
+            log::debug!("Ignoring malformed progress line: {}", e);
+            None
+        }
+    }
+}
+
+/// Task waiting for a concurrency slot
+struct QueuedTask {
+    task_id: String,
+    request: SpawnBackgroundTaskRequest,
+    progress_sink: Option<ProgressSink>,
+    queued_at: u64,
+}
+
+/// Concurrency limiter with a priority queue. Higher priority first, FIFO within
+/// the same priority.
+struct TaskQueue<T> {
+    max_concurrent: usize,
+    running: usize,
+    pending: Vec<(i32, u64, T)>,
+    next_seq: u64,
+}
+
+impl<T> TaskQueue<T> {
+    fn new(max_concurrent: usize) -> Self {
+        Self {
+            max_concurrent: max_concurrent.max(1),
+            running: 0,
+            pending: Vec::new(),
+            next_seq: 0,
+        }
+    }
+
+    /// Take a slot if one is free and nothing is waiting ahead
+    fn try_acquire(&mut self) -> bool {
+        if self.running < self.max_concurrent && self.pending.is_empty() {
+            self.running += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn enqueue(&mut self, priority: i32, item: T) {
+        self.pending.push((priority, self.next_seq, item));
+        self.next_seq += 1;
+    }
+
+    /// Free a slot held by a finished task
+    fn release(&mut self) {
+        self.running = self.running.saturating_sub(1);
+    }
+
+    /// Pop the next queued item if a slot is free, taking the slot
+    fn next_ready(&mut self) -> Option<T> {
+        if self.running >= self.max_concurrent {
+            return None;
+        }
+        let index = self
+            .pending
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, (priority, seq, _))| (*priority, std::cmp::Reverse(*seq)))
+            .map(|(index, _)| index)?;
+        self.running += 1;
+        Some(self.pending.remove(index).2)
+    }
+
+    fn remove_where(&mut self, predicate: impl Fn(&T) -> bool) -> Option<T> {
+        let index = self
+            .pending
+            .iter()
+            .position(|(_, _, item)| predicate(item))?;
+        Some(self.pending.remove(index).2)
+    }
+
+    fn pending(&self) -> impl Iterator<Item = (i32, &T)> {
+        self.pending
+            .iter()
+            .map(|(priority, _, item)| (*priority, item))
+    }
+}
+
+/// Recurring task definition; each run is spawned as a regular task
+/// with the id `{task_id}-{run}`
+struct ScheduledTaskHandle {
+    task_id: String,
+    command: String,
+    schedule: ScheduledTaskSchedule,
+    created_at: u64,
+    max_timeout_ms: Option<u64>,
+    next_run_time: Option<u64>,
+    run_count: u64,
+    last_run_task_id: Option<String>,
+    /// In-process job; not shown by `list_background_tasks`
+    internal: bool,
+    cancelled: bool,
+    stop_tx: watch::Sender<bool>,
+}
+
+/// Global background task registry
 struct BackgroundTaskRegistry {
     tasks: HashMap<String, Arc<Mutex<BackgroundTaskHandle>>>,
+    schedules: HashMap<String, Arc<Mutex<ScheduledTaskHandle>>>,
+    queue: TaskQueue<QueuedTask>,
+}
+
+impl Default for BackgroundTaskRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl BackgroundTaskRegistry {
     fn new() -> Self {
         Self {
             tasks: HashMap::new(),
+            schedules: HashMap::new(),
+            queue: TaskQueue::new(DEFAULT_MAX_CONCURRENT_TASKS),
         }
     }
 
+    fn is_queued(&self, task_id: &str) -> bool {
+        self.queue
+            .pending()
+            .any(|(_, task)| task.task_id == task_id)
+    }
+
+    fn insert_schedule(&mut self, task_id: String, handle: Arc<Mutex<ScheduledTaskHandle>>) {
+        self.schedules.insert(task_id, handle);
+    }
+
+    fn remove_schedule(&mut self, task_id: &str) -> Option<Arc<Mutex<ScheduledTaskHandle>>> {
+        self.schedules.remove(task_id)
+    }
+
+    fn get_all_schedules(&self) -> Vec<Arc<Mutex<ScheduledTaskHandle>>> {
+        self.schedules.values().cloned().collect()
+    }
+
+    /// Whether a task directory belongs to a live task or a pending schedule
+    fn owns_task_dir(&self, dir_name: &str) -> bool {
+        self.tasks.contains_key(dir_name)
+            || self.is_queued(dir_name)
+            || self.schedules.keys().any(|schedule_id| {
+                dir_name == schedule_id
+                    || dir_name
+                        .strip_prefix(schedule_id.as_str())
+                        .is_some_and(|rest| rest.starts_with('-'))
+            })
+    }
+
     fn insert(&mut self, task_id: String, handle: Arc<Mutex<BackgroundTaskHandle>>) {
         self.tasks.insert(task_id, handle);
     }
@@ -259,12 +639,163 @@ async fn get_registry() -> Arc<Mutex<BackgroundTaskRegistry>> {
 /// Spawn a background task
 #[tauri::command]
 pub async fn spawn_background_task(
+    app_handle: AppHandle,
     request: SpawnBackgroundTaskRequest,
+) -> Result<SpawnBackgroundTaskResponse, String> {
+    submit_task(
+        generate_task_id(),
+        request,
+        Some(app_progress_sink(app_handle)),
+    )
+    .await
+}
+
+/// Report progress for a running task (e.g. from the app side of a long operation)
+#[tauri::command]
+pub async fn report_background_task_progress(
+    task_id: String,
+    progress: BackgroundTaskProgress,
+) -> Result<(), String> {
+    validate_task_id(&task_id)?;
+
+    let handle = {
+        let registry = get_registry().await;
+        let registry_guard = registry.lock().await;
+        registry_guard.get(&task_id)
+    };
+    let handle = handle.ok_or_else(|| format!("Task not found: {}", task_id))?;
+    let reporter = handle.lock().await.progress.clone();
+    reporter.report(progress).await;
+    Ok(())
+}
+
+/// Set the maximum number of background tasks running at once
+#[tauri::command]
+pub async fn set_background_task_concurrency(max_concurrent: usize) -> Result<(), String> {
+    if max_concurrent == 0 {
+        return Err("Concurrency limit must be at least 1".to_string());
+    }
+    {
+        let registry = get_registry().await;
+        let mut registry_guard = registry.lock().await;
+        registry_guard.queue.max_concurrent = max_concurrent;
+    }
+    log::info!(
+        "Background task concurrency limit set to {}",
+        max_concurrent
+    );
+    start_queued_tasks().await;
+    Ok(())
+}
+
+/// Start the task now if a slot is free, otherwise queue it by priority
+async fn submit_task(
+    task_id: String,
+    request: SpawnBackgroundTaskRequest,
+    progress_sink: Option<ProgressSink>,
+) -> Result<SpawnBackgroundTaskResponse, String> {
+    validate_command(&request.command)?;
+
+    let priority = request.priority.unwrap_or(0);
+    let task_dir = get_task_dir(&task_id).await?;
+    let output_file = task_dir.join("stdout.log");
+    let error_file = task_dir.join("stderr.log");
+
+    let start_now = {
+        let registry = get_registry().await;
+        let mut registry_guard = registry.lock().await;
+        if registry_guard.queue.try_acquire() {
+            true
+        } else {
+            log::info!(
+                "Queueing background task {} (priority {}): {}",
+                task_id,
+                priority,
+                request.command
+            );
+            registry_guard.queue.enqueue(
+                priority,
+                QueuedTask {
+                    task_id: task_id.clone(),
+                    request,
+                    progress_sink,
+                    queued_at: current_time_ms(),
+                },
+            );
+            false
+        }
+    };
+
+    if !start_now {
+        return Ok(SpawnBackgroundTaskResponse {
+            task_id,
+            pid: 0,
+            output_file: output_file.to_string_lossy().to_string(),
+            error_file: error_file.to_string_lossy().to_string(),
+            success: true,
+            error: None,
+            queued: true,
+        });
+    }
+
+    match spawn_task(task_id, &request, progress_sink).await {
+        Ok(response) => Ok(response),
+        Err(e) => {
+            release_task_slot().await;
+            Err(e)
+        }
+    }
+}
+
+/// Free a concurrency slot and start whatever is queued next
+async fn release_task_slot() {
+    {
+        let registry = get_registry().await;
+        let mut registry_guard = registry.lock().await;
+        registry_guard.queue.release();
+    }
+    start_queued_tasks().await;
+}
+
+/// Start queued tasks while slots are free.
+/// Boxed to break the spawn_task -> monitor_process_exit -> start_queued_tasks type cycle.
+fn start_queued_tasks() -> Pin<Box<dyn Future<Output = ()> + Send>> {
+    Box::pin(async {
+        loop {
+            let next = {
+                let registry = get_registry().await;
+                let mut registry_guard = registry.lock().await;
+                registry_guard.queue.next_ready()
+            };
+            let Some(task) = next else {
+                break;
+            };
+
+            log::info!(
+                "Starting queued background task {} after {}ms",
+                task.task_id,
+                current_time_ms().saturating_sub(task.queued_at)
+            );
+            if let Err(e) =
+                spawn_task(task.task_id.clone(), &task.request, task.progress_sink).await
+            {
+                log::warn!("Queued task {} failed to start: {}", task.task_id, e);
+                let registry = get_registry().await;
+                let mut registry_guard = registry.lock().await;
+                registry_guard.queue.release();
+            }
+        }
+    })
+}
+
+async fn spawn_task(
+    task_id: String,
+    request: &SpawnBackgroundTaskRequest,
+    progress_sink: Option<ProgressSink>,
 ) -> Result<SpawnBackgroundTaskResponse, String> {
     // Validate command
     validate_command(&request.command)?;
 
-    let task_id = generate_task_id();
     let start_time = current_time_ms();
     let max_timeout = request.max_timeout_ms.unwrap_or(DEFAULT_MAX_TIMEOUT_MS);
 
@@ -340,32 +871,38 @@ pub async fn spawn_background_task(
     // Create broadcast channel for shutdown signal (multiple receivers)
     let (shutdown_tx, _) = broadcast::channel::<()>(1);
 
-    // Shared bytes counters
-    let stdout_bytes = Arc::new(Mutex::new(0u64));
-    let stderr_bytes = Arc::new(Mutex::new(0u64));
+    // Shared bounded output buffers
+    let max_buffer_bytes = request.max_buffer_bytes.unwrap_or(DEFAULT_MAX_BUFFER_BYTES);
+    let stdout_buffer = Arc::new(Mutex::new(OutputRingBuffer::new(max_buffer_bytes)));
+    let stderr_buffer = Arc::new(Mutex::new(OutputRingBuffer::new(max_buffer_bytes)));
+
+    let progress = ProgressReporter::new(task_id.clone(), progress_sink);
 
     // Spawn async tasks to read and write output
-    let stdout_bytes_clone = stdout_bytes.clone();
+    let stdout_buffer_clone = stdout_buffer.clone();
     let output_file_clone = output_file.clone();
+    let stdout_progress = progress.clone();
     let mut stdout_shutdown_rx = shutdown_tx.subscribe();
     tokio::spawn(async move {
         let _ = pipe_output_to_file(
             stdout,
             &output_file_clone,
-            stdout_bytes_clone,
+            stdout_buffer_clone,
+            Some(stdout_progress),
             &mut stdout_shutdown_rx,
         )
         .await;
     });
 
-    let stderr_bytes_clone = stderr_bytes.clone();
+    let stderr_buffer_clone = stderr_buffer.clone();
     let error_file_clone = error_file.clone();
     let mut stderr_shutdown_rx = shutdown_tx.subscribe();
     tokio::spawn(async move {
         let _ = pipe_output_to_file(
             stderr,
             &error_file_clone,
-            stderr_bytes_clone,
+            stderr_buffer_clone,
+            None,
             &mut stderr_shutdown_rx,
         )
         .await;
@@ -381,8 +918,10 @@ pub async fn spawn_background_task(
         error_file: error_file.clone(),
         start_time,
         max_timeout_ms: Some(max_timeout),
-        stdout_bytes_written: stdout_bytes,
-        stderr_bytes_written: stderr_bytes,
+        priority: request.priority.unwrap_or(0),
+        progress,
+        stdout_buffer,
+        stderr_buffer,
         shutdown_tx: Some(shutdown_tx),
         is_timed_out: false,
         exit_code: None,
@@ -415,15 +954,272 @@ pub async fn spawn_background_task(
         error_file: error_file.to_string_lossy().to_string(),
         success: true,
         error: None,
+        queued: false,
+    })
+}
+
+/// Convert a schedule request into a schedule definition
+fn build_schedule(
+    request: &ScheduleBackgroundTaskRequest,
+) -> Result<ScheduledTaskSchedule, String> {
+    match (&request.cron, request.interval_ms) {
+        (Some(expr), None) => Ok(ScheduledTaskSchedule::Cron {
+            expr: expr.clone(),
+            tz: request.timezone.clone(),
+        }),
+        (None, Some(0)) => Err("Interval must be greater than zero".to_string()),
+        (None, Some(interval_ms)) => Ok(ScheduledTaskSchedule::Every {
+            every_ms: interval_ms as i64,
+        }),
+        (Some(_), Some(_)) => {
+            Err("Specify either a cron expression or an interval, not both".to_string())
+        }
+        (None, None) => Err("A cron expression or an interval is required".to_string()),
+    }
+}
+
+/// Compute the next run time strictly after `after_ms`
+fn next_run_after(
+    schedule: &ScheduledTaskSchedule,
+    after_ms: u64,
+    task_id: &str,
+) -> Result<u64, String> {
+    // No stagger: background tasks are local and don't need jitter
+    let policy = ScheduledTaskExecutionPolicy {
+        stagger_ms: 0,
+        ..ScheduledTaskExecutionPolicy::default()
+    };
+    let next = compute_next_run_at(schedule, &policy, after_ms as i64, task_id)?;
+    Ok(next.max(0) as u64)
+}
+
+/// Schedule a recurring background task (cron or fixed interval)
+#[tauri::command]
+pub async fn schedule_background_task(
+    app_handle: AppHandle,
+    request: ScheduleBackgroundTaskRequest,
+) -> Result<ScheduleBackgroundTaskResponse, String> {
+    validate_command(&request.command)?;
+    let schedule = build_schedule(&request)?;
+    let task_id = generate_task_id();
+
+    log::info!(
+        "Scheduling background task {} ({:?}): {}",
+        task_id,
+        schedule,
+        request.command
+    );
+
+    let spawn_request = SpawnBackgroundTaskRequest {
+        command: request.command.clone(),
+        cwd: request.cwd.clone(),
+        max_timeout_ms: request.max_timeout_ms,
+        max_buffer_bytes: request.max_buffer_bytes,
+        priority: request.priority,
+    };
+    let progress_sink = app_progress_sink(app_handle);
+    let next_run_time = register_schedule(
+        task_id.clone(),
+        request.command.clone(),
+        request.max_timeout_ms,
+        schedule.clone(),
+        false,
+        move |run_task_id| {
+            let spawn_request = spawn_request.clone();
+            let progress_sink = progress_sink.clone();
+            async move {
+                submit_task(run_task_id, spawn_request, Some(progress_sink))
+                    .await
+                    .map(|_| ())
+            }
+        },
+    )
+    .await?;
+
+    Ok(ScheduleBackgroundTaskResponse {
+        task_id,
+        next_run_time,
+        schedule,
     })
 }
 
-/// Pipe output from reader to file with graceful shutdown
-/// Uses raw byte reading to handle non-UTF8 output and avoid line-buffering issues
+/// Run an in-process job on a fixed interval, reusing the schedule machinery.
+/// The schedule is hidden from the task list but can be killed by id like any
+/// other scheduled task. Returns the schedule's task id.
+pub async fn schedule_internal_job<F, Fut>(
+    label: &str,
+    interval_ms: u64,
+    mut job: F,
+) -> Result<String, String>
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(), String>> + Send,
+{
+    if interval_ms == 0 {
+        return Err("Interval must be greater than zero".to_string());
+    }
+    let task_id = generate_task_id();
+    register_schedule(
+        task_id.clone(),
+        label.to_string(),
+        None,
+        ScheduledTaskSchedule::Every {
+            every_ms: interval_ms as i64,
+        },
+        true,
+        move |_run_task_id| job(),
+    )
+    .await?;
+    Ok(task_id)
+}
+
+/// Run an in-process job once at `run_at_ms` (immediately if that is in the past).
+/// Returns the schedule's task id, which can be killed to cancel the run.
+pub async fn schedule_internal_job_at<F, Fut>(
+    label: &str,
+    run_at_ms: u64,
+    job: F,
+) -> Result<String, String>
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(), String>> + Send,
+{
+    let at = chrono::DateTime::from_timestamp_millis(run_at_ms as i64)
+        .ok_or_else(|| format!("Invalid run time: {}", run_at_ms))?
+        .to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+    let task_id = generate_task_id();
+    let mut job = Some(job);
+    register_schedule(
+        task_id.clone(),
+        label.to_string(),
+        None,
+        ScheduledTaskSchedule::At { at },
+        true,
+        move |_run_task_id| {
+            let job = job.take();
+            async move {
+                match job {
+                    Some(job) => job().await,
+                    None => Ok(()),
+                }
+            }
+        },
+    )
+    .await?;
+    Ok(task_id)
+}
+
+/// Register a schedule and start its run loop; `fire` is invoked with the run's task id
+async fn register_schedule<F, Fut>(
+    task_id: String,
+    command: String,
+    max_timeout_ms: Option<u64>,
+    schedule: ScheduledTaskSchedule,
+    internal: bool,
+    fire: F,
+) -> Result<u64, String>
+where
+    F: FnMut(String) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(), String>> + Send,
+{
+    let now = current_time_ms();
+    let next_run_time = next_run_after(&schedule, now, &task_id)?;
+    let (stop_tx, stop_rx) = watch::channel(false);
+
+    let handle = Arc::new(Mutex::new(ScheduledTaskHandle {
+        task_id: task_id.clone(),
+        command,
+        schedule,
+        created_at: now,
+        max_timeout_ms,
+        next_run_time: Some(next_run_time),
+        run_count: 0,
+        last_run_task_id: None,
+        internal,
+        cancelled: false,
+        stop_tx,
+    }));
+
+    {
+        let registry = get_registry().await;
+        let mut registry_guard = registry.lock().await;
+        registry_guard.insert_schedule(task_id, handle.clone());
+    }
+
+    tokio::spawn(run_schedule_loop(handle, stop_rx, fire));
+    Ok(next_run_time)
+}
+
+/// Wait for each run time and fire until cancelled or the schedule is exhausted
+async fn run_schedule_loop<F, Fut>(
+    handle: Arc<Mutex<ScheduledTaskHandle>>,
+    mut stop_rx: watch::Receiver<bool>,
+    mut fire: F,
+) where
+    F: FnMut(String) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(), String>> + Send,
+{
+    loop {
+        let next_run_time = {
+            let guard = handle.lock().await;
+            if guard.cancelled {
+                break;
+            }
+            guard.next_run_time
+        };
+        let Some(next_run_time) = next_run_time else {
+            break;
+        };
+
+        let wait_ms = next_run_time.saturating_sub(current_time_ms());
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_millis(wait_ms)) => {}
+            _ = stop_rx.changed() => {}
+        }
+
+        // Hold the handle while firing so a concurrent kill can't race a new run
+        let mut guard = handle.lock().await;
+        if guard.cancelled {
+            break;
+        }
+        if current_time_ms() < next_run_time {
+            continue;
+        }
+
+        guard.run_count += 1;
+        let run_task_id = format!("{}-{}", guard.task_id, guard.run_count);
+        guard.last_run_task_id = Some(run_task_id.clone());
+        guard.next_run_time = match guard.schedule {
+            // One-shot schedules fire once
+            ScheduledTaskSchedule::At { .. } => None,
+            _ => match next_run_after(&guard.schedule, current_time_ms(), &guard.task_id) {
+                Ok(next) => Some(next),
+                Err(e) => {
+                    log::warn!("Schedule {} has no further runs: {}", guard.task_id, e);
+                    None
+                }
+            },
+        };
+
+        log::info!(
+            "Scheduled task {} firing run {}",
+            guard.task_id,
+            run_task_id
+        );
+        if let Err(e) = fire(run_task_id.clone()).await {
+            log::warn!("Scheduled run {} failed to start: {}", run_task_id, e);
+        }
+    }
+}
+
+/// Pipe output from reader to file and the in-memory buffer with graceful shutdown
+/// Uses raw byte reading to handle non-UTF8 output and avoid line-buffering issues.
+/// With a `progress` reporter, `::progress::` lines are parsed and reported.
 async fn pipe_output_to_file(
     reader: impl tokio::io::AsyncRead + Unpin,
     file_path: &PathBuf,
-    bytes_written: Arc<Mutex<u64>>,
+    buffer: Arc<Mutex<OutputRingBuffer>>,
+    progress: Option<ProgressReporter>,
     shutdown_rx: &mut broadcast::Receiver<()>,
 ) -> Result<(), String> {
     use tokio::io::AsyncReadExt;
@@ -437,6 +1233,7 @@ async fn pipe_output_to_file(
 
     let mut buf_reader = BufReader::new(reader);
     let mut buf = [0u8; 8192];
+    let mut scanner = ProgressLineScanner::default();
 
     loop {
         tokio::select! {
@@ -451,9 +1248,14 @@ async fn pipe_output_to_file(
                         file.write_all(&buf[..n]).await.map_err(|e| e.to_string())?;
                         file.flush().await.map_err(|e| e.to_string())?;
 
-                        // Update bytes written with actual byte count
-                        let mut bytes = bytes_written.lock().await;
-                        *bytes += n as u64;
+                        // Keep the tail in memory for polling; the file has the full log
+                        buffer.lock().await.push(&buf[..n]);
+
+                        if let Some(reporter) = &progress {
+                            for update in scanner.feed(&buf[..n]) {
+                                reporter.report(update).await;
+                            }
+                        }
                     }
                     Err(e) => {
                         log::warn!("Error reading output: {}", e);
@@ -496,6 +1298,8 @@ async fn monitor_process_exit(task_id: String, handle: Arc<Mutex<BackgroundTaskH
             }
         }
     }
+
+    release_task_slot().await;
 }
 
 /// Monitor task timeout
@@ -572,19 +1376,34 @@ pub async fn get_background_task_status(task_id: String) -> Result<GetTaskStatus
     validate_task_id(&task_id)?;
 
     let registry = get_registry().await;
-    let handle = {
+    let (handle, is_queued) = {
         let registry_guard = registry.lock().await;
-        registry_guard.get(&task_id)
+        (
+            registry_guard.get(&task_id),
+            registry_guard.is_queued(&task_id),
+        )
     };
 
+    if is_queued {
+        return Ok(GetTaskStatusResponse {
+            task_id,
+            status: BackgroundTaskStatus::Queued,
+            exit_code: None,
+            running_time_ms: 0,
+            output_bytes: 0,
+            error_bytes: 0,
+            progress: None,
+        });
+    }
+
     if let Some(handle) = handle {
         let guard = handle.lock().await;
 
         let status = determine_task_status(guard.exit_code, guard.is_timed_out);
         let running_time = current_time_ms() - guard.start_time;
 
-        let stdout_bytes = *guard.stdout_bytes_written.lock().await;
-        let stderr_bytes = *guard.stderr_bytes_written.lock().await;
+        let stdout_bytes = guard.stdout_buffer.lock().await.total_written;
+        let stderr_bytes = guard.stderr_buffer.lock().await.total_written;
 
         Ok(GetTaskStatusResponse {
             task_id,
@@ -593,6 +1412,7 @@ pub async fn get_background_task_status(task_id: String) -> Result<GetTaskStatus
             running_time_ms: running_time,
             output_bytes: stdout_bytes,
             error_bytes: stderr_bytes,
+            progress: guard.progress.latest().await,
         })
     } else {
         Err(format!("Task not found: {}", task_id))
@@ -600,43 +1420,71 @@ pub async fn get_background_task_status(task_id: String) -> Result<GetTaskStatus
 }
 
 /// Get incremental output
+/// `stdout_bytes_read`/`stderr_bytes_read` are since-offset cursors returned by the
+/// previous call. With `tail_lines`, only the last N lines of each stream are returned
+/// and the cursors move to the end of the buffered output.
 #[tauri::command]
 pub async fn get_background_task_output(
     task_id: String,
     stdout_bytes_read: u64,
     stderr_bytes_read: u64,
+    tail_lines: Option<usize>,
 ) -> Result<GetIncrementalOutputResponse, String> {
     validate_task_id(&task_id)?;
 
     let registry = get_registry().await;
-    let handle = {
+    let (handle, is_queued) = {
         let registry_guard = registry.lock().await;
-        registry_guard.get(&task_id)
+        (
+            registry_guard.get(&task_id),
+            registry_guard.is_queued(&task_id),
+        )
     };
 
+    if is_queued {
+        // Nothing has run yet; keep the caller's cursors
+        return Ok(GetIncrementalOutputResponse {
+            task_id,
+            new_stdout: String::new(),
+            new_stderr: String::new(),
+            stdout_bytes_read,
+            stderr_bytes_read,
+            is_complete: false,
+            stdout_truncated: false,
+            stderr_truncated: false,
+        });
+    }
+
     if let Some(handle) = handle {
         let guard = handle.lock().await;
 
-        // Read new content from files with efficient seek
-        let output_file = &guard.output_file;
-        let error_file = &guard.error_file;
-
-        // Returns (content, next_offset) to prevent skipping data when reads are capped
-        let (new_stdout, next_stdout_offset) =
-            read_file_content(output_file, stdout_bytes_read).await?;
-        let (new_stderr, next_stderr_offset) =
-            read_file_content(error_file, stderr_bytes_read).await?;
+        let (stdout, stderr) = {
+            let stdout_buffer = guard.stdout_buffer.lock().await;
+            let stderr_buffer = guard.stderr_buffer.lock().await;
+            match tail_lines {
+                Some(lines) => (
+                    stdout_buffer.tail_lines(lines),
+                    stderr_buffer.tail_lines(lines),
+                ),
+                None => (
+                    stdout_buffer.read_since(stdout_bytes_read, MAX_READ_BYTES),
+                    stderr_buffer.read_since(stderr_bytes_read, MAX_READ_BYTES),
+                ),
+            }
+        };
 
         // Check if process is complete (including timed out tasks)
         let is_complete = guard.exit_code.is_some() || guard.is_timed_out;
 
         Ok(GetIncrementalOutputResponse {
             task_id,
-            new_stdout,
-            new_stderr,
-            stdout_bytes_read: next_stdout_offset,
-            stderr_bytes_read: next_stderr_offset,
+            new_stdout: stdout.content,
+            new_stderr: stderr.content,
+            stdout_bytes_read: stdout.next_offset,
+            stderr_bytes_read: stderr.next_offset,
             is_complete,
+            stdout_truncated: stdout.truncated,
+            stderr_truncated: stderr.truncated,
         })
     } else {
         Err(format!("Task not found: {}", task_id))
@@ -646,64 +1494,64 @@ pub async fn get_background_task_output(
 /// Maximum bytes to read in a single call to prevent memory issues
 const MAX_READ_BYTES: usize = 64 * 1024; // 64KB
 
-/// Read file content from specific byte offset with efficient seeking
-/// Returns (content, next_offset) where next_offset is from_byte + bytes_read
-/// This ensures incremental output doesn't skip data when capping reads
-async fn read_file_content(file_path: &PathBuf, from_byte: u64) -> Result<(String, u64), String> {
-    use tokio::io::AsyncSeekExt;
-
-    if !file_path.exists() {
-        return Ok((String::new(), from_byte));
-    }
+/// Kill a background task
+/// Keeps the task registered until the process is confirmed terminated.
+/// For scheduled tasks this cancels all future runs and kills the current one.
+#[tauri::command]
+pub async fn kill_background_task(task_id: String) -> Result<bool, String> {
+    validate_task_id(&task_id)?;
 
-    let mut file = OpenOptions::new()
-        .read(true)
-        .open(file_path)
-        .await
-        .map_err(|e| e.to_string())?;
+    let schedule = {
+        let registry = get_registry().await;
+        let mut registry_guard = registry.lock().await;
+        registry_guard.remove_schedule(&task_id)
+    };
 
-    let metadata = file.metadata().await.map_err(|e| e.to_string())?;
-    let file_size = metadata.len();
+    if let Some(schedule) = schedule {
+        let last_run_task_id = {
+            let mut guard = schedule.lock().await;
+            guard.cancelled = true;
+            guard.next_run_time = None;
+            let _ = guard.stop_tx.send(true);
+            guard.last_run_task_id.clone()
+        };
+        log::info!("Cancelled scheduled background task {}", task_id);
 
-    if from_byte >= file_size {
-        return Ok((String::new(), from_byte));
+        if let Some(run_task_id) = last_run_task_id {
+            if !remove_queued_task(&run_task_id).await {
+                kill_task_process(&run_task_id).await?;
+            }
+        }
+        return Ok(true);
     }
 
-    // Seek to the position
-    file.seek(std::io::SeekFrom::Start(from_byte))
-        .await
-        .map_err(|e| e.to_string())?;
-
-    // Limit bytes to read to prevent memory issues with large files
-    let remaining = (file_size - from_byte) as usize;
-    let bytes_to_read = remaining.min(MAX_READ_BYTES);
-    let mut buffer = vec![0u8; bytes_to_read];
-
-    // Use read instead of read_exact to handle partial reads
-    let n = file.read(&mut buffer).await.map_err(|e| e.to_string())?;
-
-    // Truncate buffer to actual bytes read
-    buffer.truncate(n);
-
-    // Calculate next offset based on actual bytes read
-    let next_offset = from_byte + n as u64;
+    if remove_queued_task(&task_id).await {
+        return Ok(true);
+    }
 
-    // Use lossy decoding to handle non-UTF8 output gracefully
-    let content = String::from_utf8_lossy(&buffer).to_string();
-    Ok((content, next_offset))
+    kill_task_process(&task_id).await
 }
 
-/// Kill a background task
-/// Keeps the task registered until the process is confirmed terminated
-#[tauri::command]
-pub async fn kill_background_task(task_id: String) -> Result<bool, String> {
-    validate_task_id(&task_id)?;
+/// Drop a task that is still waiting for a slot
+async fn remove_queued_task(task_id: &str) -> bool {
+    let registry = get_registry().await;
+    let mut registry_guard = registry.lock().await;
+    let removed = registry_guard
+        .queue
+        .remove_where(|task| task.task_id == task_id)
+        .is_some();
+    if removed {
+        log::info!("Removed queued background task {}", task_id);
+    }
+    removed
+}
 
+async fn kill_task_process(task_id: &str) -> Result<bool, String> {
     let registry = get_registry().await;
     // Get handle without removing from registry yet
     let handle = {
         let registry_guard = registry.lock().await;
-        registry_guard.get(&task_id).map(|h| Arc::clone(&h))
+        registry_guard.get(task_id).map(|h| Arc::clone(&h))
     };
 
     if let Some(handle) = handle {
@@ -729,7 +1577,7 @@ pub async fn kill_background_task(task_id: String) -> Result<bool, String> {
 
         // Only remove from registry after successful kill
         let mut registry_guard = registry.lock().await;
-        registry_guard.remove(&task_id);
+        registry_guard.remove(task_id);
 
         Ok(true)
     } else {
@@ -741,14 +1589,40 @@ pub async fn kill_background_task(task_id: String) -> Result<bool, String> {
 #[tauri::command]
 pub async fn list_background_tasks() -> Result<ListTasksResponse, String> {
     let registry = get_registry().await;
-    let handles = {
+    let (handles, schedules, queued) = {
         let registry_guard = registry.lock().await;
-        registry_guard.get_all()
+        let queued: Vec<BackgroundTaskInfo> = registry_guard
+            .queue
+            .pending()
+            .map(|(priority, task)| BackgroundTaskInfo {
+                task_id: task.task_id.clone(),
+                pid: 0,
+                command: task.request.command.clone(),
+                status: BackgroundTaskStatus::Queued,
+                exit_code: None,
+                start_time: task.queued_at,
+                end_time: None,
+                output_file: String::new(),
+                error_file: String::new(),
+                max_timeout_ms: task.request.max_timeout_ms,
+                is_timed_out: false,
+                priority,
+                next_run_time: None,
+                schedule: None,
+            })
+            .collect();
+        (
+            registry_guard.get_all(),
+            registry_guard.get_all_schedules(),
+            queued,
+        )
     };
 
     let mut tasks: Vec<BackgroundTaskInfo> = Vec::new();
     let mut running_count = 0;
     let mut completed_count = 0;
+    let mut scheduled_count = 0;
+    let queued_count = queued.len();
 
     for handle in handles {
         let guard = handle.lock().await;
@@ -775,15 +1649,47 @@ pub async fn list_background_tasks() -> Result<ListTasksResponse, String> {
             error_file: guard.error_file.to_string_lossy().to_string(),
             max_timeout_ms: guard.max_timeout_ms,
             is_timed_out: guard.is_timed_out,
+            priority: guard.priority,
+            next_run_time: None,
+            schedule: None,
         };
 
         tasks.push(task_info);
     }
 
+    for schedule in schedules {
+        let guard = schedule.lock().await;
+        if guard.cancelled || guard.internal {
+            continue;
+        }
+        scheduled_count += 1;
+
+        tasks.push(BackgroundTaskInfo {
+            task_id: guard.task_id.clone(),
+            pid: 0,
+            command: guard.command.clone(),
+            status: BackgroundTaskStatus::Scheduled,
+            exit_code: None,
+            start_time: guard.created_at,
+            end_time: None,
+            output_file: String::new(),
+            error_file: String::new(),
+            max_timeout_ms: guard.max_timeout_ms,
+            is_timed_out: false,
+            priority: 0,
+            next_run_time: guard.next_run_time,
+            schedule: Some(guard.schedule.clone()),
+        });
+    }
+
+    tasks.extend(queued);
+
     Ok(ListTasksResponse {
         tasks,
         running_count,
         completed_count,
+        scheduled_count,
+        queued_count,
     })
 }
 
@@ -797,6 +1703,7 @@ pub async fn cleanup_background_tasks() -> Result<u32, String> {
         return Ok(0);
     }
 
+    let registry = get_registry().await;
     let mut cleaned_count = 0;
     let entries = std::fs::read_dir(&bg_dir).map_err(|e| e.to_string())?;
 
@@ -806,6 +1713,12 @@ pub async fn cleanup_background_tasks() -> Result<u32, String> {
             continue;
         }
 
+        // Never remove output of live tasks or pending schedules
+        let dir_name = entry.file_name().to_string_lossy().to_string();
+        if registry.lock().await.owns_task_dir(&dir_name) {
+            continue;
+        }
+
         if let Ok(metadata) = entry.metadata() {
             if let Ok(modified) = metadata.modified() {
                 if modified < cutoff {
@@ -1080,6 +1993,9 @@ mod tests {
             error_file: "/tmp/stderr.log".to_string(),
             max_timeout_ms: Some(7200000),
             is_timed_out: false,
+            priority: 0,
+            next_run_time: None,
+            schedule: None,
         };
 
         let json = serde_json::to_string(&info).unwrap();
@@ -1113,6 +2029,7 @@ mod tests {
             error_file: "/tmp/err.log".to_string(),
             success: true,
             error: None,
+            queued: false,
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -1137,4 +2054,486 @@ mod tests {
     fn test_cleanup_days_is_seven() {
         assert_eq!(CLEANUP_DAYS, 7);
     }
+
+    // =========================================================================
+    // Tests for OutputRingBuffer
+    // =========================================================================
+
+    #[test]
+    fn test_ring_buffer_caps_at_configured_size() {
+        let mut buffer = OutputRingBuffer::new(10);
+        buffer.push(b"0123456789");
+        buffer.push(b"abcde");
+        assert_eq!(buffer.data.len(), 10);
+        assert_eq!(buffer.total_written, 15);
+        assert_eq!(buffer.start_offset(), 5);
+        assert_eq!(buffer.read_since(0, MAX_READ_BYTES).content, "56789abcde");
+
+        // A single write larger than the cap keeps only its tail
+        buffer.push(b"ABCDEFGHIJKLMNOP");
+        assert_eq!(buffer.data.len(), 10);
+        assert_eq!(buffer.read_since(0, MAX_READ_BYTES).content, "GHIJKLMNOP");
+    }
+
+    #[test]
+    fn test_ring_buffer_read_since_offset() {
+        let mut buffer = OutputRingBuffer::new(8);
+        buffer.push(b"hello");
+        let first = buffer.read_since(0, MAX_READ_BYTES);
+        assert_eq!(first.content, "hello");
+        assert_eq!(first.next_offset, 5);
+        assert!(!first.truncated);
+
+        buffer.push(b" world");
+        let second = buffer.read_since(first.next_offset, MAX_READ_BYTES);
+        assert_eq!(second.content, " world");
+        assert_eq!(second.next_offset, 11);
+        assert!(!second.truncated);
+
+        // Cursor older than the buffer resumes at the oldest retained byte
+        let stale = buffer.read_since(0, MAX_READ_BYTES);
+        assert_eq!(stale.content, "lo world");
+        assert!(stale.truncated);
+
+        // Reads are capped and the cursor advances by the bytes returned
+        let capped = buffer.read_since(3, 2);
+        assert_eq!(capped.content, "lo");
+        assert_eq!(capped.next_offset, 5);
+
+        let caught_up = buffer.read_since(11, MAX_READ_BYTES);
+        assert_eq!(caught_up.content, "");
+        assert_eq!(caught_up.next_offset, 11);
+    }
+
+    #[test]
+    fn test_ring_buffer_tail_lines_returns_last_n_lines() {
+        let mut buffer = OutputRingBuffer::new(1024);
+        buffer.push(b"one\ntwo\nthree\nfour\n");
+
+        let tail = buffer.tail_lines(2);
+        assert_eq!(tail.content, "three\nfour\n");
+        assert_eq!(tail.next_offset, buffer.total_written);
+        assert!(!tail.truncated);
+
+        assert_eq!(buffer.tail_lines(1).content, "four\n");
+        assert_eq!(buffer.tail_lines(4).content, "one\ntwo\nthree\nfour\n");
+        assert_eq!(buffer.tail_lines(10).content, "one\ntwo\nthree\nfour\n");
+        assert_eq!(buffer.tail_lines(0).content, "");
+
+        // Partial last line counts as a line
+        buffer.push(b"fiv");
+        assert_eq!(buffer.tail_lines(2).content, "four\nfiv");
+    }
+
+    #[test]
+    fn test_ring_buffer_tail_lines_reports_dropped_lines() {
+        let mut buffer = OutputRingBuffer::new(8);
+        buffer.push(b"aaaa\nbbbb\ncc\n");
+        let tail = buffer.tail_lines(5);
+        assert_eq!(tail.content, "bbbb\ncc\n");
+        assert!(tail.truncated);
+    }
+
+    // =========================================================================
+    // Tests for TaskQueue (priority + concurrency cap)
+    // =========================================================================
+
+    /// Drive the queue like the registry does: submit, then finish tasks one at a time
+    fn submit(queue: &mut TaskQueue<&'static str>, priority: i32, name: &'static str) -> bool {
+        if queue.try_acquire() {
+            true
+        } else {
+            queue.enqueue(priority, name);
+            false
+        }
+    }
+
+    #[test]
+    fn test_task_queue_caps_concurrency() {
+        let mut queue = TaskQueue::new(2);
+        let started: Vec<bool> = ["a", "b", "c", "d", "e"]
+            .into_iter()
+            .map(|name| submit(&mut queue, 0, name))
+            .collect();
+
+        assert_eq!(started, vec![true, true, false, false, false]);
+        assert_eq!(queue.running, 2);
+        assert_eq!(queue.pending().count(), 3);
+        // No slot is free until a running task finishes
+        assert_eq!(queue.next_ready(), None);
+
+        let mut max_running = queue.running;
+        while queue.running > 0 {
+            queue.release();
+            while queue.next_ready().is_some() {
+                max_running = max_running.max(queue.running);
+            }
+        }
+        assert_eq!(max_running, 2);
+        assert_eq!(queue.pending().count(), 0);
+    }
+
+    #[test]
+    fn test_task_queue_starts_higher_priority_first() {
+        let mut queue = TaskQueue::new(1);
+        assert!(submit(&mut queue, 0, "running"));
+        assert!(!submit(&mut queue, 0, "low-1"));
+        assert!(!submit(&mut queue, 10, "high"));
+        assert!(!submit(&mut queue, 0, "low-2"));
+        assert!(!submit(&mut queue, 5, "medium"));
+
+        let mut order = Vec::new();
+        loop {
+            queue.release();
+            match queue.next_ready() {
+                Some(name) => order.push(name),
+                None => break,
+            }
+        }
+        // FIFO within the same priority
+        assert_eq!(order, vec!["high", "medium", "low-1", "low-2"]);
+    }
+
+    #[test]
+    fn test_task_queue_new_tasks_wait_behind_queue() {
+        let mut queue = TaskQueue::new(1);
+        assert!(submit(&mut queue, 0, "a"));
+        assert!(!submit(&mut queue, 0, "b"));
+        queue.release();
+        // Newcomers can't grab a freed slot; they are ordered with the waiting tasks
+        assert!(!submit(&mut queue, 100, "c"));
+        assert_eq!(queue.next_ready(), Some("c"));
+    }
+
+    #[test]
+    fn test_task_queue_remove_queued() {
+        let mut queue = TaskQueue::new(1);
+        assert!(submit(&mut queue, 0, "a"));
+        assert!(!submit(&mut queue, 0, "b"));
+        assert_eq!(queue.remove_where(|name| *name == "b"), Some("b"));
+        assert_eq!(queue.remove_where(|name| *name == "b"), None);
+        queue.release();
+        assert_eq!(queue.next_ready(), None);
+        assert_eq!(queue.running, 0);
+    }
+
+    // =========================================================================
+    // Tests for progress reporting
+    // =========================================================================
+
+    #[test]
+    fn test_progress_scanner_parses_marker_lines() {
+        let mut scanner = ProgressLineScanner::default();
+        // Marker split across chunks, surrounded by regular output
+        assert!(scanner
+            .feed(b"building...\n::progress::{\"percent\":25,")
+            .is_empty());
+        let updates = scanner.feed(b"\"stage\":\"index\"}\nmore output\n");
+        assert_eq!(
+            updates,
+            vec![BackgroundTaskProgress {
+                percent: Some(25.0),
+                stage: Some("index".to_string()),
+                message: None,
+            }]
+        );
+
+        // Malformed payloads and unterminated lines are ignored
+        assert!(scanner.feed(b"::progress::not json\n").is_empty());
+        assert!(scanner.feed(b"::progress::{\"percent\":50}").is_empty());
+        assert_eq!(scanner.feed(b"\n").len(), 1);
+    }
+
+    #[test]
+    fn test_parse_progress_line_requires_marker_prefix() {
+        assert_eq!(
+            parse_progress_line("  ::progress:: {\"message\":\"hi\"}  "),
+            Some(BackgroundTaskProgress {
+                message: Some("hi".to_string()),
+                ..Default::default()
+            })
+        );
+        assert_eq!(parse_progress_line("echo ::progress::{}"), None);
+    }
+
+    /// This test uses Tauri test infrastructure that may not work on Windows CI
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_progress_reported_in_status_and_events() {
+        use std::sync::mpsc;
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let task_id = generate_task_id();
+        let (event_tx, event_rx) = mpsc::channel();
+        app.listen(
+            format!("background-task-progress-{}", task_id),
+            move |event| {
+                let _ = event_tx.send(event.payload().to_string());
+            },
+        );
+
+        let child = crate::shell_utils::new_async_command("sleep")
+            .arg("30")
+            .spawn()
+            .expect("spawn sleep");
+        let pid = child.id().unwrap_or_default();
+        let handle = Arc::new(Mutex::new(BackgroundTaskHandle {
+            task_id: task_id.clone(),
+            command: "sleep 30".to_string(),
+            child,
+            pid,
+            output_file: PathBuf::new(),
+            error_file: PathBuf::new(),
+            start_time: current_time_ms(),
+            max_timeout_ms: None,
+            priority: 0,
+            progress: ProgressReporter::new(
+                task_id.clone(),
+                Some(app_progress_sink(app.handle().clone())),
+            ),
+            stdout_buffer: Arc::new(Mutex::new(OutputRingBuffer::new(16))),
+            stderr_buffer: Arc::new(Mutex::new(OutputRingBuffer::new(16))),
+            shutdown_tx: None,
+            is_timed_out: false,
+            exit_code: None,
+        }));
+        get_registry()
+            .await
+            .lock()
+            .await
+            .insert(task_id.clone(), handle);
+
+        let status = get_background_task_status(task_id.clone()).await.unwrap();
+        assert_eq!(status.progress, None);
+
+        report_background_task_progress(
+            task_id.clone(),
+            BackgroundTaskProgress {
+                percent: Some(40.0),
+                stage: Some("download".to_string()),
+                message: Some("Fetching server".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        report_background_task_progress(
+            task_id.clone(),
+            BackgroundTaskProgress {
+                percent: Some(140.0),
+                stage: Some("extract".to_string()),
+                message: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let status = get_background_task_status(task_id.clone()).await.unwrap();
+        assert_eq!(
+            status.progress,
+            Some(BackgroundTaskProgress {
+                percent: Some(100.0),
+                stage: Some("extract".to_string()),
+                message: None,
+            })
+        );
+
+        let events: Vec<serde_json::Value> = (0..2)
+            .map(|_| {
+                let payload = event_rx
+                    .recv_timeout(Duration::from_secs(1))
+                    .expect("expected progress event");
+                serde_json::from_str(&payload).unwrap()
+            })
+            .collect();
+        assert_eq!(events[0]["percent"], 40.0);
+        assert_eq!(events[0]["stage"], "download");
+        assert_eq!(events[0]["message"], "Fetching server");
+        assert_eq!(events[1]["percent"], 100.0);
+        assert_eq!(events[1]["stage"], "extract");
+
+        assert!(kill_background_task(task_id).await.unwrap());
+    }
+
+    // =========================================================================
+    // Tests for scheduled tasks
+    // =========================================================================
+
+    fn schedule_request(
+        cron: Option<&str>,
+        interval_ms: Option<u64>,
+    ) -> ScheduleBackgroundTaskRequest {
+        ScheduleBackgroundTaskRequest {
+            command: "echo tick".to_string(),
+            cwd: None,
+            max_timeout_ms: None,
+            max_buffer_bytes: None,
+            priority: None,
+            cron: cron.map(|expr| expr.to_string()),
+            timezone: None,
+            interval_ms,
+        }
+    }
+
+    #[test]
+    fn test_build_schedule_requires_exactly_one_trigger() {
+        assert!(build_schedule(&schedule_request(None, None)).is_err());
+        assert!(build_schedule(&schedule_request(Some("* * * * *"), Some(1000))).is_err());
+        assert!(build_schedule(&schedule_request(None, Some(0))).is_err());
+        assert_eq!(
+            build_schedule(&schedule_request(None, Some(1000))).unwrap(),
+            ScheduledTaskSchedule::Every { every_ms: 1000 }
+        );
+        assert!(matches!(
+            build_schedule(&schedule_request(Some("*/5 * * * *"), None)).unwrap(),
+            ScheduledTaskSchedule::Cron { .. }
+        ));
+    }
+
+    #[test]
+    fn test_next_run_after_cron_is_in_future() {
+        let schedule = ScheduledTaskSchedule::Cron {
+            expr: "*/5 * * * *".to_string(),
+            tz: None,
+        };
+        let now = current_time_ms();
+        let next = next_run_after(&schedule, now, "bg_test").unwrap();
+        assert!(next > now);
+        assert!(next <= now + 5 * 60 * 1000);
+    }
+
+    #[test]
+    fn test_registry_owns_schedule_run_dirs() {
+        let (stop_tx, _) = watch::channel(false);
+        let mut registry = BackgroundTaskRegistry::new();
+        registry.insert_schedule(
+            "bg_sched".to_string(),
+            Arc::new(Mutex::new(ScheduledTaskHandle {
+                task_id: "bg_sched".to_string(),
+                command: "echo".to_string(),
+                schedule: ScheduledTaskSchedule::Every { every_ms: 1000 },
+                created_at: 0,
+                max_timeout_ms: None,
+                next_run_time: Some(1000),
+                run_count: 0,
+                last_run_task_id: None,
+                internal: false,
+                cancelled: false,
+                stop_tx,
+            })),
+        );
+
+        assert!(registry.owns_task_dir("bg_sched"));
+        assert!(registry.owns_task_dir("bg_sched-3"));
+        assert!(!registry.owns_task_dir("bg_schedx"));
+        assert!(!registry.owns_task_dir("bg_other"));
+    }
+
+    #[tokio::test]
+    async fn test_interval_schedule_fires_multiple_times() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let fired = Arc::new(AtomicUsize::new(0));
+        let counter = fired.clone();
+        let task_id = generate_task_id();
+        register_schedule(
+            task_id.clone(),
+            "echo tick".to_string(),
+            None,
+            ScheduledTaskSchedule::Every { every_ms: 30 },
+            false,
+            move |_run_task_id| {
+                let counter = counter.clone();
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                }
+            },
+        )
+        .await
+        .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(fired.load(Ordering::SeqCst) >= 2);
+
+        let listed = list_background_tasks().await.unwrap();
+        let info = listed
+            .tasks
+            .iter()
+            .find(|task| task.task_id == task_id)
+            .expect("scheduled task should be listed");
+        assert_eq!(info.status, BackgroundTaskStatus::Scheduled);
+        assert!(info.next_run_time.is_some());
+
+        assert!(kill_background_task(task_id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_one_shot_job_fires_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let fired = Arc::new(AtomicUsize::new(0));
+        let counter = fired.clone();
+        let run_at = current_time_ms() + 30;
+        schedule_internal_job_at("internal:test_once", run_at, move || async move {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(fired.load(Ordering::SeqCst), 0);
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(fired.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_internal_jobs_are_not_listed() {
+        let task_id = schedule_internal_job("internal:test_hidden", 60_000, || async { Ok(()) })
+            .await
+            .unwrap();
+
+        let listed = list_background_tasks().await.unwrap();
+        assert!(listed.tasks.iter().all(|task| task.task_id != task_id));
+
+        assert!(kill_background_task(task_id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_kill_cancels_future_scheduled_runs() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let fired = Arc::new(AtomicUsize::new(0));
+        let counter = fired.clone();
+        let task_id = generate_task_id();
+        register_schedule(
+            task_id.clone(),
+            "echo tick".to_string(),
+            None,
+            ScheduledTaskSchedule::Every { every_ms: 30 },
+            false,
+            move |_run_task_id| {
+                let counter = counter.clone();
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                }
+            },
+        )
+        .await
+        .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert!(kill_background_task(task_id.clone()).await.unwrap());
+        let fired_at_kill = fired.load(Ordering::SeqCst);
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(fired.load(Ordering::SeqCst), fired_at_kill);
+
+        let listed = list_background_tasks().await.unwrap();
+        assert!(listed.tasks.iter().all(|task| task.task_id != task_id));
+        // A second kill finds nothing to cancel
+        assert!(!kill_background_task(task_id).await.unwrap());
+    }
 }
@@ -4,7 +4,7 @@
 pub const DEFAULT_MAX_DEPTH: usize = 20;
 
 /// Directories to exclude from file operations
-pub const EXCLUDED_DIRS: &[&str] = &[
+pub const DEFAULT_EXCLUDE_DIRS: &[&str] = &[
     "node_modules",
     ".git",
     "target",
@@ -38,6 +38,14 @@ pub const EXCLUDED_DIRS: &[&str] = &[
     "bower_components",
 ];
 
+/// File globs skipped by search: minified bundles and source maps match
+/// nearly every query but are never what the user is looking for
+pub const DEFAULT_IGNORE_GLOBS: &[&str] = &["*.min.js", "*.min.css", "*.bundle.js", "*.map"];
+
+/// Version control metadata directories, excluded even when the default
+/// exclusions are turned off
+pub const VCS_DIRS: &[&str] = &[".git", ".svn", ".hg"];
+
 /// Common code file extensions
 pub const CODE_EXTENSIONS: &[&str] = &[
     // Programming languages
@@ -160,6 +168,36 @@ pub const CODE_FILENAMES: &[&str] = &[
     ".yarnrc",
 ];
 
+/// Language names and their file extensions, used by search filter pickers
+pub const LANGUAGE_EXTENSIONS: &[(&str, &[&str])] = &[
+    ("rust", &["rs"]),
+    ("typescript", &["ts", "tsx"]),
+    ("javascript", &["js", "jsx", "mjs", "cjs"]),
+    ("python", &["py"]),
+    ("go", &["go"]),
+    ("java", &["java"]),
+    ("kotlin", &["kt"]),
+    ("swift", &["swift"]),
+    ("c", &["c", "h"]),
+    ("cpp", &["cpp", "cc", "cxx", "hpp"]),
+    ("csharp", &["cs"]),
+    ("ruby", &["rb"]),
+    ("php", &["php"]),
+    ("scala", &["scala"]),
+    ("dart", &["dart"]),
+    ("lua", &["lua"]),
+    ("shell", &["sh", "bash", "zsh"]),
+    ("html", &["html", "htm"]),
+    ("css", &["css", "scss", "sass", "less"]),
+    ("vue", &["vue"]),
+    ("svelte", &["svelte"]),
+    ("sql", &["sql"]),
+    ("markdown", &["md", "mdx"]),
+    ("json", &["json"]),
+    ("yaml", &["yaml", "yml"]),
+    ("toml", &["toml"]),
+];
+
 /// Binary file extensions to exclude
 pub const BINARY_EXTENSIONS: &[&str] = &[
     // Executables and libraries
@@ -177,7 +215,20 @@ pub const BINARY_EXTENSIONS: &[&str] = &[
 
 /// Check if a directory should be excluded
 pub fn should_exclude_dir(dir_name: &str) -> bool {
-    EXCLUDED_DIRS.contains(&dir_name)
+    DEFAULT_EXCLUDE_DIRS.contains(&dir_name)
+}
+
+/// Check if a directory holds version control metadata
+pub fn is_vcs_dir(dir_name: &str) -> bool {
+    VCS_DIRS.contains(&dir_name)
+}
+
+/// Look up the file extensions for a language name (case-insensitive)
+pub fn extensions_for_language(language: &str) -> Option<&'static [&'static str]> {
+    LANGUAGE_EXTENSIONS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(language))
+        .map(|(_, extensions)| *extensions)
 }
 
 /// Check if a file extension indicates a code file
@@ -218,6 +269,14 @@ mod tests {
         assert!(!should_exclude_dir("utils"));
     }
 
+    #[test]
+    fn test_is_vcs_dir() {
+        assert!(is_vcs_dir(".git"));
+        assert!(is_vcs_dir(".hg"));
+        assert!(!is_vcs_dir("node_modules"));
+        assert!(!is_vcs_dir(".github"));
+    }
+
     #[test]
     fn test_is_code_extension_programming_languages() {
         assert!(is_code_extension("rs"));
@@ -341,12 +400,32 @@ mod tests {
 
     #[test]
     fn test_excluded_dirs_contains_expected() {
-        assert!(EXCLUDED_DIRS.contains(&"node_modules"));
-        assert!(EXCLUDED_DIRS.contains(&".git"));
-        assert!(EXCLUDED_DIRS.contains(&"target"));
-        assert!(EXCLUDED_DIRS.contains(&"target-dev"));
-        assert!(EXCLUDED_DIRS.contains(&"__pycache__"));
-        assert!(EXCLUDED_DIRS.len() > 20); // Should have many exclusions
+        assert!(DEFAULT_EXCLUDE_DIRS.contains(&"node_modules"));
+        assert!(DEFAULT_EXCLUDE_DIRS.contains(&".git"));
+        assert!(DEFAULT_EXCLUDE_DIRS.contains(&"target"));
+        assert!(DEFAULT_EXCLUDE_DIRS.contains(&"target-dev"));
+        assert!(DEFAULT_EXCLUDE_DIRS.contains(&"__pycache__"));
+        assert!(DEFAULT_EXCLUDE_DIRS.len() > 20); // Should have many exclusions
+    }
+
+    #[test]
+    fn test_search_defaults_non_empty() {
+        assert!(!DEFAULT_EXCLUDE_DIRS.is_empty());
+        assert!(!DEFAULT_IGNORE_GLOBS.is_empty());
+        assert!(!LANGUAGE_EXTENSIONS.is_empty());
+        assert!(LANGUAGE_EXTENSIONS
+            .iter()
+            .all(|(name, extensions)| !name.is_empty() && !extensions.is_empty()));
+    }
+
+    #[test]
+    fn test_extensions_for_language() {
+        assert_eq!(extensions_for_language("rust"), Some(&["rs"][..]));
+        assert_eq!(
+            extensions_for_language("TypeScript"),
+            Some(&["ts", "tsx"][..])
+        );
+        assert_eq!(extensions_for_language("cobol"), None);
     }
 
     #[test]
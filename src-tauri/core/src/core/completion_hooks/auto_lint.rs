@@ -0,0 +1,293 @@
+//! Auto Lint Hook
+//!
+//! Lints the files changed by the agent on completion and feeds errors back
+//! as a new iteration instead of letting the task finish with broken code.
+
+use super::{changed_files, truncate_for_context, CompletionHook, HookContext, HookResult};
+use crate::platform::{PlatformContext, ShellPlatform};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Max characters of lint output fed back to the agent
+const MAX_LINT_OUTPUT_CHARS: usize = 8_000;
+
+/// Outcome of linting a set of files
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintReport {
+    pub passed: bool,
+    pub diagnostics: String,
+}
+
+/// Lints a set of files
+#[async_trait::async_trait]
+pub trait Linter: Send + Sync {
+    async fn lint(&self, files: &[String]) -> Result<LintReport, String>;
+}
+
+/// Runs a project lint command with the changed files appended, e.g. `npx eslint`.
+/// A non-zero exit code means the files have lint errors.
+pub struct CommandLinter {
+    command: String,
+    shell: ShellPlatform,
+    ctx: PlatformContext,
+}
+
+impl CommandLinter {
+    pub fn new(command: impl Into<String>, ctx: PlatformContext) -> Self {
+        Self {
+            command: command.into(),
+            shell: ShellPlatform::new(),
+            ctx,
+        }
+    }
+}
+
+/// Quote a path for `sh -c`
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[async_trait::async_trait]
+impl Linter for CommandLinter {
+    async fn lint(&self, files: &[String]) -> Result<LintReport, String> {
+        let args: Vec<String> = files.iter().map(|f| shell_quote(f)).collect();
+        let command = format!("{} {}", self.command, args.join(" "));
+
+        let result = self.shell.execute(&command, None, &self.ctx).await;
+        let output = match (result.data, result.error) {
+            (Some(output), _) => output,
+            (None, error) => return Err(error.unwrap_or_else(|| "Lint command failed".into())),
+        };
+        if output.timed_out {
+            return Err("Lint command timed out".to_string());
+        }
+
+        Ok(LintReport {
+            passed: output.exit_code == 0,
+            diagnostics: format!("{}{}", output.stdout, output.stderr)
+                .trim()
+                .to_string(),
+        })
+    }
+}
+
+/// Auto lint hook
+pub struct AutoLintHook {
+    linter: Box<dyn Linter>,
+    max_iterations: u32,
+    attempts: AtomicU32,
+}
+
+impl AutoLintHook {
+    pub fn new(linter: Box<dyn Linter>) -> Self {
+        Self::with_max_iterations(linter, 3)
+    }
+
+    pub fn with_max_iterations(linter: Box<dyn Linter>, max_iterations: u32) -> Self {
+        Self {
+            linter,
+            max_iterations,
+            attempts: AtomicU32::new(0),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CompletionHook for AutoLintHook {
+    fn name(&self) -> &str {
+        "auto_lint"
+    }
+
+    async fn should_run(&self, ctx: &HookContext) -> bool {
+        let enabled = ctx
+            .settings
+            .extra
+            .get("autoLintEnabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        enabled && !changed_files(&ctx.messages).is_empty()
+    }
+
+    async fn execute(&self, ctx: &HookContext) -> Result<HookResult, String> {
+        let files = changed_files(&ctx.messages);
+        let report = self.linter.lint(&files).await?;
+
+        if report.passed {
+            self.attempts.store(0, Ordering::SeqCst);
+            return Ok(HookResult::Continue {
+                message: ctx.full_text.clone(),
+            });
+        }
+
+        let attempts = self.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+        if attempts > self.max_iterations {
+            log::warn!(
+                "Task {} still has lint errors after {} fix attempts",
+                ctx.task_id,
+                self.max_iterations
+            );
+            return Ok(HookResult::Continue {
+                message: format!(
+                    "{}\n\n[Auto Lint] Lint errors remain after {} fix attempts.",
+                    ctx.full_text, self.max_iterations
+                ),
+            });
+        }
+
+        Ok(HookResult::Iterate {
+            context: format!(
+                "Lint errors were found in the files you changed ({}). Fix them before finishing \
+                 (attempt {}/{}):\n\n{}",
+                files.join(", "),
+                attempts,
+                self.max_iterations,
+                truncate_for_context(&report.diagnostics, MAX_LINT_OUTPUT_CHARS)
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::models::{Message, MessageContent, MessageRole, ToolCall};
+    use std::sync::Mutex;
+
+    fn edit_message(path: &str) -> Message {
+        Message {
+            id: format!("msg-{}", path),
+            session_id: "session-1".to_string(),
+            role: MessageRole::Assistant,
+            content: MessageContent::ToolCalls {
+                calls: vec![ToolCall {
+                    id: format!("call-{}", path),
+                    name: "writeFile".to_string(),
+                    input: serde_json::json!({ "file_path": path, "content": "" }),
+                }],
+            },
+            created_at: 0,
+            tool_call_id: None,
+            parent_id: None,
+        }
+    }
+
+    fn lint_context(paths: &[&str]) -> HookContext {
+        let mut ctx = HookContext::for_test("All done");
+        ctx.messages = paths.iter().map(|p| edit_message(p)).collect();
+        ctx.settings
+            .extra
+            .insert("autoLintEnabled".to_string(), serde_json::json!(true));
+        ctx
+    }
+
+    /// Fails for files listed in `bad`
+    struct FakeLinter {
+        bad: Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Linter for FakeLinter {
+        async fn lint(&self, files: &[String]) -> Result<LintReport, String> {
+            let bad = self.bad.lock().unwrap();
+            let errors: Vec<String> = files
+                .iter()
+                .filter(|f| bad.contains(f))
+                .map(|f| format!("{}:1:1 error no-unused-vars", f))
+                .collect();
+            Ok(LintReport {
+                passed: errors.is_empty(),
+                diagnostics: errors.join("\n"),
+            })
+        }
+    }
+
+    fn fake_linter(bad: &[&str]) -> Box<FakeLinter> {
+        Box::new(FakeLinter {
+            bad: Mutex::new(bad.iter().map(|s| s.to_string()).collect()),
+        })
+    }
+
+    #[tokio::test]
+    async fn lint_errors_trigger_iterate() {
+        let hook = AutoLintHook::new(fake_linter(&["src/bad.ts"]));
+        let ctx = lint_context(&["src/ok.ts", "src/bad.ts"]);
+
+        assert!(hook.should_run(&ctx).await);
+        match hook.execute(&ctx).await.unwrap() {
+            HookResult::Iterate { context } => {
+                assert!(context.contains("src/bad.ts:1:1 error no-unused-vars"));
+                assert!(context.contains("attempt 1/3"));
+            }
+            other => panic!("expected Iterate, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn clean_code_continues() {
+        let hook = AutoLintHook::new(fake_linter(&[]));
+        let ctx = lint_context(&["src/ok.ts"]);
+        assert!(matches!(
+            hook.execute(&ctx).await.unwrap(),
+            HookResult::Continue { message } if message == "All done"
+        ));
+    }
+
+    #[tokio::test]
+    async fn stops_iterating_after_max_attempts() {
+        let hook = AutoLintHook::with_max_iterations(fake_linter(&["a.ts"]), 2);
+        let ctx = lint_context(&["a.ts"]);
+
+        assert!(matches!(
+            hook.execute(&ctx).await.unwrap(),
+            HookResult::Iterate { .. }
+        ));
+        assert!(matches!(
+            hook.execute(&ctx).await.unwrap(),
+            HookResult::Iterate { .. }
+        ));
+        assert!(matches!(
+            hook.execute(&ctx).await.unwrap(),
+            HookResult::Continue { message } if message.contains("Lint errors remain")
+        ));
+    }
+
+    #[tokio::test]
+    async fn skipped_when_disabled_or_nothing_changed() {
+        let hook = AutoLintHook::new(fake_linter(&[]));
+        let mut ctx = lint_context(&["a.ts"]);
+        ctx.settings.extra.clear();
+        assert!(!hook.should_run(&ctx).await);
+        assert!(!hook.should_run(&lint_context(&[])).await);
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn command_linter_reports_failures() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let bad = dir.path().join("bad file.js");
+        let clean = dir.path().join("clean.js");
+        std::fs::write(&bad, "console.log('debug');\n").unwrap();
+        std::fs::write(&clean, "export const x = 1;\n").unwrap();
+
+        let ctx = PlatformContext {
+            workspace_root: dir.path().to_path_buf(),
+            worktree_path: None,
+            max_file_size: 1024 * 1024,
+            shell_timeout_secs: 30,
+        };
+        // "Lint" rule: no console.log
+        let linter = CommandLinter::new("! grep -Hn console.log", ctx);
+
+        let report = linter
+            .lint(&[bad.to_string_lossy().to_string()])
+            .await
+            .unwrap();
+        assert!(!report.passed);
+        assert!(report.diagnostics.contains("bad file.js:1:console.log"));
+
+        let report = linter
+            .lint(&[clean.to_string_lossy().to_string()])
+            .await
+            .unwrap();
+        assert!(report.passed);
+    }
+}
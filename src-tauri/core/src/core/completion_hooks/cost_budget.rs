@@ -0,0 +1,168 @@
+//! Cost Budget Hook
+//!
+//! Stops the agent loop once the task's cumulative API spend exceeds a budget.
+
+use super::{CompletionHook, HookContext, HookResult};
+use crate::llm::types::ModelConfig;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Stop reason prefix when the budget is exceeded
+pub const COST_BUDGET_EXCEEDED: &str = "cost_budget_exceeded";
+
+/// Spend snapshot for UI display
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostBudget {
+    pub spent_usd: f64,
+    pub budget_usd: f64,
+}
+
+impl CostBudget {
+    pub fn is_exceeded(&self) -> bool {
+        self.spent_usd > self.budget_usd
+    }
+}
+
+/// Cost budget hook
+pub struct CostBudgetHook {
+    budget_usd: f64,
+    model_configs: HashMap<String, ModelConfig>,
+}
+
+impl CostBudgetHook {
+    pub fn new(budget_usd: f64, model_configs: HashMap<String, ModelConfig>) -> Self {
+        Self {
+            budget_usd,
+            model_configs,
+        }
+    }
+
+    /// Current spend against the budget
+    pub fn budget(&self, ctx: &HookContext) -> CostBudget {
+        CostBudget {
+            spent_usd: ctx.total_cost(&self.model_configs),
+            budget_usd: self.budget_usd,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CompletionHook for CostBudgetHook {
+    fn name(&self) -> &str {
+        "cost_budget"
+    }
+
+    async fn should_run(&self, _ctx: &HookContext) -> bool {
+        self.budget_usd.is_finite() && self.budget_usd > 0.0
+    }
+
+    async fn execute(&self, ctx: &HookContext) -> Result<HookResult, String> {
+        let budget = self.budget(ctx);
+        if budget.is_exceeded() {
+            log::info!(
+                "Task {} exceeded cost budget: ${:.4} > ${:.4}",
+                ctx.task_id,
+                budget.spent_usd,
+                budget.budget_usd
+            );
+            return Ok(HookResult::Stop {
+                reason: format!(
+                    "{}: spent ${:.4} of ${:.4}",
+                    COST_BUDGET_EXCEEDED, budget.spent_usd, budget.budget_usd
+                ),
+            });
+        }
+
+        Ok(HookResult::Continue {
+            message: ctx.full_text.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::completion_hooks::UsageRecord;
+    use crate::llm::ai_services::types::TokenUsage;
+    use crate::llm::types::ModelPricing;
+
+    /// $1 per 1M input tokens, $2 per 1M output tokens
+    fn model_configs() -> HashMap<String, ModelConfig> {
+        let config = ModelConfig {
+            name: "Test Model".to_string(),
+            image_input: false,
+            image_output: false,
+            audio_input: false,
+            video_input: false,
+            interleaved: false,
+            providers: vec!["test".to_string()],
+            provider_mappings: None,
+            pricing: Some(ModelPricing {
+                input: "0.000001".to_string(),
+                output: "0.000002".to_string(),
+                cached_input: None,
+                cache_creation: None,
+            }),
+            context_length: None,
+            capabilities: None,
+            context_window: None,
+        };
+        HashMap::from([("test-model".to_string(), config)])
+    }
+
+    fn record(input_tokens: u32, output_tokens: u32) -> UsageRecord {
+        UsageRecord {
+            model_id: "test-model@test".to_string(),
+            usage: TokenUsage {
+                input_tokens,
+                output_tokens,
+                cached_input_tokens: None,
+                cache_creation_input_tokens: None,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn stops_once_spend_crosses_budget() {
+        let hook = CostBudgetHook::new(1.0, model_configs());
+        let mut ctx = HookContext::for_test("text");
+
+        // $0.60
+        ctx.usage.push(record(200_000, 200_000));
+        assert!(matches!(
+            hook.execute(&ctx).await.unwrap(),
+            HookResult::Continue { .. }
+        ));
+
+        // +$0.60 = $1.20
+        ctx.usage.push(record(200_000, 200_000));
+        let budget = hook.budget(&ctx);
+        assert!((budget.spent_usd - 1.2).abs() < 1e-9);
+        assert!(budget.is_exceeded());
+        assert!(matches!(
+            hook.execute(&ctx).await.unwrap(),
+            HookResult::Stop { reason } if reason.starts_with(COST_BUDGET_EXCEEDED)
+        ));
+    }
+
+    #[tokio::test]
+    async fn continues_under_budget() {
+        let hook = CostBudgetHook::new(5.0, model_configs());
+        let mut ctx = HookContext::for_test("text");
+        ctx.usage = vec![record(100_000, 50_000); 10];
+
+        assert!(hook.should_run(&ctx).await);
+        assert!(matches!(
+            hook.execute(&ctx).await.unwrap(),
+            HookResult::Continue { .. }
+        ));
+        assert!((hook.budget(&ctx).spent_usd - 2.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn disabled_without_positive_budget() {
+        let hook = CostBudgetHook::new(0.0, model_configs());
+        assert!(!hook.should_run(&HookContext::for_test("text")).await);
+    }
+}
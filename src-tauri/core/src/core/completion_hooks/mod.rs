@@ -3,16 +3,26 @@
 //! Hooks that run after a successful agent loop completion (no tool calls).
 //! Ported from TypeScript completion-hooks.ts and ralph-loop-service.ts
 
+pub mod auto_lint;
 pub mod auto_review;
+pub mod cost_budget;
 pub mod ralph_loop;
 pub mod stop_hook;
+pub mod test_gate;
 
+pub use auto_lint::{AutoLintHook, CommandLinter, LintReport, Linter};
 pub use auto_review::AutoReviewHook;
+pub use cost_budget::{CostBudget, CostBudgetHook, COST_BUDGET_EXCEEDED};
 pub use ralph_loop::RalphLoopHook;
 pub use stop_hook::StopHook;
+pub use test_gate::TestGateHook;
 
 use crate::core::types::*;
+use crate::llm::ai_services::pricing_service::PricingService;
+use crate::llm::ai_services::types::TokenUsage;
+use crate::llm::types::ModelConfig;
 use crate::storage::models::*;
+use std::collections::HashMap;
 
 /// Hook context passed to completion hooks
 #[derive(Debug, Clone)]
@@ -22,6 +32,30 @@ pub struct HookContext {
     pub messages: Vec<Message>,
     pub full_text: String,
     pub settings: TaskSettings,
+    /// Token usage of every LLM call made for this task so far
+    pub usage: Vec<UsageRecord>,
+}
+
+/// Token usage of a single LLM call
+#[derive(Debug, Clone)]
+pub struct UsageRecord {
+    pub model_id: String,
+    pub usage: TokenUsage,
+}
+
+impl HookContext {
+    /// Cumulative cost in USD of the task's LLM calls so far
+    pub fn total_cost(&self, model_configs: &HashMap<String, ModelConfig>) -> f64 {
+        let pricing = PricingService::new();
+        self.usage
+            .iter()
+            .map(|record| {
+                pricing
+                    .calculate_cost(&record.model_id, &record.usage, model_configs)
+                    .unwrap_or(0.0)
+            })
+            .sum()
+    }
 }
 
 /// Result of a completion hook
@@ -35,6 +69,47 @@ pub enum HookResult {
     Iterate { context: String },
 }
 
+/// Tools whose `file_path` input modifies a file
+const FILE_EDIT_TOOLS: &[&str] = &["writeFile", "editFile"];
+
+/// Files written or edited by the agent in this task, in first-touched order
+pub fn changed_files(messages: &[Message]) -> Vec<String> {
+    let mut files: Vec<String> = Vec::new();
+    for message in messages {
+        let MessageContent::ToolCalls { calls } = &message.content else {
+            continue;
+        };
+        for call in calls {
+            if !FILE_EDIT_TOOLS.contains(&call.name.as_str()) {
+                continue;
+            }
+            if let Some(path) = call.input.get("file_path").and_then(|v| v.as_str()) {
+                if !files.iter().any(|f| f == path) {
+                    files.push(path.to_string());
+                }
+            }
+        }
+    }
+    files
+}
+
+/// Keep the head and tail of long tool output so it fits in the agent context
+pub fn truncate_for_context(text: &str, max_chars: usize) -> String {
+    let total = text.chars().count();
+    if total <= max_chars {
+        return text.to_string();
+    }
+    let half = max_chars / 2;
+    let head: String = text.chars().take(half).collect();
+    let tail: String = text.chars().skip(total - half).collect();
+    format!(
+        "{}\n... [{} characters truncated] ...\n{}",
+        head,
+        total - half * 2,
+        tail
+    )
+}
+
 /// Completion hook trait
 #[async_trait::async_trait]
 pub trait CompletionHook: Send + Sync {
@@ -67,19 +142,11 @@ impl CompletionHookPipeline {
     pub async fn run(&self, ctx: &HookContext) -> Result<HookResult, String> {
         for hook in &self.hooks {
             if hook.should_run(ctx).await {
-                match hook.execute(ctx).await {
-                    Ok(HookResult::Stop { .. }) => {
-                        // Stop early if a hook requests it
-                        return hook.execute(ctx).await;
-                    }
-                    Ok(result) => {
-                        // Continue with other hooks
-                        if let HookResult::Continue { .. } = result {
-                            continue;
-                        }
-                        return Ok(result);
-                    }
-                    Err(e) => return Err(e),
+                // Execute exactly once: hooks may have side effects (API calls, commands)
+                match hook.execute(ctx).await? {
+                    HookResult::Continue { .. } => continue,
+                    // Stop or Iterate short-circuits the pipeline
+                    result => return Ok(result),
                 }
             }
         }
@@ -95,3 +162,102 @@ impl Default for CompletionHookPipeline {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct CountingHook {
+        calls: Arc<AtomicUsize>,
+        result: HookResult,
+    }
+
+    #[async_trait::async_trait]
+    impl CompletionHook for CountingHook {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        async fn should_run(&self, _ctx: &HookContext) -> bool {
+            true
+        }
+
+        async fn execute(&self, _ctx: &HookContext) -> Result<HookResult, String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(self.result.clone())
+        }
+    }
+
+    fn counting_hook(result: HookResult) -> (Box<dyn CompletionHook>, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let hook = CountingHook {
+            calls: calls.clone(),
+            result,
+        };
+        (Box::new(hook), calls)
+    }
+
+    #[tokio::test]
+    async fn stop_hook_executes_exactly_once() {
+        let (stop, stop_calls) = counting_hook(HookResult::Stop {
+            reason: "done".to_string(),
+        });
+        let (after, after_calls) = counting_hook(HookResult::Continue {
+            message: String::new(),
+        });
+        let mut pipeline = CompletionHookPipeline::new();
+        pipeline.add_hook(stop);
+        pipeline.add_hook(after);
+
+        let result = pipeline.run(&HookContext::for_test("text")).await.unwrap();
+
+        assert!(matches!(result, HookResult::Stop { reason } if reason == "done"));
+        assert_eq!(stop_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(after_calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn continue_runs_every_hook_once() {
+        let (first, first_calls) = counting_hook(HookResult::Continue {
+            message: "first".to_string(),
+        });
+        let (second, second_calls) = counting_hook(HookResult::Continue {
+            message: "second".to_string(),
+        });
+        let mut pipeline = CompletionHookPipeline::new();
+        pipeline.add_hook(first);
+        pipeline.add_hook(second);
+
+        let result = pipeline.run(&HookContext::for_test("text")).await.unwrap();
+
+        assert!(matches!(result, HookResult::Continue { message } if message == "text"));
+        assert_eq!(first_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(second_calls.load(Ordering::SeqCst), 1);
+    }
+    #[test]
+    fn truncate_for_context_keeps_head_and_tail() {
+        assert_eq!(truncate_for_context("short", 10), "short");
+        let text = format!("{}{}", "a".repeat(50), "b".repeat(50));
+        let truncated = truncate_for_context(&text, 20);
+        assert!(truncated.starts_with(&"a".repeat(10)));
+        assert!(truncated.ends_with(&"b".repeat(10)));
+        assert!(truncated.contains("[80 characters truncated]"));
+    }
+}
+
+#[cfg(test)]
+impl HookContext {
+    /// Minimal context for hook tests
+    pub(crate) fn for_test(full_text: &str) -> Self {
+        Self {
+            task_id: "task-1".to_string(),
+            session_id: "session-1".to_string(),
+            messages: vec![],
+            full_text: full_text.to_string(),
+            settings: TaskSettings::default(),
+            usage: vec![],
+        }
+    }
+}
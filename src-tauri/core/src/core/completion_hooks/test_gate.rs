@@ -0,0 +1,206 @@
+//! Test Gate Hook
+//!
+//! Runs the project test command on completion and feeds failures back to the
+//! agent so regressions are fixed before the task finishes.
+
+use super::{changed_files, truncate_for_context, CompletionHook, HookContext, HookResult};
+use crate::platform::{PlatformContext, ShellPlatform};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Max characters of test output fed back to the agent
+const MAX_TEST_OUTPUT_CHARS: usize = 12_000;
+
+/// Test gate hook
+pub struct TestGateHook {
+    command: String,
+    shell: ShellPlatform,
+    ctx: PlatformContext,
+    max_iterations: u32,
+    attempts: AtomicU32,
+}
+
+impl TestGateHook {
+    /// `command` runs in the workspace root, e.g. `cargo test` or `npm test`.
+    /// `timeout_secs` bounds a single test run.
+    pub fn new(command: impl Into<String>, ctx: PlatformContext, timeout_secs: u64) -> Self {
+        Self {
+            command: command.into(),
+            shell: ShellPlatform::new(),
+            ctx: PlatformContext {
+                shell_timeout_secs: timeout_secs,
+                ..ctx
+            },
+            max_iterations: 3,
+            attempts: AtomicU32::new(0),
+        }
+    }
+
+    pub fn with_max_iterations(mut self, max_iterations: u32) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl CompletionHook for TestGateHook {
+    fn name(&self) -> &str {
+        "test_gate"
+    }
+
+    async fn should_run(&self, ctx: &HookContext) -> bool {
+        let enabled = ctx
+            .settings
+            .extra
+            .get("testGateEnabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        enabled && !changed_files(&ctx.messages).is_empty()
+    }
+
+    async fn execute(&self, ctx: &HookContext) -> Result<HookResult, String> {
+        let result = self.shell.execute(&self.command, None, &self.ctx).await;
+        let output = match (result.data, result.error) {
+            (Some(output), _) => output,
+            (None, error) => return Err(error.unwrap_or_else(|| "Test command failed".into())),
+        };
+
+        if output.exit_code == 0 && !output.timed_out {
+            self.attempts.store(0, Ordering::SeqCst);
+            return Ok(HookResult::Continue {
+                message: ctx.full_text.clone(),
+            });
+        }
+
+        let attempts = self.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+        if attempts > self.max_iterations {
+            log::warn!(
+                "Task {} still fails `{}` after {} fix attempts",
+                ctx.task_id,
+                self.command,
+                self.max_iterations
+            );
+            return Ok(HookResult::Continue {
+                message: format!(
+                    "{}\n\n[Test Gate] `{}` still fails after {} fix attempts.",
+                    ctx.full_text, self.command, self.max_iterations
+                ),
+            });
+        }
+
+        let summary = if output.timed_out {
+            format!(
+                "`{}` timed out after {}s",
+                self.command, self.ctx.shell_timeout_secs
+            )
+        } else {
+            format!(
+                "`{}` failed with exit code {}",
+                self.command, output.exit_code
+            )
+        };
+        let combined = format!("{}\n{}", output.stdout, output.stderr);
+
+        Ok(HookResult::Iterate {
+            context: format!(
+                "{}. Fix the failing tests before finishing (attempt {}/{}):\n\n{}",
+                summary,
+                attempts,
+                self.max_iterations,
+                truncate_for_context(combined.trim(), MAX_TEST_OUTPUT_CHARS)
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::models::{Message, MessageContent, MessageRole, ToolCall};
+    use tempfile::TempDir;
+
+    fn gate_context() -> HookContext {
+        let mut ctx = HookContext::for_test("Implemented the feature");
+        ctx.messages = vec![Message {
+            id: "msg-1".to_string(),
+            session_id: "session-1".to_string(),
+            role: MessageRole::Assistant,
+            content: MessageContent::ToolCalls {
+                calls: vec![ToolCall {
+                    id: "call-1".to_string(),
+                    name: "editFile".to_string(),
+                    input: serde_json::json!({ "file_path": "src/lib.rs", "edits": [] }),
+                }],
+            },
+            created_at: 0,
+            tool_call_id: None,
+            parent_id: None,
+        }];
+        ctx.settings
+            .extra
+            .insert("testGateEnabled".to_string(), serde_json::json!(true));
+        ctx
+    }
+
+    fn platform_context(dir: &TempDir) -> PlatformContext {
+        PlatformContext {
+            workspace_root: dir.path().to_path_buf(),
+            worktree_path: None,
+            max_file_size: 1024 * 1024,
+            shell_timeout_secs: 60,
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn iterates_on_failure_then_continues_when_tests_pass() {
+        let dir = TempDir::new().unwrap();
+        // Fails the first run, passes afterwards
+        let command = "if [ -f .fixed ]; then echo 'test result: ok'; \
+                       else touch .fixed; echo 'test parser::works ... FAILED'; exit 101; fi";
+        let hook = TestGateHook::new(command, platform_context(&dir), 30);
+        let ctx = gate_context();
+
+        assert!(hook.should_run(&ctx).await);
+        match hook.execute(&ctx).await.unwrap() {
+            HookResult::Iterate { context } => {
+                assert!(context.contains("failed with exit code 101"));
+                assert!(context.contains("parser::works ... FAILED"));
+                assert!(context.contains("attempt 1/3"));
+            }
+            other => panic!("expected Iterate, got {:?}", other),
+        }
+
+        assert!(matches!(
+            hook.execute(&ctx).await.unwrap(),
+            HookResult::Continue { message } if message == "Implemented the feature"
+        ));
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn timeout_counts_as_failure() {
+        let dir = TempDir::new().unwrap();
+        let hook = TestGateHook::new("sleep 5", platform_context(&dir), 1);
+        match hook.execute(&gate_context()).await.unwrap() {
+            HookResult::Iterate { context } => assert!(context.contains("timed out after 1s")),
+            other => panic!("expected Iterate, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn gives_up_after_max_iterations() {
+        let dir = TempDir::new().unwrap();
+        let hook = TestGateHook::new("exit 1", platform_context(&dir), 30).with_max_iterations(1);
+        let ctx = gate_context();
+
+        assert!(matches!(
+            hook.execute(&ctx).await.unwrap(),
+            HookResult::Iterate { .. }
+        ));
+        assert!(matches!(
+            hook.execute(&ctx).await.unwrap(),
+            HookResult::Continue { message } if message.contains("still fails")
+        ));
+    }
+}
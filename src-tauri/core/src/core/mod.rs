@@ -9,12 +9,14 @@ pub mod session;
 pub mod tool_definitions;
 pub mod tool_dependency_analyzer;
 pub mod tool_name_normalizer;
+pub mod tools;
 pub mod types;
 
 // Re-export main types for convenience
 pub use runtime::{CoreRuntime, SettingsValidator};
 pub use session::{SessionManager, SessionState};
 pub use tool_name_normalizer::{is_known_tool_name, normalize_tool_name};
+pub use tools::{ToolContext, ToolDispatchResult, ToolDispatcher, ToolRegistry};
 pub use types::*;
 
 /// Initialize the core runtime with storage
@@ -4,6 +4,9 @@
 //! Owns the lifecycle of all runtime tasks.
 
 use crate::core::session::SessionManager;
+use crate::core::tools::{
+    resolve_allowed_tools, ToolContext, ToolDispatchResult, ToolDispatcher, ToolRegistry,
+};
 use crate::core::types::*;
 use crate::llm::auth::api_key_manager::ApiKeyManager;
 use crate::llm::providers::provider_registry::ProviderRegistry;
@@ -32,6 +35,10 @@ pub struct CoreRuntime {
     provider_registry: ProviderRegistry,
     /// API key manager
     api_key_manager: ApiKeyManager,
+    /// Dispatcher for tool calls made by running tasks
+    tool_dispatcher: Arc<ToolDispatcher>,
+    /// Tool execution context of each active task
+    tool_contexts: Arc<RwLock<HashMap<RuntimeTaskId, ToolContext>>>,
 }
 
 /// Settings validator
@@ -83,6 +90,7 @@ impl CoreRuntime {
     ) -> Result<Self, String> {
         // Create session manager
         let session_manager = Arc::new(SessionManager::new(storage.clone()));
+        let tool_registry = Arc::new(ToolRegistry::create_default().await);
 
         Ok(Self {
             _storage: storage,
@@ -92,6 +100,8 @@ impl CoreRuntime {
             _settings_validator: SettingsValidator::new(),
             provider_registry,
             api_key_manager,
+            tool_dispatcher: Arc::new(ToolDispatcher::new(tool_registry)),
+            tool_contexts: Arc::new(RwLock::new(HashMap::new())),
         })
     }
 
@@ -149,6 +159,14 @@ impl CoreRuntime {
             action_sender: Arc::new(action_tx),
         };
 
+        let tool_context = self
+            .build_tool_context(&task_id, &session.id, &input)
+            .await?;
+        self.tool_contexts
+            .write()
+            .await
+            .insert(task_id.clone(), tool_context);
+
         // Store task handle
         {
             let mut tasks = self.tasks.write().await;
@@ -191,6 +209,102 @@ impl CoreRuntime {
         Ok(())
     }
 
+    /// Run the tool calls from one model turn through the tool dispatcher.
+    ///
+    /// Completed calls emit `ToolCallCompleted`; calls that need approval emit
+    /// `ToolCallRequested` and run later through `execute_approved_tool`.
+    pub async fn execute_tool_calls(
+        &self,
+        task_id: &str,
+        requests: Vec<ToolRequest>,
+    ) -> Result<Vec<ToolDispatchResult>, String> {
+        let context = self.tool_context(task_id).await?;
+        let auto_approve = context.settings.auto_approve_edits == Some(true);
+        let results = self
+            .tool_dispatcher
+            .dispatch_batch(requests, context, auto_approve)
+            .await?;
+
+        for result in &results {
+            let event = match result {
+                ToolDispatchResult::Completed(result) => RuntimeEvent::ToolCallCompleted {
+                    task_id: task_id.to_string(),
+                    result: result.clone(),
+                },
+                ToolDispatchResult::PendingApproval(request) => RuntimeEvent::ToolCallRequested {
+                    task_id: task_id.to_string(),
+                    request: request.clone(),
+                },
+            };
+            let _ = self.event_sender.send(event);
+        }
+
+        Ok(results)
+    }
+
+    /// Execute a tool call the user approved
+    pub async fn execute_approved_tool(
+        &self,
+        task_id: &str,
+        request: ToolRequest,
+    ) -> Result<ToolResult, String> {
+        let context = self.tool_context(task_id).await?;
+        let result = self
+            .tool_dispatcher
+            .execute_approved(request, context)
+            .await;
+        let _ = self.event_sender.send(RuntimeEvent::ToolCallCompleted {
+            task_id: task_id.to_string(),
+            result: result.clone(),
+        });
+        Ok(result)
+    }
+
+    /// Tool context for a new task, restricted to the agent's tool allowlist
+    async fn build_tool_context(
+        &self,
+        task_id: &str,
+        session_id: &str,
+        input: &TaskInput,
+    ) -> Result<ToolContext, String> {
+        let settings = match input.settings.clone() {
+            Some(settings) => settings,
+            None => {
+                self.session_manager
+                    .get_or_create_settings(session_id)
+                    .await?
+            }
+        };
+        let agent = match input.agent_id.as_deref() {
+            Some(agent_id) => self._storage.agents.get_agent(agent_id).await?,
+            None => None,
+        };
+        let allowed_tools = resolve_allowed_tools(agent.as_ref(), &settings);
+        let workspace = input.workspace.clone();
+
+        Ok(ToolContext {
+            session_id: session_id.to_string(),
+            task_id: task_id.to_string(),
+            workspace_root: workspace
+                .as_ref()
+                .map(|workspace| workspace.root_path.clone())
+                .unwrap_or_else(|| ".".to_string()),
+            worktree_path: workspace.and_then(|workspace| workspace.worktree_path),
+            settings,
+            llm_state: None,
+            allowed_tools,
+        })
+    }
+
+    async fn tool_context(&self, task_id: &str) -> Result<ToolContext, String> {
+        self.tool_contexts
+            .read()
+            .await
+            .get(task_id)
+            .cloned()
+            .ok_or_else(|| format!("Task '{}' not found", task_id))
+    }
+
     /// Get session manager
     pub fn session_manager(&self) -> Arc<SessionManager> {
         self.session_manager.clone()
@@ -273,6 +387,8 @@ impl CoreRuntime {
         error: Option<String>,
         event_sender: &EventSender,
     ) {
+        self.tool_contexts.write().await.remove(&task.id);
+
         let previous_state = match self.tasks.read().await.get(&task.id) {
             Some(handle) => *handle.state.read().await,
             None => RuntimeTaskState::Running,
@@ -352,6 +468,144 @@ mod tests {
         // Runtime created successfully
     }
 
+    fn tool_context(workspace_root: &std::path::Path) -> ToolContext {
+        ToolContext {
+            session_id: "session".to_string(),
+            task_id: "task".to_string(),
+            workspace_root: workspace_root.to_string_lossy().to_string(),
+            worktree_path: None,
+            settings: TaskSettings::default(),
+            llm_state: None,
+            allowed_tools: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_tool_calls_runs_platform_tools() {
+        let (runtime, _temp, mut rx) = create_test_runtime().await;
+        let workspace = TempDir::new().unwrap();
+        let read_path = workspace.path().join("a.txt");
+        let write_path = workspace.path().join("b.txt");
+        std::fs::write(&read_path, "hello").unwrap();
+        runtime
+            .tool_contexts
+            .write()
+            .await
+            .insert("task".to_string(), tool_context(workspace.path()));
+
+        let results = runtime
+            .execute_tool_calls(
+                "task",
+                vec![
+                    ToolRequest {
+                        tool_call_id: "read".to_string(),
+                        name: "read_file".to_string(),
+                        input: serde_json::json!({ "file_path": read_path }),
+                        provider_metadata: None,
+                    },
+                    ToolRequest {
+                        tool_call_id: "write".to_string(),
+                        name: "writeFile".to_string(),
+                        input: serde_json::json!({ "file_path": write_path, "content": "b" }),
+                        provider_metadata: None,
+                    },
+                ],
+            )
+            .await
+            .unwrap();
+
+        match &results[0] {
+            ToolDispatchResult::Completed(result) => {
+                assert!(result.success, "{:?}", result.error);
+                assert_eq!(result.output["content"], "hello");
+            }
+            other => panic!("expected completed read, got {:?}", other),
+        }
+        let pending = match &results[1] {
+            ToolDispatchResult::PendingApproval(request) => request.clone(),
+            other => panic!("expected pending write, got {:?}", other),
+        };
+        assert!(matches!(
+            rx.recv().await,
+            Some(RuntimeEvent::ToolCallCompleted { .. })
+        ));
+        assert!(matches!(
+            rx.recv().await,
+            Some(RuntimeEvent::ToolCallRequested { .. })
+        ));
+
+        let result = runtime
+            .execute_approved_tool("task", pending)
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(std::fs::read_to_string(&write_path).unwrap(), "b");
+    }
+
+    #[tokio::test]
+    async fn test_tool_context_uses_agent_allowlist() {
+        let (runtime, _temp, _rx) = create_test_runtime().await;
+        runtime
+            ._storage
+            .agents
+            .create_agent(&crate::storage::Agent {
+                id: "reviewer".to_string(),
+                name: "Reviewer".to_string(),
+                model: "model".to_string(),
+                system_prompt: None,
+                tools: vec!["read_file".to_string()],
+                created_at: 0,
+                updated_at: 0,
+            })
+            .await
+            .unwrap();
+        let input = TaskInput {
+            session_id: "session".to_string(),
+            agent_id: Some("reviewer".to_string()),
+            project_id: None,
+            initial_message: "review".to_string(),
+            settings: Some(TaskSettings::default()),
+            workspace: None,
+        };
+
+        let context = runtime
+            .build_tool_context("task", "session", &input)
+            .await
+            .unwrap();
+        assert_eq!(context.allowed_tools, Some(vec!["readFile".to_string()]));
+
+        runtime
+            .tool_contexts
+            .write()
+            .await
+            .insert("task".to_string(), context);
+        let results = runtime
+            .execute_tool_calls(
+                "task",
+                vec![ToolRequest {
+                    tool_call_id: "write".to_string(),
+                    name: "writeFile".to_string(),
+                    input: serde_json::json!({ "file_path": "x.txt", "content": "x" }),
+                    provider_metadata: None,
+                }],
+            )
+            .await
+            .unwrap();
+        match &results[0] {
+            ToolDispatchResult::Completed(result) => {
+                assert_eq!(result.output["reason"], "tool_not_allowed");
+            }
+            other => panic!("expected rejection, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_tool_calls_requires_active_task() {
+        let (runtime, _temp, _rx) = create_test_runtime().await;
+        let result = runtime.execute_tool_calls("missing", vec![]).await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_settings_validation() {
         let validator = SettingsValidator::new();
@@ -365,6 +619,10 @@ mod tests {
             auto_approve_edits: Some(true),
             auto_approve_plan: Some(true),
             auto_code_review: None,
+            max_iterations: None,
+            fallback_models: None,
+            oauth_account: None,
+            disable_global_system_prompt: None,
             extra: HashMap::new(),
         };
         let result = validator.validate(&risky_settings);
@@ -251,6 +251,81 @@ impl SessionManager {
             .await
     }
 
+    /// Fork a session into a new one holding messages up to and including
+    /// `from_message_id`. The fork records its parent in `metadata.forkedFrom`
+    /// and starts with a fresh event stream.
+    pub async fn fork_session(
+        &self,
+        session_id: &str,
+        from_message_id: &str,
+    ) -> Result<Session, String> {
+        let parent = self
+            .get_session(session_id)
+            .await?
+            .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+
+        let messages = self.get_messages(session_id, None, None).await?;
+        let cut = messages
+            .iter()
+            .position(|message| message.id == from_message_id)
+            .ok_or_else(|| {
+                format!(
+                    "Message '{}' not found in session '{}'",
+                    from_message_id, session_id
+                )
+            })?;
+
+        let settings = self.storage.settings.get_task_settings(session_id).await?;
+        let title = parent
+            .title
+            .as_deref()
+            .map(|title| format!("{} (fork)", title));
+        let mut fork = self
+            .create_session(parent.project_id.clone(), title, settings)
+            .await?;
+
+        let mut metadata = match parent.metadata {
+            Some(serde_json::Value::Object(map)) => map,
+            _ => serde_json::Map::new(),
+        };
+        metadata.insert(
+            "forkedFrom".to_string(),
+            serde_json::json!({
+                "sessionId": session_id,
+                "messageId": from_message_id,
+            }),
+        );
+        fork.metadata = Some(serde_json::Value::Object(metadata));
+        self.storage
+            .chat_history
+            .update_session_metadata(&fork.id, fork.metadata.clone())
+            .await?;
+        if let Some(state) = self.active_sessions.read().await.get(&fork.id) {
+            state.write().await.session = fork.clone();
+        }
+
+        // Message ids are globally unique, so copies get new ids and their
+        // parent links are rewritten to point inside the fork.
+        let mut id_map: HashMap<String, String> = HashMap::new();
+        for message in messages.into_iter().take(cut + 1) {
+            let new_id = format!("msg_{}", uuid::Uuid::new_v4().to_string().replace("-", ""));
+            id_map.insert(message.id.clone(), new_id.clone());
+            let parent_id = message
+                .parent_id
+                .as_ref()
+                .and_then(|parent_id| id_map.get(parent_id).cloned());
+            self.add_message(Message {
+                id: new_id,
+                session_id: fork.id.clone(),
+                parent_id,
+                ..message
+            })
+            .await?;
+        }
+
+        Ok(fork)
+    }
+
     /// List sessions with optional filters
     pub async fn list_sessions(
         &self,
@@ -436,4 +511,86 @@ mod tests {
         assert_eq!(state.session.status, SessionStatus::Running);
         assert_eq!(state.session.last_event_id, Some("evt-1".to_string()));
     }
+
+    fn text_message(id: &str, session_id: &str, text: &str, created_at: i64) -> Message {
+        Message {
+            id: id.to_string(),
+            session_id: session_id.to_string(),
+            role: crate::storage::MessageRole::User,
+            content: crate::storage::MessageContent::Text {
+                text: text.to_string(),
+            },
+            created_at,
+            tool_call_id: None,
+            parent_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fork_session_copies_prefix() {
+        let (manager, _temp) = create_test_manager().await;
+
+        let parent = manager
+            .create_session(None, Some("Original".to_string()), None)
+            .await
+            .unwrap();
+        for (index, id) in ["m1", "m2", "m3", "m4"].iter().enumerate() {
+            manager
+                .add_message(text_message(id, &parent.id, id, 1000 + index as i64))
+                .await
+                .unwrap();
+        }
+
+        let fork = manager.fork_session(&parent.id, "m2").await.unwrap();
+        assert_ne!(fork.id, parent.id);
+        assert_eq!(fork.title, Some("Original (fork)".to_string()));
+
+        let texts: Vec<String> = manager
+            .get_messages(&fork.id, None, None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|message| match message.content {
+                crate::storage::MessageContent::Text { text } => text,
+                other => panic!("unexpected content {:?}", other),
+            })
+            .collect();
+        assert_eq!(texts, vec!["m1", "m2"]);
+
+        let stored = manager
+            .storage
+            .chat_history
+            .get_session(&fork.id)
+            .await
+            .unwrap()
+            .unwrap();
+        let forked_from = &stored.metadata.unwrap()["forkedFrom"];
+        assert_eq!(forked_from["sessionId"], parent.id.as_str());
+        assert_eq!(forked_from["messageId"], "m2");
+        assert_eq!(stored.last_event_id, None);
+
+        // The parent is untouched
+        assert_eq!(
+            manager
+                .get_messages(&parent.id, None, None)
+                .await
+                .unwrap()
+                .len(),
+            4
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fork_session_unknown_message() {
+        let (manager, _temp) = create_test_manager().await;
+
+        let parent = manager.create_session(None, None, None).await.unwrap();
+        manager
+            .add_message(text_message("m1", &parent.id, "hi", 1000))
+            .await
+            .unwrap();
+
+        assert!(manager.fork_session(&parent.id, "missing").await.is_err());
+        assert!(manager.fork_session("sess_missing", "m1").await.is_err());
+    }
 }
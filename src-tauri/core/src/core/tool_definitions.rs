@@ -4,6 +4,7 @@
 //! All tool names use camelCase to match TypeScript conventions.
 
 use crate::core::types::ToolDefinition;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 /// Tool category for dependency analysis
@@ -612,3 +613,81 @@ pub fn get_tool_definitions() -> Vec<(ToolDefinition, ToolMetadata)> {
         ),
     ]
 }
+
+/// Built-in tool as presented to the frontend tool palette
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailableTool {
+    pub name: String,
+    pub description: String,
+    /// JSON Schema of the tool input
+    pub input_schema: serde_json::Value,
+    pub mutates_filesystem: bool,
+    pub requires_approval: bool,
+}
+
+/// Whether a tool can change files on disk. `bash` and `installSkill` are
+/// categorized as "other" but still write to the filesystem.
+fn mutates_filesystem(name: &str, metadata: &ToolMetadata) -> bool {
+    matches!(metadata.category, ToolCategory::Write | ToolCategory::Edit)
+        || matches!(name, "bash" | "installSkill")
+}
+
+/// Describe every built-in tool, sorted by name
+pub fn available_tools() -> Vec<AvailableTool> {
+    let mut tools: Vec<AvailableTool> = get_tool_definitions()
+        .into_iter()
+        .map(|(definition, metadata)| AvailableTool {
+            mutates_filesystem: mutates_filesystem(&definition.name, &metadata),
+            requires_approval: definition.requires_approval,
+            name: definition.name,
+            description: definition.description,
+            input_schema: definition.parameters,
+        })
+        .collect();
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+    tools
+}
+
+#[tauri::command]
+pub fn list_available_tools() -> Vec<AvailableTool> {
+    available_tools()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_builtin_tools_with_schemas() {
+        let tools = available_tools();
+        for name in ["readFile", "writeFile", "editFile", "bash", "webFetch"] {
+            let tool = tools
+                .iter()
+                .find(|tool| tool.name == name)
+                .unwrap_or_else(|| panic!("missing built-in tool {}", name));
+            assert!(!tool.description.is_empty());
+            assert_eq!(tool.input_schema["type"], "object");
+            assert!(tool.input_schema["properties"]
+                .as_object()
+                .is_some_and(|properties| !properties.is_empty()));
+        }
+    }
+
+    #[test]
+    fn flags_filesystem_mutations() {
+        let tools = available_tools();
+        let mutates = |name: &str| {
+            tools
+                .iter()
+                .find(|tool| tool.name == name)
+                .map(|tool| tool.mutates_filesystem)
+                .unwrap()
+        };
+        assert!(mutates("writeFile"));
+        assert!(mutates("editFile"));
+        assert!(mutates("bash"));
+        assert!(!mutates("readFile"));
+        assert!(!mutates("webSearch"));
+    }
+}
@@ -1,11 +1,14 @@
 //! Tool Dependency Analyzer
 //!
 //! Analyzes tool calls and generates execution plans based on dependencies.
+//! Also validates call ordering so hallucinated operations (e.g. editing a
+//! file that was never read) can be surfaced before execution.
 //! Ported from TypeScript tool-dependency-analyzer.ts
 
 use crate::core::tool_definitions::{ToolCategory, ToolMetadata};
 use crate::core::types::ToolRequest;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 /// Execution stage - a logical phase in the execution plan
 #[derive(Debug, Clone)]
@@ -53,6 +56,15 @@ pub struct ExecutionSummary {
     pub concurrent_groups: usize,
 }
 
+/// A tool call issued in an order that its dependencies don't satisfy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyWarning {
+    pub tool_call_id: String,
+    pub issue: String,
+    pub suggestion: String,
+}
+
 /// Tool dependency analyzer
 pub struct ToolDependencyAnalyzer;
 
@@ -153,6 +165,92 @@ impl ToolDependencyAnalyzer {
         }
     }
 
+    /// Split calls into ordered batches that are safe to run one after another.
+    ///
+    /// Consecutive side-effect-free calls share a batch and may run concurrently;
+    /// every other call gets a batch of its own so writes stay serialized and
+    /// never reorder around reads. Batches hold indices into `tool_calls`.
+    pub fn concurrent_batches(
+        &self,
+        tool_calls: &[ToolRequest],
+        tool_metadata: &HashMap<String, ToolMetadata>,
+    ) -> Vec<Vec<usize>> {
+        let mut batches: Vec<Vec<usize>> = vec![];
+        let mut current: Vec<usize> = vec![];
+
+        for (index, tool_call) in tool_calls.iter().enumerate() {
+            let parallel_safe = tool_metadata
+                .get(&tool_call.name)
+                .is_some_and(|m| m.category == ToolCategory::Read && m.can_concurrent);
+
+            if parallel_safe {
+                current.push(index);
+                continue;
+            }
+            if !current.is_empty() {
+                batches.push(std::mem::take(&mut current));
+            }
+            batches.push(vec![index]);
+        }
+        if !current.is_empty() {
+            batches.push(current);
+        }
+
+        batches
+    }
+
+    /// Check that each call's dependencies are satisfied by earlier calls.
+    ///
+    /// `already_read` holds paths read earlier in the conversation; an edit is
+    /// valid once its target was read (or fully written) before it.
+    pub fn analyze_dependencies(
+        &self,
+        tool_calls: &[ToolRequest],
+        tool_metadata: &HashMap<String, ToolMetadata>,
+        already_read: &HashSet<String>,
+    ) -> Vec<DependencyWarning> {
+        let mut known: HashSet<String> = already_read.iter().map(|p| normalize_path(p)).collect();
+        let mut warnings = vec![];
+
+        for tool_call in tool_calls {
+            let category = tool_metadata
+                .get(&tool_call.name)
+                .map(|m| m.category)
+                .unwrap_or(ToolCategory::Other);
+            let targets: Vec<String> = self
+                .extract_single_tool_targets(tool_call)
+                .iter()
+                .map(|p| normalize_path(p))
+                .collect();
+
+            match category {
+                ToolCategory::Edit => {
+                    for target in &targets {
+                        if !known.contains(target) {
+                            warnings.push(DependencyWarning {
+                                tool_call_id: tool_call.tool_call_id.clone(),
+                                issue: format!(
+                                    "{} edits '{}' before it was read",
+                                    tool_call.name, target
+                                ),
+                                suggestion: format!(
+                                    "Read '{}' first so the edit is based on its current content",
+                                    target
+                                ),
+                            });
+                        }
+                    }
+                    // Report each unread file once
+                    known.extend(targets);
+                }
+                ToolCategory::Read | ToolCategory::Write => known.extend(targets),
+                ToolCategory::Other => {}
+            }
+        }
+
+        warnings
+    }
+
     /// Categorize tool calls by their category
     fn categorize_tool_calls(
         &self,
@@ -310,6 +408,12 @@ impl ToolDependencyAnalyzer {
     }
 }
 
+/// Normalize a path for comparison between tool calls
+fn normalize_path(path: &str) -> String {
+    let path = path.trim().replace('\\', "/");
+    path.strip_prefix("./").unwrap_or(&path).to_string()
+}
+
 /// Categorized tool calls
 struct CategorizedTools {
     read: Vec<ToolRequest>,
@@ -373,4 +477,88 @@ mod tests {
         assert_eq!(plan.stages[0].groups.len(), 1);
         assert!(plan.stages[0].groups[0].concurrent);
     }
+
+    fn file_tool_metadata() -> HashMap<String, ToolMetadata> {
+        let metadata = |category| ToolMetadata {
+            category,
+            can_concurrent: category == ToolCategory::Read,
+            file_operation: true,
+            requires_approval: category != ToolCategory::Read,
+            render_doing_ui: true,
+        };
+        HashMap::from([
+            ("readFile".to_string(), metadata(ToolCategory::Read)),
+            ("writeFile".to_string(), metadata(ToolCategory::Write)),
+            ("editFile".to_string(), metadata(ToolCategory::Edit)),
+        ])
+    }
+
+    fn call(id: &str, name: &str, path: &str) -> ToolRequest {
+        ToolRequest {
+            tool_call_id: id.to_string(),
+            name: name.to_string(),
+            input: serde_json::json!({ "file_path": path }),
+            provider_metadata: None,
+        }
+    }
+
+    #[test]
+    fn edit_before_read_produces_warning() {
+        let analyzer = ToolDependencyAnalyzer::new();
+        let calls = vec![
+            call("1", "editFile", "src/main.rs"),
+            call("2", "readFile", "src/main.rs"),
+        ];
+
+        let warnings =
+            analyzer.analyze_dependencies(&calls, &file_tool_metadata(), &HashSet::new());
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].tool_call_id, "1");
+        assert!(warnings[0].issue.contains("src/main.rs"));
+        assert!(warnings[0].suggestion.starts_with("Read"));
+    }
+
+    #[test]
+    fn valid_sequence_produces_no_warnings() {
+        let analyzer = ToolDependencyAnalyzer::new();
+        let calls = vec![
+            call("1", "readFile", "./src/main.rs"),
+            call("2", "editFile", "src/main.rs"),
+            call("3", "writeFile", "src/new.rs"),
+            call("4", "editFile", "src/new.rs"),
+            call("5", "editFile", "src/lib.rs"),
+        ];
+        let already_read = HashSet::from(["src/lib.rs".to_string()]);
+
+        let warnings = analyzer.analyze_dependencies(&calls, &file_tool_metadata(), &already_read);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn independent_reads_share_a_batch() {
+        let analyzer = ToolDependencyAnalyzer::new();
+        let calls = vec![
+            call("1", "readFile", "a.rs"),
+            call("2", "readFile", "b.rs"),
+            call("3", "readFile", "c.rs"),
+        ];
+
+        let batches = analyzer.concurrent_batches(&calls, &file_tool_metadata());
+        assert_eq!(batches, vec![vec![0, 1, 2]]);
+    }
+
+    #[test]
+    fn writes_split_batches_and_keep_order() {
+        let analyzer = ToolDependencyAnalyzer::new();
+        let calls = vec![
+            call("1", "readFile", "a.rs"),
+            call("2", "readFile", "b.rs"),
+            call("3", "writeFile", "a.rs"),
+            call("4", "unknownTool", "a.rs"),
+            call("5", "readFile", "a.rs"),
+        ];
+
+        let batches = analyzer.concurrent_batches(&calls, &file_tool_metadata());
+        assert_eq!(batches, vec![vec![0, 1], vec![2], vec![3], vec![4]]);
+    }
 }
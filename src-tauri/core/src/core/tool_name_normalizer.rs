@@ -45,6 +45,15 @@ pub fn normalize_tool_name(name: &str) -> String {
         return trimmed.to_string();
     }
 
+    if let Some(canonical) = fuzzy_match(trimmed) {
+        log::info!(
+            "[ToolNameNormalizer] Corrected tool name '{}' to '{}'",
+            trimmed,
+            canonical
+        );
+        return canonical.to_string();
+    }
+
     trimmed.to_string()
 }
 
@@ -54,6 +63,75 @@ pub fn is_known_tool_name(name: &str) -> bool {
     CANONICAL_TOOL_NAMES.iter().any(|tool| *tool == normalized)
 }
 
+/// Lowercase and drop separators so `ReadFile`, `read-file` and `readfile` compare equal.
+fn squash(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '_' | '-' | ' ' | '.'))
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Edit distance allowed for a squashed name of this length. Short names get
+/// no slack, otherwise `blob` would silently become `glob`.
+fn max_edit_distance(len: usize) -> usize {
+    match len {
+        0..=4 => 0,
+        5..=8 => 1,
+        _ => 2,
+    }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Nearest canonical name for a near-miss, if there is exactly one within range.
+fn fuzzy_match(name: &str) -> Option<&'static str> {
+    nearest_candidate(name, CANONICAL_TOOL_NAMES)
+}
+
+fn nearest_candidate(name: &str, candidates: &[&'static str]) -> Option<&'static str> {
+    let squashed = squash(name);
+    if squashed.is_empty() {
+        return None;
+    }
+    let max_distance = max_edit_distance(squashed.chars().count());
+
+    let mut best: Option<(&'static str, usize)> = None;
+    let mut ambiguous = false;
+    for &canonical in candidates {
+        let distance = edit_distance(&squashed, &squash(canonical));
+        if distance > max_distance {
+            continue;
+        }
+        match best {
+            Some((_, best_distance)) if distance > best_distance => {}
+            Some((_, best_distance)) if distance == best_distance => ambiguous = true,
+            _ => {
+                best = Some((canonical, distance));
+                ambiguous = false;
+            }
+        }
+    }
+
+    if ambiguous {
+        return None;
+    }
+    best.map(|(canonical, _)| canonical)
+}
+
 fn legacy_aliases() -> HashMap<&'static str, &'static str> {
     HashMap::from([
         ("read_file", "readFile"),
@@ -130,6 +208,33 @@ mod tests {
         assert_eq!(normalize_tool_name("customTool"), "customTool");
     }
 
+    #[test]
+    fn corrects_near_miss_names() {
+        assert_eq!(normalize_tool_name("read-file"), "readFile");
+        assert_eq!(normalize_tool_name("ReadFile"), "readFile");
+        assert_eq!(normalize_tool_name("readfile"), "readFile");
+        assert_eq!(normalize_tool_name("read_fle"), "readFile");
+        assert_eq!(normalize_tool_name("web-serch"), "webSearch");
+        assert!(is_known_tool_name("READ_FILE"));
+    }
+
+    #[test]
+    fn unrelated_or_short_names_are_not_corrected() {
+        assert_eq!(normalize_tool_name("deployService"), "deployService");
+        assert_eq!(normalize_tool_name("blob"), "blob");
+        assert!(!is_known_tool_name("deployService"));
+    }
+
+    #[test]
+    fn ambiguous_near_miss_is_left_alone() {
+        let candidates = ["fetchUrl", "fetchUri"];
+        assert_eq!(
+            nearest_candidate("fetch_url", &candidates),
+            Some("fetchUrl")
+        );
+        assert_eq!(nearest_candidate("fetchUrx", &candidates), None);
+    }
+
     #[test]
     fn empty_name_returns_empty() {
         assert_eq!(normalize_tool_name(""), "");
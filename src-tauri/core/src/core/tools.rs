@@ -3,11 +3,36 @@
 //! Provides a registry of available tools and dispatch mechanism for tool execution.
 //! Tools execute on the backend host (filesystem, git, shell, search).
 
+use crate::core::tool_definitions::ToolMetadata;
+use crate::core::tool_dependency_analyzer::ToolDependencyAnalyzer;
 use crate::core::types::*;
+use crate::llm::auth::api_key_manager::LlmState;
+use crate::platform::{Platform, PlatformContext};
 use crate::storage::models::*;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::{RwLock, Semaphore};
+
+/// Default cap on tool calls executing at the same time within one batch
+pub const DEFAULT_MAX_CONCURRENT_TOOLS: usize = 4;
+
+/// Default timeout for a tool call; `None` means the tool may run indefinitely.
+///
+/// Tools that wait on the user or on a sub-agent are unbounded. Shell commands run
+/// in their own process group, which is killed when a timed-out call is dropped.
+pub fn default_tool_timeout(name: &str) -> Option<Duration> {
+    match name {
+        "askUserQuestions" | "exitPlanMode" | "callAgent" => None,
+        "readFile" | "writeFile" | "editFile" | "glob" | "listFiles" => {
+            Some(Duration::from_secs(60))
+        }
+        "webFetch" | "webSearch" => Some(Duration::from_secs(90)),
+        "codeSearch" => Some(Duration::from_secs(120)),
+        "bash" => Some(Duration::from_secs(600)),
+        _ => Some(Duration::from_secs(300)),
+    }
+}
 
 /// Tool execution context passed to all tool handlers
 #[derive(Debug, Clone)]
@@ -19,6 +44,57 @@ pub struct ToolContext {
     pub settings: TaskSettings,
     /// Optional LLM state for tools that need AI services (image generation, etc.)
     pub llm_state: Option<Arc<LlmState>>,
+    /// Tools the current agent may call; `None` allows every registered tool
+    pub allowed_tools: Option<Vec<String>>,
+}
+
+/// Setting key that overrides the agent's tool list for a session
+pub const ALLOWED_TOOLS_SETTING: &str = "allowedTools";
+
+/// Resolve the tool allowlist for a task.
+///
+/// A session-level `allowedTools` setting wins over the agent's `tools` list.
+/// Agents with an empty list predate tool configuration and stay unrestricted.
+pub fn resolve_allowed_tools(
+    agent: Option<&Agent>,
+    settings: &TaskSettings,
+) -> Option<Vec<String>> {
+    let from_settings = settings
+        .extra
+        .get(ALLOWED_TOOLS_SETTING)
+        .and_then(|value| serde_json::from_value::<Vec<String>>(value.clone()).ok());
+    let tools = match from_settings {
+        Some(tools) => tools,
+        None => agent
+            .map(|agent| agent.tools.clone())
+            .filter(|tools| !tools.is_empty())?,
+    };
+    Some(
+        tools
+            .iter()
+            .map(|name| crate::core::tool_name_normalizer::normalize_tool_name(name))
+            .filter(|name| !name.is_empty())
+            .collect(),
+    )
+}
+
+/// Structured rejection returned to the model for a tool outside the allowlist
+fn disallowed_tool_result(request: &ToolRequest, allowed: &[String]) -> ToolResult {
+    ToolResult {
+        tool_call_id: request.tool_call_id.clone(),
+        name: Some(request.name.clone()),
+        success: false,
+        output: serde_json::json!({
+            "rejected": true,
+            "reason": "tool_not_allowed",
+            "allowed_tools": allowed,
+        }),
+        error: Some(format!(
+            "Tool '{}' is not enabled for this agent. Available tools: {}",
+            request.name,
+            allowed.join(", ")
+        )),
+    }
 }
 
 /// Result of tool execution
@@ -36,13 +112,6 @@ pub type ToolHandler = Arc<
         + Sync,
 >;
 
-use crate::llm::auth::api_key_manager::LlmState;
-use crate::tools::{
-    ask_user_questions, bash_tool, call_agent, code_search, edit_file, exit_plan_mode, github_pr,
-    glob_tool, image_generation, install_skill, list_files, read_file, todo_write, web_fetch,
-    web_search, write_file,
-};
-
 /// Tool registry containing all available tools
 pub struct ToolRegistry {
     tools: RwLock<HashMap<String, ToolDefinition>>,
@@ -188,11 +257,79 @@ impl Default for ToolRegistry {
 /// Tool dispatcher that manages tool execution with approval workflow
 pub struct ToolDispatcher {
     registry: Arc<ToolRegistry>,
+    tool_metadata: HashMap<String, ToolMetadata>,
+    concurrency: Arc<Semaphore>,
+    timeout_overrides: HashMap<String, Option<Duration>>,
 }
 
 impl ToolDispatcher {
     pub fn new(registry: Arc<ToolRegistry>) -> Self {
-        Self { registry }
+        let tool_metadata = crate::core::tool_definitions::get_tool_definitions()
+            .into_iter()
+            .map(|(definition, metadata)| (definition.name, metadata))
+            .collect();
+        Self {
+            registry,
+            tool_metadata,
+            concurrency: Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_TOOLS)),
+            timeout_overrides: HashMap::new(),
+        }
+    }
+
+    /// Override the timeout for one tool; `None` disables the timeout
+    pub fn with_tool_timeout(mut self, name: &str, timeout: Option<Duration>) -> Self {
+        let name = crate::core::tool_name_normalizer::normalize_tool_name(name);
+        self.timeout_overrides.insert(name, timeout);
+        self
+    }
+
+    /// Effective timeout for a (normalized) tool name
+    pub fn tool_timeout(&self, name: &str) -> Option<Duration> {
+        match self.timeout_overrides.get(name) {
+            Some(timeout) => *timeout,
+            None => default_tool_timeout(name),
+        }
+    }
+
+    /// Execute a tool, cancelling it once its timeout elapses
+    async fn execute_with_timeout(&self, request: ToolRequest, context: ToolContext) -> ToolResult {
+        let Some(limit) = self.tool_timeout(&request.name) else {
+            return self.registry.execute(request, context).await;
+        };
+
+        let tool_call_id = request.tool_call_id.clone();
+        let name = request.name.clone();
+        match tokio::time::timeout(limit, self.registry.execute(request, context)).await {
+            Ok(result) => result,
+            Err(_) => {
+                log::warn!(
+                    "[ToolDispatcher] Tool '{}' ({}) timed out after {}ms",
+                    name,
+                    tool_call_id,
+                    limit.as_millis()
+                );
+                ToolResult {
+                    tool_call_id,
+                    name: Some(name.clone()),
+                    success: false,
+                    output: serde_json::json!({
+                        "timed_out": true,
+                        "timeout_ms": limit.as_millis() as u64,
+                    }),
+                    error: Some(format!(
+                        "Tool '{}' timed out after {}ms",
+                        name,
+                        limit.as_millis()
+                    )),
+                }
+            }
+        }
+    }
+
+    /// Limit how many side-effect-free tool calls may run at once
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.concurrency = Arc::new(Semaphore::new(max_concurrency.max(1)));
+        self
     }
 
     /// Dispatch a tool execution request
@@ -209,6 +346,10 @@ impl ToolDispatcher {
             ..request
         };
 
+        if let Some(result) = self.check_allowed(&request, &context) {
+            return Ok(ToolDispatchResult::Completed(result));
+        }
+
         // Check if tool requires approval
         let requires_approval = self.registry.requires_approval(&request.name).await;
 
@@ -217,14 +358,77 @@ impl ToolDispatcher {
             Ok(ToolDispatchResult::PendingApproval(request))
         } else {
             // Execute immediately
-            let result = self.registry.execute(request, context).await;
+            let result = self.execute_with_timeout(request, context).await;
             Ok(ToolDispatchResult::Completed(result))
         }
     }
 
+    /// Dispatch all tool calls from one model turn.
+    ///
+    /// Independent read-only calls run concurrently (bounded by the dispatcher's
+    /// semaphore); writes and unknown tools run alone, in the order issued.
+    /// Results are returned in the same order as `requests`.
+    pub async fn dispatch_batch(
+        &self,
+        requests: Vec<ToolRequest>,
+        context: ToolContext,
+        auto_approve: bool,
+    ) -> Result<Vec<ToolDispatchResult>, String> {
+        let requests: Vec<ToolRequest> = requests
+            .into_iter()
+            .map(|request| ToolRequest {
+                name: crate::core::tool_name_normalizer::normalize_tool_name(&request.name),
+                ..request
+            })
+            .collect();
+
+        let batches =
+            ToolDependencyAnalyzer::new().concurrent_batches(&requests, &self.tool_metadata);
+        let mut results: Vec<Option<ToolDispatchResult>> = vec![None; requests.len()];
+
+        for batch in batches {
+            let futures = batch.iter().map(|&index| {
+                let request = requests[index].clone();
+                let context = context.clone();
+                async move {
+                    let _permit = self
+                        .concurrency
+                        .acquire()
+                        .await
+                        .map_err(|e| format!("Tool semaphore closed: {}", e))?;
+                    self.dispatch(request, context, auto_approve).await
+                }
+            });
+            let batch_results = futures::future::join_all(futures).await;
+            for (index, result) in batch.into_iter().zip(batch_results) {
+                results[index] = Some(result?);
+            }
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
     /// Execute a tool that was pending approval
     pub async fn execute_approved(&self, request: ToolRequest, context: ToolContext) -> ToolResult {
-        self.registry.execute(request, context).await
+        if let Some(result) = self.check_allowed(&request, &context) {
+            return result;
+        }
+        self.execute_with_timeout(request, context).await
+    }
+
+    /// Rejection result when the tool isn't in the agent's allowlist
+    fn check_allowed(&self, request: &ToolRequest, context: &ToolContext) -> Option<ToolResult> {
+        let allowed = context.allowed_tools.as_ref()?;
+        let name = crate::core::tool_name_normalizer::normalize_tool_name(&request.name);
+        if allowed.iter().any(|tool| *tool == name) {
+            return None;
+        }
+        log::warn!(
+            "[ToolDispatcher] Rejected tool '{}' ({}) outside the agent allowlist",
+            name,
+            request.tool_call_id
+        );
+        Some(disallowed_tool_result(request, allowed))
     }
 }
 
@@ -237,462 +441,152 @@ pub enum ToolDispatchResult {
     PendingApproval(ToolRequest),
 }
 
-/// Execute a tool by name using the dedicated tool modules
+/// Execute a tool by name on the backend host platform
 async fn execute_tool_by_name(
     name: &str,
     request: ToolRequest,
     ctx: ToolContext,
 ) -> ToolExecutionOutput {
-    // Route to appropriate tool module based on name
-    let result: ToolExecutionOutput = match name {
-        // File tools
-        "readFile" | "read_file" => {
-            let file_path = request
-                .input
-                .get("file_path")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            let start_line = request
-                .input
-                .get("start_line")
-                .and_then(|v| v.as_u64())
-                .map(|v| v as usize);
-            let line_count = request
-                .input
-                .get("line_count")
-                .and_then(|v| v.as_u64())
-                .map(|v| v as usize);
-
-            let result = read_file::execute(file_path, start_line, line_count, &ctx).await;
-            ToolExecutionOutput {
-                success: result.success,
-                data: serde_json::to_value(&result).unwrap_or_default(),
-                error: if result.success {
-                    None
-                } else {
-                    Some(result.message)
-                },
-            }
-        }
-        "writeFile" | "write_file" => {
-            let file_path = request
-                .input
-                .get("file_path")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            let content = request
-                .input
-                .get("content")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            // Backend always auto-approves
-            let result = write_file::execute(file_path, content, false, &ctx).await;
-            ToolExecutionOutput {
-                success: result.success,
-                data: serde_json::to_value(&result).unwrap_or_default(),
-                error: if result.success {
-                    None
-                } else {
-                    Some(result.message)
-                },
-            }
-        }
-        "editFile" | "edit_file" => {
-            let file_path = request
-                .input
-                .get("file_path")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-
-            let edits: Vec<edit_file::EditBlock> = request
-                .input
-                .get("edits")
-                .and_then(|v| v.as_array())
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(|e| {
-                            Some(edit_file::EditBlock {
-                                old_string: e.get("old_string")?.as_str()?.to_string(),
-                                new_string: e.get("new_string")?.as_str()?.to_string(),
-                            })
-                        })
-                        .collect()
-                })
-                .unwrap_or_default();
-
-            // Backend always auto-approves
-            let result = edit_file::execute(file_path, edits, false, &ctx).await;
-            ToolExecutionOutput {
-                success: result.success,
-                data: serde_json::to_value(&result).unwrap_or_default(),
-                error: if result.success {
-                    None
-                } else {
-                    Some(result.message)
-                },
-            }
-        }
-        "listFiles" | "list_files" | "list_directory" => {
-            let directory_path = request
-                .input
-                .get("directory_path")
-                .or_else(|| request.input.get("path"))
-                .and_then(|v| v.as_str())
-                .unwrap_or(".");
-            let max_depth = request
-                .input
-                .get("max_depth")
-                .and_then(|v| v.as_u64())
-                .map(|v| v as usize);
-
-            let result = list_files::execute(directory_path, max_depth, &ctx).await;
-            ToolExecutionOutput {
-                success: result.success,
-                data: serde_json::to_value(&result).unwrap_or_default(),
-                error: result.error,
-            }
-        }
-        // Search tools
-        "codeSearch" | "code_search" | "search_files" => {
-            let pattern = request
-                .input
-                .get("pattern")
-                .or_else(|| request.input.get("query"))
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            let path = request
-                .input
-                .get("path")
-                .and_then(|v| v.as_str())
-                .unwrap_or(&ctx.workspace_root);
-            let file_types: Option<Vec<String>> = request
-                .input
-                .get("file_types")
-                .and_then(|v| v.as_array())
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(|v| v.as_str().map(String::from))
-                        .collect()
-                });
-
-            let result = code_search::execute(pattern, path, file_types, &ctx).await;
-            ToolExecutionOutput {
-                success: result.success,
-                data: serde_json::to_value(&result).unwrap_or_default(),
-                error: result.error,
-            }
-        }
-        "glob" => {
-            let pattern = request
-                .input
-                .get("pattern")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            let path = request.input.get("path").and_then(|v| v.as_str());
-
-            let result = glob_tool::execute(pattern, path, &ctx).await;
-            ToolExecutionOutput {
-                success: result.success,
-                data: serde_json::json!({"result": result.result}),
-                error: result.error,
-            }
-        }
-        // Shell tool
-        "bash" | "execute_shell" | "executeShell" => {
-            let command = request
-                .input
-                .get("command")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            let run_in_background = request
-                .input
-                .get("runInBackground")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
+    let platform = Platform::new();
+    let platform_ctx = platform.create_context(&ctx.workspace_root, ctx.worktree_path.as_deref());
 
-            let result = bash_tool::execute(command, run_in_background, &ctx).await;
-            ToolExecutionOutput {
-                success: result.success,
-                data: serde_json::to_value(&result).unwrap_or_default(),
-                error: result.error,
-            }
-        }
-        // Web tools
-        "webFetch" | "web_fetch" => {
-            if let Some(url) = request.input.get("url").and_then(|v| v.as_str()) {
-                match web_fetch::execute_web_fetch(url, &ctx, &request.tool_call_id).await {
-                    Ok(result) => ToolExecutionOutput {
-                        success: true,
-                        data: serde_json::to_value(&result).unwrap_or_default(),
-                        error: None,
-                    },
-                    Err(e) => ToolExecutionOutput {
-                        success: false,
-                        data: serde_json::Value::Null,
-                        error: Some(e),
-                    },
-                }
-            } else {
-                ToolExecutionOutput {
-                    success: false,
-                    data: serde_json::Value::Null,
-                    error: Some("No URL provided".to_string()),
-                }
-            }
-        }
-        "webSearch" | "web_search" => {
-            if let Some(query) = request.input.get("query").and_then(|v| v.as_str()) {
-                match web_search::execute_web_search(query).await {
-                    Ok(results) => ToolExecutionOutput {
-                        success: true,
-                        data: serde_json::to_value(&results).unwrap_or_default(),
-                        error: None,
-                    },
-                    Err(e) => ToolExecutionOutput {
-                        success: false,
-                        data: serde_json::Value::Null,
-                        error: Some(e),
-                    },
-                }
-            } else {
-                ToolExecutionOutput {
-                    success: false,
-                    data: serde_json::Value::Null,
-                    error: Some("No query provided".to_string()),
-                }
-            }
-        }
-        // GitHub PR tool
-        "githubPR" | "github_pr" => {
-            let url = request
-                .input
-                .get("url")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            let action = request
-                .input
-                .get("action")
-                .and_then(|v| v.as_str())
-                .unwrap_or("info");
-            let page = request
-                .input
-                .get("page")
-                .and_then(|v| v.as_u64())
-                .map(|v| v as u32);
-            let per_page = request
-                .input
-                .get("perPage")
-                .and_then(|v| v.as_u64())
-                .map(|v| v as u32);
-            let filename_filter = request.input.get("filenameFilter").and_then(|v| v.as_str());
-
-            let result =
-                github_pr::execute(url, action, page, per_page, filename_filter, &ctx).await;
-            ToolExecutionOutput {
-                success: result.success,
-                data: serde_json::to_value(&result).unwrap_or_default(),
-                error: result.error,
-            }
-        }
-        // Image Generation tool
-        "imageGeneration" | "image_generation" => {
-            let prompt = request
-                .input
-                .get("prompt")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            let size = request.input.get("size").and_then(|v| v.as_str());
-            let quality = request.input.get("quality").and_then(|v| v.as_str());
-            let n = request
-                .input
-                .get("n")
-                .and_then(|v| v.as_u64())
-                .map(|v| v as u32);
-
-            let result = image_generation::execute(prompt, size, quality, n, &ctx).await;
-            ToolExecutionOutput {
-                success: result.success,
-                data: serde_json::to_value(&result).unwrap_or_default(),
-                error: result.error,
-            }
-        }
-        // Agent tools
-        "callAgent" | "call_agent" => {
-            let agent_id = request
-                .input
-                .get("agentId")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            let task = request
-                .input
-                .get("task")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            let context = request.input.get("context").and_then(|v| v.as_str());
-            let targets: Option<Vec<String>> = request
-                .input
-                .get("targets")
-                .and_then(|v| v.as_array())
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(|v| v.as_str().map(String::from))
-                        .collect()
-                });
-
-            let result = call_agent::execute(agent_id, task, context, targets, &ctx).await;
-            ToolExecutionOutput {
-                success: result.success,
-                data: serde_json::to_value(&result).unwrap_or_default(),
-                error: result.error,
-            }
-        }
-        // Todo tool
-        "todoWrite" | "todo_write" => {
-            let todos: Vec<todo_write::TodoItem> = request
-                .input
-                .get("todos")
-                .and_then(|v| v.as_array())
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(|v| {
-                            Some(todo_write::TodoItem {
-                                id: v.get("id")?.as_str()?.to_string(),
-                                content: v.get("content")?.as_str()?.to_string(),
-                                status: v.get("status")?.as_str()?.to_string(),
-                            })
-                        })
-                        .collect()
-                })
-                .unwrap_or_default();
-
-            let result = todo_write::execute(todos, &ctx).await;
-            ToolExecutionOutput {
-                success: result.success,
-                data: serde_json::to_value(&result).unwrap_or_default(),
-                error: result.error,
-            }
-        }
-        // Ask user tool
-        "askUserQuestions" | "ask_user_questions" => {
-            let questions: Vec<ask_user_questions::Question> = request
-                .input
-                .get("questions")
-                .and_then(|v| v.as_array())
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(|v| {
-                            Some(ask_user_questions::Question {
-                                id: v.get("id")?.as_str()?.to_string(),
-                                question: v.get("question")?.as_str()?.to_string(),
-                                header: v.get("header")?.as_str()?.to_string(),
-                                options: v
-                                    .get("options")?
-                                    .as_array()?
-                                    .iter()
-                                    .filter_map(|o| {
-                                        Some(ask_user_questions::QuestionOption {
-                                            label: o.get("label")?.as_str()?.to_string(),
-                                            description: o
-                                                .get("description")?
-                                                .as_str()?
-                                                .to_string(),
-                                        })
-                                    })
-                                    .collect(),
-                                multi_select: v.get("multiSelect")?.as_bool()?,
-                            })
-                        })
-                        .collect()
-                })
-                .unwrap_or_default();
-
-            let result = ask_user_questions::execute(questions, &ctx).await;
-            ToolExecutionOutput {
-                success: result.success,
-                data: serde_json::to_value(&result).unwrap_or_default(),
-                error: result.error,
-            }
-        }
-        // Exit plan mode
-        "exitPlanMode" | "exit_plan_mode" => {
-            let plan = request
-                .input
-                .get("plan")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-
-            match exit_plan_mode::execute(plan, &ctx).await {
-                Ok(result) => ToolExecutionOutput {
-                    success: true,
-                    data: serde_json::to_value(&result).unwrap_or_default(),
-                    error: None,
-                },
-                Err(e) => ToolExecutionOutput {
-                    success: false,
-                    data: serde_json::Value::Null,
-                    error: Some(e),
-                },
-            }
-        }
-        // Install Skill tool
-        "installSkill" | "install_skill" => {
-            let repository = request
-                .input
-                .get("repository")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            let path = request
-                .input
-                .get("path")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            let skill_id = request.input.get("skillId").and_then(|v| v.as_str());
-
-            let result = install_skill::execute(repository, path, skill_id, &ctx).await;
-            ToolExecutionOutput {
-                success: result.success,
-                data: serde_json::to_value(&result).unwrap_or_default(),
-                error: if result.success {
-                    None
-                } else {
-                    Some(result.message)
-                },
-            }
-        }
-        // Test Custom Tool
-        "test_custom_tool" | "test_custom" => {
-            // Return placeholder - full implementation would require custom tool compiler
-            ToolExecutionOutput {
-                success: false,
-                data: serde_json::Value::Null,
-                error: Some("test_custom_tool requires custom tool compiler which is not fully implemented in backend mode".to_string()),
-            }
-        }
-        // Git tools (via platform)
-        "git_status" | "gitStatus" => {
-            let platform = crate::platform::Platform::new();
-            let platform_ctx =
-                platform.create_context(&ctx.workspace_root, ctx.worktree_path.as_deref());
-            let platform_result = platform.git.get_status(&platform_ctx).await;
-            ToolExecutionOutput {
-                success: platform_result.success,
-                data: serde_json::to_value(&platform_result.data).unwrap_or_default(),
-                error: platform_result.error,
-            }
-        }
-        _ => ToolExecutionOutput {
+    if name == "editFile" {
+        return edit_file(&platform, &request.input, &platform_ctx).await;
+    }
+
+    let Some((platform_tool, input)) = platform_tool_input(name, &request.input) else {
+        return ToolExecutionOutput {
+            success: false,
+            data: serde_json::Value::Null,
+            error: Some(format!(
+                "Tool '{}' is not available in the backend runtime",
+                name
+            )),
+        };
+    };
+
+    match platform
+        .execute_tool(platform_tool, &input, &platform_ctx)
+        .await
+    {
+        Ok(data) => ToolExecutionOutput {
+            success: data
+                .get("success")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true),
+            error: data.get("error").and_then(|v| v.as_str()).map(String::from),
+            data,
+        },
+        Err(error) => ToolExecutionOutput {
             success: false,
             data: serde_json::Value::Null,
-            error: Some(format!("Unknown tool: {}", name)),
+            error: Some(error),
         },
+    }
+}
+
+/// Map a model-facing tool call onto a platform tool and its input
+fn platform_tool_input(
+    name: &str,
+    input: &serde_json::Value,
+) -> Option<(&'static str, serde_json::Value)> {
+    let field = |keys: &[&str]| keys.iter().find_map(|key| input.get(*key)).cloned();
+    let mapped = match name {
+        "readFile" => (
+            "read_file",
+            serde_json::json!({ "path": field(&["file_path", "path"]) }),
+        ),
+        "writeFile" => (
+            "write_file",
+            serde_json::json!({
+                "path": field(&["file_path", "path"]),
+                "content": field(&["content"]),
+            }),
+        ),
+        "listFiles" => (
+            "list_directory",
+            serde_json::json!({ "path": field(&["directory_path", "path"]) }),
+        ),
+        "codeSearch" => (
+            "search_files",
+            serde_json::json!({
+                "pattern": field(&["pattern", "query"]),
+                "path": field(&["path"]),
+            }),
+        ),
+        "bash" => (
+            "execute_shell",
+            serde_json::json!({
+                "command": field(&["command"]),
+                "cwd": field(&["cwd"]),
+            }),
+        ),
+        "git_status" | "gitStatus" => ("git_status", serde_json::json!({})),
+        _ => return None,
     };
+    Some(mapped)
+}
 
-    result
+/// Apply `edits` (exact `old_string` -> `new_string` replacements) to one file
+async fn edit_file(
+    platform: &Platform,
+    input: &serde_json::Value,
+    ctx: &PlatformContext,
+) -> ToolExecutionOutput {
+    let failure = |error: String| ToolExecutionOutput {
+        success: false,
+        data: serde_json::Value::Null,
+        error: Some(error),
+    };
+
+    let Some(path) = input
+        .get("file_path")
+        .or_else(|| input.get("path"))
+        .and_then(|v| v.as_str())
+    else {
+        return failure("Missing 'file_path' parameter".to_string());
+    };
+    let edits = match input.get("edits").and_then(|v| v.as_array()) {
+        Some(edits) if !edits.is_empty() => edits,
+        _ => return failure("No edits provided".to_string()),
+    };
+
+    let read = platform.filesystem.read_file(path, ctx).await;
+    let Some(mut content) = read.data else {
+        return failure(
+            read.error
+                .unwrap_or_else(|| format!("Failed to read {}", path)),
+        );
+    };
+
+    for edit in edits {
+        let (Some(old_string), Some(new_string)) = (
+            edit.get("old_string").and_then(|v| v.as_str()),
+            edit.get("new_string").and_then(|v| v.as_str()),
+        ) else {
+            return failure("Each edit needs old_string and new_string".to_string());
+        };
+        if !content.contains(old_string) {
+            return failure(format!("old_string not found in {}", path));
+        }
+        content = content.replacen(old_string, new_string, 1);
+    }
+
+    let write = platform
+        .filesystem
+        .write_file(path, &content, false, ctx)
+        .await;
+    ToolExecutionOutput {
+        success: write.success,
+        data: serde_json::json!({ "path": path, "edits": edits.len() }),
+        error: write.error,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     #[tokio::test]
     async fn test_tool_registry() {
@@ -754,6 +648,270 @@ mod tests {
         assert!(result.is_err());
     }
 
+    /// Handler that records how many calls are in flight at once
+    fn tracking_handler(in_flight: Arc<AtomicUsize>, peak: Arc<AtomicUsize>) -> ToolHandler {
+        Arc::new(move |req, _ctx| {
+            let in_flight = in_flight.clone();
+            let peak = peak.clone();
+            Box::pin(async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                ToolExecutionOutput {
+                    success: true,
+                    data: serde_json::json!({ "id": req.tool_call_id }),
+                    error: None,
+                }
+            })
+        })
+    }
+
+    async fn tracking_dispatcher() -> (ToolDispatcher, Arc<AtomicUsize>) {
+        let registry = ToolRegistry::new();
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        for name in ["readFile", "writeFile"] {
+            let definition = ToolDefinition {
+                name: name.to_string(),
+                description: String::new(),
+                parameters: serde_json::json!({}),
+                requires_approval: false,
+            };
+            registry
+                .register(
+                    definition,
+                    tracking_handler(in_flight.clone(), peak.clone()),
+                )
+                .await
+                .unwrap();
+        }
+        (ToolDispatcher::new(Arc::new(registry)), peak)
+    }
+
+    fn test_context() -> ToolContext {
+        ToolContext {
+            session_id: "session".to_string(),
+            task_id: "task".to_string(),
+            workspace_root: ".".to_string(),
+            worktree_path: None,
+            settings: TaskSettings::default(),
+            llm_state: None,
+            allowed_tools: None,
+        }
+    }
+
+    fn request(id: &str, name: &str) -> ToolRequest {
+        ToolRequest {
+            tool_call_id: id.to_string(),
+            name: name.to_string(),
+            input: serde_json::json!({ "file_path": format!("/{}.rs", id) }),
+            provider_metadata: None,
+        }
+    }
+
+    fn completed_ids(results: &[ToolDispatchResult]) -> Vec<String> {
+        results
+            .iter()
+            .map(|result| match result {
+                ToolDispatchResult::Completed(result) => result.tool_call_id.clone(),
+                ToolDispatchResult::PendingApproval(request) => request.tool_call_id.clone(),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn independent_reads_run_concurrently() {
+        let (dispatcher, peak) = tracking_dispatcher().await;
+        let requests = vec![
+            request("a", "readFile"),
+            request("b", "read_file"),
+            request("c", "readFile"),
+        ];
+
+        let results = dispatcher
+            .dispatch_batch(requests, test_context(), true)
+            .await
+            .unwrap();
+        assert_eq!(completed_ids(&results), vec!["a", "b", "c"]);
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn write_forces_serialization() {
+        let (dispatcher, peak) = tracking_dispatcher().await;
+        let requests = vec![
+            request("a", "writeFile"),
+            request("b", "readFile"),
+            request("c", "writeFile"),
+        ];
+
+        let results = dispatcher
+            .dispatch_batch(requests, test_context(), true)
+            .await
+            .unwrap();
+        assert_eq!(completed_ids(&results), vec!["a", "b", "c"]);
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn concurrency_is_bounded_by_semaphore() {
+        let (dispatcher, peak) = tracking_dispatcher().await;
+        let dispatcher = dispatcher.with_max_concurrency(2);
+        let requests = (0..5)
+            .map(|i| request(&i.to_string(), "readFile"))
+            .collect();
+
+        dispatcher
+            .dispatch_batch(requests, test_context(), true)
+            .await
+            .unwrap();
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn slow_tool_times_out_and_batch_continues() {
+        let registry = ToolRegistry::new();
+        let slow: ToolHandler = Arc::new(|_req, _ctx| {
+            Box::pin(async move {
+                tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+                ToolExecutionOutput {
+                    success: true,
+                    data: serde_json::Value::Null,
+                    error: None,
+                }
+            })
+        });
+        let fast: ToolHandler = Arc::new(|_req, _ctx| {
+            Box::pin(async move {
+                ToolExecutionOutput {
+                    success: true,
+                    data: serde_json::json!({ "ok": true }),
+                    error: None,
+                }
+            })
+        });
+        for (name, handler) in [("webFetch", slow), ("readFile", fast)] {
+            let definition = ToolDefinition {
+                name: name.to_string(),
+                description: String::new(),
+                parameters: serde_json::json!({}),
+                requires_approval: false,
+            };
+            registry.register(definition, handler).await.unwrap();
+        }
+        let dispatcher = ToolDispatcher::new(Arc::new(registry))
+            .with_tool_timeout("web_fetch", Some(std::time::Duration::from_millis(50)));
+
+        let started = std::time::Instant::now();
+        let results = dispatcher
+            .dispatch_batch(
+                vec![request("a", "webFetch"), request("b", "readFile")],
+                test_context(),
+                true,
+            )
+            .await
+            .unwrap();
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+
+        match &results[0] {
+            ToolDispatchResult::Completed(result) => {
+                assert!(!result.success);
+                assert_eq!(result.output["timed_out"], true);
+            }
+            other => panic!("expected completed result, got {:?}", other),
+        }
+        match &results[1] {
+            ToolDispatchResult::Completed(result) => assert!(result.success),
+            other => panic!("expected completed result, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn disallowed_tool_is_rejected() {
+        let (dispatcher, peak) = tracking_dispatcher().await;
+        let context = ToolContext {
+            allowed_tools: Some(vec!["readFile".to_string()]),
+            ..test_context()
+        };
+
+        let result = dispatcher
+            .dispatch(request("a", "write_file"), context, true)
+            .await
+            .unwrap();
+        match result {
+            ToolDispatchResult::Completed(result) => {
+                assert!(!result.success);
+                assert_eq!(result.output["reason"], "tool_not_allowed");
+                assert!(result.error.unwrap().contains("writeFile"));
+            }
+            other => panic!("expected rejection, got {:?}", other),
+        }
+        assert_eq!(peak.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn allowed_tool_executes() {
+        let (dispatcher, peak) = tracking_dispatcher().await;
+        let context = ToolContext {
+            allowed_tools: Some(vec!["read_file".to_string()]),
+            ..test_context()
+        };
+
+        let result = dispatcher
+            .dispatch(request("a", "readFile"), context, true)
+            .await
+            .unwrap();
+        match result {
+            ToolDispatchResult::Completed(result) => assert!(result.success),
+            other => panic!("expected completed result, got {:?}", other),
+        }
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn session_setting_overrides_agent_tools() {
+        let agent = Agent {
+            id: "reviewer".to_string(),
+            name: "Reviewer".to_string(),
+            model: "model".to_string(),
+            system_prompt: None,
+            tools: vec!["read_file".to_string(), "codeSearch".to_string()],
+            created_at: 0,
+            updated_at: 0,
+        };
+        assert_eq!(
+            resolve_allowed_tools(Some(&agent), &TaskSettings::default()),
+            Some(vec!["readFile".to_string(), "codeSearch".to_string()])
+        );
+
+        let mut settings = TaskSettings::default();
+        settings.extra.insert(
+            ALLOWED_TOOLS_SETTING.to_string(),
+            serde_json::json!(["glob"]),
+        );
+        assert_eq!(
+            resolve_allowed_tools(Some(&agent), &settings),
+            Some(vec!["glob".to_string()])
+        );
+
+        let unconfigured = Agent {
+            tools: vec![],
+            ..agent
+        };
+        assert_eq!(
+            resolve_allowed_tools(Some(&unconfigured), &TaskSettings::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn interactive_tools_have_no_default_timeout() {
+        assert_eq!(default_tool_timeout("askUserQuestions"), None);
+        assert!(default_tool_timeout("bash").is_some());
+        assert!(default_tool_timeout("customTool").is_some());
+    }
+
     #[tokio::test]
     async fn test_default_registry() {
         let registry = ToolRegistry::create_default().await;
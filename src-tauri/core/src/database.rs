@@ -257,6 +257,42 @@ impl Database {
         Ok(results)
     }
 
+    /// Execute write statements atomically in a single transaction.
+    /// The connection stays locked for the whole transaction, so other
+    /// writers wait once instead of interleaving with each statement.
+    pub async fn transaction(
+        &self,
+        statements: Vec<(String, Vec<serde_json::Value>)>,
+    ) -> Result<u64, String> {
+        let lock = self.conn.lock().await;
+        let conn = lock.as_ref().ok_or("Database not connected")?;
+
+        let tx = conn
+            .transaction()
+            .await
+            .map_err(|e| format!("Begin transaction error: {}", e))?;
+
+        let mut rows_affected = 0;
+        for (sql, params) in statements {
+            let libsql_params: Vec<libsql::Value> =
+                params.iter().map(json_to_libsql_value).collect();
+            match tx.execute(&sql, libsql_params).await {
+                Ok(rows) => rows_affected += rows,
+                Err(e) => {
+                    if let Err(rollback_err) = tx.rollback().await {
+                        log::error!("Rollback failed: {}", rollback_err);
+                    }
+                    return Err(format!("Execute error: {}", e));
+                }
+            }
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Commit error: {}", e))?;
+        Ok(rows_affected)
+    }
+
     /// Close the database connection gracefully
     /// This should be called when the application exits to release file handles
     #[allow(dead_code)]
@@ -638,6 +674,44 @@ mod tests {
         assert_eq!(count, &serde_json::Value::Number(3.into()));
     }
 
+    #[tokio::test]
+    async fn test_transaction_commits_and_rolls_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("transaction_test.db");
+
+        let database = Database::new(db_path.to_string_lossy().to_string());
+        database.connect().await.expect("Failed to connect");
+        database
+            .execute("CREATE TABLE items (id INTEGER PRIMARY KEY)", vec![])
+            .await
+            .expect("Failed to create table");
+
+        let insert = |id: i64| {
+            (
+                "INSERT INTO items (id) VALUES (?)".to_string(),
+                vec![serde_json::Value::Number(id.into())],
+            )
+        };
+
+        let rows = database
+            .transaction(vec![insert(1), insert(2)])
+            .await
+            .expect("Transaction should commit");
+        assert_eq!(rows, 2);
+
+        // The duplicate key fails the whole transaction, so id 3 is not kept
+        let result = database.transaction(vec![insert(3), insert(1)]).await;
+        assert!(result.is_err());
+
+        let count = database
+            .query("SELECT COUNT(*) as count FROM items", vec![])
+            .await
+            .unwrap()
+            .rows[0]["count"]
+            .clone();
+        assert_eq!(count, serde_json::Value::Number(2.into()));
+    }
+
     #[tokio::test]
     async fn test_query_with_multiple_rows() {
         // Test query returning multiple rows
@@ -1,8 +1,43 @@
 // Device ID management module
 // Provides secure device identification stored in app data directory
 
+use crate::analytics::AnalyticsState;
+use serde::Serialize;
 use std::path::Path;
-use tauri::Manager;
+use std::time::UNIX_EPOCH;
+use tauri::{Manager, State};
+
+const DEVICE_ID_FILE: &str = "device_id";
+
+/// Device identity details for the analytics privacy controls
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceIdInfo {
+    pub device_id: String,
+    /// When the current id was generated (ms since epoch), if known
+    pub created_at: Option<i64>,
+    pub analytics_enabled: bool,
+}
+
+/// Write the device ID via a temp file + rename so a crash never leaves a partial id
+fn write_device_id(app_data_dir: &Path, id: &str) -> Result<(), String> {
+    let device_id_path = app_data_dir.join(DEVICE_ID_FILE);
+    let tmp_path = app_data_dir.join(format!("{}.tmp", DEVICE_ID_FILE));
+
+    std::fs::create_dir_all(app_data_dir)
+        .map_err(|e| format!("Failed to create app data dir: {}", e))?;
+    std::fs::write(&tmp_path, id).map_err(|e| format!("Failed to write device_id: {}", e))?;
+    std::fs::rename(&tmp_path, &device_id_path).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp_path);
+        format!("Failed to persist device_id: {}", e)
+    })
+}
+
+fn read_device_id(app_data_dir: &Path) -> Option<String> {
+    let id = std::fs::read_to_string(app_data_dir.join(DEVICE_ID_FILE)).ok()?;
+    let id = id.trim().to_string();
+    (!id.is_empty()).then_some(id)
+}
 
 /// Get or create device ID (stored in app data directory)
 ///
@@ -10,35 +45,118 @@ use tauri::Manager;
 /// This provides a secure way to identify the device across sessions
 /// without relying on client-side storage like localStorage.
 pub fn get_or_create_device_id(app_data_dir: &Path) -> String {
-    let device_id_path = app_data_dir.join("device_id");
-
     // Try to read existing device ID
-    if let Ok(id) = std::fs::read_to_string(&device_id_path) {
-        let id = id.trim().to_string();
-        if !id.is_empty() {
-            return id;
-        }
+    if let Some(id) = read_device_id(app_data_dir) {
+        return id;
     }
 
     // Generate new device ID
     let new_id = uuid::Uuid::new_v4().to_string();
 
     // Save it
-    if let Err(e) = std::fs::write(&device_id_path, &new_id) {
+    if let Err(e) = write_device_id(app_data_dir, &new_id) {
         log::error!("Failed to save device_id: {}", e);
     }
 
     new_id
 }
 
+/// Replace the device ID with a freshly generated one.
+/// The old id is discarded, breaking the link to analytics sent under it.
+pub fn regenerate_device_id(app_data_dir: &Path) -> Result<String, String> {
+    let previous = read_device_id(app_data_dir);
+    let mut new_id = uuid::Uuid::new_v4().to_string();
+    while previous.as_deref() == Some(new_id.as_str()) {
+        new_id = uuid::Uuid::new_v4().to_string();
+    }
+
+    write_device_id(app_data_dir, &new_id)?;
+    log::info!("Device ID has been reset");
+    Ok(new_id)
+}
+
+/// When the current device ID was created. The file is only written on
+/// creation or reset, so its modification time is the creation time.
+pub fn device_id_created_at(app_data_dir: &Path) -> Option<i64> {
+    let modified = std::fs::metadata(app_data_dir.join(DEVICE_ID_FILE))
+        .and_then(|m| m.modified())
+        .ok()?;
+    let millis = modified.duration_since(UNIX_EPOCH).ok()?.as_millis();
+    i64::try_from(millis).ok()
+}
+
+fn app_data_dir(app_handle: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))
+}
+
 /// Tauri command to get device ID
 /// Exposes device ID functionality to TypeScript
 #[tauri::command]
 pub fn get_device_id(app_handle: tauri::AppHandle) -> Result<String, String> {
-    let app_data_dir = app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    Ok(get_or_create_device_id(&app_data_dir(&app_handle)?))
+}
+
+/// Reset the analytics identity: generate and persist a new device ID
+#[tauri::command]
+pub fn reset_device_id(
+    app_handle: tauri::AppHandle,
+    analytics: State<'_, AnalyticsState>,
+) -> Result<String, String> {
+    let new_id = regenerate_device_id(&app_data_dir(&app_handle)?)?;
+    // Drop the running session so nothing more is sent under the old id
+    analytics.clear_session();
+    Ok(new_id)
+}
+
+/// Device ID details for the analytics identity settings
+#[tauri::command]
+pub fn get_device_id_info(
+    app_handle: tauri::AppHandle,
+    analytics: State<'_, AnalyticsState>,
+) -> Result<DeviceIdInfo, String> {
+    let dir = app_data_dir(&app_handle)?;
+    let device_id = get_or_create_device_id(&dir);
+    Ok(DeviceIdInfo {
+        device_id,
+        created_at: device_id_created_at(&dir),
+        analytics_enabled: analytics.is_enabled(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn device_id_is_stable_across_reads() {
+        let dir = TempDir::new().unwrap();
+        let id = get_or_create_device_id(dir.path());
+        assert_eq!(get_or_create_device_id(dir.path()), id);
+        assert!(device_id_created_at(dir.path()).is_some());
+    }
+
+    #[test]
+    fn reset_produces_new_stable_id() {
+        let dir = TempDir::new().unwrap();
+        let original = get_or_create_device_id(dir.path());
 
-    Ok(get_or_create_device_id(&app_data_dir))
+        let reset = regenerate_device_id(dir.path()).unwrap();
+        assert_ne!(reset, original);
+        assert_eq!(get_or_create_device_id(dir.path()), reset);
+        assert_eq!(get_or_create_device_id(dir.path()), reset);
+
+        // No temp file left behind
+        assert!(!dir.path().join("device_id.tmp").exists());
+    }
+
+    #[test]
+    fn reset_without_existing_id_creates_one() {
+        let dir = TempDir::new().unwrap();
+        let id = regenerate_device_id(dir.path()).unwrap();
+        assert_eq!(get_or_create_device_id(dir.path()), id);
+    }
 }
@@ -0,0 +1,1192 @@
+use crate::integrations::commands::{
+    route_inbound, GatewayCommand, InboundAction, DEFAULT_COMMAND_PREFIX,
+};
+use crate::integrations::registry::{Integration, IntegrationStatus};
+use crate::integrations::typing::{TypingIndicator, TypingSink, DEFAULT_TYPING_REFRESH};
+use futures_util::{SinkExt, StreamExt};
+use rand::Rng;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+use tokio::sync::{watch, Mutex};
+use tokio::time::sleep;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+const DISCORD_CONFIG_FILE: &str = "discord-remote.json";
+const DISCORD_API_BASE: &str = "https://discord.com/api/v10";
+const DISCORD_GATEWAY_URL: &str = "wss://gateway.discord.gg/?v=10&encoding=json";
+const DISCORD_SESSION_PREFIX: &str = "discord";
+const DEFAULT_ERROR_BACKOFF_MS: u64 = 1500;
+const MAX_ERROR_BACKOFF_MS: u64 = 30000;
+
+/// GUILD_MESSAGES | DIRECT_MESSAGES | MESSAGE_CONTENT
+const DISCORD_INTENTS: u64 = (1 << 9) | (1 << 12) | (1 << 15);
+
+// Gateway opcodes
+const OP_DISPATCH: u8 = 0;
+const OP_HEARTBEAT: u8 = 1;
+const OP_IDENTIFY: u8 = 2;
+const OP_RECONNECT: u8 = 7;
+const OP_INVALID_SESSION: u8 = 9;
+const OP_HELLO: u8 = 10;
+const OP_HEARTBEAT_ACK: u8 = 11;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscordConfig {
+    pub enabled: bool,
+    pub token: String,
+    /// Channel (or DM channel) snowflakes allowed to talk to the bot
+    #[serde(default)]
+    pub allowed_channel_ids: Vec<String>,
+    #[serde(default)]
+    pub allowed_user_ids: Vec<String>,
+    /// Empty allowlists only admit everyone when this is set explicitly
+    #[serde(default)]
+    pub allow_all: bool,
+    /// Optional reply sent to senders outside the allowlist
+    #[serde(default)]
+    pub denial_message: Option<String>,
+    /// Prefix for control commands such as `/new`
+    #[serde(default)]
+    pub command_prefix: String,
+}
+
+impl Default for DiscordConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            token: String::new(),
+            allowed_channel_ids: Vec::new(),
+            allowed_user_ids: Vec::new(),
+            allow_all: false,
+            denial_message: None,
+            command_prefix: DEFAULT_COMMAND_PREFIX.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscordInboundMessage {
+    pub channel_id: String,
+    pub guild_id: Option<String>,
+    /// True for direct messages, false for guild channels
+    pub is_dm: bool,
+    /// Stable session key; one session per Discord channel
+    pub session_key: String,
+    pub message_id: String,
+    pub text: String,
+    pub user_id: String,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscordInboundCommand {
+    pub channel_id: String,
+    pub session_key: String,
+    pub message_id: String,
+    pub command: GatewayCommand,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscordSendMessageRequest {
+    pub channel_id: String,
+    pub text: String,
+    pub reply_to_message_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscordSendMessageResponse {
+    pub message_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscordEditMessageRequest {
+    pub channel_id: String,
+    pub message_id: String,
+    pub text: String,
+}
+
+#[derive(Debug)]
+pub struct DiscordGateway {
+    config: DiscordConfig,
+    running: bool,
+    connected: bool,
+    bot_user_id: Option<String>,
+    stop_tx: Option<watch::Sender<bool>>,
+    last_event_at_ms: Option<i64>,
+    last_error: Option<String>,
+    last_error_at_ms: Option<i64>,
+    backoff_ms: u64,
+    typing: HashMap<String, TypingIndicator>,
+}
+
+impl Default for DiscordGateway {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DiscordGateway {
+    pub fn new() -> Self {
+        Self {
+            config: DiscordConfig::default(),
+            running: false,
+            connected: false,
+            bot_user_id: None,
+            stop_tx: None,
+            last_event_at_ms: None,
+            last_error: None,
+            last_error_at_ms: None,
+            backoff_ms: DEFAULT_ERROR_BACKOFF_MS,
+            typing: HashMap::new(),
+        }
+    }
+}
+
+pub type DiscordGatewayState = Arc<Mutex<DiscordGateway>>;
+
+#[derive(Debug, Deserialize)]
+struct GatewayPayload {
+    op: u8,
+    #[serde(default)]
+    d: serde_json::Value,
+    s: Option<u64>,
+    t: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct DiscordUser {
+    id: String,
+    username: String,
+    global_name: Option<String>,
+    #[serde(default)]
+    bot: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct DiscordMessage {
+    id: String,
+    channel_id: String,
+    guild_id: Option<String>,
+    #[serde(default)]
+    content: String,
+    author: DiscordUser,
+    #[serde(default)]
+    timestamp: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DiscordReady {
+    user: DiscordUser,
+}
+
+#[derive(Debug, Deserialize)]
+struct DiscordMessageResponse {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DiscordApiError {
+    message: Option<String>,
+    retry_after: Option<f64>,
+}
+
+fn config_path<R: Runtime>(app_handle: &AppHandle<R>) -> Result<PathBuf, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    Ok(app_data_dir.join(DISCORD_CONFIG_FILE))
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn record_error_state(state: &mut DiscordGateway, message: impl Into<String>) {
+    state.last_error = Some(message.into());
+    state.last_error_at_ms = Some(now_ms());
+}
+
+fn clear_error_state(state: &mut DiscordGateway) {
+    state.last_error = None;
+    state.last_error_at_ms = None;
+    state.backoff_ms = DEFAULT_ERROR_BACKOFF_MS;
+}
+
+fn compute_backoff_ms(current: u64) -> u64 {
+    let jitter = rand::thread_rng().gen_range(0..250u64);
+    let next = current.saturating_mul(2).saturating_add(jitter);
+    next.clamp(DEFAULT_ERROR_BACKOFF_MS, MAX_ERROR_BACKOFF_MS)
+}
+
+pub async fn load_config<R: Runtime>(app_handle: &AppHandle<R>) -> Result<DiscordConfig, String> {
+    let path = config_path(app_handle)?;
+    if !path.exists() {
+        return Ok(DiscordConfig::default());
+    }
+    let content = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read discord config: {}", e))?;
+    let mut parsed = serde_json::from_str::<DiscordConfig>(&content)
+        .map_err(|e| format!("Failed to parse discord config: {}", e))?;
+    sanitize_ids(&mut parsed.allowed_channel_ids);
+    sanitize_ids(&mut parsed.allowed_user_ids);
+    Ok(parsed)
+}
+
+pub async fn save_config<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    config: &DiscordConfig,
+) -> Result<(), String> {
+    let path = config_path(app_handle)?;
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create config dir: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize discord config: {}", e))?;
+    tokio::fs::write(&path, content)
+        .await
+        .map_err(|e| format!("Failed to write discord config: {}", e))?;
+    Ok(())
+}
+
+/// Trim ids and drop blanks; snowflakes are kept as strings to avoid JS precision loss
+fn sanitize_ids(ids: &mut Vec<String>) {
+    for id in ids.iter_mut() {
+        *id = id.trim().to_string();
+    }
+    ids.retain(|id| !id.is_empty());
+}
+
+fn is_sender_allowed(config: &DiscordConfig, channel_id: &str, user_id: &str) -> bool {
+    if config.allowed_channel_ids.is_empty() && config.allowed_user_ids.is_empty() {
+        return config.allow_all;
+    }
+    config.allowed_channel_ids.iter().any(|id| id == channel_id)
+        || config.allowed_user_ids.iter().any(|id| id == user_id)
+}
+
+fn denial_text(config: &DiscordConfig) -> Option<&str> {
+    config
+        .denial_message
+        .as_deref()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+}
+
+/// Session key for a Discord channel. DMs have their own channel id, so this
+/// covers both contexts.
+pub fn session_key(channel_id: &str) -> String {
+    format!("{}:{}", DISCORD_SESSION_PREFIX, channel_id)
+}
+
+/// Messages from bots (including ourselves) are never routed
+fn is_own_or_bot_message(message: &DiscordMessage, bot_user_id: Option<&str>) -> bool {
+    message.author.bot || bot_user_id.is_some_and(|id| id == message.author.id)
+}
+
+fn build_identify_payload(token: &str) -> serde_json::Value {
+    serde_json::json!({
+        "op": OP_IDENTIFY,
+        "d": {
+            "token": token,
+            "intents": DISCORD_INTENTS,
+            "properties": {
+                "os": std::env::consts::OS,
+                "browser": "talkcody",
+                "device": "talkcody",
+            },
+        },
+    })
+}
+
+fn build_heartbeat_payload(sequence: Option<u64>) -> serde_json::Value {
+    serde_json::json!({ "op": OP_HEARTBEAT, "d": sequence })
+}
+
+/// Tracks whether the last heartbeat was acknowledged. A connection that
+/// stops acknowledging is zombied and has to be replaced.
+#[derive(Debug, Default)]
+struct HeartbeatAcks {
+    pending: bool,
+}
+
+impl HeartbeatAcks {
+    /// Record a scheduled heartbeat; false when the previous one was never acknowledged
+    fn send(&mut self) -> bool {
+        !std::mem::replace(&mut self.pending, true)
+    }
+
+    fn ack(&mut self) {
+        self.pending = false;
+    }
+}
+
+fn build_http_client() -> Result<Client, String> {
+    crate::network_proxy::client_builder()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .map_err(|e| format!("Failed to build http client: {}", e))
+}
+
+/// Turn a non-success REST response into an error string
+async fn check_response(response: reqwest::Response, action: &str) -> Result<String, String> {
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read {} response: {}", action, e))?;
+    if status.is_success() {
+        return Ok(body);
+    }
+    let detail = serde_json::from_str::<DiscordApiError>(&body).ok();
+    let message = detail
+        .as_ref()
+        .and_then(|error| error.message.clone())
+        .unwrap_or_else(|| body.clone());
+    match detail.and_then(|error| error.retry_after) {
+        Some(retry_after) => Err(format!(
+            "Discord {} failed ({}): {} (retry after {:.1}s)",
+            action, status, message, retry_after
+        )),
+        None => Err(format!(
+            "Discord {} failed ({}): {}",
+            action, status, message
+        )),
+    }
+}
+
+async fn create_message(
+    client: &Client,
+    token: &str,
+    channel_id: &str,
+    text: &str,
+    reply_to_message_id: Option<&str>,
+) -> Result<String, String> {
+    let url = format!("{}/channels/{}/messages", DISCORD_API_BASE, channel_id);
+    let mut payload = serde_json::json!({ "content": text });
+    if let Some(message_id) = reply_to_message_id {
+        payload.as_object_mut().unwrap().insert(
+            "message_reference".to_string(),
+            serde_json::json!({ "message_id": message_id, "fail_if_not_exists": false }),
+        );
+    }
+    let response = client
+        .post(&url)
+        .header("Authorization", format!("Bot {}", token))
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| format!("Discord createMessage failed: {}", e))?;
+    let body = check_response(response, "createMessage").await?;
+    let parsed = serde_json::from_str::<DiscordMessageResponse>(&body)
+        .map_err(|e| format!("Failed to parse createMessage response: {}", e))?;
+    Ok(parsed.id)
+}
+
+async fn handle_message(
+    app_handle: &AppHandle,
+    gateway_state: &DiscordGatewayState,
+    client: &Client,
+    message: DiscordMessage,
+) {
+    let (config, bot_user_id) = {
+        let state = gateway_state.lock().await;
+        (state.config.clone(), state.bot_user_id.clone())
+    };
+
+    if is_own_or_bot_message(&message, bot_user_id.as_deref()) {
+        return;
+    }
+
+    if !is_sender_allowed(&config, &message.channel_id, &message.author.id) {
+        log::debug!(
+            "[DiscordGateway] Sender not in allowlist channel_id={} user_id={} (channels={}, users={})",
+            message.channel_id,
+            message.author.id,
+            config.allowed_channel_ids.len(),
+            config.allowed_user_ids.len()
+        );
+        if let Some(text) = denial_text(&config) {
+            if let Err(error) =
+                create_message(client, &config.token, &message.channel_id, text, None).await
+            {
+                log::warn!("[DiscordGateway] Failed to send denial reply: {}", error);
+            }
+        }
+        return;
+    }
+
+    if let InboundAction::Dispatch(command) =
+        route_inbound(&message.content, &config.command_prefix)
+    {
+        log::debug!(
+            "[DiscordGateway] Dispatching command channel_id={} command={:?}",
+            message.channel_id,
+            command
+        );
+        if command == GatewayCommand::Stop {
+            stop_typing(gateway_state, &message.channel_id).await;
+        }
+        let payload = DiscordInboundCommand {
+            session_key: session_key(&message.channel_id),
+            channel_id: message.channel_id,
+            message_id: message.id,
+            command,
+        };
+        if let Err(error) = app_handle.emit("discord-inbound-command", payload) {
+            log::error!("[DiscordGateway] Failed to emit command: {}", error);
+        }
+        return;
+    }
+
+    if message.content.trim().is_empty() {
+        log::debug!(
+            "[DiscordGateway] Ignoring empty message channel_id={} message_id={}",
+            message.channel_id,
+            message.id
+        );
+        return;
+    }
+
+    log::debug!(
+        "[DiscordGateway] Inbound message channel_id={} message_id={} text_len={}",
+        message.channel_id,
+        message.id,
+        message.content.len()
+    );
+
+    let payload = DiscordInboundMessage {
+        session_key: session_key(&message.channel_id),
+        is_dm: message.guild_id.is_none(),
+        channel_id: message.channel_id,
+        guild_id: message.guild_id,
+        message_id: message.id,
+        text: message.content,
+        user_id: message.author.id,
+        username: message.author.username,
+        display_name: message.author.global_name,
+        timestamp: message.timestamp,
+    };
+    if let Err(error) = app_handle.emit("discord-inbound-message", payload) {
+        log::error!("[DiscordGateway] Failed to emit message: {}", error);
+    }
+}
+
+/// Run one gateway connection until it drops, is stopped, or Discord asks us
+/// to reconnect. Returns Ok(()) when the loop should exit for good.
+async fn run_connection(
+    app_handle: &AppHandle,
+    gateway_state: &DiscordGatewayState,
+    client: &Client,
+    token: &str,
+    stop_rx: &mut watch::Receiver<bool>,
+) -> Result<(), String> {
+    let (ws_stream, _) = connect_async(DISCORD_GATEWAY_URL)
+        .await
+        .map_err(|e| format!("Failed to connect to Discord gateway: {}", e))?;
+    let (mut write, mut read) = ws_stream.split();
+
+    let mut sequence: Option<u64> = None;
+    let mut heartbeat: Option<tokio::time::Interval> = None;
+    let mut acks = HeartbeatAcks::default();
+
+    loop {
+        tokio::select! {
+            _ = stop_rx.changed() => {
+                if *stop_rx.borrow() {
+                    let _ = write.send(Message::Close(None)).await;
+                    return Ok(());
+                }
+            }
+            _ = async {
+                match heartbeat.as_mut() {
+                    Some(interval) => {
+                        interval.tick().await;
+                    }
+                    None => std::future::pending::<()>().await,
+                }
+            } => {
+                if !acks.send() {
+                    let _ = write.send(Message::Close(None)).await;
+                    return Err("Discord stopped acknowledging heartbeats".to_string());
+                }
+                write
+                    .send(Message::Text(build_heartbeat_payload(sequence).to_string()))
+                    .await
+                    .map_err(|e| format!("Failed to send heartbeat: {}", e))?;
+            }
+            frame = read.next() => {
+                let text = match frame {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(frame))) => {
+                        return Err(format!("Discord gateway closed: {:?}", frame));
+                    }
+                    Some(Ok(_)) => continue,
+                    Some(Err(error)) => {
+                        return Err(format!("Discord gateway error: {}", error));
+                    }
+                    None => return Err("Discord gateway stream ended".to_string()),
+                };
+
+                let payload = match serde_json::from_str::<GatewayPayload>(&text) {
+                    Ok(payload) => payload,
+                    Err(error) => {
+                        log::warn!("[DiscordGateway] Failed to parse gateway payload: {}", error);
+                        continue;
+                    }
+                };
+                if payload.s.is_some() {
+                    sequence = payload.s;
+                }
+
+                match payload.op {
+                    OP_HELLO => {
+                        let interval_ms = payload.d["heartbeat_interval"].as_u64().unwrap_or(41250);
+                        let mut interval = tokio::time::interval(Duration::from_millis(interval_ms));
+                        // The first tick completes immediately; skip it so heartbeats follow IDENTIFY
+                        interval.tick().await;
+                        heartbeat = Some(interval);
+                        write
+                            .send(Message::Text(build_identify_payload(token).to_string()))
+                            .await
+                            .map_err(|e| format!("Failed to send identify: {}", e))?;
+                    }
+                    OP_HEARTBEAT => {
+                        write
+                            .send(Message::Text(build_heartbeat_payload(sequence).to_string()))
+                            .await
+                            .map_err(|e| format!("Failed to send heartbeat: {}", e))?;
+                    }
+                    OP_HEARTBEAT_ACK => acks.ack(),
+                    OP_RECONNECT => {
+                        return Err("Discord requested a reconnect".to_string());
+                    }
+                    OP_INVALID_SESSION => {
+                        return Err("Discord invalidated the session".to_string());
+                    }
+                    OP_DISPATCH => {
+                        {
+                            let mut state = gateway_state.lock().await;
+                            state.last_event_at_ms = Some(now_ms());
+                        }
+                        match payload.t.as_deref() {
+                            Some("READY") => {
+                                match serde_json::from_value::<DiscordReady>(payload.d) {
+                                    Ok(ready) => {
+                                        log::info!(
+                                            "[DiscordGateway] Connected as {} ({})",
+                                            ready.user.username,
+                                            ready.user.id
+                                        );
+                                        let mut state = gateway_state.lock().await;
+                                        state.bot_user_id = Some(ready.user.id);
+                                        state.connected = true;
+                                        clear_error_state(&mut state);
+                                    }
+                                    Err(error) => {
+                                        log::warn!("[DiscordGateway] Failed to parse READY: {}", error);
+                                    }
+                                }
+                            }
+                            Some("MESSAGE_CREATE") => {
+                                match serde_json::from_value::<DiscordMessage>(payload.d) {
+                                    Ok(message) => {
+                                        handle_message(app_handle, gateway_state, client, message)
+                                            .await;
+                                    }
+                                    Err(error) => {
+                                        log::warn!(
+                                            "[DiscordGateway] Failed to parse MESSAGE_CREATE: {}",
+                                            error
+                                        );
+                                    }
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+async fn gateway_loop(
+    app_handle: AppHandle,
+    gateway_state: DiscordGatewayState,
+    mut stop_rx: watch::Receiver<bool>,
+) {
+    let client = match build_http_client() {
+        Ok(client) => client,
+        Err(error) => {
+            log::error!("[DiscordGateway] {}", error);
+            return;
+        }
+    };
+
+    log::info!("[DiscordGateway] Gateway loop started");
+
+    loop {
+        if *stop_rx.borrow() {
+            break;
+        }
+
+        let config = {
+            let state = gateway_state.lock().await;
+            state.config.clone()
+        };
+
+        if !config.enabled || config.token.is_empty() {
+            sleep(Duration::from_millis(DEFAULT_ERROR_BACKOFF_MS)).await;
+            continue;
+        }
+
+        let result = run_connection(
+            &app_handle,
+            &gateway_state,
+            &client,
+            &config.token,
+            &mut stop_rx,
+        )
+        .await;
+
+        let backoff_ms = {
+            let mut state = gateway_state.lock().await;
+            state.connected = false;
+            match &result {
+                Ok(()) => break,
+                Err(error) => {
+                    log::warn!("[DiscordGateway] Connection ended: {}", error);
+                    record_error_state(&mut state, error.clone());
+                    let backoff_ms = state.backoff_ms;
+                    state.backoff_ms = compute_backoff_ms(backoff_ms);
+                    backoff_ms
+                }
+            }
+        };
+
+        tokio::select! {
+            _ = sleep(Duration::from_millis(backoff_ms)) => {}
+            _ = stop_rx.changed() => {}
+        }
+    }
+
+    log::info!("[DiscordGateway] Gateway loop stopped");
+}
+
+#[tauri::command]
+pub async fn discord_get_config(
+    app_handle: AppHandle,
+    state: State<'_, DiscordGatewayState>,
+) -> Result<DiscordConfig, String> {
+    let config = load_config(&app_handle).await?;
+    let mut gateway = state.lock().await;
+    gateway.config = config.clone();
+    Ok(config)
+}
+
+#[tauri::command]
+pub async fn discord_set_config(
+    app_handle: AppHandle,
+    state: State<'_, DiscordGatewayState>,
+    mut config: DiscordConfig,
+) -> Result<(), String> {
+    sanitize_ids(&mut config.allowed_channel_ids);
+    sanitize_ids(&mut config.allowed_user_ids);
+    save_config(&app_handle, &config).await?;
+    let mut gateway = state.lock().await;
+    gateway.config = config.clone();
+    drop(gateway);
+
+    if config.enabled && !config.token.is_empty() {
+        log::info!(
+            "[DiscordGateway] Config updated (enabled={}, allowed_channel_ids={}, allowed_user_ids={}, allow_all={})",
+            config.enabled,
+            config.allowed_channel_ids.len(),
+            config.allowed_user_ids.len(),
+            config.allow_all
+        );
+        let _ = start_gateway(app_handle, state.inner().clone()).await;
+    }
+
+    Ok(())
+}
+
+pub async fn start_gateway(
+    app_handle: AppHandle,
+    state: DiscordGatewayState,
+) -> Result<(), String> {
+    let (config, running) = {
+        let gateway = state.lock().await;
+        (gateway.config.clone(), gateway.running)
+    };
+
+    if running {
+        log::info!("[DiscordGateway] Start requested but already running");
+        return Ok(());
+    }
+
+    if config.token.is_empty() {
+        return Err("Discord bot token is not configured".to_string());
+    }
+
+    log::info!(
+        "[DiscordGateway] Starting gateway (allowed_channel_ids={})",
+        config.allowed_channel_ids.len()
+    );
+
+    let (stop_tx, stop_rx) = watch::channel(false);
+    {
+        let mut gateway = state.lock().await;
+        gateway.running = true;
+        gateway.connected = false;
+        gateway.stop_tx = Some(stop_tx);
+        gateway.last_event_at_ms = None;
+        gateway.last_error = None;
+        gateway.last_error_at_ms = None;
+        gateway.backoff_ms = DEFAULT_ERROR_BACKOFF_MS;
+    }
+
+    let state_clone = state.clone();
+    tauri::async_runtime::spawn(async move {
+        gateway_loop(app_handle, state_clone, stop_rx).await;
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn discord_start(
+    app_handle: AppHandle,
+    state: State<'_, DiscordGatewayState>,
+) -> Result<(), String> {
+    start_gateway(app_handle, state.inner().clone()).await
+}
+
+#[tauri::command]
+pub async fn discord_stop(state: State<'_, DiscordGatewayState>) -> Result<(), String> {
+    stop_gateway(state.inner()).await;
+    Ok(())
+}
+
+async fn stop_gateway(state: &DiscordGatewayState) {
+    let indicators: Vec<TypingIndicator> = {
+        let mut gateway = state.lock().await;
+        if let Some(stop_tx) = gateway.stop_tx.take() {
+            let _ = stop_tx.send(true);
+        }
+        gateway.running = false;
+        gateway.connected = false;
+        gateway
+            .typing
+            .drain()
+            .map(|(_, indicator)| indicator)
+            .collect()
+    };
+    for mut indicator in indicators {
+        indicator.stop().await;
+    }
+    log::info!("[DiscordGateway] Stop requested");
+}
+
+/// Discord gateway exposed through the integration registry
+pub struct DiscordIntegration {
+    app_handle: AppHandle,
+    state: DiscordGatewayState,
+}
+
+impl DiscordIntegration {
+    pub fn new(app_handle: AppHandle, state: DiscordGatewayState) -> Self {
+        Self { app_handle, state }
+    }
+}
+
+#[async_trait::async_trait]
+impl Integration for DiscordIntegration {
+    fn id(&self) -> &str {
+        "discord"
+    }
+
+    async fn start(&self) -> Result<(), String> {
+        start_gateway(self.app_handle.clone(), self.state.clone()).await
+    }
+
+    async fn stop(&self) -> Result<(), String> {
+        stop_gateway(&self.state).await;
+        Ok(())
+    }
+
+    async fn status(&self) -> IntegrationStatus {
+        let gateway = self.state.lock().await;
+        IntegrationStatus {
+            id: "discord".to_string(),
+            running: gateway.running,
+            last_error: gateway.last_error.clone(),
+            last_error_at_ms: gateway.last_error_at_ms,
+            last_activity_at_ms: gateway.last_event_at_ms,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscordGatewayStatus {
+    pub running: bool,
+    pub connected: bool,
+    pub bot_user_id: Option<String>,
+    pub last_event_at_ms: Option<i64>,
+    pub last_error: Option<String>,
+    pub last_error_at_ms: Option<i64>,
+    pub backoff_ms: u64,
+}
+
+#[tauri::command]
+pub async fn discord_get_status(
+    state: State<'_, DiscordGatewayState>,
+) -> Result<DiscordGatewayStatus, String> {
+    let gateway = state.lock().await;
+    Ok(DiscordGatewayStatus {
+        running: gateway.running,
+        connected: gateway.connected,
+        bot_user_id: gateway.bot_user_id.clone(),
+        last_event_at_ms: gateway.last_event_at_ms,
+        last_error: gateway.last_error.clone(),
+        last_error_at_ms: gateway.last_error_at_ms,
+        backoff_ms: gateway.backoff_ms,
+    })
+}
+
+#[tauri::command]
+pub async fn discord_is_running(state: State<'_, DiscordGatewayState>) -> Result<bool, String> {
+    let gateway = state.lock().await;
+    Ok(gateway.running)
+}
+
+#[tauri::command]
+pub async fn discord_send_message(
+    state: State<'_, DiscordGatewayState>,
+    request: DiscordSendMessageRequest,
+) -> Result<DiscordSendMessageResponse, String> {
+    let config = {
+        let gateway = state.lock().await;
+        gateway.config.clone()
+    };
+
+    if config.token.is_empty() {
+        return Err("Discord bot token is not configured".to_string());
+    }
+
+    // First real content replaces the typing indicator
+    stop_typing(state.inner(), &request.channel_id).await;
+
+    log::debug!(
+        "[DiscordGateway] createMessage channel_id={} text_len={} reply_to={:?}",
+        request.channel_id,
+        request.text.len(),
+        request.reply_to_message_id
+    );
+    let client = build_http_client()?;
+    let message_id = create_message(
+        &client,
+        &config.token,
+        &request.channel_id,
+        &request.text,
+        request.reply_to_message_id.as_deref(),
+    )
+    .await?;
+
+    Ok(DiscordSendMessageResponse { message_id })
+}
+
+#[tauri::command]
+pub async fn discord_edit_message(
+    state: State<'_, DiscordGatewayState>,
+    request: DiscordEditMessageRequest,
+) -> Result<(), String> {
+    let config = {
+        let gateway = state.lock().await;
+        gateway.config.clone()
+    };
+
+    if config.token.is_empty() {
+        return Err("Discord bot token is not configured".to_string());
+    }
+
+    log::debug!(
+        "[DiscordGateway] editMessage channel_id={} message_id={} text_len={}",
+        request.channel_id,
+        request.message_id,
+        request.text.len()
+    );
+    let client = build_http_client()?;
+    let url = format!(
+        "{}/channels/{}/messages/{}",
+        DISCORD_API_BASE, request.channel_id, request.message_id
+    );
+    let response = client
+        .patch(&url)
+        .header("Authorization", format!("Bot {}", config.token))
+        .json(&serde_json::json!({ "content": request.text }))
+        .send()
+        .await
+        .map_err(|e| format!("Discord editMessage failed: {}", e))?;
+    check_response(response, "editMessage").await?;
+
+    Ok(())
+}
+
+struct DiscordTypingSink {
+    client: Client,
+    token: String,
+    channel_id: String,
+}
+
+#[async_trait::async_trait]
+impl TypingSink for DiscordTypingSink {
+    async fn send_typing(&self) -> Result<(), String> {
+        let url = format!("{}/channels/{}/typing", DISCORD_API_BASE, self.channel_id);
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bot {}", self.token))
+            .send()
+            .await
+            .map_err(|e| format!("Discord triggerTyping failed: {}", e))?;
+        check_response(response, "triggerTyping").await?;
+        Ok(())
+    }
+}
+
+async fn stop_typing(state: &DiscordGatewayState, channel_id: &str) {
+    let indicator = {
+        let mut gateway = state.lock().await;
+        gateway.typing.remove(channel_id)
+    };
+    if let Some(mut indicator) = indicator {
+        indicator.stop().await;
+        log::debug!("[DiscordGateway] Typing stopped channel_id={}", channel_id);
+    }
+}
+
+/// Show `typing` in the channel until the first message is sent or typing is stopped
+#[tauri::command]
+pub async fn discord_start_typing(
+    state: State<'_, DiscordGatewayState>,
+    channel_id: String,
+) -> Result<(), String> {
+    let mut gateway = state.lock().await;
+    if gateway.config.token.is_empty() {
+        return Err("Discord bot token is not configured".to_string());
+    }
+    if gateway
+        .typing
+        .get(&channel_id)
+        .is_some_and(|indicator| indicator.is_active())
+    {
+        return Ok(());
+    }
+
+    let sink = DiscordTypingSink {
+        client: build_http_client()?,
+        token: gateway.config.token.clone(),
+        channel_id: channel_id.clone(),
+    };
+    log::debug!("[DiscordGateway] Typing started channel_id={}", channel_id);
+    // Discord clears typing after ~10 seconds, so the shared cadence is plenty
+    gateway.typing.insert(
+        channel_id,
+        TypingIndicator::start(Arc::new(sink), DEFAULT_TYPING_REFRESH),
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn discord_stop_typing(
+    state: State<'_, DiscordGatewayState>,
+    channel_id: String,
+) -> Result<(), String> {
+    stop_typing(state.inner(), &channel_id).await;
+    Ok(())
+}
+
+pub fn default_state() -> DiscordGatewayState {
+    Arc::new(Mutex::new(DiscordGateway::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        build_heartbeat_payload, build_identify_payload, denial_text, is_own_or_bot_message,
+        is_sender_allowed, load_config, sanitize_ids, save_config, session_key, DiscordConfig,
+        DiscordMessage, HeartbeatAcks, DISCORD_INTENTS,
+    };
+    use tauri::test::mock_app;
+
+    fn message(channel_id: &str, guild_id: Option<&str>, author_id: &str) -> DiscordMessage {
+        serde_json::from_value(serde_json::json!({
+            "id": "900",
+            "channel_id": channel_id,
+            "guild_id": guild_id,
+            "content": "hello",
+            "author": { "id": author_id, "username": "alice", "global_name": "Alice" },
+            "timestamp": "2024-01-01T00:00:00.000000+00:00",
+        }))
+        .unwrap()
+    }
+
+    /// This test uses Tauri test infrastructure that may not work on Windows CI
+    #[tokio::test]
+    #[cfg(not(target_os = "windows"))]
+    async fn config_round_trips_through_disk() {
+        let app = mock_app();
+        let config = DiscordConfig {
+            enabled: true,
+            token: "bot-token".to_string(),
+            allowed_channel_ids: vec!["111".to_string(), " 222 ".to_string(), "".to_string()],
+            allowed_user_ids: vec!["333".to_string()],
+            allow_all: false,
+            denial_message: Some("Not allowed".to_string()),
+            command_prefix: "!".to_string(),
+        };
+
+        save_config(&app.handle(), &config)
+            .await
+            .expect("save_config should succeed");
+        let reloaded = load_config(&app.handle())
+            .await
+            .expect("load_config should succeed");
+
+        assert_eq!(
+            reloaded,
+            DiscordConfig {
+                allowed_channel_ids: vec!["111".to_string(), "222".to_string()],
+                ..config
+            }
+        );
+    }
+
+    #[test]
+    fn config_serializes_camel_case_with_defaults() {
+        let value = serde_json::to_value(DiscordConfig::default()).unwrap();
+        assert_eq!(value["allowedChannelIds"], serde_json::json!([]));
+        assert_eq!(value["commandPrefix"], "/");
+
+        let parsed: DiscordConfig =
+            serde_json::from_str(r#"{"enabled":true,"token":"t"}"#).unwrap();
+        assert!(parsed.enabled);
+        assert!(parsed.allowed_channel_ids.is_empty());
+        assert!(!parsed.allow_all);
+    }
+
+    #[test]
+    fn sanitize_ids_trims_and_drops_blanks() {
+        let mut ids = vec![" 1 ".to_string(), "".to_string(), "2".to_string()];
+        sanitize_ids(&mut ids);
+        assert_eq!(ids, vec!["1".to_string(), "2".to_string()]);
+    }
+
+    #[test]
+    fn non_allowlisted_channel_is_ignored() {
+        let config = DiscordConfig {
+            allowed_channel_ids: vec!["100".to_string()],
+            ..DiscordConfig::default()
+        };
+        assert!(!is_sender_allowed(&config, "200", "7"));
+        assert!(is_sender_allowed(&config, "100", "7"));
+    }
+
+    #[test]
+    fn allowlisted_user_passes_in_any_channel() {
+        let config = DiscordConfig {
+            allowed_channel_ids: vec!["100".to_string()],
+            allowed_user_ids: vec!["7".to_string()],
+            ..DiscordConfig::default()
+        };
+        assert!(is_sender_allowed(&config, "200", "7"));
+        assert!(!is_sender_allowed(&config, "200", "8"));
+    }
+
+    #[test]
+    fn empty_allowlist_denies_unless_allow_all() {
+        let mut config = DiscordConfig::default();
+        assert!(!is_sender_allowed(&config, "100", "7"));
+
+        config.allow_all = true;
+        assert!(is_sender_allowed(&config, "100", "7"));
+    }
+
+    #[test]
+    fn denial_text_skips_blank_message() {
+        let mut config = DiscordConfig::default();
+        assert_eq!(denial_text(&config), None);
+        config.denial_message = Some("   ".to_string());
+        assert_eq!(denial_text(&config), None);
+        config.denial_message = Some(" Go away ".to_string());
+        assert_eq!(denial_text(&config), Some("Go away"));
+    }
+
+    #[test]
+    fn missed_heartbeat_ack_is_detected() {
+        let mut acks = HeartbeatAcks::default();
+        assert!(acks.send());
+        acks.ack();
+        assert!(acks.send());
+        // No ACK since the last heartbeat: the connection is zombied
+        assert!(!acks.send());
+    }
+
+    #[test]
+    fn bot_and_own_messages_are_skipped() {
+        let from_user = message("100", Some("1"), "7");
+        assert!(!is_own_or_bot_message(&from_user, Some("42")));
+        assert!(is_own_or_bot_message(
+            &message("100", None, "42"),
+            Some("42")
+        ));
+
+        let mut from_bot = message("100", None, "8");
+        from_bot.author.bot = true;
+        assert!(is_own_or_bot_message(&from_bot, None));
+    }
+
+    #[test]
+    fn channels_and_dms_map_to_per_channel_sessions() {
+        let guild = message("100", Some("1"), "7");
+        let dm = message("555", None, "7");
+        assert!(guild.guild_id.is_some());
+        assert!(dm.guild_id.is_none());
+        assert_eq!(session_key(&guild.channel_id), "discord:100");
+        assert_eq!(session_key(&dm.channel_id), "discord:555");
+    }
+
+    #[test]
+    fn gateway_payloads_have_expected_shape() {
+        let identify = build_identify_payload("secret");
+        assert_eq!(identify["op"], 2);
+        assert_eq!(identify["d"]["token"], "secret");
+        assert_eq!(identify["d"]["intents"], DISCORD_INTENTS);
+
+        assert_eq!(build_heartbeat_payload(None)["d"], serde_json::Value::Null);
+        assert_eq!(build_heartbeat_payload(Some(5))["d"], 5);
+    }
+}
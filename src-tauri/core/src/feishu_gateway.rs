@@ -1,3 +1,9 @@
+use crate::integrations::commands::{route_inbound, GatewayCommand, InboundAction};
+use crate::integrations::downloads::GatewayDownloads;
+use crate::integrations::registry::{Integration, IntegrationStatus};
+use crate::integrations::typing::{TypingIndicator, TypingSink, DEFAULT_TYPING_REFRESH};
+use crate::storage::image_metadata::{self, ImageKind};
+use crate::storage::Storage;
 use open_lark::client::ws_client::LarkWsClient;
 use open_lark::prelude::{
     AppType, CreateMessageRequest, CreateMessageRequestBody, EventDispatcherHandler, LarkClient,
@@ -6,7 +12,7 @@ use open_lark::service::im::v1::message::UpdateMessageRequest;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::path::PathBuf;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -26,6 +32,8 @@ const FEISHU_MEDIA_PREFIX: &str = "feishu";
 const DEFAULT_ERROR_BACKOFF_MS: u64 = 1500;
 const MAX_ERROR_BACKOFF_MS: u64 = 30000;
 const MAX_FEISHU_MEDIA_BYTES: u64 = 20 * 1024 * 1024;
+// Feishu bots have no chat action API; a "Typing" reaction on the inbound message is the equivalent
+const FEISHU_TYPING_EMOJI: &str = "Typing";
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -35,7 +43,20 @@ pub struct FeishuConfig {
     pub app_secret: String,
     pub encrypt_key: String,
     pub verification_token: String,
+    /// User allowlist (Feishu open ids)
+    #[serde(alias = "allowedUserIds")]
     pub allowed_open_ids: Vec<String>,
+    #[serde(default)]
+    pub allowed_chat_ids: Vec<String>,
+    /// Empty allowlists only admit everyone when this is set explicitly
+    #[serde(default)]
+    pub allow_all: bool,
+    /// Optional reply sent to senders outside the allowlist
+    #[serde(default)]
+    pub denial_message: Option<String>,
+    /// Prefix for control commands such as `/new`; empty means `/`
+    #[serde(default)]
+    pub command_prefix: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,6 +83,15 @@ pub struct FeishuInboundMessage {
     pub attachments: Option<Vec<FeishuRemoteAttachment>>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeishuInboundCommand {
+    pub chat_id: String,
+    pub message_id: String,
+    pub open_id: String,
+    pub command: GatewayCommand,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FeishuSendMessageRequest {
@@ -91,6 +121,7 @@ pub struct FeishuGateway {
     last_error_at_ms: Option<i64>,
     backoff_ms: u64,
     stop_tx: Option<watch::Sender<bool>>,
+    typing: HashMap<String, TypingIndicator>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -115,11 +146,12 @@ impl FeishuGateway {
             last_error_at_ms: None,
             backoff_ms: DEFAULT_ERROR_BACKOFF_MS,
             stop_tx: None,
+            typing: HashMap::new(),
         }
     }
 }
 
-type FeishuGatewayState = Arc<Mutex<FeishuGateway>>;
+pub type FeishuGatewayState = Arc<Mutex<FeishuGateway>>;
 
 fn now_ms() -> i64 {
     SystemTime::now()
@@ -159,12 +191,51 @@ fn build_client(config: &FeishuConfig) -> Result<LarkClient, String> {
 }
 
 fn is_open_id_allowed(allowed_open_ids: &[String], open_id: &str) -> bool {
-    if allowed_open_ids.is_empty() {
-        return true;
-    }
     allowed_open_ids.iter().any(|id| id == open_id)
 }
 
+fn is_sender_allowed(config: &FeishuConfig, chat_id: &str, open_id: &str) -> bool {
+    if config.allowed_open_ids.is_empty() && config.allowed_chat_ids.is_empty() {
+        return config.allow_all;
+    }
+    is_open_id_allowed(&config.allowed_open_ids, open_id)
+        || config.allowed_chat_ids.iter().any(|id| id == chat_id)
+}
+
+fn denial_text(config: &FeishuConfig) -> Option<&str> {
+    config
+        .denial_message
+        .as_deref()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+}
+
+async fn send_text_message(
+    client: &LarkClient,
+    open_id: &str,
+    text: &str,
+) -> Result<String, String> {
+    let body = CreateMessageRequestBody::builder()
+        .receive_id(open_id.to_string())
+        .msg_type("text")
+        .content(serde_json::json!({ "text": text }).to_string())
+        .build();
+    let req = CreateMessageRequest::builder()
+        .receive_id_type("open_id")
+        .request_body(body)
+        .build();
+
+    let message = client
+        .im
+        .v1
+        .message
+        .create(req, None)
+        .await
+        .map_err(|error| format!("Feishu send message failed: {error:?}"))?;
+
+    Ok(message.message_id)
+}
+
 fn sender_kind(sender_type: &str) -> FeishuSenderKind {
     if sender_type == "user" {
         FeishuSenderKind::User
@@ -181,29 +252,21 @@ fn chat_kind(chat_type: &str) -> FeishuChatKind {
     }
 }
 
-async fn attachments_root<R: Runtime>(
+/// Download target for attachments; None until storage is available
+async fn gateway_downloads<R: Runtime>(
     app_handle: &AppHandle<R>,
-) -> Result<Option<PathBuf>, String> {
+) -> Result<Option<GatewayDownloads>, String> {
     let app_data_dir = app_handle
         .path()
         .app_data_dir()
         .map_err(|e| format!("Failed to get app data dir: {}", e))?;
-    Ok(Some(app_data_dir.join(FEISHU_ATTACHMENTS_DIR)))
-}
-
-async fn save_attachment_file(
-    attachments_dir: &PathBuf,
-    filename: &str,
-    data: &[u8],
-) -> Result<String, String> {
-    tokio::fs::create_dir_all(attachments_dir)
-        .await
-        .map_err(|e| format!("Failed to create attachments dir: {}", e))?;
-    let target_path = attachments_dir.join(filename);
-    tokio::fs::write(&target_path, data)
-        .await
-        .map_err(|e| format!("Failed to write attachment: {}", e))?;
-    Ok(target_path.to_string_lossy().to_string())
+    let Some(storage) = app_handle.try_state::<Storage>() else {
+        return Ok(None);
+    };
+    Ok(Some(GatewayDownloads::new(
+        app_data_dir.join(FEISHU_ATTACHMENTS_DIR),
+        storage.attachments.clone(),
+    )))
 }
 
 fn build_attachment_filename(prefix: &str, original_name: Option<&str>, suffix: &str) -> String {
@@ -231,7 +294,7 @@ struct TenantAccessTokenResponse {
 async fn get_tenant_access_token(app_id: &str, app_secret: &str) -> Result<String, String> {
     let url = "https://open.feishu.cn/open-apis/auth/v3/tenant_access_token/internal";
 
-    let http_client = reqwest::Client::new();
+    let http_client = crate::network_proxy::shared_json_client();
     let response = http_client
         .post(url)
         .json(&json!({
@@ -271,6 +334,7 @@ async fn download_message_resource(
     message_id: &str,
     file_key: &str,
     resource_type: &str,
+    downloads: &GatewayDownloads,
 ) -> Result<Vec<u8>, String> {
     // Get tenant access token
     let tenant_token =
@@ -281,7 +345,7 @@ async fn download_message_resource(
         message_id, file_key, resource_type
     );
 
-    let http_client = reqwest::Client::new();
+    let http_client = crate::network_proxy::shared_json_client();
     let response = http_client
         .get(&url)
         .header("Authorization", format!("Bearer {}", tenant_token))
@@ -298,12 +362,132 @@ async fn download_message_resource(
         return Err(format!("Download failed: HTTP {} - {}", status, body));
     }
 
-    let data = response
-        .bytes()
+    downloads
+        .read_body(response)
+        .await?
+        .ok_or_else(|| "File exceeds the attachment size limit".to_string())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ReactionResponse {
+    pub code: i32,
+    pub msg: String,
+    pub data: Option<ReactionData>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ReactionData {
+    pub reaction_id: Option<String>,
+}
+
+fn reaction_url(message_id: &str, reaction_id: Option<&str>) -> String {
+    let base = format!(
+        "https://open.feishu.cn/open-apis/im/v1/messages/{}/reactions",
+        message_id
+    );
+    match reaction_id {
+        Some(reaction_id) => format!("{}/{}", base, reaction_id),
+        None => base,
+    }
+}
+
+/// Add an emoji reaction to a message, returning the reaction id
+async fn add_message_reaction(
+    config: &FeishuConfig,
+    message_id: &str,
+    emoji_type: &str,
+) -> Result<String, String> {
+    let tenant_token = get_tenant_access_token(&config.app_id, &config.app_secret).await?;
+    let response = crate::network_proxy::shared_json_client()
+        .post(reaction_url(message_id, None))
+        .header("Authorization", format!("Bearer {}", tenant_token))
+        .json(&json!({ "reaction_type": { "emoji_type": emoji_type } }))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    let payload: ReactionResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse reaction response: {}", e))?;
+
+    if payload.code != 0 {
+        return Err(format!(
+            "Add reaction failed: {} - {}",
+            payload.code, payload.msg
+        ));
+    }
+
+    payload
+        .data
+        .and_then(|data| data.reaction_id)
+        .ok_or_else(|| "No reaction_id in response".to_string())
+}
+
+async fn delete_message_reaction(
+    config: &FeishuConfig,
+    message_id: &str,
+    reaction_id: &str,
+) -> Result<(), String> {
+    let tenant_token = get_tenant_access_token(&config.app_id, &config.app_secret).await?;
+    let response = crate::network_proxy::shared_json_client()
+        .delete(reaction_url(message_id, Some(reaction_id)))
+        .header("Authorization", format!("Bearer {}", tenant_token))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    let payload: ReactionResponse = response
+        .json()
         .await
-        .map_err(|e| format!("Failed to read response body: {}", e))?;
+        .map_err(|e| format!("Failed to parse reaction response: {}", e))?;
 
-    Ok(data.to_vec())
+    if payload.code != 0 {
+        return Err(format!(
+            "Delete reaction failed: {} - {}",
+            payload.code, payload.msg
+        ));
+    }
+    Ok(())
+}
+
+struct FeishuTypingSink {
+    config: FeishuConfig,
+    message_id: String,
+    reaction_id: Mutex<Option<String>>,
+}
+
+#[async_trait::async_trait]
+impl TypingSink for FeishuTypingSink {
+    async fn send_typing(&self) -> Result<(), String> {
+        // Reactions persist, so only the first tick needs to add one
+        let mut reaction_id = self.reaction_id.lock().await;
+        if reaction_id.is_some() {
+            return Ok(());
+        }
+        let id = add_message_reaction(&self.config, &self.message_id, FEISHU_TYPING_EMOJI).await?;
+        *reaction_id = Some(id);
+        Ok(())
+    }
+
+    async fn clear_typing(&self) -> Result<(), String> {
+        let reaction_id = self.reaction_id.lock().await.take();
+        match reaction_id {
+            Some(id) => delete_message_reaction(&self.config, &self.message_id, &id).await,
+            None => Ok(()),
+        }
+    }
+}
+
+async fn stop_typing(state: &FeishuGatewayState, open_id: &str) {
+    let indicator = {
+        let mut gateway = state.lock().await;
+        gateway.typing.remove(open_id)
+    };
+    if let Some(mut indicator) = indicator {
+        indicator.stop().await;
+        log::debug!("[FeishuGateway] Typing stopped open_id={}", open_id);
+    }
 }
 
 fn parse_text_content(content: &str) -> String {
@@ -340,7 +524,7 @@ async fn build_message_payload(
         text_parts.push(text.to_string());
     }
 
-    let Some(attachments_dir) = attachments_root(app_handle).await? else {
+    let Some(downloads) = gateway_downloads(app_handle).await? else {
         return Ok((text_parts.join("\n"), attachments));
     };
 
@@ -352,27 +536,43 @@ async fn build_message_payload(
         {
             // Use message resource download API for user-sent images
             // The open-lark image.get() only works for app-uploaded images
-            match download_message_resource(client, message_id, image_key, "image").await {
+            match download_message_resource(client, message_id, image_key, "image", &downloads)
+                .await
+            {
                 Ok(image_data) => {
                     let size = image_data.len() as u64;
                     if size <= MAX_FEISHU_MEDIA_BYTES {
+                        // Feishu doesn't say which format it sent; go by the bytes
+                        let kind = image_metadata::sniff_image(&image_data);
                         let filename = build_attachment_filename(
                             FEISHU_MEDIA_PREFIX,
-                            Some(&format!("image-{}", image_key)),
+                            Some(&match kind {
+                                Some(kind) => format!("image-{}.{}", image_key, kind.extension()),
+                                None => format!("image-{}", image_key),
+                            }),
                             "image",
                         );
-                        let saved_path =
-                            save_attachment_file(&attachments_dir, &filename, &image_data).await?;
-                        attachments.push(FeishuRemoteAttachment {
-                            id: image_key.to_string(),
-                            attachment_type: "image".to_string(),
-                            file_path: saved_path,
-                            filename,
-                            mime_type: "image/png".to_string(),
-                            size,
-                            duration_seconds: None,
-                            caption: None,
-                        });
+                        let mime_type =
+                            kind.map_or("application/octet-stream", ImageKind::mime_type);
+                        match downloads.save(&filename, mime_type, &image_data).await {
+                            Ok(saved) => attachments.push(FeishuRemoteAttachment {
+                                id: image_key.to_string(),
+                                attachment_type: "image".to_string(),
+                                file_path: saved.path,
+                                filename,
+                                mime_type: saved.mime_type,
+                                size: saved.size,
+                                duration_seconds: None,
+                                caption: None,
+                            }),
+                            Err(error) => {
+                                log::warn!(
+                                    "[FeishuGateway] Skipping image {}: {}",
+                                    filename,
+                                    error
+                                );
+                            }
+                        }
                     }
                 }
                 Err(error) => {
@@ -391,7 +591,9 @@ async fn build_message_payload(
             .and_then(|value| value.as_str())
         {
             // Use message resource download API for user-sent files
-            match download_message_resource(client, message_id, file_key, message_type).await {
+            match download_message_resource(client, message_id, file_key, message_type, &downloads)
+                .await
+            {
                 Ok(file_data) => {
                     let size = file_data.len() as u64;
                     if size <= MAX_FEISHU_MEDIA_BYTES {
@@ -404,28 +606,27 @@ async fn build_message_payload(
                             filename_from_content.or(Some(&format!("file-{}", file_key))),
                             message_type,
                         );
-                        let saved_path =
-                            save_attachment_file(&attachments_dir, &filename, &file_data).await?;
-                        let attachment_type = if message_type == "audio" {
-                            "audio"
+                        let (attachment_type, mime_type) = if message_type == "audio" {
+                            ("audio", "audio/mpeg")
                         } else {
-                            "file"
+                            ("file", "application/octet-stream")
                         };
                         let caption = filename_from_content.map(|name| name.to_string());
-                        attachments.push(FeishuRemoteAttachment {
-                            id: file_key.to_string(),
-                            attachment_type: attachment_type.to_string(),
-                            file_path: saved_path,
-                            filename,
-                            mime_type: if message_type == "audio" {
-                                "audio/mpeg".to_string()
-                            } else {
-                                "application/octet-stream".to_string()
-                            },
-                            size,
-                            duration_seconds: None,
-                            caption,
-                        });
+                        match downloads.save(&filename, mime_type, &file_data).await {
+                            Ok(saved) => attachments.push(FeishuRemoteAttachment {
+                                id: file_key.to_string(),
+                                attachment_type: attachment_type.to_string(),
+                                file_path: saved.path,
+                                filename,
+                                mime_type: saved.mime_type,
+                                size: saved.size,
+                                duration_seconds: None,
+                                caption,
+                            }),
+                            Err(error) => {
+                                log::warn!("[FeishuGateway] Skipping file {}: {}", filename, error);
+                            }
+                        }
                     }
                 }
                 Err(error) => {
@@ -500,7 +701,7 @@ async fn start_ws_connection(
 ) -> Result<(), String> {
     let client = Arc::new(build_client(&config)?);
     let ws_config = Arc::new(client.config.clone());
-    let open_id_allowlist = config.allowed_open_ids.clone();
+    let access_config = config.clone();
     let verification_token = config.verification_token.clone();
     let encrypt_key = config.encrypt_key.clone();
 
@@ -509,7 +710,7 @@ async fn start_ws_connection(
         .register_p2_im_message_receive_v1(move |event| {
             let client = client.clone();
             let app_handle = handler_app.clone();
-            let open_id_allowlist = open_id_allowlist.clone();
+            let access_config = access_config.clone();
             let state = state.clone();
             tokio::spawn(async move {
                 let sender = event.event.sender;
@@ -531,15 +732,48 @@ async fn start_ws_connection(
                 }
 
                 let open_id = sender.sender_id.open_id;
-                if !is_open_id_allowed(&open_id_allowlist, &open_id) {
+                if !is_sender_allowed(&access_config, &message.chat_id, &open_id) {
                     log::debug!(
-                        "[FeishuGateway] Open id not in allowlist open_id={} count={}",
+                        "[FeishuGateway] Sender not in allowlist open_id={} chat_id={} (users={}, chats={})",
                         open_id,
-                        open_id_allowlist.len()
+                        message.chat_id,
+                        access_config.allowed_open_ids.len(),
+                        access_config.allowed_chat_ids.len()
                     );
+                    if let Some(text) = denial_text(&access_config) {
+                        if let Err(error) = send_text_message(&client, &open_id, text).await {
+                            log::warn!("[FeishuGateway] Failed to send denial reply: {}", error);
+                        }
+                    }
                     return;
                 }
 
+                if message.message_type == "text" {
+                    let text = parse_text_content(&message.content);
+                    if let InboundAction::Dispatch(command) =
+                        route_inbound(&text, &access_config.command_prefix)
+                    {
+                        log::debug!(
+                            "[FeishuGateway] Dispatching command open_id={} command={:?}",
+                            open_id,
+                            command
+                        );
+                        if command == GatewayCommand::Stop {
+                            stop_typing(&state, &open_id).await;
+                        }
+                        let payload = FeishuInboundCommand {
+                            chat_id: open_id.clone(),
+                            message_id: message.message_id.clone(),
+                            open_id: open_id.clone(),
+                            command,
+                        };
+                        if let Err(error) = app_handle.emit("feishu-inbound-command", payload) {
+                            log::error!("[FeishuGateway] Failed to emit command: {}", error);
+                        }
+                        return;
+                    }
+                }
+
                 log::debug!(
                     "[FeishuGateway] Processing inbound message open_id={} message_id={} type={}",
                     open_id,
@@ -653,9 +887,11 @@ pub async fn feishu_set_config(
 
     if config.enabled && !config.app_id.is_empty() && !config.app_secret.is_empty() {
         log::info!(
-            "[FeishuGateway] Config updated (enabled={}, allowed_open_ids={})",
+            "[FeishuGateway] Config updated (enabled={}, allowed_open_ids={}, allowed_chat_ids={}, allow_all={})",
             config.enabled,
-            config.allowed_open_ids.len()
+            config.allowed_open_ids.len(),
+            config.allowed_chat_ids.len(),
+            config.allow_all
         );
         let _ = start_gateway(app_handle, state.inner().clone()).await;
     }
@@ -719,13 +955,66 @@ pub async fn feishu_start(
 
 #[tauri::command]
 pub async fn feishu_stop(state: State<'_, FeishuGatewayState>) -> Result<(), String> {
-    let mut gateway = state.lock().await;
-    if let Some(stop_tx) = gateway.stop_tx.take() {
-        let _ = stop_tx.send(true);
+    stop_gateway(state.inner()).await;
+    Ok(())
+}
+
+async fn stop_gateway(state: &FeishuGatewayState) {
+    let indicators: Vec<TypingIndicator> = {
+        let mut gateway = state.lock().await;
+        if let Some(stop_tx) = gateway.stop_tx.take() {
+            let _ = stop_tx.send(true);
+        }
+        gateway.running = false;
+        gateway
+            .typing
+            .drain()
+            .map(|(_, indicator)| indicator)
+            .collect()
+    };
+    for mut indicator in indicators {
+        indicator.stop().await;
     }
-    gateway.running = false;
     log::info!("[FeishuGateway] Stop requested");
-    Ok(())
+}
+
+/// Feishu gateway exposed through the integration registry
+pub struct FeishuIntegration {
+    app_handle: AppHandle,
+    state: FeishuGatewayState,
+}
+
+impl FeishuIntegration {
+    pub fn new(app_handle: AppHandle, state: FeishuGatewayState) -> Self {
+        Self { app_handle, state }
+    }
+}
+
+#[async_trait::async_trait]
+impl Integration for FeishuIntegration {
+    fn id(&self) -> &str {
+        "feishu"
+    }
+
+    async fn start(&self) -> Result<(), String> {
+        start_gateway(self.app_handle.clone(), self.state.clone()).await
+    }
+
+    async fn stop(&self) -> Result<(), String> {
+        stop_gateway(&self.state).await;
+        Ok(())
+    }
+
+    async fn status(&self) -> IntegrationStatus {
+        let gateway = self.state.lock().await;
+        IntegrationStatus {
+            id: "feishu".to_string(),
+            running: gateway.running,
+            last_error: gateway.last_error.clone(),
+            last_error_at_ms: gateway.last_error_at_ms,
+            last_activity_at_ms: gateway.last_event_at_ms,
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -768,33 +1057,18 @@ pub async fn feishu_send_message(
         gateway.config.clone()
     };
 
+    // First real content replaces the typing indicator
+    stop_typing(state.inner(), &request.open_id).await;
+
     let client = build_client(&config)?;
     log::debug!(
         "[FeishuGateway] sendMessage open_id={} text_len={}",
         request.open_id,
         request.text.len()
     );
-    let body = CreateMessageRequestBody::builder()
-        .receive_id(request.open_id.clone())
-        .msg_type("text")
-        .content(serde_json::json!({ "text": request.text }).to_string())
-        .build();
-    let req = CreateMessageRequest::builder()
-        .receive_id_type("open_id")
-        .request_body(body)
-        .build();
-
-    let message = client
-        .im
-        .v1
-        .message
-        .create(req, None)
-        .await
-        .map_err(|error| format!("Feishu send message failed: {error:?}"))?;
+    let message_id = send_text_message(&client, &request.open_id, &request.text).await?;
 
-    Ok(FeishuSendMessageResponse {
-        message_id: message.message_id,
-    })
+    Ok(FeishuSendMessageResponse { message_id })
 }
 
 #[tauri::command]
@@ -828,6 +1102,51 @@ pub async fn feishu_edit_message(
     Ok(())
 }
 
+/// Mark the inbound message as "typing" until the first reply is sent or typing is stopped
+#[tauri::command]
+pub async fn feishu_start_typing(
+    state: State<'_, FeishuGatewayState>,
+    open_id: String,
+    message_id: String,
+) -> Result<(), String> {
+    let mut gateway = state.lock().await;
+    if gateway.config.app_id.is_empty() || gateway.config.app_secret.is_empty() {
+        return Err("Feishu app_id/app_secret not configured".to_string());
+    }
+    if gateway
+        .typing
+        .get(&open_id)
+        .is_some_and(|indicator| indicator.is_active())
+    {
+        return Ok(());
+    }
+
+    let sink = FeishuTypingSink {
+        config: gateway.config.clone(),
+        message_id: message_id.clone(),
+        reaction_id: Mutex::new(None),
+    };
+    log::debug!(
+        "[FeishuGateway] Typing started open_id={} message_id={}",
+        open_id,
+        message_id
+    );
+    gateway.typing.insert(
+        open_id,
+        TypingIndicator::start(Arc::new(sink), DEFAULT_TYPING_REFRESH),
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn feishu_stop_typing(
+    state: State<'_, FeishuGatewayState>,
+    open_id: String,
+) -> Result<(), String> {
+    stop_typing(state.inner(), &open_id).await;
+    Ok(())
+}
+
 pub fn default_state() -> FeishuGatewayState {
     Arc::new(Mutex::new(FeishuGateway::new()))
 }
@@ -835,14 +1154,73 @@ pub fn default_state() -> FeishuGatewayState {
 #[cfg(test)]
 mod tests {
     use super::{
-        build_attachment_filename, chat_kind, is_open_id_allowed, parse_text_content, sender_kind,
-        FeishuChatKind, FeishuSenderKind,
+        build_attachment_filename, chat_kind, denial_text, is_open_id_allowed, is_sender_allowed,
+        parse_text_content, reaction_url, sender_kind, FeishuChatKind, FeishuConfig,
+        FeishuSenderKind,
     };
     use serde_json::{json, Value};
 
     #[test]
-    fn open_id_allowlist_allows_when_empty() {
-        assert!(is_open_id_allowed(&[], "ou_test"));
+    fn open_id_allowlist_denies_when_empty() {
+        assert!(!is_open_id_allowed(&[], "ou_test"));
+    }
+
+    #[test]
+    fn empty_allowlist_denies_unless_allow_all() {
+        let mut config = FeishuConfig::default();
+        assert!(!is_sender_allowed(&config, "oc_chat", "ou_test"));
+
+        config.allow_all = true;
+        assert!(is_sender_allowed(&config, "oc_chat", "ou_test"));
+    }
+
+    #[test]
+    fn non_allowlisted_sender_is_dropped() {
+        let config = FeishuConfig {
+            allowed_open_ids: vec!["ou_allowed".to_string()],
+            allowed_chat_ids: vec!["oc_allowed".to_string()],
+            ..FeishuConfig::default()
+        };
+        assert!(!is_sender_allowed(&config, "oc_other", "ou_other"));
+    }
+
+    #[test]
+    fn allowlisted_sender_proceeds() {
+        let config = FeishuConfig {
+            allowed_open_ids: vec!["ou_allowed".to_string()],
+            allowed_chat_ids: vec!["oc_allowed".to_string()],
+            ..FeishuConfig::default()
+        };
+        assert!(is_sender_allowed(&config, "oc_other", "ou_allowed"));
+        assert!(is_sender_allowed(&config, "oc_allowed", "ou_other"));
+    }
+
+    #[test]
+    fn denial_text_skips_blank_message() {
+        let mut config = FeishuConfig::default();
+        assert_eq!(denial_text(&config), None);
+
+        config.denial_message = Some("  ".to_string());
+        assert_eq!(denial_text(&config), None);
+
+        config.denial_message = Some("Not allowed".to_string());
+        assert_eq!(denial_text(&config), Some("Not allowed"));
+    }
+
+    #[test]
+    fn config_accepts_allowed_user_ids_alias() {
+        let config: FeishuConfig = serde_json::from_value(json!({
+            "enabled": true,
+            "appId": "app",
+            "appSecret": "secret",
+            "encryptKey": "",
+            "verificationToken": "",
+            "allowedUserIds": ["ou_1"],
+        }))
+        .expect("config should parse");
+        assert_eq!(config.allowed_open_ids, vec!["ou_1".to_string()]);
+        assert!(config.allowed_chat_ids.is_empty());
+        assert!(!config.allow_all);
     }
 
     #[test]
@@ -1067,6 +1445,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_reaction_url_format() {
+        assert_eq!(
+            reaction_url("om_123", None),
+            "https://open.feishu.cn/open-apis/im/v1/messages/om_123/reactions"
+        );
+        assert_eq!(
+            reaction_url("om_123", Some("rc_1")),
+            "https://open.feishu.cn/open-apis/im/v1/messages/om_123/reactions/rc_1"
+        );
+    }
+
     // Test for complete inbound message structure with image
     #[test]
     fn test_feishu_inbound_image_message_structure() {
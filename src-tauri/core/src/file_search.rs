@@ -1,10 +1,16 @@
 use crate::constants::{is_code_extension, is_code_filename};
+use crate::search::RipgrepSearch;
 use crate::walker::{WalkerConfig, WorkspaceWalker};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::path::Path;
 
+/// Base score for a content match: above a weak fuzzy name match (~100),
+/// below any name match where the query appears in the file name (600+)
+const CONTENT_MATCH_BASE_SCORE: f64 = 150.0;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileSearchResult {
     pub name: String,
@@ -13,13 +19,38 @@ pub struct FileSearchResult {
     pub score: f64,
 }
 
+/// Whether a unified search result matched the file name or its contents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnifiedMatchKind {
+    Name,
+    Content,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnifiedSearchResult {
+    pub kind: UnifiedMatchKind,
+    pub name: String,
+    pub path: String,
+    pub score: f64,
+    /// First matching line (content matches only)
+    pub line_number: Option<u64>,
+    pub line_content: Option<String>,
+}
+
 pub struct HighPerformanceFileSearch {
     max_results: usize,
+    exclude_dirs: Vec<String>,
+    include_ignored: bool,
 }
 
 impl Default for HighPerformanceFileSearch {
     fn default() -> Self {
-        Self { max_results: 200 }
+        Self {
+            max_results: 200,
+            exclude_dirs: Vec::new(),
+            include_ignored: false,
+        }
     }
 }
 
@@ -33,6 +64,18 @@ impl HighPerformanceFileSearch {
         self
     }
 
+    /// Directories to exclude on top of the defaults in `constants::DEFAULT_EXCLUDE_DIRS`
+    pub fn with_exclude_dirs(mut self, exclude_dirs: Option<Vec<String>>) -> Self {
+        self.exclude_dirs = exclude_dirs.unwrap_or_default();
+        self
+    }
+
+    /// Search inside the default excluded directories (node_modules, target, ...)
+    pub fn with_include_ignored(mut self, include_ignored: bool) -> Self {
+        self.include_ignored = include_ignored;
+        self
+    }
+
     /// High-performance file search with fuzzy matching and scoring
     pub fn search_files(
         &self,
@@ -49,7 +92,9 @@ impl HighPerformanceFileSearch {
         }
 
         // Use sequential file collection with unified walker for simplicity and correctness
-        let config = WalkerConfig::for_file_search();
+        let config = WalkerConfig::for_file_search()
+            .with_additional_excludes(self.exclude_dirs.clone())
+            .with_default_excludes(!self.include_ignored);
         let walker = WorkspaceWalker::new(root_path, config).build();
         let mut results = Vec::new();
 
@@ -104,6 +149,99 @@ impl HighPerformanceFileSearch {
         Ok(final_results)
     }
 
+    /// Quick-open search returning file name matches and a few content
+    /// matches in one list ranked by score. Files that already matched by name
+    /// are not repeated as content matches.
+    pub fn search_unified(
+        &self,
+        root_path: &str,
+        query: &str,
+    ) -> Result<Vec<UnifiedSearchResult>, String> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut results: Vec<UnifiedSearchResult> = self
+            .search_files(root_path, query)?
+            .into_iter()
+            .map(|result| UnifiedSearchResult {
+                kind: UnifiedMatchKind::Name,
+                name: result.name,
+                path: result.path,
+                score: result.score,
+                line_number: None,
+                line_content: None,
+            })
+            .collect();
+        let name_paths: HashSet<String> = results.iter().map(|r| r.path.clone()).collect();
+
+        let content_limit = (self.max_results / 4).max(3);
+        let content_results = RipgrepSearch::new()
+            .with_max_results(content_limit + name_paths.len())
+            .with_max_matches_per_file(1)
+            .with_exclude_dirs(Some(self.exclude_dirs.clone()))
+            .with_include_ignored(self.include_ignored)
+            .search_content(query, root_path)?;
+
+        let mut content_matches: Vec<UnifiedSearchResult> = content_results
+            .into_iter()
+            .filter(|result| !name_paths.contains(&result.file_path))
+            .filter_map(|result| {
+                let first = result.matches.into_iter().next()?;
+                let relative_path = Path::new(&result.file_path)
+                    .strip_prefix(root_path)
+                    .map(|p| p.to_string_lossy().replace('\\', "/"))
+                    .unwrap_or_else(|_| result.file_path.clone());
+                let name = Path::new(&result.file_path)
+                    .file_name()
+                    .and_then(OsStr::to_str)
+                    .unwrap_or("")
+                    .to_string();
+                Some(UnifiedSearchResult {
+                    kind: UnifiedMatchKind::Content,
+                    name,
+                    score: Self::content_match_score(&relative_path, &first.line_content, query),
+                    path: result.file_path,
+                    line_number: Some(first.line_number),
+                    line_content: Some(first.line_content),
+                })
+            })
+            .collect();
+        content_matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        content_matches.truncate(content_limit);
+
+        results.extend(content_matches);
+        results.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.path.len().cmp(&b.path.len()))
+        });
+        results.truncate(self.max_results);
+        Ok(results)
+    }
+
+    /// Score a content match: exact-case and whole-word hits rank higher,
+    /// deeper paths slightly lower
+    fn content_match_score(relative_path: &str, line: &str, query: &str) -> f64 {
+        let mut score = CONTENT_MATCH_BASE_SCORE;
+        if line.contains(query) {
+            score += 50.0;
+        }
+        let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+        let lower_line = line.to_lowercase();
+        let lower_query = query.to_lowercase();
+        let whole_word = lower_line.match_indices(&lower_query).any(|(index, _)| {
+            let before = lower_line[..index].chars().next_back();
+            let after = lower_line[index + lower_query.len()..].chars().next();
+            !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+        });
+        if whole_word {
+            score += 50.0;
+        }
+        score - relative_path.len() as f64 * 0.1
+    }
+
     /// Parse search query into keywords, splitting on spaces and non-alphanumeric chars
     fn parse_query(query: &str) -> Vec<String> {
         query
@@ -501,4 +639,135 @@ jobs:
         assert!(!result_names.contains(&"lv_LV.js"));
         assert!(!result_names.contains(&"environment.ts"));
     }
+
+    #[test]
+    fn test_unified_search_returns_name_and_content_matches() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/parser.rs"), "pub fn parse() {}\n").unwrap();
+        fs::write(
+            root.join("src/app.rs"),
+            "use crate::parser;\nfn main() {}\n",
+        )
+        .unwrap();
+        fs::write(root.join("src/util.rs"), "fn helper() {}\n").unwrap();
+
+        let search = HighPerformanceFileSearch::new();
+        let results = search
+            .search_unified(root.to_str().unwrap(), "parser")
+            .unwrap();
+
+        assert_eq!(results[0].kind, UnifiedMatchKind::Name);
+        assert_eq!(results[0].name, "parser.rs");
+
+        let content: Vec<_> = results
+            .iter()
+            .filter(|r| r.kind == UnifiedMatchKind::Content)
+            .collect();
+        assert_eq!(content.len(), 1);
+        assert_eq!(content[0].name, "app.rs");
+        assert_eq!(content[0].line_number, Some(1));
+        assert_eq!(
+            content[0].line_content.as_deref(),
+            Some("use crate::parser;")
+        );
+        assert!(results.iter().all(|r| r.name != "util.rs"));
+    }
+
+    #[test]
+    fn test_unified_search_ranks_content_between_name_matches() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        // Exact name match, a content match, and a weak fuzzy name match (c-o-n-f-i-g)
+        fs::write(root.join("config.ts"), "export {}\n").unwrap();
+        fs::write(root.join("loader.ts"), "import config from './config';\n").unwrap();
+        fs::write(root.join("co_n_fig_ure.ts"), "export {}\n").unwrap();
+
+        let search = HighPerformanceFileSearch::new();
+        let results = search
+            .search_unified(root.to_str().unwrap(), "config")
+            .unwrap();
+
+        let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["config.ts", "loader.ts", "co_n_fig_ure.ts"]);
+        assert_eq!(results[1].kind, UnifiedMatchKind::Content);
+    }
+
+    #[test]
+    fn test_search_skips_default_excluded_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("node_modules/lodash")).unwrap();
+        fs::create_dir_all(root.join("target/debug")).unwrap();
+        fs::write(root.join("src/index.ts"), "").unwrap();
+        fs::write(root.join("node_modules/lodash/index.js"), "").unwrap();
+        fs::write(root.join("target/debug/index.rs"), "").unwrap();
+
+        let results = HighPerformanceFileSearch::new()
+            .search_files(root.to_str().unwrap(), "index")
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].path.ends_with("index.ts"));
+    }
+
+    #[test]
+    fn test_search_skips_default_ignore_globs() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("vendor.js"), "").unwrap();
+        fs::write(root.join("vendor.min.js"), "").unwrap();
+
+        let results = HighPerformanceFileSearch::new()
+            .search_files(root.to_str().unwrap(), "vendor")
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "vendor.js");
+
+        let results = HighPerformanceFileSearch::new()
+            .with_include_ignored(true)
+            .search_files(root.to_str().unwrap(), "vendor")
+            .unwrap();
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn test_search_include_ignored_override() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("node_modules/lodash")).unwrap();
+        fs::write(root.join("src/index.ts"), "").unwrap();
+        fs::write(root.join("node_modules/lodash/index.js"), "").unwrap();
+
+        let results = HighPerformanceFileSearch::new()
+            .with_include_ignored(true)
+            .search_files(root.to_str().unwrap(), "index")
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(results
+            .iter()
+            .any(|r| r.path.contains("node_modules/lodash/index.js")));
+    }
+
+    #[test]
+    fn test_search_with_extra_exclude_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("generated")).unwrap();
+        fs::write(root.join("src/schema.ts"), "").unwrap();
+        fs::write(root.join("generated/schema.ts"), "").unwrap();
+
+        let results = HighPerformanceFileSearch::new()
+            .with_exclude_dirs(Some(vec!["generated".to_string()]))
+            .search_files(root.to_str().unwrap(), "schema")
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].path.contains("src"));
+    }
 }
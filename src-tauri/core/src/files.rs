@@ -0,0 +1,367 @@
+//! Files Routes
+//!
+//! Attachment upload for the local backend server:
+//! `POST /v1/sessions/:session_id/files?filename=<name>` with the file as the
+//! raw body and its type in `Content-Type`. The body is read chunk by chunk
+//! against the attachment size limit, so an oversized upload is refused with
+//! 413 before it is fully buffered. `GET /v1/attachments/:attachment_id/thumbnail`
+//! serves the cached preview of an image attachment. Requests authenticate
+//! with a webhook secret as a bearer token.
+
+use crate::integrations::webhook;
+use crate::storage::attachments::{Thumbnail, UploadBuffer, UploadRejection};
+use crate::storage::{Attachment, AttachmentOrigin, Storage};
+use axum::body::Body;
+use axum::extract::{Path, Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use futures_util::StreamExt;
+use serde::Deserialize;
+
+pub const UPLOAD_ROUTE: &str = "/v1/sessions/:session_id/files";
+pub const THUMBNAIL_ROUTE: &str = "/v1/attachments/:attachment_id/thumbnail";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UploadQuery {
+    pub filename: String,
+}
+
+/// Router exposing the file routes
+pub fn router(storage: Storage) -> Router {
+    Router::new()
+        .route(UPLOAD_ROUTE, post(upload_file))
+        .route(THUMBNAIL_ROUTE, get(thumbnail))
+        .with_state(storage)
+}
+
+fn error_response(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(serde_json::json!({ "error": message.into() }))).into_response()
+}
+
+fn rejection_response(rejection: UploadRejection) -> Response {
+    let status = StatusCode::from_u16(rejection.status_code()).unwrap_or(StatusCode::BAD_REQUEST);
+    error_response(status, rejection.to_string())
+}
+
+async fn is_authorized(storage: &Storage, headers: &HeaderMap) -> bool {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .unwrap_or_default();
+    let secrets = webhook::load_secrets(&storage.settings).await;
+    webhook::is_valid_token(token.trim(), &secrets)
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: header::HeaderName) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+/// Store an uploaded file as an attachment of the session
+pub async fn upload_file(
+    State(storage): State<Storage>,
+    Path(session_id): Path<String>,
+    Query(query): Query<UploadQuery>,
+    headers: HeaderMap,
+    body: Body,
+) -> Response {
+    if !is_authorized(&storage, &headers).await {
+        return error_response(StatusCode::UNAUTHORIZED, "Invalid token");
+    }
+    let filename = query.filename.replace(['/', '\\'], "_");
+    if filename.trim().is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "filename must not be empty");
+    }
+    match storage.chat_history.get_session(&session_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return error_response(
+                StatusCode::NOT_FOUND,
+                format!("Session not found: {}", session_id),
+            )
+        }
+        Err(e) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+    }
+
+    let declared_size =
+        header_str(&headers, header::CONTENT_LENGTH).and_then(|value| value.parse().ok());
+    let mut buffer = match UploadBuffer::new(storage.attachments.upload_limits(), declared_size) {
+        Ok(buffer) => buffer,
+        Err(rejection) => return rejection_response(rejection),
+    };
+    let mut stream = body.into_data_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    format!("Failed to read upload: {}", e),
+                )
+            }
+        };
+        if let Err(rejection) = buffer.push(&chunk) {
+            return rejection_response(rejection);
+        }
+    }
+    let data = buffer.into_inner();
+
+    let declared_mime = header_str(&headers, header::CONTENT_TYPE).unwrap_or_default();
+    let (mime_type, data) =
+        match storage
+            .attachments
+            .prepare_upload(declared_mime, &filename, &data)
+        {
+            Ok(prepared) => prepared,
+            Err(rejection) => return rejection_response(rejection),
+        };
+
+    let attachment = Attachment {
+        id: format!("att_{}", uuid::Uuid::new_v4().simple()),
+        session_id,
+        message_id: None,
+        filename,
+        mime_type,
+        size: data.len() as i64,
+        path: String::new(),
+        created_at: chrono::Utc::now().timestamp(),
+        origin: AttachmentOrigin::UserUpload,
+    };
+    if let Err(e) = storage
+        .attachments
+        .create_attachment(&attachment, &data)
+        .await
+    {
+        return error_response(StatusCode::INTERNAL_SERVER_ERROR, e);
+    }
+    match storage.attachments.get_attachment(&attachment.id).await {
+        Ok(Some(stored)) => (StatusCode::CREATED, Json(stored)).into_response(),
+        Ok(None) => error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Attachment was not stored",
+        ),
+        Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+    }
+}
+
+/// Serve the thumbnail of an image attachment; 404 for missing or non-image
+/// attachments
+pub async fn thumbnail(
+    State(storage): State<Storage>,
+    Path(attachment_id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    if !is_authorized(&storage, &headers).await {
+        return error_response(StatusCode::UNAUTHORIZED, "Invalid token");
+    }
+    match storage.attachments.get_thumbnail(&attachment_id).await {
+        Ok(Some(thumbnail)) => (
+            [(header::CONTENT_TYPE, thumbnail.mime_type)],
+            thumbnail.data,
+        )
+            .into_response(),
+        Ok(None) => error_response(
+            StatusCode::NOT_FOUND,
+            format!("No thumbnail for attachment: {}", attachment_id),
+        ),
+        Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+    }
+}
+
+/// Thumbnail of an image attachment, or `None` for missing or non-image
+/// attachments
+#[tauri::command]
+pub async fn attachment_get_thumbnail(
+    attachment_id: String,
+    storage: tauri::State<'_, Storage>,
+) -> Result<Option<Thumbnail>, String> {
+    storage.attachments.get_thumbnail(&attachment_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::attachments::UploadLimits;
+    use crate::storage::{Session, SessionStatus};
+    use tempfile::TempDir;
+
+    async fn spawn_files_server(limits: UploadLimits) -> (String, Storage, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let mut storage = Storage::new(
+            temp_dir.path().to_path_buf(),
+            temp_dir.path().join("attachments"),
+        )
+        .await
+        .expect("Failed to create storage");
+        storage.attachments = storage.attachments.clone().with_upload_limits(limits);
+        storage
+            .settings
+            .set_setting(
+                webhook::WEBHOOK_SECRETS_SETTING,
+                &serde_json::json!(["s3cret"]),
+            )
+            .await
+            .unwrap();
+        let now = chrono::Utc::now().timestamp();
+        storage
+            .chat_history
+            .create_session(&Session {
+                id: "sess_files".to_string(),
+                project_id: None,
+                title: None,
+                status: SessionStatus::Created,
+                created_at: now,
+                updated_at: now,
+                last_event_id: None,
+                metadata: None,
+            })
+            .await
+            .unwrap();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = router(storage.clone());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (format!("http://{}", addr), storage, temp_dir)
+    }
+
+    fn upload(base: &str, filename: &str) -> reqwest::RequestBuilder {
+        reqwest::Client::new()
+            .post(format!("{}/v1/sessions/sess_files/files", base))
+            .query(&[("filename", filename)])
+            .bearer_auth("s3cret")
+    }
+
+    #[tokio::test]
+    async fn upload_stores_attachment_with_validated_mime() {
+        let (base, storage, _temp) = spawn_files_server(UploadLimits::default()).await;
+
+        let response = upload(&base, "notes.txt")
+            .header("Content-Type", "TEXT/PLAIN; charset=utf-8")
+            .body("hello")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 201);
+
+        let stored: Attachment = response.json().await.unwrap();
+        assert_eq!(stored.mime_type, "text/plain");
+        assert_eq!(stored.size, 5);
+        assert_eq!(
+            storage
+                .attachments
+                .read_attachment_data(&stored.id)
+                .await
+                .unwrap()
+                .unwrap(),
+            b"hello"
+        );
+    }
+
+    #[tokio::test]
+    async fn oversized_upload_is_rejected_while_streaming() {
+        let (base, _storage, _temp) = spawn_files_server(UploadLimits {
+            max_size: 16,
+            allowed_mimes: None,
+        })
+        .await;
+
+        let response = upload(&base, "big.bin")
+            .body(vec![0u8; 17])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 413);
+
+        // No Content-Length: the running total trips the limit
+        let chunks = futures::stream::iter(
+            (0..4).map(|_| Ok::<_, std::io::Error>(bytes::Bytes::from(vec![0u8; 8]))),
+        );
+        let response = upload(&base, "big.bin")
+            .body(reqwest::Body::wrap_stream(chunks))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 413);
+    }
+
+    #[tokio::test]
+    async fn disallowed_mime_is_rejected_with_415() {
+        let (base, _storage, _temp) = spawn_files_server(UploadLimits {
+            max_size: 1024,
+            allowed_mimes: Some(vec!["image/*".to_string()]),
+        })
+        .await;
+
+        let response = upload(&base, "setup.exe")
+            .header("Content-Type", "application/x-msdownload")
+            .body("MZ..")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 415);
+    }
+
+    #[tokio::test]
+    async fn thumbnail_is_served_for_images_only() {
+        let (base, _storage, _temp) = spawn_files_server(UploadLimits::default()).await;
+
+        let mut png = Vec::new();
+        image::DynamicImage::new_rgb8(8, 8)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        let image: Attachment = upload(&base, "pixel.png")
+            .header("Content-Type", "image/png")
+            .body(png)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let text: Attachment = upload(&base, "notes.txt")
+            .header("Content-Type", "text/plain")
+            .body("hello")
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+
+        let thumbnail = |id: String| {
+            reqwest::Client::new()
+                .get(format!("{}/v1/attachments/{}/thumbnail", base, id))
+                .bearer_auth("s3cret")
+                .send()
+        };
+
+        let response = thumbnail(image.id).await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers()["content-type"], "image/png");
+        let data = response.bytes().await.unwrap();
+        assert!(image::load_from_memory(&data).is_ok());
+
+        assert_eq!(thumbnail(text.id).await.unwrap().status(), 404);
+        assert_eq!(
+            thumbnail("att_missing".to_string()).await.unwrap().status(),
+            404
+        );
+    }
+
+    #[tokio::test]
+    async fn upload_requires_a_token() {
+        let (base, _storage, _temp) = spawn_files_server(UploadLimits::default()).await;
+
+        let response = reqwest::Client::new()
+            .post(format!("{}/v1/sessions/sess_files/files", base))
+            .query(&[("filename", "a.txt")])
+            .body("hi")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 401);
+    }
+}
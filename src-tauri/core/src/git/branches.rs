@@ -0,0 +1,245 @@
+use super::types::BranchEntry;
+use git2::{
+    build::CheckoutBuilder, BranchType, CheckoutNotificationType, Error as GitError, ErrorCode,
+    Repository,
+};
+
+/// Lists local branches followed by remote-tracking branches, each sorted by name
+pub fn list_branches(repo: &Repository) -> Result<Vec<BranchEntry>, GitError> {
+    let mut entries = Vec::new();
+
+    for branch in repo.branches(None)? {
+        let (branch, branch_type) = branch?;
+        let reference = branch.get();
+        // Skip symbolic refs such as `origin/HEAD`
+        if reference.symbolic_target().is_some() {
+            continue;
+        }
+        let Some(name) = branch.name()? else {
+            continue;
+        };
+
+        let upstream = match branch_type {
+            BranchType::Local => branch
+                .upstream()
+                .ok()
+                .and_then(|upstream| upstream.name().ok().flatten().map(|s| s.to_string())),
+            BranchType::Remote => None,
+        };
+
+        entries.push(BranchEntry {
+            name: name.to_string(),
+            is_current: branch.is_head(),
+            is_remote: branch_type == BranchType::Remote,
+            upstream,
+            commit_hash: reference.target().map(|oid| oid.to_string()),
+        });
+    }
+
+    entries.sort_by(|a, b| a.is_remote.cmp(&b.is_remote).then(a.name.cmp(&b.name)));
+    Ok(entries)
+}
+
+/// Creates a local branch at `from_ref` (defaults to HEAD) without switching to it
+pub fn create_branch(
+    repo: &Repository,
+    name: &str,
+    from_ref: Option<&str>,
+) -> Result<(), GitError> {
+    if !git2::Branch::name_is_valid(name)? {
+        return Err(GitError::from_str(&format!(
+            "Invalid branch name: {}",
+            name
+        )));
+    }
+    if repo.find_branch(name, BranchType::Local).is_ok() {
+        return Err(GitError::from_str(&format!(
+            "Branch '{}' already exists",
+            name
+        )));
+    }
+
+    let start = from_ref.unwrap_or("HEAD");
+    let commit = repo.revparse_single(start)?.peel_to_commit()?;
+    repo.branch(name, &commit, false)?;
+    Ok(())
+}
+
+/// Checks out a local branch. Uncommitted changes that don't conflict with
+/// the target branch are carried over; conflicting ones abort the switch
+/// without touching the working tree.
+pub fn switch_branch(repo: &Repository, name: &str) -> Result<(), GitError> {
+    let branch = repo
+        .find_branch(name, BranchType::Local)
+        .map_err(|_| GitError::from_str(&format!("Branch '{}' not found", name)))?;
+    let reference = branch.into_reference();
+    let refname = reference
+        .name()
+        .ok_or_else(|| GitError::from_str("Branch reference name is not valid UTF-8"))?
+        .to_string();
+    let target = reference.peel_to_tree()?;
+
+    let mut conflicts = Vec::new();
+    let result = {
+        let mut checkout = CheckoutBuilder::new();
+        checkout
+            .safe()
+            .notify_on(CheckoutNotificationType::CONFLICT)
+            .notify(|_, path, _, _, _| {
+                if let Some(path) = path {
+                    conflicts.push(path.to_string_lossy().replace('\\', "/"));
+                }
+                true
+            });
+        repo.checkout_tree(target.as_object(), Some(&mut checkout))
+    };
+
+    if let Err(err) = result {
+        if err.code() == ErrorCode::Conflict || !conflicts.is_empty() {
+            return Err(GitError::from_str(&format!(
+                "Cannot switch to '{}': uncommitted changes would be overwritten ({}). Commit or discard them first.",
+                name,
+                conflicts.join(", ")
+            )));
+        }
+        return Err(err);
+    }
+
+    repo.set_head(&refname)
+}
+
+/// Deletes a local branch. Unless `force` is set, the branch must be fully
+/// merged into HEAD. The current branch can never be deleted.
+pub fn delete_branch(repo: &Repository, name: &str, force: bool) -> Result<(), GitError> {
+    let mut branch = repo
+        .find_branch(name, BranchType::Local)
+        .map_err(|_| GitError::from_str(&format!("Branch '{}' not found", name)))?;
+
+    if branch.is_head() {
+        return Err(GitError::from_str(&format!(
+            "Cannot delete the current branch '{}'",
+            name
+        )));
+    }
+
+    if !force {
+        let branch_oid = branch
+            .get()
+            .target()
+            .ok_or_else(|| GitError::from_str("Branch has no target"))?;
+        let head_oid = repo
+            .head()?
+            .target()
+            .ok_or_else(|| GitError::from_str("HEAD has no target"))?;
+        let merged = branch_oid == head_oid || repo.graph_descendant_of(head_oid, branch_oid)?;
+        if !merged {
+            return Err(GitError::from_str(&format!(
+                "Branch '{}' is not fully merged; use force to delete it",
+                name
+            )));
+        }
+    }
+
+    branch.delete()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::git;
+    use tempfile::TempDir;
+
+    /// Repo on branch `main` with `a.txt` committed
+    fn create_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        git(dir, &["init", "-b", "main"]);
+        git(dir, &["config", "user.email", "test@example.com"]);
+        git(dir, &["config", "user.name", "Test User"]);
+        std::fs::write(dir.join("a.txt"), "a\n").unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-m", "Initial commit"]);
+        temp_dir
+    }
+
+    fn current_branch(repo: &Repository) -> String {
+        repo.head().unwrap().shorthand().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_create_switch_and_delete_branch() {
+        let temp_dir = create_repo();
+        let dir = temp_dir.path();
+        let repo = Repository::open(dir).unwrap();
+
+        create_branch(&repo, "feature", None).unwrap();
+        let branches = list_branches(&repo).unwrap();
+        let names: Vec<_> = branches.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["feature", "main"]);
+        assert!(branches.iter().any(|b| b.name == "main" && b.is_current));
+
+        switch_branch(&repo, "feature").unwrap();
+        assert_eq!(current_branch(&repo), "feature");
+        std::fs::write(dir.join("a.txt"), "feature\n").unwrap();
+        git(dir, &["commit", "-am", "Feature change"]);
+
+        switch_branch(&repo, "main").unwrap();
+        assert_eq!(current_branch(&repo), "main");
+        assert_eq!(std::fs::read_to_string(dir.join("a.txt")).unwrap(), "a\n");
+
+        let err = delete_branch(&repo, "feature", false).unwrap_err();
+        assert!(err.message().contains("not fully merged"));
+        delete_branch(&repo, "feature", true).unwrap();
+        assert!(repo.find_branch("feature", BranchType::Local).is_err());
+    }
+
+    #[test]
+    fn test_switch_refuses_to_overwrite_uncommitted_changes() {
+        let temp_dir = create_repo();
+        let dir = temp_dir.path();
+        git(dir, &["checkout", "-b", "feature"]);
+        std::fs::write(dir.join("a.txt"), "feature\n").unwrap();
+        git(dir, &["commit", "-am", "Feature change"]);
+        git(dir, &["checkout", "main"]);
+        std::fs::write(dir.join("a.txt"), "local edit\n").unwrap();
+        let repo = Repository::open(dir).unwrap();
+
+        let err = switch_branch(&repo, "feature").unwrap_err();
+
+        assert!(err.message().contains("uncommitted changes"));
+        assert!(err.message().contains("a.txt"));
+        assert_eq!(current_branch(&repo), "main");
+        assert_eq!(
+            std::fs::read_to_string(dir.join("a.txt")).unwrap(),
+            "local edit\n"
+        );
+    }
+
+    #[test]
+    fn test_switch_carries_non_conflicting_changes() {
+        let temp_dir = create_repo();
+        let dir = temp_dir.path();
+        let repo = Repository::open(dir).unwrap();
+        create_branch(&repo, "feature", Some("main")).unwrap();
+        std::fs::write(dir.join("a.txt"), "local edit\n").unwrap();
+
+        switch_branch(&repo, "feature").unwrap();
+
+        assert_eq!(current_branch(&repo), "feature");
+        assert_eq!(
+            std::fs::read_to_string(dir.join("a.txt")).unwrap(),
+            "local edit\n"
+        );
+    }
+
+    #[test]
+    fn test_branch_guards() {
+        let temp_dir = create_repo();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+
+        assert!(create_branch(&repo, "main", None).is_err());
+        assert!(create_branch(&repo, "bad..name", None).is_err());
+        assert!(delete_branch(&repo, "main", true).is_err());
+        assert!(switch_branch(&repo, "missing").is_err());
+    }
+}
@@ -0,0 +1,441 @@
+use super::diff::invalidate_line_changes;
+use git2::{build::CheckoutBuilder, Error as GitError, Repository, Signature};
+use std::path::{Component, Path, PathBuf};
+
+/// Resolves a user-supplied path (absolute or repo-relative) to a path
+/// relative to the repository root, rejecting anything outside the repo.
+pub(crate) fn repo_relative_path(repo: &Repository, file_path: &str) -> Result<String, GitError> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| GitError::from_str("Repository has no working directory"))?;
+
+    let path = Path::new(file_path);
+    let relative = if path.is_absolute() {
+        let root = workdir
+            .canonicalize()
+            .unwrap_or_else(|_| workdir.to_path_buf());
+        path.strip_prefix(&root)
+            .or_else(|_| path.strip_prefix(workdir))
+            .map_err(|_| {
+                GitError::from_str(&format!("Path is outside the repository: {}", file_path))
+            })?
+            .to_path_buf()
+    } else {
+        path.to_path_buf()
+    };
+
+    let mut normalized = PathBuf::new();
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) => normalized.push(part),
+            Component::CurDir => {}
+            Component::ParentDir if normalized.pop() => {}
+            _ => {
+                return Err(GitError::from_str(&format!(
+                    "Path is outside the repository: {}",
+                    file_path
+                )))
+            }
+        }
+    }
+
+    let normalized = normalized.to_string_lossy().replace('\\', "/");
+    if normalized.is_empty() {
+        return Err(GitError::from_str(&format!(
+            "Path must name a file in the repository: {}",
+            file_path
+        )));
+    }
+    Ok(normalized)
+}
+
+/// Restores files to their HEAD version in both the index and working tree
+/// (`git checkout HEAD -- <file>`). Unchanged files and files missing from
+/// HEAD (untracked or newly staged) are left alone, so a forced checkout never
+/// deletes them. Returns the repo-relative paths that were reverted.
+pub fn checkout_files(repo: &Repository, file_paths: &[String]) -> Result<Vec<String>, GitError> {
+    let paths = file_paths
+        .iter()
+        .map(|path| repo_relative_path(repo, path))
+        .collect::<Result<Vec<_>, _>>()?;
+    let head_tree = match repo.head().and_then(|head| head.peel_to_tree()) {
+        Ok(tree) => tree,
+        // No commits yet: there is no HEAD version to restore
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    let mut reverted = Vec::new();
+    for path in paths {
+        let status = repo.status_file(Path::new(&path))?;
+        if status.is_empty() || status.is_ignored() || head_tree.get_path(Path::new(&path)).is_err()
+        {
+            continue;
+        }
+        if !reverted.contains(&path) {
+            reverted.push(path);
+        }
+    }
+
+    if reverted.is_empty() {
+        return Ok(reverted);
+    }
+
+    let mut checkout = CheckoutBuilder::new();
+    checkout.force().disable_pathspec_match(true);
+    for path in &reverted {
+        checkout.path(path);
+    }
+    repo.checkout_head(Some(&mut checkout))?;
+    invalidate_line_changes(repo, &reverted);
+
+    Ok(reverted)
+}
+
+/// Adds files to the index (`git add`); deleted files are staged as removals
+pub fn stage_files(repo: &Repository, file_paths: &[String]) -> Result<(), GitError> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| GitError::from_str("Repository has no working directory"))?
+        .to_path_buf();
+    let mut index = repo.index()?;
+    for file_path in file_paths {
+        let path = repo_relative_path(repo, file_path)?;
+        if workdir.join(&path).exists() {
+            index.add_path(Path::new(&path))?;
+        } else {
+            index.remove_path(Path::new(&path))?;
+        }
+    }
+    index.write()
+}
+
+/// Removes files from the index, keeping working-tree changes
+/// (`git restore --staged`)
+pub fn unstage_files(repo: &Repository, file_paths: &[String]) -> Result<(), GitError> {
+    let paths = file_paths
+        .iter()
+        .map(|path| repo_relative_path(repo, path))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match repo.head().and_then(|head| head.peel_to_commit()) {
+        Ok(head_commit) => repo.reset_default(Some(head_commit.as_object()), &paths),
+        Err(_) => {
+            // No commits yet: unstaging means dropping the entries entirely
+            let mut index = repo.index()?;
+            for path in &paths {
+                index.remove_path(Path::new(path))?;
+            }
+            index.write()
+        }
+    }
+}
+
+/// Parses an author override in `git commit --author` form: `Name <email>`
+fn parse_author(author: &str) -> Result<Signature<'static>, GitError> {
+    let invalid = || GitError::from_str("Author must be in the form 'Name <email>'");
+    let (name, rest) = author.trim().split_once('<').ok_or_else(invalid)?;
+    let email = rest.strip_suffix('>').ok_or_else(invalid)?.trim();
+    let name = name.trim();
+    if name.is_empty() || email.is_empty() {
+        return Err(invalid());
+    }
+    Signature::now(name, email)
+}
+
+/// Commits the current index and returns the new commit hash. The author is
+/// the repository's configured user unless `author` (`Name <email>`) is
+/// given; the committer is always the configured user. Errors if nothing is
+/// staged, unless amending HEAD (which may only reword the message).
+pub fn commit_index(
+    repo: &Repository,
+    message: &str,
+    amend: bool,
+    author: Option<&str>,
+) -> Result<String, GitError> {
+    if message.trim().is_empty() {
+        return Err(GitError::from_str("Commit message cannot be empty"));
+    }
+
+    let mut index = repo.index()?;
+    let tree_id = index.write_tree()?;
+    let tree = repo.find_tree(tree_id)?;
+    let head_commit = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let signature = repo.signature()?;
+    let author = author.map(parse_author).transpose()?;
+
+    let commit_id = if amend {
+        let head_commit =
+            head_commit.ok_or_else(|| GitError::from_str("Cannot amend: no commits yet"))?;
+        head_commit.amend(
+            Some("HEAD"),
+            author.as_ref(),
+            Some(&signature),
+            None,
+            Some(message),
+            Some(&tree),
+        )?
+    } else {
+        let nothing_staged = match &head_commit {
+            Some(commit) => commit.tree_id() == tree_id,
+            None => index.is_empty(),
+        };
+        if nothing_staged {
+            return Err(GitError::from_str("Nothing to commit: no staged changes"));
+        }
+        let parents: Vec<&git2::Commit> = head_commit.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            author.as_ref().unwrap_or(&signature),
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )?
+    };
+
+    Ok(commit_id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::git;
+    use crate::git::types::GitFileStatus;
+    use tempfile::TempDir;
+
+    /// Repo with `a.txt` and `b.txt` committed
+    fn create_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        git(dir, &["init"]);
+        git(dir, &["config", "user.email", "test@example.com"]);
+        git(dir, &["config", "user.name", "Test User"]);
+        std::fs::write(dir.join("a.txt"), "a\n").unwrap();
+        std::fs::write(dir.join("b.txt"), "b\n").unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-m", "Initial commit"]);
+        temp_dir
+    }
+
+    #[test]
+    fn test_checkout_reverts_only_listed_files() {
+        let temp_dir = create_repo();
+        let dir = temp_dir.path();
+        std::fs::write(dir.join("a.txt"), "changed a\n").unwrap();
+        std::fs::write(dir.join("b.txt"), "changed b\n").unwrap();
+        let repo = Repository::open(dir).unwrap();
+
+        let reverted = checkout_files(&repo, &["a.txt".to_string()]).unwrap();
+
+        assert_eq!(reverted, vec!["a.txt"]);
+        assert_eq!(std::fs::read_to_string(dir.join("a.txt")).unwrap(), "a\n");
+        assert_eq!(
+            std::fs::read_to_string(dir.join("b.txt")).unwrap(),
+            "changed b\n"
+        );
+    }
+
+    #[test]
+    fn test_checkout_accepts_absolute_paths_and_restores_deleted_files() {
+        let temp_dir = create_repo();
+        let dir = temp_dir.path();
+        std::fs::remove_file(dir.join("b.txt")).unwrap();
+        let repo = Repository::open(dir).unwrap();
+
+        let absolute = dir.join("b.txt").to_string_lossy().to_string();
+        let reverted = checkout_files(&repo, &[absolute]).unwrap();
+
+        assert_eq!(reverted, vec!["b.txt"]);
+        assert_eq!(std::fs::read_to_string(dir.join("b.txt")).unwrap(), "b\n");
+    }
+
+    #[test]
+    fn test_checkout_skips_clean_and_untracked_files() {
+        let temp_dir = create_repo();
+        let dir = temp_dir.path();
+        std::fs::write(dir.join("new.txt"), "new\n").unwrap();
+        let repo = Repository::open(dir).unwrap();
+
+        let reverted =
+            checkout_files(&repo, &["a.txt".to_string(), "new.txt".to_string()]).unwrap();
+
+        assert!(reverted.is_empty());
+        assert!(dir.join("new.txt").exists());
+    }
+
+    #[test]
+    fn test_checkout_keeps_staged_new_files() {
+        let temp_dir = create_repo();
+        let dir = temp_dir.path();
+        std::fs::write(dir.join("a.txt"), "changed a\n").unwrap();
+        std::fs::write(dir.join("new.txt"), "new\n").unwrap();
+        git(dir, &["add", "new.txt"]);
+        let repo = Repository::open(dir).unwrap();
+
+        let reverted =
+            checkout_files(&repo, &["a.txt".to_string(), "new.txt".to_string()]).unwrap();
+
+        assert_eq!(reverted, vec!["a.txt"]);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("new.txt")).unwrap(),
+            "new\n"
+        );
+    }
+
+    #[test]
+    fn test_checkout_rejects_paths_outside_repo() {
+        let temp_dir = create_repo();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+
+        assert!(checkout_files(&repo, &["../outside.txt".to_string()]).is_err());
+        assert!(checkout_files(&repo, &["/etc/passwd".to_string()]).is_err());
+        assert!(checkout_files(&repo, &[".".to_string()]).is_err());
+    }
+
+    fn file_status(repo: &Repository, path: &str) -> Option<(GitFileStatus, bool)> {
+        crate::git::status::get_all_file_statuses(repo)
+            .unwrap()
+            .remove(path)
+    }
+
+    #[test]
+    fn test_stage_and_unstage_modified_file() {
+        let temp_dir = create_repo();
+        let dir = temp_dir.path();
+        std::fs::write(dir.join("a.txt"), "changed a\n").unwrap();
+        let repo = Repository::open(dir).unwrap();
+        assert!(matches!(
+            file_status(&repo, "a.txt"),
+            Some((GitFileStatus::Modified, false))
+        ));
+
+        stage_files(&repo, &["a.txt".to_string()]).unwrap();
+        assert!(matches!(
+            file_status(&repo, "a.txt"),
+            Some((GitFileStatus::Modified, true))
+        ));
+
+        unstage_files(&repo, &["a.txt".to_string()]).unwrap();
+        assert!(matches!(
+            file_status(&repo, "a.txt"),
+            Some((GitFileStatus::Modified, false))
+        ));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("a.txt")).unwrap(),
+            "changed a\n"
+        );
+    }
+
+    #[test]
+    fn test_stage_new_and_deleted_files() {
+        let temp_dir = create_repo();
+        let dir = temp_dir.path();
+        std::fs::write(dir.join("new.txt"), "new\n").unwrap();
+        std::fs::remove_file(dir.join("b.txt")).unwrap();
+        let repo = Repository::open(dir).unwrap();
+
+        stage_files(&repo, &["new.txt".to_string(), "b.txt".to_string()]).unwrap();
+        assert!(matches!(
+            file_status(&repo, "new.txt"),
+            Some((GitFileStatus::Added, true))
+        ));
+        assert!(matches!(
+            file_status(&repo, "b.txt"),
+            Some((GitFileStatus::Deleted, true))
+        ));
+
+        unstage_files(&repo, &["new.txt".to_string()]).unwrap();
+        assert!(matches!(
+            file_status(&repo, "new.txt"),
+            Some((GitFileStatus::Untracked, false))
+        ));
+    }
+
+    fn head_hash(dir: &Path) -> String {
+        let output = crate::shell_utils::new_command("git")
+            .args(["rev-parse", "HEAD"])
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    #[test]
+    fn test_commit_index_commits_only_staged_changes() {
+        let temp_dir = create_repo();
+        let dir = temp_dir.path();
+        std::fs::write(dir.join("a.txt"), "changed a\n").unwrap();
+        std::fs::write(dir.join("b.txt"), "changed b\n").unwrap();
+        let repo = Repository::open(dir).unwrap();
+        stage_files(&repo, &["a.txt".to_string()]).unwrap();
+
+        let hash = commit_index(&repo, "Update a", false, None).unwrap();
+
+        assert_eq!(hash, head_hash(dir));
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.summary(), Some("Update a"));
+        assert_eq!(head.author().name(), Some("Test User"));
+        assert_eq!(head.parent_count(), 1);
+        assert!(file_status(&repo, "a.txt").is_none());
+        assert!(matches!(
+            file_status(&repo, "b.txt"),
+            Some((GitFileStatus::Modified, false))
+        ));
+    }
+
+    #[test]
+    fn test_commit_index_errors_when_nothing_staged() {
+        let temp_dir = create_repo();
+        let dir = temp_dir.path();
+        std::fs::write(dir.join("a.txt"), "changed a\n").unwrap();
+        let repo = Repository::open(dir).unwrap();
+        let before = head_hash(dir);
+
+        let err = commit_index(&repo, "Nothing", false, None).unwrap_err();
+
+        assert!(err.message().contains("Nothing to commit"));
+        assert_eq!(head_hash(dir), before);
+    }
+
+    #[test]
+    fn test_commit_index_amend_rewords_head() {
+        let temp_dir = create_repo();
+        let dir = temp_dir.path();
+        let repo = Repository::open(dir).unwrap();
+        let before = head_hash(dir);
+
+        let hash = commit_index(&repo, "Reworded", true, None).unwrap();
+
+        assert_ne!(hash, before);
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.summary(), Some("Reworded"));
+        assert_eq!(head.parent_count(), 0);
+    }
+
+    #[test]
+    fn test_commit_index_author_override() {
+        let temp_dir = create_repo();
+        let dir = temp_dir.path();
+        std::fs::write(dir.join("a.txt"), "changed a\n").unwrap();
+        let repo = Repository::open(dir).unwrap();
+        stage_files(&repo, &["a.txt".to_string()]).unwrap();
+
+        let hash = commit_index(
+            &repo,
+            "Update a",
+            false,
+            Some("Other Person <other@example.com>"),
+        )
+        .unwrap();
+
+        assert_eq!(hash, head_hash(dir));
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.author().name(), Some("Other Person"));
+        assert_eq!(head.author().email(), Some("other@example.com"));
+        assert_eq!(head.committer().name(), Some("Test User"));
+
+        let err = commit_index(&repo, "Reworded", true, Some("no-email")).unwrap_err();
+        assert!(err.message().contains("Name <email>"));
+        assert_eq!(head_hash(dir), hash);
+    }
+}
@@ -0,0 +1,199 @@
+use super::changes::repo_relative_path;
+use super::types::ConflictHunk;
+use git2::{Error as GitError, Repository};
+
+const OURS_MARKER: &str = "<<<<<<<";
+const BASE_MARKER: &str = "|||||||";
+const SEPARATOR_MARKER: &str = "=======";
+const THEIRS_MARKER: &str = ">>>>>>>";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    Ours,
+    Base,
+    Theirs,
+}
+
+/// Returns the label after `marker` if `line` is that conflict marker.
+/// Markers are exactly seven characters, optionally followed by a space and label.
+fn marker_label<'a>(line: &'a str, marker: &str) -> Option<&'a str> {
+    let rest = line.strip_prefix(marker)?;
+    if rest.is_empty() {
+        Some("")
+    } else {
+        rest.strip_prefix(' ').map(str::trim)
+    }
+}
+
+fn label(text: &str) -> Option<String> {
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Parses conflict regions from file content. Unterminated regions are ignored.
+pub fn parse_conflict_hunks(content: &str) -> Vec<ConflictHunk> {
+    let mut hunks = Vec::new();
+    let mut current: Option<(ConflictHunk, Section, Vec<&str>, Vec<&str>, Vec<&str>)> = None;
+
+    for (index, raw_line) in content.lines().enumerate() {
+        let line = raw_line.strip_suffix('\r').unwrap_or(raw_line);
+        let line_number = index + 1;
+
+        if let Some(ours_label) = marker_label(line, OURS_MARKER) {
+            // A new start marker abandons any unterminated region
+            current = Some((
+                ConflictHunk {
+                    ours: String::new(),
+                    theirs: String::new(),
+                    base: None,
+                    ours_label: label(ours_label),
+                    theirs_label: None,
+                    start_line: line_number,
+                    end_line: line_number,
+                },
+                Section::Ours,
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+            ));
+            continue;
+        }
+
+        let Some((hunk, section, ours, base, theirs)) = current.as_mut() else {
+            continue;
+        };
+
+        if *section == Section::Ours && marker_label(line, BASE_MARKER).is_some() {
+            *section = Section::Base;
+            hunk.base = Some(String::new());
+        } else if *section != Section::Theirs && line == SEPARATOR_MARKER {
+            *section = Section::Theirs;
+        } else if *section == Section::Theirs && marker_label(line, THEIRS_MARKER).is_some() {
+            let theirs_label = marker_label(line, THEIRS_MARKER).unwrap_or_default();
+            let (mut hunk, _, ours, base, theirs) = current.take().unwrap();
+            hunk.ours = ours.join("\n");
+            hunk.theirs = theirs.join("\n");
+            if hunk.base.is_some() {
+                hunk.base = Some(base.join("\n"));
+            }
+            hunk.theirs_label = label(theirs_label);
+            hunk.end_line = line_number;
+            hunks.push(hunk);
+        } else {
+            match section {
+                Section::Ours => ours.push(line),
+                Section::Base => base.push(line),
+                Section::Theirs => theirs.push(line),
+            }
+        }
+    }
+
+    hunks
+}
+
+/// Reads a file from the working tree and parses its conflict regions
+pub fn get_conflict_hunks(
+    repo: &Repository,
+    file_path: &str,
+) -> Result<Vec<ConflictHunk>, GitError> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| GitError::from_str("Repository has no working directory"))?;
+    let path = repo_relative_path(repo, file_path)?;
+    let content = std::fs::read_to_string(workdir.join(&path))
+        .map_err(|e| GitError::from_str(&format!("Failed to read {}: {}", path, e)))?;
+
+    Ok(parse_conflict_hunks(&content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TWO_CONFLICTS: &str = "\
+fn main() {
+<<<<<<< HEAD
+    let a = 1;
+=======
+    let a = 2;
+>>>>>>> feature
+    shared();
+<<<<<<< HEAD
+    ours_one();
+    ours_two();
+=======
+    theirs();
+>>>>>>> feature
+}
+";
+
+    #[test]
+    fn test_parse_two_conflict_regions() {
+        let hunks = parse_conflict_hunks(TWO_CONFLICTS);
+
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks[0].ours, "    let a = 1;");
+        assert_eq!(hunks[0].theirs, "    let a = 2;");
+        assert_eq!(hunks[0].ours_label.as_deref(), Some("HEAD"));
+        assert_eq!(hunks[0].theirs_label.as_deref(), Some("feature"));
+        assert_eq!((hunks[0].start_line, hunks[0].end_line), (2, 6));
+        assert!(hunks[0].base.is_none());
+
+        assert_eq!(hunks[1].ours, "    ours_one();\n    ours_two();");
+        assert_eq!(hunks[1].theirs, "    theirs();");
+        assert_eq!((hunks[1].start_line, hunks[1].end_line), (8, 13));
+    }
+
+    #[test]
+    fn test_parse_diff3_base_section() {
+        let content = "<<<<<<< ours\nx = 1\n||||||| base\nx = 0\n=======\nx = 2\n>>>>>>> theirs\n";
+
+        let hunks = parse_conflict_hunks(content);
+
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].base.as_deref(), Some("x = 0"));
+        assert_eq!(hunks[0].ours, "x = 1");
+        assert_eq!(hunks[0].theirs, "x = 2");
+    }
+
+    #[test]
+    fn test_parse_ignores_unterminated_and_lookalike_markers() {
+        let content = "<<<<<<<< not a marker\n<<<<<<< HEAD\nours\n=======\ntheirs\n";
+
+        assert!(parse_conflict_hunks(content).is_empty());
+        assert!(parse_conflict_hunks("no conflicts here\n").is_empty());
+    }
+
+    #[test]
+    fn test_get_conflict_hunks_from_merge() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let git = |args: &[&str]| {
+            crate::shell_utils::new_command("git")
+                .args(args)
+                .current_dir(dir)
+                .output()
+                .expect("Failed to run git")
+        };
+        git(&["init", "-b", "main"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Test User"]);
+        std::fs::write(dir.join("a.txt"), "base\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "Initial commit"]);
+        git(&["checkout", "-b", "feature"]);
+        std::fs::write(dir.join("a.txt"), "feature\n").unwrap();
+        git(&["commit", "-am", "Feature change"]);
+        git(&["checkout", "main"]);
+        std::fs::write(dir.join("a.txt"), "main\n").unwrap();
+        git(&["commit", "-am", "Main change"]);
+        assert!(!git(&["merge", "feature"]).status.success());
+
+        let repo = Repository::open(dir).unwrap();
+        let hunks = get_conflict_hunks(&repo, "a.txt").unwrap();
+
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].ours, "main");
+        assert_eq!(hunks[0].theirs, "feature");
+        assert_eq!((hunks[0].start_line, hunks[0].end_line), (1, 5));
+    }
+}
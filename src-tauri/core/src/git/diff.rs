@@ -1,4 +1,6 @@
-use super::types::{DiffHunk, DiffLine, DiffLineType, FileDiff, GitFileStatus};
+use super::types::{
+    DiffHunk, DiffLine, DiffLineType, FileDiff, GitFileStatus, WordDiffLine, WordSpan,
+};
 use git2::{Diff, DiffOptions, Error as GitError, Repository};
 use lazy_static::lazy_static;
 use lru::LruCache;
@@ -28,7 +30,7 @@ pub fn get_file_diff(repo: &Repository, file_path: &str) -> Result<FileDiff, Git
 }
 
 /// Parses a git2::Diff into our FileDiff structure
-fn parse_diff(diff: Diff, file_path: &str) -> Result<FileDiff, GitError> {
+pub(crate) fn parse_diff(diff: Diff, file_path: &str) -> Result<FileDiff, GitError> {
     use std::cell::RefCell;
     use std::rc::Rc;
 
@@ -37,7 +39,9 @@ fn parse_diff(diff: Diff, file_path: &str) -> Result<FileDiff, GitError> {
     let deletions = Rc::new(RefCell::new(0usize));
     let old_path = Rc::new(RefCell::new(None));
     let status = Rc::new(RefCell::new(GitFileStatus::Modified));
+    let binary_sizes = Rc::new(RefCell::new(None));
 
+    let binary_sizes_clone = binary_sizes.clone();
     let hunks_clone = hunks.clone();
     let additions_clone = additions.clone();
     let deletions_clone = deletions.clone();
@@ -64,7 +68,13 @@ fn parse_diff(diff: Diff, file_path: &str) -> Result<FileDiff, GitError> {
             };
             true
         },
-        None,
+        // libgit2 classifies binaries using `.gitattributes` and a NUL-byte check,
+        // and reports them here instead of through the hunk/line callbacks
+        Some(&mut |delta, _binary| {
+            *binary_sizes_clone.borrow_mut() =
+                Some((delta.old_file().size(), delta.new_file().size()));
+            true
+        }),
         Some(&mut |_delta, hunk| {
             let lines = Vec::new();
 
@@ -116,6 +126,14 @@ fn parse_diff(diff: Diff, file_path: &str) -> Result<FileDiff, GitError> {
     let final_hunks = hunks.borrow().clone();
     let final_additions = *additions.borrow();
     let final_deletions = *deletions.borrow();
+    let final_binary_sizes = *binary_sizes.borrow();
+    let (old_size, new_size) = match final_binary_sizes {
+        Some((old_size, new_size)) => (
+            (!matches!(final_status, GitFileStatus::Added)).then_some(old_size),
+            (!matches!(final_status, GitFileStatus::Deleted)).then_some(new_size),
+        ),
+        None => (None, None),
+    };
 
     Ok(FileDiff {
         path: file_path.to_string(),
@@ -124,6 +142,9 @@ fn parse_diff(diff: Diff, file_path: &str) -> Result<FileDiff, GitError> {
         hunks: final_hunks,
         additions: final_additions,
         deletions: final_deletions,
+        is_binary: final_binary_sizes.is_some(),
+        old_size,
+        new_size,
     })
 }
 
@@ -194,6 +215,199 @@ pub fn get_line_changes(
     Ok(changes)
 }
 
+/// Lines with more tokens than this are highlighted as a whole instead of token-diffed
+const MAX_WORD_DIFF_TOKENS: usize = 500;
+
+/// Gets intra-line changes for a file: every added/deleted line with the
+/// token spans that changed. Within each hunk, the n-th deleted line of a
+/// change block is paired with the n-th added line and their tokens diffed;
+/// unpaired lines are marked as changed in full.
+pub fn get_word_diff(repo: &Repository, file_path: &str) -> Result<Vec<WordDiffLine>, GitError> {
+    let file_diff = get_file_diff(repo, file_path)?;
+    let mut result = Vec::new();
+
+    for hunk in file_diff.hunks {
+        let mut deleted: Vec<DiffLine> = Vec::new();
+        let mut added: Vec<DiffLine> = Vec::new();
+
+        for line in hunk.lines {
+            match line.line_type {
+                DiffLineType::Deletion => {
+                    if !added.is_empty() {
+                        flush_word_diff_block(&mut deleted, &mut added, &mut result);
+                    }
+                    deleted.push(line);
+                }
+                DiffLineType::Addition => added.push(line),
+                DiffLineType::Context => {
+                    flush_word_diff_block(&mut deleted, &mut added, &mut result)
+                }
+            }
+        }
+        flush_word_diff_block(&mut deleted, &mut added, &mut result);
+    }
+
+    Ok(result)
+}
+
+fn word_diff_line(line: DiffLine, spans: Vec<WordSpan>) -> WordDiffLine {
+    WordDiffLine {
+        line_type: line.line_type,
+        old_line_number: line.old_line_number,
+        new_line_number: line.new_line_number,
+        content: line.content,
+        spans,
+    }
+}
+
+fn full_line_span(content: &str) -> Vec<WordSpan> {
+    let len = content.chars().count();
+    if len == 0 {
+        Vec::new()
+    } else {
+        vec![WordSpan { start: 0, end: len }]
+    }
+}
+
+/// Emits one block of consecutive deletions followed by additions
+fn flush_word_diff_block(
+    deleted: &mut Vec<DiffLine>,
+    added: &mut Vec<DiffLine>,
+    result: &mut Vec<WordDiffLine>,
+) {
+    let strip = |mut line: DiffLine| {
+        let trimmed = line.content.trim_end_matches(['\n', '\r']).len();
+        line.content.truncate(trimmed);
+        line
+    };
+    let mut deleted: Vec<DiffLine> = deleted.drain(..).map(strip).collect();
+    let mut added: Vec<DiffLine> = added.drain(..).map(strip).collect();
+
+    let paired = deleted.len().min(added.len());
+    let mut deleted_spans = Vec::with_capacity(deleted.len());
+    let mut added_spans = Vec::with_capacity(added.len());
+    for i in 0..paired {
+        let (old_spans, new_spans) = diff_words(&deleted[i].content, &added[i].content);
+        deleted_spans.push(old_spans);
+        added_spans.push(new_spans);
+    }
+    deleted_spans.extend(deleted[paired..].iter().map(|l| full_line_span(&l.content)));
+    added_spans.extend(added[paired..].iter().map(|l| full_line_span(&l.content)));
+
+    result.extend(
+        deleted
+            .drain(..)
+            .zip(deleted_spans)
+            .map(|(line, spans)| word_diff_line(line, spans)),
+    );
+    result.extend(
+        added
+            .drain(..)
+            .zip(added_spans)
+            .map(|(line, spans)| word_diff_line(line, spans)),
+    );
+}
+
+/// Splits a line into word, whitespace-run and single-punctuation tokens,
+/// returning (start, end) character offsets
+fn tokenize(text: &str) -> Vec<(usize, usize)> {
+    #[derive(PartialEq)]
+    enum Kind {
+        Word,
+        Space,
+        Punct,
+    }
+    let kind = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            Kind::Word
+        } else if c.is_whitespace() {
+            Kind::Space
+        } else {
+            Kind::Punct
+        }
+    };
+
+    let mut tokens: Vec<(usize, usize)> = Vec::new();
+    let mut previous: Option<Kind> = None;
+    for (i, c) in text.chars().enumerate() {
+        let current = kind(c);
+        let extends = current != Kind::Punct && previous.as_ref() == Some(&current);
+        match tokens.last_mut() {
+            Some(last) if extends => last.1 = i + 1,
+            _ => tokens.push((i, i + 1)),
+        }
+        previous = Some(current);
+    }
+    tokens
+}
+
+/// Token-level diff of two lines via longest common subsequence.
+/// Returns the changed spans in the old and new line, with adjacent tokens merged.
+fn diff_words(old: &str, new: &str) -> (Vec<WordSpan>, Vec<WordSpan>) {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+    if old_tokens.len() > MAX_WORD_DIFF_TOKENS || new_tokens.len() > MAX_WORD_DIFF_TOKENS {
+        return (full_line_span(old), full_line_span(new));
+    }
+
+    let old_chars: Vec<char> = old.chars().collect();
+    let new_chars: Vec<char> = new.chars().collect();
+    let old_text: Vec<&[char]> = old_tokens.iter().map(|&(s, e)| &old_chars[s..e]).collect();
+    let new_text: Vec<&[char]> = new_tokens.iter().map(|&(s, e)| &new_chars[s..e]).collect();
+
+    // lcs[i][j] = LCS length of old_text[i..] and new_text[j..]
+    let (n, m) = (old_text.len(), new_text.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_text[i] == new_text[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut old_changed = Vec::new();
+    let mut new_changed = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old_text[i] == new_text[j] {
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            new_changed.push(new_tokens[j]);
+            j += 1;
+        } else {
+            old_changed.push(old_tokens[i]);
+            i += 1;
+        }
+    }
+
+    (merge_spans(old_changed), merge_spans(new_changed))
+}
+
+fn merge_spans(tokens: Vec<(usize, usize)>) -> Vec<WordSpan> {
+    let mut spans: Vec<WordSpan> = Vec::new();
+    for (start, end) in tokens {
+        match spans.last_mut() {
+            Some(last) if last.end == start => last.end = end,
+            _ => spans.push(WordSpan { start, end }),
+        }
+    }
+    spans
+}
+
+/// Drops cached line changes for files whose contents were changed by us
+pub(crate) fn invalidate_line_changes(repo: &Repository, file_paths: &[String]) {
+    let repo_path = repo.path().to_string_lossy().to_string();
+    if let Ok(mut cache) = LINE_CHANGES_CACHE.lock() {
+        for file_path in file_paths {
+            cache.pop(&format!("{}:{}", repo_path, file_path));
+        }
+    }
+}
+
 /// Generates raw diff text for all changed files (working directory vs HEAD)
 /// Returns a string similar to `git diff` output, suitable for AI processing
 pub fn get_raw_diff_text(repo: &Repository) -> Result<String, GitError> {
@@ -210,12 +424,13 @@ pub fn get_raw_diff_text(repo: &Repository) -> Result<String, GitError> {
 }
 
 /// Formats a git2::Diff as human-readable text similar to `git diff` output
-fn format_diff_as_text(diff: Diff) -> Result<String, GitError> {
+pub(crate) fn format_diff_as_text(diff: Diff) -> Result<String, GitError> {
     use std::cell::RefCell;
     use std::rc::Rc;
 
     let output = Rc::new(RefCell::new(String::new()));
     let output_file = output.clone();
+    let output_binary = output.clone();
     let output_hunk = output.clone();
     let output_line = output.clone();
 
@@ -258,7 +473,10 @@ fn format_diff_as_text(diff: Diff) -> Result<String, GitError> {
 
             true
         },
-        None,
+        Some(&mut |_delta, _binary| {
+            output_binary.borrow_mut().push_str("Binary files differ\n");
+            true
+        }),
         Some(&mut |_delta, hunk| {
             let mut out = output_hunk.borrow_mut();
             out.push_str(&String::from_utf8_lossy(hunk.header()));
@@ -610,4 +828,75 @@ mod tests {
         assert!(diff_text.contains("README.md"), "Should contain README.md");
         assert!(diff_text.contains("code.rs"), "Should contain code.rs");
     }
+
+    #[test]
+    fn test_diff_words_marks_only_changed_token() {
+        let (old, new) = diff_words("let value = compute(1);", "let value = compute(2);");
+
+        assert_eq!(old, vec![WordSpan { start: 20, end: 21 }]);
+        assert_eq!(new, vec![WordSpan { start: 20, end: 21 }]);
+    }
+
+    #[test]
+    fn test_get_word_diff_single_token_change() {
+        let temp_dir = create_temp_git_repo_with_commit();
+        let readme = temp_dir.path().join("README.md");
+        std::fs::write(&readme, "# Initial\nLine two\nLine 3\nExtra line\n").unwrap();
+
+        let repo = Repository::open(temp_dir.path()).unwrap();
+        let lines = get_word_diff(&repo, "README.md").unwrap();
+
+        assert_eq!(lines.len(), 3);
+        assert!(matches!(lines[0].line_type, DiffLineType::Deletion));
+        assert_eq!(lines[0].content, "Line 2");
+        assert_eq!(lines[0].spans, vec![WordSpan { start: 5, end: 6 }]);
+        assert!(matches!(lines[1].line_type, DiffLineType::Addition));
+        assert_eq!(lines[1].content, "Line two");
+        assert_eq!(lines[1].spans, vec![WordSpan { start: 5, end: 8 }]);
+
+        // An added line with no deleted counterpart is highlighted in full
+        assert_eq!(lines[2].content, "Extra line");
+        assert_eq!(lines[2].spans, vec![WordSpan { start: 0, end: 10 }]);
+    }
+
+    #[test]
+    fn test_binary_file_diff_is_flagged_without_hunks() {
+        let temp_dir = create_temp_git_repo_with_commit();
+        let image = temp_dir.path().join("image.bin");
+        std::fs::write(&image, [0u8, 1, 2, 3, 0, 255]).unwrap();
+        crate::shell_utils::new_command("git")
+            .args(["add", "image.bin"])
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+
+        let repo = Repository::open(temp_dir.path()).unwrap();
+        let diff = get_file_diff(&repo, "image.bin").unwrap();
+
+        assert!(diff.is_binary);
+        assert!(diff.hunks.is_empty());
+        assert_eq!(diff.additions, 0);
+        assert_eq!(diff.old_size, None);
+        assert_eq!(diff.new_size, Some(6));
+
+        let text = get_raw_diff_text(&repo).unwrap();
+        assert!(text.contains("Binary files differ"));
+    }
+
+    #[test]
+    fn test_gitattributes_marks_text_file_binary() {
+        let temp_dir = create_temp_git_repo_with_commit();
+        std::fs::write(temp_dir.path().join(".gitattributes"), "*.dat binary\n").unwrap();
+        std::fs::write(temp_dir.path().join("data.dat"), "plain text\n").unwrap();
+        crate::shell_utils::new_command("git")
+            .args(["add", "."])
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+
+        let repo = Repository::open(temp_dir.path()).unwrap();
+
+        assert!(get_file_diff(&repo, "data.dat").unwrap().is_binary);
+        assert!(!get_file_diff(&repo, ".gitattributes").unwrap().is_binary);
+    }
 }
@@ -0,0 +1,250 @@
+use super::diff::{format_diff_as_text, parse_diff};
+use super::types::{CommitDetail, CommitInfo};
+use git2::{Commit, DiffOptions, Error as GitError, Repository, Sort, Tree};
+
+/// Walks history from HEAD, newest first.
+///
+/// `skip` and `limit` apply after path filtering, so pages stay full when
+/// `path_filter` excludes commits. A repository without commits yields an
+/// empty list.
+pub fn get_commit_log(
+    repo: &Repository,
+    limit: usize,
+    skip: usize,
+    path_filter: Option<&str>,
+) -> Result<Vec<CommitInfo>, GitError> {
+    if repo.head().is_err() {
+        return Ok(Vec::new());
+    }
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+    revwalk.push_head()?;
+
+    let mut commits = Vec::new();
+    let mut skipped = 0;
+    for oid in revwalk {
+        if commits.len() >= limit {
+            break;
+        }
+        let commit = repo.find_commit(oid?)?;
+        if let Some(path) = path_filter {
+            if !commit_touches_path(repo, &commit, path)? {
+                continue;
+            }
+        }
+        if skipped < skip {
+            skipped += 1;
+            continue;
+        }
+        commits.push(commit_info(&commit));
+    }
+
+    Ok(commits)
+}
+
+/// Shows one commit (full or abbreviated hash, or any revspec) with its diff
+/// against the first parent. Root commits are diffed against an empty tree.
+pub fn show_commit(repo: &Repository, commit_hash: &str) -> Result<CommitDetail, GitError> {
+    let commit = repo.revparse_single(commit_hash)?.peel_to_commit()?;
+    let tree = commit.tree()?;
+    let parent_tree = first_parent_tree(&commit)?;
+
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+    let paths: Vec<String> = diff
+        .deltas()
+        .filter_map(|delta| {
+            delta
+                .new_file()
+                .path()
+                .or_else(|| delta.old_file().path())
+                .and_then(|path| path.to_str())
+                .map(|path| path.to_string())
+        })
+        .collect();
+
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths {
+        let mut options = DiffOptions::new();
+        options.pathspec(&path).disable_pathspec_match(true);
+        let file_diff =
+            repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut options))?;
+        files.push(parse_diff(file_diff, &path)?);
+    }
+
+    Ok(CommitDetail {
+        commit: commit_info(&commit),
+        files,
+        diff_text: format_diff_as_text(diff)?,
+    })
+}
+
+fn first_parent_tree<'repo>(commit: &Commit<'repo>) -> Result<Option<Tree<'repo>>, GitError> {
+    match commit.parent(0) {
+        Ok(parent) => Ok(Some(parent.tree()?)),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Whether the commit changed `path` (a file or directory) relative to its first parent
+fn commit_touches_path(repo: &Repository, commit: &Commit, path: &str) -> Result<bool, GitError> {
+    let tree = commit.tree()?;
+    let parent_tree = first_parent_tree(commit)?;
+
+    let mut options = DiffOptions::new();
+    options.pathspec(path);
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut options))?;
+    Ok(diff.deltas().len() > 0)
+}
+
+fn commit_info(commit: &Commit) -> CommitInfo {
+    let hash = commit.id().to_string();
+    let author = commit.author();
+    let body = commit
+        .body()
+        .map(|body| body.trim().to_string())
+        .filter(|body| !body.is_empty());
+
+    CommitInfo {
+        short_hash: hash[..7].to_string(),
+        hash,
+        author: author.name().unwrap_or("").to_string(),
+        email: author.email().unwrap_or("").to_string(),
+        time: author.when().seconds(),
+        summary: commit.summary().unwrap_or("").to_string(),
+        body,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::git;
+    use crate::git::types::GitFileStatus;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn commit_file(dir: &Path, file: &str, content: &str, message: &str) {
+        std::fs::write(dir.join(file), content).unwrap();
+        git(dir, &["add", file]);
+        git(dir, &["commit", "-m", message]);
+    }
+
+    /// Repo with four commits: README, main.rs, README again, lib.rs
+    fn create_repo_with_history() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        git(dir, &["init"]);
+        git(dir, &["config", "user.email", "test@example.com"]);
+        git(dir, &["config", "user.name", "Test User"]);
+
+        commit_file(dir, "README.md", "# Test", "Add readme");
+        commit_file(dir, "main.rs", "fn main() {}", "Add main");
+        commit_file(
+            dir,
+            "README.md",
+            "# Test\nMore",
+            "Expand readme\n\nAdds a second line.",
+        );
+        commit_file(dir, "lib.rs", "pub fn lib() {}", "Add lib");
+        temp_dir
+    }
+
+    fn summaries(commits: &[CommitInfo]) -> Vec<&str> {
+        commits.iter().map(|c| c.summary.as_str()).collect()
+    }
+
+    #[test]
+    fn test_log_is_newest_first() {
+        let temp_dir = create_repo_with_history();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+
+        let commits = get_commit_log(&repo, 50, 0, None).unwrap();
+        assert_eq!(
+            summaries(&commits),
+            vec!["Add lib", "Expand readme", "Add main", "Add readme"]
+        );
+        assert_eq!(commits[0].author, "Test User");
+        assert_eq!(commits[0].email, "test@example.com");
+        assert_eq!(commits[0].short_hash, &commits[0].hash[..7]);
+        assert_eq!(commits[1].body.as_deref(), Some("Adds a second line."));
+        assert_eq!(commits[0].body, None);
+    }
+
+    #[test]
+    fn test_log_pagination() {
+        let temp_dir = create_repo_with_history();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+
+        let first_page = get_commit_log(&repo, 2, 0, None).unwrap();
+        let second_page = get_commit_log(&repo, 2, 2, None).unwrap();
+        let past_end = get_commit_log(&repo, 2, 10, None).unwrap();
+
+        assert_eq!(summaries(&first_page), vec!["Add lib", "Expand readme"]);
+        assert_eq!(summaries(&second_page), vec!["Add main", "Add readme"]);
+        assert!(past_end.is_empty());
+    }
+
+    #[test]
+    fn test_log_path_filter() {
+        let temp_dir = create_repo_with_history();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+
+        let readme = get_commit_log(&repo, 50, 0, Some("README.md")).unwrap();
+        assert_eq!(summaries(&readme), vec!["Expand readme", "Add readme"]);
+
+        let readme_page = get_commit_log(&repo, 1, 1, Some("README.md")).unwrap();
+        assert_eq!(summaries(&readme_page), vec!["Add readme"]);
+    }
+
+    #[test]
+    fn test_show_commit_diff() {
+        let temp_dir = create_repo_with_history();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+        let commits = get_commit_log(&repo, 50, 0, None).unwrap();
+        let expand_readme = &commits[1];
+
+        let detail = show_commit(&repo, &expand_readme.short_hash).unwrap();
+        assert_eq!(detail.commit.hash, expand_readme.hash);
+        assert_eq!(detail.files.len(), 1);
+        let file = &detail.files[0];
+        assert_eq!(file.path, "README.md");
+        assert!(matches!(file.status, GitFileStatus::Modified));
+        assert!(file.additions >= 1);
+        assert!(detail.diff_text.contains("+More"));
+        assert!(!detail.diff_text.contains("main.rs"));
+    }
+
+    #[test]
+    fn test_show_root_commit() {
+        let temp_dir = create_repo_with_history();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+        let commits = get_commit_log(&repo, 50, 0, None).unwrap();
+        let root = commits.last().unwrap();
+
+        let detail = show_commit(&repo, &root.hash).unwrap();
+        assert_eq!(detail.commit.summary, "Add readme");
+        assert_eq!(detail.files.len(), 1);
+        assert_eq!(detail.files[0].path, "README.md");
+        assert!(matches!(detail.files[0].status, GitFileStatus::Added));
+        assert_eq!(detail.files[0].additions, 1);
+        assert!(detail.diff_text.contains("+# Test"));
+    }
+
+    #[test]
+    fn test_show_unknown_commit() {
+        let temp_dir = create_repo_with_history();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+
+        assert!(show_commit(&repo, "0000000000000000000000000000000000000000").is_err());
+    }
+
+    #[test]
+    fn test_log_empty_repository() {
+        let temp_dir = TempDir::new().unwrap();
+        git(temp_dir.path(), &["init"]);
+        let repo = Repository::open(temp_dir.path()).unwrap();
+
+        assert!(get_commit_log(&repo, 10, 0, None).unwrap().is_empty());
+    }
+}
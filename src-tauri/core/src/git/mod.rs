@@ -1,10 +1,21 @@
+pub mod branches;
+pub mod changes;
+pub mod conflicts;
 pub mod diff;
+pub mod history;
+pub mod remote;
 pub mod repository;
 pub mod status;
+#[cfg(test)]
+pub(crate) mod test_support;
 pub mod types;
 pub mod worktree;
 
-use types::{DiffLineType, FileDiff, GitFileStatus, GitStatus};
+use remote::{FetchResult, PullResult, PushResult};
+use types::{
+    BranchEntry, BranchInfo, CommitDetail, CommitInfo, ConflictHunk, DiffLineType, FileDiff,
+    GitFileStatus, GitStatus, WordDiffLine,
+};
 use worktree::{MergeResult, SyncResult, WorktreeChanges, WorktreeInfo, WorktreePoolStatus};
 
 /// Gets the Git status for a repository at the given path
@@ -89,6 +100,200 @@ pub async fn git_get_raw_diff_text(repo_path: String) -> Result<String, String>
     diff::get_raw_diff_text(&repo).map_err(|e| format!("Failed to get raw diff text: {}", e))
 }
 
+/// Gets commit history (newest first), optionally only commits touching `path_filter`
+#[tauri::command]
+pub async fn git_log(
+    repo_path: String,
+    limit: Option<usize>,
+    skip: Option<usize>,
+    path_filter: Option<String>,
+) -> Result<Vec<CommitInfo>, String> {
+    let repo = repository::discover_repository(&repo_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    // Accept absolute paths by making them relative to the repo root
+    let repo_root = repository::get_repository_root(&repo).unwrap_or_default();
+    let path_filter = path_filter.filter(|p| !p.is_empty()).map(|path| {
+        if !repo_root.is_empty() && path.starts_with(&repo_root) {
+            path[repo_root.len()..].trim_start_matches('/').to_string()
+        } else {
+            path
+        }
+    });
+
+    history::get_commit_log(
+        &repo,
+        limit.unwrap_or(50),
+        skip.unwrap_or(0),
+        path_filter.as_deref(),
+    )
+    .map_err(|e| format!("Failed to get commit log: {}", e))
+}
+
+/// Gets a single commit's metadata and its diff against the first parent
+#[tauri::command]
+pub async fn git_show(repo_path: String, commit_hash: String) -> Result<CommitDetail, String> {
+    let repo = repository::discover_repository(&repo_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    history::show_commit(&repo, &commit_hash)
+        .map_err(|e| format!("Failed to show commit {}: {}", commit_hash, e))
+}
+
+/// Discards working-tree changes to the given files, restoring them to HEAD.
+/// Returns the repo-relative paths that were reverted.
+#[tauri::command]
+pub async fn git_checkout_file(
+    repo_path: String,
+    file_paths: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let repo = repository::discover_repository(&repo_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    changes::checkout_files(&repo, &file_paths)
+        .map_err(|e| format!("Failed to revert files: {}", e))
+}
+
+/// Stages files and returns the updated file statuses
+#[tauri::command]
+pub async fn git_stage_files(
+    repo_path: String,
+    paths: Vec<String>,
+) -> Result<std::collections::HashMap<String, (GitFileStatus, bool)>, String> {
+    let repo = repository::discover_repository(&repo_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    changes::stage_files(&repo, &paths).map_err(|e| format!("Failed to stage files: {}", e))?;
+    status::get_all_file_statuses(&repo)
+        .map_err(|e| format!("Failed to get all file statuses: {}", e))
+}
+
+/// Unstages files (keeping working-tree changes) and returns the updated file statuses
+#[tauri::command]
+pub async fn git_unstage_files(
+    repo_path: String,
+    paths: Vec<String>,
+) -> Result<std::collections::HashMap<String, (GitFileStatus, bool)>, String> {
+    let repo = repository::discover_repository(&repo_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    changes::unstage_files(&repo, &paths).map_err(|e| format!("Failed to unstage files: {}", e))?;
+    status::get_all_file_statuses(&repo)
+        .map_err(|e| format!("Failed to get all file statuses: {}", e))
+}
+
+/// Commits the staged changes in the repository, returning the new commit hash.
+/// `author` (`Name <email>`) overrides the configured author.
+#[tauri::command]
+pub async fn git_commit(
+    repo_path: String,
+    message: String,
+    amend: bool,
+    author: Option<String>,
+) -> Result<String, String> {
+    let repo = repository::discover_repository(&repo_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    changes::commit_index(&repo, &message, amend, author.as_deref())
+        .map_err(|e| format!("Failed to commit: {}", e))
+}
+
+/// Lists local and remote-tracking branches, marking the current one
+#[tauri::command]
+pub async fn git_list_branches(repo_path: String) -> Result<Vec<BranchEntry>, String> {
+    let repo = repository::discover_repository(&repo_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    branches::list_branches(&repo).map_err(|e| format!("Failed to list branches: {}", e))
+}
+
+/// Creates a branch at `from_ref` (HEAD when omitted)
+#[tauri::command]
+pub async fn git_create_branch(
+    repo_path: String,
+    name: String,
+    from_ref: Option<String>,
+) -> Result<(), String> {
+    let repo = repository::discover_repository(&repo_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    branches::create_branch(&repo, &name, from_ref.as_deref())
+        .map_err(|e| format!("Failed to create branch: {}", e))
+}
+
+/// Switches to a local branch and returns the new current branch
+#[tauri::command]
+pub async fn git_switch_branch(repo_path: String, name: String) -> Result<BranchInfo, String> {
+    let repo = repository::discover_repository(&repo_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    branches::switch_branch(&repo, &name).map_err(|e| format!("Failed to switch branch: {}", e))?;
+    repository::get_current_branch(&repo)
+        .map_err(|e| format!("Failed to get current branch: {}", e))
+}
+
+/// Deletes a local branch; unmerged branches require `force`
+#[tauri::command]
+pub async fn git_delete_branch(repo_path: String, name: String, force: bool) -> Result<(), String> {
+    let repo = repository::discover_repository(&repo_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    branches::delete_branch(&repo, &name, force)
+        .map_err(|e| format!("Failed to delete branch: {}", e))
+}
+
+/// Gets token-level changes for each added/deleted line of a file
+#[tauri::command]
+pub async fn git_get_word_diff(
+    repo_path: String,
+    file_path: String,
+) -> Result<Vec<WordDiffLine>, String> {
+    let repo = repository::discover_repository(&repo_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    diff::get_word_diff(&repo, &file_path).map_err(|e| format!("Failed to get word diff: {}", e))
+}
+
+/// Parses the conflict markers in a conflicted file into hunks
+#[tauri::command]
+pub async fn git_get_conflict_hunks(
+    repo_path: String,
+    file_path: String,
+) -> Result<Vec<ConflictHunk>, String> {
+    let repo = repository::discover_repository(&repo_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    conflicts::get_conflict_hunks(&repo, &file_path)
+        .map_err(|e| format!("Failed to get conflict hunks: {}", e))
+}
+
+/// Fetches from a remote (`origin` when omitted)
+#[tauri::command]
+pub async fn git_fetch(repo_path: String, remote: Option<String>) -> Result<FetchResult, String> {
+    remote::fetch(&repo_path, remote.as_deref())
+}
+
+/// Pulls a branch from a remote (current branch from `origin` when omitted)
+#[tauri::command]
+pub async fn git_pull(
+    repo_path: String,
+    remote: Option<String>,
+    branch: Option<String>,
+) -> Result<PullResult, String> {
+    remote::pull(&repo_path, remote.as_deref(), branch.as_deref())
+}
+
+/// Pushes a branch to a remote (current branch to `origin` when omitted)
+#[tauri::command]
+pub async fn git_push(
+    repo_path: String,
+    remote: Option<String>,
+    branch: Option<String>,
+    force: bool,
+) -> Result<PushResult, String> {
+    remote::push(&repo_path, remote.as_deref(), branch.as_deref(), force)
+}
+
 // ============================================================================
 // Worktree Commands
 // ============================================================================
@@ -0,0 +1,409 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::process::Output;
+
+/// Remote used when none is given
+pub const DEFAULT_REMOTE: &str = "origin";
+
+/// Result of a fetch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchResult {
+    /// Remote that was fetched
+    pub remote: String,
+    /// Remote-tracking branches that were created or moved (e.g. `origin/main`)
+    pub updated_refs: Vec<String>,
+    /// Human-readable message about the result
+    pub message: String,
+}
+
+/// Result of a pull (fetch + merge)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PullResult {
+    /// Whether the pull completed without conflicts
+    pub success: bool,
+    /// Whether the merge was a fast-forward
+    pub fast_forward: bool,
+    /// Whether HEAD was already up to date
+    pub up_to_date: bool,
+    /// HEAD commit after the pull
+    pub head_commit: Option<String>,
+    /// Whether there are conflicts
+    pub has_conflicts: bool,
+    /// List of files with conflicts
+    pub conflicted_files: Vec<String>,
+    /// Human-readable message about the result
+    pub message: String,
+}
+
+/// Result of a push
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PushResult {
+    /// Whether the remote was updated
+    pub success: bool,
+    /// Whether the remote rejected the push (e.g. non-fast-forward)
+    pub rejected: bool,
+    /// Human-readable message about the result
+    pub message: String,
+}
+
+/// Runs git in `repo_path`. Terminal prompts are disabled so a missing
+/// credential fails instead of hanging; the user's credential helper still applies.
+/// Output is kept in the C locale so it can be inspected.
+fn run_git(repo_path: &str, args: &[&str]) -> Result<Output, String> {
+    crate::shell_utils::new_command("git")
+        .args(args)
+        .current_dir(repo_path)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("LC_ALL", "C")
+        .output()
+        .map_err(|e| format!("Failed to run git {}: {}", args.join(" "), e))
+}
+
+/// Reject remote and branch names git would parse as options
+fn validate_name(kind: &str, name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err(format!("{} name is empty", kind));
+    }
+    if name.starts_with('-') {
+        return Err(format!("Invalid {} name: {}", kind.to_lowercase(), name));
+    }
+    Ok(())
+}
+
+/// Remote to use, validated
+fn resolve_remote(remote: Option<&str>) -> Result<&str, String> {
+    let remote = remote.unwrap_or(DEFAULT_REMOTE);
+    validate_name("Remote", remote)?;
+    Ok(remote)
+}
+
+/// Branch to use (the current one when not given), validated
+fn resolve_branch(repo_path: &str, branch: Option<&str>) -> Result<String, String> {
+    let branch = match branch {
+        Some(branch) => branch.to_string(),
+        None => current_branch(repo_path)?,
+    };
+    validate_name("Branch", &branch)?;
+    Ok(branch)
+}
+
+fn stderr_text(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).trim().to_string()
+}
+
+fn rev_parse(repo_path: &str, rev: &str) -> Option<String> {
+    run_git(repo_path, &["rev-parse", "--verify", "--quiet", rev])
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+}
+
+fn head_commit(repo_path: &str) -> Option<String> {
+    rev_parse(repo_path, "HEAD")
+}
+
+fn current_branch(repo_path: &str) -> Result<String, String> {
+    let output = run_git(repo_path, &["rev-parse", "--abbrev-ref", "HEAD"])?;
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || branch.is_empty() || branch == "HEAD" {
+        return Err("No branch is checked out (detached HEAD)".to_string());
+    }
+    Ok(branch)
+}
+
+/// Remote-tracking refs for `remote`, keyed by short name
+fn remote_refs(repo_path: &str, remote: &str) -> HashMap<String, String> {
+    let pattern = format!("refs/remotes/{}", remote);
+    run_git(
+        repo_path,
+        &[
+            "for-each-ref",
+            "--format=%(refname:short) %(objectname)",
+            &pattern,
+        ],
+    )
+    .map(|o| {
+        String::from_utf8_lossy(&o.stdout)
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .map(|(name, oid)| (name.to_string(), oid.to_string()))
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+fn conflicted_files(repo_path: &str) -> Vec<String> {
+    run_git(repo_path, &["diff", "--name-only", "--diff-filter=U"])
+        .map(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .map(|s| s.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Fetch from a remote, pruning deleted branches
+pub fn fetch(repo_path: &str, remote: Option<&str>) -> Result<FetchResult, String> {
+    let remote = resolve_remote(remote)?;
+    let before = remote_refs(repo_path, remote);
+
+    let output = run_git(repo_path, &["fetch", "--prune", "--", remote])?;
+    if !output.status.success() {
+        return Err(format!("Fetch failed: {}", stderr_text(&output)));
+    }
+
+    let mut updated_refs: Vec<String> = remote_refs(repo_path, remote)
+        .into_iter()
+        .filter(|(name, oid)| before.get(name) != Some(oid))
+        .map(|(name, _)| name)
+        .collect();
+    updated_refs.sort();
+
+    let message = if updated_refs.is_empty() {
+        "Already up to date".to_string()
+    } else {
+        format!("Updated {}", updated_refs.join(", "))
+    };
+
+    Ok(FetchResult {
+        remote: remote.to_string(),
+        updated_refs,
+        message,
+    })
+}
+
+/// Pull a branch (defaults to the current one) from a remote, merging rather than rebasing
+pub fn pull(
+    repo_path: &str,
+    remote: Option<&str>,
+    branch: Option<&str>,
+) -> Result<PullResult, String> {
+    let remote = resolve_remote(remote)?;
+    let branch = resolve_branch(repo_path, branch)?;
+    let before = head_commit(repo_path);
+
+    let output = run_git(
+        repo_path,
+        &["pull", "--no-rebase", "--no-edit", "--", remote, &branch],
+    )?;
+
+    if !output.status.success() {
+        let conflicted_files = conflicted_files(repo_path);
+        if !conflicted_files.is_empty() {
+            return Ok(PullResult {
+                success: false,
+                fast_forward: false,
+                up_to_date: false,
+                head_commit: head_commit(repo_path),
+                has_conflicts: true,
+                conflicted_files,
+                message: "Pull has conflicts. Please resolve manually.".to_string(),
+            });
+        }
+        return Err(format!("Pull failed: {}", stderr_text(&output)));
+    }
+
+    let after = head_commit(repo_path);
+    let up_to_date = before == after;
+    // A fast-forward moves HEAD onto the fetched commit; a merge creates a new one
+    let fast_forward =
+        !up_to_date && after.is_some() && after == rev_parse(repo_path, "FETCH_HEAD");
+    let message = if up_to_date {
+        "Already up to date".to_string()
+    } else if fast_forward {
+        format!("Fast-forwarded to {}/{}", remote, branch)
+    } else {
+        format!("Merged {}/{}", remote, branch)
+    };
+
+    Ok(PullResult {
+        success: true,
+        fast_forward,
+        up_to_date,
+        head_commit: after,
+        has_conflicts: false,
+        conflicted_files: vec![],
+        message,
+    })
+}
+
+/// Push a branch (defaults to the current one) to a remote. `force` uses
+/// `--force-with-lease`, so commits pushed by others since the last fetch
+/// are never overwritten.
+pub fn push(
+    repo_path: &str,
+    remote: Option<&str>,
+    branch: Option<&str>,
+    force: bool,
+) -> Result<PushResult, String> {
+    let remote = resolve_remote(remote)?;
+    let branch = resolve_branch(repo_path, branch)?;
+
+    let mut args = vec!["push", "--porcelain"];
+    if force {
+        args.push("--force-with-lease");
+    }
+    args.extend(["--", remote, branch.as_str()]);
+
+    let output = run_git(repo_path, &args)?;
+    if !output.status.success() {
+        // Porcelain output flags each rejected ref with `!`
+        let rejected = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .any(|line| line.starts_with('!'));
+        if rejected {
+            return Ok(PushResult {
+                success: false,
+                rejected: true,
+                message: format!(
+                    "Push to {}/{} was rejected; pull the remote changes first",
+                    remote, branch
+                ),
+            });
+        }
+        return Err(format!("Push failed: {}", stderr_text(&output)));
+    }
+
+    Ok(PushResult {
+        success: true,
+        rejected: false,
+        message: format!("Pushed {} to {}", branch, remote),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::git;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn configure_user(dir: &Path) {
+        git(dir, &["config", "user.email", "test@example.com"]);
+        git(dir, &["config", "user.name", "Test User"]);
+    }
+
+    fn commit_file(dir: &Path, file: &str, content: &str, message: &str) {
+        std::fs::write(dir.join(file), content).unwrap();
+        git(dir, &["add", file]);
+        git(dir, &["commit", "-m", message]);
+    }
+
+    /// A bare `remote.git` plus two clones of it, `local` and `other`
+    fn create_remote_setup() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        git(root, &["init", "--bare", "-b", "main", "remote.git"]);
+
+        let local = root.join("local");
+        std::fs::create_dir(&local).unwrap();
+        git(&local, &["init", "-b", "main"]);
+        configure_user(&local);
+        git(&local, &["remote", "add", "origin", "../remote.git"]);
+        commit_file(&local, "a.txt", "a\n", "Initial commit");
+        git(&local, &["push", "-u", "origin", "main"]);
+
+        git(root, &["clone", "remote.git", "other"]);
+        configure_user(&root.join("other"));
+        temp_dir
+    }
+
+    fn path_str(path: &Path) -> String {
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_push_updates_remote() {
+        let temp_dir = create_remote_setup();
+        let local = temp_dir.path().join("local");
+        commit_file(&local, "b.txt", "b\n", "Add b");
+
+        let result = push(&path_str(&local), None, None, false).unwrap();
+
+        assert!(result.success);
+        let remote_head = git(&temp_dir.path().join("remote.git"), &["rev-parse", "main"]);
+        assert_eq!(remote_head, git(&local, &["rev-parse", "HEAD"]));
+    }
+
+    #[test]
+    fn test_fetch_and_fast_forward_pull_retrieve_new_commits() {
+        let temp_dir = create_remote_setup();
+        let local = temp_dir.path().join("local");
+        let other = temp_dir.path().join("other");
+        commit_file(&other, "b.txt", "b\n", "Add b");
+        git(&other, &["push", "origin", "main"]);
+        let pushed = git(&other, &["rev-parse", "HEAD"]);
+
+        let fetched = fetch(&path_str(&local), Some("origin")).unwrap();
+        assert_eq!(fetched.updated_refs, vec!["origin/main"]);
+        assert_eq!(git(&local, &["rev-parse", "origin/main"]), pushed);
+        assert!(fetch(&path_str(&local), None)
+            .unwrap()
+            .updated_refs
+            .is_empty());
+
+        let pulled = pull(&path_str(&local), None, Some("main")).unwrap();
+        assert!(pulled.success);
+        assert!(pulled.fast_forward);
+        assert_eq!(pulled.head_commit, Some(pushed));
+
+        let again = pull(&path_str(&local), None, None).unwrap();
+        assert!(again.up_to_date);
+        assert!(!again.fast_forward);
+    }
+
+    #[test]
+    fn test_pull_reports_conflicts() {
+        let temp_dir = create_remote_setup();
+        let local = temp_dir.path().join("local");
+        let other = temp_dir.path().join("other");
+        commit_file(&other, "a.txt", "theirs\n", "Change a remotely");
+        git(&other, &["push", "origin", "main"]);
+        commit_file(&local, "a.txt", "ours\n", "Change a locally");
+
+        let result = pull(&path_str(&local), None, None).unwrap();
+
+        assert!(!result.success);
+        assert!(result.has_conflicts);
+        assert_eq!(result.conflicted_files, vec!["a.txt"]);
+    }
+
+    #[test]
+    fn test_rejects_option_like_names() {
+        let temp_dir = create_remote_setup();
+        let local = path_str(&temp_dir.path().join("local"));
+
+        let error = fetch(&local, Some("--upload-pack=touch pwned")).unwrap_err();
+        assert!(error.starts_with("Invalid remote name"));
+        let error = pull(&local, None, Some("-q")).unwrap_err();
+        assert!(error.starts_with("Invalid branch name"));
+        let error = push(&local, Some("--mirror"), None, false).unwrap_err();
+        assert!(error.starts_with("Invalid remote name"));
+        assert!(!temp_dir.path().join("local").join("pwned").exists());
+    }
+
+    #[test]
+    fn test_push_rejected_when_behind_unless_forced() {
+        let temp_dir = create_remote_setup();
+        let local = temp_dir.path().join("local");
+        let other = temp_dir.path().join("other");
+        commit_file(&other, "b.txt", "b\n", "Add b");
+        git(&other, &["push", "origin", "main"]);
+        commit_file(&local, "c.txt", "c\n", "Add c");
+
+        let result = push(&path_str(&local), None, None, false).unwrap();
+        assert!(!result.success);
+        assert!(result.rejected);
+
+        // The lease only holds once the remote commit has been fetched
+        fetch(&path_str(&local), None).unwrap();
+        let forced = push(&path_str(&local), None, None, true).unwrap();
+        assert!(forced.success);
+        let remote_head = git(&temp_dir.path().join("remote.git"), &["rev-parse", "main"]);
+        assert_eq!(remote_head, git(&local, &["rev-parse", "HEAD"]));
+    }
+}
@@ -1,6 +1,6 @@
 use super::repository::get_current_branch;
-use super::types::{FileStatus, GitFileStatus, GitStatus};
-use git2::{Error as GitError, Repository, Status, StatusOptions};
+use super::types::{FileStatus, GitFileStatus, GitStatus, SubmoduleStatus};
+use git2::{Error as GitError, Repository, Status, StatusOptions, SubmoduleIgnore};
 
 /// Gets the Git status of the repository
 pub fn get_repository_status(repo: &Repository) -> Result<GitStatus, GitError> {
@@ -63,6 +63,11 @@ pub fn get_repository_status(repo: &Repository) -> Result<GitStatus, GitError> {
 
     let branch = get_current_branch(repo).ok();
 
+    let submodules = get_submodule_statuses(repo).unwrap_or_else(|e| {
+        log::warn!("Failed to get submodule statuses: {}", e);
+        Vec::new()
+    });
+
     Ok(GitStatus {
         branch,
         modified,
@@ -70,9 +75,40 @@ pub fn get_repository_status(repo: &Repository) -> Result<GitStatus, GitError> {
         untracked,
         conflicted,
         changes_count,
+        submodules,
     })
 }
 
+/// Gets the state of every submodule registered in the repository
+pub fn get_submodule_statuses(repo: &Repository) -> Result<Vec<SubmoduleStatus>, GitError> {
+    let mut result = Vec::new();
+
+    for submodule in repo.submodules()? {
+        let path = submodule.path().to_string_lossy().replace('\\', "/");
+        let name = submodule.name().unwrap_or(&path).to_string();
+        let status = repo.submodule_status(&name, SubmoduleIgnore::None)?;
+
+        let is_uninitialized = status.is_wd_uninitialized();
+        let is_modified = !is_uninitialized
+            && (status.is_wd_modified()
+                || status.is_wd_index_modified()
+                || status.is_wd_wd_modified()
+                || status.is_wd_untracked()
+                || status.is_index_modified());
+
+        result.push(SubmoduleStatus {
+            path,
+            head: submodule.workdir_id().map(|oid| oid.to_string()),
+            recorded_head: submodule.head_id().map(|oid| oid.to_string()),
+            is_modified,
+            is_uninitialized,
+        });
+    }
+
+    result.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(result)
+}
+
 /// Converts git2::Status to GitFileStatus
 fn status_to_git_file_status(status: Status, is_staged: bool) -> GitFileStatus {
     if is_staged {
@@ -144,6 +180,7 @@ pub fn get_all_file_statuses(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::git::test_support::git as run_git;
 
     use tempfile::TempDir;
 
@@ -364,4 +401,52 @@ mod tests {
         let branch = status.branch.unwrap();
         assert!(branch.name == "main" || branch.name == "master");
     }
+
+    #[test]
+    fn test_get_repository_status_reports_submodules() {
+        let sub_dir = create_temp_git_repo_with_commit();
+        let temp_dir = create_temp_git_repo_with_commit();
+        let parent = temp_dir.path();
+        let sub_url = sub_dir.path().to_string_lossy().to_string();
+        run_git(
+            parent,
+            &[
+                "-c",
+                "protocol.file.allow=always",
+                "submodule",
+                "add",
+                &sub_url,
+                "libs/sub",
+            ],
+        );
+        run_git(parent, &["commit", "-m", "Add submodule"]);
+
+        let repo = Repository::open(parent).unwrap();
+        let status = get_repository_status(&repo).unwrap();
+        assert_eq!(status.submodules.len(), 1);
+        let submodule = &status.submodules[0];
+        assert_eq!(submodule.path, "libs/sub");
+        assert!(!submodule.is_modified);
+        assert!(!submodule.is_uninitialized);
+        assert_eq!(submodule.head, submodule.recorded_head);
+
+        // Move the submodule to a new commit
+        let checkout = parent.join("libs/sub");
+        run_git(&checkout, &["config", "user.email", "test@example.com"]);
+        run_git(&checkout, &["config", "user.name", "Test User"]);
+        std::fs::write(checkout.join("README.md"), "# Changed").unwrap();
+        run_git(&checkout, &["commit", "-am", "Change submodule"]);
+
+        let status = get_repository_status(&repo).unwrap();
+        let submodule = &status.submodules[0];
+        assert!(submodule.is_modified);
+        assert_ne!(submodule.head, submodule.recorded_head);
+
+        run_git(parent, &["submodule", "deinit", "-f", "libs/sub"]);
+        let status = get_repository_status(&repo).unwrap();
+        let submodule = &status.submodules[0];
+        assert!(submodule.is_uninitialized);
+        assert!(!submodule.is_modified);
+        assert!(submodule.head.is_none());
+    }
 }
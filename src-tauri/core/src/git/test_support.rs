@@ -0,0 +1,17 @@
+use std::path::Path;
+
+/// Runs git in `dir`, panicking on failure, and returns trimmed stdout
+pub(crate) fn git(dir: &Path, args: &[&str]) -> String {
+    let output = crate::shell_utils::new_command("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("Failed to run git");
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
@@ -66,6 +66,25 @@ pub struct GitStatus {
     pub conflicted: Vec<String>,
     /// Total count of uncommitted changes
     pub changes_count: usize,
+    /// Submodules registered in the repository
+    #[serde(default)]
+    pub submodules: Vec<SubmoduleStatus>,
+}
+
+/// Represents the state of a submodule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmoduleStatus {
+    /// Path of the submodule relative to the repository root
+    pub path: String,
+    /// Commit currently checked out in the submodule (None if uninitialized)
+    pub head: Option<String>,
+    /// Commit recorded for the submodule in the superproject's HEAD
+    pub recorded_head: Option<String>,
+    /// Whether the submodule points to a different commit or has local changes
+    pub is_modified: bool,
+    /// Whether the submodule has not been initialized/cloned
+    pub is_uninitialized: bool,
 }
 
 /// Represents a line change in a diff
@@ -94,6 +113,30 @@ pub struct DiffLine {
     pub content: String,
 }
 
+/// A changed range within a line, in character offsets (end exclusive)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WordSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// An added or deleted line with the spans that actually changed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WordDiffLine {
+    /// Addition or Deletion
+    pub line_type: DiffLineType,
+    /// Line number in old file (None if added)
+    pub old_line_number: Option<u32>,
+    /// Line number in new file (None if deleted)
+    pub new_line_number: Option<u32>,
+    /// Content of the line, without the trailing newline
+    pub content: String,
+    /// Changed spans; the whole line when it has no counterpart
+    pub spans: Vec<WordSpan>,
+}
+
 /// Represents a hunk in a diff
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -128,25 +171,83 @@ pub struct FileDiff {
     pub additions: usize,
     /// Number of lines deleted
     pub deletions: usize,
+    /// Whether the file is binary (by content or `.gitattributes`); binary diffs have no hunks
+    #[serde(default)]
+    pub is_binary: bool,
+    /// File size in bytes before the change, when known
+    #[serde(default)]
+    pub old_size: Option<u64>,
+    /// File size in bytes after the change, when known
+    #[serde(default)]
+    pub new_size: Option<u64>,
 }
 
 /// Represents information about a commit
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-#[allow(dead_code)]
 pub struct CommitInfo {
     /// Commit hash
     pub hash: String,
     /// Short commit hash
     pub short_hash: String,
-    /// Commit message
-    pub message: String,
     /// Author name
-    pub author_name: String,
+    pub author: String,
     /// Author email
-    pub author_email: String,
-    /// Timestamp in seconds since epoch
-    pub timestamp: i64,
+    pub email: String,
+    /// Author timestamp in seconds since epoch
+    pub time: i64,
+    /// First line of the commit message
+    pub summary: String,
+    /// Rest of the commit message, if any
+    pub body: Option<String>,
+}
+
+/// A single commit with its changes against the first parent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitDetail {
+    /// Commit metadata
+    pub commit: CommitInfo,
+    /// Per-file diffs
+    pub files: Vec<FileDiff>,
+    /// Diff as `git diff`-style text
+    pub diff_text: String,
+}
+
+/// A local or remote-tracking branch, as listed by `git branch -a`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchEntry {
+    /// Branch name (`feature` for local, `origin/feature` for remote-tracking)
+    pub name: String,
+    /// Whether this is the checked-out branch
+    pub is_current: bool,
+    /// Whether this is a remote-tracking branch
+    pub is_remote: bool,
+    /// Upstream branch name for local branches, if configured
+    pub upstream: Option<String>,
+    /// Hash of the commit the branch points to
+    pub commit_hash: Option<String>,
+}
+
+/// One `<<<<<<<` ... `>>>>>>>` region of a conflicted file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictHunk {
+    /// Lines between `<<<<<<<` and the next marker
+    pub ours: String,
+    /// Lines between `=======` and `>>>>>>>`
+    pub theirs: String,
+    /// Lines between `|||||||` and `=======` (diff3 conflict style only)
+    pub base: Option<String>,
+    /// Label after `<<<<<<<` (e.g. `HEAD`)
+    pub ours_label: Option<String>,
+    /// Label after `>>>>>>>` (e.g. the merged branch)
+    pub theirs_label: Option<String>,
+    /// 1-based line of the `<<<<<<<` marker
+    pub start_line: usize,
+    /// 1-based line of the `>>>>>>>` marker
+    pub end_line: usize,
 }
 
 #[cfg(test)]
@@ -257,6 +358,7 @@ mod tests {
             untracked: vec!["new_file.txt".to_string()],
             conflicted: vec![],
             changes_count: 2,
+            submodules: vec![],
         };
 
         let json = serde_json::to_string(&status).unwrap();
@@ -320,6 +422,9 @@ mod tests {
             hunks: vec![],
             additions: 10,
             deletions: 5,
+            is_binary: false,
+            old_size: None,
+            new_size: None,
         };
 
         let json = serde_json::to_string(&diff).unwrap();
@@ -338,6 +443,9 @@ mod tests {
             hunks: vec![],
             additions: 0,
             deletions: 0,
+            is_binary: false,
+            old_size: None,
+            new_size: None,
         };
 
         let json = serde_json::to_string(&diff).unwrap();
@@ -350,17 +458,19 @@ mod tests {
         let commit = CommitInfo {
             hash: "abc123def456".to_string(),
             short_hash: "abc123d".to_string(),
-            message: "Initial commit".to_string(),
-            author_name: "Test User".to_string(),
-            author_email: "test@example.com".to_string(),
-            timestamp: 1700000000,
+            author: "Test User".to_string(),
+            email: "test@example.com".to_string(),
+            time: 1700000000,
+            summary: "Initial commit".to_string(),
+            body: None,
         };
 
         let json = serde_json::to_string(&commit).unwrap();
         assert!(json.contains("\"hash\":\"abc123def456\""));
         assert!(json.contains("\"shortHash\":\"abc123d\""));
-        assert!(json.contains("\"authorName\":\"Test User\""));
-        assert!(json.contains("\"authorEmail\":\"test@example.com\""));
-        assert!(json.contains("\"timestamp\":1700000000"));
+        assert!(json.contains("\"author\":\"Test User\""));
+        assert!(json.contains("\"email\":\"test@example.com\""));
+        assert!(json.contains("\"time\":1700000000"));
+        assert!(json.contains("\"summary\":\"Initial commit\""));
     }
 }
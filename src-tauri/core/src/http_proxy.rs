@@ -12,7 +12,7 @@ use url::Url;
 static REQUEST_COUNTER: AtomicU32 = AtomicU32::new(0);
 
 const ACCEPT_HEADER_VALUE: &str = "text/event-stream, text/plain, application/json";
-const PROXY_ACCEPT_ENCODING: &str = "gzip, br, identity";
+const PROXY_ACCEPT_ENCODING: &str = "gzip, br, deflate, identity";
 const STREAM_ACCEPT_ENCODING: &str = "identity";
 
 /// Validate URL to prevent SSRF attacks
@@ -138,6 +138,62 @@ pub struct ProxyRequest {
     pub body: Option<String>,
     pub request_id: Option<u32>,
     pub allow_private_ip: Option<bool>,
+    /// Overall deadline for the request and body (proxy_fetch only)
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// Abort once the (decoded) body grows past this many bytes (proxy_fetch only)
+    #[serde(default)]
+    pub max_response_bytes: Option<u64>,
+}
+
+/// proxy_fetch failure; limit violations are reported to the frontend as JSON
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ProxyFetchError {
+    #[serde(rename_all = "camelCase")]
+    Timeout {
+        timeout_ms: u64,
+        message: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    ResponseTooLarge {
+        max_bytes: u64,
+        message: String,
+    },
+    Request {
+        message: String,
+    },
+}
+
+impl ProxyFetchError {
+    fn timeout(timeout_ms: u64) -> Self {
+        Self::Timeout {
+            timeout_ms,
+            message: format!("Request timed out after {} ms", timeout_ms),
+        }
+    }
+
+    fn too_large(max_bytes: u64) -> Self {
+        Self::ResponseTooLarge {
+            max_bytes,
+            message: format!("Response exceeded the {} byte limit", max_bytes),
+        }
+    }
+}
+
+impl From<String> for ProxyFetchError {
+    fn from(message: String) -> Self {
+        Self::Request { message }
+    }
+}
+
+impl From<ProxyFetchError> for String {
+    fn from(error: ProxyFetchError) -> Self {
+        match error {
+            ProxyFetchError::Request { message } => message,
+            limit => serde_json::to_string(&limit).unwrap_or_else(|_| format!("{:?}", limit)),
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -230,22 +286,60 @@ fn emit_to_window<R: tauri::Runtime, S: Serialize>(
     )
 }
 
+/// Read the body chunk by chunk, aborting as soon as it exceeds `max_bytes`
+async fn read_body_capped(
+    response: reqwest::Response,
+    max_bytes: Option<u64>,
+) -> Result<String, ProxyFetchError> {
+    if let (Some(max), Some(len)) = (max_bytes, response.content_length()) {
+        if len > max {
+            return Err(ProxyFetchError::too_large(max));
+        }
+    }
+
+    let mut body = Vec::new();
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("Failed to read response body: {}", e))?;
+        if let Some(max) = max_bytes {
+            if (body.len() + chunk.len()) as u64 > max {
+                return Err(ProxyFetchError::too_large(max));
+            }
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
 #[tauri::command]
 pub async fn proxy_fetch(request: ProxyRequest) -> Result<ProxyResponse, String> {
+    proxy_fetch_inner(request).await.map_err(String::from)
+}
+
+async fn proxy_fetch_inner(request: ProxyRequest) -> Result<ProxyResponse, ProxyFetchError> {
+    match request.timeout_ms {
+        Some(timeout_ms) => timeout(
+            Duration::from_millis(timeout_ms),
+            send_proxy_request(request),
+        )
+        .await
+        .map_err(|_| {
+            log::error!("Proxy fetch timed out after {} ms", timeout_ms);
+            ProxyFetchError::timeout(timeout_ms)
+        })?,
+        None => send_proxy_request(request).await,
+    }
+}
+
+async fn send_proxy_request(request: ProxyRequest) -> Result<ProxyResponse, ProxyFetchError> {
     log::info!("Proxy fetch request to: {} {}", request.method, request.url);
 
     // Validate URL to prevent SSRF attacks
     validate_url(&request.url, request.allow_private_ip.unwrap_or(false))?;
+    let max_response_bytes = request.max_response_bytes;
 
-    // Configure client with proper decompression and connection settings
-    let client = reqwest::Client::builder()
-        .connect_timeout(Duration::from_secs(10))
-        .gzip(true)
-        .brotli(true)
-        .tcp_nodelay(true)
-        .pool_max_idle_per_host(5)
-        .build()
-        .map_err(|e| format!("Failed to build client: {}", e))?;
+    // The whole body is read at once, so let the client decode compressed responses
+    let client = crate::network_proxy::shared_json_client();
 
     // Build the request
     let mut req_builder = match request.method.to_uppercase().as_str() {
@@ -254,7 +348,7 @@ pub async fn proxy_fetch(request: ProxyRequest) -> Result<ProxyResponse, String>
         "PUT" => client.put(&request.url),
         "DELETE" => client.delete(&request.url),
         "PATCH" => client.patch(&request.url),
-        _ => return Err(format!("Unsupported HTTP method: {}", request.method)),
+        _ => return Err(format!("Unsupported HTTP method: {}", request.method).into()),
     };
 
     // Add explicit encoding expectations only if not already set by client
@@ -311,7 +405,10 @@ pub async fn proxy_fetch(request: ProxyRequest) -> Result<ProxyResponse, String>
     }
 
     if status != 200 {
-        let body_preview = response.text().await.unwrap_or_default();
+        let body_preview = match read_body_capped(response, max_response_bytes).await {
+            Err(e @ ProxyFetchError::ResponseTooLarge { .. }) => return Err(e),
+            result => result.unwrap_or_default(),
+        };
         let (preview, truncated) = truncate_for_log(&body_preview, 2048);
         log::error!(
             "fetch response error: status {} (request.url: {}, body{}: {})",
@@ -347,7 +444,7 @@ pub async fn proxy_fetch(request: ProxyRequest) -> Result<ProxyResponse, String>
 
     let read_timeout = Duration::from_secs(30);
 
-    let body = timeout(read_timeout, response.text())
+    let body = timeout(read_timeout, read_body_capped(response, max_response_bytes))
         .await
         .map_err(|_| {
             log::error!(
@@ -359,10 +456,7 @@ pub async fn proxy_fetch(request: ProxyRequest) -> Result<ProxyResponse, String>
                 read_timeout.as_secs()
             )
         })?
-        .map_err(|e| {
-            log::error!("Failed to read response body: {}", e);
-            format!("Failed to read response body: {}", e)
-        })?;
+        .inspect_err(|e| log::error!("Failed to read response body: {:?}", e))?;
 
     Ok(ProxyResponse {
         status,
@@ -405,18 +499,8 @@ async fn stream_fetch_inner<R: tauri::Runtime>(
         return Err(e);
     }
 
-    // Configure client with connection settings for streaming and avoid auto-decompression.
-    let client = reqwest::Client::builder()
-        .connect_timeout(Duration::from_secs(10))
-        .gzip(false)
-        .brotli(false)
-        .tcp_nodelay(true) // Reduce latency for streaming
-        .pool_max_idle_per_host(5) // Enable connection pooling
-        .build()
-        .map_err(|e| {
-            emit_end(0, Some(format!("Failed to build client: {}", e)));
-            format!("Failed to build client: {}", e)
-        })?;
+    // The shared client does not auto-decompress, so chunks are forwarded as received
+    let client = crate::network_proxy::shared_client();
 
     // Build the request
     let mut req_builder = match request.method.to_uppercase().as_str() {
@@ -688,6 +772,172 @@ pub async fn stream_fetch(
     stream_fetch_inner(window, request).await
 }
 
+/// Minimum bytes between two download progress events
+const DOWNLOAD_PROGRESS_STEP: u64 = 256 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadProgressPayload {
+    pub channel_id: String,
+    pub downloaded: u64,
+    pub total: Option<u64>,
+    pub percent: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadResult {
+    pub path: String,
+    pub size: u64,
+    pub resumed: bool,
+}
+
+fn download_progress(
+    channel_id: &str,
+    downloaded: u64,
+    total: Option<u64>,
+) -> DownloadProgressPayload {
+    let percent = total
+        .filter(|total| *total > 0)
+        .map(|total| (downloaded as f64 / total as f64 * 100.0).min(100.0));
+    DownloadProgressPayload {
+        channel_id: channel_id.to_string(),
+        downloaded,
+        total,
+        percent,
+    }
+}
+
+/// Stream a response body to `dest_path`, resuming a partial file with `Range`
+async fn download_to_file<F>(
+    url: &str,
+    dest_path: &std::path::Path,
+    headers: HashMap<String, String>,
+    allow_private_ip: bool,
+    channel_id: &str,
+    on_progress: F,
+) -> Result<DownloadResult, String>
+where
+    F: Fn(&DownloadProgressPayload),
+{
+    use tokio::io::AsyncWriteExt;
+
+    validate_url(url, allow_private_ip)?;
+
+    let existing = tokio::fs::metadata(dest_path)
+        .await
+        .map(|m| m.len())
+        .unwrap_or(0);
+
+    // Byte counts must match the file on disk; the shared client keeps the body undecoded
+    let client = crate::network_proxy::shared_client();
+
+    let mut req_builder = client.get(url);
+    for (key, value) in &headers {
+        req_builder = req_builder.header(key, value);
+    }
+    if !has_header(&headers, "Accept-Encoding") {
+        req_builder = req_builder.header("Accept-Encoding", STREAM_ACCEPT_ENCODING);
+    }
+    if existing > 0 {
+        req_builder = req_builder.header("Range", format!("bytes={}-", existing));
+    }
+
+    let response = req_builder
+        .send()
+        .await
+        .map_err(|e| format!("Download request failed: {}", e))?;
+    let status = response.status();
+
+    // The partial file already holds the whole body
+    if existing > 0 && status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        on_progress(&download_progress(channel_id, existing, Some(existing)));
+        return Ok(DownloadResult {
+            path: dest_path.to_string_lossy().to_string(),
+            size: existing,
+            resumed: true,
+        });
+    }
+    if !status.is_success() {
+        return Err(format!("Download failed with status {}", status.as_u16()));
+    }
+
+    let resumed = existing > 0 && status == reqwest::StatusCode::PARTIAL_CONTENT;
+    let mut downloaded = if resumed { existing } else { 0 };
+    let total = response.content_length().map(|len| len + downloaded);
+
+    if let Some(parent) = dest_path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create download directory: {}", e))?;
+    }
+    let mut file = if resumed {
+        tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(dest_path)
+            .await
+    } else {
+        tokio::fs::File::create(dest_path).await
+    }
+    .map_err(|e| format!("Failed to open {}: {}", dest_path.display(), e))?;
+
+    on_progress(&download_progress(channel_id, downloaded, total));
+    let mut last_reported = downloaded;
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("Download interrupted: {}", e))?;
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| format!("Failed to write download: {}", e))?;
+        downloaded += chunk.len() as u64;
+        if downloaded - last_reported >= DOWNLOAD_PROGRESS_STEP {
+            on_progress(&download_progress(channel_id, downloaded, total));
+            last_reported = downloaded;
+        }
+    }
+    file.flush()
+        .await
+        .map_err(|e| format!("Failed to write download: {}", e))?;
+
+    // Final event: unknown totals are known now
+    on_progress(&download_progress(
+        channel_id,
+        downloaded,
+        Some(total.unwrap_or(downloaded)),
+    ));
+
+    Ok(DownloadResult {
+        path: dest_path.to_string_lossy().to_string(),
+        size: downloaded,
+        resumed,
+    })
+}
+
+/// Download a URL straight to disk, emitting `download-progress-{channel_id}` events
+#[tauri::command]
+pub async fn proxy_download_to_file(
+    app_handle: tauri::AppHandle,
+    url: String,
+    dest_path: String,
+    headers: Option<HashMap<String, String>>,
+    channel_id: String,
+    allow_private_ip: Option<bool>,
+) -> Result<DownloadResult, String> {
+    log::info!("Download request to: {} -> {}", url, dest_path);
+    let event_name = format!("download-progress-{}", channel_id);
+    download_to_file(
+        &url,
+        std::path::Path::new(&dest_path),
+        headers.unwrap_or_default(),
+        allow_private_ip.unwrap_or(false),
+        &channel_id,
+        |payload| {
+            if let Err(e) = app_handle.emit(&event_name, payload) {
+                log::warn!("Failed to emit download progress: {}", e);
+            }
+        },
+    )
+    .await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1366,6 +1616,8 @@ mod tests {
             body: None,
             request_id: Some(request_id),
             allow_private_ip: None,
+            timeout_ms: None,
+            max_response_bytes: None,
         };
 
         let window = target_window.as_ref().window();
@@ -1453,4 +1705,164 @@ mod tests {
         let stream_encoding = STREAM_ACCEPT_ENCODING;
         assert_eq!(stream_encoding, "identity");
     }
+
+    /// Serves `body`, honoring `Range: bytes=N-` requests
+    fn spawn_file_server(body: Vec<u8>) -> String {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/file.bin", server.server_addr());
+        std::thread::spawn(move || {
+            for request in server.incoming_requests() {
+                let start = request
+                    .headers()
+                    .iter()
+                    .find(|h| h.field.equiv("Range"))
+                    .and_then(|h| {
+                        h.value
+                            .as_str()
+                            .strip_prefix("bytes=")
+                            .and_then(|v| v.trim_end_matches('-').parse::<usize>().ok())
+                    });
+                let response = match start {
+                    Some(start) if start >= body.len() => {
+                        tiny_http::Response::from_data(Vec::new()).with_status_code(416)
+                    }
+                    Some(start) => {
+                        tiny_http::Response::from_data(body[start..].to_vec()).with_status_code(206)
+                    }
+                    None => tiny_http::Response::from_data(body.clone()),
+                };
+                let _ = request.respond(response);
+            }
+        });
+        url
+    }
+
+    fn test_body() -> Vec<u8> {
+        (0..600_000u32).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[tokio::test]
+    async fn test_download_to_file_writes_body_and_reports_progress() {
+        let body = test_body();
+        let url = spawn_file_server(body.clone());
+        let dir = tempfile::TempDir::new().unwrap();
+        let dest = dir.path().join("nested").join("file.bin");
+
+        let events = std::sync::Mutex::new(Vec::new());
+        let result = download_to_file(&url, &dest, HashMap::new(), false, "dl-1", |p| {
+            events.lock().unwrap().push(p.clone())
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(result.size, body.len() as u64);
+        assert!(!result.resumed);
+        assert_eq!(std::fs::read(&dest).unwrap(), body);
+
+        let events = events.into_inner().unwrap();
+        assert!(events.len() > 2);
+        let last = events.last().unwrap();
+        assert_eq!(last.channel_id, "dl-1");
+        assert_eq!(last.downloaded, body.len() as u64);
+        assert_eq!(last.total, Some(body.len() as u64));
+        assert_eq!(last.percent, Some(100.0));
+    }
+
+    #[tokio::test]
+    async fn test_download_to_file_resumes_partial_file() {
+        let body = test_body();
+        let url = spawn_file_server(body.clone());
+        let dir = tempfile::TempDir::new().unwrap();
+        let dest = dir.path().join("file.bin");
+        std::fs::write(&dest, &body[..1000]).unwrap();
+
+        let result = download_to_file(&url, &dest, HashMap::new(), false, "dl-2", |_| {})
+            .await
+            .unwrap();
+
+        assert!(result.resumed);
+        assert_eq!(result.size, body.len() as u64);
+        assert_eq!(std::fs::read(&dest).unwrap(), body);
+
+        // A complete file is left as-is
+        let again = download_to_file(&url, &dest, HashMap::new(), false, "dl-2", |_| {})
+            .await
+            .unwrap();
+        assert_eq!(again.size, body.len() as u64);
+        assert_eq!(std::fs::read(&dest).unwrap(), body);
+    }
+
+    #[tokio::test]
+    async fn test_download_to_file_rejects_invalid_url() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let dest = dir.path().join("file.bin");
+        let result = download_to_file(
+            "ftp://example.com/x",
+            &dest,
+            HashMap::new(),
+            false,
+            "dl-3",
+            |_| {},
+        )
+        .await;
+        assert!(result.is_err());
+        assert!(!dest.exists());
+    }
+
+    fn get_request(url: String) -> ProxyRequest {
+        ProxyRequest {
+            url,
+            method: "GET".to_string(),
+            headers: HashMap::new(),
+            body: None,
+            request_id: None,
+            allow_private_ip: None,
+            timeout_ms: None,
+            max_response_bytes: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_proxy_fetch_aborts_past_size_cap() {
+        let url = spawn_file_server(test_body());
+
+        let mut request = get_request(url.clone());
+        request.max_response_bytes = Some(1024);
+        let error = proxy_fetch_inner(request).await.unwrap_err();
+        assert_eq!(error, ProxyFetchError::too_large(1024));
+
+        let mut request = get_request(url);
+        request.max_response_bytes = Some(10_000_000);
+        let response = proxy_fetch_inner(request).await.unwrap();
+        assert_eq!(response.status, 200);
+    }
+
+    #[tokio::test]
+    async fn test_proxy_fetch_times_out() {
+        // Accepts the connection but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/slow", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (_socket, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(10)).await;
+        });
+
+        let mut request = get_request(url);
+        request.timeout_ms = Some(100);
+        let started = std::time::Instant::now();
+        let error = proxy_fetch_inner(request).await.unwrap_err();
+        assert_eq!(error, ProxyFetchError::timeout(100));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_proxy_fetch_error_strings() {
+        let limit: String = ProxyFetchError::too_large(10).into();
+        let value: serde_json::Value = serde_json::from_str(&limit).unwrap();
+        assert_eq!(value["kind"], "responseTooLarge");
+        assert_eq!(value["maxBytes"], 10);
+
+        let other: String = ProxyFetchError::from("boom".to_string()).into();
+        assert_eq!(other, "boom");
+    }
 }
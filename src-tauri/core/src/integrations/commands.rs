@@ -0,0 +1,169 @@
+//! Gateway Commands
+//!
+//! Slash-command parsing for chat gateways. Control commands are dispatched to
+//! the runtime instead of being sent to the agent as prompts; every other
+//! command is forwarded as text to the remote chat service's command handler.
+
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_COMMAND_PREFIX: &str = "/";
+
+/// Control command recognized by the gateways
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum GatewayCommand {
+    /// Start a fresh session, optionally with a first prompt
+    New { prompt: Option<String> },
+    /// Cancel the current generation
+    Stop,
+    /// Switch the model used for this chat
+    Model {
+        #[serde(rename = "modelId")]
+        model_id: String,
+    },
+}
+
+/// What a gateway should do with an inbound text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InboundAction {
+    /// Not a control command; forward as an inbound message
+    Prompt,
+    /// Dispatch a control command to the runtime
+    Dispatch(GatewayCommand),
+}
+
+fn effective_prefix(prefix: &str) -> &str {
+    let trimmed = prefix.trim();
+    if trimmed.is_empty() {
+        DEFAULT_COMMAND_PREFIX
+    } else {
+        trimmed
+    }
+}
+
+/// Decide how to handle an inbound text given the configured command prefix
+pub fn route_inbound(text: &str, prefix: &str) -> InboundAction {
+    let prefix = effective_prefix(prefix);
+    let trimmed = text.trim();
+    let Some(body) = trimmed.strip_prefix(prefix) else {
+        return InboundAction::Prompt;
+    };
+
+    let (head, args) = match body.split_once(char::is_whitespace) {
+        Some((head, args)) => (head, args.trim()),
+        None => (body, ""),
+    };
+    // Telegram appends the bot name in groups: /new@my_bot
+    let name = head.split('@').next().unwrap_or_default().to_lowercase();
+
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return InboundAction::Prompt;
+    }
+
+    match name.as_str() {
+        "new" => InboundAction::Dispatch(GatewayCommand::New {
+            prompt: (!args.is_empty()).then(|| args.to_string()),
+        }),
+        "stop" => InboundAction::Dispatch(GatewayCommand::Stop),
+        "model" if !args.is_empty() => InboundAction::Dispatch(GatewayCommand::Model {
+            model_id: args.to_string(),
+        }),
+        // Help, usage and unknown commands are answered by the remote chat
+        // service in the user's locale
+        _ => InboundAction::Prompt,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_new_command() {
+        assert_eq!(
+            route_inbound("/new", "/"),
+            InboundAction::Dispatch(GatewayCommand::New { prompt: None })
+        );
+        assert_eq!(
+            route_inbound("/new fix the build", "/"),
+            InboundAction::Dispatch(GatewayCommand::New {
+                prompt: Some("fix the build".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn parses_stop_command() {
+        assert_eq!(
+            route_inbound("  /stop  ", "/"),
+            InboundAction::Dispatch(GatewayCommand::Stop)
+        );
+        assert_eq!(
+            route_inbound("/stop@talkcody_bot", "/"),
+            InboundAction::Dispatch(GatewayCommand::Stop)
+        );
+    }
+
+    #[test]
+    fn parses_model_command() {
+        assert_eq!(
+            route_inbound("/model gpt-4o", "/"),
+            InboundAction::Dispatch(GatewayCommand::Model {
+                model_id: "gpt-4o".to_string()
+            })
+        );
+        // Usage help comes from the remote chat service
+        assert_eq!(route_inbound("/model", "/"), InboundAction::Prompt);
+    }
+
+    #[test]
+    fn other_commands_are_forwarded() {
+        assert_eq!(route_inbound("/frobnicate now", "/"), InboundAction::Prompt);
+        assert_eq!(route_inbound("/help", "/"), InboundAction::Prompt);
+        assert_eq!(route_inbound("/start", "/"), InboundAction::Prompt);
+    }
+
+    #[test]
+    fn normal_messages_are_prompts() {
+        assert_eq!(route_inbound("hello there", "/"), InboundAction::Prompt);
+        assert_eq!(
+            route_inbound("please look at /usr/local/bin", "/"),
+            InboundAction::Prompt
+        );
+        assert_eq!(
+            route_inbound("/usr/local/bin is missing", "/"),
+            InboundAction::Prompt
+        );
+        assert_eq!(route_inbound("/", "/"), InboundAction::Prompt);
+    }
+
+    #[test]
+    fn remote_chat_commands_stay_prompts() {
+        assert_eq!(route_inbound("/approve", "/"), InboundAction::Prompt);
+        assert_eq!(route_inbound("/status", "/"), InboundAction::Prompt);
+    }
+
+    #[test]
+    fn respects_custom_prefix() {
+        assert_eq!(
+            route_inbound("!stop", "!"),
+            InboundAction::Dispatch(GatewayCommand::Stop)
+        );
+        assert_eq!(route_inbound("/stop", "!"), InboundAction::Prompt);
+        // Empty prefix falls back to the default
+        assert_eq!(
+            route_inbound("/stop", ""),
+            InboundAction::Dispatch(GatewayCommand::Stop)
+        );
+    }
+
+    #[test]
+    fn command_serializes_for_frontend() {
+        let value = serde_json::to_value(GatewayCommand::Model {
+            model_id: "m1".to_string(),
+        })
+        .unwrap();
+        assert_eq!(value["type"], "model");
+        assert_eq!(value["modelId"], "m1");
+    }
+}
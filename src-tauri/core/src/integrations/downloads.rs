@@ -0,0 +1,91 @@
+//! Gateway Downloads
+//!
+//! Files users send through the chat gateways are downloaded before a session
+//! exists, so they are written to a gateway directory rather than stored as
+//! attachments. They still pass the attachment upload limits and have image
+//! metadata stripped first; images that can't be stripped are not saved.
+
+use std::path::{Path, PathBuf};
+
+use crate::storage::attachments::UploadBuffer;
+use crate::storage::AttachmentsRepository;
+
+/// Where a gateway writes downloaded files
+#[derive(Clone)]
+pub struct GatewayDownloads {
+    dir: PathBuf,
+    attachments: AttachmentsRepository,
+}
+
+/// A download written by `GatewayDownloads::save`
+#[derive(Debug, Clone)]
+pub struct SavedDownload {
+    pub path: String,
+    /// Validated mime type to record for the file
+    pub mime_type: String,
+    /// Size as written, after metadata stripping
+    pub size: u64,
+}
+
+impl GatewayDownloads {
+    pub fn new(dir: PathBuf, attachments: AttachmentsRepository) -> Self {
+        Self { dir, attachments }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Read a download's body, stopping as soon as it passes the upload size
+    /// limit instead of buffering an oversized file first. `Ok(None)` means the
+    /// file was refused for its size.
+    pub async fn read_body(
+        &self,
+        mut response: reqwest::Response,
+    ) -> Result<Option<Vec<u8>>, String> {
+        let limits = self.attachments.upload_limits();
+        let mut buffer = match UploadBuffer::new(limits, response.content_length()) {
+            Ok(buffer) => buffer,
+            Err(rejection) => {
+                log::warn!("[GatewayDownloads] Refusing download: {}", rejection);
+                return Ok(None);
+            }
+        };
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| format!("Failed to read download: {}", e))?
+        {
+            if let Err(rejection) = buffer.push(&chunk) {
+                log::warn!("[GatewayDownloads] Aborting download: {}", rejection);
+                return Ok(None);
+            }
+        }
+        Ok(Some(buffer.into_inner()))
+    }
+
+    /// Validate and sanitize a download, then write it as `filename`
+    pub async fn save(
+        &self,
+        filename: &str,
+        mime_type: &str,
+        data: &[u8],
+    ) -> Result<SavedDownload, String> {
+        let (mime_type, data) = self
+            .attachments
+            .prepare_upload(mime_type, filename, data)
+            .map_err(|e| e.to_string())?;
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| format!("Failed to create attachments dir: {}", e))?;
+        let target_path = self.dir.join(filename);
+        tokio::fs::write(&target_path, data.as_ref())
+            .await
+            .map_err(|e| format!("Failed to write attachment: {}", e))?;
+        Ok(SavedDownload {
+            path: target_path.to_string_lossy().to_string(),
+            mime_type,
+            size: data.len() as u64,
+        })
+    }
+}
@@ -1,15 +1,26 @@
 //! Integration Layer
 //!
-//! IM adapters for Telegram, Feishu, and future channels (Slack, Discord, WhatsApp).
+//! IM adapters for Telegram, Feishu, and future channels (Slack, Discord, WhatsApp),
+//! plus a generic inbound webhook for programmatic prompts.
+//! Long-running integrations are managed uniformly through `IntegrationRegistry`.
 //! Wraps existing gateway implementations for cloud backend integration.
 
+pub mod commands;
+pub mod downloads;
 pub mod feishu;
+pub mod registry;
 pub mod telegram;
 pub mod types;
+pub mod typing;
+pub mod webhook;
 
+pub use commands::{route_inbound, GatewayCommand, InboundAction};
+pub use downloads::{GatewayDownloads, SavedDownload};
 pub use feishu::{FeishuAdapter, FeishuConfig};
+pub use registry::{Integration, IntegrationRegistry, IntegrationStatus};
 pub use telegram::{TelegramAdapter, TelegramConfig};
 pub use types::*;
+pub use typing::{TypingIndicator, TypingSink};
 
 /// Integration factory for creating adapters
 pub struct IntegrationFactory;
@@ -0,0 +1,249 @@
+//! Integration Registry
+//!
+//! Uniform lifecycle for long-running integrations (chat gateways, webhooks).
+//! Each integration registers itself once at startup; the frontend drives all
+//! of them through the generic `integration_*` commands.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::RwLock;
+
+/// Snapshot of an integration's lifecycle state
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrationStatus {
+    pub id: String,
+    pub running: bool,
+    pub last_error: Option<String>,
+    pub last_error_at_ms: Option<i64>,
+    /// Most recent inbound activity (poll, event, request)
+    pub last_activity_at_ms: Option<i64>,
+}
+
+impl IntegrationStatus {
+    pub fn new(id: impl Into<String>, running: bool) -> Self {
+        Self {
+            id: id.into(),
+            running,
+            last_error: None,
+            last_error_at_ms: None,
+            last_activity_at_ms: None,
+        }
+    }
+}
+
+/// A long-running integration managed by the registry
+#[async_trait::async_trait]
+pub trait Integration: Send + Sync {
+    /// Stable identifier, e.g. `telegram`
+    fn id(&self) -> &str;
+
+    /// Start (or resume) the integration; starting a running integration is a no-op
+    async fn start(&self) -> Result<(), String>;
+
+    /// Stop the integration; stopping a stopped integration is a no-op
+    async fn stop(&self) -> Result<(), String>;
+
+    /// Current lifecycle state
+    async fn status(&self) -> IntegrationStatus;
+}
+
+/// Registry of integrations keyed by id
+#[derive(Clone, Default)]
+pub struct IntegrationRegistry {
+    integrations: Arc<RwLock<BTreeMap<String, Arc<dyn Integration>>>>,
+}
+
+impl IntegrationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an integration; ids must be unique
+    pub async fn register(&self, integration: Arc<dyn Integration>) -> Result<(), String> {
+        let id = integration.id().to_string();
+        let mut integrations = self.integrations.write().await;
+        if integrations.contains_key(&id) {
+            return Err(format!("Integration already registered: {}", id));
+        }
+        integrations.insert(id, integration);
+        Ok(())
+    }
+
+    /// Remove an integration, stopping it first
+    pub async fn unregister(&self, id: &str) -> Result<(), String> {
+        let integration = self.integrations.write().await.remove(id);
+        match integration {
+            Some(integration) => integration.stop().await,
+            None => Err(format!("Integration not found: {}", id)),
+        }
+    }
+
+    pub async fn get(&self, id: &str) -> Option<Arc<dyn Integration>> {
+        self.integrations.read().await.get(id).cloned()
+    }
+
+    pub async fn ids(&self) -> Vec<String> {
+        self.integrations.read().await.keys().cloned().collect()
+    }
+
+    async fn require(&self, id: &str) -> Result<Arc<dyn Integration>, String> {
+        self.get(id)
+            .await
+            .ok_or_else(|| format!("Integration not found: {}", id))
+    }
+
+    pub async fn start(&self, id: &str) -> Result<(), String> {
+        self.require(id).await?.start().await
+    }
+
+    pub async fn stop(&self, id: &str) -> Result<(), String> {
+        self.require(id).await?.stop().await
+    }
+
+    pub async fn status(&self, id: &str) -> Result<IntegrationStatus, String> {
+        Ok(self.require(id).await?.status().await)
+    }
+
+    /// Status of every registered integration, ordered by id
+    pub async fn statuses(&self) -> Vec<IntegrationStatus> {
+        let integrations: Vec<_> = self.integrations.read().await.values().cloned().collect();
+        let mut statuses = Vec::with_capacity(integrations.len());
+        for integration in integrations {
+            statuses.push(integration.status().await);
+        }
+        statuses
+    }
+
+    /// Stop everything, e.g. on app exit. Errors are logged, not returned.
+    pub async fn stop_all(&self) {
+        let integrations: Vec<_> = self.integrations.read().await.values().cloned().collect();
+        for integration in integrations {
+            if let Err(error) = integration.stop().await {
+                log::warn!(
+                    "[IntegrationRegistry] Failed to stop {}: {}",
+                    integration.id(),
+                    error
+                );
+            }
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn integration_list(
+    registry: State<'_, IntegrationRegistry>,
+) -> Result<Vec<IntegrationStatus>, String> {
+    Ok(registry.statuses().await)
+}
+
+#[tauri::command]
+pub async fn integration_start(
+    registry: State<'_, IntegrationRegistry>,
+    id: String,
+) -> Result<(), String> {
+    registry.start(&id).await
+}
+
+#[tauri::command]
+pub async fn integration_stop(
+    registry: State<'_, IntegrationRegistry>,
+    id: String,
+) -> Result<(), String> {
+    registry.stop(&id).await
+}
+
+#[tauri::command]
+pub async fn integration_get_status(
+    registry: State<'_, IntegrationRegistry>,
+    id: String,
+) -> Result<IntegrationStatus, String> {
+    registry.status(&id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct MockIntegration {
+        running: AtomicBool,
+        starts: AtomicUsize,
+        stops: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl Integration for MockIntegration {
+        fn id(&self) -> &str {
+            "mock"
+        }
+
+        async fn start(&self) -> Result<(), String> {
+            self.starts.fetch_add(1, Ordering::SeqCst);
+            self.running.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn stop(&self) -> Result<(), String> {
+            self.stops.fetch_add(1, Ordering::SeqCst);
+            self.running.store(false, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn status(&self) -> IntegrationStatus {
+            IntegrationStatus::new("mock", self.running.load(Ordering::SeqCst))
+        }
+    }
+
+    #[tokio::test]
+    async fn start_stop_and_status_flow_through_registry() {
+        let registry = IntegrationRegistry::new();
+        let mock = Arc::new(MockIntegration::default());
+        registry.register(mock.clone()).await.unwrap();
+
+        assert_eq!(registry.ids().await, vec!["mock".to_string()]);
+        assert!(!registry.status("mock").await.unwrap().running);
+
+        registry.start("mock").await.unwrap();
+        assert_eq!(mock.starts.load(Ordering::SeqCst), 1);
+        assert!(registry.status("mock").await.unwrap().running);
+
+        registry.stop("mock").await.unwrap();
+        assert_eq!(mock.stops.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            registry.statuses().await,
+            vec![IntegrationStatus::new("mock", false)]
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_duplicates_and_unknown_ids() {
+        let registry = IntegrationRegistry::new();
+        registry
+            .register(Arc::new(MockIntegration::default()))
+            .await
+            .unwrap();
+        assert!(registry
+            .register(Arc::new(MockIntegration::default()))
+            .await
+            .is_err());
+
+        assert!(registry.start("missing").await.is_err());
+        assert!(registry.status("missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn unregister_stops_integration() {
+        let registry = IntegrationRegistry::new();
+        let mock = Arc::new(MockIntegration::default());
+        registry.register(mock.clone()).await.unwrap();
+        registry.start("mock").await.unwrap();
+
+        registry.unregister("mock").await.unwrap();
+        assert!(!mock.running.load(Ordering::SeqCst));
+        assert!(registry.get("mock").await.is_none());
+    }
+}
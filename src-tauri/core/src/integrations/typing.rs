@@ -0,0 +1,152 @@
+//! Typing Indicator
+//!
+//! Keeps a platform "typing" signal alive while an agent is generating. The
+//! gateway stops it when the first reply is sent or the gateway shuts down.
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+/// Default refresh cadence. Telegram clears `typing` after ~5 seconds.
+pub const DEFAULT_TYPING_REFRESH: Duration = Duration::from_secs(4);
+
+/// Platform-specific typing signal
+#[async_trait::async_trait]
+pub trait TypingSink: Send + Sync + 'static {
+    /// Send (or refresh) the typing signal
+    async fn send_typing(&self) -> Result<(), String>;
+
+    /// Clear the typing signal. Platforms whose indicator expires on its own
+    /// can keep the default no-op.
+    async fn clear_typing(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Refreshes a typing signal periodically until content arrives
+pub struct TypingIndicator {
+    sink: Arc<dyn TypingSink>,
+    stop_tx: Option<watch::Sender<bool>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl std::fmt::Debug for TypingIndicator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TypingIndicator")
+            .field("active", &self.is_active())
+            .finish()
+    }
+}
+
+impl TypingIndicator {
+    /// Send the typing signal immediately and keep refreshing it every `interval`
+    pub fn start(sink: Arc<dyn TypingSink>, interval: Duration) -> Self {
+        let (stop_tx, mut stop_rx) = watch::channel(false);
+        let loop_sink = sink.clone();
+        let handle = tokio::spawn(async move {
+            loop {
+                if *stop_rx.borrow() {
+                    break;
+                }
+                if let Err(error) = loop_sink.send_typing().await {
+                    log::debug!("[TypingIndicator] Failed to send typing signal: {}", error);
+                }
+                tokio::select! {
+                    _ = tokio::time::sleep(interval) => {}
+                    _ = stop_rx.changed() => {}
+                }
+            }
+        });
+
+        Self {
+            sink,
+            stop_tx: Some(stop_tx),
+            handle: Some(handle),
+        }
+    }
+
+    /// Whether the refresh loop is still running
+    pub fn is_active(&self) -> bool {
+        self.stop_tx.is_some()
+    }
+
+    /// Stop refreshing and clear the platform signal
+    pub async fn stop(&mut self) {
+        let Some(stop_tx) = self.stop_tx.take() else {
+            return;
+        };
+        let _ = stop_tx.send(true);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.await;
+        }
+        if let Err(error) = self.sink.clear_typing().await {
+            log::debug!("[TypingIndicator] Failed to clear typing signal: {}", error);
+        }
+    }
+}
+
+impl Drop for TypingIndicator {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct RecordingSink {
+        sent: AtomicUsize,
+        cleared: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl TypingSink for RecordingSink {
+        async fn send_typing(&self) -> Result<(), String> {
+            self.sent.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn clear_typing(&self) -> Result<(), String> {
+            self.cleared.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_typing_on_start_and_refreshes() {
+        let sink = Arc::new(RecordingSink::default());
+        let mut indicator = TypingIndicator::start(sink.clone(), Duration::from_millis(20));
+
+        tokio::time::sleep(Duration::from_millis(70)).await;
+        assert!(sink.sent.load(Ordering::SeqCst) >= 2);
+        assert!(indicator.is_active());
+
+        indicator.stop().await;
+    }
+
+    #[tokio::test]
+    async fn stop_clears_signal_and_ends_refresh() {
+        let sink = Arc::new(RecordingSink::default());
+        let mut indicator = TypingIndicator::start(sink.clone(), Duration::from_millis(20));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(sink.sent.load(Ordering::SeqCst) >= 1);
+
+        indicator.stop().await;
+        assert!(!indicator.is_active());
+        assert_eq!(sink.cleared.load(Ordering::SeqCst), 1);
+
+        let sent_after_stop = sink.sent.load(Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(sink.sent.load(Ordering::SeqCst), sent_after_stop);
+
+        // Stopping again is a no-op
+        indicator.stop().await;
+        assert_eq!(sink.cleared.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,472 @@
+//! Webhook Integration
+//!
+//! Generic inbound HTTP endpoint so CI pipelines and scripts can prompt the
+//! agent: `POST /v1/integrations/webhook/:token`. The path token must match
+//! one of the secrets stored under the `webhook_secrets` setting.
+
+use crate::core::types::{RuntimeEvent, TaskHandle, TaskInput};
+use crate::core::CoreRuntime;
+use crate::storage::{Message, MessageRole, SettingsRepository, Storage, TaskSettings};
+use axum::extract::{Path, State as AxumState};
+use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::State;
+use tokio::sync::broadcast;
+
+pub const WEBHOOK_SECRETS_SETTING: &str = "webhook_secrets";
+pub const WEBHOOK_ROUTE: &str = "/v1/integrations/webhook/:token";
+
+/// How long a non-streaming request waits for the task to finish
+const WEBHOOK_RESULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Body accepted by the webhook route
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookPromptRequest {
+    pub prompt: String,
+    /// Continue an existing session instead of creating one
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Stream runtime events as SSE instead of waiting for the result
+    #[serde(default)]
+    pub stream: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookPromptResponse {
+    pub session_id: String,
+    pub task_id: String,
+    /// `completed`, `failed` or `timeout`
+    pub status: String,
+    /// Messages created by the task, excluding the prompt itself
+    pub messages: Vec<Message>,
+    pub error: Option<String>,
+}
+
+/// State shared by the webhook handlers
+#[derive(Clone)]
+pub struct WebhookState {
+    pub runtime: CoreRuntime,
+    pub storage: Storage,
+    /// Runtime events, forwarded from the runtime's event sender
+    pub events: broadcast::Sender<RuntimeEvent>,
+}
+
+/// Router exposing the webhook route
+pub fn router(state: WebhookState) -> Router {
+    Router::new()
+        .route(WEBHOOK_ROUTE, post(handle_webhook))
+        .with_state(state)
+}
+
+/// Serve the webhook and file routes on an already bound listener
+pub async fn serve(listener: tokio::net::TcpListener, state: WebhookState) -> Result<(), String> {
+    let files = crate::files::router(state.storage.clone());
+    axum::serve(listener, router(state).merge(files))
+        .await
+        .map_err(|e| format!("Webhook server failed: {}", e))
+}
+
+pub async fn load_secrets(settings: &SettingsRepository) -> Vec<String> {
+    settings
+        .get_setting_or_default::<Vec<String>>(WEBHOOK_SECRETS_SETTING, Vec::new())
+        .await
+        .unwrap_or_else(|e| {
+            log::warn!("[Webhook] Failed to load webhook secrets: {}", e);
+            Vec::new()
+        })
+        .into_iter()
+        .filter(|secret| !secret.trim().is_empty())
+        .collect()
+}
+
+/// Compare without short-circuiting so response timing doesn't leak the secret
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+pub(crate) fn is_valid_token(token: &str, secrets: &[String]) -> bool {
+    !token.is_empty()
+        && secrets.iter().fold(false, |found, secret| {
+            constant_time_eq(token.as_bytes(), secret.as_bytes()) | found
+        })
+}
+
+fn error_response(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(serde_json::json!({ "error": message.into() }))).into_response()
+}
+
+/// Events that belong to the given task or its session
+fn event_belongs_to(event: &RuntimeEvent, task_id: &str, session_id: &str) -> bool {
+    match event {
+        RuntimeEvent::TaskStateChanged { task_id: id, .. }
+        | RuntimeEvent::ToolCallRequested { task_id: id, .. }
+        | RuntimeEvent::ToolCallCompleted { task_id: id, .. } => id == task_id,
+        RuntimeEvent::TaskCompleted { task_id: id, .. } => id == task_id,
+        RuntimeEvent::Error {
+            task_id: id,
+            session_id: sid,
+            ..
+        } => id.as_deref() == Some(task_id) || sid.as_deref() == Some(session_id),
+        RuntimeEvent::MessageCreated {
+            session_id: sid, ..
+        }
+        | RuntimeEvent::Token {
+            session_id: sid, ..
+        }
+        | RuntimeEvent::ReasoningStart {
+            session_id: sid, ..
+        }
+        | RuntimeEvent::ReasoningDelta {
+            session_id: sid, ..
+        }
+        | RuntimeEvent::ReasoningEnd {
+            session_id: sid, ..
+        }
+        | RuntimeEvent::Usage {
+            session_id: sid, ..
+        }
+        | RuntimeEvent::Done {
+            session_id: sid, ..
+        } => sid == session_id,
+    }
+}
+
+fn is_task_completed(event: &RuntimeEvent, task_id: &str) -> bool {
+    matches!(event, RuntimeEvent::TaskCompleted { task_id: id, .. } if id == task_id)
+}
+
+async fn handle_webhook(
+    AxumState(state): AxumState<WebhookState>,
+    Path(token): Path<String>,
+    Json(request): Json<WebhookPromptRequest>,
+) -> Response {
+    let secrets = load_secrets(&state.storage.settings).await;
+    if !is_valid_token(&token, &secrets) {
+        log::warn!("[Webhook] Rejected request with invalid token");
+        return error_response(StatusCode::UNAUTHORIZED, "Invalid webhook token");
+    }
+
+    let prompt = request.prompt.trim();
+    if prompt.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "prompt must not be empty");
+    }
+
+    let settings = request.model.as_ref().map(|model| {
+        let mut settings = TaskSettings::default();
+        settings
+            .extra
+            .insert("model".to_string(), serde_json::json!(model));
+        settings
+    });
+
+    let session_manager = state.runtime.session_manager();
+    let session_id = match request.session_id.as_deref() {
+        Some(session_id) => match session_manager.get_session(session_id).await {
+            Ok(Some(session)) => session.id,
+            Ok(None) => {
+                return error_response(
+                    StatusCode::NOT_FOUND,
+                    format!("Session not found: {}", session_id),
+                )
+            }
+            Err(e) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+        },
+        None => match session_manager
+            .create_session(None, Some(webhook_title(prompt)), settings.clone())
+            .await
+        {
+            Ok(session) => session.id,
+            Err(e) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+        },
+    };
+
+    // Subscribe before starting so no event is missed
+    let events = state.events.subscribe();
+    let handle = match state
+        .runtime
+        .start_task(TaskInput {
+            session_id: session_id.clone(),
+            agent_id: None,
+            project_id: None,
+            initial_message: prompt.to_string(),
+            settings,
+            workspace: None,
+        })
+        .await
+    {
+        Ok(handle) => handle,
+        Err(e) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+    };
+
+    log::info!(
+        "[Webhook] Started task {} in session {} (stream={})",
+        handle.task_id,
+        handle.session_id,
+        request.stream
+    );
+
+    if request.stream {
+        stream_events(handle, events).into_response()
+    } else {
+        Json(collect_result(handle, events, WEBHOOK_RESULT_TIMEOUT).await).into_response()
+    }
+}
+
+fn webhook_title(prompt: &str) -> String {
+    let first_line = prompt.lines().next().unwrap_or_default();
+    let title: String = first_line.chars().take(60).collect();
+    format!("Webhook: {}", title)
+}
+
+async fn collect_result(
+    handle: TaskHandle,
+    mut events: broadcast::Receiver<RuntimeEvent>,
+    timeout: Duration,
+) -> WebhookPromptResponse {
+    let mut response = WebhookPromptResponse {
+        session_id: handle.session_id.clone(),
+        task_id: handle.task_id.clone(),
+        status: "timeout".to_string(),
+        messages: Vec::new(),
+        error: None,
+    };
+
+    let wait = async {
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    log::warn!("[Webhook] Event receiver lagged by {}", skipped);
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            if !event_belongs_to(&event, &handle.task_id, &handle.session_id) {
+                continue;
+            }
+            match event {
+                RuntimeEvent::MessageCreated { message, .. }
+                    if message.role != MessageRole::User =>
+                {
+                    response.messages.push(message);
+                }
+                RuntimeEvent::Error { message, .. } => {
+                    response.error = Some(message);
+                }
+                RuntimeEvent::TaskCompleted { .. } => {
+                    response.status = if response.error.is_some() {
+                        "failed".to_string()
+                    } else {
+                        "completed".to_string()
+                    };
+                    break;
+                }
+                _ => {}
+            }
+        }
+    };
+
+    if tokio::time::timeout(timeout, wait).await.is_err() {
+        log::warn!("[Webhook] Task {} did not finish in time", handle.task_id);
+    }
+    response
+}
+
+fn stream_events(
+    handle: TaskHandle,
+    mut events: broadcast::Receiver<RuntimeEvent>,
+) -> Sse<impl futures::Stream<Item = Result<Event, std::convert::Infallible>>> {
+    let stream = async_stream::stream! {
+        let started = serde_json::json!({
+            "session_id": handle.session_id,
+            "task_id": handle.task_id,
+        });
+        if let Ok(event) = Event::default().event("started").json_data(started) {
+            yield Ok::<_, std::convert::Infallible>(event);
+        }
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            if !event_belongs_to(&event, &handle.task_id, &handle.session_id) {
+                continue;
+            }
+            let done = is_task_completed(&event, &handle.task_id);
+            if let Ok(sse_event) = Event::default().json_data(&event) {
+                yield Ok(sse_event);
+            }
+            if done {
+                break;
+            }
+        }
+    };
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// List configured webhook secrets
+#[tauri::command]
+pub async fn webhook_list_secrets(storage: State<'_, Storage>) -> Result<Vec<String>, String> {
+    Ok(load_secrets(&storage.settings).await)
+}
+
+/// Generate and store a new webhook secret
+#[tauri::command]
+pub async fn webhook_create_secret(storage: State<'_, Storage>) -> Result<String, String> {
+    let mut secrets = load_secrets(&storage.settings).await;
+    let secret = uuid::Uuid::new_v4().simple().to_string();
+    secrets.push(secret.clone());
+    storage
+        .settings
+        .set_setting(WEBHOOK_SECRETS_SETTING, &serde_json::json!(secrets))
+        .await?;
+    Ok(secret)
+}
+
+#[tauri::command]
+pub async fn webhook_revoke_secret(
+    storage: State<'_, Storage>,
+    secret: String,
+) -> Result<(), String> {
+    let mut secrets = load_secrets(&storage.settings).await;
+    secrets.retain(|existing| existing != &secret);
+    storage
+        .settings
+        .set_setting(WEBHOOK_SECRETS_SETTING, &serde_json::json!(secrets))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::auth::api_key_manager::ApiKeyManager;
+    use crate::llm::providers::provider_registry::ProviderRegistry;
+    use tempfile::TempDir;
+    use tokio::sync::mpsc;
+
+    async fn spawn_webhook_server() -> (String, WebhookState, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(
+            temp_dir.path().to_path_buf(),
+            temp_dir.path().join("attachments"),
+        )
+        .await
+        .expect("Failed to create storage");
+        storage
+            .settings
+            .set_setting(WEBHOOK_SECRETS_SETTING, &serde_json::json!(["s3cret"]))
+            .await
+            .unwrap();
+
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel::<RuntimeEvent>();
+        let (broadcast_tx, _) = broadcast::channel(100);
+        let forward_tx = broadcast_tx.clone();
+        tokio::spawn(async move {
+            while let Some(event) = event_rx.recv().await {
+                let _ = forward_tx.send(event);
+            }
+        });
+
+        let api_key_manager =
+            ApiKeyManager::new(storage.settings.get_db(), temp_dir.path().to_path_buf());
+        let runtime = CoreRuntime::new(
+            storage.clone(),
+            event_tx,
+            ProviderRegistry::default(),
+            api_key_manager,
+        )
+        .await
+        .expect("Failed to create runtime");
+
+        let state = WebhookState {
+            runtime,
+            storage,
+            events: broadcast_tx,
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, state.clone()));
+        (format!("http://{}", addr), state, temp_dir)
+    }
+
+    #[tokio::test]
+    async fn valid_token_creates_session() {
+        let (base, state, _temp) = spawn_webhook_server().await;
+
+        let response = reqwest::Client::new()
+            .post(format!("{}/v1/integrations/webhook/s3cret", base))
+            .json(&serde_json::json!({ "prompt": "run the tests", "model": "gpt-4o" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+
+        let body: WebhookPromptResponse = response.json().await.unwrap();
+        assert_eq!(body.status, "completed");
+        assert!(body.session_id.starts_with("sess_"));
+
+        let session = state
+            .runtime
+            .session_manager()
+            .get_session(&body.session_id)
+            .await
+            .unwrap()
+            .expect("session should exist");
+        assert_eq!(session.title.as_deref(), Some("Webhook: run the tests"));
+
+        let messages = state
+            .runtime
+            .session_manager()
+            .get_messages(&body.session_id, None, None)
+            .await
+            .unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].role, MessageRole::User);
+    }
+
+    #[tokio::test]
+    async fn invalid_token_returns_401() {
+        let (base, _state, _temp) = spawn_webhook_server().await;
+
+        let response = reqwest::Client::new()
+            .post(format!("{}/v1/integrations/webhook/wrong", base))
+            .json(&serde_json::json!({ "prompt": "run the tests" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 401);
+    }
+
+    #[tokio::test]
+    async fn unknown_session_returns_404() {
+        let (base, _state, _temp) = spawn_webhook_server().await;
+
+        let response = reqwest::Client::new()
+            .post(format!("{}/v1/integrations/webhook/s3cret", base))
+            .json(&serde_json::json!({ "prompt": "hi", "session_id": "sess_missing" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 404);
+    }
+
+    #[test]
+    fn token_must_match_a_configured_secret() {
+        let secrets = vec!["alpha".to_string(), "beta".to_string()];
+        assert!(is_valid_token("beta", &secrets));
+        assert!(!is_valid_token("bet", &secrets));
+        assert!(!is_valid_token("", &secrets));
+        assert!(!is_valid_token("alpha", &[]));
+    }
+}
@@ -22,11 +22,14 @@ pub mod constants;
 pub mod database;
 pub mod device_id;
 pub mod directory_tree;
+pub mod discord_gateway;
 pub mod feishu_gateway;
 pub mod file_search;
+pub mod files;
 pub mod glob;
 pub mod http_proxy;
 pub mod list_files;
+pub mod network_proxy;
 pub mod oauth_callback_server;
 pub mod script_executor;
 pub mod search;
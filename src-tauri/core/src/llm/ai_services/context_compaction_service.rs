@@ -1,11 +1,74 @@
 use crate::llm::ai_services::model_resolver::{resolve_model_identifiers, FallbackStrategy};
 use crate::llm::ai_services::stream_collector::StreamCollector;
 use crate::llm::ai_services::stream_runner::StreamRunner;
-use crate::llm::ai_services::types::{ContextCompactionRequest, ContextCompactionResult};
+use crate::llm::ai_services::types::{
+    CompactionMessage, CompactionStreamEvent, ContextCompactionRequest, ContextCompactionResult,
+};
 use crate::llm::auth::api_key_manager::ApiKeyManager;
 use crate::llm::providers::provider_registry::ProviderRegistry;
+use crate::llm::types::StreamEvent;
+use std::collections::HashSet;
 use std::time::{Duration, Instant};
 
+/// Tool results kept verbatim when the request doesn't say otherwise
+const DEFAULT_PRESERVED_TOOL_RESULTS: usize = 2;
+
+/// Split of the history into what gets summarized and what is kept as-is
+#[derive(Debug, Clone, PartialEq)]
+struct CompactionPlan {
+    to_summarize: String,
+    preserved: Vec<CompactionMessage>,
+    tokens_before: usize,
+}
+
+/// Rough token estimate (~4 characters per token)
+fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Source of summary text for streaming compaction
+#[async_trait::async_trait]
+pub trait Summarizer: Send + Sync {
+    /// Generate the summary for `prompt`, passing each chunk to `on_delta`
+    async fn summarize(
+        &self,
+        prompt: String,
+        on_delta: &mut (dyn FnMut(String) + Send),
+    ) -> Result<(), String>;
+}
+
+/// Summarizer backed by the provider stream runner
+struct RunnerSummarizer {
+    runner: StreamRunner,
+    model: String,
+    fallback_models: Option<Vec<String>>,
+    timeout: Duration,
+}
+
+#[async_trait::async_trait]
+impl Summarizer for RunnerSummarizer {
+    async fn summarize(
+        &self,
+        prompt: String,
+        on_delta: &mut (dyn FnMut(String) + Send),
+    ) -> Result<(), String> {
+        let request = StreamCollector::create_completion_request(
+            self.model.clone(),
+            self.fallback_models.clone(),
+            prompt,
+        );
+        self.runner
+            .stream_live(request, self.timeout, |event| match event {
+                StreamEvent::TextDelta { text } => on_delta(text),
+                StreamEvent::Error { message } => {
+                    log::error!("Stream error: {}", message);
+                }
+                _ => {}
+            })
+            .await
+    }
+}
+
 pub struct ContextCompactionService {
     compression_timeout_ms: u64,
 }
@@ -37,12 +100,13 @@ impl ContextCompactionService {
 
         log::info!("Starting AI context compaction");
 
-        if request.conversation_history.trim().is_empty() {
+        let plan = self.plan_compaction(&request);
+        if plan.to_summarize.trim().is_empty() {
             log::error!("No conversation history provided for compaction");
             return Err("Conversation history is required for compaction".to_string());
         }
 
-        let prompt = self.build_compaction_prompt(&request.conversation_history);
+        let prompt = self.build_compaction_prompt(&plan.to_summarize);
         log::info!(
             "Context compaction prompt generated (length: {} chars)",
             prompt.len()
@@ -87,10 +151,171 @@ impl ContextCompactionService {
         log::info!(
             "Compressed summary length: {} characters (from {})",
             compressed_summary.len(),
-            request.conversation_history.len()
+            plan.to_summarize.len()
         );
 
-        Ok(ContextCompactionResult { compressed_summary })
+        let result = self.build_result(compressed_summary, plan);
+        if let Some(target) = request.target_tokens {
+            if result.tokens_after > target {
+                log::warn!(
+                    "Compacted context is {} tokens, above the {} token target",
+                    result.tokens_after,
+                    target
+                );
+            }
+        }
+        Ok(result)
+    }
+
+    /// Compress conversation history, reporting summary chunks as they arrive
+    /// and finishing with a `Completed` or `Error` event
+    pub async fn compact_context_streaming<E>(
+        &self,
+        request: ContextCompactionRequest,
+        api_keys: &ApiKeyManager,
+        registry: &ProviderRegistry,
+        mut on_event: E,
+    ) -> Result<ContextCompactionResult, String>
+    where
+        E: FnMut(CompactionStreamEvent) + Send,
+    {
+        let resolved_models = match resolve_model_identifiers(
+            api_keys,
+            registry,
+            request.model.clone(),
+            request.fallback_models.clone(),
+            FallbackStrategy::Compaction,
+        )
+        .await
+        {
+            Ok(models) => models,
+            Err(message) => {
+                on_event(CompactionStreamEvent::Error {
+                    message: message.clone(),
+                });
+                return Err(message);
+            }
+        };
+
+        let summarizer = RunnerSummarizer {
+            runner: StreamRunner::new(registry.clone(), api_keys.clone()),
+            model: resolved_models[0].clone(),
+            fallback_models: Some(resolved_models[1..].to_vec()).filter(|m| !m.is_empty()),
+            timeout: self.timeout(),
+        };
+        self.compact_with_summarizer(request, &summarizer, on_event)
+            .await
+    }
+
+    /// Streaming compaction against any [`Summarizer`]
+    pub async fn compact_with_summarizer<E>(
+        &self,
+        request: ContextCompactionRequest,
+        summarizer: &dyn Summarizer,
+        mut on_event: E,
+    ) -> Result<ContextCompactionResult, String>
+    where
+        E: FnMut(CompactionStreamEvent) + Send,
+    {
+        let plan = self.plan_compaction(&request);
+        let result = if plan.to_summarize.trim().is_empty() {
+            Err("Conversation history is required for compaction".to_string())
+        } else {
+            let prompt = self.build_compaction_prompt(&plan.to_summarize);
+            let mut summary = String::new();
+            let outcome = {
+                let mut on_delta = |text: String| {
+                    summary.push_str(&text);
+                    on_event(CompactionStreamEvent::SummaryDelta { text });
+                };
+                summarizer.summarize(prompt, &mut on_delta).await
+            };
+            let summary = summary.trim().to_string();
+            outcome
+                .and_then(|()| self.validate_compaction_summary(&summary))
+                .map(|()| self.build_result(summary, plan))
+        };
+
+        match &result {
+            Ok(result) => on_event(CompactionStreamEvent::Completed {
+                result: result.clone(),
+            }),
+            Err(message) => on_event(CompactionStreamEvent::Error {
+                message: message.clone(),
+            }),
+        }
+        result
+    }
+
+    /// Decide which messages are summarized. Pinned messages and the most
+    /// recent tool results are kept verbatim; everything else is rendered
+    /// into the history handed to the model.
+    fn plan_compaction(&self, request: &ContextCompactionRequest) -> CompactionPlan {
+        let messages = match &request.messages {
+            Some(messages) => messages,
+            None => {
+                return CompactionPlan {
+                    to_summarize: request.conversation_history.clone(),
+                    preserved: Vec::new(),
+                    tokens_before: estimate_tokens(&request.conversation_history),
+                }
+            }
+        };
+
+        let pinned: HashSet<&str> = request
+            .pinned_message_ids
+            .iter()
+            .map(String::as_str)
+            .collect();
+        let keep_tool_results = request
+            .preserve_recent_tool_results
+            .unwrap_or(DEFAULT_PRESERVED_TOOL_RESULTS);
+        let recent_tool_results: HashSet<&str> = messages
+            .iter()
+            .rev()
+            .filter(|message| message.role == "tool")
+            .take(keep_tool_results)
+            .map(|message| message.id.as_str())
+            .collect();
+
+        let mut preserved = Vec::new();
+        let mut summarized = Vec::new();
+        let mut tokens_before = 0;
+        for message in messages {
+            tokens_before += estimate_tokens(&message.content);
+            let id = message.id.as_str();
+            if pinned.contains(id) || recent_tool_results.contains(id) {
+                preserved.push(message.clone());
+            } else {
+                summarized.push(format!("{}: {}", message.role, message.content));
+            }
+        }
+
+        CompactionPlan {
+            to_summarize: summarized.join("\n\n"),
+            preserved,
+            tokens_before,
+        }
+    }
+
+    fn build_result(
+        &self,
+        compressed_summary: String,
+        plan: CompactionPlan,
+    ) -> ContextCompactionResult {
+        let tokens_after = estimate_tokens(&compressed_summary)
+            + plan
+                .preserved
+                .iter()
+                .map(|message| estimate_tokens(&message.content))
+                .sum::<usize>();
+        ContextCompactionResult {
+            compressed_summary,
+            preserved_messages: plan.preserved,
+            tokens_before: plan.tokens_before,
+            tokens_after,
+            tokens_saved: plan.tokens_before.saturating_sub(tokens_after),
+        }
     }
 
     fn validate_compaction_summary(&self, summary: &str) -> Result<(), String> {
@@ -202,6 +427,8 @@ mod tests {
                         cache_creation: None,
                     }),
                     context_length: Some(8192),
+                    capabilities: None,
+                    context_window: None,
                 },
             )]),
         };
@@ -275,6 +502,10 @@ mod tests {
         let service = ContextCompactionService::new();
         let request = ContextCompactionRequest {
             conversation_history: "   ".to_string(),
+            messages: None,
+            pinned_message_ids: Vec::new(),
+            preserve_recent_tool_results: None,
+            target_tokens: None,
             model: None,
             fallback_models: None,
         };
@@ -305,4 +536,174 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    fn message(id: &str, role: &str, content: &str) -> CompactionMessage {
+        CompactionMessage {
+            id: id.to_string(),
+            role: role.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    fn structured_request(
+        messages: Vec<CompactionMessage>,
+        pinned: &[&str],
+    ) -> ContextCompactionRequest {
+        ContextCompactionRequest {
+            conversation_history: String::new(),
+            messages: Some(messages),
+            pinned_message_ids: pinned.iter().map(|id| id.to_string()).collect(),
+            preserve_recent_tool_results: Some(1),
+            target_tokens: Some(200),
+            model: None,
+            fallback_models: None,
+        }
+    }
+
+    fn long_history() -> Vec<CompactionMessage> {
+        let prose = "Discussed the refactor of the session layer in detail. ".repeat(40);
+        vec![
+            message("m1", "user", "Always keep the public API stable."),
+            message("m2", "assistant", &prose),
+            message("m3", "tool", "old listing output"),
+            message("m4", "assistant", &prose),
+            message("m5", "tool", "latest test output: 42 passed"),
+        ]
+    }
+
+    #[test]
+    fn plan_keeps_pinned_and_recent_tool_results() {
+        let service = ContextCompactionService::new();
+        let plan = service.plan_compaction(&structured_request(long_history(), &["m1"]));
+
+        let preserved: Vec<&str> = plan.preserved.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(preserved, vec!["m1", "m5"]);
+        assert!(!plan
+            .to_summarize
+            .contains("Always keep the public API stable."));
+        assert!(plan.to_summarize.contains("old listing output"));
+        assert!(!plan.to_summarize.contains("42 passed"));
+    }
+
+    #[test]
+    fn plan_falls_back_to_rendered_history() {
+        let service = ContextCompactionService::new();
+        let request = ContextCompactionRequest {
+            conversation_history: "User: hi".to_string(),
+            messages: None,
+            pinned_message_ids: vec!["m1".to_string()],
+            preserve_recent_tool_results: None,
+            target_tokens: None,
+            model: None,
+            fallback_models: None,
+        };
+        let plan = service.plan_compaction(&request);
+
+        assert_eq!(plan.to_summarize, "User: hi");
+        assert!(plan.preserved.is_empty());
+    }
+
+    #[test]
+    fn result_reports_tokens_saved_under_target() {
+        let service = ContextCompactionService::new();
+        let request = structured_request(long_history(), &["m1"]);
+        let plan = service.plan_compaction(&request);
+        let result = service.build_result("Refactored the session layer.".to_string(), plan);
+
+        assert_eq!(
+            result.preserved_messages[0].content,
+            "Always keep the public API stable."
+        );
+        assert!(result.tokens_after < request.target_tokens.unwrap());
+        assert!(result.tokens_before > request.target_tokens.unwrap());
+        assert_eq!(
+            result.tokens_saved,
+            result.tokens_before - result.tokens_after
+        );
+    }
+
+    #[tokio::test]
+    async fn compact_fails_when_everything_is_preserved() {
+        let (api_keys, registry) = setup_context().await;
+        let service = ContextCompactionService::new();
+        let request = structured_request(vec![message("m1", "user", "keep me")], &["m1"]);
+
+        let result = service.compact_context(request, &api_keys, &registry).await;
+
+        assert!(result.is_err());
+    }
+
+    struct MockSummarizer {
+        chunks: Vec<&'static str>,
+    }
+
+    #[async_trait::async_trait]
+    impl Summarizer for MockSummarizer {
+        async fn summarize(
+            &self,
+            _prompt: String,
+            on_delta: &mut (dyn FnMut(String) + Send),
+        ) -> Result<(), String> {
+            for chunk in &self.chunks {
+                on_delta(chunk.to_string());
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn streaming_emits_chunks_before_final_result() {
+        let service = ContextCompactionService::new();
+        let summarizer = MockSummarizer {
+            chunks: vec!["Refactored ", "the session ", "layer."],
+        };
+        let mut events = Vec::new();
+
+        let result = service
+            .compact_with_summarizer(
+                structured_request(long_history(), &["m1"]),
+                &summarizer,
+                |event| events.push(event),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.compressed_summary, "Refactored the session layer.");
+        assert_eq!(events.len(), 4);
+        let deltas: Vec<&str> = events[..3]
+            .iter()
+            .map(|event| match event {
+                CompactionStreamEvent::SummaryDelta { text } => text.as_str(),
+                other => panic!("expected delta, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(deltas, vec!["Refactored ", "the session ", "layer."]);
+        match &events[3] {
+            CompactionStreamEvent::Completed { result } => {
+                assert_eq!(result.preserved_messages.len(), 2);
+            }
+            other => panic!("expected completion, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn streaming_reports_empty_summary_as_error() {
+        let service = ContextCompactionService::new();
+        let summarizer = MockSummarizer { chunks: vec!["  "] };
+        let mut events = Vec::new();
+
+        let result = service
+            .compact_with_summarizer(
+                structured_request(long_history(), &[]),
+                &summarizer,
+                |event| events.push(event),
+            )
+            .await;
+
+        assert!(result.is_err());
+        assert!(matches!(
+            events.last(),
+            Some(CompactionStreamEvent::Error { .. })
+        ));
+    }
 }
@@ -1,11 +1,25 @@
 use crate::llm::ai_services::model_resolver::{resolve_model_identifiers, FallbackStrategy};
-use crate::llm::ai_services::stream_collector::StreamCollector;
+use crate::llm::ai_services::stream_collector::{json_string_field, StreamCollector};
 use crate::llm::ai_services::stream_runner::StreamRunner;
-use crate::llm::ai_services::types::{GitMessageContext, GitMessageResult};
+use crate::llm::ai_services::types::{CommitMessageStyle, GitMessageContext, GitMessageResult};
 use crate::llm::auth::api_key_manager::ApiKeyManager;
 use crate::llm::providers::provider_registry::ProviderRegistry;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::future::Future;
 use std::time::Duration;
 
+lazy_static! {
+    /// `type(scope)!: description` per the Conventional Commits spec
+    static ref CONVENTIONAL_SUBJECT: Regex = Regex::new(
+        r"^(feat|fix|docs|style|refactor|perf|test|build|ci|chore|revert)(\([\w\-./ ]+\))?!?: \S"
+    )
+    .expect("valid conventional commit regex");
+}
+
+/// Subject length used when the request doesn't set one
+pub const DEFAULT_MAX_SUBJECT_LENGTH: usize = 72;
+
 pub struct GitMessageService;
 
 impl GitMessageService {
@@ -30,12 +44,6 @@ impl GitMessageService {
             return Err("No diff text provided".to_string());
         }
 
-        let prompt = self.build_prompt(&context);
-        log::info!(
-            "Generated prompt for git commit message (length: {})",
-            prompt.len()
-        );
-
         let resolved_models = resolve_model_identifiers(
             api_keys,
             registry,
@@ -46,24 +54,32 @@ impl GitMessageService {
         .await?;
         let model_identifier = resolved_models[0].clone();
         let fallback_models = resolved_models[1..].to_vec();
-
-        let request = StreamCollector::create_completion_request(
-            model_identifier,
-            if fallback_models.is_empty() {
-                None
-            } else {
-                Some(fallback_models)
-            },
-            prompt,
-        );
         let runner = StreamRunner::new(registry.clone(), api_keys.clone());
-        let result =
-            StreamCollector::collect_with_runner(&runner, request, Duration::from_secs(30)).await?;
 
-        let message = self.post_process_message(&result.text);
-        if message.is_empty() {
-            return Err("Empty commit message generated".to_string());
-        }
+        let message = self
+            .generate_with(&context, |prompt| {
+                let request = StreamCollector::create_json_field_request(
+                    model_identifier.clone(),
+                    if fallback_models.is_empty() {
+                        None
+                    } else {
+                        Some(fallback_models.clone())
+                    },
+                    prompt,
+                    "message",
+                );
+                let runner = &runner;
+                async move {
+                    let result = StreamCollector::collect_with_runner(
+                        runner,
+                        request,
+                        Duration::from_secs(30),
+                    )
+                    .await?;
+                    json_string_field(&result.text, "message")
+                }
+            })
+            .await?;
 
         Ok(GitMessageResult {
             message,
@@ -71,6 +87,94 @@ impl GitMessageService {
         })
     }
 
+    /// Runs the prompt through `complete` and post-processes the output. A
+    /// message that breaks the requested format or subject length is
+    /// regenerated once; a subject still over the limit is then truncated.
+    async fn generate_with<F, Fut>(
+        &self,
+        context: &GitMessageContext,
+        mut complete: F,
+    ) -> Result<String, String>
+    where
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output = Result<String, String>>,
+    {
+        let prompt = match context.style {
+            CommitMessageStyle::Plain => self.build_prompt(context),
+            CommitMessageStyle::Conventional => self.build_conventional_prompt(context),
+        };
+        log::info!(
+            "Generated prompt for git commit message (length: {})",
+            prompt.len()
+        );
+
+        let raw = complete(prompt.clone()).await?;
+        let mut message = self.post_process(&raw, context);
+
+        if let Some(problem) = self.validation_problem(&message, context) {
+            log::warn!(
+                "Generated commit message rejected ({}), regenerating: {}",
+                problem,
+                message
+            );
+            let retry_prompt = format!(
+                "{}\n\nYour previous answer \"{}\" {}",
+                prompt,
+                message.lines().next().unwrap_or_default(),
+                problem
+            );
+            let raw = complete(retry_prompt).await?;
+            message = self.post_process(&raw, context);
+            if let Some(problem) = self.validation_problem(&message, context) {
+                log::warn!("Regenerated commit message still rejected: {}", problem);
+            }
+        }
+
+        let message = truncate_subject(&message, max_subject_length(context));
+        if message.is_empty() {
+            return Err("Empty commit message generated".to_string());
+        }
+        Ok(message)
+    }
+
+    /// Why `message` should be regenerated, phrased as feedback for the model
+    fn validation_problem(&self, message: &str, context: &GitMessageContext) -> Option<String> {
+        if context.style == CommitMessageStyle::Conventional && !is_conventional(message) {
+            return Some(
+                "did not follow the required format. The first line MUST start with one of the \
+                 allowed types, e.g. \"fix(parser): handle empty input\"."
+                    .to_string(),
+            );
+        }
+        let max_length = max_subject_length(context);
+        let subject_length = message.lines().next().unwrap_or_default().chars().count();
+        if subject_length > max_length {
+            return Some(format!(
+                "has a {}-character subject line. Keep the subject line within {} characters.",
+                subject_length, max_length
+            ));
+        }
+        None
+    }
+
+    /// Extra prompt instructions for the body and language options
+    fn option_instructions(&self, context: &GitMessageContext) -> String {
+        let mut instructions = String::new();
+        if context.include_body {
+            instructions.push_str(
+                "\n\nAfter the subject line, add a blank line followed by a short body \
+                 (1-3 lines or bullet points) explaining what changed and why.",
+            );
+        }
+        if let Some(language) = context.language.as_deref().and_then(language_name) {
+            instructions.push_str(&format!("\n\nWrite the commit message in {}.", language));
+            if context.style == CommitMessageStyle::Conventional {
+                instructions.push_str(" Keep the type and scope in English.");
+            }
+        }
+        instructions
+    }
+
     /// Build the prompt for commit message generation
     fn build_prompt(&self, context: &GitMessageContext) -> String {
         let user_input_section = context
@@ -87,7 +191,7 @@ impl GitMessageService {
              Generate a concise git commit message that follows these guidelines:\n\
              1. Use conventional commit format: type(scope): description\n\
              2. Types: feat, fix, docs, style, refactor, test, chore\n\
-             3. Keep the message under 72 characters for the subject line\n\
+             3. Keep the message under {} characters for the subject line\n\
              4. Be specific about what was changed based on the actual diff content\n\
              5. Use imperative mood (e.g., \"add\", \"fix\", \"update\")\n\n\
              Examples:\n\
@@ -95,11 +199,80 @@ impl GitMessageService {
              - fix(api): resolve data validation error\n\
              - docs: update installation instructions\n\
              - refactor: simplify user service logic\n\n\
-             Provide ONLY the commit message without any explanations or formatting.",
-            user_input_section, context.diff_text
+             Provide ONLY the commit message without any explanations or formatting.{}",
+            user_input_section,
+            context.diff_text,
+            max_subject_length(context),
+            self.option_instructions(context)
         )
     }
 
+    /// Build the prompt for Conventional Commits mode
+    fn build_conventional_prompt(&self, context: &GitMessageContext) -> String {
+        let user_input_section = context
+            .user_input
+            .as_ref()
+            .map(|input| format!("User task description: \"{}\"\n", input))
+            .unwrap_or_default();
+
+        format!(
+            "You are an AI assistant that writes git commit messages in the Conventional Commits 1.0.0 format.\n\n\
+             {}\
+             File changes (git diff):\n\
+             {}\n\n\
+             Rules:\n\
+             1. The first line MUST be: type(scope)!: description\n\
+             2. type is one of: feat, fix, docs, style, refactor, perf, test, build, ci, chore, revert\n\
+             3. (scope) is optional and names the affected area, e.g. (auth) or (api)\n\
+             4. Add ! before the colon only for breaking changes, and then also add a footer line \
+             \"BREAKING CHANGE: <what breaks>\" after a blank line\n\
+             5. description is lowercase, imperative mood, no trailing period, under {} characters in total\n\n\
+             Examples:\n\
+             - feat(auth): add user authentication system\n\
+             - fix(api): resolve data validation error\n\
+             - refactor!: drop support for legacy config files\n\n\
+             BREAKING CHANGE: config.ini is no longer read\n\n\
+             Provide ONLY the commit message without any explanations or formatting.{}",
+            user_input_section,
+            context.diff_text,
+            max_subject_length(context),
+            self.option_instructions(context)
+        )
+    }
+
+    fn post_process(&self, raw: &str, context: &GitMessageContext) -> String {
+        if context.include_body {
+            return self.post_process_with_body(raw);
+        }
+        match context.style {
+            CommitMessageStyle::Plain => self.post_process_message(raw),
+            CommitMessageStyle::Conventional => self.post_process_conventional(raw),
+        }
+    }
+
+    /// Keeps the subject and everything after it, normalizing the gap to one blank line
+    fn post_process_with_body(&self, raw: &str) -> String {
+        let text = raw
+            .lines()
+            .filter(|line| !line.trim_start().starts_with("```"))
+            .map(str::trim_end)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut lines = text.trim().lines();
+        let Some(subject) = lines.next() else {
+            return String::new();
+        };
+        let subject = subject.trim().trim_matches('`').trim();
+        let body = lines.collect::<Vec<_>>().join("\n");
+        let body = body.trim();
+
+        if body.is_empty() {
+            subject.to_string()
+        } else {
+            format!("{}\n\n{}", subject, body)
+        }
+    }
+
     fn post_process_message(&self, raw: &str) -> String {
         let trimmed = raw.trim();
         if trimmed.is_empty() {
@@ -108,6 +281,26 @@ impl GitMessageService {
         trimmed.lines().next().unwrap_or(trimmed).trim().to_string()
     }
 
+    /// Keeps the subject plus a `BREAKING CHANGE:` footer if the model wrote one,
+    /// dropping code fences and any other prose
+    fn post_process_conventional(&self, raw: &str) -> String {
+        let mut lines = raw
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with("```"));
+        let Some(subject) = lines.next() else {
+            return String::new();
+        };
+        let subject = subject.trim_matches('`').trim();
+
+        match lines.find(|line| {
+            line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+        }) {
+            Some(footer) => format!("{}\n\n{}", subject, footer),
+            None => subject.to_string(),
+        }
+    }
+
     /// Get the preferred model for git message generation
     pub fn preferred_model() -> &'static str {
         "gemini-2.5-flash-lite"
@@ -119,3 +312,240 @@ impl Default for GitMessageService {
         Self::new()
     }
 }
+
+fn max_subject_length(context: &GitMessageContext) -> usize {
+    context
+        .max_subject_length
+        .filter(|&max| max > 0)
+        .unwrap_or(DEFAULT_MAX_SUBJECT_LENGTH)
+}
+
+/// Maps a language code to the name used in the prompt; `None` for English
+fn language_name(code: &str) -> Option<String> {
+    let primary = code.split(['-', '_']).next().unwrap_or(code).to_lowercase();
+    let name = match primary.as_str() {
+        "" | "en" => return None,
+        "zh" => "Chinese",
+        "ja" => "Japanese",
+        "ko" => "Korean",
+        "de" => "German",
+        "fr" => "French",
+        "es" => "Spanish",
+        "pt" => "Portuguese",
+        "ru" => "Russian",
+        _ => code,
+    };
+    Some(name.to_string())
+}
+
+/// Shortens the subject line to `max_chars` characters, cutting at a word
+/// boundary when there is one (CJK text without spaces is cut mid-run)
+fn truncate_subject(message: &str, max_chars: usize) -> String {
+    let (subject, rest) = match message.split_once('\n') {
+        Some((subject, rest)) => (subject, Some(rest)),
+        None => (message, None),
+    };
+    if subject.chars().count() <= max_chars {
+        return message.to_string();
+    }
+
+    let hard_cut: String = subject.chars().take(max_chars).collect();
+    let next_is_space = subject
+        .chars()
+        .nth(max_chars)
+        .is_some_and(char::is_whitespace);
+    let cut = if next_is_space {
+        hard_cut.as_str()
+    } else {
+        match hard_cut.rfind(char::is_whitespace) {
+            Some(index) if index >= hard_cut.len() / 2 => &hard_cut[..index],
+            _ => hard_cut.as_str(),
+        }
+    };
+    let cut =
+        cut.trim_end_matches(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | ':' | '-'));
+
+    match rest {
+        Some(rest) => format!("{}\n{}", cut, rest),
+        None => cut.to_string(),
+    }
+}
+
+/// Whether the first line of `message` is a valid Conventional Commits subject
+pub fn is_conventional(message: &str) -> bool {
+    message
+        .lines()
+        .next()
+        .is_some_and(|subject| CONVENTIONAL_SUBJECT.is_match(subject))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    fn context(style: CommitMessageStyle) -> GitMessageContext {
+        GitMessageContext {
+            user_input: None,
+            diff_text: "diff --git a/src/lib.rs b/src/lib.rs\n+fn added() {}\n".to_string(),
+            model: None,
+            fallback_models: None,
+            style,
+            max_subject_length: None,
+            include_body: false,
+            language: None,
+        }
+    }
+
+    /// Returns canned responses in order and records the prompts it saw
+    async fn generate(
+        style: CommitMessageStyle,
+        responses: &[&str],
+    ) -> (Result<String, String>, Vec<String>) {
+        generate_for(&context(style), responses).await
+    }
+
+    async fn generate_for(
+        context: &GitMessageContext,
+        responses: &[&str],
+    ) -> (Result<String, String>, Vec<String>) {
+        let prompts = RefCell::new(Vec::new());
+        let responses = RefCell::new(responses.iter().map(|r| r.to_string()).collect::<Vec<_>>());
+        let result = GitMessageService::new()
+            .generate_with(context, |prompt| {
+                prompts.borrow_mut().push(prompt);
+                let response = responses.borrow_mut().remove(0);
+                async move { Ok(response) }
+            })
+            .await;
+        (result, prompts.into_inner())
+    }
+
+    #[test]
+    fn test_is_conventional() {
+        assert!(is_conventional("feat: add login"));
+        assert!(is_conventional("fix(api): handle timeouts"));
+        assert!(is_conventional(
+            "refactor(core)!: drop v1 config\n\nBREAKING CHANGE: x"
+        ));
+        assert!(!is_conventional("Add login"));
+        assert!(!is_conventional("feature: add login"));
+        assert!(!is_conventional("feat:add login"));
+    }
+
+    #[tokio::test]
+    async fn test_conventional_output_starts_with_type_prefix() {
+        let (result, prompts) = generate(
+            CommitMessageStyle::Conventional,
+            &["```\nfeat(lib): add helper function\n```"],
+        )
+        .await;
+
+        let message = result.unwrap();
+        assert!(is_conventional(&message));
+        assert_eq!(message, "feat(lib): add helper function");
+        assert_eq!(prompts.len(), 1);
+        assert!(prompts[0].contains("Conventional Commits"));
+    }
+
+    #[tokio::test]
+    async fn test_conventional_regenerates_once_when_invalid() {
+        let (result, prompts) = generate(
+            CommitMessageStyle::Conventional,
+            &["Added a helper function", "feat: add helper function"],
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), "feat: add helper function");
+        assert_eq!(prompts.len(), 2);
+        assert!(prompts[1].contains("Added a helper function"));
+    }
+
+    #[tokio::test]
+    async fn test_conventional_keeps_breaking_change_footer() {
+        let (result, _) = generate(
+            CommitMessageStyle::Conventional,
+            &["feat(api)!: remove v1 endpoints\n\nBREAKING CHANGE: /v1 routes are gone"],
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap(),
+            "feat(api)!: remove v1 endpoints\n\nBREAKING CHANGE: /v1 routes are gone"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_plain_mode_is_unaffected() {
+        let (result, prompts) = generate(
+            CommitMessageStyle::Plain,
+            &["Added a helper function\n\nMore details"],
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), "Added a helper function");
+        assert_eq!(prompts.len(), 1);
+        assert!(prompts[0].contains("following conventional commit format"));
+    }
+
+    #[tokio::test]
+    async fn test_subject_respects_length_cap() {
+        let mut context = context(CommitMessageStyle::Plain);
+        context.max_subject_length = Some(30);
+        let long = "Add a helper function that formats every single thing";
+
+        let (result, prompts) = generate_for(&context, &[long, long]).await;
+
+        let message = result.unwrap();
+        assert!(message.chars().count() <= 30);
+        assert_eq!(message, "Add a helper function that");
+        assert_eq!(prompts.len(), 2);
+        assert!(prompts[0].contains("under 30 characters"));
+        assert!(prompts[1].contains("within 30 characters"));
+    }
+
+    #[tokio::test]
+    async fn test_short_subject_is_not_regenerated() {
+        let mut context = context(CommitMessageStyle::Plain);
+        context.max_subject_length = Some(30);
+
+        let (result, prompts) = generate_for(&context, &["Add helper"]).await;
+
+        assert_eq!(result.unwrap(), "Add helper");
+        assert_eq!(prompts.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_include_body_produces_multi_line_message() {
+        let mut context = context(CommitMessageStyle::Conventional);
+        context.include_body = true;
+
+        let (result, prompts) = generate_for(
+            &context,
+            &["feat(lib): add helper\n\n- Adds `added()` to lib.rs\n- Used by the parser\n"],
+        )
+        .await;
+
+        let message = result.unwrap();
+        assert_eq!(
+            message,
+            "feat(lib): add helper\n\n- Adds `added()` to lib.rs\n- Used by the parser"
+        );
+        assert!(message.lines().count() > 1);
+        assert!(prompts[0].contains("short body"));
+    }
+
+    #[tokio::test]
+    async fn test_language_instruction_and_cjk_length() {
+        let mut context = context(CommitMessageStyle::Conventional);
+        context.language = Some("zh-CN".to_string());
+        context.max_subject_length = Some(12);
+        let subject = "feat: 添加辅助函数以格式化所有输出";
+
+        let (result, prompts) = generate_for(&context, &[subject, subject]).await;
+
+        assert!(prompts[0].contains("Write the commit message in Chinese"));
+        assert!(prompts[0].contains("Keep the type and scope in English"));
+        assert_eq!(result.unwrap(), "feat: 添加辅助函数");
+    }
+}
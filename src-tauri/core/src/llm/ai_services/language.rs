@@ -0,0 +1,26 @@
+//! Language helpers shared by the AI services
+
+/// Map the app's locale codes to language names the model understands.
+/// Unknown values are passed through, so a plain name like "German" works too.
+pub fn language_display_name(language: &str) -> &str {
+    match language.to_ascii_lowercase().as_str() {
+        "en" | "en-us" | "en-gb" => "English",
+        "zh" | "zh-cn" | "zh-hans" => "Simplified Chinese",
+        "zh-tw" | "zh-hant" => "Traditional Chinese",
+        "ja" => "Japanese",
+        "ko" => "Korean",
+        _ => language,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_locale_codes() {
+        assert_eq!(language_display_name("zh"), "Simplified Chinese");
+        assert_eq!(language_display_name("EN"), "English");
+        assert_eq!(language_display_name("German"), "German");
+    }
+}
@@ -1,6 +1,7 @@
 pub mod completion_service;
 pub mod context_compaction_service;
 pub mod git_message_service;
+pub mod language;
 pub mod model_resolver;
 pub mod pricing_service;
 pub mod prompt_enhancement_service;
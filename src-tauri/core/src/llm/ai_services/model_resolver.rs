@@ -280,6 +280,8 @@ mod tests {
                             cache_creation: None,
                         }),
                         context_length: Some(8192),
+                        capabilities: None,
+                        context_window: None,
                     },
                 ),
                 (
@@ -300,6 +302,8 @@ mod tests {
                             cache_creation: None,
                         }),
                         context_length: Some(4096),
+                        capabilities: None,
+                        context_window: None,
                     },
                 ),
             ]),
@@ -65,6 +65,18 @@ impl PricingService {
         Ok(CalculateCostResult { cost })
     }
 
+    /// Like `calculate_cost`, but `None` when the model has no pricing
+    /// instead of reporting it as free
+    pub fn try_calculate_cost(
+        &self,
+        model_id: &str,
+        usage: &TokenUsage,
+        model_configs: &HashMap<String, ModelConfig>,
+    ) -> Option<f64> {
+        self.get_model(model_id, model_configs)?.pricing.as_ref()?;
+        self.calculate_cost(model_id, usage, model_configs).ok()
+    }
+
     /// Get model config by ID (handles @provider suffix)
     fn get_model<'a>(
         &self,
@@ -130,6 +142,8 @@ mod tests {
                 cache_creation: cache_creation.map(|s| s.to_string()),
             }),
             context_length: None,
+            capabilities: None,
+            context_window: None,
         }
     }
 
@@ -170,6 +184,39 @@ mod tests {
         assert!((cost - expected).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn try_calculate_cost_separates_unpriced_from_free() {
+        let service = PricingService::new();
+        let mut configs = HashMap::new();
+        configs.insert(
+            "free-model".to_string(),
+            create_simple_model_config("0", "0"),
+        );
+        let mut unpriced = create_simple_model_config("1", "1");
+        unpriced.pricing = None;
+        configs.insert("unpriced-model".to_string(), unpriced);
+
+        let usage = TokenUsage {
+            input_tokens: 10,
+            output_tokens: 10,
+            cached_input_tokens: None,
+            cache_creation_input_tokens: None,
+        };
+
+        assert_eq!(
+            service.try_calculate_cost("free-model@test", &usage, &configs),
+            Some(0.0)
+        );
+        assert_eq!(
+            service.try_calculate_cost("unpriced-model", &usage, &configs),
+            None
+        );
+        assert_eq!(
+            service.try_calculate_cost("missing", &usage, &configs),
+            None
+        );
+    }
+
     #[test]
     fn falls_back_to_input_rate_when_cached_rates_missing() {
         let service = PricingService::new();
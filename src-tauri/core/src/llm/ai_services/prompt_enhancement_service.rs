@@ -1,3 +1,4 @@
+use crate::llm::ai_services::language::language_display_name;
 use crate::llm::ai_services::model_resolver::{resolve_model_identifier, FallbackStrategy};
 use crate::llm::ai_services::stream_collector::StreamCollector;
 use crate::llm::ai_services::stream_runner::StreamRunner;
@@ -16,6 +17,10 @@ const MAX_CONTEXT_CHARS: usize = 8000;
 /// Maximum number of search queries to generate
 const MAX_SEARCH_QUERIES: usize = 3;
 
+/// Markers around the index of a masked code segment, e.g. `@@CODE_0@@`
+const CODE_PLACEHOLDER_PREFIX: &str = "@@CODE_";
+const CODE_PLACEHOLDER_SUFFIX: &str = "@@";
+
 /// Technical abbreviations commonly found in coding contexts
 const TECH_ABBREVIATIONS: &[&str] = &[
     "API", "UI", "JWT", "HTTP", "REST", "SQL", "ORM", "CLI", "CSS", "HTML", "SDK", "IDE", "CI",
@@ -54,7 +59,12 @@ ${context}
 3. Add relevant technical details, file references, and code patterns from the context
 4. Maintain the original intent while making it clearer and more actionable
 5. Output ONLY the enhanced prompt text, no explanations or metadata
-6. Keep the language consistent with the original prompt (if Chinese, respond in Chinese)"#;
+6. ${language}"#;
+
+const DEFAULT_LANGUAGE_INSTRUCTION: &str =
+    "Keep the language consistent with the original prompt (if Chinese, respond in Chinese)";
+
+const CODE_PLACEHOLDER_INSTRUCTION: &str = "7. The prompt contains placeholders like @@CODE_0@@ that stand for code snippets. Copy every placeholder exactly once and unchanged; do not add, translate or rewrite code around them";
 
 pub struct PromptEnhancementService;
 
@@ -124,8 +134,16 @@ impl PromptEnhancementService {
             context_snippets.join("\n\n")
         };
 
-        let system_prompt = build_system_prompt(&context_text);
-        let user_prompt = request.original_prompt.clone();
+        // Code is masked so the model can only rewrite the prose around it
+        let (user_prompt, code_segments) = mask_code_segments(&request.original_prompt);
+        if !code_segments.is_empty() {
+            log::info!("Masked {} code segments", code_segments.len());
+        }
+        let system_prompt = build_system_prompt(
+            &context_text,
+            request.target_language.as_deref(),
+            !code_segments.is_empty(),
+        );
 
         // Step 5: Call LLM
         let preferred_model = request.model.clone();
@@ -153,6 +171,15 @@ impl PromptEnhancementService {
         if enhanced_prompt.is_empty() {
             return Err("Empty enhanced prompt generated".to_string());
         }
+        let enhanced_prompt = match restore_code_segments(&enhanced_prompt, &code_segments) {
+            Some(restored) => restored,
+            None => {
+                log::warn!(
+                    "Enhanced prompt lost or duplicated code placeholders, keeping original prompt"
+                );
+                request.original_prompt.clone()
+            }
+        };
 
         Ok(PromptEnhancementResult {
             enhanced_prompt,
@@ -337,9 +364,181 @@ fn search_codebase(project_path: &str, queries: &[String]) -> Vec<String> {
     snippets
 }
 
-/// Build the system prompt by injecting context into the template
-fn build_system_prompt(context: &str) -> String {
-    SYSTEM_PROMPT_TEMPLATE.replace("${context}", context)
+/// Build the system prompt by injecting context and the language into the template
+fn build_system_prompt(context: &str, target_language: Option<&str>, has_code: bool) -> String {
+    let language = match target_language.map(str::trim).filter(|l| !l.is_empty()) {
+        Some(language) => format!(
+            "Write the enhanced prompt in {}, regardless of the original prompt's language",
+            language_display_name(language)
+        ),
+        None => DEFAULT_LANGUAGE_INSTRUCTION.to_string(),
+    };
+    let mut prompt = SYSTEM_PROMPT_TEMPLATE
+        .replace("${context}", context)
+        .replace("${language}", &language);
+    if has_code {
+        prompt.push('\n');
+        prompt.push_str(CODE_PLACEHOLDER_INSTRUCTION);
+    }
+    prompt
+}
+
+fn code_placeholder(index: usize) -> String {
+    format!(
+        "{}{}{}",
+        CODE_PLACEHOLDER_PREFIX, index, CODE_PLACEHOLDER_SUFFIX
+    )
+}
+
+/// Replace fenced code blocks and inline code spans with placeholders.
+/// Returns the masked text and the original segments, indexed by placeholder.
+fn mask_code_segments(text: &str) -> (String, Vec<String>) {
+    let mut masked = String::with_capacity(text.len());
+    let mut segments = Vec::new();
+    let mut prose_start = 0;
+    let mut offset = 0;
+    let mut lines = text.split_inclusive('\n');
+
+    while let Some(line) = lines.next() {
+        let line_start = offset;
+        offset += line.len();
+        let Some((fence_char, fence_len)) = opening_fence(line) else {
+            continue;
+        };
+
+        // Consume lines up to the closing fence; an unclosed fence runs to the end
+        let mut block_end = text.len();
+        for inner in lines.by_ref() {
+            offset += inner.len();
+            if is_closing_fence(inner, fence_char, fence_len) {
+                block_end = offset - trailing_newline_len(inner);
+                break;
+            }
+        }
+
+        mask_inline_code(&text[prose_start..line_start], &mut masked, &mut segments);
+        masked.push_str(&code_placeholder(segments.len()));
+        segments.push(text[line_start..block_end].to_string());
+        prose_start = block_end;
+    }
+
+    mask_inline_code(&text[prose_start..], &mut masked, &mut segments);
+    (masked, segments)
+}
+
+/// Opening fence (up to 3 spaces, then 3+ backticks or tildes) and its length
+fn opening_fence(line: &str) -> Option<(char, usize)> {
+    let trimmed = strip_fence_indent(line)?;
+    let fence_char = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let fence_len = trimmed.chars().take_while(|c| *c == fence_char).count();
+    if fence_len < 3 {
+        return None;
+    }
+    // A backtick fence's info string cannot contain backticks (that's inline code)
+    if fence_char == '`' && trimmed[fence_len..].contains('`') {
+        return None;
+    }
+    Some((fence_char, fence_len))
+}
+
+fn is_closing_fence(line: &str, fence_char: char, fence_len: usize) -> bool {
+    let Some(trimmed) = strip_fence_indent(line) else {
+        return false;
+    };
+    let run = trimmed.chars().take_while(|c| *c == fence_char).count();
+    run >= fence_len && trimmed[run..].trim().is_empty()
+}
+
+fn strip_fence_indent(line: &str) -> Option<&str> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    (indent <= 3).then(|| &line[indent..])
+}
+
+fn trailing_newline_len(line: &str) -> usize {
+    if line.ends_with("\r\n") {
+        2
+    } else if line.ends_with('\n') {
+        1
+    } else {
+        0
+    }
+}
+
+/// Mask inline code spans: a run of N backticks closed by a run of exactly N
+fn mask_inline_code(text: &str, masked: &mut String, segments: &mut Vec<String>) {
+    let bytes = text.as_bytes();
+    let run_len = |from: usize| bytes[from..].iter().take_while(|b| **b == b'`').count();
+    let mut copied = 0;
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        if bytes[pos] != b'`' {
+            pos += 1;
+            continue;
+        }
+        let open_len = run_len(pos);
+        let mut search = pos + open_len;
+        let mut close = None;
+        while search < bytes.len() {
+            if bytes[search] == b'`' {
+                let len = run_len(search);
+                if len == open_len {
+                    close = Some(search + len);
+                    break;
+                }
+                search += len;
+            } else {
+                search += 1;
+            }
+        }
+
+        match close {
+            Some(end) => {
+                masked.push_str(&text[copied..pos]);
+                masked.push_str(&code_placeholder(segments.len()));
+                segments.push(text[pos..end].to_string());
+                copied = end;
+                pos = end;
+            }
+            // Unmatched backticks are literal text
+            None => pos += open_len,
+        }
+    }
+    masked.push_str(&text[copied..]);
+}
+
+/// Put the original code segments back in place of their placeholders.
+/// Returns `None` if any placeholder is missing, repeated or unknown.
+fn restore_code_segments(text: &str, segments: &[String]) -> Option<String> {
+    let pattern = format!(
+        r"{}(\d+){}",
+        regex::escape(CODE_PLACEHOLDER_PREFIX),
+        regex::escape(CODE_PLACEHOLDER_SUFFIX)
+    );
+    let re = Regex::new(&pattern).ok()?;
+
+    let mut seen = vec![false; segments.len()];
+    for cap in re.captures_iter(text) {
+        let index: usize = cap[1].parse().ok()?;
+        if index >= segments.len() || seen[index] {
+            return None;
+        }
+        seen[index] = true;
+    }
+    if seen.contains(&false) {
+        return None;
+    }
+
+    // Single pass, so code that happens to contain a placeholder is left alone
+    let restored = re.replace_all(text, |cap: &regex::Captures| {
+        cap[1]
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| segments.get(index))
+            .cloned()
+            .unwrap_or_default()
+    });
+    Some(restored.into_owned())
 }
 
 #[cfg(test)]
@@ -482,7 +681,7 @@ mod tests {
     #[test]
     fn system_prompt_context_replacement() {
         let context = "// File: src/main.rs, Line 1\nfn main() {}";
-        let prompt = build_system_prompt(context);
+        let prompt = build_system_prompt(context, None, false);
         assert!(
             prompt.contains(context),
             "System prompt should contain the injected context"
@@ -499,7 +698,7 @@ mod tests {
 
     #[test]
     fn system_prompt_empty_context() {
-        let prompt = build_system_prompt("No context available.");
+        let prompt = build_system_prompt("No context available.", None, false);
         assert!(
             prompt.contains("No context available."),
             "Should handle empty context gracefully"
@@ -570,4 +769,87 @@ mod tests {
             "Should extract snake_case"
         );
     }
+
+    #[test]
+    fn system_prompt_target_language() {
+        let prompt = build_system_prompt("ctx", Some("zh"), false);
+        assert!(prompt.contains("in Simplified Chinese"));
+        assert!(!prompt.contains("${language}"));
+        assert!(!prompt.contains("@@CODE_"));
+
+        let prompt = build_system_prompt("ctx", None, true);
+        assert!(prompt.contains(DEFAULT_LANGUAGE_INSTRUCTION));
+        assert!(prompt.contains(CODE_PLACEHOLDER_INSTRUCTION));
+    }
+
+    #[test]
+    fn mask_fenced_and_inline_code() {
+        let text = "Fix `parse_args` here:\n```rust\nfn main() {}\n```\nthen run ``a ` b``.";
+        let (masked, segments) = mask_code_segments(text);
+        assert_eq!(
+            masked,
+            "Fix @@CODE_0@@ here:\n@@CODE_1@@\nthen run @@CODE_2@@."
+        );
+        assert_eq!(
+            segments,
+            vec!["`parse_args`", "```rust\nfn main() {}\n```", "``a ` b``"]
+        );
+    }
+
+    #[test]
+    fn mask_tilde_and_unclosed_fences() {
+        let text = "a\n~~~~\n```\nnot a close\n~~~~\nb\n```js\nunclosed";
+        let (masked, segments) = mask_code_segments(text);
+        assert_eq!(masked, "a\n@@CODE_0@@\nb\n@@CODE_1@@");
+        assert_eq!(segments[0], "~~~~\n```\nnot a close\n~~~~");
+        assert_eq!(segments[1], "```js\nunclosed");
+    }
+
+    #[test]
+    fn unmatched_backticks_are_prose() {
+        let (masked, segments) = mask_code_segments("it's a ` lone backtick");
+        assert_eq!(masked, "it's a ` lone backtick");
+        assert!(segments.is_empty());
+    }
+
+    #[test]
+    fn code_fence_round_trips_while_prose_is_rewritten() {
+        let fence = "```python\ndef  add(a,b):\n    return a+b   # keep   spacing\n```";
+        let original = format!("pls make this faster `src/utils/math.py`\n{}\nthx", fence);
+        let (masked, segments) = mask_code_segments(&original);
+        assert!(!masked.contains("def  add"));
+
+        // Stand-in for the model: rewrites the prose, keeps the placeholders
+        let rewritten = masked
+            .replace("pls make this faster", "Optimize the performance of")
+            .replace("thx", "Keep the public signature unchanged.");
+        let restored = restore_code_segments(&rewritten, &segments).unwrap();
+
+        assert!(restored.contains(fence), "fence must be byte-for-byte");
+        assert!(restored.contains("`src/utils/math.py`"));
+        assert!(restored.starts_with("Optimize the performance of"));
+        assert!(!restored.contains("pls"));
+        assert_ne!(restored, original);
+    }
+
+    #[test]
+    fn restore_rejects_missing_or_duplicated_placeholders() {
+        let segments = vec!["`a`".to_string(), "`b`".to_string()];
+        assert_eq!(
+            restore_code_segments("x @@CODE_1@@ y @@CODE_0@@", &segments).as_deref(),
+            Some("x `b` y `a`")
+        );
+        assert!(restore_code_segments("x @@CODE_0@@", &segments).is_none());
+        assert!(restore_code_segments("@@CODE_0@@ @@CODE_0@@ @@CODE_1@@", &segments).is_none());
+        assert!(restore_code_segments("@@CODE_0@@ @@CODE_1@@ @@CODE_2@@", &segments).is_none());
+    }
+
+    #[test]
+    fn restore_does_not_expand_placeholders_inside_code() {
+        let segments = vec!["`@@CODE_1@@`".to_string(), "`b`".to_string()];
+        assert_eq!(
+            restore_code_segments("@@CODE_0@@ @@CODE_1@@", &segments).as_deref(),
+            Some("`@@CODE_1@@` `b`")
+        );
+    }
 }
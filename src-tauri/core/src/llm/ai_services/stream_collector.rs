@@ -1,6 +1,10 @@
 use crate::llm::ai_services::stream_runner::StreamRunner;
-use crate::llm::types::{Message, StreamEvent, StreamTextRequest};
+use crate::llm::protocols::claude_protocol::JSON_RESPONSE_TOOL;
+use crate::llm::types::{
+    Message, MessageContent, ResponseFormat, ResponseFormatType, StreamEvent, StreamTextRequest,
+};
 use futures_util::StreamExt;
+use std::future::Future;
 use std::time::{Duration, Instant};
 
 /// Collects text deltas from a stream and returns the complete text
@@ -60,11 +64,74 @@ impl StreamCollector {
         })
     }
 
-    /// Collect text using the non-window stream runner
+    /// Collect text using the non-window stream runner. Requests with a
+    /// `response_format` are validated as JSON and re-prompted once.
     pub async fn collect_with_runner(
         runner: &StreamRunner,
         request: StreamTextRequest,
         timeout: Duration,
+    ) -> Result<CollectResult, String> {
+        if request.response_format.is_none() {
+            return Self::collect_once(runner, request, timeout).await;
+        }
+        Self::collect_json(request, |request| {
+            Self::collect_once(runner, request, timeout)
+        })
+        .await
+    }
+
+    /// Collect with `collect`; when the text isn't valid JSON, ask the model
+    /// once more with the parse error. The returned text is the bare JSON.
+    pub async fn collect_json<F, Fut>(
+        request: StreamTextRequest,
+        mut collect: F,
+    ) -> Result<CollectResult, String>
+    where
+        F: FnMut(StreamTextRequest) -> Fut,
+        Fut: Future<Output = Result<CollectResult, String>>,
+    {
+        let first = collect(request.clone()).await?;
+        let error = match extract_json(&first.text) {
+            Ok(json) => {
+                return Ok(CollectResult {
+                    text: json,
+                    ..first
+                })
+            }
+            Err(e) => e,
+        };
+        log::warn!("Response is not valid JSON ({}), re-prompting once", error);
+
+        let mut retry = request;
+        retry.messages.push(Message::Assistant {
+            content: MessageContent::Text(first.text),
+            provider_options: None,
+        });
+        retry.messages.push(Message::User {
+            content: MessageContent::Text(format!(
+                "Your previous response was not valid JSON ({}). \
+                 Reply again with only the JSON, without prose or code fences.",
+                error
+            )),
+            provider_options: None,
+        });
+        let second = collect(retry).await?;
+        match extract_json(&second.text) {
+            Ok(json) => Ok(CollectResult {
+                text: json,
+                ..second
+            }),
+            Err(e) => Err(format!(
+                "Model did not return valid JSON after a retry: {}",
+                e
+            )),
+        }
+    }
+
+    async fn collect_once(
+        runner: &StreamRunner,
+        request: StreamTextRequest,
+        timeout: Duration,
     ) -> Result<CollectResult, String> {
         let start_time = Instant::now();
         let mut first_delta_time: Option<Duration> = None;
@@ -80,6 +147,12 @@ impl StreamCollector {
                     delta_count += 1;
                     full_text.push_str(&text);
                 }
+                // Anthropic answers JSON requests through a forced tool call
+                StreamEvent::ToolCall {
+                    tool_name, input, ..
+                } if tool_name == JSON_RESPONSE_TOOL => {
+                    full_text.push_str(&input.to_string());
+                }
                 StreamEvent::Error { message } => {
                     log::error!("Stream error: {}", message);
                 }
@@ -107,7 +180,7 @@ impl StreamCollector {
             model,
             fallback_models,
             messages: vec![Message::User {
-                content: crate::llm::types::MessageContent::Text(prompt),
+                content: MessageContent::Text(prompt),
                 provider_options: None,
             }],
             tools: None,
@@ -123,10 +196,65 @@ impl StreamCollector {
             previous_response_id: None,
             transport_session_id: None,
             allow_transport_fallback: None,
+            oauth_account: None,
+            disable_global_system_prompt: None,
+            response_format: None,
+            seed: None,
+            stop: None,
             continuation_context: None,
+            session_id: None,
             trace_context: None,
         }
     }
+
+    /// Like `create_completion_request`, but the answer must be a JSON object
+    /// holding the string `field`; read it back with `json_string_field`
+    pub fn create_json_field_request(
+        model: String,
+        fallback_models: Option<Vec<String>>,
+        prompt: String,
+        field: &str,
+    ) -> StreamTextRequest {
+        let prompt = format!(
+            "{}\n\nRespond with only a JSON object of the form {{\"{}\": \"...\"}}.",
+            prompt, field
+        );
+        let mut request = Self::create_completion_request(model, fallback_models, prompt);
+        request.response_format = Some(ResponseFormat {
+            format_type: ResponseFormatType::JsonSchema,
+            schema: Some(serde_json::json!({
+                "type": "object",
+                "properties": { field: { "type": "string" } },
+                "required": [field],
+                "additionalProperties": false
+            })),
+        });
+        request
+    }
+}
+
+/// The string `field` of the JSON object `json`
+pub fn json_string_field(json: &str, field: &str) -> Result<String, String> {
+    let value: serde_json::Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    value
+        .get(field)
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| format!("Response JSON has no \"{}\" string", field))
+}
+
+/// The JSON in `text`, which may be wrapped in a Markdown code fence
+fn extract_json(text: &str) -> Result<String, String> {
+    let trimmed = text.trim();
+    let unfenced = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.strip_suffix("```"))
+        .map(str::trim)
+        .unwrap_or(trimmed);
+    serde_json::from_str::<serde_json::Value>(unfenced)
+        .map(|_| unfenced.to_string())
+        .map_err(|e| e.to_string())
 }
 
 #[derive(Debug, Clone)]
@@ -245,4 +373,119 @@ mod tests {
         assert_eq!(request.messages.len(), 1);
         assert!(request.stream.unwrap_or(false));
     }
+
+    #[test]
+    fn json_field_request_asks_for_the_field() {
+        let request = StreamCollector::create_json_field_request(
+            "gpt-4o".to_string(),
+            None,
+            "Generate a title".to_string(),
+            "title",
+        );
+
+        let format = request.response_format.expect("response format");
+        assert_eq!(format.format_type, ResponseFormatType::JsonSchema);
+        assert_eq!(
+            format.schema.unwrap()["required"],
+            serde_json::json!(["title"])
+        );
+        assert!(matches!(
+            &request.messages[0],
+            Message::User { content: MessageContent::Text(text), .. }
+                if text.ends_with("{\"title\": \"...\"}.")
+        ));
+    }
+
+    #[test]
+    fn json_string_field_reads_the_field() {
+        assert_eq!(
+            json_string_field("{\"title\":\"Fix login\"}", "title").unwrap(),
+            "Fix login"
+        );
+        assert!(json_string_field("{\"title\":1}", "title").is_err());
+        assert!(json_string_field("{}", "title").is_err());
+        assert!(json_string_field("not json", "title").is_err());
+    }
+
+    fn collected(text: &str) -> CollectResult {
+        CollectResult {
+            text: text.to_string(),
+            total_time_ms: 1,
+            time_to_first_delta_ms: Some(1),
+            delta_count: 1,
+        }
+    }
+
+    fn json_request() -> StreamTextRequest {
+        let mut request = StreamCollector::create_completion_request(
+            "gpt-4o".to_string(),
+            None,
+            "Generate a title as JSON".to_string(),
+        );
+        request.response_format = Some(crate::llm::types::ResponseFormat {
+            format_type: crate::llm::types::ResponseFormatType::JsonObject,
+            schema: None,
+        });
+        request
+    }
+
+    #[tokio::test]
+    async fn collect_json_reprompts_once_on_invalid_json() {
+        let mut requests = Vec::new();
+        let result = StreamCollector::collect_json(json_request(), |request| {
+            let text = if requests.is_empty() {
+                "Sure! Here is the title: Fix login"
+            } else {
+                "```json\n{\"title\": \"Fix login\"}\n```"
+            };
+            requests.push(request);
+            async move { Ok(collected(text)) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(result.text, "{\"title\": \"Fix login\"}");
+        assert_eq!(requests.len(), 2);
+        let retry = &requests[1].messages;
+        assert_eq!(retry.len(), 3);
+        assert!(matches!(
+            &retry[1],
+            Message::Assistant { content: MessageContent::Text(text), .. }
+                if text.starts_with("Sure!")
+        ));
+        assert!(matches!(
+            &retry[2],
+            Message::User { content: MessageContent::Text(text), .. }
+                if text.contains("not valid JSON")
+        ));
+    }
+
+    #[tokio::test]
+    async fn collect_json_fails_after_second_invalid_response() {
+        let mut attempts = 0;
+        let result = StreamCollector::collect_json(json_request(), |_| {
+            attempts += 1;
+            async { Ok(collected("still not json")) }
+        })
+        .await;
+
+        assert_eq!(attempts, 2);
+        assert!(result
+            .unwrap_err()
+            .contains("did not return valid JSON after a retry"));
+    }
+
+    #[tokio::test]
+    async fn collect_json_accepts_valid_json_first_time() {
+        let mut attempts = 0;
+        let result = StreamCollector::collect_json(json_request(), |_| {
+            attempts += 1;
+            async { Ok(collected("{\"title\":\"Fix login\"}")) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(attempts, 1);
+        assert_eq!(result.text, "{\"title\":\"Fix login\"}");
+    }
 }
@@ -1,7 +1,10 @@
+use crate::llm::models::capability_checks;
 use crate::llm::protocols::stream_parser::StreamParseState;
 use crate::llm::providers::provider::{ProviderContext, ProviderTransport};
 use crate::llm::providers::provider_registry::ProviderRegistry;
+use crate::llm::streaming::model_fallback;
 use crate::llm::streaming::openai_responses_ws::{self, OpenAiResponsesWsOutcome};
+use crate::llm::streaming::sse_buffer::SseBuffer;
 use crate::llm::streaming::stream_handler::{
     is_transient_provider_retryable_error, should_retry_transient_http_error,
     transient_provider_retry_delay_ms, TRANSIENT_PROVIDER_RETRY_LIMIT,
@@ -10,6 +13,8 @@ use crate::llm::types::{ResponseTransport, StreamEvent, StreamTextRequest};
 use futures_util::StreamExt;
 use std::time::Duration;
 
+/// Streams requests for the internal AI services (titles, commit messages,
+/// compaction, ...), so the user's global system prompt is not applied
 pub struct StreamRunner {
     registry: ProviderRegistry,
     api_keys: crate::llm::auth::api_key_manager::ApiKeyManager,
@@ -84,32 +89,64 @@ impl StreamRunner {
         Err(last_error.unwrap_or_else(|| "No available model attempts".to_string()))
     }
 
-    fn build_attempt_models(request: &StreamTextRequest) -> Vec<String> {
-        let mut attempt_models = vec![request.model.clone()];
+    /// Like [`stream`](Self::stream) but forwards events as they arrive.
+    ///
+    /// Fallback models are only tried while nothing has been forwarded yet;
+    /// once output has reached the caller a failure is returned as-is.
+    pub async fn stream_live<F>(
+        &self,
+        request: StreamTextRequest,
+        timeout: Duration,
+        mut on_event: F,
+    ) -> Result<(), String>
+    where
+        F: FnMut(StreamEvent) + Send,
+    {
+        let attempt_models = Self::build_attempt_models(&request);
+        let mut last_error: Option<String> = None;
 
-        if let Some(fallback_models) = &request.fallback_models {
-            for fallback_model in fallback_models {
-                if fallback_model.trim().is_empty() || attempt_models.contains(fallback_model) {
-                    continue;
-                }
-                attempt_models.push(fallback_model.clone());
+        for (attempt_index, attempt_model) in attempt_models.into_iter().enumerate() {
+            let mut attempt_request = request.clone();
+            attempt_request.model = attempt_model;
+            attempt_request.fallback_models = None;
+            if attempt_index > 0 {
+                attempt_request.previous_response_id = None;
+                attempt_request.transport_session_id = None;
+            }
+
+            let mut forwarded = false;
+            match self
+                .stream_once(attempt_request, timeout, |event| {
+                    forwarded |= stream_event_starts_output(&event);
+                    on_event(event);
+                })
+                .await
+            {
+                Ok(()) => return Ok(()),
+                Err(err) if forwarded => return Err(err),
+                Err(err) => last_error = Some(err),
             }
         }
 
-        attempt_models
+        Err(last_error.unwrap_or_else(|| "No available model attempts".to_string()))
+    }
+
+    fn build_attempt_models(request: &StreamTextRequest) -> Vec<String> {
+        model_fallback::model_chain(request)
     }
 
     async fn stream_once<F>(
         &self,
-        request: StreamTextRequest,
+        mut request: StreamTextRequest,
         timeout: Duration,
         mut on_event: F,
     ) -> Result<(), String>
     where
         F: FnMut(StreamEvent) + Send,
     {
-        let (_model_key, provider_id, provider_model_name) =
+        let (model_key, provider_id, provider_model_name) =
             self.resolve_model_info(&request.model).await?;
+        capability_checks::check_request(&self.api_keys, &model_key, &mut request).await?;
 
         let provider = self
             .registry
@@ -117,9 +154,12 @@ impl StreamRunner {
             .ok_or_else(|| format!("Provider not found: {}", provider_id))?;
         let provider_config = provider.config();
 
+        let api_keys = self
+            .api_keys
+            .with_oauth_account(request.oauth_account.clone());
         let provider_ctx = ProviderContext {
             provider_config,
-            api_key_manager: &self.api_keys,
+            api_key_manager: &api_keys,
             model: &provider_model_name,
             messages: &request.messages,
             tools: request.tools.as_deref(),
@@ -135,6 +175,9 @@ impl StreamRunner {
             transport_session_id: request.transport_session_id.as_deref(),
             allow_transport_fallback: request.allow_transport_fallback,
             continuation_context: request.continuation_context.as_ref(),
+            response_format: request.response_format.as_ref(),
+            seed: request.seed,
+            stop: request.stop.as_deref(),
         };
 
         let built_request = provider.build_complete_request(&provider_ctx).await?;
@@ -169,15 +212,7 @@ impl StreamRunner {
                 timeout
             };
 
-        let client = reqwest::Client::builder()
-            .connect_timeout(Duration::from_secs(10))
-            .timeout(client_timeout)
-            .gzip(false)
-            .brotli(false)
-            .tcp_nodelay(true)
-            .pool_max_idle_per_host(5)
-            .build()
-            .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+        let client = crate::network_proxy::shared_client();
 
         let mut last_error: Option<String> = None;
 
@@ -199,6 +234,7 @@ impl StreamRunner {
                 req_builder = req_builder.header(key, value);
             }
             req_builder = req_builder
+                .timeout(client_timeout)
                 .header("Accept", "text/event-stream")
                 .json(&built_request.body);
 
@@ -230,7 +266,7 @@ impl StreamRunner {
             }
 
             let mut stream = response.bytes_stream();
-            let mut buffer: Vec<u8> = Vec::new();
+            let mut buffer = SseBuffer::new();
             let mut state = StreamParseState::default();
             let mut attempt_events: Vec<StreamEvent> = Vec::new();
             let mut saw_output = false;
@@ -251,12 +287,9 @@ impl StreamRunner {
                 if bytes.is_empty() {
                     continue;
                 }
-                buffer.extend_from_slice(&bytes);
-
-                while let Some((idx, delimiter_len)) = find_sse_delimiter(&buffer) {
-                    let event_bytes = buffer[..idx].to_vec();
-                    buffer.drain(..idx + delimiter_len);
+                buffer.extend(&bytes);
 
+                while let Some(event_bytes) = buffer.next_event() {
                     let event_str = String::from_utf8(event_bytes)
                         .map_err(|e| format!("Invalid UTF-8 in SSE event: {}", e))?;
 
@@ -383,16 +416,6 @@ impl StreamRunner {
     }
 }
 
-fn find_sse_delimiter(buf: &[u8]) -> Option<(usize, usize)> {
-    if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
-        return Some((pos, 4));
-    }
-    if let Some(pos) = buf.windows(2).position(|w| w == b"\n\n") {
-        return Some((pos, 2));
-    }
-    None
-}
-
 struct SseEvent {
     event: Option<String>,
     data: String,
@@ -455,7 +478,13 @@ mod tests {
             previous_response_id: None,
             transport_session_id: None,
             allow_transport_fallback: None,
+            oauth_account: None,
+            disable_global_system_prompt: None,
+            response_format: None,
+            seed: None,
+            stop: None,
             continuation_context: None,
+            session_id: None,
             trace_context: None,
         };
 
@@ -1,5 +1,6 @@
+use crate::llm::ai_services::language::language_display_name;
 use crate::llm::ai_services::model_resolver::{resolve_model_identifiers, FallbackStrategy};
-use crate::llm::ai_services::stream_collector::StreamCollector;
+use crate::llm::ai_services::stream_collector::{json_string_field, StreamCollector};
 use crate::llm::ai_services::stream_runner::StreamRunner;
 use crate::llm::ai_services::types::{TitleGenerationRequest, TitleGenerationResult};
 use crate::llm::auth::api_key_manager::ApiKeyManager;
@@ -30,19 +31,29 @@ impl TaskTitleService {
             return Err("No user input provided".to_string());
         }
 
+        let max_length = clamp_max_length(request.max_length);
         let language = request.language.as_deref().unwrap_or("en");
-        let language_instruction = if language == "zh" {
-            "Generate the title in Chinese."
-        } else {
-            "Generate the title in English."
-        };
+        let language_instruction = language_instruction(language);
 
-        let prompt = self.build_prompt(&request.user_input, language_instruction);
+        let prompt = self.build_prompt(&request.user_input, &language_instruction, max_length);
         log::info!(
             "Generated prompt for title generation (length: {})",
             prompt.len()
         );
 
+        let generated = self
+            .request_title(&request, prompt, api_keys, registry)
+            .await;
+        self.finish_title(generated, &request.user_input, max_length)
+    }
+
+    async fn request_title(
+        &self,
+        request: &TitleGenerationRequest,
+        prompt: String,
+        api_keys: &ApiKeyManager,
+        registry: &ProviderRegistry,
+    ) -> Result<String, String> {
         let preferred_model = request.model.clone();
         let resolved_models = resolve_model_identifiers(
             api_keys,
@@ -55,7 +66,7 @@ impl TaskTitleService {
         let model_identifier = resolved_models[0].clone();
         let fallback_models = resolved_models[1..].to_vec();
 
-        let request = StreamCollector::create_completion_request(
+        let request = StreamCollector::create_json_field_request(
             model_identifier,
             if fallback_models.is_empty() {
                 None
@@ -63,13 +74,37 @@ impl TaskTitleService {
                 Some(fallback_models)
             },
             prompt,
+            "title",
         );
 
         let runner = StreamRunner::new(registry.clone(), api_keys.clone());
         let result =
             StreamCollector::collect_with_runner(&runner, request, Duration::from_secs(30)).await?;
+        json_string_field(&result.text, "title")
+    }
 
-        let title = self.post_process_title(&result.text);
+    /// Validate the model output, falling back to the truncated user input so
+    /// a session always gets a title
+    fn finish_title(
+        &self,
+        generated: Result<String, String>,
+        user_input: &str,
+        max_length: usize,
+    ) -> Result<TitleGenerationResult, String> {
+        let title = match generated {
+            Ok(raw) => self.post_process_title(&raw),
+            Err(e) => {
+                log::warn!("Title generation failed, using first message: {}", e);
+                String::new()
+            }
+        };
+        let title = if title.is_empty() {
+            fallback_title(user_input)
+        } else {
+            title
+        };
+
+        let title = truncate_title(&title, max_length);
         if title.is_empty() {
             return Err("Empty title generated".to_string());
         }
@@ -91,7 +126,12 @@ impl TaskTitleService {
     }
 
     /// Build the prompt for title generation
-    fn build_prompt(&self, user_input: &str, language_instruction: &str) -> String {
+    fn build_prompt(
+        &self,
+        user_input: &str,
+        language_instruction: &str,
+        max_length: usize,
+    ) -> String {
         format!(
             "You are an AI assistant that generates concise, descriptive titles for tasks.\n\n\
              User's message: \"{}\"\n\n\
@@ -101,7 +141,8 @@ impl TaskTitleService {
              2. Use title case (capitalize first letter of main words)\n\
              3. Be specific and descriptive\n\
              4. Avoid generic titles like \"New Chat\" or \"Question\"\n\
-             5. Focus on the main topic or intent\n\n\
+             5. Focus on the main topic or intent\n\
+             6. Never exceed {} characters\n\n\
              Examples:\n\
              - \"Fix Login Bug\"\n\
              - \"Create User Dashboard\"\n\
@@ -109,8 +150,8 @@ impl TaskTitleService {
              - \"Database Schema Design\"\n\
              - \"API Rate Limiting Issue\"\n\n\
              {}\n\n\
-             Provide ONLY the title without any quotes, explanations, or additional formatting.",
-            user_input, language_instruction
+             Provide ONLY the title without explanations or additional formatting.",
+            user_input, max_length, language_instruction
         )
     }
 
@@ -120,6 +161,49 @@ impl TaskTitleService {
     }
 }
 
+/// Default title length cap, in characters
+const DEFAULT_TITLE_MAX_LENGTH: usize = 50;
+/// Bounds for a caller-provided length cap
+const MIN_TITLE_MAX_LENGTH: usize = 10;
+const MAX_TITLE_MAX_LENGTH: usize = 200;
+
+fn clamp_max_length(max_length: Option<usize>) -> usize {
+    max_length
+        .unwrap_or(DEFAULT_TITLE_MAX_LENGTH)
+        .clamp(MIN_TITLE_MAX_LENGTH, MAX_TITLE_MAX_LENGTH)
+}
+
+fn language_instruction(language: &str) -> String {
+    let language = match language.trim() {
+        "" => "en",
+        language => language,
+    };
+    format!("Generate the title in {}.", language_display_name(language))
+}
+
+/// First non-empty line of the user's message, with whitespace collapsed
+fn fallback_title(user_input: &str) -> String {
+    user_input
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .find(|line| !line.is_empty())
+        .unwrap_or_default()
+}
+
+/// Cap a title at `max_length` characters (not bytes, so CJK titles are not
+/// cut mid-character), preferring a word boundary and marking the cut with "…"
+fn truncate_title(title: &str, max_length: usize) -> String {
+    if title.chars().count() <= max_length {
+        return title.to_string();
+    }
+    let keep: String = title.chars().take(max_length.saturating_sub(1)).collect();
+    let cut = match keep.rfind(char::is_whitespace) {
+        Some(index) if keep[..index].chars().count() >= max_length / 2 => &keep[..index],
+        _ => keep.as_str(),
+    };
+    format!("{}…", cut.trim_end())
+}
+
 impl Default for TaskTitleService {
     fn default() -> Self {
         Self::new()
@@ -194,6 +278,8 @@ mod tests {
                         cache_creation: None,
                     }),
                     context_length: Some(8192),
+                    capabilities: None,
+                    context_window: None,
                 },
             )]),
         };
@@ -215,6 +301,7 @@ mod tests {
         let prompt = service.build_prompt(
             "How do I use React hooks?",
             "Generate the title in English.",
+            50,
         );
 
         assert!(prompt.contains("How do I use React hooks?"));
@@ -224,7 +311,7 @@ mod tests {
     #[test]
     fn build_prompt_contains_guidelines() {
         let service = TaskTitleService::new();
-        let prompt = service.build_prompt("test", "Generate the title in English.");
+        let prompt = service.build_prompt("test", "Generate the title in English.", 50);
 
         assert!(prompt.contains("5-10 words maximum"));
         assert!(prompt.contains("title case"));
@@ -234,7 +321,7 @@ mod tests {
     #[test]
     fn build_prompt_contains_examples() {
         let service = TaskTitleService::new();
-        let prompt = service.build_prompt("test", "Generate the title in English.");
+        let prompt = service.build_prompt("test", "Generate the title in English.", 50);
 
         assert!(prompt.contains("Fix Login Bug"));
         assert!(prompt.contains("Create User Dashboard"));
@@ -244,7 +331,7 @@ mod tests {
     #[test]
     fn build_prompt_uses_english_instruction() {
         let service = TaskTitleService::new();
-        let prompt = service.build_prompt("test", "Generate the title in English.");
+        let prompt = service.build_prompt("test", "Generate the title in English.", 50);
 
         assert!(prompt.contains("Generate the title in English."));
     }
@@ -252,7 +339,7 @@ mod tests {
     #[test]
     fn build_prompt_uses_chinese_instruction() {
         let service = TaskTitleService::new();
-        let prompt = service.build_prompt("test", "Generate the title in Chinese.");
+        let prompt = service.build_prompt("test", "Generate the title in Chinese.", 50);
 
         assert!(prompt.contains("Generate the title in Chinese."));
     }
@@ -264,6 +351,7 @@ mod tests {
         let request = TitleGenerationRequest {
             user_input: "   ".to_string(),
             language: None,
+            max_length: None,
             model: None,
             fallback_models: None,
         };
@@ -278,4 +366,86 @@ mod tests {
     fn preferred_model_type_returns_small() {
         assert_eq!(TaskTitleService::preferred_model_type(), "small");
     }
+
+    #[test]
+    fn build_prompt_includes_length_cap() {
+        let service = TaskTitleService::new();
+        let prompt = service.build_prompt("test", "Generate the title in English.", 24);
+
+        assert!(prompt.contains("Never exceed 24 characters"));
+    }
+
+    #[test]
+    fn language_instruction_uses_requested_language() {
+        assert_eq!(
+            language_instruction("zh"),
+            "Generate the title in Simplified Chinese."
+        );
+        assert_eq!(
+            language_instruction("ja"),
+            "Generate the title in Japanese."
+        );
+        assert_eq!(language_instruction(""), "Generate the title in English.");
+
+        let service = TaskTitleService::new();
+        let prompt = service.build_prompt("test", &language_instruction("ko"), 50);
+        assert!(prompt.contains("Generate the title in Korean."));
+    }
+
+    #[test]
+    fn max_length_is_clamped() {
+        assert_eq!(clamp_max_length(None), DEFAULT_TITLE_MAX_LENGTH);
+        assert_eq!(clamp_max_length(Some(1)), MIN_TITLE_MAX_LENGTH);
+        assert_eq!(clamp_max_length(Some(10_000)), MAX_TITLE_MAX_LENGTH);
+    }
+
+    #[test]
+    fn title_respects_length_cap() {
+        let service = TaskTitleService::new();
+        let result = service
+            .finish_title(
+                Ok(
+                    "\"Refactor The Authentication Middleware For Multi Tenant Support\""
+                        .to_string(),
+                ),
+                "ignored",
+                30,
+            )
+            .unwrap();
+
+        assert!(result.title.chars().count() <= 30, "{}", result.title);
+        assert_eq!(result.title, "Refactor The Authentication…");
+    }
+
+    #[test]
+    fn truncate_counts_characters_not_bytes() {
+        let title = "重构用户认证模块并添加多租户支持以及缓存层";
+        let truncated = truncate_title(title, 10);
+
+        assert_eq!(truncated.chars().count(), 10);
+        assert_eq!(truncated, "重构用户认证模块并…");
+        assert_eq!(truncate_title("Short", 10), "Short");
+    }
+
+    #[test]
+    fn model_failure_falls_back_to_truncated_first_message() {
+        let service = TaskTitleService::new();
+        let user_input = "\n  Please   help me migrate the billing service from MySQL to Postgres\nDetails follow";
+        let result = service
+            .finish_title(Err("connection refused".to_string()), user_input, 20)
+            .unwrap();
+
+        assert_eq!(result.title, "Please help me…");
+        assert!(result.title.chars().count() <= 20);
+    }
+
+    #[test]
+    fn empty_model_output_falls_back_to_first_message() {
+        let service = TaskTitleService::new();
+        let result = service
+            .finish_title(Ok("  \n".to_string()), "Fix login bug", 50)
+            .unwrap();
+
+        assert_eq!(result.title, "Fix login bug");
+    }
 }
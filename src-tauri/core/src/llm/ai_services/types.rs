@@ -34,17 +34,56 @@ pub struct CompletionRange {
 // Context Compaction Service Types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContextCompactionRequest {
-    #[serde(rename = "conversationHistory")]
+    /// Pre-rendered history; ignored when `messages` is provided
+    #[serde(default, rename = "conversationHistory")]
     pub conversation_history: String,
+    /// Structured history, required for pinning and tool-result preservation
+    #[serde(default)]
+    pub messages: Option<Vec<CompactionMessage>>,
+    /// Messages that are kept verbatim and never summarized
+    #[serde(default, rename = "pinnedMessageIds")]
+    pub pinned_message_ids: Vec<String>,
+    /// How many of the most recent tool results to keep verbatim
+    #[serde(default, rename = "preserveRecentToolResults")]
+    pub preserve_recent_tool_results: Option<usize>,
+    /// Token budget the compacted context should fit in
+    #[serde(default, rename = "targetTokens")]
+    pub target_tokens: Option<usize>,
     pub model: Option<String>,
     #[serde(default, rename = "fallbackModels")]
     pub fallback_models: Option<Vec<String>>,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompactionMessage {
+    pub id: String,
+    /// `user`, `assistant`, `system` or `tool`
+    pub role: String,
+    pub content: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContextCompactionResult {
     #[serde(rename = "compressedSummary")]
     pub compressed_summary: String,
+    /// Pinned messages and recent tool results, in original order
+    #[serde(default, rename = "preservedMessages")]
+    pub preserved_messages: Vec<CompactionMessage>,
+    #[serde(default, rename = "tokensBefore")]
+    pub tokens_before: usize,
+    #[serde(default, rename = "tokensAfter")]
+    pub tokens_after: usize,
+    #[serde(default, rename = "tokensSaved")]
+    pub tokens_saved: usize,
+}
+
+/// Progress events for `llm_compact_context_streaming`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum CompactionStreamEvent {
+    SummaryDelta { text: String },
+    Completed { result: ContextCompactionResult },
+    Error { message: String },
 }
 
 // Git Message Service Types
@@ -57,6 +96,26 @@ pub struct GitMessageContext {
     pub model: Option<String>,
     #[serde(default, rename = "fallbackModels")]
     pub fallback_models: Option<Vec<String>>,
+    #[serde(default)]
+    pub style: CommitMessageStyle,
+    /// Maximum subject line length in characters (defaults to 72)
+    #[serde(default, rename = "maxSubjectLength")]
+    pub max_subject_length: Option<usize>,
+    /// Whether to add a body explaining the change below the subject
+    #[serde(default, rename = "includeBody")]
+    pub include_body: bool,
+    /// Language code for the message text, e.g. "en", "zh", "ja"
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitMessageStyle {
+    #[default]
+    Plain,
+    /// Conventional Commits (`type(scope)!: description`), validated after generation
+    Conventional,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -98,6 +157,9 @@ pub struct TitleGenerationRequest {
     #[serde(rename = "userInput")]
     pub user_input: String,
     pub language: Option<String>,
+    /// Maximum title length in characters
+    #[serde(default, rename = "maxLength")]
+    pub max_length: Option<usize>,
     pub model: Option<String>,
     #[serde(default, rename = "fallbackModels")]
     pub fallback_models: Option<Vec<String>>,
@@ -120,6 +182,9 @@ pub struct PromptEnhancementRequest {
     #[serde(rename = "enableContextExtraction")]
     pub enable_context_extraction: bool,
     pub model: Option<String>,
+    /// Language of the enhanced prompt (e.g. "en", "zh"); defaults to the prompt's own language
+    #[serde(default, rename = "targetLanguage")]
+    pub target_language: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
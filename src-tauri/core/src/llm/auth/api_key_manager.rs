@@ -1,6 +1,5 @@
 use crate::llm::types::CustomProvidersConfiguration;
 use crate::llm::types::{AuthType, ModelsConfiguration, ProviderConfig};
-use reqwest::Client;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -10,6 +9,7 @@ use tauri::State;
 use tokio::sync::{Mutex, RwLock};
 
 use crate::database::Database;
+use crate::llm::auth::oauth_accounts;
 
 const MODELS_CACHE_TTL: Duration = Duration::from_secs(300); // 5 minutes
 
@@ -31,12 +31,15 @@ pub struct ApiKeyManager {
     db: Arc<Database>,
     app_data_dir: PathBuf,
     models_cache: RwLock<Option<ModelsCacheEntry>>,
+    /// OAuth account label chosen for the current request, see `oauth_accounts`
+    oauth_account: Option<String>,
 }
 
 impl std::fmt::Debug for ApiKeyManager {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ApiKeyManager")
             .field("app_data_dir", &self.app_data_dir)
+            .field("oauth_account", &self.oauth_account)
             .finish_non_exhaustive()
     }
 }
@@ -53,6 +56,7 @@ impl Clone for ApiKeyManager {
             db: self.db.clone(),
             app_data_dir: self.app_data_dir.clone(),
             models_cache: RwLock::new(None),
+            oauth_account: self.oauth_account.clone(),
         }
     }
 }
@@ -63,9 +67,23 @@ impl ApiKeyManager {
             db,
             app_data_dir,
             models_cache: RwLock::new(None),
+            oauth_account: None,
         }
     }
 
+    /// Copy of this manager that resolves OAuth credentials from the named
+    /// account `label` instead of each provider's active account
+    pub fn with_oauth_account(&self, label: Option<String>) -> Self {
+        Self {
+            oauth_account: label.filter(|label| !label.trim().is_empty()),
+            ..self.clone()
+        }
+    }
+
+    pub fn oauth_account(&self) -> Option<&str> {
+        self.oauth_account.as_deref()
+    }
+
     /// Load models configuration with caching (5 minutes TTL)
     pub async fn load_models_config(&self) -> Result<ModelsConfiguration, String> {
         let custom_models_mtime = self.custom_models_modified_time().await?;
@@ -107,7 +125,9 @@ impl ApiKeyManager {
         };
 
         let custom_config = self.load_custom_models().await?;
-        Ok(Self::merge_models_config(base_config, custom_config))
+        let mut config = Self::merge_models_config(base_config, custom_config);
+        config.resolve_capabilities();
+        Ok(config)
     }
 
     /// Clear the models configuration cache
@@ -330,6 +350,9 @@ impl ApiKeyManager {
     }
 
     async fn get_oauth_token(&self, provider_id: &str) -> Result<Option<String>, String> {
+        if let Some(account) = oauth_accounts::selected_account(self, provider_id).await? {
+            return Ok(Some(account.access_token));
+        }
         match provider_id {
             "openai" => self.get_setting("openai_oauth_access_token").await,
             "anthropic" => self.get_setting("claude_oauth_access_token").await,
@@ -372,7 +395,7 @@ impl ApiKeyManager {
             .await?
             .filter(|value| !value.trim().is_empty());
 
-        let client = Client::builder()
+        let client = crate::network_proxy::client_builder()
             .timeout(Duration::from_secs(20))
             .build()
             .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
@@ -452,14 +475,21 @@ impl ApiKeyManager {
         if provider_id != "openai" {
             return Ok(());
         }
-        if let Some(account_id) = self.get_setting("openai_oauth_account_id").await? {
-            if !account_id.trim().is_empty() {
-                headers.insert("chatgpt-account-id".to_string(), account_id);
-            }
+        if let Some(account_id) = self.get_openai_account_id().await? {
+            headers.insert("chatgpt-account-id".to_string(), account_id);
         }
         Ok(())
     }
 
+    /// ChatGPT account id of the selected OpenAI OAuth account
+    pub async fn get_openai_account_id(&self) -> Result<Option<String>, String> {
+        let account_id = match oauth_accounts::selected_account(self, "openai").await? {
+            Some(account) => account.account_id,
+            None => self.get_setting("openai_oauth_account_id").await?,
+        };
+        Ok(account_id.filter(|id| !id.trim().is_empty()))
+    }
+
     pub async fn load_oauth_tokens(&self) -> Result<HashMap<String, String>, String> {
         let mut tokens = HashMap::new();
         if let Some(token) = self.get_setting("openai_oauth_access_token").await? {
@@ -530,7 +560,7 @@ pub async fn llm_set_setting(
 mod tests {
     use super::*;
     use crate::database::Database;
-    use crate::llm::types::ProtocolType;
+    use crate::llm::types::{ModelCapabilities, ProtocolType};
     use std::collections::HashMap;
     use std::sync::Arc;
     use tempfile::TempDir;
@@ -739,4 +769,58 @@ mod tests {
             .expect("no header");
         assert!(other_headers.get("chatgpt-account-id").is_none());
     }
+
+    #[tokio::test]
+    async fn bundled_models_report_capabilities() {
+        let ctx = setup().await;
+        let config = ctx
+            .api_keys
+            .load_models_config_from_source()
+            .await
+            .expect("load models config");
+        let model = |key: &str| config.models.get(key).expect(key);
+
+        let sonnet = model("claude-sonnet-4.6");
+        assert_eq!(
+            sonnet.capabilities,
+            Some(ModelCapabilities {
+                vision: true,
+                tools: true,
+                reasoning: true,
+                audio: false,
+                json_mode: false,
+            })
+        );
+        assert_eq!(sonnet.context_window, Some(1_000_000));
+
+        let deepseek = model("deepseek-v4-pro").capabilities.unwrap();
+        assert!(deepseek.tools && deepseek.json_mode && !deepseek.vision);
+
+        let whisper = model("whisper-1");
+        assert_eq!(
+            whisper.capabilities,
+            Some(ModelCapabilities {
+                audio: true,
+                ..Default::default()
+            })
+        );
+        assert_eq!(whisper.context_window, None);
+
+        let image = model("gemini-2.5-flash-image").capabilities.unwrap();
+        assert!(image.vision && !image.tools);
+        assert_eq!(model("gpt-5.5").context_window, Some(1_050_000));
+
+        // Every bundled model lists its capabilities explicitly
+        let raw: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../../../packages/shared/src/data/models-config.json"
+        ))
+        .unwrap();
+        for (key, entry) in raw["models"].as_object().unwrap() {
+            assert!(
+                entry.get("capabilities").is_some(),
+                "{} lacks capabilities",
+                key
+            );
+        }
+    }
 }
@@ -1,3 +1,6 @@
 pub mod api_key_manager;
 pub mod oauth;
+pub mod oauth_accounts;
 pub mod openai_usage;
+pub mod openai_usage_history;
+pub mod token_refresh;
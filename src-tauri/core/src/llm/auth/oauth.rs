@@ -1,4 +1,5 @@
 use crate::llm::auth::api_key_manager::{normalize_domain, ApiKeyManager, LlmState};
+use crate::llm::auth::oauth_accounts::{self, OAuthAccountCredentials, OAuthAccountStatus};
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
@@ -12,15 +13,17 @@ const OPENAI_REDIRECT_URI: &str = "http://localhost:1455/auth/callback";
 const OPENAI_AUTH_URL: &str = "https://auth.openai.com/oauth/authorize";
 const OPENAI_TOKEN_URL: &str = "https://auth.openai.com/oauth/token";
 const OPENAI_OAUTH_SCOPE: &str = "openid profile email offline_access";
+/// RFC 7009 revocation endpoint
+const OPENAI_REVOKE_URL: &str = "https://auth.openai.com/oauth/revoke";
 
 const CLAUDE_CLIENT_ID: &str = "app_01Kcx9v5mR2eGz4B2KG1hp6P";
 const CLAUDE_REDIRECT_URI: &str = "http://localhost:1455/auth/callback";
 const CLAUDE_AUTH_URL: &str = "https://claude.ai/oauth/authorize";
 const CLAUDE_TOKEN_URL: &str = "https://claude.ai/oauth/token";
 
-const GITHUB_COPILOT_ACCESS_TOKEN_KEY: &str = "github_copilot_oauth_access_token";
+pub(crate) const GITHUB_COPILOT_ACCESS_TOKEN_KEY: &str = "github_copilot_oauth_access_token";
 const GITHUB_COPILOT_COPILOT_TOKEN_KEY: &str = "github_copilot_oauth_copilot_token";
-const GITHUB_COPILOT_EXPIRES_AT_KEY: &str = "github_copilot_oauth_expires_at";
+pub(crate) const GITHUB_COPILOT_EXPIRES_AT_KEY: &str = "github_copilot_oauth_expires_at";
 const GITHUB_COPILOT_ENTERPRISE_URL_KEY: &str = "github_copilot_oauth_enterprise_url";
 
 const GITHUB_COPILOT_CLIENT_ID: &str = "Iv1.b507a08c87ecfe98";
@@ -30,6 +33,7 @@ const GITHUB_COPILOT_PLUGIN_VERSION: &str = "copilot-chat/0.35.0";
 const GITHUB_COPILOT_INTEGRATION_ID: &str = "vscode-chat";
 
 const OAUTH_STATE_TIMEOUT: Duration = Duration::from_secs(600); // 10 minutes
+const REVOKE_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// OAuth state entry with timestamp for expiration
 #[derive(Clone, Debug)]
@@ -261,7 +265,7 @@ pub async fn llm_openai_oauth_complete(
         return Err("Invalid or expired OAuth state".to_string());
     }
 
-    let client = reqwest::Client::new();
+    let client = crate::network_proxy::shared_json_client();
 
     let redirect_uri = request
         .redirect_uri
@@ -350,10 +354,10 @@ pub struct OpenAIOAuthRefreshResponse {
     pub account_id: Option<String>,
 }
 
-pub(crate) async fn refresh_openai_oauth_tokens(
+/// Exchange an OpenAI refresh token for new tokens without storing them
+pub(crate) async fn request_openai_token_refresh(
     client: &reqwest::Client,
     refresh_token: &str,
-    api_keys: &ApiKeyManager,
 ) -> Result<OpenAIOAuthRefreshResponse, String> {
     let params = [
         ("grant_type", "refresh_token"),
@@ -399,25 +403,47 @@ pub(crate) async fn refresh_openai_oauth_tokens(
 
     let account_id = extract_openai_account_id(&access_token);
 
+    Ok(OpenAIOAuthRefreshResponse {
+        access_token,
+        refresh_token,
+        expires_at,
+        account_id,
+    })
+}
+
+pub(crate) async fn refresh_openai_oauth_tokens(
+    client: &reqwest::Client,
+    refresh_token: &str,
+    api_keys: &ApiKeyManager,
+) -> Result<OpenAIOAuthRefreshResponse, String> {
+    let refreshed = request_openai_token_refresh(client, refresh_token).await?;
+
     api_keys
-        .set_setting("openai_oauth_access_token", &access_token)
+        .set_setting("openai_oauth_access_token", &refreshed.access_token)
         .await?;
     api_keys
-        .set_setting("openai_oauth_refresh_token", &refresh_token)
+        .set_setting("openai_oauth_refresh_token", &refreshed.refresh_token)
         .await?;
     api_keys
-        .set_setting("openai_oauth_expires_at", &expires_at.to_string())
+        .set_setting("openai_oauth_expires_at", &refreshed.expires_at.to_string())
         .await?;
-    if let Some(ref id) = account_id {
+    if let Some(ref id) = refreshed.account_id {
         api_keys.set_setting("openai_oauth_account_id", id).await?;
     }
-
-    Ok(OpenAIOAuthRefreshResponse {
-        access_token,
+    oauth_accounts::replace_rotated_tokens(
+        api_keys,
+        "openai",
         refresh_token,
-        expires_at,
-        account_id,
-    })
+        &OAuthAccountCredentials {
+            access_token: refreshed.access_token.clone(),
+            refresh_token: Some(refreshed.refresh_token.clone()),
+            expires_at: Some(refreshed.expires_at),
+            account_id: refreshed.account_id.clone(),
+        },
+    )
+    .await?;
+
+    Ok(refreshed)
 }
 
 #[tauri::command]
@@ -426,7 +452,7 @@ pub async fn llm_openai_oauth_refresh(
     state: State<'_, LlmState>,
 ) -> Result<OpenAIOAuthRefreshResponse, String> {
     let api_keys = state.api_keys.lock().await;
-    let client = reqwest::Client::new();
+    let client = crate::network_proxy::shared_json_client();
     refresh_openai_oauth_tokens(&client, &request.refresh_token, &api_keys).await
 }
 
@@ -444,24 +470,172 @@ pub async fn llm_openai_oauth_refresh_from_store(
         return Err("OpenAI OAuth refresh token missing".to_string());
     }
 
-    let client = reqwest::Client::new();
+    let client = crate::network_proxy::shared_json_client();
     refresh_openai_oauth_tokens(&client, &refresh_token, &api_keys).await
 }
 
+const OPENAI_OAUTH_SETTING_KEYS: &[&str] = &[
+    "openai_oauth_access_token",
+    "openai_oauth_refresh_token",
+    "openai_oauth_expires_at",
+    "openai_oauth_account_id",
+];
+
+async fn disconnect_openai_oauth(
+    client: &reqwest::Client,
+    api_keys: &ApiKeyManager,
+    revoke_url: &str,
+) -> Result<OAuthDisconnectResponse, String> {
+    let revocation = TokenRevocation {
+        url: revoke_url,
+        client_id: OPENAI_CLIENT_ID,
+        refresh_token_key: "openai_oauth_refresh_token",
+        access_token_key: "openai_oauth_access_token",
+    };
+    disconnect_oauth(
+        client,
+        api_keys,
+        Some(revocation),
+        OPENAI_OAUTH_SETTING_KEYS,
+    )
+    .await
+}
+
 #[tauri::command]
-pub async fn llm_openai_oauth_disconnect(state: State<'_, LlmState>) -> Result<(), String> {
+pub async fn llm_openai_oauth_disconnect(
+    state: State<'_, LlmState>,
+) -> Result<OAuthDisconnectResponse, String> {
     let api_keys = state.api_keys.lock().await;
-    api_keys
-        .set_setting("openai_oauth_access_token", "")
-        .await?;
-    api_keys
-        .set_setting("openai_oauth_refresh_token", "")
-        .await?;
-    api_keys.set_setting("openai_oauth_expires_at", "").await?;
-    api_keys.set_setting("openai_oauth_account_id", "").await?;
+    let client = crate::network_proxy::shared_json_client();
+    disconnect_openai_oauth(&client, &api_keys, OPENAI_REVOKE_URL).await
+}
+
+// ============================================================================
+// Disconnect and token revocation
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RevocationStatus {
+    /// The provider confirmed the token was revoked
+    Revoked,
+    /// The provider has no revocation endpoint
+    Unsupported,
+    /// There was no stored token to revoke
+    NoToken,
+    /// The revocation request failed; the token may still be valid server-side
+    Failed,
+}
+
+/// Local tokens are always cleared; `revocation` reports what the provider did
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OAuthDisconnectResponse {
+    pub revocation: RevocationStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+struct TokenRevocation<'a> {
+    url: &'a str,
+    client_id: &'a str,
+    refresh_token_key: &'a str,
+    access_token_key: &'a str,
+}
+
+/// Revoke a token per RFC 7009
+async fn revoke_token(
+    client: &reqwest::Client,
+    url: &str,
+    client_id: &str,
+    token: &str,
+    token_type_hint: &str,
+) -> Result<(), String> {
+    let params = [
+        ("token", token),
+        ("token_type_hint", token_type_hint),
+        ("client_id", client_id),
+    ];
+    let response = client
+        .post(url)
+        .timeout(REVOKE_TIMEOUT)
+        .form(&params)
+        .send()
+        .await
+        .map_err(|e| format!("Revocation request failed: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Token revocation failed ({}): {}", status, text));
+    }
     Ok(())
 }
 
+async fn stored_token(api_keys: &ApiKeyManager, key: &str) -> Option<String> {
+    api_keys
+        .get_setting(key)
+        .await
+        .ok()
+        .flatten()
+        .filter(|token| !token.trim().is_empty())
+}
+
+/// Revoke the stored refresh token (or the access token when there is none),
+/// then clear `setting_keys` whatever the revocation outcome
+async fn disconnect_oauth(
+    client: &reqwest::Client,
+    api_keys: &ApiKeyManager,
+    revocation: Option<TokenRevocation<'_>>,
+    setting_keys: &[&str],
+) -> Result<OAuthDisconnectResponse, String> {
+    let response = match revocation {
+        None => OAuthDisconnectResponse {
+            revocation: RevocationStatus::Unsupported,
+            error: None,
+        },
+        Some(revocation) => {
+            let token = match stored_token(api_keys, revocation.refresh_token_key).await {
+                Some(token) => Some((token, "refresh_token")),
+                None => stored_token(api_keys, revocation.access_token_key)
+                    .await
+                    .map(|token| (token, "access_token")),
+            };
+            match token {
+                None => OAuthDisconnectResponse {
+                    revocation: RevocationStatus::NoToken,
+                    error: None,
+                },
+                Some((token, hint)) => {
+                    match revoke_token(client, revocation.url, revocation.client_id, &token, hint)
+                        .await
+                    {
+                        Ok(()) => OAuthDisconnectResponse {
+                            revocation: RevocationStatus::Revoked,
+                            error: None,
+                        },
+                        Err(e) => {
+                            log::warn!("OAuth token revocation failed: {}", e);
+                            OAuthDisconnectResponse {
+                                revocation: RevocationStatus::Failed,
+                                error: Some(e),
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    for key in setting_keys {
+        api_keys.set_setting(key, "").await?;
+    }
+    Ok(response)
+}
+
 // ============================================================================
 // Claude OAuth
 // ============================================================================
@@ -530,7 +704,7 @@ pub async fn llm_claude_oauth_complete(
         return Err("Invalid or expired OAuth state".to_string());
     }
 
-    let client = reqwest::Client::new();
+    let client = crate::network_proxy::shared_json_client();
 
     let params = [
         ("grant_type", "authorization_code"),
@@ -607,17 +781,15 @@ pub struct ClaudeOAuthRefreshResponse {
     pub expires_at: i64,
 }
 
-#[tauri::command]
-pub async fn llm_claude_oauth_refresh(
-    request: ClaudeOAuthRefreshRequest,
-    state: State<'_, LlmState>,
+/// Exchange a Claude refresh token for new tokens without storing them
+pub(crate) async fn request_claude_token_refresh(
+    client: &reqwest::Client,
+    refresh_token: &str,
 ) -> Result<ClaudeOAuthRefreshResponse, String> {
-    let client = reqwest::Client::new();
-
     let params = [
         ("grant_type", "refresh_token"),
         ("client_id", CLAUDE_CLIENT_ID),
-        ("refresh_token", &request.refresh_token),
+        ("refresh_token", refresh_token),
     ];
 
     let response = client
@@ -651,41 +823,79 @@ pub async fn llm_claude_oauth_refresh(
     let refresh_token = token_response["refresh_token"]
         .as_str()
         .map(|s| s.to_string())
-        .unwrap_or(request.refresh_token);
+        .unwrap_or(refresh_token.to_string());
 
     let expires_in = token_response["expires_in"].as_i64().unwrap_or(3600);
     let expires_at = chrono::Utc::now().timestamp() + expires_in;
 
+    Ok(ClaudeOAuthRefreshResponse {
+        access_token,
+        refresh_token,
+        expires_at,
+    })
+}
+
+pub(crate) async fn refresh_claude_oauth_tokens(
+    client: &reqwest::Client,
+    refresh_token: &str,
+    api_keys: &ApiKeyManager,
+) -> Result<ClaudeOAuthRefreshResponse, String> {
+    let refreshed = request_claude_token_refresh(client, refresh_token).await?;
+
     // Save to settings
-    let api_keys = state.api_keys.lock().await;
     api_keys
-        .set_setting("claude_oauth_access_token", &access_token)
+        .set_setting("claude_oauth_access_token", &refreshed.access_token)
         .await?;
     api_keys
-        .set_setting("claude_oauth_refresh_token", &refresh_token)
+        .set_setting("claude_oauth_refresh_token", &refreshed.refresh_token)
         .await?;
     api_keys
-        .set_setting("claude_oauth_expires_at", &expires_at.to_string())
+        .set_setting("claude_oauth_expires_at", &refreshed.expires_at.to_string())
         .await?;
-
-    Ok(ClaudeOAuthRefreshResponse {
-        access_token,
+    oauth_accounts::replace_rotated_tokens(
+        api_keys,
+        "anthropic",
         refresh_token,
-        expires_at,
-    })
+        &OAuthAccountCredentials {
+            access_token: refreshed.access_token.clone(),
+            refresh_token: Some(refreshed.refresh_token.clone()),
+            expires_at: Some(refreshed.expires_at),
+            account_id: None,
+        },
+    )
+    .await?;
+
+    Ok(refreshed)
 }
 
 #[tauri::command]
-pub async fn llm_claude_oauth_disconnect(state: State<'_, LlmState>) -> Result<(), String> {
+pub async fn llm_claude_oauth_refresh(
+    request: ClaudeOAuthRefreshRequest,
+    state: State<'_, LlmState>,
+) -> Result<ClaudeOAuthRefreshResponse, String> {
+    let client = crate::network_proxy::shared_json_client();
     let api_keys = state.api_keys.lock().await;
-    api_keys
-        .set_setting("claude_oauth_access_token", "")
-        .await?;
-    api_keys
-        .set_setting("claude_oauth_refresh_token", "")
-        .await?;
-    api_keys.set_setting("claude_oauth_expires_at", "").await?;
-    Ok(())
+    refresh_claude_oauth_tokens(&client, &request.refresh_token, &api_keys).await
+}
+
+/// Claude has no public revocation endpoint, so tokens are only cleared locally
+#[tauri::command]
+pub async fn llm_claude_oauth_disconnect(
+    state: State<'_, LlmState>,
+) -> Result<OAuthDisconnectResponse, String> {
+    let api_keys = state.api_keys.lock().await;
+    let client = crate::network_proxy::shared_json_client();
+    disconnect_oauth(
+        &client,
+        &api_keys,
+        None,
+        &[
+            "claude_oauth_access_token",
+            "claude_oauth_refresh_token",
+            "claude_oauth_expires_at",
+        ],
+    )
+    .await
 }
 
 // ============================================================================
@@ -827,7 +1037,7 @@ pub async fn llm_github_copilot_oauth_start_device_code(
     let domain = github_copilot_domain(request.enterprise_url.as_deref());
     let url = format!("https://{}/login/device/code", domain);
 
-    let client = reqwest::Client::new();
+    let client = crate::network_proxy::shared_json_client();
     let response = client
         .post(&url)
         .header("Content-Type", "application/json")
@@ -875,7 +1085,7 @@ pub async fn llm_github_copilot_oauth_poll_device_code(
     let domain = github_copilot_domain(request.enterprise_url.as_deref());
     let url = format!("https://{}/login/oauth/access_token", domain);
 
-    let client = reqwest::Client::new();
+    let client = crate::network_proxy::shared_json_client();
     let response = client
         .post(&url)
         .header("Content-Type", "application/json")
@@ -968,11 +1178,11 @@ pub async fn llm_github_copilot_oauth_poll_device_code(
     })
 }
 
-#[tauri::command]
-pub async fn llm_github_copilot_oauth_refresh(
-    state: State<'_, LlmState>,
+/// Exchange the stored GitHub access token for a fresh Copilot API token
+pub(crate) async fn refresh_github_copilot_token(
+    client: &reqwest::Client,
+    api_keys: &ApiKeyManager,
 ) -> Result<GitHubCopilotOAuthTokens, String> {
-    let api_keys = state.api_keys.lock().await;
     let access_token = api_keys
         .get_setting(GITHUB_COPILOT_ACCESS_TOKEN_KEY)
         .await?
@@ -986,9 +1196,8 @@ pub async fn llm_github_copilot_oauth_refresh(
         .await?
         .filter(|value| !value.trim().is_empty());
 
-    let client = reqwest::Client::new();
     let (copilot_token, expires_at_ms) =
-        github_copilot_api_token(&client, &access_token, enterprise_url.as_deref()).await?;
+        github_copilot_api_token(client, &access_token, enterprise_url.as_deref()).await?;
 
     api_keys
         .set_setting(GITHUB_COPILOT_COPILOT_TOKEN_KEY, &copilot_token)
@@ -1006,21 +1215,34 @@ pub async fn llm_github_copilot_oauth_refresh(
 }
 
 #[tauri::command]
-pub async fn llm_github_copilot_oauth_disconnect(state: State<'_, LlmState>) -> Result<(), String> {
+pub async fn llm_github_copilot_oauth_refresh(
+    state: State<'_, LlmState>,
+) -> Result<GitHubCopilotOAuthTokens, String> {
     let api_keys = state.api_keys.lock().await;
-    api_keys
-        .set_setting(GITHUB_COPILOT_ACCESS_TOKEN_KEY, "")
-        .await?;
-    api_keys
-        .set_setting(GITHUB_COPILOT_COPILOT_TOKEN_KEY, "")
-        .await?;
-    api_keys
-        .set_setting(GITHUB_COPILOT_EXPIRES_AT_KEY, "")
-        .await?;
-    api_keys
-        .set_setting(GITHUB_COPILOT_ENTERPRISE_URL_KEY, "")
-        .await?;
-    Ok(())
+    let client = crate::network_proxy::shared_json_client();
+    refresh_github_copilot_token(&client, &api_keys).await
+}
+
+/// GitHub only revokes OAuth app tokens with the client secret, which a device
+/// flow client does not have, so tokens are only cleared locally
+#[tauri::command]
+pub async fn llm_github_copilot_oauth_disconnect(
+    state: State<'_, LlmState>,
+) -> Result<OAuthDisconnectResponse, String> {
+    let api_keys = state.api_keys.lock().await;
+    let client = crate::network_proxy::shared_json_client();
+    disconnect_oauth(
+        &client,
+        &api_keys,
+        None,
+        &[
+            GITHUB_COPILOT_ACCESS_TOKEN_KEY,
+            GITHUB_COPILOT_COPILOT_TOKEN_KEY,
+            GITHUB_COPILOT_EXPIRES_AT_KEY,
+            GITHUB_COPILOT_ENTERPRISE_URL_KEY,
+        ],
+    )
+    .await
 }
 
 #[tauri::command]
@@ -1069,6 +1291,9 @@ pub struct OAuthProviderStatus {
     pub is_connected: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub has_refresh_token: Option<bool>,
+    /// Connected accounts, default account first
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accounts: Option<Vec<OAuthAccountStatus>>,
 }
 
 #[derive(Serialize)]
@@ -1104,16 +1329,20 @@ pub async fn llm_oauth_status(state: State<'_, LlmState>) -> Result<OAuthStatusR
         .await?
         .filter(|s| !s.is_empty());
 
-    let openai = if openai_access.is_some() || openai_refresh.is_some() {
-        Some(OAuthProviderStatus {
-            expires_at: openai_expires,
-            account_id: openai_account,
-            is_connected: Some(true),
-            has_refresh_token: Some(openai_refresh.is_some()),
-        })
-    } else {
-        None
-    };
+    let openai_accounts = oauth_accounts::list_accounts(&api_keys, "openai").await?;
+
+    let openai =
+        if openai_access.is_some() || openai_refresh.is_some() || !openai_accounts.is_empty() {
+            Some(OAuthProviderStatus {
+                expires_at: openai_expires,
+                account_id: openai_account,
+                is_connected: Some(true),
+                has_refresh_token: Some(openai_refresh.is_some()),
+                accounts: Some(openai_accounts),
+            })
+        } else {
+            None
+        };
 
     // Anthropic status - only return metadata, not tokens
     let anthropic_access = api_keys
@@ -1125,12 +1354,15 @@ pub async fn llm_oauth_status(state: State<'_, LlmState>) -> Result<OAuthStatusR
         .await?
         .and_then(|s| s.parse::<i64>().ok());
 
-    let anthropic = if anthropic_access.is_some() {
+    let anthropic_accounts = oauth_accounts::list_accounts(&api_keys, "anthropic").await?;
+
+    let anthropic = if anthropic_access.is_some() || !anthropic_accounts.is_empty() {
         Some(OAuthProviderStatus {
             expires_at: anthropic_expires,
             account_id: None,
             is_connected: Some(true),
             has_refresh_token: None,
+            accounts: Some(anthropic_accounts),
         })
     } else {
         None
@@ -1273,4 +1505,130 @@ mod tests {
         let token = format!("{}.{}.", header, payload);
         assert_eq!(extract_openai_account_id(&token), None);
     }
+
+    async fn api_keys_with_openai_tokens() -> (ApiKeyManager, tempfile::TempDir) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db_path = dir.path().join("oauth.db");
+        let db = std::sync::Arc::new(crate::database::Database::new(
+            db_path.to_string_lossy().to_string(),
+        ));
+        db.connect().await.unwrap();
+        db.execute(
+            "CREATE TABLE IF NOT EXISTS settings (key TEXT PRIMARY KEY, value TEXT, updated_at INTEGER)",
+            vec![],
+        )
+        .await
+        .unwrap();
+        let api_keys = ApiKeyManager::new(db, std::path::PathBuf::from("/tmp"));
+        api_keys
+            .set_setting("openai_oauth_access_token", "access-123")
+            .await
+            .unwrap();
+        api_keys
+            .set_setting("openai_oauth_refresh_token", "refresh-456")
+            .await
+            .unwrap();
+        api_keys
+            .set_setting("openai_oauth_account_id", "acct_test123")
+            .await
+            .unwrap();
+        (api_keys, dir)
+    }
+
+    /// Answers every request with `status` and reports the request body
+    fn spawn_revocation_server(status: u16) -> (String, std::sync::mpsc::Receiver<String>) {
+        use std::io::Read;
+
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                let mut body = String::new();
+                let _ = request.as_reader().read_to_string(&mut body);
+                let _ = tx.send(body);
+                let _ = request.respond(tiny_http::Response::empty(status));
+            }
+        });
+        (format!("http://127.0.0.1:{}/oauth/revoke", port), rx)
+    }
+
+    async fn assert_openai_tokens_cleared(api_keys: &ApiKeyManager) {
+        for key in OPENAI_OAUTH_SETTING_KEYS {
+            let value = api_keys.get_setting(key).await.unwrap().unwrap_or_default();
+            assert!(value.is_empty(), "{} was not cleared", key);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_revokes_refresh_token() {
+        let (api_keys, _dir) = api_keys_with_openai_tokens().await;
+        let (url, requests) = spawn_revocation_server(200);
+
+        let response = disconnect_openai_oauth(&reqwest::Client::new(), &api_keys, &url)
+            .await
+            .unwrap();
+
+        assert_eq!(response.revocation, RevocationStatus::Revoked);
+        let body = requests
+            .recv_timeout(std::time::Duration::from_secs(1))
+            .unwrap();
+        assert!(body.contains("token=refresh-456"));
+        assert!(body.contains("token_type_hint=refresh_token"));
+        assert!(body.contains(&format!("client_id={}", OPENAI_CLIENT_ID)));
+        assert_openai_tokens_cleared(&api_keys).await;
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_clears_tokens_when_revocation_fails() {
+        let (api_keys, _dir) = api_keys_with_openai_tokens().await;
+        let (url, requests) = spawn_revocation_server(503);
+
+        let response = disconnect_openai_oauth(&reqwest::Client::new(), &api_keys, &url)
+            .await
+            .unwrap();
+
+        assert_eq!(response.revocation, RevocationStatus::Failed);
+        assert!(response.error.unwrap().contains("503"));
+        assert!(requests
+            .recv_timeout(std::time::Duration::from_secs(1))
+            .is_ok());
+        assert_openai_tokens_cleared(&api_keys).await;
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_clears_tokens_when_endpoint_unreachable() {
+        let (api_keys, _dir) = api_keys_with_openai_tokens().await;
+        // Bind then drop a listener so nothing is listening on the port
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let url = format!("http://127.0.0.1:{}/oauth/revoke", port);
+
+        let response = disconnect_openai_oauth(&reqwest::Client::new(), &api_keys, &url)
+            .await
+            .unwrap();
+
+        assert_eq!(response.revocation, RevocationStatus::Failed);
+        assert_openai_tokens_cleared(&api_keys).await;
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_without_revocation_endpoint() {
+        let (api_keys, _dir) = api_keys_with_openai_tokens().await;
+
+        let response = disconnect_oauth(
+            &reqwest::Client::new(),
+            &api_keys,
+            None,
+            OPENAI_OAUTH_SETTING_KEYS,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.revocation, RevocationStatus::Unsupported);
+        assert_openai_tokens_cleared(&api_keys).await;
+    }
 }
@@ -0,0 +1,698 @@
+// Named OAuth accounts
+// A provider's regular OAuth connection is its "default" account. Additional
+// connections (e.g. personal and work subscriptions) are saved under a label and
+// selected per request, falling back to the provider's active account setting.
+// Named accounts are refreshed with their own refresh tokens before they expire.
+
+use std::collections::BTreeMap;
+use std::future::Future;
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::llm::auth::api_key_manager::{ApiKeyManager, LlmState};
+use crate::llm::auth::oauth::{request_claude_token_refresh, request_openai_token_refresh};
+
+/// Label of the account held in the provider's regular OAuth settings
+pub const DEFAULT_OAUTH_ACCOUNT: &str = "default";
+
+const ACCOUNTS_KEY_PREFIX: &str = "oauth_accounts_";
+const ACTIVE_ACCOUNT_KEY_PREFIX: &str = "oauth_active_account_";
+/// Named accounts expiring within this many seconds are refreshed before use
+const REFRESH_LEAD_SECS: i64 = 5 * 60;
+
+/// Settings keys of a provider's default account
+struct DefaultAccountKeys {
+    access_token: &'static str,
+    refresh_token: &'static str,
+    expires_at: &'static str,
+    account_id: Option<&'static str>,
+}
+
+fn default_account_keys(provider_id: &str) -> Option<DefaultAccountKeys> {
+    match provider_id {
+        "openai" => Some(DefaultAccountKeys {
+            access_token: "openai_oauth_access_token",
+            refresh_token: "openai_oauth_refresh_token",
+            expires_at: "openai_oauth_expires_at",
+            account_id: Some("openai_oauth_account_id"),
+        }),
+        "anthropic" => Some(DefaultAccountKeys {
+            access_token: "claude_oauth_access_token",
+            refresh_token: "claude_oauth_refresh_token",
+            expires_at: "claude_oauth_expires_at",
+            account_id: None,
+        }),
+        _ => None,
+    }
+}
+
+/// Whether `provider_id` can hold named accounts
+pub fn supports_named_accounts(provider_id: &str) -> bool {
+    default_account_keys(provider_id).is_some()
+}
+
+/// Tokens of one OAuth account
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OAuthAccountCredentials {
+    pub access_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<String>,
+}
+
+/// Account metadata for the status response - does NOT include tokens
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OAuthAccountStatus {
+    pub label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<String>,
+    pub has_refresh_token: bool,
+    pub is_active: bool,
+}
+
+fn validate_label(provider_id: &str, label: &str) -> Result<String, String> {
+    if !supports_named_accounts(provider_id) {
+        return Err(format!(
+            "Provider {} does not support multiple OAuth accounts",
+            provider_id
+        ));
+    }
+    let label = label.trim();
+    if label.is_empty() {
+        return Err("Account label must not be empty".to_string());
+    }
+    if label == DEFAULT_OAUTH_ACCOUNT {
+        return Err(format!(
+            "\"{}\" is reserved for the regular OAuth connection",
+            DEFAULT_OAUTH_ACCOUNT
+        ));
+    }
+    Ok(label.to_string())
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.trim().is_empty())
+}
+
+/// Named accounts saved for `provider_id`, by label
+pub async fn load_accounts(
+    api_keys: &ApiKeyManager,
+    provider_id: &str,
+) -> Result<BTreeMap<String, OAuthAccountCredentials>, String> {
+    if !supports_named_accounts(provider_id) {
+        return Ok(BTreeMap::new());
+    }
+    match non_empty(
+        api_keys
+            .get_setting(&format!("{}{}", ACCOUNTS_KEY_PREFIX, provider_id))
+            .await?,
+    ) {
+        Some(raw) => serde_json::from_str(&raw)
+            .map_err(|e| format!("Failed to parse OAuth accounts for {}: {}", provider_id, e)),
+        None => Ok(BTreeMap::new()),
+    }
+}
+
+async fn store_accounts(
+    api_keys: &ApiKeyManager,
+    provider_id: &str,
+    accounts: &BTreeMap<String, OAuthAccountCredentials>,
+) -> Result<(), String> {
+    let raw = serde_json::to_string(accounts)
+        .map_err(|e| format!("Failed to serialize OAuth accounts: {}", e))?;
+    api_keys
+        .set_setting(&format!("{}{}", ACCOUNTS_KEY_PREFIX, provider_id), &raw)
+        .await
+}
+
+/// Save `credentials` as the named account `label`, replacing any account with that label
+pub async fn save_account(
+    api_keys: &ApiKeyManager,
+    provider_id: &str,
+    label: &str,
+    credentials: OAuthAccountCredentials,
+) -> Result<(), String> {
+    let label = validate_label(provider_id, label)?;
+    if credentials.access_token.trim().is_empty() {
+        return Err("OAuth account has no access token".to_string());
+    }
+    let mut accounts = load_accounts(api_keys, provider_id).await?;
+    accounts.insert(label, credentials);
+    store_accounts(api_keys, provider_id, &accounts).await
+}
+
+/// Tokens of the provider's regular OAuth connection, if connected
+pub async fn default_account(
+    api_keys: &ApiKeyManager,
+    provider_id: &str,
+) -> Result<Option<OAuthAccountCredentials>, String> {
+    let Some(keys) = default_account_keys(provider_id) else {
+        return Ok(None);
+    };
+    let Some(access_token) = non_empty(api_keys.get_setting(keys.access_token).await?) else {
+        return Ok(None);
+    };
+    let account_id = match keys.account_id {
+        Some(key) => non_empty(api_keys.get_setting(key).await?),
+        None => None,
+    };
+    Ok(Some(OAuthAccountCredentials {
+        access_token,
+        refresh_token: non_empty(api_keys.get_setting(keys.refresh_token).await?),
+        expires_at: api_keys
+            .get_setting(keys.expires_at)
+            .await?
+            .and_then(|value| value.parse::<i64>().ok()),
+        account_id,
+    }))
+}
+
+/// Copy the regular OAuth connection into the named account `label`,
+/// so the provider can be connected again with another account
+pub async fn save_default_account_as(
+    api_keys: &ApiKeyManager,
+    provider_id: &str,
+    label: &str,
+) -> Result<(), String> {
+    let label = validate_label(provider_id, label)?;
+    let credentials = default_account(api_keys, provider_id)
+        .await?
+        .ok_or_else(|| format!("No OAuth account connected for {}", provider_id))?;
+    save_account(api_keys, provider_id, &label, credentials).await
+}
+
+/// Remove the named account `label`; clears the active selection if it pointed there
+pub async fn remove_account(
+    api_keys: &ApiKeyManager,
+    provider_id: &str,
+    label: &str,
+) -> Result<(), String> {
+    let label = validate_label(provider_id, label)?;
+    let mut accounts = load_accounts(api_keys, provider_id).await?;
+    if accounts.remove(&label).is_none() {
+        return Ok(());
+    }
+    store_accounts(api_keys, provider_id, &accounts).await?;
+    if active_account(api_keys, provider_id).await? == label {
+        set_active_account(api_keys, provider_id, None).await?;
+    }
+    Ok(())
+}
+
+/// Label of the account used when a request does not choose one
+pub async fn active_account(api_keys: &ApiKeyManager, provider_id: &str) -> Result<String, String> {
+    Ok(non_empty(
+        api_keys
+            .get_setting(&format!("{}{}", ACTIVE_ACCOUNT_KEY_PREFIX, provider_id))
+            .await?,
+    )
+    .unwrap_or_else(|| DEFAULT_OAUTH_ACCOUNT.to_string()))
+}
+
+/// Make `label` the provider's active account; `None` selects the default account
+pub async fn set_active_account(
+    api_keys: &ApiKeyManager,
+    provider_id: &str,
+    label: Option<&str>,
+) -> Result<(), String> {
+    let label = match label.map(str::trim) {
+        None | Some("") | Some(DEFAULT_OAUTH_ACCOUNT) => String::new(),
+        Some(label) => {
+            let label = validate_label(provider_id, label)?;
+            if !load_accounts(api_keys, provider_id)
+                .await?
+                .contains_key(&label)
+            {
+                return Err(format!(
+                    "OAuth account {} not found for {}",
+                    label, provider_id
+                ));
+            }
+            label
+        }
+    };
+    api_keys
+        .set_setting(
+            &format!("{}{}", ACTIVE_ACCOUNT_KEY_PREFIX, provider_id),
+            &label,
+        )
+        .await
+}
+
+/// Named account `api_keys` should use for `provider_id`: the account the request
+/// selected, else the provider's active account. `None` means the default account,
+/// which is also used when the selected label is not saved for this provider.
+pub async fn selected_account(
+    api_keys: &ApiKeyManager,
+    provider_id: &str,
+) -> Result<Option<OAuthAccountCredentials>, String> {
+    if !supports_named_accounts(provider_id) {
+        return Ok(None);
+    }
+    let label = match api_keys.oauth_account() {
+        Some(label) => label.to_string(),
+        None => active_account(api_keys, provider_id).await?,
+    };
+    if label == DEFAULT_OAUTH_ACCOUNT {
+        return Ok(None);
+    }
+    let Some(account) = load_accounts(api_keys, provider_id).await?.remove(&label) else {
+        log::warn!(
+            "OAuth account {} not found for {}, using the default account",
+            label,
+            provider_id
+        );
+        return Ok(None);
+    };
+    if !needs_refresh(&account, chrono::Utc::now().timestamp()) {
+        return Ok(Some(account));
+    }
+    match refresh_account_with(
+        api_keys,
+        provider_id,
+        &label,
+        account.clone(),
+        &request_refresh,
+    )
+    .await
+    {
+        Ok(refreshed) => Ok(Some(refreshed)),
+        Err(e) => {
+            log::warn!(
+                "Failed to refresh OAuth account {} for {}: {}",
+                label,
+                provider_id,
+                e
+            );
+            Ok(Some(account))
+        }
+    }
+}
+
+fn needs_refresh(credentials: &OAuthAccountCredentials, now_secs: i64) -> bool {
+    credentials.refresh_token.is_some()
+        && credentials
+            .expires_at
+            .is_some_and(|expires_at| expires_at - REFRESH_LEAD_SECS <= now_secs)
+}
+
+/// Exchange a named account's refresh token at the provider's OAuth endpoint
+async fn request_refresh(
+    provider_id: String,
+    refresh_token: String,
+) -> Result<OAuthAccountCredentials, String> {
+    let client = crate::network_proxy::shared_json_client();
+    match provider_id.as_str() {
+        "openai" => {
+            let refreshed = request_openai_token_refresh(&client, &refresh_token).await?;
+            Ok(OAuthAccountCredentials {
+                access_token: refreshed.access_token,
+                refresh_token: Some(refreshed.refresh_token),
+                expires_at: Some(refreshed.expires_at),
+                account_id: refreshed.account_id,
+            })
+        }
+        "anthropic" => {
+            let refreshed = request_claude_token_refresh(&client, &refresh_token).await?;
+            Ok(OAuthAccountCredentials {
+                access_token: refreshed.access_token,
+                refresh_token: Some(refreshed.refresh_token),
+                expires_at: Some(refreshed.expires_at),
+                account_id: None,
+            })
+        }
+        _ => Err(format!(
+            "Provider {} does not support multiple OAuth accounts",
+            provider_id
+        )),
+    }
+}
+
+/// Refresh the named account `label` with `refresh` and store the new tokens
+async fn refresh_account_with<F, Fut>(
+    api_keys: &ApiKeyManager,
+    provider_id: &str,
+    label: &str,
+    credentials: OAuthAccountCredentials,
+    refresh: &F,
+) -> Result<OAuthAccountCredentials, String>
+where
+    F: Fn(String, String) -> Fut,
+    Fut: Future<Output = Result<OAuthAccountCredentials, String>>,
+{
+    let refresh_token = credentials
+        .refresh_token
+        .clone()
+        .ok_or_else(|| format!("OAuth account {} has no refresh token", label))?;
+    let mut refreshed = refresh(provider_id.to_string(), refresh_token).await?;
+    if refreshed.account_id.is_none() {
+        refreshed.account_id = credentials.account_id;
+    }
+    save_account(api_keys, provider_id, label, refreshed.clone()).await?;
+    log::info!("Refreshed OAuth account {} for {}", label, provider_id);
+    Ok(refreshed)
+}
+
+/// Refresh every named account of `provider_id` that is about to expire
+pub async fn refresh_expiring_accounts(
+    api_keys: &ApiKeyManager,
+    provider_id: &str,
+) -> Result<(), String> {
+    refresh_expiring_accounts_with(
+        api_keys,
+        provider_id,
+        chrono::Utc::now().timestamp(),
+        &request_refresh,
+    )
+    .await
+}
+
+async fn refresh_expiring_accounts_with<F, Fut>(
+    api_keys: &ApiKeyManager,
+    provider_id: &str,
+    now_secs: i64,
+    refresh: &F,
+) -> Result<(), String>
+where
+    F: Fn(String, String) -> Fut,
+    Fut: Future<Output = Result<OAuthAccountCredentials, String>>,
+{
+    for (label, credentials) in load_accounts(api_keys, provider_id).await? {
+        if !needs_refresh(&credentials, now_secs) {
+            continue;
+        }
+        if let Err(e) =
+            refresh_account_with(api_keys, provider_id, &label, credentials, refresh).await
+        {
+            log::warn!(
+                "Failed to refresh OAuth account {} for {}: {}",
+                label,
+                provider_id,
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+/// After the default account's refresh token rotated from `old_refresh_token`,
+/// give named accounts saved from that connection the new tokens too
+pub async fn replace_rotated_tokens(
+    api_keys: &ApiKeyManager,
+    provider_id: &str,
+    old_refresh_token: &str,
+    refreshed: &OAuthAccountCredentials,
+) -> Result<(), String> {
+    let mut accounts = load_accounts(api_keys, provider_id).await?;
+    let mut changed = false;
+    for credentials in accounts.values_mut() {
+        if credentials.refresh_token.as_deref() == Some(old_refresh_token) {
+            let account_id = credentials.account_id.take();
+            *credentials = refreshed.clone();
+            if credentials.account_id.is_none() {
+                credentials.account_id = account_id;
+            }
+            changed = true;
+        }
+    }
+    if changed {
+        store_accounts(api_keys, provider_id, &accounts).await?;
+    }
+    Ok(())
+}
+
+/// Every connected account of `provider_id`, default account first
+pub async fn list_accounts(
+    api_keys: &ApiKeyManager,
+    provider_id: &str,
+) -> Result<Vec<OAuthAccountStatus>, String> {
+    let active = active_account(api_keys, provider_id).await?;
+    let status = |label: String, credentials: OAuthAccountCredentials| OAuthAccountStatus {
+        is_active: label == active,
+        label,
+        expires_at: credentials.expires_at,
+        account_id: credentials.account_id,
+        has_refresh_token: credentials.refresh_token.is_some(),
+    };
+
+    let mut accounts = Vec::new();
+    if let Some(credentials) = default_account(api_keys, provider_id).await? {
+        accounts.push(status(DEFAULT_OAUTH_ACCOUNT.to_string(), credentials));
+    }
+    for (label, credentials) in load_accounts(api_keys, provider_id).await? {
+        accounts.push(status(label, credentials));
+    }
+    Ok(accounts)
+}
+
+/// Save the current OAuth connection of `provider_id` as the account `label`
+#[tauri::command]
+pub async fn llm_oauth_save_account(
+    provider_id: String,
+    label: String,
+    state: State<'_, LlmState>,
+) -> Result<Vec<OAuthAccountStatus>, String> {
+    let api_keys = state.api_keys.lock().await;
+    save_default_account_as(&api_keys, &provider_id, &label).await?;
+    list_accounts(&api_keys, &provider_id).await
+}
+
+#[tauri::command]
+pub async fn llm_oauth_remove_account(
+    provider_id: String,
+    label: String,
+    state: State<'_, LlmState>,
+) -> Result<Vec<OAuthAccountStatus>, String> {
+    let api_keys = state.api_keys.lock().await;
+    remove_account(&api_keys, &provider_id, &label).await?;
+    list_accounts(&api_keys, &provider_id).await
+}
+
+/// Choose the account used by requests that do not name one; `None` selects the default account
+#[tauri::command]
+pub async fn llm_oauth_set_active_account(
+    provider_id: String,
+    label: Option<String>,
+    state: State<'_, LlmState>,
+) -> Result<Vec<OAuthAccountStatus>, String> {
+    let api_keys = state.api_keys.lock().await;
+    set_active_account(&api_keys, &provider_id, label.as_deref()).await?;
+    list_accounts(&api_keys, &provider_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    async fn setup() -> (ApiKeyManager, TempDir) {
+        let dir = TempDir::new().expect("temp dir");
+        let db_path = dir.path().join("oauth-accounts.db");
+        let db = Arc::new(Database::new(db_path.to_string_lossy().to_string()));
+        db.connect().await.expect("db connect");
+        db.execute(
+            "CREATE TABLE IF NOT EXISTS settings (key TEXT PRIMARY KEY, value TEXT, updated_at INTEGER)",
+            vec![],
+        )
+        .await
+        .expect("create settings");
+        (
+            ApiKeyManager::new(db, std::path::PathBuf::from("/tmp")),
+            dir,
+        )
+    }
+
+    /// Simulate completing the OpenAI OAuth flow for one account
+    async fn connect_openai(api_keys: &ApiKeyManager, name: &str) {
+        for (key, value) in [
+            ("openai_oauth_access_token", format!("{}-token", name)),
+            ("openai_oauth_refresh_token", format!("{}-refresh", name)),
+            ("openai_oauth_expires_at", "1760000000".to_string()),
+            ("openai_oauth_account_id", format!("acct_{}", name)),
+        ] {
+            api_keys.set_setting(key, &value).await.expect("set token");
+        }
+    }
+
+    fn labels(accounts: &[OAuthAccountStatus]) -> Vec<(&str, bool)> {
+        accounts
+            .iter()
+            .map(|account| (account.label.as_str(), account.is_active))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_two_accounts_for_one_provider() {
+        let (api_keys, _dir) = setup().await;
+        connect_openai(&api_keys, "personal").await;
+        save_default_account_as(&api_keys, "openai", "personal")
+            .await
+            .expect("save personal");
+        connect_openai(&api_keys, "work").await;
+        save_default_account_as(&api_keys, "openai", "work")
+            .await
+            .expect("save work");
+
+        let accounts = list_accounts(&api_keys, "openai").await.unwrap();
+        assert_eq!(
+            labels(&accounts),
+            vec![("default", true), ("personal", false), ("work", false)]
+        );
+        assert_eq!(accounts[1].account_id.as_deref(), Some("acct_personal"));
+        assert!(accounts[1].has_refresh_token);
+
+        set_active_account(&api_keys, "openai", Some("personal"))
+            .await
+            .expect("select personal");
+        let accounts = list_accounts(&api_keys, "openai").await.unwrap();
+        assert_eq!(
+            labels(&accounts),
+            vec![("default", false), ("personal", true), ("work", false)]
+        );
+
+        remove_account(&api_keys, "openai", "personal")
+            .await
+            .expect("remove personal");
+        let accounts = list_accounts(&api_keys, "openai").await.unwrap();
+        assert_eq!(labels(&accounts), vec![("default", true), ("work", false)]);
+    }
+
+    #[tokio::test]
+    async fn test_request_account_overrides_active_account() {
+        let (api_keys, _dir) = setup().await;
+        connect_openai(&api_keys, "personal").await;
+        save_default_account_as(&api_keys, "openai", "personal")
+            .await
+            .unwrap();
+        connect_openai(&api_keys, "work").await;
+        save_default_account_as(&api_keys, "openai", "work")
+            .await
+            .unwrap();
+        set_active_account(&api_keys, "openai", Some("work"))
+            .await
+            .unwrap();
+
+        let selected = selected_account(&api_keys, "openai").await.unwrap();
+        assert_eq!(selected.unwrap().access_token, "work-token");
+
+        let personal = api_keys.with_oauth_account(Some("personal".to_string()));
+        let selected = selected_account(&personal, "openai").await.unwrap();
+        assert_eq!(selected.unwrap().access_token, "personal-token");
+
+        // The default account and unknown labels use the regular connection
+        let default = api_keys.with_oauth_account(Some(DEFAULT_OAUTH_ACCOUNT.to_string()));
+        assert!(selected_account(&default, "openai")
+            .await
+            .unwrap()
+            .is_none());
+        let unknown = api_keys.with_oauth_account(Some("missing".to_string()));
+        assert!(selected_account(&unknown, "openai")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_accounts() {
+        let (api_keys, _dir) = setup().await;
+        assert!(save_default_account_as(&api_keys, "openai", "work")
+            .await
+            .unwrap_err()
+            .contains("No OAuth account connected"));
+
+        connect_openai(&api_keys, "personal").await;
+        assert!(save_default_account_as(&api_keys, "openai", "default")
+            .await
+            .is_err());
+        assert!(save_default_account_as(&api_keys, "openai", "  ")
+            .await
+            .is_err());
+        assert!(save_default_account_as(&api_keys, "github_copilot", "work")
+            .await
+            .is_err());
+        assert!(set_active_account(&api_keys, "openai", Some("missing"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_refreshes_expiring_named_accounts() {
+        let (api_keys, _dir) = setup().await;
+        connect_openai(&api_keys, "personal").await;
+        save_default_account_as(&api_keys, "openai", "personal")
+            .await
+            .expect("save personal");
+        let work = OAuthAccountCredentials {
+            access_token: "work-token".to_string(),
+            refresh_token: Some("work-refresh".to_string()),
+            expires_at: Some(1_770_000_000),
+            account_id: Some("acct_work".to_string()),
+        };
+        save_account(&api_keys, "openai", "work", work.clone())
+            .await
+            .expect("save work");
+
+        let refresh = |provider_id: String, refresh_token: String| async move {
+            assert_eq!(provider_id, "openai");
+            Ok(OAuthAccountCredentials {
+                access_token: format!("{}-new-token", refresh_token),
+                refresh_token: Some(format!("{}-new", refresh_token)),
+                expires_at: Some(1_760_003_600),
+                account_id: None,
+            })
+        };
+        refresh_expiring_accounts_with(&api_keys, "openai", 1_759_999_900, &refresh)
+            .await
+            .expect("refresh accounts");
+
+        let accounts = load_accounts(&api_keys, "openai").await.unwrap();
+        assert_eq!(
+            accounts["personal"],
+            OAuthAccountCredentials {
+                access_token: "personal-refresh-new-token".to_string(),
+                refresh_token: Some("personal-refresh-new".to_string()),
+                expires_at: Some(1_760_003_600),
+                account_id: Some("acct_personal".to_string()),
+            }
+        );
+        assert_eq!(accounts["work"], work);
+    }
+
+    #[tokio::test]
+    async fn test_rotated_default_tokens_update_copied_accounts() {
+        let (api_keys, _dir) = setup().await;
+        connect_openai(&api_keys, "personal").await;
+        save_default_account_as(&api_keys, "openai", "personal")
+            .await
+            .expect("save personal");
+        connect_openai(&api_keys, "work").await;
+        save_default_account_as(&api_keys, "openai", "work")
+            .await
+            .expect("save work");
+
+        let refreshed = OAuthAccountCredentials {
+            access_token: "rotated-token".to_string(),
+            refresh_token: Some("rotated-refresh".to_string()),
+            expires_at: Some(1_760_003_600),
+            account_id: None,
+        };
+        replace_rotated_tokens(&api_keys, "openai", "work-refresh", &refreshed)
+            .await
+            .expect("replace tokens");
+
+        let accounts = load_accounts(&api_keys, "openai").await.unwrap();
+        assert_eq!(accounts["work"].access_token, "rotated-token");
+        assert_eq!(accounts["work"].account_id.as_deref(), Some("acct_work"));
+        assert_eq!(accounts["personal"].access_token, "personal-token");
+    }
+}
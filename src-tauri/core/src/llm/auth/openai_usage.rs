@@ -6,6 +6,7 @@ use std::time::Duration;
 use tauri::State;
 
 const OPENAI_USAGE_DEFAULT_URL: &str = "https://chatgpt.com/backend-api/wham/usage";
+const USAGE_REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
 const OPENAI_OAUTH_MISSING_MESSAGE: &str =
     "OpenAI OAuth not connected. Please connect your OpenAI account in settings.";
 const OPENAI_OAUTH_EXPIRED_MESSAGE: &str =
@@ -36,6 +37,7 @@ async fn fetch_usage_with_token(
     }
 
     let response = request
+        .timeout(USAGE_REQUEST_TIMEOUT)
         .send()
         .await
         .map_err(|e| format!("OpenAI usage request failed: {}", e))?;
@@ -64,7 +66,7 @@ async fn refresh_access_token(
     api_keys: &ApiKeyManager,
     refresh_token: &str,
 ) -> Result<String, String> {
-    let client = reqwest::Client::new();
+    let client = crate::network_proxy::shared_json_client();
     let refreshed = refresh_openai_oauth_tokens(&client, refresh_token, api_keys).await?;
     Ok(refreshed.access_token)
 }
@@ -76,10 +78,7 @@ pub async fn fetch_openai_oauth_usage(api_keys: &ApiKeyManager) -> Result<Value,
         .unwrap_or_default();
     let refresh_token = load_refresh_token(api_keys).await?;
 
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(20))
-        .build()
-        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+    let client = crate::network_proxy::shared_json_client();
 
     if token.trim().is_empty() {
         let Some(refresh_token) = refresh_token else {
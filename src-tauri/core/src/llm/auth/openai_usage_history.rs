@@ -0,0 +1,216 @@
+// Daily history of OpenAI subscription usage
+// The usage endpoint only reports the current window, so one snapshot per UTC
+// day is persisted and returned as a time series for charting.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::State;
+
+use crate::background_tasks;
+use crate::database::Database;
+use crate::llm::auth::api_key_manager::{ApiKeyManager, LlmState};
+use crate::llm::auth::openai_usage::fetch_openai_oauth_usage;
+
+/// How often the background task takes a snapshot
+pub const USAGE_POLL_INTERVAL_MS: u64 = DAY_MS as u64;
+/// Longest history the command returns
+pub const MAX_HISTORY_DAYS: u32 = 365;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Usage as reported on one day; the latest snapshot of the day wins
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageSnapshot {
+    /// UTC date, `YYYY-MM-DD`
+    pub day: String,
+    pub captured_at: i64,
+    /// Raw response from the usage endpoint
+    pub usage: Value,
+}
+
+fn day_key(timestamp_ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(timestamp_ms)
+        .unwrap_or_default()
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+/// Store `usage` as the snapshot for the day containing `now_ms`
+pub async fn save_snapshot(db: &Database, now_ms: i64, usage: &Value) -> Result<(), String> {
+    db.execute(
+        "INSERT INTO openai_usage_snapshots (day, captured_at, payload) VALUES (?, ?, ?) \
+         ON CONFLICT(day) DO UPDATE SET captured_at = excluded.captured_at, payload = excluded.payload",
+        vec![
+            Value::String(day_key(now_ms)),
+            Value::Number(now_ms.into()),
+            Value::String(usage.to_string()),
+        ],
+    )
+    .await?;
+    Ok(())
+}
+
+/// Snapshots for the last `days` days up to `now_ms`, oldest first
+pub async fn load_history(
+    db: &Database,
+    now_ms: i64,
+    days: u32,
+) -> Result<Vec<UsageSnapshot>, String> {
+    let days = days.clamp(1, MAX_HISTORY_DAYS) as i64;
+    let first_day = day_key(now_ms - (days - 1) * DAY_MS);
+    let rows = db
+        .query(
+            "SELECT day, captured_at, payload FROM openai_usage_snapshots \
+             WHERE day >= ? ORDER BY day ASC",
+            vec![Value::String(first_day)],
+        )
+        .await?
+        .rows;
+
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            Some(UsageSnapshot {
+                day: row["day"].as_str()?.to_string(),
+                captured_at: row["captured_at"].as_i64()?,
+                usage: row["payload"]
+                    .as_str()
+                    .and_then(|payload| serde_json::from_str(payload).ok())
+                    .unwrap_or(Value::Null),
+            })
+        })
+        .collect())
+}
+
+async fn is_connected(api_keys: &ApiKeyManager) -> Result<bool, String> {
+    for key in ["openai_oauth_access_token", "openai_oauth_refresh_token"] {
+        if let Some(token) = api_keys.get_setting(key).await? {
+            if !token.trim().is_empty() {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Fetch current usage and store it as today's snapshot.
+/// Does nothing when OpenAI OAuth is not connected.
+pub async fn poll_usage(db: &Database, api_keys: &ApiKeyManager) -> Result<(), String> {
+    if !is_connected(api_keys).await? {
+        return Ok(());
+    }
+    let usage = fetch_openai_oauth_usage(api_keys).await?;
+    save_snapshot(db, chrono::Utc::now().timestamp_millis(), &usage).await
+}
+
+/// Snapshot once now, then every `USAGE_POLL_INTERVAL_MS` as a background schedule
+pub async fn start_background_polling(
+    db: Arc<Database>,
+    api_keys: ApiKeyManager,
+) -> Result<String, String> {
+    if let Err(e) = poll_usage(&db, &api_keys).await {
+        log::warn!("Initial OpenAI usage snapshot failed: {}", e);
+    }
+    background_tasks::schedule_internal_job(
+        "internal:openai_usage_history",
+        USAGE_POLL_INTERVAL_MS,
+        move || {
+            let db = db.clone();
+            let api_keys = api_keys.clone();
+            async move { poll_usage(&db, &api_keys).await }
+        },
+    )
+    .await
+}
+
+/// Refresh today's snapshot, then return the last `days` days of usage
+#[tauri::command]
+pub async fn llm_openai_usage_history(
+    days: u32,
+    state: State<'_, LlmState>,
+    db: State<'_, Arc<Database>>,
+) -> Result<Vec<UsageSnapshot>, String> {
+    let api_keys = state.api_keys.lock().await.clone();
+    // A failed poll still leaves the stored history worth returning
+    if let Err(e) = poll_usage(db.inner(), &api_keys).await {
+        log::warn!("Failed to refresh OpenAI usage snapshot: {}", e);
+    }
+    load_history(db.inner(), chrono::Utc::now().timestamp_millis(), days).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    // 2025-10-09T08:53:20Z
+    const NOW: i64 = 1_760_000_000_000;
+
+    async fn create_test_db() -> (Arc<Database>, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test_usage_history.db");
+        let db = Arc::new(Database::new(db_path.to_string_lossy().to_string()));
+        db.connect().await.unwrap();
+        db.execute(
+            "CREATE TABLE openai_usage_snapshots (day TEXT PRIMARY KEY, captured_at INTEGER NOT NULL, payload TEXT NOT NULL)",
+            vec![],
+        )
+        .await
+        .unwrap();
+        (db, temp_dir)
+    }
+
+    fn usage(percent: i64) -> Value {
+        serde_json::json!({ "rate_limit": { "primary_window": { "used_percent": percent } } })
+    }
+
+    #[test]
+    fn test_day_key_is_utc_date() {
+        assert_eq!(day_key(NOW), "2025-10-09");
+        assert_eq!(day_key(NOW + DAY_MS), "2025-10-10");
+    }
+
+    #[tokio::test]
+    async fn test_history_is_ordered_and_one_per_day() {
+        let (db, _temp_dir) = create_test_db().await;
+        // Out of order, with two polls on the last day
+        save_snapshot(&db, NOW + 2 * DAY_MS, &usage(30))
+            .await
+            .unwrap();
+        save_snapshot(&db, NOW, &usage(10)).await.unwrap();
+        save_snapshot(&db, NOW + DAY_MS, &usage(20)).await.unwrap();
+        save_snapshot(&db, NOW + 2 * DAY_MS + 60_000, &usage(35))
+            .await
+            .unwrap();
+
+        let history = load_history(&db, NOW + 2 * DAY_MS + 60_000, 7)
+            .await
+            .unwrap();
+
+        let days: Vec<&str> = history.iter().map(|s| s.day.as_str()).collect();
+        assert_eq!(days, vec!["2025-10-09", "2025-10-10", "2025-10-11"]);
+        let last = history.last().unwrap();
+        assert_eq!(last.captured_at, NOW + 2 * DAY_MS + 60_000);
+        assert_eq!(last.usage, usage(35));
+    }
+
+    #[tokio::test]
+    async fn test_history_limited_to_requested_days() {
+        let (db, _temp_dir) = create_test_db().await;
+        for day in 0..10 {
+            save_snapshot(&db, NOW + day * DAY_MS, &usage(day))
+                .await
+                .unwrap();
+        }
+
+        let history = load_history(&db, NOW + 9 * DAY_MS, 3).await.unwrap();
+        let days: Vec<&str> = history.iter().map(|s| s.day.as_str()).collect();
+        assert_eq!(days, vec!["2025-10-16", "2025-10-17", "2025-10-18"]);
+
+        let today = load_history(&db, NOW + 9 * DAY_MS, 0).await.unwrap();
+        assert_eq!(today.len(), 1);
+    }
+}
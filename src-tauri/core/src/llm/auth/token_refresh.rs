@@ -0,0 +1,485 @@
+// Proactive OAuth token refresh
+// Schedules a one-shot background job shortly before each connected provider's
+// token expires, so streams don't fail mid-request on an expired token.
+// A periodic sweep picks up new connections, disconnects and manual refreshes,
+// and refreshes named OAuth accounts that are about to expire.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+use crate::background_tasks;
+use crate::llm::auth::api_key_manager::ApiKeyManager;
+use crate::llm::auth::oauth::{
+    refresh_claude_oauth_tokens, refresh_github_copilot_token, refresh_openai_oauth_tokens,
+    GITHUB_COPILOT_ACCESS_TOKEN_KEY, GITHUB_COPILOT_EXPIRES_AT_KEY,
+};
+use crate::llm::auth::oauth_accounts;
+
+/// Emitted when a proactive refresh fails and the user should reconnect
+pub const OAUTH_REFRESH_FAILED_EVENT: &str = "oauth-refresh-failed";
+/// How long before expiry the refresh runs
+pub const REFRESH_LEAD_MS: i64 = 5 * 60 * 1000;
+/// How often stored expiries are re-read to pick up connection changes
+pub const SWEEP_INTERVAL_MS: u64 = 15 * 60 * 1000;
+
+/// Serialized with the same names as `OAuthStatusResponse` fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum OAuthProvider {
+    #[serde(rename = "openai")]
+    OpenAi,
+    #[serde(rename = "anthropic")]
+    Claude,
+    #[serde(rename = "githubCopilot")]
+    GithubCopilot,
+}
+
+impl OAuthProvider {
+    pub const ALL: [OAuthProvider; 3] = [
+        OAuthProvider::OpenAi,
+        OAuthProvider::Claude,
+        OAuthProvider::GithubCopilot,
+    ];
+
+    /// Setting holding the token that a refresh needs
+    fn credential_key(self) -> &'static str {
+        match self {
+            OAuthProvider::OpenAi => "openai_oauth_refresh_token",
+            OAuthProvider::Claude => "claude_oauth_refresh_token",
+            OAuthProvider::GithubCopilot => GITHUB_COPILOT_ACCESS_TOKEN_KEY,
+        }
+    }
+
+    fn expires_at_key(self) -> &'static str {
+        match self {
+            OAuthProvider::OpenAi => "openai_oauth_expires_at",
+            OAuthProvider::Claude => "claude_oauth_expires_at",
+            OAuthProvider::GithubCopilot => GITHUB_COPILOT_EXPIRES_AT_KEY,
+        }
+    }
+
+    /// OpenAI and Claude store seconds, Copilot stores milliseconds
+    fn expires_at_ms(self, stored: i64) -> i64 {
+        match self {
+            OAuthProvider::OpenAi | OAuthProvider::Claude => stored * 1000,
+            OAuthProvider::GithubCopilot => stored,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OAuthRefreshFailedPayload {
+    pub provider: OAuthProvider,
+    pub error: String,
+}
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+type RefreshFn =
+    Arc<dyn Fn(ApiKeyManager, OAuthProvider) -> BoxFuture<Result<(), String>> + Send + Sync>;
+type FailureFn = Arc<dyn Fn(OAuthRefreshFailedPayload) + Send + Sync>;
+
+/// When to refresh a token expiring at `expires_at_ms`; never in the past
+pub fn refresh_at_ms(expires_at_ms: i64, now_ms: i64) -> i64 {
+    (expires_at_ms - REFRESH_LEAD_MS).max(now_ms)
+}
+
+/// Refresh a provider's token through its OAuth endpoint
+pub async fn refresh_provider(
+    api_keys: ApiKeyManager,
+    provider: OAuthProvider,
+) -> Result<(), String> {
+    let client = crate::network_proxy::shared_json_client();
+    match provider {
+        OAuthProvider::GithubCopilot => {
+            refresh_github_copilot_token(&client, &api_keys).await?;
+        }
+        OAuthProvider::OpenAi | OAuthProvider::Claude => {
+            let refresh_token = api_keys
+                .get_setting(provider.credential_key())
+                .await?
+                .filter(|token| !token.trim().is_empty())
+                .ok_or("OAuth refresh token missing")?;
+            if provider == OAuthProvider::OpenAi {
+                refresh_openai_oauth_tokens(&client, &refresh_token, &api_keys).await?;
+            } else {
+                refresh_claude_oauth_tokens(&client, &refresh_token, &api_keys).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+struct ScheduledRefresh {
+    task_id: String,
+    expires_at_ms: i64,
+    run_at_ms: i64,
+}
+
+/// Keeps one pending refresh per connected provider
+pub struct TokenRefreshScheduler {
+    api_keys: ApiKeyManager,
+    refresh: RefreshFn,
+    on_failure: FailureFn,
+    scheduled: Mutex<HashMap<OAuthProvider, ScheduledRefresh>>,
+}
+
+impl TokenRefreshScheduler {
+    /// `refresh` renews a provider's stored token (normally `refresh_provider`);
+    /// `on_failure` is told when a refresh fails
+    pub fn new<R, Fut, E>(api_keys: ApiKeyManager, refresh: R, on_failure: E) -> Arc<Self>
+    where
+        R: Fn(ApiKeyManager, OAuthProvider) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+        E: Fn(OAuthRefreshFailedPayload) + Send + Sync + 'static,
+    {
+        let refresh: RefreshFn = Arc::new(
+            move |api_keys: ApiKeyManager,
+                  provider: OAuthProvider|
+                  -> BoxFuture<Result<(), String>> {
+                Box::pin(refresh(api_keys, provider))
+            },
+        );
+        Arc::new(Self {
+            api_keys,
+            refresh,
+            on_failure: Arc::new(on_failure),
+            scheduled: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Expiry of the provider's token in ms, or None when it is not connected
+    async fn token_expires_at_ms(&self, provider: OAuthProvider) -> Result<Option<i64>, String> {
+        let connected = self
+            .api_keys
+            .get_setting(provider.credential_key())
+            .await?
+            .is_some_and(|token| !token.trim().is_empty());
+        if !connected {
+            return Ok(None);
+        }
+        Ok(self
+            .api_keys
+            .get_setting(provider.expires_at_key())
+            .await?
+            .and_then(|value| value.trim().parse::<i64>().ok())
+            .map(|stored| provider.expires_at_ms(stored)))
+    }
+
+    /// When the pending refresh for `provider` will run, if one is scheduled
+    pub async fn scheduled_run_at(&self, provider: OAuthProvider) -> Option<i64> {
+        self.scheduled
+            .lock()
+            .await
+            .get(&provider)
+            .map(|scheduled| scheduled.run_at_ms)
+    }
+
+    /// (Re)schedule the refresh for `provider` from its stored expiry.
+    /// Returns the run time, or None when the provider is not connected.
+    pub fn schedule(
+        self: &Arc<Self>,
+        provider: OAuthProvider,
+    ) -> BoxFuture<Result<Option<i64>, String>> {
+        let this = self.clone();
+        Box::pin(async move {
+            let expires_at = this.token_expires_at_ms(provider).await?;
+            let previous = {
+                let mut scheduled = this.scheduled.lock().await;
+                if let (Some(existing), Some(expires_at)) = (scheduled.get(&provider), expires_at) {
+                    if existing.expires_at_ms == expires_at {
+                        return Ok(Some(existing.run_at_ms));
+                    }
+                }
+                scheduled.remove(&provider)
+            };
+            // Killing waits for a run that is firing, so the map must not be locked here
+            if let Some(previous) = previous {
+                let _ = background_tasks::kill_background_task(previous.task_id).await;
+            }
+            let Some(expires_at) = expires_at else {
+                return Ok(None);
+            };
+
+            let run_at = refresh_at_ms(expires_at, chrono::Utc::now().timestamp_millis());
+            let job_scheduler = this.clone();
+            let task_id = background_tasks::schedule_internal_job_at(
+                "internal:oauth_refresh",
+                run_at.max(0) as u64,
+                move || async move {
+                    job_scheduler.run_refresh(provider, expires_at).await;
+                    Ok(())
+                },
+            )
+            .await?;
+            let displaced = this.scheduled.lock().await.insert(
+                provider,
+                ScheduledRefresh {
+                    task_id,
+                    expires_at_ms: expires_at,
+                    run_at_ms: run_at,
+                },
+            );
+            if let Some(displaced) = displaced {
+                let _ = background_tasks::kill_background_task(displaced.task_id).await;
+            }
+            Ok(Some(run_at))
+        })
+    }
+
+    async fn run_refresh(self: &Arc<Self>, provider: OAuthProvider, expires_at: i64) {
+        if let Err(error) = (self.refresh)(self.api_keys.clone(), provider).await {
+            // The entry stays, so the sweep doesn't retry until the user reconnects
+            log::warn!("Proactive {:?} token refresh failed: {}", provider, error);
+            (self.on_failure)(OAuthRefreshFailedPayload { provider, error });
+            return;
+        }
+        log::info!("Proactively refreshed {:?} OAuth token", provider);
+
+        let refreshed = self.token_expires_at_ms(provider).await.ok().flatten();
+        if refreshed == Some(expires_at) {
+            log::warn!("{:?} token expiry unchanged after refresh", provider);
+            return;
+        }
+        // Forget this run before rescheduling; killing it from inside would deadlock
+        {
+            let mut scheduled = self.scheduled.lock().await;
+            if scheduled
+                .get(&provider)
+                .is_some_and(|existing| existing.expires_at_ms == expires_at)
+            {
+                scheduled.remove(&provider);
+            }
+        }
+        if let Err(e) = self.schedule(provider).await {
+            log::warn!(
+                "Failed to schedule next {:?} token refresh: {}",
+                provider,
+                e
+            );
+        }
+    }
+
+    /// Schedule every connected provider
+    pub async fn schedule_all(self: &Arc<Self>) {
+        for provider in OAuthProvider::ALL {
+            if let Err(e) = self.schedule(provider).await {
+                log::warn!("Failed to schedule {:?} token refresh: {}", provider, e);
+            }
+        }
+    }
+
+    /// Refresh named accounts, which have no pending refresh of their own
+    async fn refresh_named_accounts(&self) {
+        for provider_id in ["openai", "anthropic"] {
+            if let Err(e) =
+                oauth_accounts::refresh_expiring_accounts(&self.api_keys, provider_id).await
+            {
+                log::warn!("Failed to refresh {} OAuth accounts: {}", provider_id, e);
+            }
+        }
+    }
+
+    /// Schedule now, then re-read stored expiries every `SWEEP_INTERVAL_MS`
+    pub async fn start(self: Arc<Self>) -> Result<String, String> {
+        self.schedule_all().await;
+        self.refresh_named_accounts().await;
+        background_tasks::schedule_internal_job(
+            "internal:oauth_refresh_sweep",
+            SWEEP_INTERVAL_MS,
+            move || {
+                let this = self.clone();
+                async move {
+                    this.schedule_all().await;
+                    this.refresh_named_accounts().await;
+                    Ok(())
+                }
+            },
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+
+    async fn create_api_keys() -> (ApiKeyManager, TempDir) {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("token-refresh.db");
+        let db = Arc::new(Database::new(db_path.to_string_lossy().to_string()));
+        db.connect().await.unwrap();
+        db.execute(
+            "CREATE TABLE IF NOT EXISTS settings (key TEXT PRIMARY KEY, value TEXT, updated_at INTEGER)",
+            vec![],
+        )
+        .await
+        .unwrap();
+        (
+            ApiKeyManager::new(db, std::path::PathBuf::from("/tmp")),
+            dir,
+        )
+    }
+
+    fn now_secs() -> i64 {
+        chrono::Utc::now().timestamp()
+    }
+
+    async fn connect_openai(api_keys: &ApiKeyManager, expires_at_secs: i64) {
+        api_keys
+            .set_setting("openai_oauth_refresh_token", "refresh-token")
+            .await
+            .unwrap();
+        api_keys
+            .set_setting("openai_oauth_expires_at", &expires_at_secs.to_string())
+            .await
+            .unwrap();
+    }
+
+    /// Refresher that records when it ran and extends the expiry by an hour
+    fn recording_refresh(
+        refreshed_at: Arc<std::sync::Mutex<Vec<i64>>>,
+    ) -> impl Fn(ApiKeyManager, OAuthProvider) -> BoxFuture<Result<(), String>> + Send + Sync {
+        move |api_keys, _provider| {
+            let refreshed_at = refreshed_at.clone();
+            Box::pin(async move {
+                refreshed_at
+                    .lock()
+                    .unwrap()
+                    .push(chrono::Utc::now().timestamp_millis());
+                api_keys
+                    .set_setting("openai_oauth_expires_at", &(now_secs() + 3600).to_string())
+                    .await
+            })
+        }
+    }
+
+    #[test]
+    fn test_refresh_at_is_lead_before_expiry() {
+        let now = 1_000_000;
+        assert_eq!(
+            refresh_at_ms(now + 60 * 60 * 1000, now),
+            now + 55 * 60 * 1000
+        );
+        // Already inside the lead window: refresh right away
+        assert_eq!(refresh_at_ms(now + 1000, now), now);
+        assert_eq!(refresh_at_ms(now - 1000, now), now);
+    }
+
+    #[tokio::test]
+    async fn test_near_expiry_token_is_refreshed_before_expiry() {
+        let (api_keys, _dir) = create_api_keys().await;
+        // Expires one second after the refresh window opens
+        let expires_at_secs = now_secs() + REFRESH_LEAD_MS / 1000 + 1;
+        connect_openai(&api_keys, expires_at_secs).await;
+
+        let refreshed_at = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let failures = Arc::new(AtomicUsize::new(0));
+        let failure_counter = failures.clone();
+        let scheduler = TokenRefreshScheduler::new(
+            api_keys,
+            recording_refresh(refreshed_at.clone()),
+            move |_| {
+                failure_counter.fetch_add(1, Ordering::SeqCst);
+            },
+        );
+
+        let run_at = scheduler
+            .schedule(OAuthProvider::OpenAi)
+            .await
+            .unwrap()
+            .expect("refresh should be scheduled");
+        assert!(run_at < expires_at_secs * 1000);
+        assert!(scheduler
+            .schedule(OAuthProvider::Claude)
+            .await
+            .unwrap()
+            .is_none());
+
+        tokio::time::sleep(std::time::Duration::from_millis(2500)).await;
+
+        let refreshed_at = refreshed_at.lock().unwrap().clone();
+        assert_eq!(refreshed_at.len(), 1);
+        assert!(refreshed_at[0] >= run_at);
+        assert!(refreshed_at[0] < expires_at_secs * 1000);
+        assert_eq!(failures.load(Ordering::SeqCst), 0);
+
+        // The next refresh follows the new expiry
+        let next = scheduler
+            .scheduled_run_at(OAuthProvider::OpenAi)
+            .await
+            .expect("next refresh should be scheduled");
+        assert!(next > chrono::Utc::now().timestamp_millis() + 50 * 60 * 1000);
+    }
+
+    #[tokio::test]
+    async fn test_failed_refresh_reports_provider() {
+        let (api_keys, _dir) = create_api_keys().await;
+        connect_openai(&api_keys, now_secs()).await;
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let tx = std::sync::Mutex::new(tx);
+        let scheduler = TokenRefreshScheduler::new(
+            api_keys,
+            |_, _| async { Err("invalid_grant".to_string()) },
+            move |payload| {
+                let _ = tx.lock().unwrap().send(payload);
+            },
+        );
+
+        scheduler.schedule(OAuthProvider::OpenAi).await.unwrap();
+
+        let payload =
+            tokio::task::spawn_blocking(move || rx.recv_timeout(std::time::Duration::from_secs(2)))
+                .await
+                .unwrap()
+                .expect("failure should be reported");
+        assert_eq!(payload.provider, OAuthProvider::OpenAi);
+        assert_eq!(payload.error, "invalid_grant");
+        // Not retried by the sweep until the stored expiry changes
+        let run_at = scheduler.scheduled_run_at(OAuthProvider::OpenAi).await;
+        assert!(run_at.is_some());
+        assert_eq!(
+            scheduler.schedule(OAuthProvider::OpenAi).await.unwrap(),
+            run_at
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reschedule_replaces_pending_refresh() {
+        let (api_keys, _dir) = create_api_keys().await;
+        connect_openai(&api_keys, now_secs() + 3600).await;
+        let scheduler = TokenRefreshScheduler::new(
+            api_keys.clone(),
+            recording_refresh(Arc::new(std::sync::Mutex::new(Vec::new()))),
+            |_| {},
+        );
+
+        let first = scheduler.schedule(OAuthProvider::OpenAi).await.unwrap();
+        connect_openai(&api_keys, now_secs() + 7200).await;
+        let second = scheduler.schedule(OAuthProvider::OpenAi).await.unwrap();
+        assert!(second > first);
+
+        // Disconnecting cancels the pending refresh
+        api_keys
+            .set_setting("openai_oauth_refresh_token", "")
+            .await
+            .unwrap();
+        assert!(scheduler
+            .schedule(OAuthProvider::OpenAi)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(scheduler
+            .scheduled_run_at(OAuthProvider::OpenAi)
+            .await
+            .is_none());
+    }
+}
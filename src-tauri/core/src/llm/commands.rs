@@ -5,9 +5,9 @@ use crate::llm::ai_services::pricing_service::PricingService;
 use crate::llm::ai_services::prompt_enhancement_service::PromptEnhancementService;
 use crate::llm::ai_services::task_title_service::TaskTitleService;
 use crate::llm::ai_services::types::{
-    CalculateCostRequest, CalculateCostResult, CompletionContext, CompletionResult,
-    ContextCompactionRequest, ContextCompactionResult, GitMessageContext, GitMessageResult,
-    PromptEnhancementRequest, PromptEnhancementResult, TitleGenerationRequest,
+    CalculateCostRequest, CalculateCostResult, CompactionStreamEvent, CompletionContext,
+    CompletionResult, ContextCompactionRequest, ContextCompactionResult, GitMessageContext,
+    GitMessageResult, PromptEnhancementRequest, PromptEnhancementResult, TitleGenerationRequest,
     TitleGenerationResult,
 };
 use crate::llm::auth::api_key_manager::LlmState;
@@ -22,7 +22,8 @@ use crate::llm::types::{
     ImageGenerationRequest, ImageGenerationResponse, ModelsConfiguration, StreamResponse,
     StreamTextRequest, TranscriptionRequest, TranscriptionResponse,
 };
-use tauri::{Manager, State, Window};
+use crate::storage::{SessionCost, Storage};
+use tauri::{Emitter, Manager, State, Window};
 
 #[tauri::command]
 pub async fn llm_get_provider_configs(
@@ -242,13 +243,9 @@ pub async fn llm_download_image(
         request.url
     );
 
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(60))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
-
-    let response = client
+    let response = crate::network_proxy::shared_client()
         .get(&request.url)
+        .timeout(Duration::from_secs(60))
         .header("Accept", "image/*,*/*")
         .send()
         .await
@@ -294,6 +291,15 @@ pub fn llm_calculate_cost(request: CalculateCostRequest) -> Result<CalculateCost
     service.calculate_cost_request(request)
 }
 
+/// Cumulative token usage and spend recorded for a session
+#[tauri::command]
+pub async fn llm_get_session_cost(
+    session_id: String,
+    storage: State<'_, Storage>,
+) -> Result<SessionCost, String> {
+    storage.chat_history.get_session_cost(&session_id).await
+}
+
 /// Get AI code completion
 #[tauri::command]
 pub async fn llm_get_completion(
@@ -360,6 +366,38 @@ pub async fn llm_compact_context(
     service.compact_context(request, &api_keys, &registry).await
 }
 
+/// Compact conversation context, streaming the summary as
+/// `llm-compaction-{channel_id}` events
+#[tauri::command]
+pub async fn llm_compact_context_streaming(
+    window: Window,
+    request: ContextCompactionRequest,
+    channel_id: String,
+    state: State<'_, LlmState>,
+) -> Result<(), String> {
+    let (registry, api_keys) = {
+        let registry = state.registry.lock().await;
+        let api_keys = state.api_keys.lock().await;
+        (registry.clone(), api_keys.clone())
+    };
+
+    let event_name = format!("llm-compaction-{}", channel_id);
+    tauri::async_runtime::spawn(async move {
+        let service = ContextCompactionService::new();
+        let emit = |event: CompactionStreamEvent| {
+            let _ = window.emit(&event_name, &event);
+        };
+        if let Err(e) = service
+            .compact_context_streaming(request, &api_keys, &registry, emit)
+            .await
+        {
+            log::error!("[llm_compact_context_streaming] Compaction error: {}", e);
+        }
+    });
+
+    Ok(())
+}
+
 /// Enhance user prompt with context
 #[tauri::command]
 pub async fn llm_enhance_prompt(
@@ -185,7 +185,7 @@ impl GoogleImageClient {
 
         log::info!("[GoogleImageClient] Imagen URL: {}", url);
 
-        let client = reqwest::Client::builder()
+        let client = crate::network_proxy::client_builder()
             .timeout(Duration::from_secs(120))
             .build()
             .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
@@ -264,7 +264,7 @@ impl GoogleImageClient {
         let base_url = self.resolve_vertex_base_url();
         let url = format!("{}/models/{}:generateContent", base_url, model);
 
-        let client = reqwest::Client::builder()
+        let client = crate::network_proxy::client_builder()
             .timeout(Duration::from_secs(120))
             .build()
             .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
@@ -73,7 +73,7 @@ impl OpenAiImageClient {
             response_format: request.response_format,
         };
 
-        let client = reqwest::Client::builder()
+        let client = crate::network_proxy::client_builder()
             .timeout(Duration::from_secs(120))
             .build()
             .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
@@ -148,6 +148,8 @@ async fn setup_test_context() -> (
             provider_mappings: None,
             pricing: None,
             context_length: Some(65536),
+            capabilities: None,
+            context_window: None,
         },
     );
     models.insert(
@@ -163,6 +165,8 @@ async fn setup_test_context() -> (
             provider_mappings: None,
             pricing: None,
             context_length: None,
+            capabilities: None,
+            context_window: None,
         },
     );
     models.insert(
@@ -178,6 +182,8 @@ async fn setup_test_context() -> (
             provider_mappings: None,
             pricing: None,
             context_length: Some(8192),
+            capabilities: None,
+            context_window: None,
         },
     );
 
@@ -321,6 +327,8 @@ async fn resolve_image_generator_model_returns_error_when_no_image_model_availab
             provider_mappings: None,
             pricing: None,
             context_length: Some(65536),
+            capabilities: None,
+            context_window: None,
         },
     );
 
@@ -397,6 +405,8 @@ async fn resolve_image_generator_model_finds_volcengine_model() {
             provider_mappings: None,
             pricing: None,
             context_length: None,
+            capabilities: None,
+            context_window: None,
         },
     );
 
@@ -481,6 +491,8 @@ async fn resolve_image_generator_model_finds_alibaba_model() {
             provider_mappings: None,
             pricing: None,
             context_length: None,
+            capabilities: None,
+            context_window: None,
         },
     );
 
@@ -565,6 +577,8 @@ async fn resolve_image_generator_model_finds_zhipu_image_model() {
             provider_mappings: None,
             pricing: None,
             context_length: None,
+            capabilities: None,
+            context_window: None,
         },
     );
 
@@ -0,0 +1,301 @@
+// Image input limits
+// Downscales base64 image parts that exceed the configured dimension or size
+// cap before a request is built, so providers don't reject them opaquely.
+// Resized images are re-encoded as PNG, the type every protocol labels them with.
+
+use std::io::Cursor;
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use image::{DynamicImage, ImageFormat};
+
+use crate::llm::auth::api_key_manager::ApiKeyManager;
+use crate::llm::types::{ContentPart, Message, MessageContent};
+
+/// Settings key for the longest allowed image side, in pixels
+pub const MAX_IMAGE_DIMENSION_SETTING: &str = "max_image_dimension";
+/// Settings key for the largest allowed decoded image, in bytes
+pub const MAX_IMAGE_BYTES_SETTING: &str = "max_image_bytes";
+
+pub const DEFAULT_MAX_IMAGE_DIMENSION: u32 = 2048;
+pub const DEFAULT_MAX_IMAGE_BYTES: usize = 5 * 1024 * 1024;
+
+/// Each extra pass to get under the byte cap shrinks the sides by this factor
+const SHRINK_FACTOR: f64 = 0.75;
+const MIN_DIMENSION: u32 = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageLimits {
+    pub max_dimension: u32,
+    pub max_bytes: usize,
+}
+
+impl Default for ImageLimits {
+    fn default() -> Self {
+        Self {
+            max_dimension: DEFAULT_MAX_IMAGE_DIMENSION,
+            max_bytes: DEFAULT_MAX_IMAGE_BYTES,
+        }
+    }
+}
+
+impl ImageLimits {
+    /// Limits from settings; missing or invalid values use the defaults
+    pub async fn load(api_keys: &ApiKeyManager) -> Result<Self, String> {
+        let defaults = Self::default();
+        let max_dimension = api_keys
+            .get_setting(MAX_IMAGE_DIMENSION_SETTING)
+            .await?
+            .and_then(|value| value.trim().parse::<u32>().ok())
+            .filter(|value| *value >= MIN_DIMENSION)
+            .unwrap_or(defaults.max_dimension);
+        let max_bytes = api_keys
+            .get_setting(MAX_IMAGE_BYTES_SETTING)
+            .await?
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(defaults.max_bytes);
+        Ok(Self {
+            max_dimension,
+            max_bytes,
+        })
+    }
+}
+
+fn message_parts_mut(message: &mut Message) -> Option<&mut Vec<ContentPart>> {
+    match message {
+        Message::User {
+            content: MessageContent::Parts(parts),
+            ..
+        }
+        | Message::Assistant {
+            content: MessageContent::Parts(parts),
+            ..
+        }
+        | Message::Tool { content: parts, .. } => Some(parts),
+        _ => None,
+    }
+}
+
+/// Whether any message carries an image part
+pub fn has_images(messages: &[Message]) -> bool {
+    messages.iter().any(|message| {
+        let parts = match message {
+            Message::User {
+                content: MessageContent::Parts(parts),
+                ..
+            }
+            | Message::Assistant {
+                content: MessageContent::Parts(parts),
+                ..
+            }
+            | Message::Tool { content: parts, .. } => parts,
+            _ => return false,
+        };
+        parts
+            .iter()
+            .any(|part| matches!(part, ContentPart::Image { .. }))
+    })
+}
+
+/// Downscale every image part over `limits`; returns how many were resized
+pub fn fit_images(messages: &mut [Message], limits: ImageLimits) -> usize {
+    let mut resized = 0;
+    for parts in messages.iter_mut().filter_map(message_parts_mut) {
+        for part in parts.iter_mut() {
+            let ContentPart::Image { image } = part else {
+                continue;
+            };
+            match fit_image(image, limits) {
+                Ok(Some(fitted)) => {
+                    *image = fitted;
+                    resized += 1;
+                }
+                Ok(None) => {}
+                Err(e) => log::warn!("Sending image unchanged: {}", e),
+            }
+        }
+    }
+    resized
+}
+
+/// [`fit_images`] on the blocking pool: decoding and re-encoding large images
+/// would otherwise stall the async worker
+pub async fn fit_images_blocking(
+    messages: &mut Vec<Message>,
+    limits: ImageLimits,
+) -> Result<usize, String> {
+    let mut owned = std::mem::take(messages);
+    let (owned, resized) = tokio::task::spawn_blocking(move || {
+        let resized = fit_images(&mut owned, limits);
+        (owned, resized)
+    })
+    .await
+    .map_err(|e| format!("Failed to spawn blocking task: {}", e))?;
+    *messages = owned;
+    Ok(resized)
+}
+
+/// Downscale a base64 image to fit `limits`, preserving its aspect ratio.
+/// Returns `None` when it already fits.
+pub fn fit_image(base64_image: &str, limits: ImageLimits) -> Result<Option<String>, String> {
+    let bytes = STANDARD
+        .decode(base64_image.trim())
+        .map_err(|e| format!("Invalid base64 image: {}", e))?;
+    let (width, height) = image::ImageReader::new(Cursor::new(&bytes))
+        .with_guessed_format()
+        .map_err(|e| format!("Unreadable image: {}", e))?
+        .into_dimensions()
+        .map_err(|e| format!("Unsupported image format: {}", e))?;
+    if width.max(height) <= limits.max_dimension && bytes.len() <= limits.max_bytes {
+        return Ok(None);
+    }
+
+    let original =
+        image::load_from_memory(&bytes).map_err(|e| format!("Failed to decode image: {}", e))?;
+    let mut max_side = limits.max_dimension.min(width.max(height));
+    loop {
+        let resized = if width.max(height) > max_side {
+            original.thumbnail(max_side, max_side)
+        } else {
+            original.clone()
+        };
+        let encoded = encode_png(&resized)?;
+        if encoded.len() <= limits.max_bytes || max_side <= MIN_DIMENSION {
+            log::info!(
+                "Resized image from {}x{} ({} bytes) to {}x{} ({} bytes)",
+                width,
+                height,
+                bytes.len(),
+                resized.width(),
+                resized.height(),
+                encoded.len()
+            );
+            return Ok(Some(STANDARD.encode(encoded)));
+        }
+        max_side = ((max_side as f64 * SHRINK_FACTOR) as u32).max(MIN_DIMENSION);
+    }
+}
+
+fn encode_png(image: &DynamicImage) -> Result<Vec<u8>, String> {
+    let mut out = Cursor::new(Vec::new());
+    image
+        .write_to(&mut out, ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode image: {}", e))?;
+    Ok(out.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png_base64(width: u32, height: u32, noisy: bool) -> String {
+        let image = DynamicImage::ImageRgb8(image::RgbImage::from_fn(width, height, |x, y| {
+            if noisy {
+                // Hashed pixels, so PNG can't compress them much
+                let mut h = ((x as u64) << 32) | y as u64;
+                h = (h ^ (h >> 33)).wrapping_mul(0xff51_afd7_ed55_8ccd);
+                h = (h ^ (h >> 33)).wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+                h ^= h >> 33;
+                image::Rgb([h as u8, (h >> 8) as u8, (h >> 16) as u8])
+            } else {
+                image::Rgb([(x % 256) as u8, (y % 256) as u8, 90])
+            }
+        }));
+        STANDARD.encode(encode_png(&image).unwrap())
+    }
+
+    fn dimensions(base64_image: &str) -> (u32, u32) {
+        let image = image::load_from_memory(&STANDARD.decode(base64_image).unwrap()).unwrap();
+        (image.width(), image.height())
+    }
+
+    fn user_image(image: String) -> Message {
+        Message::User {
+            content: MessageContent::Parts(vec![
+                ContentPart::Text {
+                    text: "what is this?".to_string(),
+                },
+                ContentPart::Image { image },
+            ]),
+            provider_options: None,
+        }
+    }
+
+    #[test]
+    fn test_small_image_is_left_alone() {
+        let image = png_base64(100, 50, false);
+        assert_eq!(fit_image(&image, ImageLimits::default()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_oversized_image_is_scaled_under_dimension_cap() {
+        let limits = ImageLimits {
+            max_dimension: 1024,
+            max_bytes: DEFAULT_MAX_IMAGE_BYTES,
+        };
+        let mut messages = vec![user_image(png_base64(3000, 1500, false))];
+
+        assert_eq!(fit_images(&mut messages, limits), 1);
+
+        let Message::User {
+            content: MessageContent::Parts(parts),
+            ..
+        } = &messages[0]
+        else {
+            panic!("expected user parts");
+        };
+        let ContentPart::Image { image } = &parts[1] else {
+            panic!("expected image part");
+        };
+        assert_eq!(dimensions(image), (1024, 512));
+    }
+
+    #[test]
+    fn test_oversized_image_is_shrunk_under_byte_cap() {
+        let original = png_base64(800, 400, true);
+        let limits = ImageLimits {
+            max_dimension: DEFAULT_MAX_IMAGE_DIMENSION,
+            max_bytes: 200_000,
+        };
+        assert!(STANDARD.decode(&original).unwrap().len() > limits.max_bytes);
+
+        let fitted = fit_image(&original, limits).unwrap().expect("resized");
+
+        assert!(STANDARD.decode(&fitted).unwrap().len() <= limits.max_bytes);
+        let (width, height) = dimensions(&fitted);
+        assert!(width < 800);
+        // Aspect ratio kept, up to rounding
+        assert!((width as i64 - 2 * height as i64).abs() <= 2);
+    }
+
+    #[tokio::test]
+    async fn test_blocking_fit_keeps_message_order() {
+        let limits = ImageLimits {
+            max_dimension: 100,
+            max_bytes: DEFAULT_MAX_IMAGE_BYTES,
+        };
+        let mut messages = vec![
+            user_image(png_base64(400, 200, false)),
+            user_image(png_base64(50, 50, false)),
+        ];
+
+        assert_eq!(fit_images_blocking(&mut messages, limits).await.unwrap(), 1);
+        let sizes: Vec<_> = messages
+            .iter_mut()
+            .filter_map(message_parts_mut)
+            .flatten()
+            .filter_map(|part| match part {
+                ContentPart::Image { image } => Some(dimensions(image)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(sizes, vec![(100, 50), (50, 50)]);
+    }
+
+    #[test]
+    fn test_invalid_image_is_sent_unchanged() {
+        let mut messages = vec![user_image("not base64!".to_string())];
+        assert_eq!(fit_images(&mut messages, ImageLimits::default()), 0);
+        assert!(has_images(&messages));
+    }
+}
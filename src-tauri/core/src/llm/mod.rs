@@ -2,11 +2,13 @@ pub mod ai_services;
 pub mod auth;
 pub mod commands;
 pub mod image_generation;
+pub mod image_input;
 pub mod models;
 pub mod protocols;
 pub mod providers;
 pub mod streaming;
 pub mod testing;
+pub mod token_estimation;
 pub mod tracing;
 pub mod transcription;
 pub mod types;
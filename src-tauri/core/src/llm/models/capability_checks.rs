@@ -0,0 +1,288 @@
+// Request checks against model capabilities
+// Catches requests a model can't serve before they reach the provider,
+// where they would fail opaquely or be silently ignored.
+
+use crate::llm::auth::api_key_manager::ApiKeyManager;
+use crate::llm::image_input;
+use crate::llm::types::{ModelCapabilities, StreamTextRequest};
+
+/// Settings key choosing what happens to tools sent to a model without tool calling
+pub const UNSUPPORTED_TOOLS_SETTING: &str = "unsupported_tools_behavior";
+
+/// What to do when a request carries tools the model can't call
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnsupportedToolsBehavior {
+    /// Drop the tools, log a warning and send the request anyway
+    #[default]
+    Strip,
+    /// Fail the request
+    Error,
+}
+
+impl UnsupportedToolsBehavior {
+    /// `"error"` fails the request; anything else, including no setting, strips
+    pub fn from_setting(value: Option<&str>) -> Self {
+        match value.map(str::trim) {
+            Some(value) if value.eq_ignore_ascii_case("error") => Self::Error,
+            _ => Self::Strip,
+        }
+    }
+
+    pub async fn load(api_keys: &ApiKeyManager) -> Result<Self, String> {
+        Ok(Self::from_setting(
+            api_keys
+                .get_setting(UNSUPPORTED_TOOLS_SETTING)
+                .await?
+                .as_deref(),
+        ))
+    }
+}
+
+/// Apply `behavior` when `request` has tools but the model can't call them
+pub fn enforce_tool_support(
+    request: &mut StreamTextRequest,
+    model_key: &str,
+    capabilities: &ModelCapabilities,
+    behavior: UnsupportedToolsBehavior,
+) -> Result<(), String> {
+    let tool_count = request.tools.as_ref().map_or(0, |tools| tools.len());
+    if capabilities.tools || tool_count == 0 {
+        return Ok(());
+    }
+    match behavior {
+        UnsupportedToolsBehavior::Strip => {
+            log::warn!(
+                "Model {} does not support tool calling, dropping {} tools from the request",
+                model_key,
+                tool_count
+            );
+            request.tools = None;
+            Ok(())
+        }
+        UnsupportedToolsBehavior::Error => Err(format!(
+            "Model {} does not support tool calling, but the request includes {} tools",
+            model_key, tool_count
+        )),
+    }
+}
+
+/// Fail when `request` carries images but the model can't see them
+pub fn enforce_vision_support(
+    request: &StreamTextRequest,
+    model_key: &str,
+    capabilities: &ModelCapabilities,
+) -> Result<(), String> {
+    if capabilities.vision || !image_input::has_images(&request.messages) {
+        return Ok(());
+    }
+    Err(format!(
+        "Model {} does not support image input; remove the images or pick a vision model",
+        model_key
+    ))
+}
+
+/// Check `request` against the capabilities of `model_key` and shrink
+/// oversized images. Models missing from the models config are only resized.
+pub async fn check_request(
+    api_keys: &ApiKeyManager,
+    model_key: &str,
+    request: &mut StreamTextRequest,
+) -> Result<(), String> {
+    let models = api_keys.load_models_config().await?;
+    let capabilities = models
+        .models
+        .get(model_key)
+        .map(|model| model.resolved_capabilities());
+    if let Some(capabilities) = &capabilities {
+        enforce_vision_support(request, model_key, capabilities)?;
+    }
+    if image_input::has_images(&request.messages) {
+        let limits = image_input::ImageLimits::load(api_keys).await?;
+        image_input::fit_images_blocking(&mut request.messages, limits).await?;
+    }
+    let Some(capabilities) = capabilities else {
+        return Ok(());
+    };
+    if request
+        .tools
+        .as_ref()
+        .is_some_and(|tools| !tools.is_empty())
+    {
+        let behavior = UnsupportedToolsBehavior::load(api_keys).await?;
+        enforce_tool_support(request, model_key, &capabilities, behavior)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn request_with_tools() -> StreamTextRequest {
+        serde_json::from_value(serde_json::json!({
+            "model": "whisper-1@openai",
+            "messages": [],
+            "tools": [{
+                "type": "function",
+                "name": "readFile",
+                "description": null,
+                "parameters": { "type": "object" },
+                "strict": false
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn no_tools() -> ModelCapabilities {
+        ModelCapabilities {
+            audio: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_behavior_from_setting() {
+        assert_eq!(
+            UnsupportedToolsBehavior::from_setting(None),
+            UnsupportedToolsBehavior::Strip
+        );
+        assert_eq!(
+            UnsupportedToolsBehavior::from_setting(Some(" Error ")),
+            UnsupportedToolsBehavior::Error
+        );
+        assert_eq!(
+            UnsupportedToolsBehavior::from_setting(Some("bogus")),
+            UnsupportedToolsBehavior::Strip
+        );
+    }
+
+    #[test]
+    fn test_strips_tools_for_tool_less_model() {
+        let mut request = request_with_tools();
+        enforce_tool_support(
+            &mut request,
+            "whisper-1",
+            &no_tools(),
+            UnsupportedToolsBehavior::Strip,
+        )
+        .unwrap();
+        assert!(request.tools.is_none());
+    }
+
+    #[test]
+    fn test_errors_for_tool_less_model() {
+        let mut request = request_with_tools();
+        let error = enforce_tool_support(
+            &mut request,
+            "whisper-1",
+            &no_tools(),
+            UnsupportedToolsBehavior::Error,
+        )
+        .unwrap_err();
+        assert!(error.contains("does not support tool calling"));
+        assert_eq!(request.tools.as_ref().map(Vec::len), Some(1));
+    }
+
+    #[test]
+    fn test_keeps_tools_for_tool_capable_model() {
+        let mut request = request_with_tools();
+        let capabilities = ModelCapabilities {
+            tools: true,
+            ..Default::default()
+        };
+        enforce_tool_support(
+            &mut request,
+            "gpt-5.5",
+            &capabilities,
+            UnsupportedToolsBehavior::Error,
+        )
+        .unwrap();
+        assert_eq!(request.tools.as_ref().map(Vec::len), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_check_request_uses_configured_behavior() {
+        let dir = TempDir::new().expect("temp dir");
+        let db_path = dir.path().join("capability-checks.db");
+        let db = Arc::new(Database::new(db_path.to_string_lossy().to_string()));
+        db.connect().await.expect("db connect");
+        db.execute(
+            "CREATE TABLE IF NOT EXISTS settings (key TEXT PRIMARY KEY, value TEXT, updated_at INTEGER)",
+            vec![],
+        )
+        .await
+        .expect("create settings");
+        let api_keys = ApiKeyManager::new(db, std::path::PathBuf::from("/tmp"));
+
+        // Bundled config: whisper-1 has no tool calling, gpt-5.5 does
+        let mut request = request_with_tools();
+        check_request(&api_keys, "whisper-1", &mut request)
+            .await
+            .unwrap();
+        assert!(request.tools.is_none());
+
+        api_keys
+            .set_setting(UNSUPPORTED_TOOLS_SETTING, "error")
+            .await
+            .unwrap();
+        let mut request = request_with_tools();
+        assert!(check_request(&api_keys, "whisper-1", &mut request)
+            .await
+            .is_err());
+
+        let mut request = request_with_tools();
+        check_request(&api_keys, "gpt-5.5", &mut request)
+            .await
+            .unwrap();
+        assert!(request.tools.is_some());
+
+        // Models outside the config are not checked
+        let mut request = request_with_tools();
+        check_request(&api_keys, "unknown-model", &mut request)
+            .await
+            .unwrap();
+        assert!(request.tools.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_check_request_rejects_images_for_non_vision_model() {
+        let dir = TempDir::new().expect("temp dir");
+        let db_path = dir.path().join("capability-checks.db");
+        let db = Arc::new(Database::new(db_path.to_string_lossy().to_string()));
+        db.connect().await.expect("db connect");
+        db.execute(
+            "CREATE TABLE IF NOT EXISTS settings (key TEXT PRIMARY KEY, value TEXT, updated_at INTEGER)",
+            vec![],
+        )
+        .await
+        .expect("create settings");
+        let api_keys = ApiKeyManager::new(db, std::path::PathBuf::from("/tmp"));
+
+        let request_with_image = || -> StreamTextRequest {
+            serde_json::from_value(serde_json::json!({
+                "model": "deepseek-v4-pro@deepseek",
+                "messages": [{
+                    "role": "user",
+                    "content": [
+                        { "type": "text", "text": "what is this?" },
+                        { "type": "image", "image": "aGVsbG8=" }
+                    ]
+                }]
+            }))
+            .unwrap()
+        };
+
+        // Bundled config: deepseek-v4-pro has no vision, gpt-5.5 does
+        let error = check_request(&api_keys, "deepseek-v4-pro", &mut request_with_image())
+            .await
+            .unwrap_err();
+        assert!(error.contains("does not support image input"));
+
+        check_request(&api_keys, "gpt-5.5", &mut request_with_image())
+            .await
+            .unwrap();
+    }
+}
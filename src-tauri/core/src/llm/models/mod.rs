@@ -1,2 +1,3 @@
+pub mod capability_checks;
 pub mod model_registry;
 pub mod model_sync;
@@ -240,7 +240,7 @@ impl ModelRegistry {
         ))
     }
 
-    fn provider_available(
+    pub(crate) fn provider_available(
         provider_id: &str,
         api_keys: &HashMap<String, String>,
         registry: &ProviderRegistry,
@@ -400,6 +400,8 @@ mod tests {
                     cache_creation: None,
                 }),
                 context_length: None,
+                capabilities: None,
+                context_window: None,
             },
         );
         ModelsConfiguration {
@@ -453,6 +455,8 @@ mod tests {
                 cache_creation: None,
             }),
             context_length: None,
+            capabilities: None,
+            context_window: None,
         };
         let custom_config = ModelsConfiguration {
             version: "custom".to_string(),
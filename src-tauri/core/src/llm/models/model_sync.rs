@@ -10,6 +10,7 @@ use tauri::{AppHandle, Emitter};
 use tokio::sync::Semaphore;
 
 const CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 const VERSION_ENDPOINT: &str = "/api/models/version";
 const CONFIGS_ENDPOINT: &str = "/api/models/configs";
 const MODELS_CACHE_FILENAME: &str = "models-cache.json";
@@ -50,6 +51,7 @@ async fn fetch_remote_version(client: &Client) -> Result<ModelVersionResponse, S
     let url = build_api_url(VERSION_ENDPOINT);
     let response = client
         .get(url)
+        .timeout(REQUEST_TIMEOUT)
         .send()
         .await
         .map_err(|e| format!("Failed to fetch model version: {}", e))?;
@@ -76,6 +78,7 @@ async fn fetch_remote_config(client: &Client) -> Result<ModelsConfiguration, Str
     let url = build_api_url(CONFIGS_ENDPOINT);
     let response = client
         .get(url)
+        .timeout(REQUEST_TIMEOUT)
         .send()
         .await
         .map_err(|e| format!("Failed to fetch model configs: {}", e))?;
@@ -148,7 +151,7 @@ pub async fn check_for_updates(
         }
     };
 
-    let client = Client::new();
+    let client = crate::network_proxy::shared_json_client();
 
     let local_version = match api_keys.load_models_config().await {
         Ok(config) => Some(config.version),
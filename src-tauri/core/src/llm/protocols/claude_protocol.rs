@@ -1,11 +1,53 @@
-use crate::llm::protocols::{LlmProtocol, ProtocolStreamState, ToolCallAccum};
-use crate::llm::types::{ContentPart, Message, MessageContent, StreamEvent, ToolDefinition};
+use crate::llm::protocols::message_normalizer::merge_consecutive_roles;
+use crate::llm::protocols::{cap_stop_sequences, LlmProtocol, ProtocolStreamState, ToolCallAccum};
+use crate::llm::types::{
+    ContentPart, Message, MessageContent, ResponseFormat, ResponseFormatType, StreamEvent,
+    ToolDefinition,
+};
 use serde_json::{json, Value};
 use std::collections::HashMap;
 
 pub struct ClaudeProtocol;
 
+/// Tool the model is forced to call when JSON output is requested;
+/// its input is the response
+pub const JSON_RESPONSE_TOOL: &str = "json_response";
+
+/// Upper bound on `stop_sequences`; Anthropic allows far more than OpenAI
+const MAX_STOP_SEQUENCES: usize = 16;
+
 impl ClaudeProtocol {
+    /// Anthropic has no JSON mode, so force a tool call whose input schema is
+    /// the requested format
+    pub fn apply_response_format(&self, body: &mut Value, format: &ResponseFormat) {
+        let schema = match (&format.format_type, &format.schema) {
+            (ResponseFormatType::JsonSchema, Some(schema)) => schema.clone(),
+            _ => json!({ "type": "object" }),
+        };
+        let tool = json!({
+            "name": JSON_RESPONSE_TOOL,
+            "description": "Respond with the final answer as JSON matching the input schema",
+            "input_schema": schema
+        });
+        match body.get_mut("tools").and_then(Value::as_array_mut) {
+            Some(tools) => tools.push(tool),
+            None => body["tools"] = json!([tool]),
+        }
+        body["tool_choice"] = json!({ "type": "tool", "name": JSON_RESPONSE_TOOL });
+        // Extended thinking can't be combined with a forced tool choice
+        if let Some(obj) = body.as_object_mut() {
+            obj.remove("thinking");
+        }
+    }
+
+    /// Anthropic's name for the OpenAI `stop` parameter is `stop_sequences`
+    pub fn apply_stop_sequences(&self, body: &mut Value, stop: &[String]) {
+        let stop = cap_stop_sequences(stop, MAX_STOP_SEQUENCES, "Anthropic");
+        if !stop.is_empty() {
+            body["stop_sequences"] = json!(stop);
+        }
+    }
+
     #[allow(dead_code)]
     fn build_messages(&self, messages: &[Message]) -> Vec<Value> {
         let mut result = Vec::new();
@@ -166,7 +208,8 @@ impl LlmProtocol for ClaudeProtocol {
 
         let mut body = json!({
             "model": model,
-            "messages": self.build_messages(messages),
+            // Anthropic requires user and assistant turns to alternate
+            "messages": merge_consecutive_roles(self.build_messages(messages)),
             "stream": true,
             "max_tokens": max_tokens.unwrap_or(1024)
         });
@@ -612,6 +655,61 @@ mod tests {
         assert_eq!(body.get("max_output_tokens"), Some(&json!(128)));
     }
 
+    #[test]
+    fn build_request_merges_consecutive_user_messages() {
+        let protocol = ClaudeProtocol;
+        let messages = vec![
+            Message::User {
+                content: MessageContent::Text("from the gateway".to_string()),
+                provider_options: None,
+            },
+            Message::User {
+                content: MessageContent::Text("from the app".to_string()),
+                provider_options: None,
+            },
+        ];
+
+        let body = LlmProtocol::build_request(
+            &protocol, "claude-3", &messages, None, None, None, None, None, None, None,
+        )
+        .expect("build request");
+
+        assert_eq!(
+            body["messages"],
+            json!([{
+                "role": "user",
+                "content": [{ "type": "text", "text": "from the gateway\n\nfrom the app" }]
+            }])
+        );
+    }
+
+    #[test]
+    fn response_format_forces_json_tool() {
+        let protocol = ClaudeProtocol;
+        let mut body = json!({
+            "model": "claude-3",
+            "messages": [],
+            "thinking": { "type": "enabled" }
+        });
+        let schema = json!({ "type": "object", "properties": { "title": { "type": "string" } } });
+
+        protocol.apply_response_format(
+            &mut body,
+            &ResponseFormat {
+                format_type: ResponseFormatType::JsonSchema,
+                schema: Some(schema.clone()),
+            },
+        );
+
+        assert_eq!(body["tools"][0]["name"], JSON_RESPONSE_TOOL);
+        assert_eq!(body["tools"][0]["input_schema"], schema);
+        assert_eq!(
+            body["tool_choice"],
+            json!({ "type": "tool", "name": JSON_RESPONSE_TOOL })
+        );
+        assert!(body.get("thinking").is_none());
+    }
+
     #[test]
     fn parse_stream_emits_reasoning_signature_delta() {
         let protocol = ClaudeProtocol;
@@ -0,0 +1,204 @@
+// Message sequence normalization
+// Some providers reject two messages with the same role in a row, which happens
+// when gateway input and app input interleave. Protocols that need it opt in by
+// running their built messages through `merge_consecutive_roles`.
+use serde_json::{json, Map, Value};
+
+/// Roles whose messages answer one specific call and must stay separate
+const UNMERGEABLE_ROLES: &[&str] = &["tool"];
+
+/// Merge adjacent messages that share a role into one.
+/// Content is appended in order, so tool calls and results keep their sequence;
+/// touching text parts are joined with a blank line.
+pub fn merge_consecutive_roles(messages: Vec<Value>) -> Vec<Value> {
+    let mut result: Vec<Value> = Vec::with_capacity(messages.len());
+    for message in messages {
+        let role = message.get("role").and_then(Value::as_str);
+        let merge = role.is_some_and(|role| !UNMERGEABLE_ROLES.contains(&role))
+            && result
+                .last()
+                .and_then(|previous| previous.get("role"))
+                .and_then(Value::as_str)
+                == role;
+        match result.last_mut() {
+            Some(previous) if merge => merge_into(previous, message),
+            _ => result.push(message),
+        }
+    }
+    result
+}
+
+fn merge_into(previous: &mut Value, next: Value) {
+    let (Some(previous), Value::Object(next)) = (previous.as_object_mut(), next) else {
+        return;
+    };
+    for (key, value) in next {
+        match key.as_str() {
+            "role" => {}
+            "content" => {
+                let merged = merge_content(previous.remove("content"), value);
+                previous.insert(key, merged);
+            }
+            _ => match (previous.get_mut(&key), value) {
+                // OpenAI assistant tool calls
+                (Some(Value::Array(existing)), Value::Array(more)) => existing.extend(more),
+                (Some(_), _) => {}
+                (None, value) => {
+                    previous.insert(key, value);
+                }
+            },
+        }
+    }
+}
+
+fn merge_content(previous: Option<Value>, next: Value) -> Value {
+    match (previous, next) {
+        (None | Some(Value::Null), next) => next,
+        (Some(previous), Value::Null) => previous,
+        (Some(Value::String(previous)), Value::String(next)) => {
+            Value::String(join_text(&previous, &next))
+        }
+        (Some(previous), next) => {
+            let mut parts = into_parts(previous);
+            for part in into_parts(next) {
+                let joined = match (parts.last().and_then(plain_text), plain_text(&part)) {
+                    (Some(last), Some(text)) => Some(join_text(last, text)),
+                    _ => None,
+                };
+                match (joined, parts.last_mut()) {
+                    (Some(joined), Some(last)) => last["text"] = Value::String(joined),
+                    _ => parts.push(part),
+                }
+            }
+            Value::Array(parts)
+        }
+    }
+}
+
+fn into_parts(content: Value) -> Vec<Value> {
+    match content {
+        Value::Array(parts) => parts,
+        Value::String(text) => vec![json!({ "type": "text", "text": text })],
+        other => vec![other],
+    }
+}
+
+/// Text of a part that carries nothing but text, e.g. no cache control
+fn plain_text(part: &Value) -> Option<&str> {
+    let part: &Map<String, Value> = part.as_object()?;
+    if part.len() != 2 || part.get("type").and_then(Value::as_str) != Some("text") {
+        return None;
+    }
+    part.get("text").and_then(Value::as_str)
+}
+
+fn join_text(previous: &str, next: &str) -> String {
+    match (previous.is_empty(), next.is_empty()) {
+        (true, _) => next.to_string(),
+        (_, true) => previous.to_string(),
+        _ => format!("{}\n\n{}", previous, next),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_two_consecutive_user_messages() {
+        let merged = merge_consecutive_roles(vec![
+            json!({ "role": "user", "content": "from the gateway" }),
+            json!({ "role": "user", "content": "from the app" }),
+            json!({ "role": "assistant", "content": "ok" }),
+        ]);
+
+        assert_eq!(
+            merged,
+            vec![
+                json!({ "role": "user", "content": "from the gateway\n\nfrom the app" }),
+                json!({ "role": "assistant", "content": "ok" }),
+            ]
+        );
+    }
+
+    #[test]
+    fn merges_part_arrays_and_joins_touching_text() {
+        let merged = merge_consecutive_roles(vec![
+            json!({ "role": "user", "content": [
+                { "type": "image", "source": { "data": "abc" } },
+                { "type": "text", "text": "first" }
+            ] }),
+            json!({ "role": "user", "content": "second" }),
+        ]);
+
+        assert_eq!(
+            merged,
+            vec![json!({ "role": "user", "content": [
+                { "type": "image", "source": { "data": "abc" } },
+                { "type": "text", "text": "first\n\nsecond" }
+            ] })]
+        );
+    }
+
+    #[test]
+    fn keeps_claude_tool_use_and_result_order() {
+        let merged = merge_consecutive_roles(vec![
+            json!({ "role": "user", "content": [{ "type": "text", "text": "read a.rs" }] }),
+            json!({ "role": "assistant", "content": [
+                { "type": "text", "text": "reading" },
+                { "type": "tool_use", "id": "call_1", "name": "readFile", "input": {} }
+            ] }),
+            json!({ "role": "assistant", "content": [
+                { "type": "tool_use", "id": "call_2", "name": "readFile", "input": {} }
+            ] }),
+            json!({ "role": "user", "content": [
+                { "type": "tool_result", "tool_use_id": "call_1", "content": "a" }
+            ] }),
+            json!({ "role": "user", "content": [
+                { "type": "tool_result", "tool_use_id": "call_2", "content": "b" }
+            ] }),
+            json!({ "role": "user", "content": [{ "type": "text", "text": "thanks" }] }),
+        ]);
+
+        assert_eq!(merged.len(), 3);
+        assert_eq!(
+            merged[1]["content"],
+            json!([
+                { "type": "text", "text": "reading" },
+                { "type": "tool_use", "id": "call_1", "name": "readFile", "input": {} },
+                { "type": "tool_use", "id": "call_2", "name": "readFile", "input": {} }
+            ])
+        );
+        assert_eq!(
+            merged[2]["content"],
+            json!([
+                { "type": "tool_result", "tool_use_id": "call_1", "content": "a" },
+                { "type": "tool_result", "tool_use_id": "call_2", "content": "b" },
+                { "type": "text", "text": "thanks" }
+            ])
+        );
+    }
+
+    #[test]
+    fn concatenates_openai_tool_calls_and_keeps_tool_messages_apart() {
+        let merged = merge_consecutive_roles(vec![
+            json!({ "role": "assistant", "content": null, "tool_calls": [{ "id": "call_1" }] }),
+            json!({ "role": "assistant", "content": "more", "tool_calls": [{ "id": "call_2" }] }),
+            json!({ "role": "tool", "tool_call_id": "call_1", "content": "a" }),
+            json!({ "role": "tool", "tool_call_id": "call_2", "content": "b" }),
+        ]);
+
+        assert_eq!(
+            merged,
+            vec![
+                json!({
+                    "role": "assistant",
+                    "content": "more",
+                    "tool_calls": [{ "id": "call_1" }, { "id": "call_2" }]
+                }),
+                json!({ "role": "tool", "tool_call_id": "call_1", "content": "a" }),
+                json!({ "role": "tool", "tool_call_id": "call_2", "content": "b" }),
+            ]
+        );
+    }
+}
@@ -6,6 +6,7 @@ use std::collections::{HashMap, HashSet};
 
 // Re-export new modular traits
 pub mod header_builder;
+pub mod message_normalizer;
 pub mod request_builder;
 pub mod stream_parser;
 
@@ -143,6 +144,20 @@ pub(crate) fn parse_openai_usage(usage: &Value) -> ParsedUsage {
     }
 }
 
+/// The non-empty stop sequences, truncated to the provider's `max`
+pub(crate) fn cap_stop_sequences(stop: &[String], max: usize, provider: &str) -> Vec<String> {
+    let stop: Vec<String> = stop.iter().filter(|s| !s.is_empty()).cloned().collect();
+    if stop.len() > max {
+        log::warn!(
+            "{} accepts at most {} stop sequences, dropping {}",
+            provider,
+            max,
+            stop.len() - max
+        );
+    }
+    stop.into_iter().take(max).collect()
+}
+
 fn usage_i32(usage: &Value, keys: &[&str]) -> Option<i32> {
     keys.iter()
         .find_map(|key| usage.get(*key).and_then(json_value_to_i32))
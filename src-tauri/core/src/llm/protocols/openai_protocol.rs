@@ -1,17 +1,36 @@
 use crate::llm::protocols::{
+    cap_stop_sequences,
     header_builder::{HeaderBuildContext, ProtocolHeaderBuilder},
     parse_openai_usage,
     request_builder::{ProtocolRequestBuilder, RequestBuildContext},
     stream_parser::{self, ProtocolStreamParser, StreamParseContext, StreamParseState},
     LlmProtocol, ProtocolStreamState, ToolCallAccum,
 };
-use crate::llm::types::{ContentPart, Message, MessageContent, StreamEvent, ToolDefinition};
+use crate::llm::types::{
+    ContentPart, Message, MessageContent, ResponseFormat, ResponseFormatType, StreamEvent,
+    ToolDefinition,
+};
 use serde_json::{json, Value};
 use std::collections::HashMap;
 
 pub struct OpenAiProtocol;
 
+/// Chat Completions rejects more than four stop sequences
+const MAX_STOP_SEQUENCES: usize = 4;
+
 impl OpenAiProtocol {
+    /// Chat Completions `response_format`; a `json_schema` without a schema
+    /// falls back to plain JSON mode
+    fn build_response_format(format: &ResponseFormat) -> Value {
+        match (&format.format_type, &format.schema) {
+            (ResponseFormatType::JsonSchema, Some(schema)) => json!({
+                "type": "json_schema",
+                "json_schema": { "name": "response", "schema": schema }
+            }),
+            _ => json!({ "type": "json_object" }),
+        }
+    }
+
     fn build_messages(&self, messages: &[Message]) -> Vec<Value> {
         let mut result = Vec::new();
 
@@ -422,6 +441,18 @@ impl ProtocolRequestBuilder for OpenAiProtocol {
         if let Some(top_k) = ctx.top_k {
             body["top_k"] = json!(top_k);
         }
+        if let Some(format) = ctx.response_format {
+            body["response_format"] = Self::build_response_format(format);
+        }
+        if let Some(seed) = ctx.seed {
+            body["seed"] = json!(seed);
+        }
+        if let Some(stop) = ctx.stop {
+            let stop = cap_stop_sequences(stop, MAX_STOP_SEQUENCES, "OpenAI");
+            if !stop.is_empty() {
+                body["stop"] = json!(stop);
+            }
+        }
 
         if let Some(options) = ctx.provider_options {
             if let Some(openai_opts) = options.get("openai") {
@@ -636,6 +667,9 @@ impl LlmProtocol for OpenAiProtocol {
             transport_session_id: None,
             allow_transport_fallback: None,
             continuation_context: None,
+            response_format: None,
+            seed: None,
+            stop: None,
         };
         ProtocolRequestBuilder::build_request(self, ctx)
     }
@@ -1166,6 +1200,107 @@ mod tests {
         assert_eq!(body.get("max_tokens"), Some(&json!(120)));
     }
 
+    fn build_chat_request(
+        response_format: Option<&ResponseFormat>,
+        seed: Option<i64>,
+        stop: Option<&[String]>,
+    ) -> Value {
+        let messages = vec![Message::User {
+            content: MessageContent::Text("title for this task".to_string()),
+            provider_options: None,
+        }];
+        let ctx = RequestBuildContext {
+            model: "gpt-4o",
+            messages: &messages,
+            tools: None,
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
+            top_k: None,
+            provider_options: None,
+            extra_body: None,
+            conversation_mode: None,
+            input_mode: None,
+            previous_response_id: None,
+            transport_session_id: None,
+            allow_transport_fallback: None,
+            continuation_context: None,
+            response_format,
+            seed,
+            stop,
+        };
+        ProtocolRequestBuilder::build_request(&OpenAiProtocol, ctx).expect("build request")
+    }
+
+    #[test]
+    fn build_request_maps_json_object_response_format() {
+        let body = build_chat_request(
+            Some(&ResponseFormat {
+                format_type: ResponseFormatType::JsonObject,
+                schema: None,
+            }),
+            None,
+            None,
+        );
+
+        assert_eq!(
+            body.get("response_format"),
+            Some(&json!({ "type": "json_object" }))
+        );
+    }
+
+    #[test]
+    fn build_request_maps_json_schema_response_format() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "title": { "type": "string" } },
+            "required": ["title"]
+        });
+        let body = build_chat_request(
+            Some(&ResponseFormat {
+                format_type: ResponseFormatType::JsonSchema,
+                schema: Some(schema.clone()),
+            }),
+            None,
+            None,
+        );
+
+        assert_eq!(
+            body.get("response_format"),
+            Some(&json!({
+                "type": "json_schema",
+                "json_schema": { "name": "response", "schema": schema }
+            }))
+        );
+    }
+
+    #[test]
+    fn build_request_passes_seed_through() {
+        let body = build_chat_request(None, Some(42), None);
+        assert_eq!(body.get("seed"), Some(&json!(42)));
+
+        let body = build_chat_request(None, None, None);
+        assert!(body.get("seed").is_none());
+    }
+
+    #[test]
+    fn build_request_caps_stop_sequences() {
+        let stop: Vec<String> = ["</answer>", "", "END", "###", "\n\n", "STOP"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let body = build_chat_request(None, None, Some(&stop));
+
+        assert_eq!(
+            body.get("stop"),
+            Some(&json!(["</answer>", "END", "###", "\n\n"]))
+        );
+        assert!(body.get("stop_sequences").is_none());
+
+        let body = build_chat_request(None, None, Some(&[]));
+        assert!(body.get("stop").is_none());
+    }
+
     #[test]
     fn build_request_includes_openrouter_reasoning_when_only_openrouter_is_set() {
         let protocol = OpenAiProtocol;
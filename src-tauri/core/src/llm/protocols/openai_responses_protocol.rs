@@ -5,8 +5,8 @@ use crate::llm::protocols::{
     ProtocolRequestBuilder, ProtocolStreamParser, ProtocolStreamState, ToolCallAccum,
 };
 use crate::llm::types::{
-    ContentPart, Message, MessageContent, StreamEvent, ToolDefinition, TransportFallbackSource,
-    TransportFallbackTarget,
+    ContentPart, Message, MessageContent, ResponseFormatType, StreamEvent, ToolDefinition,
+    TransportFallbackSource, TransportFallbackTarget,
 };
 use serde_json::{json, Value};
 
@@ -228,6 +228,22 @@ impl ProtocolRequestBuilder for OpenAiResponsesProtocol {
                 body["previous_response_id"] = json!(previous_response_id);
             }
         }
+        if ctx.seed.is_some() {
+            log::info!("The Responses API has no seed parameter, ignoring the request seed");
+        }
+        if ctx.stop.is_some_and(|stop| !stop.is_empty()) {
+            log::info!("The Responses API has no stop parameter, ignoring the stop sequences");
+        }
+        if let Some(format) = ctx.response_format {
+            body["text"]["format"] = match (&format.format_type, &format.schema) {
+                (ResponseFormatType::JsonSchema, Some(schema)) => json!({
+                    "type": "json_schema",
+                    "name": "response",
+                    "schema": schema
+                }),
+                _ => json!({ "type": "json_object" }),
+            };
+        }
         if let Some(provider_options) = ctx.provider_options {
             if let Some(openai_opts) = provider_options.get("openai") {
                 if let Some(reasoning_effort) = openai_opts.get("reasoningEffort") {
@@ -1401,6 +1417,9 @@ impl LlmProtocol for OpenAiResponsesProtocol {
             transport_session_id: None,
             allow_transport_fallback: None,
             continuation_context: None,
+            response_format: None,
+            seed: None,
+            stop: None,
         };
         ProtocolRequestBuilder::build_request(self, ctx)
     }
@@ -1518,6 +1537,9 @@ mod tests {
             transport_session_id: None,
             allow_transport_fallback: None,
             continuation_context: None,
+            response_format: None,
+            seed: None,
+            stop: None,
         };
 
         let body = ProtocolRequestBuilder::build_request(&protocol, ctx).expect("build request");
@@ -1,7 +1,7 @@
 // Protocol-level request building trait
 // Handles conversion from internal message types to provider-specific API format
 use crate::llm::types::{
-    ContinuationContext, ConversationMode, InputMode, Message, ToolDefinition,
+    ContinuationContext, ConversationMode, InputMode, Message, ResponseFormat, ToolDefinition,
 };
 use serde_json::Value;
 
@@ -23,6 +23,9 @@ pub struct RequestBuildContext<'a> {
     pub transport_session_id: Option<&'a str>,
     pub allow_transport_fallback: Option<bool>,
     pub continuation_context: Option<&'a ContinuationContext>,
+    pub response_format: Option<&'a ResponseFormat>,
+    pub seed: Option<i64>,
+    pub stop: Option<&'a [String]>,
 }
 
 /// Trait for building protocol-specific requests
@@ -84,7 +84,7 @@ impl ProtocolImpl for ClaudeProtocolWrapper {
     ) -> Result<Value, String> {
         use crate::llm::protocols::LlmProtocol;
 
-        self.0.build_request(
+        let mut body = self.0.build_request(
             ctx.model,
             ctx.messages,
             ctx.tools,
@@ -94,7 +94,17 @@ impl ProtocolImpl for ClaudeProtocolWrapper {
             ctx.top_k,
             ctx.provider_options,
             ctx.extra_body,
-        )
+        )?;
+        if let Some(format) = ctx.response_format {
+            self.0.apply_response_format(&mut body, format);
+        }
+        if let Some(stop) = ctx.stop {
+            self.0.apply_stop_sequences(&mut body, stop);
+        }
+        if ctx.seed.is_some() {
+            log::info!("Anthropic has no seed parameter, ignoring the request seed");
+        }
+        Ok(body)
     }
     fn parse_stream_event(
         &self,
@@ -344,4 +354,46 @@ mod tests {
         let error_msg = result.unwrap_err();
         assert!(error_msg.contains("Authentication required"));
     }
+
+    #[test]
+    fn claude_request_maps_stop_to_stop_sequences() {
+        use crate::llm::protocols::request_builder::RequestBuildContext;
+        use crate::llm::types::{Message, MessageContent};
+
+        let messages = vec![Message::User {
+            content: MessageContent::Text("list three files".to_string()),
+            provider_options: None,
+        }];
+        let stop = vec!["</files>".to_string(), "".to_string(), "DONE".to_string()];
+        let ctx = RequestBuildContext {
+            model: "claude-sonnet-4-5",
+            messages: &messages,
+            tools: None,
+            temperature: None,
+            max_tokens: Some(1024),
+            top_p: None,
+            top_k: None,
+            provider_options: None,
+            extra_body: None,
+            conversation_mode: None,
+            input_mode: None,
+            previous_response_id: None,
+            transport_session_id: None,
+            allow_transport_fallback: None,
+            continuation_context: None,
+            response_format: None,
+            seed: None,
+            stop: Some(&stop),
+        };
+
+        let body = ClaudeProtocolWrapper(ClaudeProtocol)
+            .build_request(ctx)
+            .expect("build request");
+
+        assert_eq!(
+            body["stop_sequences"],
+            serde_json::json!(["</files>", "DONE"])
+        );
+        assert!(body.get("stop").is_none());
+    }
 }
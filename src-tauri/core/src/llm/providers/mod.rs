@@ -1,5 +1,6 @@
 pub mod provider;
 pub mod provider_configs;
+pub mod provider_health;
 pub mod provider_registry;
 
 // New provider implementations
@@ -98,6 +98,9 @@ impl OpenAiProvider {
             transport_session_id: ctx.transport_session_id,
             allow_transport_fallback: ctx.allow_transport_fallback,
             continuation_context: ctx.continuation_context,
+            response_format: ctx.response_format,
+            seed: ctx.seed,
+            stop: ctx.stop,
         };
         self.responses_protocol.build_request(request_ctx)
     }
@@ -149,10 +152,7 @@ impl Provider for OpenAiProvider {
             let creds = api_key_manager.get_credentials(&self.base.config).await?;
             match creds {
                 ProviderCredentials::Token(token) => {
-                    let account_id = api_key_manager
-                        .get_setting("openai_oauth_account_id")
-                        .await?
-                        .or(None);
+                    let account_id = api_key_manager.get_openai_account_id().await?;
                     Ok(Creds::OAuth { token, account_id })
                 }
                 _ => Ok(Creds::None),
@@ -180,14 +180,8 @@ impl Provider for OpenAiProvider {
             headers.insert("originator".to_string(), "codex_cli_rs".to_string());
 
             // Add account header if available
-            if let Some(account_id) = ctx
-                .api_key_manager
-                .get_setting("openai_oauth_account_id")
-                .await?
-            {
-                if !account_id.is_empty() {
-                    headers.insert("openai-organization".to_string(), account_id);
-                }
+            if let Some(account_id) = ctx.api_key_manager.get_openai_account_id().await? {
+                headers.insert("openai-organization".to_string(), account_id);
             }
         }
         Ok(())
@@ -211,6 +205,9 @@ impl Provider for OpenAiProvider {
                 transport_session_id: ctx.transport_session_id,
                 allow_transport_fallback: ctx.allow_transport_fallback,
                 continuation_context: ctx.continuation_context,
+                response_format: ctx.response_format,
+                seed: ctx.seed,
+                stop: ctx.stop,
             };
             self.responses_protocol.build_request(request_ctx)
         } else {
@@ -231,6 +228,9 @@ impl Provider for OpenAiProvider {
                 transport_session_id: ctx.transport_session_id,
                 allow_transport_fallback: ctx.allow_transport_fallback,
                 continuation_context: ctx.continuation_context,
+                response_format: ctx.response_format,
+                seed: ctx.seed,
+                stop: ctx.stop,
             };
             let mut body = self.protocol.build_request(request_ctx)?;
             // OpenAI native API requires max_completion_tokens instead of deprecated max_tokens
@@ -313,6 +313,7 @@ mod tests {
     use super::*;
     use crate::database::Database;
     use crate::llm::auth::api_key_manager::ApiKeyManager;
+    use crate::llm::auth::oauth_accounts;
     use crate::llm::protocols::openai_responses_protocol::{
         parse_openai_oauth_event_legacy, parse_openai_oauth_function_call_done,
     };
@@ -389,7 +390,13 @@ mod tests {
             previous_response_id: None,
             transport_session_id: None,
             allow_transport_fallback: None,
+            oauth_account: None,
+            disable_global_system_prompt: None,
+            response_format: None,
+            seed: None,
+            stop: None,
             continuation_context: None,
+            session_id: None,
             trace_context: None,
         };
 
@@ -411,6 +418,9 @@ mod tests {
             transport_session_id: request.transport_session_id.as_deref(),
             allow_transport_fallback: request.allow_transport_fallback,
             continuation_context: request.continuation_context.as_ref(),
+            response_format: request.response_format.as_ref(),
+            seed: request.seed,
+            stop: request.stop.as_deref(),
         };
 
         let body = provider.build_oauth_request(&ctx).expect("request body");
@@ -490,7 +500,13 @@ mod tests {
             previous_response_id: Some("resp_prev_456".to_string()),
             transport_session_id: None,
             allow_transport_fallback: None,
+            oauth_account: None,
+            disable_global_system_prompt: None,
+            response_format: None,
+            seed: None,
+            stop: None,
             continuation_context: None,
+            session_id: None,
             trace_context: None,
         };
 
@@ -512,6 +528,9 @@ mod tests {
             transport_session_id: request.transport_session_id.as_deref(),
             allow_transport_fallback: request.allow_transport_fallback,
             continuation_context: request.continuation_context.as_ref(),
+            response_format: request.response_format.as_ref(),
+            seed: request.seed,
+            stop: request.stop.as_deref(),
         };
 
         let body = provider.build_oauth_request(&ctx).expect("request body");
@@ -769,7 +788,13 @@ mod tests {
             previous_response_id: None,
             transport_session_id: None,
             allow_transport_fallback: None,
+            oauth_account: None,
+            disable_global_system_prompt: None,
+            response_format: None,
+            seed: None,
+            stop: None,
             continuation_context: None,
+            session_id: None,
             trace_context: None,
         };
 
@@ -791,6 +816,9 @@ mod tests {
             transport_session_id: request.transport_session_id.as_deref(),
             allow_transport_fallback: request.allow_transport_fallback,
             continuation_context: request.continuation_context.as_ref(),
+            response_format: request.response_format.as_ref(),
+            seed: request.seed,
+            stop: request.stop.as_deref(),
         };
 
         let body = provider.build_oauth_request(&ctx).expect("request body");
@@ -924,6 +952,9 @@ mod tests {
             transport_session_id: Some("session-123"),
             allow_transport_fallback: Some(true),
             continuation_context: None,
+            response_format: None,
+            seed: None,
+            stop: None,
         };
 
         let mut state = StreamParseState::default();
@@ -993,6 +1024,9 @@ mod tests {
             transport_session_id: Some("session-123"),
             allow_transport_fallback: Some(true),
             continuation_context: None,
+            response_format: None,
+            seed: None,
+            stop: None,
         };
 
         assert_eq!(
@@ -1061,6 +1095,9 @@ mod tests {
             transport_session_id: Some("session-123"),
             allow_transport_fallback: Some(true),
             continuation_context: None,
+            response_format: None,
+            seed: None,
+            stop: None,
         };
 
         assert_eq!(
@@ -1118,6 +1155,9 @@ mod tests {
             transport_session_id: Some("session-123"),
             allow_transport_fallback: Some(true),
             continuation_context: None,
+            response_format: None,
+            seed: None,
+            stop: None,
         };
 
         assert_eq!(
@@ -1129,4 +1169,85 @@ mod tests {
             ProviderTransport::HttpSse
         );
     }
+
+    #[tokio::test]
+    async fn openai_oauth_uses_account_selected_by_request() {
+        let dir = TempDir::new().expect("temp dir");
+        let db_path = dir.path().join("talkcody-test.db");
+        let db = Arc::new(Database::new(db_path.to_string_lossy().to_string()));
+        db.connect().await.expect("db connect");
+        db.execute(
+            "CREATE TABLE IF NOT EXISTS settings (key TEXT PRIMARY KEY, value TEXT, updated_at INTEGER)",
+            vec![],
+        )
+        .await
+        .expect("create settings");
+        let api_keys = ApiKeyManager::new(db, std::path::PathBuf::from("/tmp"));
+        // Connect a personal and a work account, saving each under a label
+        for name in ["personal", "work"] {
+            api_keys
+                .set_setting("openai_oauth_access_token", &format!("{}-token", name))
+                .await
+                .expect("set oauth token");
+            api_keys
+                .set_setting("openai_oauth_account_id", &format!("acct_{}", name))
+                .await
+                .expect("set account id");
+            oauth_accounts::save_default_account_as(&api_keys, "openai", name)
+                .await
+                .expect("save account");
+        }
+        oauth_accounts::set_active_account(&api_keys, "openai", Some("personal"))
+            .await
+            .expect("select personal");
+
+        let provider = OpenAiProvider::new(ProviderConfig {
+            id: "openai".to_string(),
+            name: "OpenAI".to_string(),
+            protocol: ProtocolType::OpenAiCompatible,
+            base_url: "https://api.openai.com/v1".to_string(),
+            api_key_name: "OPENAI_API_KEY".to_string(),
+            supports_oauth: true,
+            supports_coding_plan: false,
+            supports_international: false,
+            coding_plan_base_url: None,
+            international_base_url: None,
+            headers: None,
+            extra_body: None,
+            auth_type: crate::llm::types::AuthType::Bearer,
+        });
+
+        let request: StreamTextRequest = serde_json::from_value(json!({
+            "model": "gpt-5.2-codex@openai",
+            "messages": [],
+            "oauthAccount": "work"
+        }))
+        .expect("parse request");
+        let scoped = api_keys.with_oauth_account(request.oauth_account.clone());
+
+        match provider
+            .get_credentials(&scoped)
+            .await
+            .expect("credentials")
+        {
+            Creds::OAuth { token, account_id } => {
+                assert_eq!(token, "work-token");
+                assert_eq!(account_id.as_deref(), Some("acct_work"));
+            }
+            other => panic!("Unexpected credentials: {:?}", other),
+        }
+
+        // Without a request selection the active account is used
+        match provider
+            .get_credentials(&api_keys)
+            .await
+            .expect("credentials")
+        {
+            Creds::OAuth { token, account_id } => {
+                assert_eq!(token, "personal-token");
+                assert_eq!(account_id.as_deref(), Some("acct_personal"));
+            }
+            other => panic!("Unexpected credentials: {:?}", other),
+        }
+    }
 }
@@ -9,7 +9,7 @@ use crate::llm::protocols::{
 };
 use crate::llm::types::ProtocolType;
 use crate::llm::types::{
-    ContinuationContext, ConversationMode, InputMode, Message, ProviderConfig,
+    ContinuationContext, ConversationMode, InputMode, Message, ProviderConfig, ResponseFormat,
     ResponseMetadataProvider, ResponseTransport, StreamEvent, ToolDefinition, TraceContext,
 };
 use async_trait::async_trait;
@@ -37,6 +37,9 @@ pub struct ProviderContext<'a> {
     pub transport_session_id: Option<&'a str>,
     pub allow_transport_fallback: Option<bool>,
     pub continuation_context: Option<&'a ContinuationContext>,
+    pub response_format: Option<&'a ResponseFormat>,
+    pub seed: Option<i64>,
+    pub stop: Option<&'a [String]>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -200,6 +203,9 @@ pub trait Provider: Send + Sync {
             transport_session_id: ctx.transport_session_id,
             allow_transport_fallback: ctx.allow_transport_fallback,
             continuation_context: ctx.continuation_context,
+            response_format: ctx.response_format,
+            seed: ctx.seed,
+            stop: ctx.stop,
         };
 
         self.build_protocol_request(request_ctx)
@@ -284,7 +290,10 @@ pub trait Provider: Send + Sync {
     }
 }
 
-fn normalize_provider_base_url(base_url: &str, provider_config: &ProviderConfig) -> String {
+pub(crate) fn normalize_provider_base_url(
+    base_url: &str,
+    provider_config: &ProviderConfig,
+) -> String {
     let trimmed = base_url.trim_end_matches('/');
     if !is_custom_provider_id(&provider_config.id) {
         return trimmed.to_string();
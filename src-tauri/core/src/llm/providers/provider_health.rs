@@ -0,0 +1,316 @@
+// Provider health checks
+// Lists models on every configured provider with its real credentials, so
+// users can see which providers are reachable and authenticated before a long task.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::llm::auth::api_key_manager::{ApiKeyManager, LlmState};
+use crate::llm::models::model_registry::ModelRegistry;
+use crate::llm::providers::provider::{normalize_provider_base_url, Provider, ProviderContext};
+use crate::llm::providers::provider_registry::ProviderRegistry;
+
+/// Per-provider limit for the health request
+pub const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Health of one provider
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderHealth {
+    pub provider_id: String,
+    /// The provider answered the request
+    pub reachable: bool,
+    /// The provider accepted the credentials
+    pub authenticated: bool,
+    /// Time until the response headers arrived, or until the check gave up
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// List models on `provider` with its configured credentials.
+/// Any answer other than 401/403 counts as authenticated: some providers
+/// don't serve a models list, but they still check the credentials.
+pub async fn check_provider(
+    client: &reqwest::Client,
+    provider: &dyn Provider,
+    api_keys: &ApiKeyManager,
+    timeout: Duration,
+) -> ProviderHealth {
+    let provider_id = provider.id().to_string();
+    let started = Instant::now();
+    let failed = |error: String, started: Instant| ProviderHealth {
+        provider_id: provider_id.clone(),
+        reachable: false,
+        authenticated: false,
+        latency_ms: started.elapsed().as_millis() as u64,
+        error: Some(error),
+    };
+
+    let ctx = ProviderContext {
+        provider_config: provider.config(),
+        api_key_manager: api_keys,
+        model: "",
+        messages: &[],
+        tools: None,
+        temperature: None,
+        max_tokens: None,
+        top_p: None,
+        top_k: None,
+        provider_options: None,
+        trace_context: None,
+        conversation_mode: None,
+        input_mode: None,
+        previous_response_id: None,
+        transport_session_id: None,
+        allow_transport_fallback: None,
+        continuation_context: None,
+        response_format: None,
+        seed: None,
+        stop: None,
+    };
+    let base_url = match provider.resolve_base_url(&ctx).await {
+        Ok(base_url) => normalize_provider_base_url(&base_url, provider.config()),
+        Err(e) => return failed(e, started),
+    };
+    let headers = match provider.get_credentials(api_keys).await {
+        Ok(credentials) => match provider.build_headers(&ctx, &credentials).await {
+            Ok(headers) => headers,
+            Err(e) => return failed(e, started),
+        },
+        Err(e) => return failed(e, started),
+    };
+
+    let mut request = client
+        .get(format!("{}/models", base_url.trim_end_matches('/')))
+        .timeout(timeout);
+    for (name, value) in headers {
+        // The health request has no body
+        if !name.eq_ignore_ascii_case("content-type") {
+            request = request.header(name, value);
+        }
+    }
+
+    let started = Instant::now();
+    let response = match request.send().await {
+        Ok(response) => response,
+        Err(e) if e.is_timeout() => {
+            return failed(
+                format!("Timed out after {}ms", timeout.as_millis()),
+                started,
+            )
+        }
+        Err(e) => return failed(format!("Request failed: {}", e), started),
+    };
+    let latency_ms = started.elapsed().as_millis() as u64;
+    let status = response.status();
+    let authenticated =
+        status != reqwest::StatusCode::UNAUTHORIZED && status != reqwest::StatusCode::FORBIDDEN;
+
+    ProviderHealth {
+        provider_id,
+        reachable: true,
+        authenticated,
+        latency_ms,
+        error: (!status.is_success()).then(|| format!("HTTP {}", status)),
+    }
+}
+
+/// Check every provider that has credentials configured, concurrently.
+/// Results are sorted by provider id.
+pub async fn check_providers(
+    client: &reqwest::Client,
+    registry: &ProviderRegistry,
+    api_keys: &ApiKeyManager,
+    timeout: Duration,
+) -> Result<Vec<ProviderHealth>, String> {
+    let credentials = ModelRegistry::load_provider_credentials(api_keys).await?;
+    let custom_providers = api_keys.load_custom_providers().await?;
+
+    let providers: Vec<Box<dyn Provider>> = registry
+        .providers()
+        .iter()
+        .filter(|config| {
+            ModelRegistry::provider_available(&config.id, &credentials, registry, &custom_providers)
+        })
+        .filter_map(|config| registry.create_provider(&config.id))
+        .collect();
+
+    let mut results = futures::future::join_all(
+        providers
+            .iter()
+            .map(|provider| check_provider(client, provider.as_ref(), api_keys, timeout)),
+    )
+    .await;
+    results.sort_by(|a, b| a.provider_id.cmp(&b.provider_id));
+    Ok(results)
+}
+
+#[tauri::command]
+pub async fn llm_check_providers_health(
+    state: State<'_, LlmState>,
+) -> Result<Vec<ProviderHealth>, String> {
+    let (registry, api_keys) = {
+        let registry = state.registry.lock().await;
+        let api_keys = state.api_keys.lock().await;
+        (registry.clone(), api_keys.clone())
+    };
+    let client = crate::network_proxy::shared_json_client();
+    check_providers(&client, &registry, &api_keys, HEALTH_CHECK_TIMEOUT).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::llm::types::{AuthType, ProtocolType, ProviderConfig};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    async fn setup() -> (ApiKeyManager, TempDir) {
+        let dir = TempDir::new().expect("temp dir");
+        let db_path = dir.path().join("provider-health.db");
+        let db = Arc::new(Database::new(db_path.to_string_lossy().to_string()));
+        db.connect().await.expect("db connect");
+        db.execute(
+            "CREATE TABLE IF NOT EXISTS settings (key TEXT PRIMARY KEY, value TEXT, updated_at INTEGER)",
+            vec![],
+        )
+        .await
+        .expect("create settings");
+        (
+            ApiKeyManager::new(db, std::path::PathBuf::from("/tmp")),
+            dir,
+        )
+    }
+
+    fn provider_config(id: &str, base_url: &str) -> ProviderConfig {
+        ProviderConfig {
+            id: id.to_string(),
+            name: id.to_string(),
+            protocol: ProtocolType::OpenAiCompatible,
+            base_url: base_url.to_string(),
+            api_key_name: format!("{}_API_KEY", id.to_uppercase()),
+            supports_oauth: false,
+            supports_coding_plan: false,
+            supports_international: false,
+            coding_plan_base_url: None,
+            international_base_url: None,
+            headers: None,
+            extra_body: None,
+            auth_type: AuthType::Bearer,
+        }
+    }
+
+    /// Serve `/v1/models` with `status` after `delay`; returns the base URL
+    fn mock_provider(status: u16, delay: Duration, expected_key: &'static str) -> String {
+        let server = tiny_http::Server::http("127.0.0.1:0").expect("server");
+        let port = match server.server_addr() {
+            tiny_http::ListenAddr::IP(addr) => addr.port(),
+            _ => panic!("Expected IP SocketAddr"),
+        };
+        std::thread::spawn(move || {
+            for request in server.incoming_requests() {
+                assert_eq!(request.url(), "/v1/models");
+                let authorization = request
+                    .headers()
+                    .iter()
+                    .find(|header| header.field.equiv("Authorization"))
+                    .map(|header| header.value.to_string());
+                assert_eq!(authorization, Some(format!("Bearer {}", expected_key)));
+                std::thread::sleep(delay);
+                let _ = request.respond(
+                    tiny_http::Response::from_string("{\"data\":[]}").with_status_code(status),
+                );
+            }
+        });
+        format!("http://127.0.0.1:{}/v1", port)
+    }
+
+    #[tokio::test]
+    async fn test_reports_status_per_provider() {
+        let (api_keys, _dir) = setup().await;
+        let ok_url = mock_provider(200, Duration::ZERO, "ok-key");
+        let unauthorized_url = mock_provider(401, Duration::ZERO, "bad-key");
+        let slow_url = mock_provider(200, Duration::from_secs(5), "slow-key");
+
+        let registry = ProviderRegistry::new(vec![
+            provider_config("ok", &ok_url),
+            provider_config("unauthorized", &unauthorized_url),
+            provider_config("slow", &slow_url),
+            provider_config("unconfigured", &ok_url),
+        ]);
+        for (id, key) in [
+            ("ok", "ok-key"),
+            ("unauthorized", "bad-key"),
+            ("slow", "slow-key"),
+        ] {
+            api_keys
+                .set_setting(&format!("api_key_{}", id), key)
+                .await
+                .expect("set api key");
+        }
+
+        let started = Instant::now();
+        let results = check_providers(
+            &reqwest::Client::new(),
+            &registry,
+            &api_keys,
+            Duration::from_millis(500),
+        )
+        .await
+        .expect("health check");
+        // Checks run concurrently, so the slow provider doesn't delay the others
+        assert!(started.elapsed() < Duration::from_secs(3));
+
+        let ids: Vec<&str> = results.iter().map(|r| r.provider_id.as_str()).collect();
+        assert_eq!(ids, vec!["ok", "slow", "unauthorized"]);
+
+        let ok = &results[0];
+        assert!(ok.reachable && ok.authenticated);
+        assert_eq!(ok.error, None);
+
+        let slow = &results[1];
+        assert!(!slow.reachable && !slow.authenticated);
+        assert!(slow.error.as_deref().unwrap().contains("Timed out"));
+        assert!(slow.latency_ms >= 500);
+
+        let unauthorized = &results[2];
+        assert!(unauthorized.reachable);
+        assert!(!unauthorized.authenticated);
+        assert_eq!(unauthorized.error.as_deref(), Some("HTTP 401 Unauthorized"));
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_provider() {
+        let (api_keys, _dir) = setup().await;
+        api_keys
+            .set_setting("api_key_down", "key")
+            .await
+            .expect("set api key");
+        // Bind then drop a listener so the port refuses connections
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let provider = crate::llm::providers::DefaultProvider::new(provider_config(
+            "down",
+            &format!("http://127.0.0.1:{}/v1", port),
+        ));
+
+        let health = check_provider(
+            &reqwest::Client::new(),
+            &provider,
+            &api_keys,
+            Duration::from_millis(500),
+        )
+        .await;
+
+        assert!(!health.reachable);
+        assert!(!health.authenticated);
+        assert!(health.error.unwrap().contains("Request failed"));
+    }
+}
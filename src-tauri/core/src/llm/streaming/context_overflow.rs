@@ -0,0 +1,384 @@
+// Context-length overflow recovery
+// When a provider rejects a request for exceeding the model's context window,
+// the history is compacted with the context compaction service and the
+// request is retried once before giving up with an actionable error.
+
+use std::future::Future;
+
+use crate::llm::ai_services::types::{
+    CompactionMessage, ContextCompactionRequest, ContextCompactionResult,
+};
+use crate::llm::types::{ContentPart, Message, MessageContent, StreamTextRequest};
+
+/// Prefix of stream errors caused by an overflowing context window
+pub const CONTEXT_LENGTH_EXCEEDED: &str = "Context length exceeded";
+
+/// Provider error fragments that mean the prompt didn't fit the context window
+const CONTEXT_LENGTH_PATTERNS: &[&str] = &[
+    "context_length_exceeded",
+    "model_context_window_exceeded",
+    "maximum context length",
+    "context window",
+    "prompt is too long",
+    "input is too long",
+    "reduce the length of the messages",
+    "too many input tokens",
+];
+
+/// Whether an HTTP error from a provider reports a context-length overflow
+pub fn is_context_length_error(status: u16, body: &str) -> bool {
+    if !matches!(status, 400 | 413 | 422) {
+        return false;
+    }
+    let body = body.to_lowercase();
+    CONTEXT_LENGTH_PATTERNS
+        .iter()
+        .any(|pattern| body.contains(pattern))
+}
+
+/// Stream error message for a context-length overflow
+pub fn context_length_error(status: u16, body: &str) -> String {
+    format!("{}: HTTP {}: {}", CONTEXT_LENGTH_EXCEEDED, status, body)
+}
+
+/// Whether a stream error, or the final error of `retry_after_compaction`,
+/// comes from an overflowing context window
+pub fn is_context_length_exceeded(error: &str) -> bool {
+    error.starts_with(CONTEXT_LENGTH_EXCEEDED)
+}
+
+fn render_content(content: &MessageContent) -> String {
+    match content {
+        MessageContent::Text(text) => text.clone(),
+        MessageContent::Parts(parts) => render_parts(parts),
+    }
+}
+
+fn render_parts(parts: &[ContentPart]) -> String {
+    parts
+        .iter()
+        .filter_map(|part| match part {
+            ContentPart::Text { text } => Some(text.clone()),
+            ContentPart::Image { .. } => Some("[image]".to_string()),
+            ContentPart::Video { .. } => Some("[video]".to_string()),
+            ContentPart::ToolCall {
+                tool_name, input, ..
+            } => Some(format!("[tool call {}: {}]", tool_name, input)),
+            ContentPart::ToolResult {
+                tool_name, output, ..
+            } => Some(format!("[tool result {}: {}]", tool_name, output)),
+            ContentPart::Reasoning { .. } => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn compaction_message(index: usize, message: &Message) -> CompactionMessage {
+    let (role, content) = match message {
+        Message::System { content, .. } => ("system", content.clone()),
+        Message::User { content, .. } => ("user", render_content(content)),
+        Message::Assistant { content, .. } => ("assistant", render_content(content)),
+        Message::Tool { content, .. } => ("tool", render_parts(content)),
+    };
+    CompactionMessage {
+        id: index.to_string(),
+        role: role.to_string(),
+        content,
+    }
+}
+
+/// Index of the last user message: it and everything after it (the current
+/// turn with its tool calls and results) is kept verbatim
+fn current_turn_start(messages: &[Message]) -> usize {
+    messages
+        .iter()
+        .rposition(|message| matches!(message, Message::User { .. }))
+        .unwrap_or(messages.len().saturating_sub(1))
+}
+
+/// Compaction request summarizing everything before the current turn.
+/// System messages and the current turn are pinned; tool results are not
+/// preserved on their own, since they'd lose the calls they answer.
+pub fn compaction_request(messages: &[Message]) -> ContextCompactionRequest {
+    let turn_start = current_turn_start(messages);
+    let pinned_message_ids = messages
+        .iter()
+        .enumerate()
+        .filter(|(index, message)| {
+            *index >= turn_start || matches!(message, Message::System { .. })
+        })
+        .map(|(index, _)| index.to_string())
+        .collect();
+    ContextCompactionRequest {
+        conversation_history: String::new(),
+        messages: Some(
+            messages
+                .iter()
+                .enumerate()
+                .map(|(index, message)| compaction_message(index, message))
+                .collect(),
+        ),
+        pinned_message_ids,
+        preserve_recent_tool_results: Some(0),
+        target_tokens: None,
+        model: None,
+        fallback_models: None,
+    }
+}
+
+/// `request` with its history replaced by the summary in `result`
+pub fn compacted_request(
+    request: &StreamTextRequest,
+    result: &ContextCompactionResult,
+) -> StreamTextRequest {
+    let turn_start = current_turn_start(&request.messages);
+    let (earlier, current_turn) = request.messages.split_at(turn_start);
+    let mut messages: Vec<Message> = earlier
+        .iter()
+        .filter(|message| matches!(message, Message::System { .. }))
+        .cloned()
+        .collect();
+    messages.push(Message::User {
+        content: MessageContent::Text(format!(
+            "Summary of the earlier conversation, compacted to fit the context window:\n\n{}",
+            result.compressed_summary
+        )),
+        provider_options: None,
+    });
+    messages.extend(current_turn.iter().cloned());
+
+    StreamTextRequest {
+        messages,
+        // The provider-side conversation no longer matches the history
+        previous_response_id: None,
+        continuation_context: None,
+        ..request.clone()
+    }
+}
+
+/// Run `attempt`; on a context-length overflow, compact the history with
+/// `compact` and run it once more. `on_compacted` receives the compacted
+/// request before the retry, so the caller can hand the new history on.
+/// A second overflow, or a failed compaction, ends with an error telling the
+/// user how to recover.
+pub async fn retry_after_compaction<T, A, AFut, C, CFut, O>(
+    request: StreamTextRequest,
+    mut attempt: A,
+    compact: C,
+    on_compacted: O,
+) -> Result<T, String>
+where
+    A: FnMut(StreamTextRequest) -> AFut,
+    AFut: Future<Output = Result<T, String>>,
+    C: FnOnce(ContextCompactionRequest) -> CFut,
+    CFut: Future<Output = Result<ContextCompactionResult, String>>,
+    O: FnOnce(&StreamTextRequest, &ContextCompactionResult),
+{
+    let error = match attempt(request.clone()).await {
+        Err(error) if is_context_length_exceeded(&error) => error,
+        result => return result,
+    };
+    log::warn!(
+        "Request for {} exceeded the context window, compacting and retrying: {}",
+        request.model,
+        error
+    );
+
+    let compacted = match compact(compaction_request(&request.messages)).await {
+        Ok(result) => result,
+        Err(e) => {
+            return Err(format!(
+                "{}: the conversation is too long for {} and compacting it failed ({}). \
+                 Compact the conversation or switch to a model with a larger context window.",
+                CONTEXT_LENGTH_EXCEEDED, request.model, e
+            ))
+        }
+    };
+    log::info!(
+        "Compacted context from ~{} to ~{} tokens",
+        compacted.tokens_before,
+        compacted.tokens_after
+    );
+
+    let retry = compacted_request(&request, &compacted);
+    on_compacted(&retry, &compacted);
+    match attempt(retry).await {
+        Err(error) if is_context_length_exceeded(&error) => Err(format!(
+            "{}: the conversation is still too long for {} after compacting it. \
+             Compact the conversation further or switch to a model with a larger context window.",
+            CONTEXT_LENGTH_EXCEEDED, request.model
+        )),
+        result => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::ai_services::context_compaction_service::{
+        ContextCompactionService, Summarizer,
+    };
+    use std::sync::Mutex;
+
+    const OPENAI_OVERFLOW: &str = r#"{"error":{"message":"This model's maximum context length is 128000 tokens.","code":"context_length_exceeded"}}"#;
+
+    struct FixedSummarizer;
+
+    #[async_trait::async_trait]
+    impl Summarizer for FixedSummarizer {
+        async fn summarize(
+            &self,
+            _prompt: String,
+            on_delta: &mut (dyn FnMut(String) + Send),
+        ) -> Result<(), String> {
+            on_delta("User asked to refactor the parser; it was read.".to_string());
+            Ok(())
+        }
+    }
+
+    fn request() -> StreamTextRequest {
+        serde_json::from_value(serde_json::json!({
+            "model": "gpt-5.5@openai",
+            "previousResponseId": "resp_1",
+            "messages": [
+                { "role": "system", "content": "You are a coding agent." },
+                { "role": "user", "content": "refactor the parser" },
+                { "role": "assistant", "content": [
+                    { "type": "tool-call", "toolCallId": "call_1", "toolName": "readFile", "input": {} }
+                ] },
+                { "role": "tool", "content": [
+                    { "type": "tool-result", "toolCallId": "call_1", "toolName": "readFile", "output": { "type": "text", "value": "fn parse() {}" } }
+                ] },
+                { "role": "user", "content": "now add tests" },
+                { "role": "assistant", "content": [
+                    { "type": "tool-call", "toolCallId": "call_2", "toolName": "readFile", "input": {} }
+                ] },
+                { "role": "tool", "content": [
+                    { "type": "tool-result", "toolCallId": "call_2", "toolName": "readFile", "output": { "type": "text", "value": "mod tests {}" } }
+                ] }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn detects_provider_context_length_errors() {
+        assert!(is_context_length_error(400, OPENAI_OVERFLOW));
+        assert!(is_context_length_error(
+            400,
+            r#"{"type":"error","error":{"type":"invalid_request_error","message":"prompt is too long: 210000 tokens > 200000 maximum"}}"#
+        ));
+        assert!(!is_context_length_error(
+            429,
+            "maximum context length reached for this minute"
+        ));
+        assert!(!is_context_length_error(400, "invalid tool schema"));
+    }
+
+    #[test]
+    fn compaction_pins_system_prompt_and_current_turn() {
+        let request = compaction_request(&request().messages);
+        assert_eq!(request.pinned_message_ids, vec!["0", "4", "5", "6"]);
+        assert_eq!(request.preserve_recent_tool_results, Some(0));
+    }
+
+    #[tokio::test]
+    async fn retries_once_with_compacted_history() {
+        let attempts = Mutex::new(Vec::new());
+        let mut reported = None;
+        let result = retry_after_compaction(
+            request(),
+            |request: StreamTextRequest| {
+                let first = {
+                    let mut attempts = attempts.lock().unwrap();
+                    attempts.push(request);
+                    attempts.len() == 1
+                };
+                async move {
+                    if first {
+                        Err(context_length_error(400, OPENAI_OVERFLOW))
+                    } else {
+                        Ok("done")
+                    }
+                }
+            },
+            |compaction| async move {
+                ContextCompactionService::new()
+                    .compact_with_summarizer(compaction, &FixedSummarizer, |_| {})
+                    .await
+            },
+            |compacted, _| reported = Some(compacted.messages.len()),
+        )
+        .await;
+
+        assert_eq!(result, Ok("done"));
+        let attempts = attempts.into_inner().unwrap();
+        assert_eq!(attempts.len(), 2);
+        let retried = &attempts[1];
+        assert_eq!(reported, Some(retried.messages.len()));
+        assert_eq!(retried.messages.len(), 5);
+        assert!(matches!(
+            &retried.messages[0],
+            Message::System { content, .. } if content == "You are a coding agent."
+        ));
+        assert!(matches!(
+            &retried.messages[1],
+            Message::User { content: MessageContent::Text(text), .. }
+                if text.contains("User asked to refactor the parser")
+        ));
+        assert!(matches!(
+            &retried.messages[2],
+            Message::User { content: MessageContent::Text(text), .. } if text == "now add tests"
+        ));
+        assert!(matches!(&retried.messages[4], Message::Tool { .. }));
+        assert_eq!(retried.previous_response_id, None);
+    }
+
+    #[tokio::test]
+    async fn reports_clear_error_when_retry_still_overflows() {
+        let mut attempts = 0;
+        let result: Result<(), String> = retry_after_compaction(
+            request(),
+            |_| {
+                attempts += 1;
+                async { Err(context_length_error(400, OPENAI_OVERFLOW)) }
+            },
+            |compaction| async move {
+                ContextCompactionService::new()
+                    .compact_with_summarizer(compaction, &FixedSummarizer, |_| {})
+                    .await
+            },
+            |_, _| {},
+        )
+        .await;
+
+        assert_eq!(attempts, 2);
+        let error = result.unwrap_err();
+        assert!(error.starts_with(CONTEXT_LENGTH_EXCEEDED));
+        assert!(error.contains("still too long for gpt-5.5@openai"));
+        assert!(error.contains("switch to a model with a larger context window"));
+    }
+
+    #[tokio::test]
+    async fn other_errors_are_not_retried() {
+        let mut attempts = 0;
+        let mut compacted = false;
+        let result: Result<(), String> = retry_after_compaction(
+            request(),
+            |_| {
+                attempts += 1;
+                async { Err("HTTP error 401".to_string()) }
+            },
+            |_| {
+                compacted = true;
+                async { Err("compaction should not run".to_string()) }
+            },
+            |_, _| panic!("nothing was compacted"),
+        )
+        .await;
+
+        assert_eq!(attempts, 1);
+        assert!(!compacted);
+        assert_eq!(result, Err("HTTP error 401".to_string()));
+    }
+}
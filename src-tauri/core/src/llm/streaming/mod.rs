@@ -1,2 +1,6 @@
+pub mod context_overflow;
+pub mod model_fallback;
 pub mod openai_responses_ws;
+pub mod sse_buffer;
 pub mod stream_handler;
+pub mod system_prompt;
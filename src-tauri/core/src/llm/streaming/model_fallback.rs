@@ -0,0 +1,286 @@
+// Model fallback
+// When a request's model is overloaded, rate-limited or erroring server-side,
+// the request is retried with its `fallback_models` in order. Unavailable
+// errors are held back while the chain runs, so the frontend only sees the
+// model that finally answered or one error covering every model.
+
+use std::future::Future;
+
+use crate::llm::streaming::stream_handler::is_transient_provider_retryable_error;
+use crate::llm::types::StreamTextRequest;
+
+/// Prefix of stream errors held back so the next model can be tried
+pub const MODEL_UNAVAILABLE: &str = "Model unavailable";
+
+/// Prefix of the error returned once every model in the chain was unavailable
+pub const ALL_MODELS_FAILED: &str = "All models failed";
+
+/// Output of a completion along with the model that produced it
+#[derive(Debug, Clone)]
+pub struct ServedCompletion<T> {
+    pub output: T,
+    /// Model that actually served the request
+    pub served_by: String,
+    /// Models tried before `served_by`, with the error each returned
+    pub failed_attempts: Vec<(String, String)>,
+}
+
+/// One model attempt within a chain
+#[derive(Debug, Clone)]
+pub struct ModelAttempt {
+    pub model: String,
+    /// Return unavailable errors instead of emitting them, so the chain can
+    /// move on or report every failure at once
+    pub hold_back_unavailable: bool,
+    /// Model tried just before this one and the error it returned
+    pub previous_failure: Option<(String, String)>,
+}
+
+/// Ordered models to try: the request's model, then its `fallback_models`,
+/// skipping blanks and duplicates
+pub fn model_chain(request: &StreamTextRequest) -> Vec<String> {
+    let mut chain = vec![request.model.clone()];
+    for model in request.fallback_models.iter().flatten() {
+        let model = model.trim();
+        if !model.is_empty() && !chain.iter().any(|existing| existing == model) {
+            chain.push(model.to_string());
+        }
+    }
+    chain
+}
+
+/// Whether an HTTP error should move on to the next model: overloads, rate
+/// limits and server errors. Anything else (bad request, auth) would fail
+/// the same way on every model.
+pub fn is_unavailable_http_error(status: u16, body: &str) -> bool {
+    if status == 429 || status >= 500 {
+        return true;
+    }
+    let normalized = body.to_ascii_lowercase();
+    normalized.contains("rate limit")
+        || normalized.contains("overloaded")
+        || is_transient_provider_retryable_error(body)
+}
+
+/// Stream error message for an unavailable model whose error was not emitted
+pub fn model_unavailable_error(status: u16, body: &str) -> String {
+    format!("{}: HTTP {}: {}", MODEL_UNAVAILABLE, status, body)
+}
+
+/// Whether a stream error was held back for the next model to be tried
+pub fn is_model_unavailable(error: &str) -> bool {
+    error.starts_with(MODEL_UNAVAILABLE)
+}
+
+/// Whether an error is the chain-wide failure from `run_model_chain`
+pub fn is_all_models_failed(error: &str) -> bool {
+    error.starts_with(ALL_MODELS_FAILED)
+}
+
+/// Run `attempt` against each model in turn until one succeeds.
+///
+/// Only errors accepted by `can_fall_back` advance the chain; other errors
+/// are returned straight away. When every model fails, the error lists each
+/// model with its failure, including the last one.
+pub async fn run_model_chain<T, P, F, Fut>(
+    models: Vec<String>,
+    can_fall_back: P,
+    mut attempt: F,
+) -> Result<ServedCompletion<T>, String>
+where
+    P: Fn(&str) -> bool,
+    F: FnMut(ModelAttempt) -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    let mut failed_attempts: Vec<(String, String)> = Vec::new();
+    let hold_back_unavailable = models.len() > 1;
+    for model in models {
+        let model_attempt = ModelAttempt {
+            model: model.clone(),
+            hold_back_unavailable,
+            previous_failure: failed_attempts.last().cloned(),
+        };
+        match attempt(model_attempt).await {
+            Ok(output) => {
+                if !failed_attempts.is_empty() {
+                    log::warn!(
+                        "[Model Fallback] Served by fallback model {} after {} failed attempt(s)",
+                        model,
+                        failed_attempts.len()
+                    );
+                }
+                return Ok(ServedCompletion {
+                    output,
+                    served_by: model,
+                    failed_attempts,
+                });
+            }
+            Err(error) if can_fall_back(&error) => {
+                log::warn!("[Model Fallback] Model {} unavailable: {}", model, error);
+                failed_attempts.push((model, error));
+            }
+            Err(error) => return Err(format!("{}: {}", model, error)),
+        }
+    }
+
+    Err(format!(
+        "{}: {}",
+        ALL_MODELS_FAILED,
+        failed_attempts
+            .iter()
+            .map(|(model, error)| format!("{} ({})", model, error))
+            .collect::<Vec<_>>()
+            .join("; ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain_with_fallbacks(models: &[&str]) -> Vec<String> {
+        let request: StreamTextRequest = serde_json::from_value(serde_json::json!({
+            "model": "primary",
+            "fallbackModels": models,
+            "messages": [],
+        }))
+        .unwrap();
+        model_chain(&request)
+    }
+
+    #[test]
+    fn test_model_chain_orders_and_dedupes() {
+        assert_eq!(
+            chain_with_fallbacks(&["backup", " ", "primary", "backup-2"]),
+            vec!["primary", "backup", "backup-2"]
+        );
+        assert_eq!(chain_with_fallbacks(&[]), vec!["primary"]);
+    }
+
+    #[test]
+    fn test_unavailable_http_error_classification() {
+        assert!(is_unavailable_http_error(503, "Service Unavailable"));
+        assert!(is_unavailable_http_error(429, "Too Many Requests"));
+        assert!(is_unavailable_http_error(
+            400,
+            "Our servers are currently overloaded"
+        ));
+        assert!(!is_unavailable_http_error(400, "invalid request"));
+        assert!(!is_unavailable_http_error(401, "bad api key"));
+    }
+
+    #[test]
+    fn test_model_unavailable_error_is_recognized() {
+        let error = model_unavailable_error(503, "busy");
+        assert_eq!(error, "Model unavailable: HTTP 503: busy");
+        assert!(is_model_unavailable(&error));
+        assert!(!is_model_unavailable("HTTP error 503"));
+    }
+
+    #[tokio::test]
+    async fn test_fallback_model_serves_when_primary_unavailable() {
+        let chain = chain_with_fallbacks(&["backup"]);
+        let mut previous_failures = Vec::new();
+        let served = run_model_chain(chain, is_model_unavailable, |attempt| {
+            assert!(attempt.hold_back_unavailable);
+            previous_failures.push(attempt.previous_failure.map(|(model, _)| model));
+            let model = attempt.model;
+            async move {
+                if model == "primary" {
+                    Err(model_unavailable_error(503, "Service Unavailable"))
+                } else {
+                    Ok(format!("answer from {}", model))
+                }
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(served.served_by, "backup");
+        assert_eq!(served.output, "answer from backup");
+        assert_eq!(served.failed_attempts.len(), 1);
+        assert_eq!(served.failed_attempts[0].0, "primary");
+        assert_eq!(previous_failures, vec![None, Some("primary".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_all_models_failing_aggregates_errors() {
+        let chain = chain_with_fallbacks(&["backup"]);
+        let error = run_model_chain(chain, is_model_unavailable, |attempt| async move {
+            Err::<(), _>(model_unavailable_error(
+                503,
+                &format!("{} overloaded", attempt.model),
+            ))
+        })
+        .await
+        .unwrap_err();
+
+        assert!(is_all_models_failed(&error));
+        assert!(error.contains("primary (Model unavailable: HTTP 503: primary overloaded)"));
+        assert!(error.contains("backup (Model unavailable: HTTP 503: backup overloaded)"));
+    }
+
+    /// Mirrors `execute_http_sse_stream`: held-back errors carry the
+    /// unavailable prefix, emitted ones are the plain HTTP error
+    fn http_error(status: u16, body: &str, hold_back: bool) -> String {
+        if hold_back && is_unavailable_http_error(status, body) {
+            model_unavailable_error(status, body)
+        } else {
+            format!("HTTP error {}", status)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_last_model_http_error_is_included_in_aggregate() {
+        let chain = chain_with_fallbacks(&["backup"]);
+        let error = run_model_chain(chain, is_model_unavailable, |attempt| async move {
+            Err::<(), _>(http_error(
+                503,
+                "Service Unavailable",
+                attempt.hold_back_unavailable,
+            ))
+        })
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            error,
+            "All models failed: primary (Model unavailable: HTTP 503: Service Unavailable); \
+             backup (Model unavailable: HTTP 503: Service Unavailable)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_single_model_errors_are_not_held_back() {
+        let error = run_model_chain(
+            chain_with_fallbacks(&[]),
+            is_model_unavailable,
+            |attempt| async move {
+                Err::<(), _>(http_error(
+                    503,
+                    "Service Unavailable",
+                    attempt.hold_back_unavailable,
+                ))
+            },
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(error, "primary: HTTP error 503");
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_error_skips_fallbacks() {
+        let chain = chain_with_fallbacks(&["backup"]);
+        let mut attempts = Vec::new();
+        let error = run_model_chain(chain, is_model_unavailable, |attempt| {
+            attempts.push(attempt.model);
+            async { Err::<(), _>("HTTP error 400".to_string()) }
+        })
+        .await
+        .unwrap_err();
+
+        assert_eq!(attempts, vec!["primary"]);
+        assert_eq!(error, "primary: HTTP error 400");
+    }
+}
@@ -0,0 +1,144 @@
+// Incremental SSE event buffer
+// Remembers how far the buffer has already been scanned so each chunk only
+// scans the newly appended bytes instead of the whole accumulated event.
+
+/// Longest delimiter (`\r\n\r\n`) minus one: a delimiter split across chunks
+/// can start at most this many bytes before the previous end of the buffer
+const DELIMITER_OVERLAP: usize = 3;
+
+/// Byte buffer that splits an SSE byte stream into raw events
+#[derive(Debug, Default)]
+pub struct SseBuffer {
+    buf: Vec<u8>,
+    /// Bytes before this offset are known not to start a delimiter
+    /// (except for the trailing overlap)
+    scanned: usize,
+}
+
+impl SseBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn extend(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// Remove and return the next complete event, without its delimiter
+    pub fn next_event(&mut self) -> Option<Vec<u8>> {
+        let from = self.scanned.saturating_sub(DELIMITER_OVERLAP);
+        match find_sse_delimiter(&self.buf, from) {
+            Some((idx, delimiter_len)) => {
+                let event = self.buf[..idx].to_vec();
+                self.buf.drain(..idx + delimiter_len);
+                self.scanned = 0;
+                Some(event)
+            }
+            None => {
+                self.scanned = self.buf.len();
+                None
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+}
+
+/// Find the first SSE delimiter at or after `from`, returns (index, delimiter_length).
+/// Handles both \n\n and \r\n\r\n delimiters
+pub fn find_sse_delimiter(buf: &[u8], from: usize) -> Option<(usize, usize)> {
+    let mut pos = from;
+    while pos < buf.len() {
+        let rest = &buf[pos..];
+        if rest.starts_with(b"\r\n\r\n") {
+            return Some((pos, 4));
+        }
+        if rest.starts_with(b"\n\n") {
+            return Some((pos, 2));
+        }
+        pos += 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain_events(buffer: &mut SseBuffer) -> Vec<String> {
+        let mut events = Vec::new();
+        while let Some(event) = buffer.next_event() {
+            events.push(String::from_utf8(event).unwrap());
+        }
+        events
+    }
+
+    #[test]
+    fn find_sse_delimiter_prefers_crlf() {
+        let data = b"event: ping\r\n\r\n";
+        assert_eq!(find_sse_delimiter(data, 0), Some((11, 4)));
+        assert_eq!(find_sse_delimiter(b"data: a\n\n", 0), Some((7, 2)));
+        assert_eq!(find_sse_delimiter(b"data: a\n\n", 8), None);
+    }
+
+    #[test]
+    fn splits_multiple_events_in_one_chunk() {
+        let mut buffer = SseBuffer::new();
+        buffer.extend(b"data: a\n\ndata: b\r\n\r\ndata: c");
+        assert_eq!(drain_events(&mut buffer), vec!["data: a", "data: b"]);
+        assert_eq!(buffer.len(), "data: c".len());
+    }
+
+    #[test]
+    fn finds_delimiter_split_across_chunks() {
+        for split in 1..4 {
+            let mut buffer = SseBuffer::new();
+            let mut events = Vec::new();
+            let delimiter = b"\r\n\r\n";
+            buffer.extend(b"data: first");
+            buffer.extend(&delimiter[..split]);
+            events.extend(drain_events(&mut buffer));
+            assert!(events.is_empty(), "split at {}", split);
+            buffer.extend(&delimiter[split..]);
+            buffer.extend(b"data: second\n");
+            events.extend(drain_events(&mut buffer));
+            buffer.extend(b"\n");
+            events.extend(drain_events(&mut buffer));
+            assert_eq!(events, vec!["data: first", "data: second"]);
+            assert!(buffer.is_empty());
+        }
+    }
+
+    #[test]
+    fn large_event_in_many_small_chunks() {
+        // ~1 MiB of tool-call arguments delivered in 7-byte chunks; with a full
+        // rescan per chunk this is quadratic, with a resume offset it is linear
+        let payload = format!("data: {{\"arguments\":\"{}\"}}", "x".repeat(1024 * 1024));
+        let stream = format!("{}\r\n\r\ndata: [DONE]\n\n", payload);
+
+        let mut buffer = SseBuffer::new();
+        let mut events = Vec::new();
+        let started = std::time::Instant::now();
+        for chunk in stream.as_bytes().chunks(7) {
+            buffer.extend(chunk);
+            events.extend(drain_events(&mut buffer));
+        }
+        let elapsed = started.elapsed();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0], payload);
+        assert_eq!(events[1], "data: [DONE]");
+        assert!(buffer.is_empty());
+        assert!(
+            elapsed < std::time::Duration::from_secs(5),
+            "took {:?}",
+            elapsed
+        );
+    }
+}
@@ -1,25 +1,35 @@
+use crate::llm::ai_services::context_compaction_service::ContextCompactionService;
+use crate::llm::ai_services::pricing_service::PricingService;
+use crate::llm::ai_services::types::TokenUsage;
 use crate::llm::auth::api_key_manager::ApiKeyManager;
+use crate::llm::models::capability_checks;
 use crate::llm::protocols::openai_responses_protocol::classify_continuation_rejection;
 use crate::llm::protocols::stream_parser::StreamParseState;
 use crate::llm::providers::provider::{ProviderContext, ProviderRoute, ProviderTransport};
 use crate::llm::providers::provider_registry::ProviderRegistry;
+use crate::llm::streaming::context_overflow::{self, is_context_length_error};
+use crate::llm::streaming::model_fallback;
 use crate::llm::streaming::openai_responses_ws::{self, OpenAiResponsesWsOutcome};
+use crate::llm::streaming::sse_buffer::SseBuffer;
+use crate::llm::streaming::system_prompt;
 use crate::llm::testing::fixtures::FixtureInput;
 use crate::llm::testing::{Recorder, RecordingContext, TestConfig, TestMode};
 use crate::llm::tracing::types::{float_attr, int_attr};
 use crate::llm::tracing::TraceWriter;
 use crate::llm::types::{StreamEvent, StreamTextRequest};
+use crate::storage::{Storage, UsageRecord};
 use futures_util::StreamExt;
 use serde_json;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU32, Ordering};
-use std::sync::{Arc, OnceLock};
+use std::sync::Arc;
 use std::time::Duration;
 use tauri::{Emitter, Manager};
 use tokio::time::timeout;
 
 static REQUEST_COUNTER: AtomicU32 = AtomicU32::new(1000);
-static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+/// Overall timeout for a streaming request unless the provider overrides it
+const STREAM_REQUEST_TIMEOUT: Duration = Duration::from_secs(3000);
 
 pub(crate) const TRANSIENT_PROVIDER_RETRY_LIMIT: u32 = 3;
 pub(crate) const TRANSIENT_PROVIDER_RETRY_BASE_DELAY_MS: u64 = 1000;
@@ -52,6 +62,25 @@ pub(crate) fn should_retry_transient_http_error(status: u16, body: &str) -> bool
 /// Token usage info: (input_tokens, output_tokens, total_tokens, cached_input_tokens, cache_creation_input_tokens)
 type TokenUsageInfo = (i32, i32, Option<i32>, Option<i32>, Option<i32>);
 
+/// Session a request's usage is billed to: the request's `session_id`, else
+/// `session_id` trace metadata or the trace id (the frontend uses the task id
+/// for all of them). Tracing may be off, so the trace is only a fallback.
+fn usage_session_id(request: &StreamTextRequest) -> Option<String> {
+    let from_trace = request.trace_context.as_ref().and_then(|trace_context| {
+        trace_context
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get("session_id"))
+            .or(trace_context.trace_id.as_ref())
+    });
+    request
+        .session_id
+        .as_ref()
+        .or(from_trace)
+        .filter(|id| !id.is_empty())
+        .cloned()
+}
+
 pub struct StreamHandler {
     registry: ProviderRegistry,
     api_keys: ApiKeyManager,
@@ -62,12 +91,20 @@ impl StreamHandler {
         Self { registry, api_keys }
     }
 
+    /// Stream a completion, falling back through the request's
+    /// `fallback_models` when a model is overloaded or rate-limited.
+    /// A `model-fallback` event tells the frontend which model took over.
     pub async fn stream_completion(
         &self,
         window: tauri::Window,
-        request: StreamTextRequest,
+        mut request: StreamTextRequest,
         request_id: String,
     ) -> Result<String, String> {
+        if request.disable_global_system_prompt.is_none() {
+            request.disable_global_system_prompt = self
+                .session_disables_global_system_prompt(&window, &request)
+                .await;
+        }
         // Use provided request_id if non-zero, otherwise generate one
         let request_id = if request_id != "0" {
             request_id
@@ -76,6 +113,123 @@ impl StreamHandler {
         };
         let event_name = format!("llm-stream-{}", request_id);
 
+        let chain = model_fallback::model_chain(&request);
+        let (window, request, request_id, event_name) =
+            (&window, &request, &request_id, &event_name);
+        let result = model_fallback::run_model_chain(
+            chain,
+            model_fallback::is_model_unavailable,
+            move |attempt| {
+                let mut request = StreamTextRequest {
+                    model: attempt.model.clone(),
+                    fallback_models: None,
+                    ..request.clone()
+                };
+                if let Some((from, reason)) = attempt.previous_failure {
+                    // A response chain or transport session belongs to the previous model
+                    request.previous_response_id = None;
+                    request.transport_session_id = None;
+                    let _ = window.emit(
+                        event_name,
+                        &StreamEvent::ModelFallback {
+                            from,
+                            to: attempt.model,
+                            reason,
+                        },
+                    );
+                }
+                self.stream_with_model(
+                    window,
+                    request,
+                    request_id.clone(),
+                    attempt.hold_back_unavailable,
+                )
+            },
+        )
+        .await;
+
+        match result {
+            Ok(served) => {
+                log::info!(
+                    "[LLM Stream {}] Served by model {}",
+                    request_id,
+                    served.served_by
+                );
+                Ok(served.output)
+            }
+            Err(error) => {
+                // Held-back errors were never emitted; report the whole chain once
+                if model_fallback::is_all_models_failed(&error) {
+                    let _ = window.emit(
+                        event_name,
+                        &StreamEvent::Error {
+                            message: error.clone(),
+                        },
+                    );
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Stream with one model. When the prompt overflows the model's context
+    /// window, the history is compacted and the request retried once.
+    /// With `has_fallback`, an unavailable model's error is returned to the
+    /// model chain instead of being emitted.
+    async fn stream_with_model(
+        &self,
+        window: &tauri::Window,
+        request: StreamTextRequest,
+        request_id: String,
+        has_fallback: bool,
+    ) -> Result<String, String> {
+        let event_name = format!("llm-stream-{}", request_id);
+
+        let result = context_overflow::retry_after_compaction(
+            request,
+            |request| {
+                self.stream_completion_attempt(window, request, request_id.clone(), has_fallback)
+            },
+            |compaction| async move {
+                ContextCompactionService::new()
+                    .compact_context_streaming(compaction, &self.api_keys, &self.registry, |_| {})
+                    .await
+            },
+            |compacted, result| {
+                let _ = window.emit(
+                    &event_name,
+                    &StreamEvent::ContextCompacted {
+                        messages: compacted.messages.clone(),
+                        tokens_before: result.tokens_before,
+                        tokens_after: result.tokens_after,
+                    },
+                );
+            },
+        )
+        .await;
+        // Attempts leave context overflows for this retry to report
+        if let Err(error) = &result {
+            if context_overflow::is_context_length_exceeded(error) {
+                let _ = window.emit(
+                    &event_name,
+                    &StreamEvent::Error {
+                        message: error.clone(),
+                    },
+                );
+            }
+        }
+        result
+    }
+
+    async fn stream_completion_attempt(
+        &self,
+        window: &tauri::Window,
+        mut request: StreamTextRequest,
+        request_id: String,
+        has_fallback: bool,
+    ) -> Result<String, String> {
+        let event_name = format!("llm-stream-{}", request_id);
+
         log::info!(
             "[LLM Stream {}] Starting stream completion for model: {}",
             request_id,
@@ -90,6 +244,8 @@ impl StreamHandler {
             model_key,
             provider_id
         );
+        capability_checks::check_request(&self.api_keys, &model_key, &mut request).await?;
+        system_prompt::apply_global_system_prompt(&self.api_keys, &mut request).await?;
         let provider = self
             .registry
             .create_provider(&provider_id)
@@ -102,9 +258,12 @@ impl StreamHandler {
             provider_config.protocol
         );
 
+        let api_keys = self
+            .api_keys
+            .with_oauth_account(request.oauth_account.clone());
         let provider_ctx = ProviderContext {
             provider_config,
-            api_key_manager: &self.api_keys,
+            api_key_manager: &api_keys,
             model: &provider_model_name,
             messages: &request.messages,
             tools: request.tools.as_deref(),
@@ -120,6 +279,9 @@ impl StreamHandler {
             transport_session_id: request.transport_session_id.as_deref(),
             allow_transport_fallback: request.allow_transport_fallback,
             continuation_context: request.continuation_context.as_ref(),
+            response_format: request.response_format.as_ref(),
+            seed: request.seed,
+            stop: request.stop.as_deref(),
         };
 
         let built_request = provider.build_complete_request(&provider_ctx).await?;
@@ -171,40 +333,8 @@ impl StreamHandler {
 
             let request_transport = built_request.transport;
             let request_route = built_request.route;
-            let mut attributes = HashMap::new();
-            attributes.insert(
-                crate::llm::tracing::types::attributes::GEN_AI_REQUEST_MODEL.to_string(),
-                crate::llm::tracing::types::string_attr(&provider_model_name),
-            );
-            attributes.insert(
-                crate::llm::tracing::types::attributes::GEN_AI_SYSTEM.to_string(),
-                crate::llm::tracing::types::string_attr(&provider_id),
-            );
-
-            if let Some(t) = request.temperature {
-                attributes.insert(
-                    crate::llm::tracing::types::attributes::GEN_AI_REQUEST_TEMPERATURE.to_string(),
-                    float_attr(t as f64),
-                );
-            }
-            if let Some(p) = request.top_p {
-                attributes.insert(
-                    crate::llm::tracing::types::attributes::GEN_AI_REQUEST_TOP_P.to_string(),
-                    float_attr(p as f64),
-                );
-            }
-            if let Some(k) = request.top_k {
-                attributes.insert(
-                    crate::llm::tracing::types::attributes::GEN_AI_REQUEST_TOP_K.to_string(),
-                    int_attr(k as i64),
-                );
-            }
-            if let Some(m) = request.max_tokens {
-                attributes.insert(
-                    crate::llm::tracing::types::attributes::GEN_AI_REQUEST_MAX_TOKENS.to_string(),
-                    int_attr(m as i64),
-                );
-            }
+            let attributes =
+                Self::request_trace_attributes(&request, &provider_model_name, &provider_id);
 
             let span_id = trace_writer.start_span(
                 trace_id,
@@ -326,17 +456,7 @@ impl StreamHandler {
             Duration::from_secs(300)
         };
 
-        let client = HTTP_CLIENT.get_or_init(|| {
-            reqwest::Client::builder()
-                .connect_timeout(Duration::from_secs(10))
-                .timeout(Duration::from_secs(3000)) // Add overall request timeout
-                .gzip(false)
-                .brotli(false)
-                .tcp_nodelay(true)
-                .pool_max_idle_per_host(5)
-                .build()
-                .expect("Failed to build HTTP client")
-        });
+        let client = &crate::network_proxy::shared_client();
         log::debug!("[LLM Stream {}] HTTP client ready", request_id);
 
         let mut state = StreamParseState::default();
@@ -355,7 +475,7 @@ impl StreamHandler {
                 &built_request,
                 |event| {
                     self.handle_stream_event(
-                        &window,
+                        window,
                         &event_name,
                         &request_id,
                         &event,
@@ -378,7 +498,7 @@ impl StreamHandler {
                     state.response_metadata_transport =
                         Some(crate::llm::types::ResponseTransport::HttpSse);
                     self.execute_http_sse_stream(
-                        &window,
+                        window,
                         &event_name,
                         &request_id,
                         &provider_ctx,
@@ -398,6 +518,7 @@ impl StreamHandler {
                         &mut response_text,
                         &mut recorder,
                         client,
+                        has_fallback,
                     )
                     .await?;
                 }
@@ -424,7 +545,7 @@ impl StreamHandler {
             }
         } else {
             self.execute_http_sse_stream(
-                &window,
+                window,
                 &event_name,
                 &request_id,
                 &provider_ctx,
@@ -444,6 +565,7 @@ impl StreamHandler {
                 &mut response_text,
                 &mut recorder,
                 client,
+                has_fallback,
             )
             .await?;
         }
@@ -521,6 +643,11 @@ impl StreamHandler {
             trace_writer.end_span(span_id.clone(), chrono::Utc::now().timestamp_millis());
         }
 
+        if let Some(usage) = trace_usage {
+            self.record_session_usage(window, &request, &model_key, usage)
+                .await;
+        }
+
         if !done_emitted {
             let _ = window.emit(
                 &event_name,
@@ -560,6 +687,7 @@ impl StreamHandler {
         response_text: &mut String,
         recorder: &mut Option<Recorder>,
         client: &reqwest::Client,
+        has_fallback: bool,
     ) -> Result<(), String> {
         let mut response = None;
         let mut last_error: Option<String> = None;
@@ -569,10 +697,10 @@ impl StreamHandler {
             for (key, value) in headers {
                 req_builder = req_builder.header(key, value);
             }
-            if let Some(timeout_override) = request_timeout_override {
-                req_builder = req_builder.timeout(timeout_override);
-            }
-            req_builder = req_builder.header("Accept", "text/event-stream").json(body);
+            req_builder = req_builder
+                .timeout(request_timeout_override.unwrap_or(STREAM_REQUEST_TIMEOUT))
+                .header("Accept", "text/event-stream")
+                .json(body);
 
             if attempt > 0 {
                 let delay_ms = transient_provider_retry_delay_ms(attempt);
@@ -664,6 +792,14 @@ impl StreamHandler {
                         })),
                     );
                 }
+                if is_context_length_error(status, &text) {
+                    // stream_completion compacts and retries, then reports the outcome
+                    return Err(context_overflow::context_length_error(status, &text));
+                }
+                if has_fallback && model_fallback::is_unavailable_http_error(status, &text) {
+                    // stream_completion moves on to the next model, which reports the outcome
+                    return Err(model_fallback::model_unavailable_error(status, &text));
+                }
                 let error_event = StreamEvent::Error {
                     message: format!("HTTP {}: {}", status, text),
                 };
@@ -684,7 +820,7 @@ impl StreamHandler {
         let status = response.status().as_u16();
         let response_headers = response.headers().clone();
         let mut stream = response.bytes_stream();
-        let mut buffer: Vec<u8> = Vec::new();
+        let mut buffer = SseBuffer::new();
         let mut chunk_count = 0;
         const STREAM_MAX_RETRIES: u32 = 3;
         const STREAM_BASE_DELAY_MS: u64 = 1000;
@@ -791,12 +927,9 @@ impl StreamHandler {
                 continue;
             }
 
-            buffer.extend_from_slice(&bytes);
-
-            while let Some((idx, delimiter_len)) = Self::find_sse_delimiter(&buffer) {
-                let event_bytes = buffer[..idx].to_vec();
-                buffer.drain(..idx + delimiter_len);
+            buffer.extend(&bytes);
 
+            while let Some(event_bytes) = buffer.next_event() {
                 let event_str = match String::from_utf8(event_bytes) {
                     Ok(value) => value,
                     Err(err) => {
@@ -1006,6 +1139,82 @@ impl StreamHandler {
         }
     }
 
+    /// The `disable_global_system_prompt` task setting of the request's session
+    async fn session_disables_global_system_prompt(
+        &self,
+        window: &tauri::Window,
+        request: &StreamTextRequest,
+    ) -> Option<bool> {
+        let session_id = usage_session_id(request)?;
+        let storage = window.app_handle().try_state::<Storage>()?;
+        match storage.settings.resolve_task_settings(&session_id).await {
+            Ok(settings) => settings.disable_global_system_prompt,
+            Err(e) => {
+                log::debug!("No task settings for session {}: {}", session_id, e);
+                None
+            }
+        }
+    }
+
+    /// Persist token usage and cost for the session the request belongs to
+    async fn record_session_usage(
+        &self,
+        window: &tauri::Window,
+        request: &StreamTextRequest,
+        model_key: &str,
+        usage: TokenUsageInfo,
+    ) {
+        let Some(session_id) = usage_session_id(request) else {
+            return;
+        };
+        let Some(storage) = window.app_handle().try_state::<Storage>() else {
+            return;
+        };
+
+        let (input_tokens, output_tokens, _, cached_input_tokens, cache_creation_input_tokens) =
+            usage;
+        let token_usage = TokenUsage {
+            input_tokens: input_tokens.max(0) as u32,
+            output_tokens: output_tokens.max(0) as u32,
+            cached_input_tokens: cached_input_tokens.map(|value| value.max(0) as u32),
+            cache_creation_input_tokens: cache_creation_input_tokens
+                .map(|value| value.max(0) as u32),
+        };
+        // Unknown pricing is stored as NULL so it isn't mistaken for a free request
+        let cost = match self.api_keys.load_models_config().await {
+            Ok(config) => {
+                PricingService::new().try_calculate_cost(model_key, &token_usage, &config.models)
+            }
+            Err(e) => {
+                log::warn!(
+                    "[LLM Stream] Failed to load pricing for {}: {}",
+                    model_key,
+                    e
+                );
+                None
+            }
+        };
+
+        let record = UsageRecord {
+            id: uuid::Uuid::new_v4().to_string(),
+            session_id,
+            model: model_key.to_string(),
+            input_tokens: i64::from(input_tokens),
+            output_tokens: i64::from(output_tokens),
+            cached_input_tokens: i64::from(cached_input_tokens.unwrap_or(0)),
+            cache_creation_input_tokens: i64::from(cache_creation_input_tokens.unwrap_or(0)),
+            cost,
+            created_at: chrono::Utc::now().timestamp(),
+        };
+        if let Err(e) = storage.chat_history.record_usage(&record).await {
+            log::warn!(
+                "[LLM Stream] Failed to record usage for session {}: {}",
+                record.session_id,
+                e
+            );
+        }
+    }
+
     fn capture_trace_event(
         trace_usage: &mut Option<TokenUsageInfo>,
         trace_finish_reason: &mut Option<String>,
@@ -1089,20 +1298,6 @@ impl StreamHandler {
         Ok((model_key, provider_id, provider_model_name))
     }
 
-    /// Find SSE delimiter in buffer, returns (index, delimiter_length)
-    /// Handles both \n\n and \r\n\r\n delimiters
-    fn find_sse_delimiter(buf: &[u8]) -> Option<(usize, usize)> {
-        // First check for \r\n\r\n (4 bytes)
-        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
-            return Some((pos, 4));
-        }
-        // Then check for \n\n (2 bytes)
-        if let Some(pos) = buf.windows(2).position(|w| w == b"\n\n") {
-            return Some((pos, 2));
-        }
-        None
-    }
-
     fn parse_sse_event(raw: &str) -> Option<SseEvent> {
         let mut event: Option<String> = None;
         let mut data_lines = Vec::new();
@@ -1146,6 +1341,55 @@ impl StreamHandler {
         let _ = window.emit(event_name, event);
     }
 
+    /// Span attributes describing the request parameters
+    fn request_trace_attributes(
+        request: &StreamTextRequest,
+        provider_model_name: &str,
+        provider_id: &str,
+    ) -> HashMap<String, serde_json::Value> {
+        let mut attributes = HashMap::new();
+        attributes.insert(
+            crate::llm::tracing::types::attributes::GEN_AI_REQUEST_MODEL.to_string(),
+            crate::llm::tracing::types::string_attr(provider_model_name),
+        );
+        attributes.insert(
+            crate::llm::tracing::types::attributes::GEN_AI_SYSTEM.to_string(),
+            crate::llm::tracing::types::string_attr(provider_id),
+        );
+
+        if let Some(t) = request.temperature {
+            attributes.insert(
+                crate::llm::tracing::types::attributes::GEN_AI_REQUEST_TEMPERATURE.to_string(),
+                float_attr(t as f64),
+            );
+        }
+        if let Some(p) = request.top_p {
+            attributes.insert(
+                crate::llm::tracing::types::attributes::GEN_AI_REQUEST_TOP_P.to_string(),
+                float_attr(p as f64),
+            );
+        }
+        if let Some(k) = request.top_k {
+            attributes.insert(
+                crate::llm::tracing::types::attributes::GEN_AI_REQUEST_TOP_K.to_string(),
+                int_attr(k as i64),
+            );
+        }
+        if let Some(m) = request.max_tokens {
+            attributes.insert(
+                crate::llm::tracing::types::attributes::GEN_AI_REQUEST_MAX_TOKENS.to_string(),
+                int_attr(m as i64),
+            );
+        }
+        if let Some(seed) = request.seed {
+            attributes.insert(
+                crate::llm::tracing::types::attributes::GEN_AI_REQUEST_SEED.to_string(),
+                int_attr(seed),
+            );
+        }
+        attributes
+    }
+
     fn build_response_payload(
         finish_reason: Option<&str>,
         ttft_ms: Option<i64>,
@@ -1227,6 +1471,56 @@ mod tests {
     use std::sync::Arc;
     use tempfile::TempDir;
 
+    #[test]
+    fn usage_session_id_prefers_request_then_trace_metadata() {
+        let mut request = StreamTextRequest {
+            model: "gpt-5".to_string(),
+            fallback_models: None,
+            messages: vec![],
+            tools: None,
+            stream: Some(true),
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
+            top_k: None,
+            provider_options: None,
+            request_id: None,
+            conversation_mode: None,
+            input_mode: None,
+            previous_response_id: None,
+            transport_session_id: None,
+            allow_transport_fallback: None,
+            oauth_account: None,
+            disable_global_system_prompt: None,
+            response_format: None,
+            seed: None,
+            stop: None,
+            continuation_context: None,
+            session_id: None,
+            trace_context: None,
+        };
+        assert_eq!(usage_session_id(&request), None);
+
+        request.trace_context = Some(crate::llm::types::TraceContext {
+            trace_id: Some("task-1".to_string()),
+            parent_span_id: None,
+            span_name: None,
+            metadata: None,
+        });
+        assert_eq!(usage_session_id(&request), Some("task-1".to_string()));
+
+        request.trace_context.as_mut().unwrap().metadata = Some(HashMap::from([(
+            "session_id".to_string(),
+            "sess-9".to_string(),
+        )]));
+        assert_eq!(usage_session_id(&request), Some("sess-9".to_string()));
+
+        // With tracing off the request still names its session
+        request.trace_context = None;
+        request.session_id = Some("sess-10".to_string());
+        assert_eq!(usage_session_id(&request), Some("sess-10".to_string()));
+    }
+
     #[test]
     fn detects_decode_response_body_error() {
         assert!(StreamHandler::is_decode_response_body_error(
@@ -1323,6 +1617,9 @@ mod tests {
             transport_session_id: None,
             allow_transport_fallback: None,
             continuation_context: None,
+            response_format: None,
+            seed: None,
+            stop: None,
         };
 
         let base_url = provider
@@ -1376,7 +1673,13 @@ mod tests {
             previous_response_id: None,
             transport_session_id: None,
             allow_transport_fallback: None,
+            oauth_account: None,
+            disable_global_system_prompt: None,
+            response_format: None,
+            seed: None,
+            stop: None,
             continuation_context: None,
+            session_id: None,
             trace_context: None,
         };
 
@@ -1398,6 +1701,9 @@ mod tests {
             transport_session_id: request.transport_session_id.as_deref(),
             allow_transport_fallback: request.allow_transport_fallback,
             continuation_context: request.continuation_context.as_ref(),
+            response_format: request.response_format.as_ref(),
+            seed: request.seed,
+            stop: request.stop.as_deref(),
         };
 
         let endpoint = provider.resolve_endpoint_path(&ctx).await;
@@ -1456,7 +1762,13 @@ mod tests {
             previous_response_id: None,
             transport_session_id: None,
             allow_transport_fallback: None,
+            oauth_account: None,
+            disable_global_system_prompt: None,
+            response_format: None,
+            seed: None,
+            stop: None,
             continuation_context: None,
+            session_id: None,
             trace_context: None,
         };
 
@@ -1478,6 +1790,9 @@ mod tests {
             transport_session_id: request.transport_session_id.as_deref(),
             allow_transport_fallback: request.allow_transport_fallback,
             continuation_context: request.continuation_context.as_ref(),
+            response_format: request.response_format.as_ref(),
+            seed: request.seed,
+            stop: request.stop.as_deref(),
         };
 
         let endpoint = provider.resolve_endpoint_path(&ctx).await;
@@ -1559,7 +1874,13 @@ mod tests {
             previous_response_id: None,
             transport_session_id: None,
             allow_transport_fallback: None,
+            oauth_account: None,
+            disable_global_system_prompt: None,
+            response_format: None,
+            seed: None,
+            stop: None,
             continuation_context: None,
+            session_id: None,
             trace_context: None,
         };
 
@@ -1579,6 +1900,9 @@ mod tests {
             transport_session_id: request.transport_session_id.as_deref(),
             allow_transport_fallback: request.allow_transport_fallback,
             continuation_context: request.continuation_context.as_ref(),
+            response_format: request.response_format.as_ref(),
+            seed: request.seed,
+            stop: request.stop.as_deref(),
         };
         let body = OpenAiResponsesProtocol
             .build_request(request_ctx)
@@ -1762,13 +2086,6 @@ mod tests {
         assert_eq!(tool_calls, vec!["call_b".to_string(), "call_a".to_string()]);
     }
 
-    #[test]
-    fn find_sse_delimiter_prefers_crlf() {
-        let data = b"event: ping\r\n\r\n";
-        let delimiter = StreamHandler::find_sse_delimiter(data);
-        assert_eq!(delimiter, Some((11, 4)));
-    }
-
     #[test]
     fn build_response_payload_includes_response_text() {
         let payload = StreamHandler::build_response_payload(
@@ -1791,6 +2108,34 @@ mod tests {
         assert_eq!(payload["response_text"], json!("final response"));
     }
 
+    #[test]
+    fn request_trace_attributes_record_seed() {
+        use crate::llm::tracing::types::attributes;
+
+        let request: StreamTextRequest = serde_json::from_value(json!({
+            "model": "gpt-4o@openai",
+            "messages": [],
+            "temperature": 0.5,
+            "seed": 42
+        }))
+        .expect("request");
+
+        let attrs = StreamHandler::request_trace_attributes(&request, "gpt-4o", "openai");
+
+        assert_eq!(attrs[attributes::GEN_AI_REQUEST_SEED], json!(42));
+        assert_eq!(attrs[attributes::GEN_AI_REQUEST_MODEL], json!("gpt-4o"));
+        assert_eq!(attrs[attributes::GEN_AI_SYSTEM], json!("openai"));
+        assert!(attrs.contains_key(attributes::GEN_AI_REQUEST_TEMPERATURE));
+
+        let request = StreamTextRequest {
+            seed: None,
+            stop: None,
+            ..request
+        };
+        let attrs = StreamHandler::request_trace_attributes(&request, "gpt-4o", "openai");
+        assert!(!attrs.contains_key(attributes::GEN_AI_REQUEST_SEED));
+    }
+
     #[test]
     fn parse_sse_event_preserves_data_lines() {
         let raw = "event: message\ndata: first\ndata: second\n";
@@ -1845,6 +2190,9 @@ mod tests {
             transport_session_id: None,
             allow_transport_fallback: None,
             continuation_context: None,
+            response_format: None,
+            seed: None,
+            stop: None,
         };
 
         let base_url = provider
@@ -2129,7 +2477,13 @@ mod tests {
             previous_response_id: None,
             transport_session_id: None,
             allow_transport_fallback: None,
+            oauth_account: None,
+            disable_global_system_prompt: None,
+            response_format: None,
+            seed: None,
+            stop: None,
             continuation_context: None,
+            session_id: None,
             trace_context: None,
         };
 
@@ -2149,6 +2503,9 @@ mod tests {
             transport_session_id: request.transport_session_id.as_deref(),
             allow_transport_fallback: request.allow_transport_fallback,
             continuation_context: request.continuation_context.as_ref(),
+            response_format: request.response_format.as_ref(),
+            seed: request.seed,
+            stop: request.stop.as_deref(),
         };
         let body = OpenAiResponsesProtocol
             .build_request(request_ctx)
@@ -0,0 +1,232 @@
+// Global system prompt injection
+// Wraps the system message of every stream with user-configured instructions
+// (coding standards, language preference) so agents don't each have to repeat them.
+
+use crate::llm::auth::api_key_manager::ApiKeyManager;
+use crate::llm::types::{Message, StreamTextRequest};
+
+/// Settings key for text prepended to the system message
+pub const GLOBAL_SYSTEM_PREFIX_SETTING: &str = "global_system_prefix";
+/// Settings key for text appended to the system message
+pub const GLOBAL_SYSTEM_SUFFIX_SETTING: &str = "global_system_suffix";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GlobalSystemPrompt {
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+}
+
+impl GlobalSystemPrompt {
+    pub async fn load(api_keys: &ApiKeyManager) -> Result<Self, String> {
+        let non_blank = |value: Option<String>| value.filter(|value| !value.trim().is_empty());
+        Ok(Self {
+            prefix: non_blank(api_keys.get_setting(GLOBAL_SYSTEM_PREFIX_SETTING).await?),
+            suffix: non_blank(api_keys.get_setting(GLOBAL_SYSTEM_SUFFIX_SETTING).await?),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prefix.is_none() && self.suffix.is_none()
+    }
+
+    /// Wrap the first system message, or add one when there is none.
+    /// Protocols map it as usual, e.g. to the Responses "developer" role.
+    pub fn apply(&self, messages: &mut Vec<Message>) {
+        if self.is_empty() {
+            return;
+        }
+        let existing = messages.iter_mut().find_map(|message| match message {
+            Message::System { content, .. } => Some(content),
+            _ => None,
+        });
+        let wrap = |content: &str| {
+            [
+                self.prefix.as_deref(),
+                Some(content),
+                self.suffix.as_deref(),
+            ]
+            .into_iter()
+            .flatten()
+            .filter(|part| !part.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+        };
+        match existing {
+            Some(content) => *content = wrap(content),
+            None => messages.insert(
+                0,
+                Message::System {
+                    content: wrap(""),
+                    provider_options: None,
+                },
+            ),
+        }
+    }
+}
+
+/// Apply the configured prefix/suffix unless the request's session opted out.
+/// JSON-mode requests are left alone: user instructions could break the schema.
+/// Internal services (titles, commit messages, compaction) never call this.
+pub async fn apply_global_system_prompt(
+    api_keys: &ApiKeyManager,
+    request: &mut StreamTextRequest,
+) -> Result<(), String> {
+    if request.disable_global_system_prompt == Some(true) || request.response_format.is_some() {
+        return Ok(());
+    }
+    GlobalSystemPrompt::load(api_keys)
+        .await?
+        .apply(&mut request.messages);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::llm::protocols::openai_responses_protocol::OpenAiResponsesProtocol;
+    use crate::llm::protocols::request_builder::{ProtocolRequestBuilder, RequestBuildContext};
+    use crate::llm::types::{MessageContent, ResponseFormat, ResponseFormatType};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    async fn setup() -> (ApiKeyManager, TempDir) {
+        let dir = TempDir::new().expect("temp dir");
+        let db_path = dir.path().join("system-prompt.db");
+        let db = Arc::new(Database::new(db_path.to_string_lossy().to_string()));
+        db.connect().await.expect("db connect");
+        db.execute(
+            "CREATE TABLE IF NOT EXISTS settings (key TEXT PRIMARY KEY, value TEXT, updated_at INTEGER)",
+            vec![],
+        )
+        .await
+        .expect("create settings");
+        let api_keys = ApiKeyManager::new(db, std::path::PathBuf::from("/tmp"));
+        api_keys
+            .set_setting(GLOBAL_SYSTEM_PREFIX_SETTING, "Answer in French.")
+            .await
+            .expect("set prefix");
+        api_keys
+            .set_setting(GLOBAL_SYSTEM_SUFFIX_SETTING, "Follow the team style guide.")
+            .await
+            .expect("set suffix");
+        (api_keys, dir)
+    }
+
+    fn request(system: Option<&str>) -> StreamTextRequest {
+        let mut messages = vec![serde_json::json!({ "role": "user", "content": "hi" })];
+        if let Some(system) = system {
+            messages.insert(
+                0,
+                serde_json::json!({ "role": "system", "content": system }),
+            );
+        }
+        serde_json::from_value(serde_json::json!({
+            "model": "gpt-5.5@openai",
+            "messages": messages
+        }))
+        .unwrap()
+    }
+
+    fn build_responses_body(request: &StreamTextRequest) -> serde_json::Value {
+        let ctx = RequestBuildContext {
+            model: "gpt-5.5",
+            messages: &request.messages,
+            tools: None,
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
+            top_k: None,
+            provider_options: None,
+            extra_body: None,
+            conversation_mode: None,
+            input_mode: None,
+            previous_response_id: None,
+            transport_session_id: None,
+            allow_transport_fallback: None,
+            continuation_context: None,
+            response_format: None,
+            seed: None,
+            stop: None,
+        };
+        OpenAiResponsesProtocol
+            .build_request(ctx)
+            .expect("build request")
+    }
+
+    #[tokio::test]
+    async fn test_wraps_system_message_in_built_request() {
+        let (api_keys, _dir) = setup().await;
+        let mut request = request(Some("You are a coding agent."));
+
+        apply_global_system_prompt(&api_keys, &mut request)
+            .await
+            .unwrap();
+
+        let body = build_responses_body(&request);
+        let developer = body["input"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|item| item["role"] == "developer")
+            .expect("developer message");
+        assert_eq!(
+            developer["content"][0]["text"],
+            "Answer in French.\n\nYou are a coding agent.\n\nFollow the team style guide."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_adds_system_message_when_missing() {
+        let (api_keys, _dir) = setup().await;
+        let mut request = request(None);
+
+        apply_global_system_prompt(&api_keys, &mut request)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            &request.messages[0],
+            Message::System { content, .. }
+                if content == "Answer in French.\n\nFollow the team style guide."
+        ));
+        assert!(matches!(
+            &request.messages[1],
+            Message::User { content: MessageContent::Text(text), .. } if text == "hi"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_session_override_suppresses_injection() {
+        let (api_keys, _dir) = setup().await;
+        let mut request = request(Some("You are a coding agent."));
+        request.disable_global_system_prompt = Some(true);
+
+        apply_global_system_prompt(&api_keys, &mut request)
+            .await
+            .unwrap();
+
+        let body = build_responses_body(&request);
+        assert!(!body.to_string().contains("Answer in French."));
+        assert!(!body.to_string().contains("Follow the team style guide."));
+    }
+
+    #[tokio::test]
+    async fn test_json_mode_request_is_left_alone() {
+        let (api_keys, _dir) = setup().await;
+        let mut request = request(Some("Reply with JSON."));
+        request.response_format = Some(ResponseFormat {
+            format_type: ResponseFormatType::JsonObject,
+            schema: None,
+        });
+
+        apply_global_system_prompt(&api_keys, &mut request)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            &request.messages[0],
+            Message::System { content, .. } if content == "Reply with JSON."
+        ));
+    }
+}
@@ -48,6 +48,9 @@ fn perf_openai_build_request() {
         transport_session_id: None,
         allow_transport_fallback: None,
         continuation_context: None,
+        response_format: None,
+        seed: None,
+        stop: None,
     };
 
     let iterations = 300;
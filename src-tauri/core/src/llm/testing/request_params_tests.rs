@@ -46,7 +46,13 @@ fn build_test_context(
         previous_response_id: None,
         transport_session_id: None,
         allow_transport_fallback: None,
+        oauth_account: None,
+        disable_global_system_prompt: None,
+        response_format: None,
+        seed: None,
+        stop: None,
         continuation_context: None,
+        session_id: None,
         trace_context: None,
     };
 
@@ -76,6 +82,9 @@ async fn google_provider_strips_top_k() {
         transport_session_id: request.transport_session_id.as_deref(),
         allow_transport_fallback: request.allow_transport_fallback,
         continuation_context: request.continuation_context.as_ref(),
+        response_format: request.response_format.as_ref(),
+        seed: request.seed,
+        stop: request.stop.as_deref(),
     };
 
     let body = provider.build_request(&ctx).await.expect("build request");
@@ -104,6 +113,9 @@ async fn non_google_provider_keeps_top_k() {
         transport_session_id: request.transport_session_id.as_deref(),
         allow_transport_fallback: request.allow_transport_fallback,
         continuation_context: request.continuation_context.as_ref(),
+        response_format: request.response_format.as_ref(),
+        seed: request.seed,
+        stop: request.stop.as_deref(),
     };
 
     let body = provider.build_request(&ctx).await.expect("build request");
@@ -132,6 +144,9 @@ async fn non_openai_provider_transport_remains_http_sse() {
         transport_session_id: Some("session-123"),
         allow_transport_fallback: Some(true),
         continuation_context: request.continuation_context.as_ref(),
+        response_format: request.response_format.as_ref(),
+        seed: request.seed,
+        stop: request.stop.as_deref(),
     };
 
     assert_eq!(
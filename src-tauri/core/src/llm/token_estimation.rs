@@ -0,0 +1,505 @@
+use crate::llm::types::{ContentPart, Message, MessageContent, ToolDefinition};
+use serde::{Deserialize, Serialize};
+
+/// Formatting tokens each message adds around its content (role markers, separators)
+pub const MESSAGE_OVERHEAD_TOKENS: usize = 3;
+/// Tokens that prime the assistant reply after the last message
+pub const REPLY_PRIMING_TOKENS: usize = 3;
+/// Wrapper tokens around each tool definition in the request
+pub const TOOL_DEFINITION_OVERHEAD_TOKENS: usize = 8;
+/// Flat cost for an image or video part (a typical high-detail image)
+pub const MEDIA_PART_TOKENS: usize = 765;
+
+/// How a token estimate was computed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EstimationMethod {
+    /// CJK characters count as one token, everything else as 4 characters per token
+    Heuristic,
+    /// BPE approximation of OpenAI's cl100k_base encoding (GPT-4, GPT-3.5)
+    BpeCl100k,
+    /// BPE approximation of OpenAI's o200k_base encoding (GPT-4o and newer)
+    BpeO200k,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenEstimate {
+    pub tokens: usize,
+    pub method: EstimationMethod,
+}
+
+/// Estimates tokens for `text`, using the BPE approximation when `model` is a
+/// recognized OpenAI model and the character heuristic otherwise
+pub fn estimate_tokens(text: &str, model: Option<&str>) -> TokenEstimate {
+    match model.and_then(bpe_method_for_model) {
+        Some(method) => TokenEstimate {
+            tokens: estimate_tokens_bpe(text, method),
+            method,
+        },
+        None => TokenEstimate {
+            tokens: estimate_tokens_heuristic(text),
+            method: EstimationMethod::Heuristic,
+        },
+    }
+}
+
+/// Token estimate for a whole request, split by where the tokens come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessagesTokenEstimate {
+    pub system: usize,
+    pub user: usize,
+    /// Assistant messages plus the reply priming tokens
+    pub assistant: usize,
+    /// Tool definitions plus tool-result messages
+    pub tools: usize,
+    pub total: usize,
+    pub method: Option<EstimationMethod>,
+}
+
+/// Estimates a conversation's token usage, including per-message overhead
+/// and tool definitions. Each string is costed with [`estimate_tokens`].
+pub fn estimate_messages_tokens(
+    messages: &[Message],
+    tools: &[ToolDefinition],
+    model: Option<&str>,
+) -> MessagesTokenEstimate {
+    let mut method = None;
+    let mut estimate_text = |text: &str| {
+        if text.is_empty() {
+            return 0;
+        }
+        let estimate = estimate_tokens(text, model);
+        method = Some(estimate.method);
+        estimate.tokens
+    };
+
+    let mut breakdown = MessagesTokenEstimate::default();
+    for message in messages {
+        match message {
+            Message::System { content, .. } => {
+                breakdown.system += MESSAGE_OVERHEAD_TOKENS + estimate_text(content);
+            }
+            Message::User { content, .. } => {
+                breakdown.user +=
+                    MESSAGE_OVERHEAD_TOKENS + content_tokens(content, &mut estimate_text);
+            }
+            Message::Assistant { content, .. } => {
+                breakdown.assistant +=
+                    MESSAGE_OVERHEAD_TOKENS + content_tokens(content, &mut estimate_text);
+            }
+            Message::Tool { content, .. } => {
+                breakdown.tools += MESSAGE_OVERHEAD_TOKENS
+                    + content
+                        .iter()
+                        .map(|part| part_tokens(part, &mut estimate_text))
+                        .sum::<usize>();
+            }
+        }
+    }
+
+    for tool in tools {
+        let parameters = serde_json::to_string(&tool.parameters).unwrap_or_default();
+        breakdown.tools += TOOL_DEFINITION_OVERHEAD_TOKENS
+            + estimate_text(&tool.name)
+            + estimate_text(tool.description.as_deref().unwrap_or_default())
+            + estimate_text(&parameters);
+    }
+
+    if !messages.is_empty() {
+        breakdown.assistant += REPLY_PRIMING_TOKENS;
+    }
+
+    breakdown.total = breakdown.system + breakdown.user + breakdown.assistant + breakdown.tools;
+    breakdown.method = method;
+    breakdown
+}
+
+fn content_tokens(
+    content: &MessageContent,
+    estimate_text: &mut impl FnMut(&str) -> usize,
+) -> usize {
+    match content {
+        MessageContent::Text(text) => estimate_text(text),
+        MessageContent::Parts(parts) => parts
+            .iter()
+            .map(|part| part_tokens(part, estimate_text))
+            .sum(),
+    }
+}
+
+fn part_tokens(part: &ContentPart, estimate_text: &mut impl FnMut(&str) -> usize) -> usize {
+    match part {
+        ContentPart::Text { text } | ContentPart::Reasoning { text, .. } => estimate_text(text),
+        ContentPart::Image { .. } | ContentPart::Video { .. } => MEDIA_PART_TOKENS,
+        ContentPart::ToolCall {
+            tool_name, input, ..
+        } => estimate_text(tool_name) + estimate_text(&input.to_string()),
+        ContentPart::ToolResult { output, .. } => match output.as_str() {
+            Some(text) => estimate_text(text),
+            None => estimate_text(&output.to_string()),
+        },
+    }
+}
+
+/// Fast character-based estimate: 1 token per CJK character, 4 other characters per token
+pub fn estimate_tokens_heuristic(text: &str) -> usize {
+    let mut cjk_count = 0;
+    let mut other_count = 0;
+    for c in text.chars() {
+        if is_cjk_char(c) {
+            cjk_count += 1;
+        } else {
+            other_count += 1;
+        }
+    }
+    let other_tokens = if other_count > 0 {
+        (other_count / 4).max(1)
+    } else {
+        0
+    };
+    (cjk_count + other_tokens).max(1)
+}
+
+#[inline]
+pub fn is_cjk_char(c: char) -> bool {
+    matches!(c, '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' | '\u{F900}'..='\u{FAFF}' | '\u{3040}'..='\u{309F}' | '\u{30A0}'..='\u{30FF}' | '\u{AC00}'..='\u{D7AF}')
+}
+
+/// Picks the BPE approximation for a model id such as `gpt-4o`,
+/// `openai/gpt-4.1-mini` or `gpt-4@openai`
+pub fn bpe_method_for_model(model: &str) -> Option<EstimationMethod> {
+    let name = model.split('@').next().unwrap_or(model);
+    let name = name.rsplit('/').next().unwrap_or(name).to_lowercase();
+
+    const O200K_PREFIXES: &[&str] = &[
+        "gpt-4o",
+        "chatgpt-4o",
+        "gpt-4.1",
+        "gpt-4.5",
+        "gpt-5",
+        "gpt-oss",
+        "o1",
+        "o3",
+        "o4",
+    ];
+    const CL100K_PREFIXES: &[&str] =
+        &["gpt-4", "gpt-3.5", "text-embedding-3", "text-embedding-ada"];
+
+    if O200K_PREFIXES.iter().any(|prefix| name.starts_with(prefix)) {
+        Some(EstimationMethod::BpeO200k)
+    } else if CL100K_PREFIXES
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+    {
+        Some(EstimationMethod::BpeCl100k)
+    } else {
+        None
+    }
+}
+
+/// Approximates a tiktoken-style BPE count without the vocabulary. Text is
+/// split the way the cl100k/o200k pre-tokenizer does (words with their
+/// leading space, digits in groups of three, punctuation runs, newlines), then
+/// each piece is costed: short words are a single token, long or camelCase
+/// words split, and CJK is roughly one token per character.
+pub fn estimate_tokens_bpe(text: &str, method: EstimationMethod) -> usize {
+    let chars: Vec<char> = text.chars().collect();
+    let len = chars.len();
+    let is_punct = |c: char| !c.is_whitespace() && !c.is_alphanumeric();
+    let mut total = 0.0f64;
+    let mut i = 0;
+
+    while i < len {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if c == '\'' {
+            if let Some(contraction) = contraction_len(&chars[i + 1..]) {
+                total += 1.0;
+                i += 1 + contraction;
+                continue;
+            }
+        }
+
+        if c.is_alphabetic() {
+            i = consume_word(&chars, i, method, &mut total);
+        } else if c.is_numeric() {
+            let end = run_end(&chars, i, char::is_numeric);
+            total += (end - i).div_ceil(3) as f64;
+            i = end;
+        } else if is_punct(c) && next.is_some_and(char::is_alphabetic) {
+            // Punctuation directly before a word often merges with it, e.g. `(a` or `.get`
+            total += 0.5;
+            i = consume_word(&chars, i + 1, method, &mut total);
+        } else if is_punct(c) {
+            i = consume_punct(&chars, i, &mut total);
+        } else if c == ' ' && next.is_some_and(char::is_alphabetic) {
+            i = consume_word(&chars, i + 1, method, &mut total);
+        } else if c == ' ' && next.is_some_and(is_punct) {
+            i = consume_punct(&chars, i + 1, &mut total);
+        } else {
+            let end = run_end(&chars, i, char::is_whitespace);
+            let run = &chars[i..end];
+            let trailing_spaces = run
+                .iter()
+                .rev()
+                .take_while(|c| **c != '\n' && **c != '\r')
+                .count();
+            let has_newline = trailing_spaces < run.len();
+            if has_newline {
+                total += 1.0;
+            }
+            // A single space before a word or punctuation belongs to that piece
+            let followed_by_piece = chars
+                .get(end)
+                .is_some_and(|n| n.is_alphabetic() || is_punct(*n));
+            let leave_one = followed_by_piece && trailing_spaces > 0;
+            let standalone = trailing_spaces - usize::from(leave_one);
+            if standalone > 0 {
+                total += 1.0;
+            }
+            i = if leave_one { end - 1 } else { end };
+        }
+    }
+
+    if len == 0 {
+        0
+    } else {
+        (total.round() as usize).max(1)
+    }
+}
+
+fn run_end(chars: &[char], start: usize, predicate: impl Fn(char) -> bool) -> usize {
+    let mut end = start;
+    while end < chars.len() && predicate(chars[end]) {
+        end += 1;
+    }
+    end
+}
+
+/// `'s`, `'t`, `'re`, `'ve`, `'m`, `'ll`, `'d` are single tokens
+fn contraction_len(rest: &[char]) -> Option<usize> {
+    let lower: String = rest.iter().take(2).flat_map(|c| c.to_lowercase()).collect();
+    let two = ["re", "ve", "ll"].iter().any(|s| lower.starts_with(s));
+    let one = ["s", "t", "m", "d"].iter().any(|s| lower.starts_with(s));
+    let len = if two {
+        2
+    } else if one {
+        1
+    } else {
+        return None;
+    };
+    // Only when the contraction ends the word, e.g. `don't` but not `'static`
+    match rest.get(len) {
+        Some(c) if c.is_alphabetic() => None,
+        _ => Some(len),
+    }
+}
+
+fn consume_word(chars: &[char], start: usize, method: EstimationMethod, total: &mut f64) -> usize {
+    let end = run_end(chars, start, char::is_alphabetic);
+    *total += word_tokens(&chars[start..end], method);
+    end
+}
+
+/// Punctuation run plus any newlines directly after it, e.g. `");\n`
+fn consume_punct(chars: &[char], start: usize, total: &mut f64) -> usize {
+    let end = run_end(chars, start, |c| !c.is_whitespace() && !c.is_alphanumeric());
+    *total += ((end - start).div_ceil(2)).max(1) as f64;
+    run_end(chars, end, |c| c == '\n' || c == '\r')
+}
+
+fn word_tokens(word: &[char], method: EstimationMethod) -> f64 {
+    let cjk_weight = match method {
+        EstimationMethod::BpeO200k => 0.8,
+        _ => 1.2,
+    };
+
+    let mut tokens = 0.0;
+    let mut segment_len = 0usize;
+    let mut segment_ascii = true;
+    let flush = |len: &mut usize, ascii: &mut bool, tokens: &mut f64| {
+        if *len > 0 {
+            *tokens += if !*ascii {
+                len.div_ceil(3) as f64
+            } else if *len <= 8 {
+                1.0
+            } else {
+                1.0 + (*len - 8).div_ceil(7) as f64
+            };
+        }
+        *len = 0;
+        *ascii = true;
+    };
+
+    for (index, &c) in word.iter().enumerate() {
+        if is_cjk_char(c) {
+            flush(&mut segment_len, &mut segment_ascii, &mut tokens);
+            tokens += cjk_weight;
+            continue;
+        }
+        // camelCase boundaries start a new sub-word
+        let camel_boundary =
+            c.is_uppercase() && index > 0 && word[index - 1].is_lowercase() && segment_len > 0;
+        if camel_boundary {
+            flush(&mut segment_len, &mut segment_ascii, &mut tokens);
+        }
+        segment_len += 1;
+        segment_ascii &= c.is_ascii();
+    }
+    flush(&mut segment_len, &mut segment_ascii, &mut tokens);
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// cl100k_base encodes this as 12 tokens:
+    /// `def`,` add`,`(a`,`,`,` b`,`):\n`,`   `,` return`,` a`,` +`,` b`,`\n`
+    const PYTHON_SNIPPET: &str = "def add(a, b):\n    return a + b\n";
+    const PYTHON_SNIPPET_TOKENS: usize = 12;
+
+    #[test]
+    fn test_heuristic_counts_cjk_per_char() {
+        assert_eq!(estimate_tokens_heuristic("你好世界"), 4);
+        assert_eq!(estimate_tokens_heuristic("abcdefgh"), 2);
+        assert_eq!(estimate_tokens_heuristic(""), 1);
+    }
+
+    #[test]
+    fn test_bpe_estimate_on_prose() {
+        // 9 words plus the period
+        let text = "The quick brown fox jumps over the lazy dog.";
+        assert_eq!(estimate_tokens_bpe(text, EstimationMethod::BpeCl100k), 10);
+    }
+
+    #[test]
+    fn test_bpe_closer_than_heuristic_on_code() {
+        let bpe = estimate_tokens_bpe(PYTHON_SNIPPET, EstimationMethod::BpeCl100k);
+        let heuristic = estimate_tokens_heuristic(PYTHON_SNIPPET);
+
+        assert!(
+            bpe.abs_diff(PYTHON_SNIPPET_TOKENS) <= 2,
+            "BPE estimate {} too far from {}",
+            bpe,
+            PYTHON_SNIPPET_TOKENS
+        );
+        assert!(bpe.abs_diff(PYTHON_SNIPPET_TOKENS) < heuristic.abs_diff(PYTHON_SNIPPET_TOKENS));
+    }
+
+    #[test]
+    fn test_bpe_splits_long_and_camel_case_words() {
+        let method = EstimationMethod::BpeCl100k;
+        assert_eq!(estimate_tokens_bpe("user", method), 1);
+        assert_eq!(estimate_tokens_bpe("getUserName", method), 3);
+        assert!(estimate_tokens_bpe("internationalization", method) >= 2);
+        assert_eq!(estimate_tokens_bpe("1234567", method), 3);
+        assert_eq!(estimate_tokens_bpe("", method), 0);
+    }
+
+    #[test]
+    fn test_model_selects_method() {
+        assert_eq!(
+            estimate_tokens("hello", Some("gpt-4o-mini")).method,
+            EstimationMethod::BpeO200k
+        );
+        assert_eq!(
+            estimate_tokens("hello", Some("openai/gpt-4-turbo")).method,
+            EstimationMethod::BpeCl100k
+        );
+        assert_eq!(
+            estimate_tokens("hello", Some("gpt-3.5-turbo@openai")).method,
+            EstimationMethod::BpeCl100k
+        );
+        assert_eq!(
+            estimate_tokens("hello", Some("claude-sonnet-4")).method,
+            EstimationMethod::Heuristic
+        );
+        assert_eq!(
+            estimate_tokens("hello", None).method,
+            EstimationMethod::Heuristic
+        );
+    }
+
+    fn conversation() -> Vec<Message> {
+        vec![
+            Message::System {
+                content: "You are a helpful coding assistant.".to_string(),
+                provider_options: None,
+            },
+            Message::User {
+                content: MessageContent::Text("Read src/main.rs please".to_string()),
+                provider_options: None,
+            },
+            Message::Assistant {
+                content: MessageContent::Parts(vec![ContentPart::ToolCall {
+                    tool_call_id: "call_1".to_string(),
+                    tool_name: "read_file".to_string(),
+                    input: serde_json::json!({ "path": "src/main.rs" }),
+                    provider_metadata: None,
+                }]),
+                provider_options: None,
+            },
+            Message::Tool {
+                content: vec![ContentPart::ToolResult {
+                    tool_call_id: "call_1".to_string(),
+                    tool_name: "read_file".to_string(),
+                    output: serde_json::json!("fn main() {}"),
+                }],
+                provider_options: None,
+            },
+        ]
+    }
+
+    fn read_file_tool() -> ToolDefinition {
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            name: "read_file".to_string(),
+            description: Some("Read a file from the workspace".to_string()),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": { "path": { "type": "string" } },
+                "required": ["path"]
+            }),
+            strict: false,
+        }
+    }
+
+    #[test]
+    fn test_messages_breakdown_sums_to_total() {
+        let estimate = estimate_messages_tokens(&conversation(), &[], Some("gpt-4o"));
+
+        assert!(estimate.system > MESSAGE_OVERHEAD_TOKENS);
+        assert!(estimate.user > MESSAGE_OVERHEAD_TOKENS);
+        assert!(estimate.assistant > MESSAGE_OVERHEAD_TOKENS + REPLY_PRIMING_TOKENS);
+        assert!(estimate.tools > MESSAGE_OVERHEAD_TOKENS);
+        assert_eq!(
+            estimate.total,
+            estimate.system + estimate.user + estimate.assistant + estimate.tools
+        );
+        assert_eq!(estimate.method, Some(EstimationMethod::BpeO200k));
+    }
+
+    #[test]
+    fn test_tool_definitions_increase_tools_count() {
+        let without = estimate_messages_tokens(&conversation(), &[], None);
+        let with = estimate_messages_tokens(&conversation(), &[read_file_tool()], None);
+
+        assert!(with.tools > without.tools + TOOL_DEFINITION_OVERHEAD_TOKENS);
+        assert_eq!(with.user, without.user);
+        assert_eq!(with.total - without.total, with.tools - without.tools);
+        assert_eq!(with.method, Some(EstimationMethod::Heuristic));
+    }
+
+    #[test]
+    fn test_empty_conversation() {
+        assert_eq!(
+            estimate_messages_tokens(&[], &[], None),
+            MessagesTokenEstimate::default()
+        );
+    }
+}
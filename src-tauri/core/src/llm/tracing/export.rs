@@ -0,0 +1,573 @@
+// Trace export for external observability tools
+// Converts a stored trace into OTLP/JSON (Tempo, Grafana, OTel collectors)
+// or Jaeger JSON (Jaeger UI "JSON file" import)
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tauri::State;
+
+use crate::database::Database;
+
+use super::types::{attributes, Span, SpanEvent, Trace};
+
+/// Service name reported on the exported resource
+const SERVICE_NAME: &str = "talkcody";
+/// Instrumentation scope of the exported spans
+const SCOPE_NAME: &str = "talkcody.llm";
+/// Attribute keeping the original (non-hex) trace id for cross-referencing
+const LOCAL_TRACE_ID_ATTR: &str = "talkcody.trace_id";
+
+/// OTLP span kinds
+const SPAN_KIND_INTERNAL: i64 = 1;
+const SPAN_KIND_CLIENT: i64 = 3;
+/// OTLP status codes
+const STATUS_CODE_UNSET: i64 = 0;
+const STATUS_CODE_ERROR: i64 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceExportFormat {
+    Otlp,
+    Jaeger,
+}
+
+impl TraceExportFormat {
+    pub fn parse(format: &str) -> Result<Self, String> {
+        match format.trim().to_ascii_lowercase().as_str() {
+            "" | "otlp" | "otlp-json" => Ok(Self::Otlp),
+            "jaeger" | "jaeger-json" => Ok(Self::Jaeger),
+            other => Err(format!("Unsupported trace export format: {}", other)),
+        }
+    }
+}
+
+/// A trace with all of its spans and span events
+#[derive(Debug, Clone)]
+pub struct TraceRecord {
+    pub trace: Trace,
+    pub spans: Vec<Span>,
+    pub events: Vec<SpanEvent>,
+}
+
+/// Load a trace and its spans/events from the database
+pub async fn load_trace(db: &Database, trace_id: &str) -> Result<TraceRecord, String> {
+    let id = Value::String(trace_id.to_string());
+
+    let trace_rows = db
+        .query(
+            "SELECT id, started_at, ended_at, metadata FROM traces WHERE id = ?",
+            vec![id.clone()],
+        )
+        .await?
+        .rows;
+    let row = trace_rows
+        .first()
+        .ok_or_else(|| format!("Trace not found: {}", trace_id))?;
+    let trace = Trace {
+        id: trace_id.to_string(),
+        started_at: row["started_at"].as_i64().unwrap_or_default(),
+        ended_at: row["ended_at"].as_i64(),
+        metadata: parse_json_column(&row["metadata"]),
+    };
+
+    let spans = db
+        .query(
+            "SELECT id, parent_span_id, name, started_at, ended_at, attributes FROM spans WHERE trace_id = ? ORDER BY started_at ASC",
+            vec![id.clone()],
+        )
+        .await?
+        .rows
+        .iter()
+        .map(|row| Span {
+            id: row["id"].as_str().unwrap_or_default().to_string(),
+            trace_id: trace_id.to_string(),
+            parent_span_id: row["parent_span_id"].as_str().map(str::to_string),
+            name: row["name"].as_str().unwrap_or_default().to_string(),
+            started_at: row["started_at"].as_i64().unwrap_or_default(),
+            ended_at: row["ended_at"].as_i64(),
+            attributes: match parse_json_column(&row["attributes"]) {
+                Some(Value::Object(map)) => map.into_iter().collect(),
+                _ => HashMap::new(),
+            },
+        })
+        .collect();
+
+    let events = db
+        .query(
+            "SELECT e.id, e.span_id, e.timestamp, e.event_type, e.payload FROM span_events e JOIN spans s ON s.id = e.span_id WHERE s.trace_id = ? ORDER BY e.timestamp ASC",
+            vec![id],
+        )
+        .await?
+        .rows
+        .iter()
+        .map(|row| SpanEvent {
+            id: row["id"].as_str().unwrap_or_default().to_string(),
+            span_id: row["span_id"].as_str().unwrap_or_default().to_string(),
+            timestamp: row["timestamp"].as_i64().unwrap_or_default(),
+            event_type: row["event_type"].as_str().unwrap_or_default().to_string(),
+            payload: parse_json_column(&row["payload"]),
+        })
+        .collect();
+
+    Ok(TraceRecord {
+        trace,
+        spans,
+        events,
+    })
+}
+
+/// JSON columns are stored as text; fall back to the raw string if it isn't JSON
+fn parse_json_column(value: &Value) -> Option<Value> {
+    match value {
+        Value::Null => None,
+        Value::String(text) => {
+            Some(serde_json::from_str(text).unwrap_or_else(|_| Value::String(text.clone())))
+        }
+        other => Some(other.clone()),
+    }
+}
+
+/// Convert a trace to the requested export format
+pub fn export_record(record: &TraceRecord, format: TraceExportFormat) -> Value {
+    match format {
+        TraceExportFormat::Otlp => to_otlp_json(record),
+        TraceExportFormat::Jaeger => to_jaeger_json(record),
+    }
+}
+
+/// OTLP trace ids are 16 bytes of hex. Local ids ("YYYYMMDDhhmmssfff-uuid" or
+/// external ids like task ids) are hashed so the mapping is stable.
+pub fn otlp_trace_id(trace_id: &str) -> String {
+    if is_hex_id(trace_id, 32) {
+        return trace_id.to_ascii_lowercase();
+    }
+    hex::encode(&Sha256::digest(trace_id.as_bytes())[..16])
+}
+
+/// OTLP span ids are 8 bytes of hex, which is what the writer generates
+pub fn otlp_span_id(span_id: &str) -> String {
+    if is_hex_id(span_id, 16) {
+        return span_id.to_ascii_lowercase();
+    }
+    hex::encode(&Sha256::digest(span_id.as_bytes())[..8])
+}
+
+fn is_hex_id(id: &str, len: usize) -> bool {
+    id.len() == len && id.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn millis_to_nanos(millis: i64) -> String {
+    (millis.max(0) as u128 * 1_000_000).to_string()
+}
+
+/// Encode a JSON value as an OTLP `AnyValue`
+fn otlp_any_value(value: &Value) -> Value {
+    match value {
+        Value::String(s) => json!({ "stringValue": s }),
+        Value::Bool(b) => json!({ "boolValue": b }),
+        // OTLP/JSON encodes 64-bit integers as strings
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => json!({ "intValue": i.to_string() }),
+            None => json!({ "doubleValue": n.as_f64().unwrap_or_default() }),
+        },
+        Value::Array(items) => json!({
+            "arrayValue": {
+                "values": items.iter().map(otlp_any_value).collect::<Vec<_>>()
+            }
+        }),
+        Value::Object(map) => json!({
+            "kvlistValue": {
+                "values": map
+                    .iter()
+                    .map(|(k, v)| json!({ "key": k, "value": otlp_any_value(v) }))
+                    .collect::<Vec<_>>()
+            }
+        }),
+        Value::Null => json!({ "stringValue": "" }),
+    }
+}
+
+/// Attributes sorted by key so exports are deterministic
+fn otlp_attributes(attrs: &HashMap<String, Value>) -> Vec<Value> {
+    let mut keys: Vec<&String> = attrs.keys().collect();
+    keys.sort();
+    keys.into_iter()
+        .map(|key| json!({ "key": key, "value": otlp_any_value(&attrs[key]) }))
+        .collect()
+}
+
+/// Event payloads become attributes: objects are flattened one level,
+/// anything else is kept under a `payload` key
+fn event_attributes(payload: &Option<Value>) -> HashMap<String, Value> {
+    match payload {
+        Some(Value::Object(map)) => map.clone().into_iter().collect(),
+        Some(other) => HashMap::from([("payload".to_string(), other.clone())]),
+        None => HashMap::new(),
+    }
+}
+
+fn span_kind(span: &Span) -> i64 {
+    if span.attributes.contains_key(attributes::GEN_AI_SYSTEM) {
+        SPAN_KIND_CLIENT
+    } else {
+        SPAN_KIND_INTERNAL
+    }
+}
+
+fn span_error(span: &Span) -> Option<String> {
+    span.attributes
+        .get(attributes::ERROR_TYPE)
+        .map(|value| match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        })
+}
+
+fn events_by_span(events: &[SpanEvent]) -> HashMap<&str, Vec<&SpanEvent>> {
+    let mut grouped: HashMap<&str, Vec<&SpanEvent>> = HashMap::new();
+    for event in events {
+        grouped.entry(&event.span_id).or_default().push(event);
+    }
+    grouped
+}
+
+/// Build an OTLP/JSON `ExportTraceServiceRequest` for the trace
+pub fn to_otlp_json(record: &TraceRecord) -> Value {
+    let trace_id = otlp_trace_id(&record.trace.id);
+    let events = events_by_span(&record.events);
+
+    let spans: Vec<Value> = record
+        .spans
+        .iter()
+        .map(|span| {
+            let mut attrs = span.attributes.clone();
+            attrs.insert(
+                LOCAL_TRACE_ID_ATTR.to_string(),
+                Value::String(record.trace.id.clone()),
+            );
+            let span_events: Vec<Value> = events
+                .get(span.id.as_str())
+                .into_iter()
+                .flatten()
+                .map(|event| {
+                    json!({
+                        "timeUnixNano": millis_to_nanos(event.timestamp),
+                        "name": event.event_type,
+                        "attributes": otlp_attributes(&event_attributes(&event.payload)),
+                    })
+                })
+                .collect();
+            let status = match span_error(span) {
+                Some(message) => json!({ "code": STATUS_CODE_ERROR, "message": message }),
+                None => json!({ "code": STATUS_CODE_UNSET }),
+            };
+
+            json!({
+                "traceId": trace_id,
+                "spanId": otlp_span_id(&span.id),
+                "parentSpanId": span.parent_span_id.as_deref().map(otlp_span_id).unwrap_or_default(),
+                "name": span.name,
+                "kind": span_kind(span),
+                "startTimeUnixNano": millis_to_nanos(span.started_at),
+                // Spans that never closed are exported with zero duration
+                "endTimeUnixNano": millis_to_nanos(span.ended_at.unwrap_or(span.started_at)),
+                "attributes": otlp_attributes(&attrs),
+                "events": span_events,
+                "status": status,
+            })
+        })
+        .collect();
+
+    let resource_attrs = HashMap::from([(
+        "service.name".to_string(),
+        Value::String(SERVICE_NAME.to_string()),
+    )]);
+
+    json!({
+        "resourceSpans": [{
+            "resource": { "attributes": otlp_attributes(&resource_attrs) },
+            "scopeSpans": [{
+                "scope": { "name": SCOPE_NAME, "version": env!("CARGO_PKG_VERSION") },
+                "spans": spans,
+            }],
+        }],
+    })
+}
+
+/// Encode a JSON value as a Jaeger tag/field
+fn jaeger_tag(key: &str, value: &Value) -> Value {
+    match value {
+        Value::String(s) => json!({ "key": key, "type": "string", "value": s }),
+        Value::Bool(b) => json!({ "key": key, "type": "bool", "value": b }),
+        Value::Number(n) if n.is_i64() => json!({ "key": key, "type": "int64", "value": n }),
+        Value::Number(n) => json!({ "key": key, "type": "float64", "value": n }),
+        other => json!({ "key": key, "type": "string", "value": other.to_string() }),
+    }
+}
+
+fn jaeger_tags(attrs: &HashMap<String, Value>) -> Vec<Value> {
+    let mut keys: Vec<&String> = attrs.keys().collect();
+    keys.sort();
+    keys.into_iter()
+        .map(|key| jaeger_tag(key, &attrs[key]))
+        .collect()
+}
+
+/// Build a Jaeger JSON document (as accepted by the Jaeger UI import)
+pub fn to_jaeger_json(record: &TraceRecord) -> Value {
+    let trace_id = otlp_trace_id(&record.trace.id);
+    let events = events_by_span(&record.events);
+
+    let spans: Vec<Value> = record
+        .spans
+        .iter()
+        .map(|span| {
+            let references: Vec<Value> = span
+                .parent_span_id
+                .iter()
+                .map(|parent| {
+                    json!({
+                        "refType": "CHILD_OF",
+                        "traceID": trace_id,
+                        "spanID": otlp_span_id(parent),
+                    })
+                })
+                .collect();
+            let mut tags = span.attributes.clone();
+            tags.insert(
+                LOCAL_TRACE_ID_ATTR.to_string(),
+                Value::String(record.trace.id.clone()),
+            );
+            if span_error(span).is_some() {
+                tags.insert("error".to_string(), Value::Bool(true));
+            }
+            let logs: Vec<Value> = events
+                .get(span.id.as_str())
+                .into_iter()
+                .flatten()
+                .map(|event| {
+                    let mut fields = event_attributes(&event.payload);
+                    fields.insert("event".to_string(), Value::String(event.event_type.clone()));
+                    json!({
+                        "timestamp": event.timestamp * 1000,
+                        "fields": jaeger_tags(&fields),
+                    })
+                })
+                .collect();
+            let ended_at = span.ended_at.unwrap_or(span.started_at);
+
+            json!({
+                "traceID": trace_id,
+                "spanID": otlp_span_id(&span.id),
+                "operationName": span.name,
+                "references": references,
+                "startTime": span.started_at * 1000,
+                "duration": (ended_at - span.started_at).max(0) * 1000,
+                "tags": jaeger_tags(&tags),
+                "logs": logs,
+                "processID": "p1",
+            })
+        })
+        .collect();
+
+    json!({
+        "data": [{
+            "traceID": trace_id,
+            "spans": spans,
+            "processes": {
+                "p1": { "serviceName": SERVICE_NAME, "tags": [] }
+            },
+        }],
+    })
+}
+
+/// Export a stored trace as a pretty-printed JSON document.
+/// `format` is "otlp" (default) or "jaeger".
+#[tauri::command]
+pub async fn export_traces(
+    trace_id: String,
+    format: Option<String>,
+    db: State<'_, Arc<Database>>,
+) -> Result<String, String> {
+    let format = TraceExportFormat::parse(format.as_deref().unwrap_or_default())?;
+    let record = load_trace(db.inner(), &trace_id).await?;
+    serde_json::to_string_pretty(&export_record(&record, format))
+        .map_err(|e| format!("Failed to serialize trace export: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::tracing::types::{int_attr, string_attr};
+    use crate::llm::tracing::{schema, TraceWriter};
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    async fn record_trace() -> (TraceRecord, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test_export.db");
+        let db = Arc::new(Database::new(db_path.to_string_lossy().to_string()));
+        db.connect().await.unwrap();
+        schema::init_tracing_schema(&db).await.unwrap();
+
+        let writer = TraceWriter::new(db.clone());
+        writer.start();
+
+        let trace_id = writer.start_trace();
+        let root = writer.start_span(
+            trace_id.clone(),
+            None,
+            "llm.stream_completion".to_string(),
+            HashMap::from([
+                (attributes::GEN_AI_SYSTEM.to_string(), string_attr("openai")),
+                (
+                    attributes::GEN_AI_REQUEST_MODEL.to_string(),
+                    string_attr("gpt-4o"),
+                ),
+                (
+                    attributes::GEN_AI_REQUEST_MAX_TOKENS.to_string(),
+                    int_attr(1024),
+                ),
+            ]),
+        );
+        let child = writer.start_span(
+            trace_id.clone(),
+            Some(root.clone()),
+            "llm.request".to_string(),
+            HashMap::from([(attributes::ERROR_TYPE.to_string(), string_attr("timeout"))]),
+        );
+        writer.request_flush();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        writer.add_event(
+            child.clone(),
+            attributes::HTTP_REQUEST_BODY.to_string(),
+            Some(json!({ "bytes": 512 })),
+        );
+        writer.end_span(child, chrono::Utc::now().timestamp_millis() + 5);
+        writer.end_span(root, chrono::Utc::now().timestamp_millis() + 10);
+        writer.request_flush();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let record = load_trace(&db, &trace_id).await.unwrap();
+        (record, temp_dir)
+    }
+
+    fn attr<'a>(attrs: &'a Value, key: &str) -> &'a Value {
+        &attrs
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|a| a["key"] == key)
+            .unwrap_or_else(|| panic!("missing attribute {}", key))["value"]
+    }
+
+    #[tokio::test]
+    async fn test_export_otlp_shape() {
+        let (record, _temp_dir) = record_trace().await;
+        assert_eq!(record.spans.len(), 2);
+        assert_eq!(record.events.len(), 1);
+
+        let export = export_record(&record, TraceExportFormat::Otlp);
+        let resource_spans = export["resourceSpans"].as_array().unwrap();
+        assert_eq!(resource_spans.len(), 1);
+        assert_eq!(
+            attr(&resource_spans[0]["resource"]["attributes"], "service.name"),
+            &json!({ "stringValue": "talkcody" })
+        );
+
+        let scope_spans = resource_spans[0]["scopeSpans"].as_array().unwrap();
+        assert_eq!(scope_spans[0]["scope"]["name"], SCOPE_NAME);
+        let spans = scope_spans[0]["spans"].as_array().unwrap();
+        assert_eq!(spans.len(), 2);
+
+        let trace_id = spans[0]["traceId"].as_str().unwrap();
+        assert!(is_hex_id(trace_id, 32));
+        for span in spans {
+            assert_eq!(span["traceId"], trace_id);
+            assert!(is_hex_id(span["spanId"].as_str().unwrap(), 16));
+            let start: u128 = span["startTimeUnixNano"].as_str().unwrap().parse().unwrap();
+            let end: u128 = span["endTimeUnixNano"].as_str().unwrap().parse().unwrap();
+            assert!(end >= start);
+        }
+
+        let root = spans
+            .iter()
+            .find(|s| s["name"] == "llm.stream_completion")
+            .unwrap();
+        assert_eq!(root["parentSpanId"], "");
+        assert_eq!(root["kind"], SPAN_KIND_CLIENT);
+        assert_eq!(
+            attr(&root["attributes"], attributes::GEN_AI_REQUEST_MODEL),
+            &json!({ "stringValue": "gpt-4o" })
+        );
+        assert_eq!(
+            attr(&root["attributes"], attributes::GEN_AI_REQUEST_MAX_TOKENS),
+            &json!({ "intValue": "1024" })
+        );
+        assert_eq!(
+            attr(&root["attributes"], LOCAL_TRACE_ID_ATTR),
+            &json!({ "stringValue": record.trace.id })
+        );
+
+        let child = spans.iter().find(|s| s["name"] == "llm.request").unwrap();
+        assert_eq!(child["parentSpanId"], root["spanId"]);
+        assert_eq!(child["status"]["code"], STATUS_CODE_ERROR);
+        assert_eq!(child["status"]["message"], "timeout");
+        let events = child["events"].as_array().unwrap();
+        assert_eq!(events[0]["name"], attributes::HTTP_REQUEST_BODY);
+        assert_eq!(
+            attr(&events[0]["attributes"], "bytes"),
+            &json!({ "intValue": "512" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_jaeger_shape() {
+        let (record, _temp_dir) = record_trace().await;
+        let export = export_record(&record, TraceExportFormat::Jaeger);
+
+        let trace = &export["data"][0];
+        assert_eq!(trace["processes"]["p1"]["serviceName"], SERVICE_NAME);
+        let spans = trace["spans"].as_array().unwrap();
+        assert_eq!(spans.len(), 2);
+
+        let child = spans
+            .iter()
+            .find(|s| s["operationName"] == "llm.request")
+            .unwrap();
+        assert_eq!(child["references"][0]["refType"], "CHILD_OF");
+        assert!(child["tags"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|t| t["key"] == "error" && t["value"] == true));
+        assert_eq!(child["logs"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_id_mapping() {
+        let hex = "a1b2c3d4e5f67890a1b2c3d4e5f67890";
+        assert_eq!(otlp_trace_id(hex), hex);
+        let mapped = otlp_trace_id("20260130123456789-abc12345");
+        assert!(is_hex_id(&mapped, 32));
+        assert_eq!(mapped, otlp_trace_id("20260130123456789-abc12345"));
+        assert_eq!(otlp_span_id("a1b2c3d4e5f67890"), "a1b2c3d4e5f67890");
+        assert!(is_hex_id(&otlp_span_id("task-123"), 16));
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(
+            TraceExportFormat::parse("").unwrap(),
+            TraceExportFormat::Otlp
+        );
+        assert_eq!(
+            TraceExportFormat::parse("Jaeger").unwrap(),
+            TraceExportFormat::Jaeger
+        );
+        assert!(TraceExportFormat::parse("zipkin").is_err());
+    }
+}
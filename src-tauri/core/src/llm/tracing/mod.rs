@@ -2,8 +2,11 @@
 // Provides non-blocking telemetry collection for LLM operations
 // Following OpenTelemetry GenAI semantic conventions
 
+pub mod export;
 pub mod ids;
+pub mod retention;
 pub mod schema;
+pub mod stats;
 pub mod types;
 pub mod writer;
 
@@ -0,0 +1,342 @@
+// Trace retention for LLM tracing
+// Deletes old traces (with their spans and events) so the trace tables stay bounded.
+// Automatic pruning is opt-in through the retention settings; traces are only
+// removed on demand otherwise. Pruning never runs during shutdown.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::State;
+
+use crate::background_tasks;
+use crate::database::Database;
+use crate::storage::SettingsRepository;
+
+/// Settings key for the retention window in days
+pub const TRACE_RETENTION_DAYS_SETTING: &str = "trace_retention_days";
+/// Settings key for the maximum number of traces kept
+pub const TRACE_MAX_COUNT_SETTING: &str = "trace_max_count";
+
+/// 0 keeps traces regardless of age
+pub const DEFAULT_TRACE_RETENTION_DAYS: u32 = 0;
+/// 0 keeps any number of traces
+pub const DEFAULT_TRACE_MAX_COUNT: u32 = 0;
+/// How often background pruning runs
+pub const PRUNE_INTERVAL_MS: u64 = 6 * 60 * 60 * 1000;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Rows removed by a prune
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PruneResult {
+    pub traces: u64,
+    pub spans: u64,
+    pub events: u64,
+}
+
+impl PruneResult {
+    fn add(&mut self, other: PruneResult) {
+        self.traces += other.traces;
+        self.spans += other.spans;
+        self.events += other.events;
+    }
+}
+
+/// Delete the traces selected by `trace_filter` (a subquery over `traces`
+/// returning ids), children first so foreign keys stay satisfied
+async fn delete_traces(
+    db: &Database,
+    trace_filter: &str,
+    params: Vec<Value>,
+) -> Result<PruneResult, String> {
+    let events = db
+        .execute(
+            &format!(
+                "DELETE FROM span_events WHERE span_id IN (SELECT id FROM spans WHERE trace_id IN ({}))",
+                trace_filter
+            ),
+            params.clone(),
+        )
+        .await?
+        .rows_affected;
+    let spans = db
+        .execute(
+            &format!("DELETE FROM spans WHERE trace_id IN ({})", trace_filter),
+            params.clone(),
+        )
+        .await?
+        .rows_affected;
+    let traces = db
+        .execute(
+            &format!("DELETE FROM traces WHERE id IN ({})", trace_filter),
+            params,
+        )
+        .await?
+        .rows_affected;
+
+    Ok(PruneResult {
+        traces,
+        spans,
+        events,
+    })
+}
+
+/// Delete traces that started before `cutoff_ms`
+pub async fn prune_before(db: &Database, cutoff_ms: i64) -> Result<PruneResult, String> {
+    delete_traces(
+        db,
+        "SELECT id FROM traces WHERE started_at < ?",
+        vec![Value::Number(cutoff_ms.into())],
+    )
+    .await
+}
+
+/// Keep only the `max_traces` most recent traces
+pub async fn prune_to_max_count(db: &Database, max_traces: u32) -> Result<PruneResult, String> {
+    delete_traces(
+        db,
+        "SELECT id FROM traces ORDER BY started_at DESC, id DESC LIMIT -1 OFFSET ?",
+        vec![Value::Number(max_traces.into())],
+    )
+    .await
+}
+
+fn retention_cutoff(now_ms: i64, older_than_days: u32) -> i64 {
+    now_ms - older_than_days as i64 * DAY_MS
+}
+
+/// Apply the retention window, then the count cap. A limit of 0 is not applied.
+pub async fn prune_with_limits(
+    db: &Database,
+    now_ms: i64,
+    retention_days: u32,
+    max_traces: u32,
+) -> Result<PruneResult, String> {
+    let mut result = PruneResult::default();
+    if retention_days > 0 {
+        result.add(prune_before(db, retention_cutoff(now_ms, retention_days)).await?);
+    }
+    if max_traces > 0 {
+        result.add(prune_to_max_count(db, max_traces).await?);
+    }
+    Ok(result)
+}
+
+/// Prune using the configured retention settings
+pub async fn prune_with_settings(db: &Arc<Database>) -> Result<PruneResult, String> {
+    let settings = SettingsRepository::new(db.clone());
+    let retention_days = settings
+        .get_setting_or_default(TRACE_RETENTION_DAYS_SETTING, DEFAULT_TRACE_RETENTION_DAYS)
+        .await
+        .unwrap_or_else(|e| {
+            log::warn!("Invalid trace retention setting: {}", e);
+            DEFAULT_TRACE_RETENTION_DAYS
+        });
+    let max_traces = settings
+        .get_setting_or_default(TRACE_MAX_COUNT_SETTING, DEFAULT_TRACE_MAX_COUNT)
+        .await
+        .unwrap_or_else(|e| {
+            log::warn!("Invalid trace max count setting: {}", e);
+            DEFAULT_TRACE_MAX_COUNT
+        });
+    if retention_days == 0 && max_traces == 0 {
+        return Ok(PruneResult::default());
+    }
+
+    let result = prune_with_limits(
+        db,
+        chrono::Utc::now().timestamp_millis(),
+        retention_days,
+        max_traces,
+    )
+    .await?;
+    if result.traces > 0 {
+        log::info!(
+            "Pruned {} traces ({} spans, {} events)",
+            result.traces,
+            result.spans,
+            result.events
+        );
+    }
+    Ok(result)
+}
+
+/// Prune once now, then every `PRUNE_INTERVAL_MS` as a hidden background
+/// schedule. Each run is a no-op unless a retention limit is configured.
+pub async fn start_background_pruning(db: Arc<Database>) -> Result<String, String> {
+    if let Err(e) = prune_with_settings(&db).await {
+        log::warn!("Initial trace pruning failed: {}", e);
+    }
+    background_tasks::schedule_internal_job("internal:prune_traces", PRUNE_INTERVAL_MS, move || {
+        let db = db.clone();
+        async move { prune_with_settings(&db).await.map(|_| ()) }
+    })
+    .await
+}
+
+/// Delete traces older than `older_than_days`
+#[tauri::command]
+pub async fn prune_traces(
+    older_than_days: u32,
+    db: State<'_, Arc<Database>>,
+) -> Result<PruneResult, String> {
+    let cutoff = retention_cutoff(chrono::Utc::now().timestamp_millis(), older_than_days);
+    prune_before(db.inner(), cutoff).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::tracing::schema::{self, queries};
+    use tempfile::TempDir;
+
+    const NOW: i64 = 1_760_000_000_000;
+
+    async fn create_test_db() -> (Arc<Database>, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test_retention.db");
+        let db = Arc::new(Database::new(db_path.to_string_lossy().to_string()));
+        db.connect().await.unwrap();
+        schema::init_tracing_schema(&db).await.unwrap();
+        (db, temp_dir)
+    }
+
+    /// Insert a trace with one span and one event, started `age_days` before NOW
+    async fn insert_trace(db: &Database, id: &str, age_days: i64) {
+        let started_at = NOW - age_days * DAY_MS;
+        let span_id = format!("{}-span", id);
+        db.transaction(vec![
+            (
+                queries::INSERT_TRACE.to_string(),
+                vec![
+                    Value::String(id.to_string()),
+                    Value::Number(started_at.into()),
+                    Value::Null,
+                    Value::Null,
+                ],
+            ),
+            (
+                queries::INSERT_SPAN.to_string(),
+                vec![
+                    Value::String(span_id.clone()),
+                    Value::String(id.to_string()),
+                    Value::Null,
+                    Value::String("llm.request".to_string()),
+                    Value::Number(started_at.into()),
+                    Value::Null,
+                    Value::String("{}".to_string()),
+                ],
+            ),
+            (
+                queries::INSERT_SPAN_EVENT.to_string(),
+                vec![
+                    Value::String(format!("{}-event", id)),
+                    Value::String(span_id),
+                    Value::Number(started_at.into()),
+                    Value::String("test".to_string()),
+                    Value::Null,
+                ],
+            ),
+        ])
+        .await
+        .unwrap();
+    }
+
+    async fn trace_ids(db: &Database) -> Vec<String> {
+        db.query("SELECT id FROM traces ORDER BY id", vec![])
+            .await
+            .unwrap()
+            .rows
+            .iter()
+            .map(|row| row["id"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    async fn count(db: &Database, table: &str) -> i64 {
+        db.query(&format!("SELECT COUNT(*) as count FROM {}", table), vec![])
+            .await
+            .unwrap()
+            .rows[0]["count"]
+            .as_i64()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_prunes_only_old_traces() {
+        let (db, _temp_dir) = create_test_db().await;
+        insert_trace(&db, "old-1", 30).await;
+        insert_trace(&db, "old-2", 8).await;
+        insert_trace(&db, "recent-1", 6).await;
+        insert_trace(&db, "recent-2", 0).await;
+
+        let result = prune_before(&db, retention_cutoff(NOW, 7)).await.unwrap();
+
+        assert_eq!(
+            result,
+            PruneResult {
+                traces: 2,
+                spans: 2,
+                events: 2
+            }
+        );
+        assert_eq!(trace_ids(&db).await, vec!["recent-1", "recent-2"]);
+        assert_eq!(count(&db, "spans").await, 2);
+        assert_eq!(count(&db, "span_events").await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_count_cap_evicts_oldest() {
+        let (db, _temp_dir) = create_test_db().await;
+        for (id, age) in [("a", 5), ("b", 4), ("c", 3), ("d", 2), ("e", 1)] {
+            insert_trace(&db, id, age).await;
+        }
+
+        let result = prune_to_max_count(&db, 3).await.unwrap();
+
+        assert_eq!(result.traces, 2);
+        assert_eq!(trace_ids(&db).await, vec!["c", "d", "e"]);
+        assert_eq!(count(&db, "spans").await, 3);
+        assert_eq!(count(&db, "span_events").await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_prune_with_limits_applies_both() {
+        let (db, _temp_dir) = create_test_db().await;
+        for (id, age) in [("a", 10), ("b", 3), ("c", 2), ("d", 1)] {
+            insert_trace(&db, id, age).await;
+        }
+
+        let result = prune_with_limits(&db, NOW, 7, 2).await.unwrap();
+
+        assert_eq!(result.traces, 2);
+        assert_eq!(trace_ids(&db).await, vec!["c", "d"]);
+    }
+
+    #[tokio::test]
+    async fn test_default_limits_keep_everything() {
+        let (db, _temp_dir) = create_test_db().await;
+        insert_trace(&db, "old", 365).await;
+        insert_trace(&db, "recent", 0).await;
+
+        let result = prune_with_limits(
+            &db,
+            NOW,
+            DEFAULT_TRACE_RETENTION_DAYS,
+            DEFAULT_TRACE_MAX_COUNT,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result, PruneResult::default());
+        assert_eq!(trace_ids(&db).await, vec!["old", "recent"]);
+    }
+
+    #[tokio::test]
+    async fn test_prune_empty_db() {
+        let (db, _temp_dir) = create_test_db().await;
+        let result = prune_with_limits(&db, NOW, 7, 10).await.unwrap();
+        assert_eq!(result, PruneResult::default());
+    }
+}
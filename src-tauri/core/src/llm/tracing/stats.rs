@@ -0,0 +1,303 @@
+// Latency statistics over stored traces
+// Aggregates time-to-first-token and total span duration per model
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::State;
+
+use crate::database::Database;
+
+use super::types::attributes;
+
+/// p50/p95/p99 of a set of latency samples, in milliseconds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyPercentiles {
+    pub count: usize,
+    pub p50: i64,
+    pub p95: i64,
+    pub p99: i64,
+}
+
+impl LatencyPercentiles {
+    /// Nearest-rank percentiles; `None` when there are no samples
+    pub fn from_samples(mut samples: Vec<i64>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        Some(Self {
+            count: samples.len(),
+            p50: nearest_rank(&samples, 50.0),
+            p95: nearest_rank(&samples, 95.0),
+            p99: nearest_rank(&samples, 99.0),
+        })
+    }
+}
+
+/// Value at the nearest rank for `percentile` in a sorted, non-empty slice
+fn nearest_rank(sorted: &[i64], percentile: f64) -> i64 {
+    let rank = ((percentile / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Latency stats for one model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelLatencyStats {
+    pub model: String,
+    /// Number of spans recorded for the model
+    pub span_count: usize,
+    /// Time to first token; only spans with a recorded TTFT count
+    pub ttft_ms: Option<LatencyPercentiles>,
+    /// Span duration; only closed spans count
+    pub total_ms: Option<LatencyPercentiles>,
+}
+
+#[derive(Default)]
+struct ModelSamples {
+    span_count: usize,
+    ttft: Vec<i64>,
+    total: Vec<i64>,
+}
+
+/// Aggregate latency per model for spans started in `[since, until)`.
+/// Models are sorted by name.
+pub async fn query_stats(
+    db: &Database,
+    model: Option<&str>,
+    since: Option<i64>,
+    until: Option<i64>,
+) -> Result<Vec<ModelLatencyStats>, String> {
+    // Attribute keys contain dots, so the JSON path quotes them
+    let mut sql = format!(
+        "SELECT CASE WHEN json_valid(s.attributes) \
+         THEN json_extract(s.attributes, '$.\"{}\"') END AS model, \
+         s.started_at, s.ended_at, \
+         (SELECT json_extract(e.payload, '$.ttft_ms') FROM span_events e \
+          WHERE e.span_id = s.id AND e.event_type = '{}' AND json_valid(e.payload) LIMIT 1) AS ttft_ms \
+         FROM spans s WHERE model IS NOT NULL",
+        attributes::GEN_AI_REQUEST_MODEL,
+        attributes::GEN_AI_TTFT_MS
+    );
+    let mut params = Vec::new();
+    if let Some(model) = model {
+        sql.push_str(" AND model = ?");
+        params.push(Value::String(model.to_string()));
+    }
+    if let Some(since) = since {
+        sql.push_str(" AND s.started_at >= ?");
+        params.push(Value::Number(since.into()));
+    }
+    if let Some(until) = until {
+        sql.push_str(" AND s.started_at < ?");
+        params.push(Value::Number(until.into()));
+    }
+
+    let rows = db.query(&sql, params).await?.rows;
+
+    let mut by_model: BTreeMap<String, ModelSamples> = BTreeMap::new();
+    for row in &rows {
+        let Some(model) = row["model"].as_str() else {
+            continue;
+        };
+        let samples = by_model.entry(model.to_string()).or_default();
+        samples.span_count += 1;
+        if let Some(ttft) = row["ttft_ms"].as_i64().filter(|v| *v >= 0) {
+            samples.ttft.push(ttft);
+        }
+        if let (Some(started), Some(ended)) = (row["started_at"].as_i64(), row["ended_at"].as_i64())
+        {
+            if ended >= started {
+                samples.total.push(ended - started);
+            }
+        }
+    }
+
+    Ok(by_model
+        .into_iter()
+        .map(|(model, samples)| ModelLatencyStats {
+            model,
+            span_count: samples.span_count,
+            ttft_ms: LatencyPercentiles::from_samples(samples.ttft),
+            total_ms: LatencyPercentiles::from_samples(samples.total),
+        })
+        .collect())
+}
+
+/// Latency percentiles per model, optionally filtered by model and start time (ms)
+#[tauri::command]
+pub async fn query_trace_stats(
+    model: Option<String>,
+    since: Option<i64>,
+    until: Option<i64>,
+    db: State<'_, Arc<Database>>,
+) -> Result<Vec<ModelLatencyStats>, String> {
+    query_stats(db.inner(), model.as_deref(), since, until).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::tracing::schema::{self, queries};
+    use tempfile::TempDir;
+
+    async fn create_test_db() -> (Arc<Database>, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test_stats.db");
+        let db = Arc::new(Database::new(db_path.to_string_lossy().to_string()));
+        db.connect().await.unwrap();
+        schema::init_tracing_schema(&db).await.unwrap();
+        db.execute(
+            queries::INSERT_TRACE,
+            vec![
+                Value::String("trace".to_string()),
+                Value::Number(0.into()),
+                Value::Null,
+                Value::Null,
+            ],
+        )
+        .await
+        .unwrap();
+        (db, temp_dir)
+    }
+
+    async fn insert_span(
+        db: &Database,
+        id: &str,
+        model: &str,
+        started_at: i64,
+        duration_ms: Option<i64>,
+        ttft_ms: Option<i64>,
+    ) {
+        let mut attrs = serde_json::Map::new();
+        attrs.insert(
+            attributes::GEN_AI_REQUEST_MODEL.to_string(),
+            Value::String(model.to_string()),
+        );
+        db.execute(
+            queries::INSERT_SPAN,
+            vec![
+                Value::String(id.to_string()),
+                Value::String("trace".to_string()),
+                Value::Null,
+                Value::String("llm.stream_completion".to_string()),
+                Value::Number(started_at.into()),
+                duration_ms
+                    .map(|d| Value::Number((started_at + d).into()))
+                    .unwrap_or(Value::Null),
+                Value::String(Value::Object(attrs).to_string()),
+            ],
+        )
+        .await
+        .unwrap();
+        if let Some(ttft_ms) = ttft_ms {
+            db.execute(
+                queries::INSERT_SPAN_EVENT,
+                vec![
+                    Value::String(format!("{}-ttft", id)),
+                    Value::String(id.to_string()),
+                    Value::Number(started_at.into()),
+                    Value::String(attributes::GEN_AI_TTFT_MS.to_string()),
+                    serde_json::json!({ "ttft_ms": ttft_ms }),
+                ],
+            )
+            .await
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_nearest_rank_percentiles() {
+        let stats = LatencyPercentiles::from_samples((1..=100).rev().collect()).unwrap();
+        assert_eq!(
+            stats,
+            LatencyPercentiles {
+                count: 100,
+                p50: 50,
+                p95: 95,
+                p99: 99
+            }
+        );
+
+        let single = LatencyPercentiles::from_samples(vec![42]).unwrap();
+        assert_eq!((single.p50, single.p95, single.p99), (42, 42, 42));
+        assert!(LatencyPercentiles::from_samples(vec![]).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_percentiles_per_model() {
+        let (db, _temp_dir) = create_test_db().await;
+        // gpt-4o: TTFT 10..=200 step 10, duration = 10x TTFT
+        for i in 1..=20 {
+            insert_span(
+                &db,
+                &format!("gpt-{}", i),
+                "gpt-4o",
+                1_000 + i,
+                Some(i * 100),
+                Some(i * 10),
+            )
+            .await;
+        }
+        // claude: one span without TTFT, one still open
+        insert_span(&db, "claude-1", "claude", 1_000, Some(900), Some(300)).await;
+        insert_span(&db, "claude-2", "claude", 1_001, Some(1_100), None).await;
+        insert_span(&db, "claude-3", "claude", 1_002, None, Some(500)).await;
+
+        let stats = query_stats(&db, None, None, None).await.unwrap();
+        assert_eq!(stats.len(), 2);
+
+        let claude = &stats[0];
+        assert_eq!(claude.model, "claude");
+        assert_eq!(claude.span_count, 3);
+        let ttft = claude.ttft_ms.as_ref().unwrap();
+        assert_eq!((ttft.count, ttft.p50, ttft.p99), (2, 300, 500));
+        let total = claude.total_ms.as_ref().unwrap();
+        assert_eq!((total.count, total.p50, total.p99), (2, 900, 1_100));
+
+        let gpt = &stats[1];
+        assert_eq!(gpt.model, "gpt-4o");
+        assert_eq!(gpt.span_count, 20);
+        assert_eq!(
+            gpt.ttft_ms,
+            Some(LatencyPercentiles {
+                count: 20,
+                p50: 100,
+                p95: 190,
+                p99: 200
+            })
+        );
+        assert_eq!(
+            gpt.total_ms,
+            Some(LatencyPercentiles {
+                count: 20,
+                p50: 1_000,
+                p95: 1_900,
+                p99: 2_000
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_filters_by_model_and_time() {
+        let (db, _temp_dir) = create_test_db().await;
+        insert_span(&db, "a", "gpt-4o", 1_000, Some(100), Some(10)).await;
+        insert_span(&db, "b", "gpt-4o", 2_000, Some(200), Some(20)).await;
+        insert_span(&db, "c", "gpt-4o", 3_000, Some(300), Some(30)).await;
+        insert_span(&db, "d", "claude", 2_000, Some(400), Some(40)).await;
+
+        let stats = query_stats(&db, Some("gpt-4o"), Some(1_500), Some(3_000))
+            .await
+            .unwrap();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].span_count, 1);
+        assert_eq!(stats[0].ttft_ms.as_ref().unwrap().p50, 20);
+
+        let none = query_stats(&db, Some("missing"), None, None).await.unwrap();
+        assert!(none.is_empty());
+    }
+}
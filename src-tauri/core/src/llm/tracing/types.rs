@@ -65,8 +65,9 @@ pub enum TraceCommand {
     #[cfg(test)]
     /// Flush all pending writes
     Flush,
-    /// Shutdown the writer
-    Shutdown,
+    /// Flush pending writes and stop the writer; the number of items
+    /// flushed is sent back on `ack` once they are committed
+    Shutdown { ack: std::sync::mpsc::Sender<usize> },
 }
 
 /// OpenTelemetry GenAI semantic attribute keys
@@ -80,6 +81,7 @@ pub mod attributes {
     pub const GEN_AI_REQUEST_TOP_P: &str = "gen_ai.request.top_p";
     pub const GEN_AI_REQUEST_TOP_K: &str = "gen_ai.request.top_k";
     pub const GEN_AI_REQUEST_MAX_TOKENS: &str = "gen_ai.request.max_tokens";
+    pub const GEN_AI_REQUEST_SEED: &str = "gen_ai.request.seed";
 
     // HTTP attributes
     pub const HTTP_REQUEST_BODY: &str = "http.request.body";
@@ -111,6 +113,8 @@ pub fn float_attr(value: f64) -> serde_json::Value {
 pub const BATCH_SIZE: usize = 100;
 pub const BATCH_TIMEOUT_MS: u64 = 50;
 pub const CHANNEL_CAPACITY: usize = 10000;
+/// How long shutdown waits for the final flush to be committed
+pub const SHUTDOWN_TIMEOUT_MS: u64 = 5000;
 
 #[cfg(test)]
 mod tests {
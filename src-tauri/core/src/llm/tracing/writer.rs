@@ -1,6 +1,9 @@
 // Async trace writer with non-blocking channel and batching
 // Ensures stream processing never waits for database writes
+// Commands are queued in a bounded channel and committed in one transaction
+// per batch, so heavy streaming does not contend with other DB writers
 
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc;
@@ -12,7 +15,10 @@ use crate::database::Database;
 use super::{
     ids::{generate_event_id, generate_span_id, generate_trace_id},
     schema::queries,
-    types::{Span, SpanEvent, Trace, TraceCommand, BATCH_SIZE, BATCH_TIMEOUT_MS, CHANNEL_CAPACITY},
+    types::{
+        Span, SpanEvent, Trace, TraceCommand, BATCH_SIZE, BATCH_TIMEOUT_MS, CHANNEL_CAPACITY,
+        SHUTDOWN_TIMEOUT_MS,
+    },
 };
 
 /// Async trace writer that batches writes to the database
@@ -22,6 +28,8 @@ pub struct TraceWriter {
     db: Arc<Database>,
     receiver: Arc<Mutex<Option<mpsc::Receiver<TraceCommand>>>>,
     span_trace_ids: Arc<std::sync::Mutex<std::collections::HashMap<String, String>>>,
+    /// Number of batches written to the database
+    flush_count: Arc<AtomicU64>,
 }
 
 impl TraceWriter {
@@ -35,6 +43,7 @@ impl TraceWriter {
             db,
             receiver: Arc::new(Mutex::new(Some(receiver))),
             span_trace_ids: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+            flush_count: Arc::new(AtomicU64::new(0)),
         }
     }
 
@@ -43,11 +52,12 @@ impl TraceWriter {
     pub fn start(&self) {
         let db = self.db.clone();
         let receiver_guard = self.receiver.clone();
+        let flush_count = self.flush_count.clone();
 
         tokio::spawn(async move {
             let receiver = receiver_guard.lock().await.take();
             if let Some(rx) = receiver {
-                Self::run_writer(db, rx).await;
+                Self::run_writer(db, rx, flush_count).await;
             } else {
                 log::warn!("TraceWriter::start() called but receiver already taken");
             }
@@ -55,7 +65,11 @@ impl TraceWriter {
     }
 
     /// Background task that processes commands and batches writes
-    async fn run_writer(db: Arc<Database>, mut receiver: mpsc::Receiver<TraceCommand>) {
+    async fn run_writer(
+        db: Arc<Database>,
+        mut receiver: mpsc::Receiver<TraceCommand>,
+        flush_count: Arc<AtomicU64>,
+    ) {
         let mut batch: Vec<TraceCommand> = Vec::with_capacity(BATCH_SIZE);
         let mut flush_interval = interval(Duration::from_millis(BATCH_TIMEOUT_MS));
 
@@ -69,21 +83,24 @@ impl TraceWriter {
                         #[cfg(test)]
                         TraceCommand::Flush => {
                             if !batch.is_empty() {
-                                Self::flush_batch(&db, &mut batch).await;
+                                Self::flush_batch(&db, &mut batch, &flush_count).await;
                             }
                         }
-                        TraceCommand::Shutdown => {
-                            log::info!("TraceWriter received shutdown command, flushing remaining {} items", batch.len());
+                        TraceCommand::Shutdown { ack } => {
+                            let remaining = batch.len();
+                            log::info!("TraceWriter received shutdown command, flushing remaining {} items", remaining);
                             if !batch.is_empty() {
-                                Self::flush_batch(&db, &mut batch).await;
+                                Self::flush_batch(&db, &mut batch, &flush_count).await;
                             }
+                            // The caller may have timed out and gone away
+                            let _ = ack.send(remaining);
                             log::info!("TraceWriter shutdown complete");
                             break;
                         }
                         other => {
                             batch.push(other);
                             if batch.len() >= BATCH_SIZE {
-                                Self::flush_batch(&db, &mut batch).await;
+                                Self::flush_batch(&db, &mut batch, &flush_count).await;
                             }
                         }
                     }
@@ -92,7 +109,7 @@ impl TraceWriter {
                 // Flush on timeout
                 _ = flush_interval.tick() => {
                     if !batch.is_empty() {
-                        Self::flush_batch(&db, &mut batch).await;
+                        Self::flush_batch(&db, &mut batch, &flush_count).await;
                     }
                 }
 
@@ -100,7 +117,7 @@ impl TraceWriter {
                 else => {
                     log::info!("TraceWriter channel closed, flushing remaining {} items", batch.len());
                     if !batch.is_empty() {
-                        Self::flush_batch(&db, &mut batch).await;
+                        Self::flush_batch(&db, &mut batch, &flush_count).await;
                     }
                     break;
                 }
@@ -108,9 +125,13 @@ impl TraceWriter {
         }
     }
 
-    /// Flush a batch of commands to the database
+    /// Flush a batch of commands to the database in one transaction
     /// Ensures CreateTrace commands are executed first to satisfy foreign key constraints
-    async fn flush_batch(db: &Arc<Database>, batch: &mut Vec<TraceCommand>) {
+    async fn flush_batch(
+        db: &Arc<Database>,
+        batch: &mut Vec<TraceCommand>,
+        flush_count: &AtomicU64,
+    ) {
         if batch.is_empty() {
             return;
         }
@@ -191,19 +212,32 @@ impl TraceWriter {
         statements.extend(span_events);
         statements.extend(span_closes);
 
-        // Execute batch
-        if !statements.is_empty() {
-            match db.batch(statements).await {
-                Ok(_) => {
-                    // Batch write successful
-                }
-                Err(e) => {
-                    log::error!("TraceWriter batch write failed: {}", e);
+        if statements.is_empty() {
+            return;
+        }
+
+        flush_count.fetch_add(1, Ordering::Relaxed);
+        if let Err(e) = db.transaction(statements.clone()).await {
+            // One bad row (e.g. an event for a span that was never written)
+            // rolls back the whole batch; retry row by row to keep the rest
+            log::warn!(
+                "TraceWriter batch transaction failed, retrying {} statements individually: {}",
+                statements.len(),
+                e
+            );
+            for (sql, params) in statements {
+                if let Err(e) = db.execute(&sql, params).await {
+                    log::error!("TraceWriter write failed: {}", e);
                 }
             }
         }
     }
 
+    /// Number of batches flushed to the database so far
+    pub fn flush_count(&self) -> u64 {
+        self.flush_count.load(Ordering::Relaxed)
+    }
+
     /// Start a new trace and return its ID
     /// This is non-blocking - the trace is queued for writing
     pub fn start_trace(&self) -> String {
@@ -391,43 +425,30 @@ impl TraceWriter {
     }
 
     /// Shutdown the writer gracefully (blocking version for sync contexts)
-    /// This creates a new runtime to execute the async shutdown
+    /// Waits until every queued command has been committed, or until
+    /// `SHUTDOWN_TIMEOUT_MS` elapses.
     pub fn shutdown_blocking(&self) {
-        match tokio::runtime::Handle::try_current() {
-            Ok(handle) => {
-                // We're in an async context, block on shutdown
-                let sender = self.sender.clone();
-                handle.block_on(async move {
-                    match sender.send(TraceCommand::Shutdown).await {
-                        Ok(_) => {
-                            tokio::time::sleep(Duration::from_millis(100)).await;
-                            log::info!("TraceWriter shutdown complete");
-                        }
-                        Err(e) => {
-                            log::error!("Failed to send shutdown command: {:?}", e);
-                        }
-                    }
-                });
-            }
-            Err(_) => {
-                // No async runtime available, try creating one
-                if let Ok(rt) = tokio::runtime::Runtime::new() {
-                    let sender = self.sender.clone();
-                    rt.block_on(async move {
-                        match sender.send(TraceCommand::Shutdown).await {
-                            Ok(_) => {
-                                tokio::time::sleep(Duration::from_millis(100)).await;
-                                log::info!("TraceWriter shutdown complete (new runtime)");
-                            }
-                            Err(e) => {
-                                log::error!("Failed to send shutdown command: {:?}", e);
-                            }
-                        }
-                    });
-                } else {
-                    log::error!("Failed to create tokio runtime for TraceWriter shutdown");
-                }
+        let sender = self.sender.clone();
+        let (ack_tx, ack_rx) = std::sync::mpsc::channel();
+
+        // A plain thread can block on the channels whether or not the caller
+        // is inside a Tokio runtime
+        let result = std::thread::spawn(move || {
+            sender
+                .blocking_send(TraceCommand::Shutdown { ack: ack_tx })
+                .map_err(|e| format!("Failed to send shutdown command: {}", e))?;
+            ack_rx
+                .recv_timeout(Duration::from_millis(SHUTDOWN_TIMEOUT_MS))
+                .map_err(|e| format!("Timed out waiting for final flush: {}", e))
+        })
+        .join();
+
+        match result {
+            Ok(Ok(flushed)) => {
+                log::info!("TraceWriter shutdown complete ({} items flushed)", flushed)
             }
+            Ok(Err(e)) => log::error!("TraceWriter shutdown failed: {}", e),
+            Err(_) => log::error!("TraceWriter shutdown thread panicked"),
         }
     }
 }
@@ -439,6 +460,7 @@ impl Clone for TraceWriter {
             db: self.db.clone(),
             receiver: self.receiver.clone(),
             span_trace_ids: self.span_trace_ids.clone(),
+            flush_count: self.flush_count.clone(),
         }
     }
 }
@@ -618,4 +640,72 @@ mod tests {
         assert!(!trace_id2.is_empty());
         assert_ne!(trace_id1, trace_id2);
     }
+
+    #[tokio::test]
+    async fn test_events_are_batched_into_transactions() {
+        let (writer, db, _temp_dir) = create_test_writer().await;
+
+        let trace_id = writer.start_trace();
+        let span_id = writer.start_span(trace_id, None, "test.span".to_string(), HashMap::new());
+        writer.request_flush();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let flushes_before = writer.flush_count();
+
+        let event_count = 250;
+        for i in 0..event_count {
+            writer.add_event(
+                span_id.clone(),
+                "stream.chunk".to_string(),
+                Some(serde_json::json!({ "index": i })),
+            );
+        }
+        writer.request_flush();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let count = db
+            .query("SELECT COUNT(*) as count FROM span_events", vec![])
+            .await
+            .unwrap()
+            .rows[0]["count"]
+            .as_i64()
+            .unwrap();
+        assert_eq!(count, event_count);
+
+        let flushes = writer.flush_count() - flushes_before;
+        assert!(flushes >= 1);
+        assert!(
+            flushes < event_count as u64 / 10,
+            "expected batched writes, got {} transactions for {} events",
+            flushes,
+            event_count
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_shutdown_drains_queue() {
+        let (writer, db, _temp_dir) = create_test_writer().await;
+
+        let trace_id = writer.start_trace();
+        let span_id = writer.start_span(trace_id, None, "test.span".to_string(), HashMap::new());
+        let event_count = 1000;
+        for i in 0..event_count {
+            writer.add_event(
+                span_id.clone(),
+                "stream.chunk".to_string(),
+                Some(serde_json::json!({ "index": i })),
+            );
+        }
+
+        // No sleep: everything still queued must be committed before this returns
+        writer.shutdown_blocking();
+
+        let count = db
+            .query("SELECT COUNT(*) as count FROM span_events", vec![])
+            .await
+            .unwrap()
+            .rows[0]["count"]
+            .as_i64()
+            .unwrap();
+        assert_eq!(count, event_count);
+    }
 }
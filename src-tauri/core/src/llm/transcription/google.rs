@@ -114,7 +114,7 @@ impl GoogleTranscriptionClient {
             api_key
         );
 
-        let client = reqwest::Client::builder()
+        let client = crate::network_proxy::client_builder()
             .timeout(Duration::from_secs(120))
             .build()
             .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
@@ -114,7 +114,7 @@ impl GroqTranscriptionClient {
             .unwrap_or_else(|| "verbose_json".to_string());
         form = form.text("response_format", response_format);
 
-        let client = reqwest::Client::builder()
+        let client = crate::network_proxy::client_builder()
             .timeout(Duration::from_secs(60))
             .build()
             .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
@@ -75,7 +75,7 @@ impl OpenAITranscriptionClient {
             form = form.text("temperature", temperature.to_string());
         }
 
-        let client = reqwest::Client::builder()
+        let client = crate::network_proxy::client_builder()
             .timeout(Duration::from_secs(120))
             .build()
             .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
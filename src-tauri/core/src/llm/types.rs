@@ -62,6 +62,52 @@ pub struct ModelConfig {
     pub provider_mappings: Option<HashMap<String, String>>,
     pub pricing: Option<ModelPricing>,
     pub context_length: Option<u32>,
+    /// Filled from the model flags when the config doesn't list them
+    #[serde(default)]
+    pub capabilities: Option<ModelCapabilities>,
+    /// Maximum context in tokens, mirrors `context_length`
+    #[serde(default, rename = "contextWindow")]
+    pub context_window: Option<u32>,
+}
+
+/// Features a model supports, so callers can disable the ones it lacks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelCapabilities {
+    /// Image input
+    #[serde(default)]
+    pub vision: bool,
+    /// Tool calling
+    #[serde(default)]
+    pub tools: bool,
+    #[serde(default)]
+    pub reasoning: bool,
+    /// Audio input
+    #[serde(default)]
+    pub audio: bool,
+    /// Guaranteed JSON output
+    #[serde(default)]
+    pub json_mode: bool,
+}
+
+impl ModelConfig {
+    /// Capabilities from the config, or inferred from the model flags:
+    /// models that neither generate images nor only transcribe audio are chat
+    /// models with tools and JSON output, and interleaved thinking implies reasoning
+    pub fn resolved_capabilities(&self) -> ModelCapabilities {
+        if let Some(capabilities) = self.capabilities {
+            return capabilities;
+        }
+        let transcription = self.audio_input && self.context_length.is_none();
+        let chat = !self.image_output && !transcription;
+        ModelCapabilities {
+            vision: self.image_input,
+            tools: chat,
+            reasoning: self.interleaved,
+            audio: self.audio_input,
+            json_mode: chat,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -80,6 +126,16 @@ pub struct ModelsConfiguration {
     pub models: HashMap<String, ModelConfig>,
 }
 
+impl ModelsConfiguration {
+    /// Fill `capabilities` and `context_window` on every model
+    pub fn resolve_capabilities(&mut self) {
+        for model in self.models.values_mut() {
+            model.capabilities = Some(model.resolved_capabilities());
+            model.context_window = model.context_window.or(model.context_length);
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AvailableModel {
     pub key: String,
@@ -170,6 +226,25 @@ pub enum TransportFallbackTarget {
     FreshWebsocketBaseline,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseFormatType {
+    /// Any valid JSON object
+    JsonObject,
+    /// JSON matching `ResponseFormat::schema`
+    JsonSchema,
+}
+
+/// Structured output requested from the model
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResponseFormat {
+    #[serde(rename = "type")]
+    pub format_type: ResponseFormatType,
+    /// JSON Schema the output must match, for `json_schema`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<serde_json::Value>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StreamTextRequest {
     pub model: String,
@@ -199,8 +274,27 @@ pub struct StreamTextRequest {
     pub transport_session_id: Option<String>,
     #[serde(default, rename = "allowTransportFallback")]
     pub allow_transport_fallback: Option<bool>,
+    /// Named OAuth account to authenticate with; the provider's active account when unset
+    #[serde(default, rename = "oauthAccount")]
+    pub oauth_account: Option<String>,
+    /// Skip the global system prompt prefix/suffix for this request's session
+    #[serde(default, rename = "disableGlobalSystemPrompt")]
+    pub disable_global_system_prompt: Option<bool>,
+    /// Constrain the output to JSON. Only `StreamCollector` validates and
+    /// re-prompts, so this is not accepted from the window streaming path.
+    #[serde(skip)]
+    pub response_format: Option<ResponseFormat>,
+    /// Sampling seed for reproducible output, where the provider supports one
+    #[serde(default)]
+    pub seed: Option<i64>,
+    /// Sequences that end generation, capped at the provider's limit
+    #[serde(default)]
+    pub stop: Option<Vec<String>>,
     #[serde(default, rename = "continuationContext")]
     pub continuation_context: Option<ContinuationContext>,
+    /// Session the request's token usage is recorded against
+    #[serde(default, rename = "sessionId")]
+    pub session_id: Option<String>,
     #[serde(rename = "traceContext")]
     pub trace_context: Option<TraceContext>,
 }
@@ -336,6 +430,21 @@ pub enum StreamEvent {
         from: TransportFallbackSource,
         to: TransportFallbackTarget,
     },
+    /// A fallback model took over after `from` was unavailable
+    ModelFallback {
+        from: String,
+        to: String,
+        reason: String,
+    },
+    /// The history overflowed the context window and was compacted; the
+    /// request was retried with `messages`, which replace the caller's history
+    ContextCompacted {
+        messages: Vec<Message>,
+        #[serde(rename = "tokensBefore")]
+        tokens_before: usize,
+        #[serde(rename = "tokensAfter")]
+        tokens_after: usize,
+    },
     Usage {
         input_tokens: i32,
         output_tokens: i32,
@@ -527,12 +636,18 @@ mod tests {
             previous_response_id: Some("resp_prev".to_string()),
             transport_session_id: Some("session_123".to_string()),
             allow_transport_fallback: Some(true),
+            oauth_account: None,
+            disable_global_system_prompt: None,
+            response_format: None,
+            seed: None,
+            stop: None,
             continuation_context: Some(ContinuationContext {
                 iteration: 2,
                 baseline_message_count: 3,
                 delta_message_count: 1,
                 fallback_count: 0,
             }),
+            session_id: None,
             trace_context: None,
         };
 
@@ -638,4 +753,91 @@ mod tests {
             CustomProviderType::OpenAiCompatible
         ));
     }
+
+    #[test]
+    fn model_capabilities_inferred_when_config_omits_them() {
+        let mut config: ModelsConfiguration = serde_json::from_value(serde_json::json!({
+            "version": "synced",
+            "models": {
+                "chat": {
+                    "name": "Chat",
+                    "imageInput": true,
+                    "interleaved": true,
+                    "providers": ["openai"],
+                    "context_length": 128000
+                },
+                "painter": {
+                    "name": "Painter",
+                    "imageInput": true,
+                    "imageOutput": true,
+                    "providers": ["google"],
+                    "context_length": 32768
+                },
+                "listener": {
+                    "name": "Listener",
+                    "audioInput": true,
+                    "providers": ["openai"]
+                },
+                "custom": {
+                    "name": "Custom",
+                    "providers": ["openai-compatible-local"]
+                },
+                "explicit": {
+                    "name": "Explicit",
+                    "providers": ["openai"],
+                    "context_length": 8192,
+                    "capabilities": { "tools": true }
+                }
+            }
+        }))
+        .unwrap();
+        config.resolve_capabilities();
+
+        let capabilities = |key: &str| config.models[key].capabilities.unwrap();
+        assert_eq!(
+            capabilities("chat"),
+            ModelCapabilities {
+                vision: true,
+                tools: true,
+                reasoning: true,
+                audio: false,
+                json_mode: true,
+            }
+        );
+        assert_eq!(
+            capabilities("painter"),
+            ModelCapabilities {
+                vision: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            capabilities("listener"),
+            ModelCapabilities {
+                audio: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            capabilities("custom"),
+            ModelCapabilities {
+                tools: true,
+                json_mode: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            capabilities("explicit"),
+            ModelCapabilities {
+                tools: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(config.models["chat"].context_window, Some(128000));
+        assert_eq!(config.models["listener"].context_window, None);
+
+        let serialized = serde_json::to_value(&config.models["chat"]).unwrap();
+        assert_eq!(serialized["contextWindow"], 128000);
+        assert_eq!(serialized["capabilities"]["jsonMode"], true);
+    }
 }
@@ -0,0 +1,467 @@
+// Network proxy configuration
+// Applies HTTP/HTTPS/SOCKS5 proxy settings to every outgoing reqwest client:
+// provider traffic, OAuth, gateways, media services and the frontend fetch proxy.
+
+use crate::storage::settings::SettingsRepository;
+use crate::storage::Storage;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+/// Setting key holding the user's proxy configuration (JSON object)
+pub const PROXY_SETTINGS_KEY: &str = "network_proxy";
+
+const LOCALHOST_HOSTS: &[&str] = &["localhost", "127.0.0.1", "::1"];
+
+static CONFIGURED_PROXY: RwLock<Option<ProxyConfig>> = RwLock::new(None);
+/// Bumped on every change so cached clients know to rebuild
+static PROXY_GENERATION: AtomicU64 = AtomicU64::new(0);
+/// Shared clients, rebuilt when the proxy configuration changes
+static SHARED_CLIENT: ClientCache = Mutex::new(None);
+static SHARED_JSON_CLIENT: ClientCache = Mutex::new(None);
+
+type ClientCache = Mutex<Option<(u64, Arc<reqwest::Client>)>>;
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyConfig {
+    /// Proxy URL: http://, https://, socks5:// or socks5h://
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Send localhost traffic (Ollama, LM Studio, MCP servers) directly
+    #[serde(default = "default_true")]
+    pub bypass_localhost: bool,
+    /// Extra hosts or domains that skip the proxy
+    #[serde(default)]
+    pub no_proxy: Vec<String>,
+}
+
+impl Default for ProxyConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            username: None,
+            password: None,
+            bypass_localhost: true,
+            no_proxy: Vec::new(),
+        }
+    }
+}
+
+impl ProxyConfig {
+    /// Read `ALL_PROXY`, `HTTPS_PROXY`, `HTTP_PROXY` and `NO_PROXY` (either case)
+    pub fn from_env() -> Self {
+        Self::from_env_with(|key| std::env::var(key).ok())
+    }
+
+    fn from_env_with(get: impl Fn(&str) -> Option<String>) -> Self {
+        let lookup = |key: &str| {
+            get(key)
+                .or_else(|| get(&key.to_lowercase()))
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        let url = lookup("ALL_PROXY")
+            .or_else(|| lookup("HTTPS_PROXY"))
+            .or_else(|| lookup("HTTP_PROXY"));
+        let no_proxy = lookup("NO_PROXY")
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(|host| host.trim().to_string())
+                    .filter(|host| !host.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            url,
+            no_proxy,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.url
+            .as_deref()
+            .is_some_and(|url| !url.trim().is_empty())
+    }
+
+    /// Hosts that bypass the proxy, including localhost when requested
+    pub fn no_proxy_list(&self) -> Vec<String> {
+        let mut hosts = self.no_proxy.clone();
+        if self.bypass_localhost {
+            for host in LOCALHOST_HOSTS {
+                if !hosts.iter().any(|h| h.eq_ignore_ascii_case(host)) {
+                    hosts.push(host.to_string());
+                }
+            }
+        }
+        hosts
+    }
+
+    /// Whether requests to `host` skip the proxy
+    pub fn bypasses(&self, host: &str) -> bool {
+        let host = host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_lowercase();
+        self.no_proxy_list().iter().any(|entry| {
+            let entry = entry.trim_start_matches('.').to_lowercase();
+            entry == "*" || host == entry || host.ends_with(&format!(".{}", entry))
+        })
+    }
+
+    /// Build the reqwest proxy, or None when no proxy is configured
+    pub fn to_reqwest_proxy(&self) -> Result<Option<reqwest::Proxy>, String> {
+        let Some(url) = self.url.as_deref().map(str::trim).filter(|u| !u.is_empty()) else {
+            return Ok(None);
+        };
+
+        let scheme = url.split("://").next().unwrap_or_default().to_lowercase();
+        if !matches!(scheme.as_str(), "http" | "https" | "socks5" | "socks5h") {
+            return Err(format!("Unsupported proxy scheme: {}", scheme));
+        }
+
+        let mut proxy =
+            reqwest::Proxy::all(url).map_err(|e| format!("Invalid proxy URL: {}", e))?;
+        if let Some(username) = self.username.as_deref().filter(|u| !u.is_empty()) {
+            proxy = proxy.basic_auth(username, self.password.as_deref().unwrap_or(""));
+        }
+        let no_proxy = self.no_proxy_list().join(",");
+        proxy = proxy.no_proxy(reqwest::NoProxy::from_string(&no_proxy));
+        Ok(Some(proxy))
+    }
+
+    /// Apply this configuration to a client builder
+    pub fn apply(&self, builder: reqwest::ClientBuilder) -> Result<reqwest::ClientBuilder, String> {
+        Ok(match self.to_reqwest_proxy()? {
+            Some(proxy) => builder.proxy(proxy),
+            None => builder,
+        })
+    }
+}
+
+/// The proxy in effect: the saved setting if it has a URL, otherwise the environment
+pub fn current_proxy_config() -> ProxyConfig {
+    let configured = CONFIGURED_PROXY.read().ok().and_then(|guard| guard.clone());
+    match configured {
+        Some(config) if config.is_enabled() => config,
+        Some(config) => ProxyConfig {
+            bypass_localhost: config.bypass_localhost,
+            ..ProxyConfig::from_env()
+        },
+        None => ProxyConfig::from_env(),
+    }
+}
+
+/// Replace the saved proxy configuration for clients built from now on
+pub fn set_current_proxy_config(config: Option<ProxyConfig>) {
+    if let Ok(mut guard) = CONFIGURED_PROXY.write() {
+        *guard = config;
+    }
+    PROXY_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Changes whenever the proxy configuration changes
+pub fn proxy_generation() -> u64 {
+    PROXY_GENERATION.load(Ordering::SeqCst)
+}
+
+/// Start a client builder with the current proxy applied.
+/// An invalid proxy is logged and ignored so requests still go out directly.
+pub fn client_builder() -> reqwest::ClientBuilder {
+    match current_proxy_config().apply(reqwest::Client::builder()) {
+        Ok(builder) => builder,
+        Err(e) => {
+            log::warn!("Ignoring proxy configuration: {}", e);
+            reqwest::Client::builder()
+        }
+    }
+}
+
+/// The process-wide HTTP client, with the current proxy applied.
+/// Bodies are not decoded so SSE streams and downloads see the raw bytes.
+/// There is no overall timeout; callers set one per request with
+/// `RequestBuilder::timeout`.
+pub fn shared_client() -> Arc<reqwest::Client> {
+    cached_client(&SHARED_CLIENT, proxy_generation(), false)
+}
+
+/// Like `shared_client`, but decodes gzip, brotli and deflate responses.
+/// For non-streaming calls that read the whole body, such as JSON APIs.
+pub fn shared_json_client() -> Arc<reqwest::Client> {
+    cached_client(&SHARED_JSON_CLIENT, proxy_generation(), true)
+}
+
+fn cached_client(cache: &ClientCache, generation: u64, decompress: bool) -> Arc<reqwest::Client> {
+    let mut guard = cache.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((built_for, client)) = guard.as_ref() {
+        if *built_for == generation {
+            return client.clone();
+        }
+    }
+
+    let client = Arc::new(
+        client_builder()
+            .connect_timeout(Duration::from_secs(10))
+            .gzip(decompress)
+            .brotli(decompress)
+            .deflate(decompress)
+            .tcp_nodelay(true)
+            .pool_max_idle_per_host(5)
+            .build()
+            .expect("Failed to build HTTP client"),
+    );
+    *guard = Some((generation, client.clone()));
+    client
+}
+
+/// Load the saved proxy setting
+pub async fn load_proxy_setting(settings: &SettingsRepository) {
+    match settings
+        .get_setting_or_default::<Option<ProxyConfig>>(PROXY_SETTINGS_KEY, None)
+        .await
+    {
+        Ok(config) => set_current_proxy_config(config),
+        Err(e) => log::warn!("Failed to read proxy setting: {}", e),
+    }
+}
+
+#[tauri::command]
+pub fn get_proxy_config() -> ProxyConfig {
+    current_proxy_config()
+}
+
+/// Validate, persist and apply a proxy configuration
+#[tauri::command]
+pub async fn set_proxy_config(
+    config: ProxyConfig,
+    storage: tauri::State<'_, Storage>,
+) -> Result<(), String> {
+    config.to_reqwest_proxy()?;
+    let value =
+        serde_json::to_value(&config).map_err(|e| format!("Failed to serialize proxy: {}", e))?;
+    storage
+        .settings
+        .set_setting(PROXY_SETTINGS_KEY, &value)
+        .await?;
+    set_current_proxy_config(Some(config));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::io::Read;
+    use std::sync::mpsc;
+
+    /// Records the request target of every request it receives
+    fn spawn_recording_server() -> (u16, mpsc::Receiver<String>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                let mut body = String::new();
+                let _ = request.as_reader().read_to_string(&mut body);
+                let _ = tx.send(request.url().to_string());
+                let _ = request.respond(tiny_http::Response::from_string("ok"));
+            }
+        });
+        (port, rx)
+    }
+
+    fn proxy_at(port: u16) -> ProxyConfig {
+        ProxyConfig {
+            url: Some(format!("http://127.0.0.1:{}", port)),
+            ..ProxyConfig::default()
+        }
+    }
+
+    #[test]
+    fn reads_proxy_from_env() {
+        let env: HashMap<&str, &str> = HashMap::from([
+            ("HTTPS_PROXY", "http://proxy.corp:8080"),
+            ("no_proxy", "internal.corp, .svc"),
+        ]);
+        let config = ProxyConfig::from_env_with(|key| env.get(key).map(|v| v.to_string()));
+        assert_eq!(config.url.as_deref(), Some("http://proxy.corp:8080"));
+        assert_eq!(config.no_proxy, vec!["internal.corp", ".svc"]);
+
+        let env: HashMap<&str, &str> = HashMap::from([
+            ("ALL_PROXY", "socks5://127.0.0.1:1080"),
+            ("HTTP_PROXY", "http://proxy.corp:8080"),
+        ]);
+        let config = ProxyConfig::from_env_with(|key| env.get(key).map(|v| v.to_string()));
+        assert_eq!(config.url.as_deref(), Some("socks5://127.0.0.1:1080"));
+
+        let config = ProxyConfig::from_env_with(|_| None);
+        assert!(!config.is_enabled());
+    }
+
+    #[test]
+    fn no_proxy_list_excludes_localhost() {
+        let config = ProxyConfig {
+            no_proxy: vec!["internal.corp".to_string()],
+            ..proxy_at(8080)
+        };
+        assert!(config.bypasses("localhost"));
+        assert!(config.bypasses("127.0.0.1"));
+        assert!(config.bypasses("[::1]"));
+        assert!(config.bypasses("api.internal.corp"));
+        assert!(!config.bypasses("api.openai.com"));
+
+        let config = ProxyConfig {
+            bypass_localhost: false,
+            ..proxy_at(8080)
+        };
+        assert!(!config.bypasses("localhost"));
+    }
+
+    #[test]
+    fn builds_socks_and_authenticated_proxies() {
+        let socks = ProxyConfig {
+            url: Some("socks5h://127.0.0.1:1080".to_string()),
+            username: Some("user".to_string()),
+            password: Some("secret".to_string()),
+            ..ProxyConfig::default()
+        };
+        assert!(socks.to_reqwest_proxy().unwrap().is_some());
+        assert!(ProxyConfig::default().to_reqwest_proxy().unwrap().is_none());
+
+        let invalid = ProxyConfig {
+            url: Some("ftp://proxy:21".to_string()),
+            ..ProxyConfig::default()
+        };
+        assert!(invalid.to_reqwest_proxy().is_err());
+    }
+
+    #[tokio::test]
+    async fn client_sends_requests_through_configured_proxy() {
+        let (proxy_port, proxy_rx) = spawn_recording_server();
+        let client = proxy_at(proxy_port)
+            .apply(reqwest::Client::builder())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let response = client
+            .get("http://provider.invalid/v1/models")
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        // Proxies receive the absolute URL as the request target
+        assert_eq!(
+            proxy_rx
+                .recv_timeout(std::time::Duration::from_secs(1))
+                .unwrap(),
+            "http://provider.invalid/v1/models"
+        );
+    }
+
+    #[tokio::test]
+    async fn client_bypasses_proxy_for_localhost() {
+        let (proxy_port, proxy_rx) = spawn_recording_server();
+        let (direct_port, direct_rx) = spawn_recording_server();
+        let client = proxy_at(proxy_port)
+            .apply(reqwest::Client::builder())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        client
+            .get(format!("http://localhost:{}/api/tags", direct_port))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            direct_rx
+                .recv_timeout(std::time::Duration::from_secs(1))
+                .unwrap(),
+            "/api/tags"
+        );
+        assert!(proxy_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn shared_client_is_reused_until_proxy_changes() {
+        let cache: ClientCache = Mutex::new(None);
+        let first = cached_client(&cache, 1, false);
+        let second = cached_client(&cache, 1, false);
+        assert!(Arc::ptr_eq(&first, &second));
+
+        let rebuilt = cached_client(&cache, 2, false);
+        assert!(!Arc::ptr_eq(&first, &rebuilt));
+    }
+
+    #[tokio::test]
+    async fn json_client_decodes_gzip_responses() {
+        use flate2::write::GzEncoder;
+        use std::io::Write;
+
+        let body = r#"{"data":[{"id":"gpt-4o"}]}"#;
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(body.as_bytes()).unwrap();
+        let gzipped = encoder.finish().unwrap();
+
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+        std::thread::spawn(move || {
+            for request in server.incoming_requests().take(2) {
+                let response = tiny_http::Response::from_data(gzipped.clone())
+                    .with_header(tiny_http::Header::from_bytes("Content-Encoding", "gzip").unwrap())
+                    .with_header(
+                        tiny_http::Header::from_bytes("Content-Type", "application/json").unwrap(),
+                    );
+                let _ = request.respond(response);
+            }
+        });
+        let url = format!("http://127.0.0.1:{}/v1/models", port);
+
+        let json: serde_json::Value = shared_json_client()
+            .get(&url)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(json["data"][0]["id"], "gpt-4o");
+
+        // The streaming client passes the encoded bytes through untouched
+        let raw = shared_client()
+            .get(&url)
+            .send()
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap();
+        assert_eq!(&raw[..2], &[0x1f, 0x8b]);
+    }
+
+    #[test]
+    fn saved_config_takes_precedence_over_env() {
+        let before = proxy_generation();
+        set_current_proxy_config(Some(proxy_at(3128)));
+        assert!(proxy_generation() > before);
+        assert_eq!(
+            current_proxy_config().url.as_deref(),
+            Some("http://127.0.0.1:3128")
+        );
+        set_current_proxy_config(None);
+    }
+}
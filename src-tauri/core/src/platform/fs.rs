@@ -38,7 +38,9 @@ impl FileSystemPlatform {
     }
 
     /// Validate that a path for writing is within the workspace root
-    /// For write operations, the file may not exist yet, so we validate the parent directory
+    /// For write operations the file and its parent directories may not exist yet,
+    /// so we canonicalize the nearest existing ancestor and re-append the missing
+    /// components, which may not contain `..`
     fn validate_write_path(&self, path: &Path, ctx: &PlatformContext) -> Result<PathBuf, String> {
         // Get absolute path (without requiring file to exist)
         let absolute_path = if path.is_absolute() {
@@ -53,42 +55,42 @@ impl FileSystemPlatform {
             .canonicalize()
             .map_err(|e| format!("Invalid workspace root: {}", e))?;
 
-        // For new files, validate that the parent directory is within workspace
+        let file_name = absolute_path
+            .file_name()
+            .ok_or_else(|| format!("Invalid path: {}", absolute_path.display()))?;
         let parent = absolute_path
             .parent()
             .ok_or_else(|| "Invalid path: no parent directory".to_string())?;
 
-        // Create parent if it doesn't exist for validation purposes
-        if !parent.exists() {
-            // Parent doesn't exist yet, check if it would be within workspace
-            let parent_absolute = if parent.is_absolute() {
-                parent.to_path_buf()
-            } else {
-                ctx.workspace_root.join(parent)
-            };
-
-            if !parent_absolute.starts_with(&canonical_root) {
-                return Err(format!(
-                    "Path '{}' is outside workspace root '{}'",
-                    absolute_path.display(),
-                    canonical_root.display()
-                ));
-            }
-        } else {
-            let canonical_parent = parent
-                .canonicalize()
-                .map_err(|e| format!("Invalid parent directory: {}", e))?;
-
-            if !canonical_parent.starts_with(&canonical_root) {
-                return Err(format!(
-                    "Path '{}' is outside workspace root '{}'",
-                    absolute_path.display(),
-                    canonical_root.display()
-                ));
+        let mut missing = Vec::new();
+        let mut ancestor = parent;
+        while !ancestor.exists() {
+            // file_name() is None for a trailing `..`, which can't be resolved lexically
+            match (ancestor.file_name(), ancestor.parent()) {
+                (Some(name), Some(next)) => {
+                    missing.push(name);
+                    ancestor = next;
+                }
+                _ => return Err(format!("Invalid path: {}", absolute_path.display())),
             }
         }
 
-        Ok(absolute_path)
+        let mut resolved = ancestor
+            .canonicalize()
+            .map_err(|e| format!("Invalid parent directory: {}", e))?;
+        for name in missing.iter().rev() {
+            resolved.push(name);
+        }
+
+        if !resolved.starts_with(&canonical_root) {
+            return Err(format!(
+                "Path '{}' is outside workspace root '{}'",
+                absolute_path.display(),
+                canonical_root.display()
+            ));
+        }
+
+        Ok(resolved.join(file_name))
     }
 
     /// Read file contents
@@ -120,21 +122,31 @@ impl FileSystemPlatform {
         }
     }
 
-    /// Write file contents
+    /// Write file contents, creating missing parent directories when `create_dirs` is set
     pub async fn write_file(
         &self,
         path: &str,
         content: &str,
+        create_dirs: bool,
         ctx: &PlatformContext,
     ) -> PlatformResult<()> {
         let path = Path::new(path);
 
         match self.validate_write_path(path, ctx) {
             Ok(validated_path) => {
-                // Ensure parent directory exists
                 if let Some(parent) = validated_path.parent() {
-                    if let Err(e) = tokio::fs::create_dir_all(parent).await {
-                        return PlatformResult::error(format!("Failed to create directory: {}", e));
+                    if create_dirs {
+                        if let Err(e) = tokio::fs::create_dir_all(parent).await {
+                            return PlatformResult::error(format!(
+                                "Failed to create directory: {}",
+                                e
+                            ));
+                        }
+                    } else if !parent.is_dir() {
+                        return PlatformResult::error(format!(
+                            "Parent directory does not exist: {}",
+                            parent.display()
+                        ));
                     }
                 }
 
@@ -147,6 +159,61 @@ impl FileSystemPlatform {
         }
     }
 
+    /// Append to a file, creating it (and missing parent directories) if needed.
+    /// The resulting file size is checked against `max_file_size`.
+    pub async fn append_file(
+        &self,
+        path: &str,
+        content: &str,
+        ctx: &PlatformContext,
+    ) -> PlatformResult<()> {
+        use tokio::io::AsyncWriteExt;
+
+        let path = Path::new(path);
+
+        match self.validate_write_path(path, ctx) {
+            Ok(validated_path) => {
+                let existing_size = match tokio::fs::metadata(&validated_path).await {
+                    Ok(metadata) => metadata.len(),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+                    Err(e) => {
+                        return PlatformResult::error(format!("Failed to get file metadata: {}", e))
+                    }
+                };
+
+                let total_size = existing_size + content.len() as u64;
+                if total_size > ctx.max_file_size as u64 {
+                    return PlatformResult::error(format!(
+                        "File too large after append: {} bytes (max: {})",
+                        total_size, ctx.max_file_size
+                    ));
+                }
+
+                if let Some(parent) = validated_path.parent() {
+                    if let Err(e) = tokio::fs::create_dir_all(parent).await {
+                        return PlatformResult::error(format!("Failed to create directory: {}", e));
+                    }
+                }
+
+                let mut file = match tokio::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&validated_path)
+                    .await
+                {
+                    Ok(file) => file,
+                    Err(e) => return PlatformResult::error(format!("Failed to open file: {}", e)),
+                };
+
+                match file.write_all(content.as_bytes()).await {
+                    Ok(_) => PlatformResult::success(()),
+                    Err(e) => PlatformResult::error(format!("Failed to append to file: {}", e)),
+                }
+            }
+            Err(e) => PlatformResult::error(e),
+        }
+    }
+
     /// Check if file exists
     pub async fn file_exists(&self, path: &str, ctx: &PlatformContext) -> PlatformResult<bool> {
         let path = Path::new(path);
@@ -292,7 +359,7 @@ mod tests {
         let test_path = test_file.to_string_lossy().to_string();
 
         // Write file
-        let write_result = fs.write_file(&test_path, "Hello, World!", &ctx).await;
+        let write_result = fs.write_file(&test_path, "Hello, World!", true, &ctx).await;
         assert!(write_result.success);
 
         // Read file
@@ -354,4 +421,156 @@ mod tests {
         assert!(not_exists_result.success);
         assert_eq!(not_exists_result.data, Some(false));
     }
+
+    #[tokio::test]
+    async fn test_write_file_creates_parent_directories() {
+        let fs = FileSystemPlatform::new();
+        let temp_dir = TempDir::new().unwrap();
+
+        let ctx = PlatformContext {
+            workspace_root: temp_dir.path().to_path_buf(),
+            worktree_path: None,
+            max_file_size: 1024 * 1024,
+            shell_timeout_secs: 60,
+        };
+
+        let result = fs.write_file("a/b/c.txt", "nested", true, &ctx).await;
+        assert!(result.success, "{:?}", result.error);
+        assert!(temp_dir.path().join("a/b").is_dir());
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("a/b/c.txt")).unwrap(),
+            "nested"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_file_without_create_dirs() {
+        let fs = FileSystemPlatform::new();
+        let temp_dir = TempDir::new().unwrap();
+
+        let ctx = PlatformContext {
+            workspace_root: temp_dir.path().to_path_buf(),
+            worktree_path: None,
+            max_file_size: 1024 * 1024,
+            shell_timeout_secs: 60,
+        };
+
+        let result = fs.write_file("missing/c.txt", "x", false, &ctx).await;
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("does not exist"));
+        assert!(!temp_dir.path().join("missing").exists());
+    }
+
+    #[tokio::test]
+    async fn test_write_file_rejects_escape_through_missing_dirs() {
+        let fs = FileSystemPlatform::new();
+        let workspace = TempDir::new().unwrap();
+        let workspace_root = workspace.path().join("ws");
+        std::fs::create_dir(&workspace_root).unwrap();
+
+        let ctx = PlatformContext {
+            workspace_root: workspace_root.clone(),
+            worktree_path: None,
+            max_file_size: 1024 * 1024,
+            shell_timeout_secs: 60,
+        };
+
+        let result = fs.write_file("../escaped.txt", "x", true, &ctx).await;
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("outside workspace"));
+
+        // Lexically inside the root, but `..` climbs out through directories that don't exist
+        let result = fs
+            .write_file("a/../../b/escaped.txt", "x", true, &ctx)
+            .await;
+        assert!(!result.success);
+        assert!(!workspace.path().join("b").exists());
+        assert!(!workspace_root.join("a").exists());
+    }
+
+    #[tokio::test]
+    async fn test_append_file_to_existing() {
+        let fs = FileSystemPlatform::new();
+        let temp_dir = TempDir::new().unwrap();
+
+        let ctx = PlatformContext {
+            workspace_root: temp_dir.path().to_path_buf(),
+            worktree_path: None,
+            max_file_size: 1024 * 1024,
+            shell_timeout_secs: 60,
+        };
+
+        std::fs::write(temp_dir.path().join("log.txt"), "first\n").unwrap();
+        let result = fs.append_file("log.txt", "second\n", &ctx).await;
+        assert!(result.success, "{:?}", result.error);
+
+        let read_result = fs
+            .read_file(&temp_dir.path().join("log.txt").to_string_lossy(), &ctx)
+            .await;
+        assert_eq!(read_result.data, Some("first\nsecond\n".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_append_file_creates_then_appends() {
+        let fs = FileSystemPlatform::new();
+        let temp_dir = TempDir::new().unwrap();
+
+        let ctx = PlatformContext {
+            workspace_root: temp_dir.path().to_path_buf(),
+            worktree_path: None,
+            max_file_size: 1024 * 1024,
+            shell_timeout_secs: 60,
+        };
+
+        assert!(fs.append_file("out/result.txt", "a", &ctx).await.success);
+        assert!(fs.append_file("out/result.txt", "b", &ctx).await.success);
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("out/result.txt")).unwrap(),
+            "ab"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_append_file_respects_max_file_size() {
+        let fs = FileSystemPlatform::new();
+        let temp_dir = TempDir::new().unwrap();
+
+        let ctx = PlatformContext {
+            workspace_root: temp_dir.path().to_path_buf(),
+            worktree_path: None,
+            max_file_size: 8,
+            shell_timeout_secs: 60,
+        };
+
+        std::fs::write(temp_dir.path().join("small.txt"), "12345").unwrap();
+        let result = fs.append_file("small.txt", "6789", &ctx).await;
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("File too large"));
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("small.txt")).unwrap(),
+            "12345"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_append_file_outside_workspace() {
+        let fs = FileSystemPlatform::new();
+        let workspace_dir = TempDir::new().unwrap();
+        let outside_dir = TempDir::new().unwrap();
+
+        let ctx = PlatformContext {
+            workspace_root: workspace_dir.path().to_path_buf(),
+            worktree_path: None,
+            max_file_size: 1024 * 1024,
+            shell_timeout_secs: 60,
+        };
+
+        let outside_file = outside_dir.path().join("outside.txt");
+        let result = fs
+            .append_file(&outside_file.to_string_lossy(), "data", &ctx)
+            .await;
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("outside workspace"));
+        assert!(!outside_file.exists());
+    }
 }
@@ -136,6 +136,43 @@ impl GitPlatform {
         }
     }
 
+    /// Get the current branch with its upstream and ahead/behind counts
+    pub async fn get_branch_info(&self, ctx: &PlatformContext) -> PlatformResult<GitBranchInfo> {
+        let path = self.get_effective_path(ctx);
+
+        let validated_path = match self.validate_path(&path, ctx) {
+            Ok(p) => p,
+            Err(e) => return PlatformResult::error(e),
+        };
+
+        let repo = match crate::git::repository::discover_repository(&validated_path) {
+            Ok(repo) => repo,
+            Err(e) => return PlatformResult::error(format!("Failed to open repository: {}", e)),
+        };
+
+        let info = match crate::git::repository::get_current_branch(&repo) {
+            Ok(info) => info,
+            Err(e) => return PlatformResult::error(format!("Failed to get branch: {}", e)),
+        };
+
+        let branch = if info.is_head {
+            match repo.head().ok().and_then(|head| head.target()) {
+                Some(oid) => oid.to_string()[..7].to_string(),
+                None => return PlatformResult::error("HEAD has no target".to_string()),
+            }
+        } else {
+            info.name
+        };
+
+        PlatformResult::success(GitBranchInfo {
+            branch,
+            upstream: info.upstream,
+            ahead: info.ahead.unwrap_or(0),
+            behind: info.behind.unwrap_or(0),
+            is_detached: info.is_head,
+        })
+    }
+
     /// Get file diff
     pub async fn get_file_diff(
         &self,
@@ -215,6 +252,57 @@ impl GitPlatform {
             Err(e) => PlatformResult::error(e),
         }
     }
+
+    /// Commit the staged changes, returning the new commit hash
+    pub async fn commit(
+        &self,
+        message: &str,
+        amend: bool,
+        author: Option<&str>,
+        ctx: &PlatformContext,
+    ) -> PlatformResult<String> {
+        let path = self.get_effective_path(ctx);
+
+        match self.validate_path(&path, ctx) {
+            Ok(validated_path) => match crate::git::git_commit(
+                validated_path.to_string_lossy().to_string(),
+                message.to_string(),
+                amend,
+                author.map(|a| a.to_string()),
+            )
+            .await
+            {
+                Ok(hash) => PlatformResult::success(hash),
+                Err(e) => PlatformResult::error(e),
+            },
+            Err(e) => PlatformResult::error(e),
+        }
+    }
+
+    /// Get commit history (newest first), optionally limited to one path
+    pub async fn get_log(
+        &self,
+        limit: Option<usize>,
+        path_filter: Option<&str>,
+        ctx: &PlatformContext,
+    ) -> PlatformResult<Vec<crate::git::types::CommitInfo>> {
+        let path = self.get_effective_path(ctx);
+
+        match self.validate_path(&path, ctx) {
+            Ok(validated_path) => match crate::git::git_log(
+                validated_path.to_string_lossy().to_string(),
+                limit,
+                None,
+                path_filter.map(|p| p.to_string()),
+            )
+            .await
+            {
+                Ok(commits) => PlatformResult::success(commits),
+                Err(e) => PlatformResult::error(e),
+            },
+            Err(e) => PlatformResult::error(e),
+        }
+    }
 }
 
 impl Default for GitPlatform {
@@ -226,10 +314,98 @@ impl Default for GitPlatform {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::git::test_support::git;
 
     #[test]
     fn test_git_platform_creation() {
         let _git = GitPlatform::new();
         // Platform created successfully
     }
+
+    fn commit_file(dir: &Path, file: &str, message: &str) {
+        std::fs::write(dir.join(file), message).unwrap();
+        git(dir, &["add", file]);
+        git(dir, &["commit", "-m", message]);
+    }
+
+    fn configure_user(dir: &Path) {
+        git(dir, &["config", "user.email", "test@test.com"]);
+        git(dir, &["config", "user.name", "Test User"]);
+    }
+
+    fn context(dir: &Path) -> PlatformContext {
+        PlatformContext {
+            workspace_root: dir.to_path_buf(),
+            worktree_path: None,
+            max_file_size: 1024 * 1024,
+            shell_timeout_secs: 60,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_branch_info_ahead_behind() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let local = temp_dir.path().join("local");
+        let remote = temp_dir.path().join("remote.git");
+        let other = temp_dir.path().join("other");
+        std::fs::create_dir(&local).unwrap();
+
+        git(&local, &["init"]);
+        configure_user(&local);
+        git(&local, &["checkout", "-b", "main"]);
+        commit_file(&local, "README.md", "Initial commit");
+        git(
+            temp_dir.path(),
+            &["init", "--bare", remote.to_str().unwrap()],
+        );
+        git(
+            &local,
+            &["remote", "add", "origin", remote.to_str().unwrap()],
+        );
+        git(&local, &["push", "-u", "origin", "main"]);
+
+        // Diverge: one commit pushed from another clone, one local commit
+        git(
+            temp_dir.path(),
+            &[
+                "clone",
+                "--branch",
+                "main",
+                remote.to_str().unwrap(),
+                other.to_str().unwrap(),
+            ],
+        );
+        configure_user(&other);
+        commit_file(&other, "remote.txt", "Remote change");
+        git(&other, &["push", "origin", "main"]);
+        commit_file(&local, "local.txt", "Local change");
+        git(&local, &["fetch", "origin"]);
+
+        let result = GitPlatform::new().get_branch_info(&context(&local)).await;
+        assert!(result.success, "{:?}", result.error);
+        let info = result.data.unwrap();
+        assert_eq!(info.branch, "main");
+        assert_eq!(info.upstream.as_deref(), Some("origin/main"));
+        assert_eq!(info.ahead, 1);
+        assert_eq!(info.behind, 1);
+        assert!(!info.is_detached);
+    }
+
+    #[tokio::test]
+    async fn test_get_branch_info_detached() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        git(dir, &["init"]);
+        configure_user(dir);
+        commit_file(dir, "README.md", "Initial commit");
+        git(dir, &["checkout", "--detach", "HEAD"]);
+
+        let result = GitPlatform::new().get_branch_info(&context(dir)).await;
+        assert!(result.success, "{:?}", result.error);
+        let info = result.data.unwrap();
+        assert!(info.is_detached);
+        assert_eq!(info.branch.len(), 7);
+        assert!(info.upstream.is_none());
+        assert_eq!((info.ahead, info.behind), (0, 0));
+    }
 }
@@ -73,7 +73,29 @@ impl Platform {
                     .get("content")
                     .and_then(|v| v.as_str())
                     .ok_or("Missing 'content' parameter")?;
-                let result = self.filesystem.write_file(path, content, ctx).await;
+                let create_dirs = input
+                    .get("create_dirs")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(true);
+                let result = self
+                    .filesystem
+                    .write_file(path, content, create_dirs, ctx)
+                    .await;
+                Ok(serde_json::json!({
+                    "success": result.success,
+                    "error": result.error
+                }))
+            }
+            "append_file" => {
+                let path = input
+                    .get("path")
+                    .and_then(|v| v.as_str())
+                    .ok_or("Missing 'path' parameter")?;
+                let content = input
+                    .get("content")
+                    .and_then(|v| v.as_str())
+                    .ok_or("Missing 'content' parameter")?;
+                let result = self.filesystem.append_file(path, content, ctx).await;
                 Ok(serde_json::json!({
                     "success": result.success,
                     "error": result.error
@@ -140,6 +162,10 @@ impl Platform {
                     "stdout": result.data.as_ref().map(|r| &r.stdout),
                     "stderr": result.data.as_ref().map(|r| &r.stderr),
                     "exit_code": result.data.as_ref().map(|r| r.exit_code),
+                    "stdout_truncated": result.data.as_ref().map(|r| r.stdout_truncated),
+                    "stderr_truncated": result.data.as_ref().map(|r| r.stderr_truncated),
+                    "stdout_bytes": result.data.as_ref().map(|r| r.stdout_bytes),
+                    "stderr_bytes": result.data.as_ref().map(|r| r.stderr_bytes),
                     "error": result.error
                 }))
             }
@@ -176,6 +202,36 @@ impl Platform {
                     "error": result.error
                 }))
             }
+            "git_commit" => {
+                let message = input
+                    .get("message")
+                    .and_then(|v| v.as_str())
+                    .ok_or("Missing 'message' parameter")?;
+                let amend = input
+                    .get("amend")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                let author = input.get("author").and_then(|v| v.as_str());
+                let result = self.git.commit(message, amend, author, ctx).await;
+                Ok(serde_json::json!({
+                    "success": result.success,
+                    "commit": result.data,
+                    "error": result.error
+                }))
+            }
+            "git_log" => {
+                let limit = input
+                    .get("limit")
+                    .and_then(|v| v.as_u64())
+                    .map(|v| v as usize);
+                let path = input.get("path").and_then(|v| v.as_str());
+                let result = self.git.get_log(limit, path, ctx).await;
+                Ok(serde_json::json!({
+                    "success": result.success,
+                    "commits": result.data,
+                    "error": result.error
+                }))
+            }
             _ => Err(format!("Unknown tool: {}", tool_name)),
         }
     }
@@ -190,6 +246,7 @@ impl Default for Platform {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::git::test_support::git;
 
     #[test]
     fn test_platform_creation() {
@@ -206,4 +263,120 @@ mod tests {
         assert_eq!(ctx.workspace_root, temp_dir);
         assert!(ctx.worktree_path.is_none());
     }
+
+    fn create_test_repo() -> tempfile::TempDir {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        git(dir, &["init"]);
+        git(dir, &["config", "user.email", "test@test.com"]);
+        git(dir, &["config", "user.name", "Test User"]);
+        std::fs::write(dir.join("README.md"), "# Test").unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-m", "Initial commit"]);
+        temp_dir
+    }
+
+    #[tokio::test]
+    async fn test_execute_tool_git_log() {
+        let temp_dir = create_test_repo();
+        std::fs::write(temp_dir.path().join("main.rs"), "fn main() {}").unwrap();
+        git(temp_dir.path(), &["add", "."]);
+        git(temp_dir.path(), &["commit", "-m", "Add main"]);
+
+        let platform = Platform::new();
+        let ctx = platform.create_context(temp_dir.path(), None::<&std::path::Path>);
+        let result = platform
+            .execute_tool("git_log", &serde_json::json!({}), &ctx)
+            .await
+            .unwrap();
+
+        assert_eq!(result["success"], true);
+        let commits = result["commits"].as_array().unwrap();
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0]["summary"], "Add main");
+        assert_eq!(commits[1]["summary"], "Initial commit");
+        assert!(commits[0]["hash"].as_str().unwrap().len() >= 40);
+
+        let result = platform
+            .execute_tool("git_log", &serde_json::json!({ "limit": 1 }), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(result["commits"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_execute_tool_git_commit() {
+        let temp_dir = create_test_repo();
+        std::fs::write(temp_dir.path().join("lib.rs"), "pub fn lib() {}").unwrap();
+        git(temp_dir.path(), &["add", "lib.rs"]);
+
+        let platform = Platform::new();
+        let ctx = platform.create_context(temp_dir.path(), None::<&std::path::Path>);
+        let result = platform
+            .execute_tool(
+                "git_commit",
+                &serde_json::json!({ "message": "Add lib" }),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["success"], true);
+        let hash = result["commit"].as_str().unwrap().to_string();
+
+        let log = platform
+            .execute_tool("git_log", &serde_json::json!({ "limit": 1 }), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(log["commits"][0]["hash"], hash);
+        assert_eq!(log["commits"][0]["summary"], "Add lib");
+
+        // Nothing staged: the commit is reported as a failure, not an Err
+        let result = platform
+            .execute_tool(
+                "git_commit",
+                &serde_json::json!({ "message": "Empty" }),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["success"], false);
+        assert!(result["error"]
+            .as_str()
+            .unwrap()
+            .contains("Nothing to commit"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_tool_append_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let platform = Platform::new();
+        let ctx = platform.create_context(temp_dir.path(), None::<&std::path::Path>);
+
+        for line in ["one\n", "two\n"] {
+            let result = platform
+                .execute_tool(
+                    "append_file",
+                    &serde_json::json!({ "path": "notes.txt", "content": line }),
+                    &ctx,
+                )
+                .await
+                .unwrap();
+            assert_eq!(result["success"], true);
+        }
+
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("notes.txt")).unwrap(),
+            "one\ntwo\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_tool_git_commit_requires_message() {
+        let platform = Platform::new();
+        let ctx = platform.create_context(std::env::temp_dir(), None::<&std::path::Path>);
+        let result = platform
+            .execute_tool("git_commit", &serde_json::json!({}), &ctx)
+            .await;
+        assert!(result.is_err());
+    }
 }
@@ -5,6 +5,89 @@
 
 use crate::platform::types::*;
 use std::path::Path;
+use std::process::Stdio;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Maximum output kept per stream (256KB); the rest is drained and counted
+const MAX_OUTPUT_BYTES: usize = 256 * 1024;
+
+/// Output of one stream, capped at `MAX_OUTPUT_BYTES`
+struct CappedOutput {
+    text: String,
+    total_bytes: usize,
+}
+
+impl CappedOutput {
+    fn truncated(&self) -> bool {
+        self.total_bytes > MAX_OUTPUT_BYTES
+    }
+}
+
+/// Read a stream to the end, keeping at most `MAX_OUTPUT_BYTES`
+async fn read_capped<R: AsyncRead + Unpin>(reader: Option<R>) -> CappedOutput {
+    let mut buffer = Vec::new();
+    let mut total_bytes = 0;
+
+    if let Some(mut reader) = reader {
+        let mut chunk = [0u8; 4096];
+        while let Ok(n) = reader.read(&mut chunk).await {
+            if n == 0 {
+                break;
+            }
+            total_bytes += n;
+            let remaining = MAX_OUTPUT_BYTES.saturating_sub(buffer.len());
+            if remaining > 0 {
+                buffer.extend_from_slice(&chunk[..n.min(remaining)]);
+            }
+        }
+    }
+
+    CappedOutput {
+        text: String::from_utf8_lossy(&buffer).to_string(),
+        total_bytes,
+    }
+}
+
+/// Kills a command's whole process tree when dropped while still armed, so
+/// pipelines and grandchildren don't outlive a timeout or a cancelled tool call
+struct ProcessTreeGuard {
+    pid: Option<u32>,
+}
+
+impl ProcessTreeGuard {
+    /// The command finished on its own; leave any detached processes alone
+    fn disarm(&mut self) {
+        self.pid = None;
+    }
+}
+
+impl Drop for ProcessTreeGuard {
+    fn drop(&mut self) {
+        if let Some(pid) = self.pid.take() {
+            kill_process_tree(pid);
+        }
+    }
+}
+
+/// Kill the process group led by `pid` (the shell runs in its own group)
+#[cfg(unix)]
+fn kill_process_tree(pid: u32) {
+    let _ = std::process::Command::new("kill")
+        .args(["-KILL", "--", &format!("-{}", pid)])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+/// Kill `pid` and every process it started
+#[cfg(windows)]
+fn kill_process_tree(pid: u32) {
+    let _ = crate::shell_utils::new_command("taskkill")
+        .args(["/T", "/F", "/PID", &pid.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
 
 /// Shell operations provider
 #[derive(Clone)]
@@ -15,9 +98,15 @@ impl ShellPlatform {
         Self
     }
 
-    /// Validate that working directory is within workspace
+    /// Validate that working directory is within workspace.
+    /// Relative paths are resolved against the workspace root, not the process cwd.
     fn validate_cwd(&self, cwd: &str, ctx: &PlatformContext) -> Result<String, String> {
         let path = Path::new(cwd);
+        let path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            ctx.workspace_root.join(path)
+        };
         let canonical_path = path
             .canonicalize()
             .map_err(|e| format!("Invalid working directory: {}", e))?;
@@ -78,14 +167,43 @@ impl ShellPlatform {
         if let Some(dir) = working_dir {
             cmd.current_dir(dir);
         }
+        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+        // Dropping the child on timeout must not leave the process running
+        cmd.kill_on_drop(true);
+        // Own process group, so a timeout can kill the whole pipeline
+        #[cfg(unix)]
+        cmd.process_group(0);
+
+        let mut child = match cmd.spawn() {
+            Ok(child) => child,
+            Err(e) => return PlatformResult::error(format!("Failed to execute command: {}", e)),
+        };
+        // Armed until the command exits; fires on timeout or when this future is dropped
+        let mut tree_guard = ProcessTreeGuard { pid: child.id() };
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
 
         let timeout_duration = Duration::from_secs(ctx.shell_timeout_secs);
+        let run = async {
+            let (stdout, stderr, status) =
+                tokio::join!(read_capped(stdout), read_capped(stderr), child.wait());
+            status.map(|status| (stdout, stderr, status))
+        };
+
+        let outcome = timeout(timeout_duration, run).await;
+        if outcome.is_ok() {
+            tree_guard.disarm();
+        }
 
-        match timeout(timeout_duration, cmd.output()).await {
-            Ok(Ok(output)) => PlatformResult::success(ShellResult {
-                stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
-                exit_code: output.status.code().unwrap_or(-1),
+        match outcome {
+            Ok(Ok((stdout, stderr, status))) => PlatformResult::success(ShellResult {
+                stdout_truncated: stdout.truncated(),
+                stderr_truncated: stderr.truncated(),
+                stdout_bytes: stdout.total_bytes,
+                stderr_bytes: stderr.total_bytes,
+                stdout: stdout.text,
+                stderr: stderr.text,
+                exit_code: status.code().unwrap_or(-1),
                 timed_out: false,
             }),
             Ok(Err(e)) => PlatformResult::error(format!("Failed to execute command: {}", e)),
@@ -94,6 +212,10 @@ impl ShellPlatform {
                 stderr: "Command timed out".to_string(),
                 exit_code: -1,
                 timed_out: true,
+                stdout_truncated: false,
+                stderr_truncated: false,
+                stdout_bytes: 0,
+                stderr_bytes: 0,
             }),
         }
     }
@@ -194,6 +316,84 @@ mod tests {
         assert_eq!(shell_result.exit_code, 0);
     }
 
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_shell_output_truncation_reported() {
+        let shell = ShellPlatform::new();
+        let temp_dir = TempDir::new().unwrap();
+
+        let ctx = PlatformContext {
+            workspace_root: temp_dir.path().to_path_buf(),
+            worktree_path: None,
+            max_file_size: 1024 * 1024,
+            shell_timeout_secs: 60,
+        };
+
+        // 300000 bytes of 'x' on stdout, short message on stderr
+        let result = shell
+            .execute(
+                "head -c 300000 /dev/zero | tr '\\0' x; echo err >&2",
+                None,
+                &ctx,
+            )
+            .await;
+        assert!(result.success, "{:?}", result.error);
+
+        let shell_result = result.data.unwrap();
+        assert!(shell_result.stdout_truncated);
+        assert_eq!(shell_result.stdout_bytes, 300_000);
+        assert_eq!(shell_result.stdout.len(), MAX_OUTPUT_BYTES);
+        assert!(!shell_result.stderr_truncated);
+        assert_eq!(shell_result.stderr_bytes, 4);
+        assert_eq!(shell_result.stderr, "err\n");
+    }
+
+    #[tokio::test]
+    async fn test_cwd_inside_workspace_allowed() {
+        let shell = ShellPlatform::new();
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+
+        let ctx = PlatformContext {
+            workspace_root: temp_dir.path().to_path_buf(),
+            worktree_path: None,
+            max_file_size: 1024 * 1024,
+            shell_timeout_secs: 60,
+        };
+
+        let absolute = temp_dir.path().join("src");
+        let result = shell
+            .execute("echo ok", Some(&absolute.to_string_lossy()), &ctx)
+            .await;
+        assert!(result.success, "{:?}", result.error);
+
+        // Relative paths resolve against the workspace root
+        let result = shell.execute("echo ok", Some("src"), &ctx).await;
+        assert!(result.success, "{:?}", result.error);
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_cwd_outside_workspace_rejected() {
+        let shell = ShellPlatform::new();
+        let temp_dir = TempDir::new().unwrap();
+
+        let ctx = PlatformContext {
+            workspace_root: temp_dir.path().to_path_buf(),
+            worktree_path: None,
+            max_file_size: 1024 * 1024,
+            shell_timeout_secs: 60,
+        };
+
+        let result = shell.execute("echo hi", Some("/etc"), &ctx).await;
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("outside workspace root"));
+
+        let result = shell.execute("echo hi", Some(".."), &ctx).await;
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("outside workspace root"));
+    }
+
     #[tokio::test]
     async fn test_dangerous_command_detection() {
         let shell = ShellPlatform::new();
@@ -211,6 +411,39 @@ mod tests {
         assert!(result.error.unwrap().contains("dangerous"));
     }
 
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_timeout_kills_background_children() {
+        let shell = ShellPlatform::new();
+        let temp_dir = TempDir::new().unwrap();
+
+        let ctx = PlatformContext {
+            workspace_root: temp_dir.path().to_path_buf(),
+            worktree_path: None,
+            max_file_size: 1024 * 1024,
+            shell_timeout_secs: 1,
+        };
+
+        let result = shell
+            .execute("sleep 30 & echo $! > child.pid; wait", None, &ctx)
+            .await;
+        assert!(result.data.unwrap().timed_out);
+
+        let pid = std::fs::read_to_string(temp_dir.path().join("child.pid")).unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        let state = std::process::Command::new("ps")
+            .args(["-o", "stat=", "-p", pid.trim()])
+            .output()
+            .unwrap();
+        let state = String::from_utf8_lossy(&state.stdout);
+        // Gone, or a zombie waiting to be reaped by init
+        assert!(
+            state.trim().is_empty() || state.trim().starts_with('Z'),
+            "grandchild still running: {}",
+            state
+        );
+    }
+
     #[test]
     fn test_env_vars() {
         let shell = ShellPlatform::new();
@@ -92,6 +92,18 @@ pub struct GitStatus {
     pub untracked: Vec<String>,
 }
 
+/// Current branch and upstream tracking summary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitBranchInfo {
+    /// Branch name, or the short commit hash when HEAD is detached
+    pub branch: String,
+    pub upstream: Option<String>,
+    pub ahead: usize,
+    pub behind: usize,
+    pub is_detached: bool,
+}
+
 /// Git file status
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -109,6 +121,18 @@ pub struct ShellResult {
     pub stderr: String,
     pub exit_code: i32,
     pub timed_out: bool,
+    /// Whether stdout was cut at the output cap
+    #[serde(default)]
+    pub stdout_truncated: bool,
+    /// Whether stderr was cut at the output cap
+    #[serde(default)]
+    pub stderr_truncated: bool,
+    /// Full stdout length in bytes before truncation
+    #[serde(default)]
+    pub stdout_bytes: usize,
+    /// Full stderr length in bytes before truncation
+    #[serde(default)]
+    pub stderr_bytes: usize,
 }
 
 /// Search result
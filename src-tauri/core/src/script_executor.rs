@@ -83,6 +83,8 @@ impl ScriptExecutor {
         // Configure stdio
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
+        // Kill the script when a timeout (or cancelled caller) drops the child
+        cmd.kill_on_drop(true);
 
         // Execute with timeout if specified
         let timeout_duration = request.timeout_ms.map(Duration::from_millis);
@@ -264,6 +266,43 @@ mod tests {
         assert!(exec_result.error.unwrap().contains("timeout"));
     }
 
+    #[tokio::test]
+    #[cfg(target_os = "linux")]
+    async fn test_timeout_kills_process() {
+        use std::io::Write;
+        use tempfile::{NamedTempFile, TempDir};
+
+        let dir = TempDir::new().unwrap();
+        let pid_file = dir.path().join("pid");
+        let mut script_file = NamedTempFile::new().unwrap();
+        writeln!(script_file, "#!/bin/bash").unwrap();
+        writeln!(script_file, "echo $$ > '{}'", pid_file.display()).unwrap();
+        writeln!(script_file, "while true; do sleep 1; done").unwrap();
+        script_file.flush().unwrap();
+
+        let request = ScriptExecutionRequest {
+            script_path: script_file.path().to_string_lossy().to_string(),
+            script_type: "bash".to_string(),
+            args: vec![],
+            working_dir: None,
+            timeout_ms: Some(500),
+            environment: None,
+        };
+        let result = ScriptExecutor::execute(request).await.unwrap();
+        assert!(!result.success);
+
+        let pid = std::fs::read_to_string(&pid_file)
+            .unwrap()
+            .trim()
+            .to_string();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        // Gone, or a zombie waiting to be reaped
+        let alive = std::fs::read_to_string(format!("/proc/{}/stat", pid))
+            .map(|stat| !stat.contains(") Z "))
+            .unwrap_or(false);
+        assert!(!alive, "script process {} still running after timeout", pid);
+    }
+
     #[tokio::test]
     #[cfg(not(target_os = "windows"))]
     async fn test_successful_execution() {
@@ -1,4 +1,7 @@
-use crate::constants::{is_code_extension, is_code_filename};
+use crate::constants::{
+    extensions_for_language, is_code_extension, is_code_filename, DEFAULT_EXCLUDE_DIRS,
+    DEFAULT_IGNORE_GLOBS, LANGUAGE_EXTENSIONS,
+};
 use crate::walker::{WalkerConfig, WorkspaceWalker};
 use grep::regex::{RegexMatcher, RegexMatcherBuilder};
 use grep::searcher::sinks::UTF8;
@@ -29,11 +32,41 @@ pub struct SearchResult {
     pub matches: Vec<SearchMatch>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageExtensions {
+    pub language: String,
+    pub extensions: Vec<String>,
+}
+
+/// Default search filters, exposed so the UI can pre-populate its pickers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchDefaults {
+    pub exclude_dirs: Vec<String>,
+    pub ignore_globs: Vec<String>,
+    pub languages: Vec<LanguageExtensions>,
+}
+
+pub fn search_defaults() -> SearchDefaults {
+    let to_strings = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+    SearchDefaults {
+        exclude_dirs: to_strings(DEFAULT_EXCLUDE_DIRS),
+        ignore_globs: to_strings(DEFAULT_IGNORE_GLOBS),
+        languages: LANGUAGE_EXTENSIONS
+            .iter()
+            .map(|(language, extensions)| LanguageExtensions {
+                language: language.to_string(),
+                extensions: to_strings(extensions),
+            })
+            .collect(),
+    }
+}
+
 pub struct RipgrepSearch {
     max_results: usize,
     max_matches_per_file: usize,
     file_types: Option<HashSet<String>>,
     exclude_dirs: Option<HashSet<String>>,
+    include_ignored: bool,
 }
 
 impl Default for RipgrepSearch {
@@ -43,6 +76,7 @@ impl Default for RipgrepSearch {
             max_matches_per_file: 10,
             file_types: None,
             exclude_dirs: None,
+            include_ignored: false,
         }
     }
 }
@@ -66,9 +100,14 @@ impl RipgrepSearch {
         self.file_types = file_types.and_then(|types| {
             let normalized_types: HashSet<String> = types
                 .into_iter()
-                .filter_map(|file_type| {
+                .flat_map(|file_type| {
                     let normalized = file_type.trim().to_lowercase();
-                    (!normalized.is_empty()).then_some(normalized)
+                    // Accept language names ("rust") as well as extensions ("rs")
+                    match extensions_for_language(&normalized) {
+                        Some(extensions) => extensions.iter().map(|e| e.to_string()).collect(),
+                        None if normalized.is_empty() => vec![],
+                        None => vec![normalized],
+                    }
                 })
                 .collect();
 
@@ -82,6 +121,12 @@ impl RipgrepSearch {
         self
     }
 
+    /// Search inside the default excluded directories (node_modules, target, ...)
+    pub fn with_include_ignored(mut self, include_ignored: bool) -> Self {
+        self.include_ignored = include_ignored;
+        self
+    }
+
     #[inline]
     fn is_valid_file(&self, path: &Path) -> bool {
         // If file_types is specified, use it for filtering
@@ -228,8 +273,9 @@ impl RipgrepSearch {
             .map(|dirs| dirs.into_iter().collect())
             .unwrap_or_default();
 
-        let config =
-            WalkerConfig::for_content_search().with_additional_excludes(additional_excludes);
+        let config = WalkerConfig::for_content_search()
+            .with_additional_excludes(additional_excludes)
+            .with_default_excludes(!self.include_ignored);
         let walker = WorkspaceWalker::new(root_path, config).build();
 
         // Collect files in parallel batches
@@ -333,6 +379,20 @@ impl RipgrepSearch {
     }
 }
 
+/// Case-insensitive regex for searching in-memory text, matching content search semantics.
+/// Literal patterns are escaped; `is_regex` patterns must compile.
+pub fn build_text_regex(pattern: &str, is_regex: bool) -> Result<regex::Regex, String> {
+    let pattern = if is_regex {
+        pattern.to_string()
+    } else {
+        escape(pattern)
+    };
+    regex::RegexBuilder::new(&pattern)
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| format!("Invalid search pattern: {}", e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -428,6 +488,51 @@ mod tests {
         assert!(search.file_types.is_none());
     }
 
+    #[test]
+    fn test_with_file_types_expands_language_names() {
+        let search = RipgrepSearch::new()
+            .with_file_types(Some(vec!["TypeScript".to_string(), "rs".to_string()]));
+
+        let types = search.file_types.unwrap();
+        assert!(types.contains("ts"));
+        assert!(types.contains("tsx"));
+        assert!(types.contains("rs"));
+        assert!(!types.contains("typescript"));
+    }
+
+    #[test]
+    fn test_search_defaults_match_constants() {
+        let defaults = search_defaults();
+        assert_eq!(defaults.exclude_dirs.len(), DEFAULT_EXCLUDE_DIRS.len());
+        assert_eq!(defaults.ignore_globs.len(), DEFAULT_IGNORE_GLOBS.len());
+        assert!(defaults
+            .languages
+            .iter()
+            .any(|l| l.language == "rust" && l.extensions == vec!["rs".to_string()]));
+    }
+
+    #[test]
+    fn test_search_uses_default_excludes_and_globs() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        fs::write(root.join("app.js"), "const needle = 1;\n").unwrap();
+        fs::write(root.join("app.min.js"), "const needle=1;\n").unwrap();
+        fs::write(root.join("node_modules/pkg/index.js"), "needle\n").unwrap();
+
+        let results = RipgrepSearch::new()
+            .search_content("needle", root.to_str().unwrap())
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].file_path.ends_with("app.js"));
+
+        let results = RipgrepSearch::new()
+            .with_include_ignored(true)
+            .search_content("needle", root.to_str().unwrap())
+            .unwrap();
+        assert_eq!(results.len(), 3);
+    }
+
     #[test]
     fn test_with_exclude_dirs() {
         let search = RipgrepSearch::new()
@@ -3,20 +3,270 @@
 //! Also manages file system operations for attachment storage.
 
 use crate::database::Database;
+use crate::storage::image_metadata;
 use crate::storage::models::{Attachment, AttachmentOrigin};
+use image::ImageFormat;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::fmt;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Default longest side of generated image thumbnails, in pixels
+pub const DEFAULT_THUMBNAIL_MAX_DIMENSION: u32 = 256;
+
+/// Default cap on the size of a single attachment (50MB)
+pub const DEFAULT_MAX_UPLOAD_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Settings key for the attachment size cap, in bytes
+pub const MAX_UPLOAD_BYTES_SETTING: &str = "attachments_max_upload_bytes";
+/// Settings key for the mime allowlist, an array of patterns like `image/*`
+pub const ALLOWED_UPLOAD_MIMES_SETTING: &str = "attachments_allowed_mimes";
+
+/// Limits checked before an attachment is stored
+#[derive(Debug, Clone)]
+pub struct UploadLimits {
+    pub max_size: u64,
+    /// Allowed mime types, `image/*` style wildcards included; `None` allows any type
+    pub allowed_mimes: Option<Vec<String>>,
+}
+
+impl Default for UploadLimits {
+    fn default() -> Self {
+        Self {
+            max_size: DEFAULT_MAX_UPLOAD_BYTES,
+            allowed_mimes: None,
+        }
+    }
+}
+
+/// Why an upload was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UploadRejection {
+    TooLarge {
+        size: u64,
+        max_size: u64,
+    },
+    UnsupportedMime(String),
+    /// The image's metadata could not be stripped
+    UnprocessableImage(String),
+}
+
+impl UploadRejection {
+    /// HTTP status a route should answer with
+    pub fn status_code(&self) -> u16 {
+        match self {
+            UploadRejection::TooLarge { .. } => 413,
+            UploadRejection::UnsupportedMime(_) => 415,
+            UploadRejection::UnprocessableImage(_) => 422,
+        }
+    }
+}
+
+impl fmt::Display for UploadRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UploadRejection::TooLarge { size, max_size } => write!(
+                f,
+                "Attachment is too large ({} bytes, max {} bytes)",
+                size, max_size
+            ),
+            UploadRejection::UnsupportedMime(mime) => {
+                write!(f, "Attachment type not allowed: {}", mime)
+            }
+            UploadRejection::UnprocessableImage(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl UploadLimits {
+    /// Limits from stored setting values; missing or malformed values keep the
+    /// defaults, and an empty allowlist allows any type
+    pub fn from_settings(
+        max_size: Option<&serde_json::Value>,
+        allowed_mimes: Option<&serde_json::Value>,
+    ) -> Self {
+        let defaults = Self::default();
+        let max_size = max_size
+            .and_then(serde_json::Value::as_u64)
+            .filter(|max_size| *max_size > 0)
+            .unwrap_or(defaults.max_size);
+        let allowed_mimes = allowed_mimes
+            .and_then(serde_json::Value::as_array)
+            .map(|patterns| {
+                patterns
+                    .iter()
+                    .filter_map(serde_json::Value::as_str)
+                    .map(|pattern| pattern.trim().to_ascii_lowercase())
+                    .filter(|pattern| !pattern.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|patterns| !patterns.is_empty());
+        Self {
+            max_size,
+            allowed_mimes,
+        }
+    }
+
+    /// Check a size up front, e.g. from Content-Length before reading the body
+    pub fn check_size(&self, size: u64) -> Result<(), UploadRejection> {
+        if size > self.max_size {
+            return Err(UploadRejection::TooLarge {
+                size,
+                max_size: self.max_size,
+            });
+        }
+        Ok(())
+    }
+
+    /// Validate an upload and return the normalized mime type to store
+    pub fn validate(
+        &self,
+        mime_type: &str,
+        filename: &str,
+        size: u64,
+    ) -> Result<String, UploadRejection> {
+        self.check_size(size)?;
+
+        let mime = normalize_mime(mime_type, filename);
+        if let Some(allowed) = &self.allowed_mimes {
+            if !allowed.iter().any(|pattern| mime_matches(pattern, &mime)) {
+                return Err(UploadRejection::UnsupportedMime(mime));
+            }
+        }
+        Ok(mime)
+    }
+}
+
+/// Collects a streamed body, refusing it as soon as it passes the size cap
+/// rather than after the whole body has been buffered
+#[derive(Debug)]
+pub struct UploadBuffer<'a> {
+    limits: &'a UploadLimits,
+    data: Vec<u8>,
+}
+
+impl<'a> UploadBuffer<'a> {
+    /// Start a buffer, checking the declared length (e.g. Content-Length) first
+    pub fn new(
+        limits: &'a UploadLimits,
+        declared_size: Option<u64>,
+    ) -> Result<Self, UploadRejection> {
+        if let Some(size) = declared_size {
+            limits.check_size(size)?;
+        }
+        let capacity = declared_size.unwrap_or(0).min(limits.max_size) as usize;
+        Ok(Self {
+            limits,
+            data: Vec::with_capacity(capacity),
+        })
+    }
+
+    pub fn push(&mut self, chunk: &[u8]) -> Result<(), UploadRejection> {
+        self.limits
+            .check_size((self.data.len() + chunk.len()) as u64)?;
+        self.data.extend_from_slice(chunk);
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+/// Downscaled preview of an image attachment
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Thumbnail {
+    pub data: Vec<u8>,
+    pub mime_type: String,
+}
+
 /// Repository for attachment operations
 #[derive(Clone)]
 pub struct AttachmentsRepository {
     db: Arc<Database>,
     storage_root: PathBuf,
+    upload_limits: UploadLimits,
+    thumbnail_max_dimension: u32,
+    strip_image_metadata: bool,
 }
 
 impl AttachmentsRepository {
     pub fn new(db: Arc<Database>, storage_root: PathBuf) -> Self {
-        Self { db, storage_root }
+        Self {
+            db,
+            storage_root,
+            upload_limits: UploadLimits::default(),
+            thumbnail_max_dimension: DEFAULT_THUMBNAIL_MAX_DIMENSION,
+            strip_image_metadata: true,
+        }
+    }
+
+    /// Strip EXIF/XMP metadata from images before storing them (on by default)
+    pub fn with_strip_image_metadata(mut self, enabled: bool) -> Self {
+        self.strip_image_metadata = enabled;
+        self
+    }
+
+    pub fn with_thumbnail_max_dimension(mut self, max_dimension: u32) -> Self {
+        self.thumbnail_max_dimension = max_dimension.max(1);
+        self
+    }
+
+    pub fn with_upload_limits(mut self, limits: UploadLimits) -> Self {
+        self.upload_limits = limits;
+        self
+    }
+
+    pub fn upload_limits(&self) -> &UploadLimits {
+        &self.upload_limits
+    }
+
+    /// Validate an attachment against the upload limits without storing it
+    pub fn validate_upload(
+        &self,
+        attachment: &Attachment,
+        data: &[u8],
+    ) -> Result<String, UploadRejection> {
+        // Metadata-only records (data stored elsewhere) carry their size on the record
+        let size = (attachment.size.max(0) as u64).max(data.len() as u64);
+        self.upload_limits.validate(
+            sniffed_mime(&attachment.mime_type, data),
+            &attachment.filename,
+            size,
+        )
+    }
+
+    /// Validate incoming bytes and strip image metadata as `create_attachment`
+    /// does, for files stored outside the repository. Returns the mime type
+    /// to record and the bytes to write.
+    pub fn prepare_upload<'a>(
+        &self,
+        mime_type: &str,
+        filename: &str,
+        data: &'a [u8],
+    ) -> Result<(String, Cow<'a, [u8]>), UploadRejection> {
+        let mime_type = self.upload_limits.validate(
+            sniffed_mime(mime_type, data),
+            filename,
+            data.len() as u64,
+        )?;
+        let data = self.strip_metadata(&mime_type, data)?;
+        Ok((mime_type, data))
+    }
+
+    fn strip_metadata<'a>(
+        &self,
+        mime_type: &str,
+        data: &'a [u8],
+    ) -> Result<Cow<'a, [u8]>, UploadRejection> {
+        if self.strip_image_metadata {
+            image_metadata::strip_metadata(mime_type, data)
+                .map_err(UploadRejection::UnprocessableImage)
+        } else {
+            Ok(Cow::Borrowed(data))
+        }
     }
 
     fn attachment_path(&self, attachment_id: &str) -> PathBuf {
@@ -24,11 +274,83 @@ impl AttachmentsRepository {
         self.storage_root.join(prefix).join(attachment_id)
     }
 
+    fn thumbnail_path(&self, attachment_id: &str, format: ImageFormat) -> PathBuf {
+        let extension = format.extensions_str().first().copied().unwrap_or("img");
+        self.attachment_path(attachment_id)
+            .with_extension(format!("thumb.{}", extension))
+    }
+
+    /// Downscale an image attachment and cache the result next to it.
+    /// Non-image attachments and images that fail to decode get no thumbnail.
+    fn write_thumbnail(&self, attachment_id: &str, mime_type: &str, data: &[u8]) {
+        let Some(format) = thumbnail_format(mime_type) else {
+            return;
+        };
+        match render_thumbnail(data, format, self.thumbnail_max_dimension) {
+            Ok(bytes) => {
+                if let Err(e) = std::fs::write(self.thumbnail_path(attachment_id, format), bytes) {
+                    log::warn!("Failed to write thumbnail for {}: {}", attachment_id, e);
+                }
+            }
+            Err(e) => log::warn!("Failed to render thumbnail for {}: {}", attachment_id, e),
+        }
+    }
+
+    /// Cached thumbnail for an image attachment; `None` when the attachment
+    /// doesn't exist or isn't an image
+    pub async fn get_thumbnail(&self, attachment_id: &str) -> Result<Option<Thumbnail>, String> {
+        let attachment = match self.get_attachment(attachment_id).await? {
+            Some(a) => a,
+            None => return Ok(None),
+        };
+        let format = match thumbnail_format(&attachment.mime_type) {
+            Some(format) => format,
+            None => return Ok(None),
+        };
+
+        let path = self.thumbnail_path(attachment_id, format);
+        if !path.exists() {
+            // Attachments stored before thumbnails existed are rendered on demand
+            let data = std::fs::read(&attachment.path)
+                .map_err(|e| format!("Failed to read attachment file: {}", e))?;
+            self.write_thumbnail(attachment_id, &attachment.mime_type, &data);
+        }
+
+        match std::fs::read(&path) {
+            Ok(data) => Ok(Some(Thumbnail {
+                data,
+                mime_type: format.to_mime_type().to_string(),
+            })),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("Failed to read thumbnail: {}", e)),
+        }
+    }
+
+    fn remove_thumbnails(&self, attachment_id: &str) {
+        for format in [ImageFormat::Png, ImageFormat::Jpeg] {
+            let _ = std::fs::remove_file(self.thumbnail_path(attachment_id, format));
+        }
+    }
+
     pub async fn create_attachment(
         &self,
         attachment: &Attachment,
         data: &[u8],
     ) -> Result<(), String> {
+        let mime_type = self
+            .validate_upload(attachment, data)
+            .map_err(|e| e.to_string())?;
+        let data = self
+            .strip_metadata(&mime_type, data)
+            .map_err(|e| e.to_string())?;
+        let data = data.as_ref();
+        // Stripping changes the size; metadata-only records keep the declared one
+        let size = if data.is_empty() {
+            attachment.size
+        } else {
+            data.len() as i64
+        };
+
         let file_path = self.attachment_path(&attachment.id);
         if let Some(parent) = file_path.parent() {
             std::fs::create_dir_all(parent)
@@ -40,6 +362,9 @@ impl AttachmentsRepository {
             .map_err(|e| format!("Failed to write attachment file: {}", e))?;
         std::fs::rename(&temp_path, &file_path)
             .map_err(|e| format!("Failed to finalize attachment file: {}", e))?;
+        if !data.is_empty() {
+            self.write_thumbnail(&attachment.id, &mime_type, data);
+        }
 
         let message_id = match &attachment.message_id {
             Some(message_id) => message_id.clone(),
@@ -59,11 +384,11 @@ impl AttachmentsRepository {
                 vec![
                     serde_json::json!(attachment.id),
                     serde_json::json!(message_id),
-                    serde_json::json!(attachment_type(&attachment.mime_type, &attachment.filename)),
+                    serde_json::json!(attachment_type(&mime_type, &attachment.filename)),
                     serde_json::json!(attachment.filename),
                     serde_json::json!(file_path.to_string_lossy()),
-                    serde_json::json!(attachment.mime_type),
-                    serde_json::json!(attachment.size),
+                    serde_json::json!(mime_type),
+                    serde_json::json!(size),
                     serde_json::json!(to_db_timestamp(attachment.created_at)),
                 ],
             )
@@ -131,6 +456,7 @@ impl AttachmentsRepository {
 
     pub async fn delete_attachment(&self, attachment_id: &str) -> Result<(), String> {
         if let Some(attachment) = self.get_attachment(attachment_id).await? {
+            self.remove_thumbnails(attachment_id);
             let _ = std::fs::remove_file(&attachment.path);
             if let Some(parent) = Path::new(&attachment.path).parent() {
                 let _ = std::fs::remove_dir(parent);
@@ -149,6 +475,7 @@ impl AttachmentsRepository {
     pub async fn delete_session_attachments(&self, session_id: &str) -> Result<u64, String> {
         let attachments = self.list_attachments(session_id, None).await?;
         for attachment in &attachments {
+            self.remove_thumbnails(&attachment.id);
             let _ = std::fs::remove_file(&attachment.path);
             if let Some(parent) = Path::new(&attachment.path).parent() {
                 let _ = std::fs::remove_dir(parent);
@@ -241,6 +568,79 @@ impl AttachmentsRepository {
     }
 }
 
+/// Lowercase the mime type and drop parameters (`; charset=...`); fall back to
+/// guessing from the filename when the client sent nothing useful
+/// The mime type of recognized image bytes, otherwise the declared one
+fn sniffed_mime<'a>(mime_type: &'a str, data: &[u8]) -> &'a str {
+    image_metadata::sniff_image(data).map_or(mime_type, image_metadata::ImageKind::mime_type)
+}
+
+fn normalize_mime(mime_type: &str, filename: &str) -> String {
+    let mime = mime_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    if mime.is_empty() || mime == "application/octet-stream" {
+        if let Some(guess) = mime_guess::from_path(filename).first_raw() {
+            return guess.to_string();
+        }
+    }
+    if mime.is_empty() {
+        "application/octet-stream".to_string()
+    } else {
+        mime
+    }
+}
+
+fn mime_matches(pattern: &str, mime: &str) -> bool {
+    let pattern = pattern.trim().to_ascii_lowercase();
+    if pattern == "*/*" || pattern == "*" {
+        return true;
+    }
+    match pattern.strip_suffix("/*") {
+        Some(prefix) => mime
+            .split_once('/')
+            .is_some_and(|(mime_type, _)| mime_type == prefix),
+        None => pattern == mime,
+    }
+}
+
+/// Thumbnails keep the source format: JPEG for photos, PNG for everything
+/// else so transparency survives
+fn thumbnail_format(mime_type: &str) -> Option<ImageFormat> {
+    match mime_type {
+        "image/jpeg" | "image/jpg" => Some(ImageFormat::Jpeg),
+        "image/png" => Some(ImageFormat::Png),
+        _ => None,
+    }
+}
+
+fn render_thumbnail(
+    data: &[u8],
+    format: ImageFormat,
+    max_dimension: u32,
+) -> Result<Vec<u8>, String> {
+    let image = image::load_from_memory(data).map_err(|e| e.to_string())?;
+    let image = if image.width() > max_dimension || image.height() > max_dimension {
+        image.thumbnail(max_dimension, max_dimension)
+    } else {
+        image
+    };
+    // The JPEG encoder has no alpha channel
+    let image = match format {
+        ImageFormat::Jpeg => image::DynamicImage::ImageRgb8(image.to_rgb8()),
+        _ => image,
+    };
+
+    let mut out = std::io::Cursor::new(Vec::new());
+    image
+        .write_to(&mut out, format)
+        .map_err(|e| e.to_string())?;
+    Ok(out.into_inner())
+}
+
 fn attachment_type(mime_type: &str, _filename: &str) -> &'static str {
     if mime_type.starts_with("image/") {
         "image"
@@ -305,3 +705,347 @@ fn row_to_attachment(row: &serde_json::Value) -> Attachment {
         origin: AttachmentOrigin::UserUpload,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::chat_history::ChatHistoryRepository;
+    use crate::storage::models::{Session, SessionStatus};
+    use tempfile::TempDir;
+
+    async fn create_test_repo(limits: UploadLimits) -> (AttachmentsRepository, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db = Arc::new(Database::new(db_path.to_string_lossy().to_string()));
+        db.connect()
+            .await
+            .expect("Failed to connect to test database");
+
+        let migrations = super::super::migrations::talkcody_db::talkcody_migrations();
+        let runner = super::super::migrations::MigrationRunner::new(&db, &migrations);
+        runner.init().await.expect("Failed to init migrations");
+        runner.migrate().await.expect("Failed to run migrations");
+
+        let now = chrono::Utc::now().timestamp();
+        ChatHistoryRepository::new(db.clone())
+            .create_session(&Session {
+                id: "session-1".to_string(),
+                project_id: None,
+                title: None,
+                status: SessionStatus::Created,
+                created_at: now,
+                updated_at: now,
+                last_event_id: None,
+                metadata: None,
+            })
+            .await
+            .expect("Failed to create session");
+
+        let repo = AttachmentsRepository::new(db, temp_dir.path().join("attachments"))
+            .with_upload_limits(limits);
+        (repo, temp_dir)
+    }
+
+    fn attachment(id: &str, filename: &str, mime_type: &str, size: usize) -> Attachment {
+        Attachment {
+            id: id.to_string(),
+            session_id: "session-1".to_string(),
+            message_id: None,
+            filename: filename.to_string(),
+            mime_type: mime_type.to_string(),
+            size: size as i64,
+            path: String::new(),
+            created_at: chrono::Utc::now().timestamp(),
+            origin: AttachmentOrigin::UserUpload,
+        }
+    }
+
+    #[test]
+    fn test_normalize_mime() {
+        assert_eq!(
+            normalize_mime("Text/Plain; charset=utf-8", "a.txt"),
+            "text/plain"
+        );
+        assert_eq!(normalize_mime("", "photo.png"), "image/png");
+        assert_eq!(
+            normalize_mime("application/octet-stream", "doc.pdf"),
+            "application/pdf"
+        );
+        assert_eq!(normalize_mime("", "noext"), "application/octet-stream");
+    }
+
+    #[test]
+    fn test_mime_matches_wildcards() {
+        assert!(mime_matches("image/*", "image/png"));
+        assert!(!mime_matches("image/*", "imagex/png"));
+        assert!(mime_matches("text/plain", "text/plain"));
+        assert!(mime_matches("*/*", "application/zip"));
+        assert!(!mime_matches("text/plain", "text/html"));
+    }
+
+    #[test]
+    fn test_upload_buffer_stops_at_the_cap() {
+        let limits = UploadLimits {
+            max_size: 8,
+            allowed_mimes: None,
+        };
+        let rejection = UploadBuffer::new(&limits, Some(9)).unwrap_err();
+        assert_eq!(rejection.status_code(), 413);
+
+        // Without a declared length the running total is what gets checked
+        let mut buffer = UploadBuffer::new(&limits, None).unwrap();
+        buffer.push(b"12345").unwrap();
+        let rejection = buffer.push(b"6789").unwrap_err();
+        assert_eq!(
+            rejection,
+            UploadRejection::TooLarge {
+                size: 9,
+                max_size: 8
+            }
+        );
+        buffer.push(b"678").unwrap();
+        assert_eq!(buffer.into_inner(), b"12345678");
+    }
+
+    #[test]
+    fn test_upload_limits_from_settings() {
+        let limits = UploadLimits::from_settings(
+            Some(&serde_json::json!(1024)),
+            Some(&serde_json::json!([" Image/* ", "", "application/pdf"])),
+        );
+        assert_eq!(limits.max_size, 1024);
+        assert_eq!(
+            limits.allowed_mimes,
+            Some(vec!["image/*".to_string(), "application/pdf".to_string()])
+        );
+
+        let defaults = UploadLimits::from_settings(
+            Some(&serde_json::json!("big")),
+            Some(&serde_json::json!([])),
+        );
+        assert_eq!(defaults.max_size, DEFAULT_MAX_UPLOAD_BYTES);
+        assert_eq!(defaults.allowed_mimes, None);
+        assert_eq!(
+            UploadLimits::from_settings(None, None).max_size,
+            DEFAULT_MAX_UPLOAD_BYTES
+        );
+    }
+
+    #[tokio::test]
+    async fn test_oversized_upload_rejected_with_413() {
+        let (repo, _temp) = create_test_repo(UploadLimits {
+            max_size: 16,
+            allowed_mimes: None,
+        })
+        .await;
+
+        let data = vec![0u8; 17];
+        let record = attachment("att-big", "big.bin", "application/octet-stream", 17);
+        let rejection = repo.validate_upload(&record, &data).unwrap_err();
+        assert_eq!(rejection.status_code(), 413);
+
+        assert!(repo.create_attachment(&record, &data).await.is_err());
+        assert!(!repo.attachment_exists("att-big").await.unwrap());
+        assert!(!repo.attachment_path("att-big").exists());
+    }
+
+    #[tokio::test]
+    async fn test_disallowed_mime_rejected_with_415() {
+        let (repo, _temp) = create_test_repo(UploadLimits {
+            max_size: DEFAULT_MAX_UPLOAD_BYTES,
+            allowed_mimes: Some(vec!["image/*".to_string(), "text/plain".to_string()]),
+        })
+        .await;
+
+        let record = attachment("att-exe", "setup.exe", "application/x-msdownload", 4);
+        let rejection = repo.validate_upload(&record, b"MZ..").unwrap_err();
+        assert_eq!(rejection.status_code(), 415);
+        assert!(repo.create_attachment(&record, b"MZ..").await.is_err());
+        assert!(!repo.attachment_exists("att-exe").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_allowed_upload_stores_normalized_mime() {
+        let (repo, _temp) = create_test_repo(UploadLimits {
+            max_size: 1024,
+            allowed_mimes: Some(vec!["image/*".to_string()]),
+        })
+        .await;
+
+        let record = attachment("att-png", "pic.png", "IMAGE/PNG; q=1", 4);
+        repo.create_attachment(&record, b"\x89PNG").await.unwrap();
+
+        let stored = repo.get_attachment("att-png").await.unwrap().unwrap();
+        assert_eq!(stored.mime_type, "image/png");
+        assert_eq!(
+            repo.read_attachment_data("att-png").await.unwrap().unwrap(),
+            b"\x89PNG"
+        );
+    }
+
+    fn encode_image(width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+        let image =
+            image::DynamicImage::ImageRgb8(image::RgbImage::from_fn(width, height, |x, y| {
+                image::Rgb([(x % 256) as u8, (y % 256) as u8, 128])
+            }));
+        let mut out = std::io::Cursor::new(Vec::new());
+        image.write_to(&mut out, format).unwrap();
+        out.into_inner()
+    }
+
+    #[tokio::test]
+    async fn test_image_upload_produces_thumbnail_within_cap() {
+        let (repo, _temp) = create_test_repo(UploadLimits::default()).await;
+        let repo = repo.with_thumbnail_max_dimension(64);
+
+        for (id, filename, mime, format) in [
+            ("att-png", "wide.png", "image/png", ImageFormat::Png),
+            ("att-jpg", "wide.jpg", "image/jpeg", ImageFormat::Jpeg),
+        ] {
+            let data = encode_image(400, 200, format);
+            repo.create_attachment(&attachment(id, filename, mime, data.len()), &data)
+                .await
+                .unwrap();
+
+            let thumbnail = repo.get_thumbnail(id).await.unwrap().expect("thumbnail");
+            assert_eq!(thumbnail.mime_type, mime);
+            let decoded = image::load_from_memory(&thumbnail.data).unwrap();
+            assert_eq!((decoded.width(), decoded.height()), (64, 32));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_small_image_thumbnail_is_not_upscaled() {
+        let (repo, _temp) = create_test_repo(UploadLimits::default()).await;
+        let data = encode_image(20, 10, ImageFormat::Png);
+        repo.create_attachment(
+            &attachment("att-small", "s.png", "image/png", data.len()),
+            &data,
+        )
+        .await
+        .unwrap();
+
+        let thumbnail = repo.get_thumbnail("att-small").await.unwrap().unwrap();
+        let decoded = image::load_from_memory(&thumbnail.data).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (20, 10));
+    }
+
+    #[tokio::test]
+    async fn test_text_attachment_has_no_thumbnail() {
+        let (repo, _temp) = create_test_repo(UploadLimits::default()).await;
+        repo.create_attachment(
+            &attachment("att-txt", "notes.txt", "text/plain", 5),
+            b"hello",
+        )
+        .await
+        .unwrap();
+
+        assert!(repo.get_thumbnail("att-txt").await.unwrap().is_none());
+        assert!(repo.get_thumbnail("missing").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delete_attachment_removes_thumbnail() {
+        let (repo, _temp) = create_test_repo(UploadLimits::default()).await;
+        let data = encode_image(300, 300, ImageFormat::Png);
+        repo.create_attachment(
+            &attachment("att-del", "d.png", "image/png", data.len()),
+            &data,
+        )
+        .await
+        .unwrap();
+        let thumb_path = repo.thumbnail_path("att-del", ImageFormat::Png);
+        assert!(thumb_path.exists());
+
+        repo.delete_attachment("att-del").await.unwrap();
+        assert!(!thumb_path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_jpeg_upload_is_stored_without_exif() {
+        use crate::storage::image_metadata::tests::{plain_jpeg, with_exif};
+
+        let (repo, _temp) = create_test_repo(UploadLimits::default()).await;
+        let original = plain_jpeg(32, 16);
+        let tagged = with_exif(&original, 1);
+        repo.create_attachment(
+            &attachment("att-gps", "gps.jpg", "image/jpeg", tagged.len()),
+            &tagged,
+        )
+        .await
+        .unwrap();
+
+        let stored = repo.read_attachment_data("att-gps").await.unwrap().unwrap();
+        assert_eq!(stored, original);
+        let record = repo.get_attachment("att-gps").await.unwrap().unwrap();
+        assert_eq!(record.size, original.len() as i64);
+
+        // Disabled via the setting, the upload is stored as sent
+        let repo = repo.with_strip_image_metadata(false);
+        repo.create_attachment(
+            &attachment("att-raw", "raw.jpg", "image/jpeg", tagged.len()),
+            &tagged,
+        )
+        .await
+        .unwrap();
+        let stored = repo.read_attachment_data("att-raw").await.unwrap().unwrap();
+        assert_eq!(stored, tagged);
+    }
+
+    #[tokio::test]
+    async fn test_prepare_upload_applies_limits_and_strips_exif() {
+        use crate::storage::image_metadata::tests::{plain_jpeg, with_exif};
+
+        let (repo, _temp) = create_test_repo(UploadLimits {
+            max_size: 64 * 1024,
+            allowed_mimes: Some(vec!["image/*".to_string()]),
+        })
+        .await;
+        let original = plain_jpeg(32, 16);
+        let tagged = with_exif(&original, 1);
+
+        let (mime_type, data) = repo.prepare_upload("", "photo.jpg", &tagged).unwrap();
+        assert_eq!(mime_type, "image/jpeg");
+        assert_eq!(data.as_ref(), original.as_slice());
+
+        let rejection = repo
+            .prepare_upload("application/zip", "a.zip", b"PK")
+            .unwrap_err();
+        assert_eq!(rejection.status_code(), 415);
+        let rejection = repo
+            .prepare_upload("image/png", "big.png", &vec![0u8; 64 * 1024 + 1])
+            .unwrap_err();
+        assert_eq!(rejection.status_code(), 413);
+    }
+
+    #[tokio::test]
+    async fn test_prepare_upload_trusts_bytes_over_declared_mime() {
+        use crate::storage::image_metadata::tests::{plain_jpeg, with_exif};
+
+        let (repo, _temp) = create_test_repo(UploadLimits::default()).await;
+        let original = plain_jpeg(16, 16);
+        let tagged = with_exif(&original, 1);
+
+        // A JPEG sent as a binary file is still recognized and stripped
+        let (mime_type, data) = repo
+            .prepare_upload("application/octet-stream", "photo.bin", &tagged)
+            .unwrap();
+        assert_eq!(mime_type, "image/jpeg");
+        assert_eq!(data.as_ref(), original.as_slice());
+
+        let mut truncated = tagged.clone();
+        truncated.truncate(10);
+        let rejection = repo
+            .prepare_upload("image/jpeg", "broken.jpg", &truncated)
+            .unwrap_err();
+        assert_eq!(rejection.status_code(), 422);
+
+        let mut heic = vec![0, 0, 0, 24];
+        heic.extend_from_slice(b"ftypheic");
+        heic.extend_from_slice(&[0; 12]);
+        let rejection = repo
+            .prepare_upload("image/heic", "photo.heic", &heic)
+            .unwrap_err();
+        assert_eq!(rejection.status_code(), 422);
+    }
+}
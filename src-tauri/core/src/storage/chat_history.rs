@@ -147,6 +147,31 @@ impl ChatHistoryRepository {
         Ok(())
     }
 
+    pub async fn update_session_metadata(
+        &self,
+        session_id: &str,
+        metadata: Option<Value>,
+    ) -> Result<(), String> {
+        let settings_map = self.get_conversation_settings(session_id).await?;
+        let compat = compat_from_settings_map(&settings_map)?;
+        let merged_settings = settings_map_to_string(set_compat_on_settings_map(
+            settings_map,
+            ServerSessionCompat { metadata, ..compat },
+        ))?;
+
+        self.db
+            .execute(
+                "UPDATE conversations SET settings = ?, updated_at = ? WHERE id = ?",
+                vec![
+                    serde_json::json!(merged_settings),
+                    serde_json::json!(to_db_timestamp(chrono::Utc::now().timestamp())),
+                    serde_json::json!(session_id),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
     pub async fn update_session_title(&self, session_id: &str, title: &str) -> Result<(), String> {
         self.db
             .execute(
@@ -295,6 +320,94 @@ impl ChatHistoryRepository {
         Ok(())
     }
 
+    // ============== Usage Operations ==============
+
+    pub async fn record_usage(&self, usage: &UsageRecord) -> Result<(), String> {
+        self.db
+            .execute(
+                r#"
+                INSERT INTO session_usage (
+                    id, conversation_id, model, input_tokens, output_tokens,
+                    cached_input_tokens, cache_creation_input_tokens, cost, created_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+                vec![
+                    serde_json::json!(usage.id),
+                    serde_json::json!(usage.session_id),
+                    serde_json::json!(usage.model),
+                    serde_json::json!(usage.input_tokens),
+                    serde_json::json!(usage.output_tokens),
+                    serde_json::json!(usage.cached_input_tokens),
+                    serde_json::json!(usage.cache_creation_input_tokens),
+                    serde_json::json!(usage.cost),
+                    serde_json::json!(to_db_timestamp(usage.created_at)),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Total spend for a session, with per-model totals ordered by cost
+    pub async fn get_session_cost(&self, session_id: &str) -> Result<SessionCost, String> {
+        let result = self
+            .db
+            .query(
+                r#"
+                SELECT model,
+                       COUNT(*) AS request_count,
+                       COALESCE(SUM(input_tokens), 0) AS input_tokens,
+                       COALESCE(SUM(output_tokens), 0) AS output_tokens,
+                       COALESCE(SUM(cost), 0) AS cost,
+                       SUM(CASE WHEN cost IS NULL THEN 1 ELSE 0 END) AS unpriced_request_count
+                FROM session_usage
+                WHERE conversation_id = ?
+                GROUP BY model
+                ORDER BY cost DESC, model ASC
+                "#,
+                vec![serde_json::json!(session_id)],
+            )
+            .await?;
+
+        let by_model: Vec<ModelCost> = result
+            .rows
+            .iter()
+            .map(|row| ModelCost {
+                model: row
+                    .get("model")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string(),
+                request_count: row
+                    .get("request_count")
+                    .and_then(|v| v.as_i64())
+                    .unwrap_or(0),
+                input_tokens: row
+                    .get("input_tokens")
+                    .and_then(|v| v.as_i64())
+                    .unwrap_or(0),
+                output_tokens: row
+                    .get("output_tokens")
+                    .and_then(|v| v.as_i64())
+                    .unwrap_or(0),
+                cost: row.get("cost").and_then(|v| v.as_f64()).unwrap_or(0.0),
+                unpriced_request_count: row
+                    .get("unpriced_request_count")
+                    .and_then(|v| v.as_i64())
+                    .unwrap_or(0),
+            })
+            .collect();
+
+        Ok(SessionCost {
+            session_id: session_id.to_string(),
+            request_count: by_model.iter().map(|m| m.request_count).sum(),
+            input_tokens: by_model.iter().map(|m| m.input_tokens).sum(),
+            output_tokens: by_model.iter().map(|m| m.output_tokens).sum(),
+            cost: by_model.iter().map(|m| m.cost).sum(),
+            unpriced_request_count: by_model.iter().map(|m| m.unpriced_request_count).sum(),
+            by_model,
+        })
+    }
+
     // ============== Event Operations ==============
 
     /// Unified schema does not persist events yet; keep server streaming in-memory only.
@@ -687,4 +800,131 @@ mod tests {
             _ => panic!("expected text message"),
         }
     }
+
+    fn usage(
+        session_id: &str,
+        model: &str,
+        input: i64,
+        output: i64,
+        cost: Option<f64>,
+    ) -> UsageRecord {
+        UsageRecord {
+            id: uuid::Uuid::new_v4().to_string(),
+            session_id: session_id.to_string(),
+            model: model.to_string(),
+            input_tokens: input,
+            output_tokens: output,
+            cached_input_tokens: 0,
+            cache_creation_input_tokens: 0,
+            cost,
+            created_at: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_cost_aggregates_by_model() {
+        let (db, _temp) = create_test_db().await;
+        let repo = ChatHistoryRepository::new(db);
+
+        for id in ["cost-session", "other-session"] {
+            let session = Session {
+                id: id.to_string(),
+                project_id: None,
+                title: None,
+                status: SessionStatus::Created,
+                created_at: chrono::Utc::now().timestamp(),
+                updated_at: chrono::Utc::now().timestamp(),
+                last_event_id: None,
+                metadata: None,
+            };
+            repo.create_session(&session)
+                .await
+                .expect("Failed to create session");
+        }
+
+        for record in [
+            usage("cost-session", "gpt-5", 1000, 200, Some(0.5)),
+            usage("cost-session", "gpt-5", 500, 100, Some(0.25)),
+            usage("cost-session", "claude-haiku", 300, 50, Some(0.125)),
+            usage("cost-session", "local-model", 10, 10, None),
+            usage("other-session", "gpt-5", 9999, 9999, Some(9.0)),
+        ] {
+            repo.record_usage(&record)
+                .await
+                .expect("Failed to record usage");
+        }
+
+        let cost = repo
+            .get_session_cost("cost-session")
+            .await
+            .expect("Failed to get session cost");
+        assert_eq!(cost.request_count, 4);
+        assert_eq!(cost.input_tokens, 1810);
+        assert_eq!(cost.output_tokens, 360);
+        assert!((cost.cost - 0.875).abs() < 1e-9);
+        assert_eq!(cost.unpriced_request_count, 1);
+        assert_eq!(
+            cost.by_model,
+            vec![
+                ModelCost {
+                    model: "gpt-5".to_string(),
+                    request_count: 2,
+                    input_tokens: 1500,
+                    output_tokens: 300,
+                    cost: 0.75,
+                    unpriced_request_count: 0,
+                },
+                ModelCost {
+                    model: "claude-haiku".to_string(),
+                    request_count: 1,
+                    input_tokens: 300,
+                    output_tokens: 50,
+                    cost: 0.125,
+                    unpriced_request_count: 0,
+                },
+                ModelCost {
+                    model: "local-model".to_string(),
+                    request_count: 1,
+                    input_tokens: 10,
+                    output_tokens: 10,
+                    cost: 0.0,
+                    unpriced_request_count: 1,
+                },
+            ]
+        );
+
+        let empty = repo.get_session_cost("missing").await.unwrap();
+        assert_eq!(empty.request_count, 0);
+        assert!(empty.by_model.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_session_usage_follows_its_session() {
+        let (db, _temp) = create_test_db().await;
+        let repo = ChatHistoryRepository::new(db);
+
+        assert!(repo
+            .record_usage(&usage("missing", "gpt-5", 1, 1, Some(0.1)))
+            .await
+            .is_err());
+
+        let session = Session {
+            id: "usage-session".to_string(),
+            project_id: None,
+            title: None,
+            status: SessionStatus::Created,
+            created_at: chrono::Utc::now().timestamp(),
+            updated_at: chrono::Utc::now().timestamp(),
+            last_event_id: None,
+            metadata: None,
+        };
+        repo.create_session(&session).await.unwrap();
+        repo.record_usage(&usage("usage-session", "gpt-5", 1, 1, Some(0.1)))
+            .await
+            .unwrap();
+
+        repo.delete_session("usage-session").await.unwrap();
+        let cost = repo.get_session_cost("usage-session").await.unwrap();
+        assert_eq!(cost.request_count, 0);
+    }
 }
@@ -0,0 +1,365 @@
+//! Image Metadata Stripping
+//! Removes EXIF/XMP metadata (GPS position, device details) from uploaded
+//! images before they are persisted.
+//!
+//! The format is sniffed from the file's magic bytes, not its declared mime
+//! type. JPEG metadata segments are dropped without touching the compressed
+//! image data. When the EXIF orientation is not the default, the rotation is
+//! applied to the pixels and the image is re-encoded, since the orientation
+//! tag goes away with the rest of the EXIF block. HEIC has no decoder here,
+//! so it is rejected rather than stored with its metadata.
+
+use image::DynamicImage;
+use std::borrow::Cow;
+
+/// Settings key controlling metadata stripping (on unless set to false)
+pub const STRIP_IMAGE_METADATA_SETTING: &str = "strip_image_metadata";
+
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+const XMP_HEADER: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+const ORIENTATION_TAG: u16 = 0x0112;
+const JPEG_QUALITY: u8 = 92;
+
+/// HEIF brands that mark an ISO-BMFF file as a HEIC/HEIF image
+const HEIF_BRANDS: &[&[u8; 4]] = &[
+    b"heic", b"heix", b"hevc", b"hevx", b"heim", b"heis", b"mif1", b"msf1",
+];
+
+/// Image format identified from a file's leading bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageKind {
+    Jpeg,
+    Png,
+    Gif,
+    Webp,
+    Heic,
+}
+
+impl ImageKind {
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            ImageKind::Jpeg => "image/jpeg",
+            ImageKind::Png => "image/png",
+            ImageKind::Gif => "image/gif",
+            ImageKind::Webp => "image/webp",
+            ImageKind::Heic => "image/heic",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageKind::Jpeg => "jpg",
+            ImageKind::Png => "png",
+            ImageKind::Gif => "gif",
+            ImageKind::Webp => "webp",
+            ImageKind::Heic => "heic",
+        }
+    }
+}
+
+/// Identify an image by its magic bytes
+pub fn sniff_image(data: &[u8]) -> Option<ImageKind> {
+    if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some(ImageKind::Jpeg)
+    } else if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some(ImageKind::Png)
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        Some(ImageKind::Gif)
+    } else if data.len() >= 12 && &data[..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        Some(ImageKind::Webp)
+    } else if data.len() >= 12
+        && &data[4..8] == b"ftyp"
+        && HEIF_BRANDS.iter().any(|brand| &data[8..12] == *brand)
+    {
+        Some(ImageKind::Heic)
+    } else {
+        None
+    }
+}
+
+/// Strip metadata from an image. The format comes from the bytes; the
+/// declared mime type only decides whether unrecognized bytes claiming to be
+/// a JPEG or HEIC are refused. Fails rather than return an image that may
+/// still carry its metadata.
+pub fn strip_metadata<'a>(mime_type: &str, data: &'a [u8]) -> Result<Cow<'a, [u8]>, String> {
+    match sniff_image(data) {
+        Some(ImageKind::Jpeg) => strip_jpeg_metadata(data)
+            .map(Cow::Owned)
+            .ok_or_else(|| "Could not parse JPEG to strip its metadata".to_string()),
+        Some(ImageKind::Heic) => Err(heic_unsupported()),
+        Some(_) => Ok(Cow::Borrowed(data)),
+        None => match mime_type {
+            "image/jpeg" | "image/jpg" => Err("File is labelled as a JPEG but is not one".into()),
+            "image/heic" | "image/heif" => Err(heic_unsupported()),
+            _ => Ok(Cow::Borrowed(data)),
+        },
+    }
+}
+
+fn heic_unsupported() -> String {
+    "HEIC images can't have their metadata stripped; send a JPEG or PNG instead".to_string()
+}
+
+/// Remove EXIF and XMP segments from a JPEG, applying the EXIF orientation
+/// to the pixels first if needed. Returns `None` for malformed input.
+pub fn strip_jpeg_metadata(data: &[u8]) -> Option<Vec<u8>> {
+    let (stripped, orientation) = remove_metadata_segments(data)?;
+    match orientation {
+        Some(orientation) if orientation != 1 => {
+            let image = image::load_from_memory(&stripped).ok()?;
+            encode_jpeg(&apply_orientation(image, orientation))
+        }
+        _ => Some(stripped),
+    }
+}
+
+/// Walk the JPEG markers up to the start of scan, dropping metadata segments.
+/// Returns the remaining bytes and the EXIF orientation, if any.
+fn remove_metadata_segments(data: &[u8]) -> Option<(Vec<u8>, Option<u16>)> {
+    if data.len() < 4 || data[0] != 0xFF || data[1] != 0xD8 {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(&data[..2]);
+    let mut orientation = None;
+    let mut pos = 2;
+
+    loop {
+        if pos + 1 >= data.len() || data[pos] != 0xFF {
+            return None;
+        }
+        // Markers may be preceded by any number of 0xFF fill bytes
+        let mut marker_pos = pos + 1;
+        while marker_pos < data.len() && data[marker_pos] == 0xFF {
+            marker_pos += 1;
+        }
+        let marker = *data.get(marker_pos)?;
+        let segment_start = marker_pos + 1;
+
+        // Standalone markers carry no length
+        if marker == 0x01 || (0xD0..=0xD7).contains(&marker) {
+            out.extend_from_slice(&data[pos..segment_start]);
+            pos = segment_start;
+            continue;
+        }
+        // Start of scan: the entropy-coded data and everything after is kept as is
+        if marker == 0xDA || marker == 0xD9 {
+            out.extend_from_slice(&data[pos..]);
+            return Some((out, orientation));
+        }
+
+        let length =
+            u16::from_be_bytes([*data.get(segment_start)?, *data.get(segment_start + 1)?]) as usize;
+        if length < 2 {
+            return None;
+        }
+        let segment_end = segment_start + length;
+        let payload = data.get(segment_start + 2..segment_end)?;
+
+        if marker == 0xE1 && payload.starts_with(EXIF_HEADER) {
+            orientation = orientation.or_else(|| read_orientation(&payload[EXIF_HEADER.len()..]));
+        } else if marker == 0xE1 && payload.starts_with(XMP_HEADER) {
+            // XMP can repeat the GPS position, drop it too
+        } else {
+            out.extend_from_slice(&data[pos..segment_end]);
+        }
+        pos = segment_end;
+    }
+}
+
+/// Read the orientation tag from IFD0 of a TIFF-structured EXIF block
+fn read_orientation(tiff: &[u8]) -> Option<u16> {
+    let little_endian = match tiff.get(..2)? {
+        b"II" => true,
+        b"MM" => false,
+        _ => return None,
+    };
+    let read_u16 = |offset: usize| -> Option<u16> {
+        let bytes = [*tiff.get(offset)?, *tiff.get(offset + 1)?];
+        Some(if little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    };
+    let read_u32 = |offset: usize| -> Option<u32> {
+        let bytes: [u8; 4] = tiff.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    };
+
+    let ifd0 = read_u32(4)? as usize;
+    let entries = read_u16(ifd0)? as usize;
+    (0..entries)
+        .map(|i| ifd0 + 2 + i * 12)
+        .find(|&entry| read_u16(entry) == Some(ORIENTATION_TAG))
+        .and_then(|entry| read_u16(entry + 8))
+        .filter(|orientation| (1..=8).contains(orientation))
+}
+
+/// Rotate/flip pixels so the image displays upright without the EXIF tag
+fn apply_orientation(image: DynamicImage, orientation: u16) -> DynamicImage {
+    match orientation {
+        2 => image.fliph(),
+        3 => image.rotate180(),
+        4 => image.flipv(),
+        5 => image.rotate90().fliph(),
+        6 => image.rotate90(),
+        7 => image.rotate270().fliph(),
+        8 => image.rotate270(),
+        _ => image,
+    }
+}
+
+fn encode_jpeg(image: &DynamicImage) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    let encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut out, JPEG_QUALITY);
+    image.to_rgb8().write_with_encoder(encoder).ok()?;
+    Some(out)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Little-endian EXIF block with an orientation tag and a GPS IFD
+    pub(crate) fn exif_segment(orientation: u16) -> Vec<u8> {
+        let mut tiff = Vec::new();
+        tiff.extend_from_slice(b"II");
+        tiff.extend_from_slice(&42u16.to_le_bytes());
+        tiff.extend_from_slice(&8u32.to_le_bytes());
+        // IFD0: orientation + GPS IFD pointer
+        tiff.extend_from_slice(&2u16.to_le_bytes());
+        tiff.extend_from_slice(&ORIENTATION_TAG.to_le_bytes());
+        tiff.extend_from_slice(&3u16.to_le_bytes());
+        tiff.extend_from_slice(&1u32.to_le_bytes());
+        tiff.extend_from_slice(&orientation.to_le_bytes());
+        tiff.extend_from_slice(&[0, 0]);
+        tiff.extend_from_slice(&0x8825u16.to_le_bytes());
+        tiff.extend_from_slice(&4u16.to_le_bytes());
+        tiff.extend_from_slice(&1u32.to_le_bytes());
+        tiff.extend_from_slice(&38u32.to_le_bytes());
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+        // GPS IFD: GPSLatitudeRef = "N"
+        tiff.extend_from_slice(&1u16.to_le_bytes());
+        tiff.extend_from_slice(&1u16.to_le_bytes());
+        tiff.extend_from_slice(&2u16.to_le_bytes());
+        tiff.extend_from_slice(&2u32.to_le_bytes());
+        tiff.extend_from_slice(b"N\0\0\0");
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+
+        let mut payload = EXIF_HEADER.to_vec();
+        payload.extend_from_slice(&tiff);
+        let mut segment = vec![0xFF, 0xE1];
+        segment.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+        segment.extend_from_slice(&payload);
+        segment
+    }
+
+    pub(crate) fn plain_jpeg(width: u32, height: u32) -> Vec<u8> {
+        let image = DynamicImage::ImageRgb8(image::RgbImage::from_fn(width, height, |x, y| {
+            image::Rgb([(x * 4 % 256) as u8, (y * 4 % 256) as u8, 90])
+        }));
+        encode_jpeg(&image).unwrap()
+    }
+
+    /// Insert an EXIF segment right after SOI
+    pub(crate) fn with_exif(jpeg: &[u8], orientation: u16) -> Vec<u8> {
+        let mut out = jpeg[..2].to_vec();
+        out.extend_from_slice(&exif_segment(orientation));
+        out.extend_from_slice(&jpeg[2..]);
+        out
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack.windows(needle.len()).any(|w| w == needle)
+    }
+
+    #[test]
+    fn test_read_orientation() {
+        let segment = exif_segment(6);
+        assert_eq!(read_orientation(&segment[4 + EXIF_HEADER.len()..]), Some(6));
+        assert_eq!(read_orientation(b"XX\0\0"), None);
+    }
+
+    #[test]
+    fn test_strip_keeps_pixels_for_upright_image() {
+        let original = plain_jpeg(40, 20);
+        let tagged = with_exif(&original, 1);
+        assert!(contains(&tagged, EXIF_HEADER));
+
+        let stripped = strip_jpeg_metadata(&tagged).unwrap();
+        assert!(!contains(&stripped, EXIF_HEADER));
+        // Only the metadata segment is removed; image data is byte-identical
+        assert_eq!(stripped, original);
+    }
+
+    #[test]
+    fn test_strip_applies_rotation() {
+        let tagged = with_exif(&plain_jpeg(40, 20), 6);
+        let stripped = strip_jpeg_metadata(&tagged).unwrap();
+        assert!(!contains(&stripped, EXIF_HEADER));
+
+        let decoded = image::load_from_memory(&stripped).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (20, 40));
+    }
+
+    #[test]
+    fn test_strip_drops_xmp() {
+        let original = plain_jpeg(8, 8);
+        let mut payload = XMP_HEADER.to_vec();
+        payload.extend_from_slice(b"<x:xmpmeta>GPS</x:xmpmeta>");
+        let mut tagged = original[..2].to_vec();
+        tagged.extend_from_slice(&[0xFF, 0xE1]);
+        tagged.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+        tagged.extend_from_slice(&payload);
+        tagged.extend_from_slice(&original[2..]);
+
+        assert_eq!(strip_jpeg_metadata(&tagged).unwrap(), original);
+    }
+
+    #[test]
+    fn test_non_jpeg_is_unchanged() {
+        let data = b"not an image".to_vec();
+        assert!(matches!(
+            strip_metadata("text/plain", &data),
+            Ok(Cow::Borrowed(_))
+        ));
+        let png = b"\x89PNG\r\n\x1a\n rest".to_vec();
+        assert!(matches!(
+            strip_metadata("image/png", &png),
+            Ok(Cow::Borrowed(_))
+        ));
+    }
+
+    #[test]
+    fn test_sniffs_jpeg_regardless_of_mime() {
+        let original = plain_jpeg(8, 8);
+        let tagged = with_exif(&original, 1);
+        let stripped = strip_metadata("image/png", &tagged).unwrap();
+        assert_eq!(stripped.as_ref(), original.as_slice());
+        assert_eq!(sniff_image(&tagged), Some(ImageKind::Jpeg));
+    }
+
+    #[test]
+    fn test_malformed_jpeg_fails_closed() {
+        let mut truncated = with_exif(&plain_jpeg(8, 8), 1);
+        truncated.truncate(10);
+        assert!(strip_metadata("image/jpeg", &truncated).is_err());
+        assert!(strip_metadata("image/jpeg", b"not an image").is_err());
+    }
+
+    #[test]
+    fn test_heic_is_rejected() {
+        let mut heic = vec![0, 0, 0, 24];
+        heic.extend_from_slice(b"ftypheic");
+        heic.extend_from_slice(&[0; 12]);
+        assert_eq!(sniff_image(&heic), Some(ImageKind::Heic));
+        assert!(strip_metadata("application/octet-stream", &heic).is_err());
+        assert!(strip_metadata("image/heic", b"garbage").is_err());
+    }
+}
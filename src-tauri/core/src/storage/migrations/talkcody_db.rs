@@ -597,6 +597,61 @@ pub fn talkcody_migrations() -> MigrationRegistry {
         down_sql: None,
     });
 
+    // Migration 10: Per-session token usage and cost written by the stream path
+    registry.register(Migration {
+        version: 10,
+        name: "create_session_usage_table",
+        up_sql: r#"
+            CREATE TABLE IF NOT EXISTS session_usage (
+                id TEXT PRIMARY KEY,
+                conversation_id TEXT NOT NULL,
+                model TEXT NOT NULL,
+                input_tokens INTEGER NOT NULL DEFAULT 0,
+                output_tokens INTEGER NOT NULL DEFAULT 0,
+                cached_input_tokens INTEGER NOT NULL DEFAULT 0,
+                cache_creation_input_tokens INTEGER NOT NULL DEFAULT 0,
+                cost REAL,
+                created_at INTEGER NOT NULL,
+                FOREIGN KEY (conversation_id) REFERENCES conversations (id) ON DELETE CASCADE
+            );
+            CREATE INDEX IF NOT EXISTS idx_session_usage_conversation ON session_usage(conversation_id);
+
+            -- foreign_keys is off on these connections, so enforce the reference here
+            CREATE TRIGGER IF NOT EXISTS session_usage_conversation_exists
+            BEFORE INSERT ON session_usage
+            WHEN NOT EXISTS (SELECT 1 FROM conversations WHERE id = NEW.conversation_id)
+            BEGIN
+                SELECT RAISE(ABORT, 'session_usage references an unknown conversation');
+            END;
+            CREATE TRIGGER IF NOT EXISTS session_usage_conversation_delete
+            AFTER DELETE ON conversations
+            BEGIN
+                DELETE FROM session_usage WHERE conversation_id = OLD.id;
+            END;
+        "#,
+        down_sql: Some(
+            r#"
+            DROP TRIGGER IF EXISTS session_usage_conversation_delete;
+            DROP TRIGGER IF EXISTS session_usage_conversation_exists;
+            DROP TABLE IF EXISTS session_usage;
+        "#,
+        ),
+    });
+
+    // Migration 11: Daily OpenAI subscription usage snapshots
+    registry.register(Migration {
+        version: 11,
+        name: "create_openai_usage_snapshots_table",
+        up_sql: r#"
+            CREATE TABLE IF NOT EXISTS openai_usage_snapshots (
+                day TEXT PRIMARY KEY,
+                captured_at INTEGER NOT NULL,
+                payload TEXT NOT NULL
+            );
+        "#,
+        down_sql: Some("DROP TABLE IF EXISTS openai_usage_snapshots;"),
+    });
+
     registry
 }
 
@@ -607,6 +662,6 @@ mod tests {
     #[test]
     fn test_talkcody_migrations_count() {
         let registry = talkcody_migrations();
-        assert_eq!(registry.migrations().len(), 9);
+        assert_eq!(registry.migrations().len(), 11);
     }
 }
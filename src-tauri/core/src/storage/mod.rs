@@ -8,6 +8,7 @@
 pub mod agents;
 pub mod attachments;
 pub mod chat_history;
+pub mod image_metadata;
 pub mod migrations;
 pub mod models;
 pub mod settings;
@@ -63,7 +64,31 @@ impl Storage {
         let chat_history = ChatHistoryRepository::new(db.clone());
         let agents = AgentsRepository::new(db.clone());
         let settings = SettingsRepository::new(db.clone());
-        let attachments = AttachmentsRepository::new(db_for_attachments, attachments_root);
+        // Read once at startup; changing the setting applies after a restart
+        let strip_image_metadata = settings
+            .get_setting(image_metadata::STRIP_IMAGE_METADATA_SETTING)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|value| value.as_bool())
+            .unwrap_or(true);
+        let upload_limits = attachments::UploadLimits::from_settings(
+            settings
+                .get_setting(attachments::MAX_UPLOAD_BYTES_SETTING)
+                .await
+                .ok()
+                .flatten()
+                .as_ref(),
+            settings
+                .get_setting(attachments::ALLOWED_UPLOAD_MIMES_SETTING)
+                .await
+                .ok()
+                .flatten()
+                .as_ref(),
+        );
+        let attachments = AttachmentsRepository::new(db_for_attachments, attachments_root)
+            .with_strip_image_metadata(strip_image_metadata)
+            .with_upload_limits(upload_limits);
 
         Ok(Self {
             chat_history,
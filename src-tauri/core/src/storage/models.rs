@@ -272,11 +272,66 @@ pub struct TaskSettings {
     pub auto_approve_plan: Option<bool>,
     /// Enable auto code review
     pub auto_code_review: Option<bool>,
+    /// Maximum agent loop iterations for the task; caps the caller's own limit
+    pub max_iterations: Option<u32>,
+    /// Models to try, in order, when the primary model is overloaded or rate-limited
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_models: Option<Vec<String>>,
+    /// Named OAuth account the session's requests authenticate with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oauth_account: Option<String>,
+    /// Leave out the global system prompt prefix/suffix for this session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_global_system_prompt: Option<bool>,
     /// Additional custom settings
     #[serde(flatten)]
     pub extra: HashMap<String, serde_json::Value>,
 }
 
+/// Token usage and cost of a single LLM request within a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageRecord {
+    pub id: String,
+    pub session_id: SessionId,
+    pub model: String,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cached_input_tokens: i64,
+    pub cache_creation_input_tokens: i64,
+    /// `None` when the model has no pricing, so unknown spend isn't counted as free
+    pub cost: Option<f64>,
+    pub created_at: i64,
+}
+
+/// Spend for one model within a session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelCost {
+    pub model: String,
+    pub request_count: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    /// Spend of the priced requests
+    pub cost: f64,
+    /// Requests whose cost is unknown because the model had no pricing
+    pub unpriced_request_count: i64,
+}
+
+/// Cumulative spend for a session, broken down by model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionCost {
+    pub session_id: SessionId,
+    pub request_count: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost: f64,
+    /// When non-zero, `cost` is a lower bound
+    pub unpriced_request_count: i64,
+    pub by_model: Vec<ModelCost>,
+}
+
 /// Attachment/file upload metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
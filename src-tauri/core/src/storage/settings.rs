@@ -139,6 +139,17 @@ impl SettingsRepository {
         Ok(self.get_task_settings(task_id).await?.unwrap_or_default())
     }
 
+    /// Task settings as stored by the server, else the desktop frontend's flat
+    /// conversation settings
+    pub async fn resolve_task_settings(&self, task_id: &str) -> Result<TaskSettings, String> {
+        if let Some(settings) = self.get_task_settings(task_id).await? {
+            return Ok(settings);
+        }
+        let settings_map = self.get_conversation_settings(task_id).await?;
+        serde_json::from_value(Value::Object(settings_map))
+            .map_err(|e| format!("Failed to parse task settings: {}", e))
+    }
+
     pub async fn set_task_settings(
         &self,
         task_id: &str,
@@ -175,6 +186,15 @@ impl SettingsRepository {
         if updates.auto_code_review.is_some() {
             settings.auto_code_review = updates.auto_code_review;
         }
+        if updates.max_iterations.is_some() {
+            settings.max_iterations = updates.max_iterations;
+        }
+        if updates.fallback_models.is_some() {
+            settings.fallback_models = updates.fallback_models;
+        }
+        if updates.disable_global_system_prompt.is_some() {
+            settings.disable_global_system_prompt = updates.disable_global_system_prompt;
+        }
         for (key, value) in updates.extra {
             settings.extra.insert(key, value);
         }
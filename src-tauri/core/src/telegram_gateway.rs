@@ -1,7 +1,14 @@
-use bytes::Bytes;
+use crate::integrations::commands::{
+    route_inbound, GatewayCommand, InboundAction, DEFAULT_COMMAND_PREFIX,
+};
+use crate::integrations::downloads::{GatewayDownloads, SavedDownload};
+use crate::integrations::registry::{Integration, IntegrationStatus};
+use crate::integrations::typing::{TypingIndicator, TypingSink, DEFAULT_TYPING_REFRESH};
+use crate::storage::Storage;
 use rand::Rng;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -26,6 +33,17 @@ pub struct TelegramConfig {
     pub enabled: bool,
     pub token: String,
     pub allowed_chat_ids: Vec<i64>,
+    #[serde(default)]
+    pub allowed_user_ids: Vec<i64>,
+    /// Empty allowlists only admit everyone when this is set explicitly
+    #[serde(default)]
+    pub allow_all: bool,
+    /// Optional reply sent to senders outside the allowlist
+    #[serde(default)]
+    pub denial_message: Option<String>,
+    /// Prefix for control commands such as `/new`
+    #[serde(default)]
+    pub command_prefix: String,
     pub poll_timeout_secs: u64,
 }
 
@@ -35,6 +53,10 @@ impl Default for TelegramConfig {
             enabled: false,
             token: String::new(),
             allowed_chat_ids: Vec::new(),
+            allowed_user_ids: Vec::new(),
+            allow_all: false,
+            denial_message: None,
+            command_prefix: DEFAULT_COMMAND_PREFIX.to_string(),
             poll_timeout_secs: DEFAULT_POLL_TIMEOUT_SECS,
         }
     }
@@ -66,6 +88,14 @@ pub struct TelegramInboundMessage {
     pub attachments: Option<Vec<TelegramRemoteAttachment>>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelegramInboundCommand {
+    pub chat_id: i64,
+    pub message_id: i64,
+    pub command: GatewayCommand,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TelegramSendMessageRequest {
@@ -92,7 +122,7 @@ pub struct TelegramEditMessageRequest {
     pub parse_mode: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct TelegramGateway {
     config: TelegramConfig,
     running: bool,
@@ -102,6 +132,7 @@ pub struct TelegramGateway {
     last_error: Option<String>,
     last_error_at_ms: Option<i64>,
     backoff_ms: u64,
+    typing: HashMap<i64, TypingIndicator>,
 }
 
 impl Default for TelegramGateway {
@@ -121,11 +152,12 @@ impl TelegramGateway {
             last_error: None,
             last_error_at_ms: None,
             backoff_ms: DEFAULT_ERROR_BACKOFF_MS,
+            typing: HashMap::new(),
         }
     }
 }
 
-type TelegramGatewayState = Arc<Mutex<TelegramGateway>>;
+pub type TelegramGatewayState = Arc<Mutex<TelegramGateway>>;
 
 fn config_path<R: Runtime>(app_handle: &AppHandle<R>) -> Result<PathBuf, String> {
     let app_data_dir = app_handle
@@ -240,6 +272,7 @@ pub async fn load_config<R: Runtime>(app_handle: &AppHandle<R>) -> Result<Telegr
     let mut parsed = serde_json::from_str::<TelegramConfig>(&content)
         .map_err(|e| format!("Failed to parse telegram config: {}", e))?;
     sanitize_allowed_chat_ids(&mut parsed.allowed_chat_ids);
+    sanitize_allowed_chat_ids(&mut parsed.allowed_user_ids);
     Ok(parsed)
 }
 
@@ -265,11 +298,51 @@ fn sanitize_allowed_chat_ids(allowed_chat_ids: &mut Vec<i64>) {
     allowed_chat_ids.retain(|id| *id != 0);
 }
 
-fn is_chat_allowed(config: &TelegramConfig, chat_id: i64) -> bool {
-    if config.allowed_chat_ids.is_empty() {
-        return true;
+fn is_sender_allowed(config: &TelegramConfig, chat_id: i64, user_id: Option<i64>) -> bool {
+    if config.allowed_chat_ids.is_empty() && config.allowed_user_ids.is_empty() {
+        return config.allow_all;
     }
     config.allowed_chat_ids.contains(&chat_id)
+        || user_id.is_some_and(|id| config.allowed_user_ids.contains(&id))
+}
+
+fn denial_text(config: &TelegramConfig) -> Option<&str> {
+    config
+        .denial_message
+        .as_deref()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+}
+
+async fn send_text_reply(
+    client: &Client,
+    token: &str,
+    chat_id: i64,
+    text: &str,
+) -> Result<(), String> {
+    let url = format!("https://api.telegram.org/bot{}/sendMessage", token);
+    let response = client
+        .post(&url)
+        .json(&serde_json::json!({
+            "chat_id": chat_id,
+            "text": text,
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("Telegram sendMessage failed: {}", e))?;
+
+    let payload = response
+        .json::<TelegramBoolResponse>()
+        .await
+        .map_err(|e| format!("Failed to parse sendMessage response: {}", e))?;
+
+    if !payload.ok {
+        let description = payload
+            .description
+            .unwrap_or_else(|| "Telegram sendMessage returned ok=false".to_string());
+        return Err(description);
+    }
+    Ok(())
 }
 
 fn is_group_chat(chat_type: &Option<String>, chat_id: i64) -> bool {
@@ -284,7 +357,7 @@ async fn poll_loop(
     gateway_state: TelegramGatewayState,
     stop_rx: watch::Receiver<bool>,
 ) {
-    let client = Client::builder()
+    let client = crate::network_proxy::client_builder()
         .timeout(Duration::from_secs(DEFAULT_POLL_TIMEOUT_SECS + 10))
         .build();
 
@@ -294,8 +367,8 @@ async fn poll_loop(
     }
     let client = client.unwrap();
 
-    let attachments_dir = match attachments_root(&app_handle).await {
-        Ok(path) => path,
+    let downloads = match gateway_downloads(&app_handle).await {
+        Ok(downloads) => downloads,
         Err(error) => {
             log::warn!(
                 "[TelegramGateway] Failed to resolve attachments dir: {}",
@@ -307,7 +380,7 @@ async fn poll_loop(
 
     log::info!(
         "[TelegramGateway] Polling loop started (attachments_dir={:?})",
-        attachments_dir
+        downloads.as_ref().map(GatewayDownloads::dir)
     );
 
     loop {
@@ -407,20 +480,67 @@ async fn poll_loop(
                                     continue;
                                 }
 
-                                if !is_chat_allowed(&config, message.chat.id) {
+                                let user_id = message.from.as_ref().and_then(|user| user.id);
+                                if !is_sender_allowed(&config, message.chat.id, user_id) {
                                     log::debug!(
-                                        "[TelegramGateway] Chat id {} not in allowlist (count={})",
+                                        "[TelegramGateway] Sender not in allowlist chat_id={} user_id={:?} (chats={}, users={})",
                                         message.chat.id,
-                                        config.allowed_chat_ids.len()
+                                        user_id,
+                                        config.allowed_chat_ids.len(),
+                                        config.allowed_user_ids.len()
                                     );
+                                    if let Some(text) = denial_text(&config) {
+                                        if let Err(error) = send_text_reply(
+                                            &client,
+                                            &config.token,
+                                            message.chat.id,
+                                            text,
+                                        )
+                                        .await
+                                        {
+                                            log::warn!(
+                                                "[TelegramGateway] Failed to send denial reply: {}",
+                                                error
+                                            );
+                                        }
+                                    }
                                     continue;
                                 }
 
+                                if let Some(text) = message.text.as_deref() {
+                                    if let InboundAction::Dispatch(command) =
+                                        route_inbound(text, &config.command_prefix)
+                                    {
+                                        log::debug!(
+                                                "[TelegramGateway] Dispatching command chat_id={} command={:?}",
+                                                message.chat.id,
+                                                command
+                                            );
+                                        if command == GatewayCommand::Stop {
+                                            stop_typing(&gateway_state, message.chat.id).await;
+                                        }
+                                        let payload = TelegramInboundCommand {
+                                            chat_id: message.chat.id,
+                                            message_id: message.message_id,
+                                            command,
+                                        };
+                                        if let Err(error) =
+                                            app_handle.emit("telegram-inbound-command", payload)
+                                        {
+                                            log::error!(
+                                                "[TelegramGateway] Failed to emit command: {}",
+                                                error
+                                            );
+                                        }
+                                        continue;
+                                    }
+                                }
+
                                 let (text, attachments) = match build_message_payload(
                                     &client,
                                     &config.token,
                                     &message,
-                                    attachments_dir.as_ref(),
+                                    downloads.as_ref(),
                                 )
                                 .await
                                 {
@@ -541,29 +661,42 @@ async fn poll_loop(
     log::info!("[TelegramGateway] Polling loop stopped");
 }
 
-async fn save_attachment_file(
-    attachments_dir: &PathBuf,
-    filename: &str,
-    data: Bytes,
-) -> Result<String, String> {
-    tokio::fs::create_dir_all(attachments_dir)
-        .await
-        .map_err(|e| format!("Failed to create attachments dir: {}", e))?;
-    let target_path = attachments_dir.join(filename);
-    tokio::fs::write(&target_path, data)
-        .await
-        .map_err(|e| format!("Failed to write attachment: {}", e))?;
-    Ok(target_path.to_string_lossy().to_string())
-}
-
-async fn attachments_root<R: Runtime>(
+/// Download target for attachments; None until storage is available
+async fn gateway_downloads<R: Runtime>(
     app_handle: &AppHandle<R>,
-) -> Result<Option<PathBuf>, String> {
+) -> Result<Option<GatewayDownloads>, String> {
     let app_data_dir = app_handle
         .path()
         .app_data_dir()
         .map_err(|e| format!("Failed to get app data dir: {}", e))?;
-    Ok(Some(app_data_dir.join(TELEGRAM_ATTACHMENTS_DIR)))
+    let Some(storage) = app_handle.try_state::<Storage>() else {
+        return Ok(None);
+    };
+    Ok(Some(GatewayDownloads::new(
+        app_data_dir.join(TELEGRAM_ATTACHMENTS_DIR),
+        storage.attachments.clone(),
+    )))
+}
+
+/// Save a download, logging and skipping files the upload limits reject.
+/// `data` is `None` when the download was already refused for its size.
+async fn save_download(
+    downloads: &GatewayDownloads,
+    filename: &str,
+    mime_type: &str,
+    data: Option<&[u8]>,
+) -> Option<SavedDownload> {
+    match downloads.save(filename, mime_type, data?).await {
+        Ok(saved) => Some(saved),
+        Err(error) => {
+            log::warn!(
+                "[TelegramGateway] Skipping attachment {}: {}",
+                filename,
+                error
+            );
+            None
+        }
+    }
 }
 
 async fn fetch_file_info(
@@ -596,7 +729,12 @@ async fn fetch_file_info(
         .ok_or_else(|| "Telegram getFile returned empty result".to_string())
 }
 
-async fn download_file(client: &Client, token: &str, file_path: &str) -> Result<Bytes, String> {
+async fn download_file(
+    client: &Client,
+    token: &str,
+    file_path: &str,
+    downloads: &GatewayDownloads,
+) -> Result<Option<Vec<u8>>, String> {
     let url = format!("https://api.telegram.org/file/bot{}/{}", token, file_path);
     let response = client
         .get(&url)
@@ -607,10 +745,7 @@ async fn download_file(client: &Client, token: &str, file_path: &str) -> Result<
     if !status.is_success() {
         return Err(format!("Telegram download failed: status {}", status));
     }
-    response
-        .bytes()
-        .await
-        .map_err(|e| format!("Failed to read file bytes: {}", e))
+    downloads.read_body(response).await
 }
 
 fn sanitize_filename(value: &str) -> String {
@@ -642,7 +777,7 @@ async fn build_message_payload(
     client: &Client,
     token: &str,
     message: &TelegramMessage,
-    attachments_dir: Option<&PathBuf>,
+    downloads: Option<&GatewayDownloads>,
 ) -> Result<(String, Vec<TelegramRemoteAttachment>), String> {
     let mut text_parts: Vec<String> = Vec::new();
     let mut attachments: Vec<TelegramRemoteAttachment> = Vec::new();
@@ -655,7 +790,7 @@ async fn build_message_payload(
         text_parts.push(caption.clone());
     }
 
-    let Some(attachments_dir) = attachments_dir else {
+    let Some(downloads) = downloads else {
         return Ok((text_parts.join("\n"), attachments));
     };
 
@@ -668,22 +803,23 @@ async fn build_message_payload(
             if let Some(file_path) = file_info.file_path.as_ref() {
                 let size = file_info.file_size.unwrap_or(0);
                 if size <= MAX_TELEGRAM_MEDIA_BYTES {
-                    let bytes = download_file(client, token, file_path).await?;
+                    let bytes = download_file(client, token, file_path, downloads).await?;
                     let filename = build_attachment_filename(
                         TELEGRAM_MEDIA_PREFIX,
                         Some(&format!("photo-{}", photo.file_unique_id)),
                         &photo.file_unique_id,
                         "photo",
                     );
-                    let saved_path = save_attachment_file(attachments_dir, &filename, bytes).await;
-                    if let Ok(path) = saved_path {
+                    let saved =
+                        save_download(downloads, &filename, "image/jpeg", bytes.as_deref()).await;
+                    if let Some(saved) = saved {
                         attachments.push(TelegramRemoteAttachment {
                             id: photo.file_unique_id.clone(),
                             attachment_type: "image".to_string(),
-                            file_path: path,
+                            file_path: saved.path,
                             filename,
-                            mime_type: "image/jpeg".to_string(),
-                            size,
+                            mime_type: saved.mime_type,
+                            size: saved.size,
                             duration_seconds: None,
                             caption: None,
                         });
@@ -698,25 +834,23 @@ async fn build_message_payload(
         if let Some(file_path) = file_info.file_path.as_ref() {
             let size = file_info.file_size.unwrap_or(0);
             if size <= MAX_TELEGRAM_MEDIA_BYTES {
-                let bytes = download_file(client, token, file_path).await?;
+                let bytes = download_file(client, token, file_path, downloads).await?;
                 let filename = build_attachment_filename(
                     TELEGRAM_MEDIA_PREFIX,
                     Some(&format!("voice-{}", voice.file_unique_id)),
                     &voice.file_unique_id,
                     "voice",
                 );
-                let saved_path = save_attachment_file(attachments_dir, &filename, bytes).await;
-                if let Ok(path) = saved_path {
+                let mime_type = voice.mime_type.as_deref().unwrap_or("audio/ogg");
+                let saved = save_download(downloads, &filename, mime_type, bytes.as_deref()).await;
+                if let Some(saved) = saved {
                     attachments.push(TelegramRemoteAttachment {
                         id: voice.file_unique_id.clone(),
                         attachment_type: "voice".to_string(),
-                        file_path: path,
+                        file_path: saved.path,
                         filename,
-                        mime_type: voice
-                            .mime_type
-                            .clone()
-                            .unwrap_or_else(|| "audio/ogg".to_string()),
-                        size,
+                        mime_type: saved.mime_type,
+                        size: saved.size,
                         duration_seconds: voice.duration,
                         caption: None,
                     });
@@ -730,25 +864,23 @@ async fn build_message_payload(
         if let Some(file_path) = file_info.file_path.as_ref() {
             let size = file_info.file_size.unwrap_or(0);
             if size <= MAX_TELEGRAM_MEDIA_BYTES {
-                let bytes = download_file(client, token, file_path).await?;
+                let bytes = download_file(client, token, file_path, downloads).await?;
                 let filename = build_attachment_filename(
                     TELEGRAM_MEDIA_PREFIX,
                     audio.file_name.as_deref(),
                     &audio.file_unique_id,
                     "audio",
                 );
-                let saved_path = save_attachment_file(attachments_dir, &filename, bytes).await;
-                if let Ok(path) = saved_path {
+                let mime_type = audio.mime_type.as_deref().unwrap_or("audio/mpeg");
+                let saved = save_download(downloads, &filename, mime_type, bytes.as_deref()).await;
+                if let Some(saved) = saved {
                     attachments.push(TelegramRemoteAttachment {
                         id: audio.file_unique_id.clone(),
                         attachment_type: "audio".to_string(),
-                        file_path: path,
+                        file_path: saved.path,
                         filename,
-                        mime_type: audio
-                            .mime_type
-                            .clone()
-                            .unwrap_or_else(|| "audio/mpeg".to_string()),
-                        size,
+                        mime_type: saved.mime_type,
+                        size: saved.size,
                         duration_seconds: audio.duration,
                         caption: None,
                     });
@@ -762,25 +894,26 @@ async fn build_message_payload(
         if let Some(file_path) = file_info.file_path.as_ref() {
             let size = file_info.file_size.unwrap_or(0);
             if size <= MAX_TELEGRAM_MEDIA_BYTES {
-                let bytes = download_file(client, token, file_path).await?;
+                let bytes = download_file(client, token, file_path, downloads).await?;
                 let filename = build_attachment_filename(
                     TELEGRAM_MEDIA_PREFIX,
                     document.file_name.as_deref(),
                     &document.file_unique_id,
                     "document",
                 );
-                let saved_path = save_attachment_file(attachments_dir, &filename, bytes).await;
-                if let Ok(path) = saved_path {
+                let mime_type = document
+                    .mime_type
+                    .as_deref()
+                    .unwrap_or("application/octet-stream");
+                let saved = save_download(downloads, &filename, mime_type, bytes.as_deref()).await;
+                if let Some(saved) = saved {
                     attachments.push(TelegramRemoteAttachment {
                         id: document.file_unique_id.clone(),
                         attachment_type: "file".to_string(),
-                        file_path: path,
+                        file_path: saved.path,
                         filename,
-                        mime_type: document
-                            .mime_type
-                            .clone()
-                            .unwrap_or_else(|| "application/octet-stream".to_string()),
-                        size,
+                        mime_type: saved.mime_type,
+                        size: saved.size,
                         duration_seconds: None,
                         caption: None,
                     });
@@ -843,6 +976,7 @@ struct TelegramChat {
 
 #[derive(Debug, Deserialize, Serialize)]
 struct TelegramUser {
+    pub id: Option<i64>,
     pub username: Option<String>,
     pub first_name: Option<String>,
     pub last_name: Option<String>,
@@ -900,6 +1034,12 @@ struct TelegramFileInfoResponse {
     pub description: Option<String>,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+struct TelegramBoolResponse {
+    pub ok: bool,
+    pub description: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 struct TelegramSendMessageResult {
     pub message_id: i64,
@@ -968,6 +1108,7 @@ pub async fn telegram_set_config(
     mut config: TelegramConfig,
 ) -> Result<(), String> {
     sanitize_allowed_chat_ids(&mut config.allowed_chat_ids);
+    sanitize_allowed_chat_ids(&mut config.allowed_user_ids);
     save_config(&app_handle, &config).await?;
     let mut gateway = state.lock().await;
     gateway.config = config.clone();
@@ -975,9 +1116,11 @@ pub async fn telegram_set_config(
 
     if config.enabled && !config.token.is_empty() {
         log::info!(
-            "[TelegramGateway] Config updated (enabled={}, allowed_chat_ids={}, poll_timeout_secs={})",
+            "[TelegramGateway] Config updated (enabled={}, allowed_chat_ids={}, allowed_user_ids={}, allow_all={}, poll_timeout_secs={})",
             config.enabled,
             config.allowed_chat_ids.len(),
+            config.allowed_user_ids.len(),
+            config.allow_all,
             config.poll_timeout_secs
         );
         let _ = start_gateway(app_handle, state.inner().clone()).await;
@@ -1051,13 +1194,66 @@ pub async fn telegram_start(
 
 #[tauri::command]
 pub async fn telegram_stop(state: State<'_, TelegramGatewayState>) -> Result<(), String> {
-    let mut gateway = state.lock().await;
-    if let Some(stop_tx) = gateway.stop_tx.take() {
-        let _ = stop_tx.send(true);
+    stop_gateway(state.inner()).await;
+    Ok(())
+}
+
+async fn stop_gateway(state: &TelegramGatewayState) {
+    let indicators: Vec<TypingIndicator> = {
+        let mut gateway = state.lock().await;
+        if let Some(stop_tx) = gateway.stop_tx.take() {
+            let _ = stop_tx.send(true);
+        }
+        gateway.running = false;
+        gateway
+            .typing
+            .drain()
+            .map(|(_, indicator)| indicator)
+            .collect()
+    };
+    for mut indicator in indicators {
+        indicator.stop().await;
     }
-    gateway.running = false;
     log::info!("[TelegramGateway] Stop requested");
-    Ok(())
+}
+
+/// Telegram gateway exposed through the integration registry
+pub struct TelegramIntegration {
+    app_handle: AppHandle,
+    state: TelegramGatewayState,
+}
+
+impl TelegramIntegration {
+    pub fn new(app_handle: AppHandle, state: TelegramGatewayState) -> Self {
+        Self { app_handle, state }
+    }
+}
+
+#[async_trait::async_trait]
+impl Integration for TelegramIntegration {
+    fn id(&self) -> &str {
+        "telegram"
+    }
+
+    async fn start(&self) -> Result<(), String> {
+        start_gateway(self.app_handle.clone(), self.state.clone()).await
+    }
+
+    async fn stop(&self) -> Result<(), String> {
+        stop_gateway(&self.state).await;
+        Ok(())
+    }
+
+    async fn status(&self) -> IntegrationStatus {
+        let gateway = self.state.lock().await;
+        IntegrationStatus {
+            id: "telegram".to_string(),
+            running: gateway.running,
+            last_error: gateway.last_error.clone(),
+            last_error_at_ms: gateway.last_error_at_ms,
+            last_activity_at_ms: gateway.last_poll_at_ms,
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -1106,7 +1302,10 @@ pub async fn telegram_send_message(
         return Err("Telegram bot token is not configured".to_string());
     }
 
-    let client = Client::builder()
+    // First real content replaces the typing indicator
+    stop_typing(state.inner(), request.chat_id).await;
+
+    let client = crate::network_proxy::client_builder()
         .timeout(Duration::from_secs(15))
         .build()
         .map_err(|e| format!("Failed to build http client: {}", e))?;
@@ -1177,7 +1376,7 @@ pub async fn telegram_edit_message(
         return Err("Telegram bot token is not configured".to_string());
     }
 
-    let client = Client::builder()
+    let client = crate::network_proxy::client_builder()
         .timeout(Duration::from_secs(15))
         .build()
         .map_err(|e| format!("Failed to build http client: {}", e))?;
@@ -1233,6 +1432,110 @@ pub async fn telegram_edit_message(
     Ok(())
 }
 
+fn build_chat_action_payload(chat_id: i64, action: &str) -> serde_json::Value {
+    serde_json::json!({
+        "chat_id": chat_id,
+        "action": action,
+    })
+}
+
+async fn send_chat_action(
+    client: &Client,
+    token: &str,
+    chat_id: i64,
+    action: &str,
+) -> Result<(), String> {
+    let url = format!("https://api.telegram.org/bot{}/sendChatAction", token);
+    let response = client
+        .post(&url)
+        .json(&build_chat_action_payload(chat_id, action))
+        .send()
+        .await
+        .map_err(|e| format!("Telegram sendChatAction failed: {}", e))?;
+
+    let payload = response
+        .json::<TelegramBoolResponse>()
+        .await
+        .map_err(|e| format!("Failed to parse sendChatAction response: {}", e))?;
+
+    if !payload.ok {
+        let description = payload
+            .description
+            .unwrap_or_else(|| "Telegram sendChatAction returned ok=false".to_string());
+        return Err(description);
+    }
+
+    Ok(())
+}
+
+struct TelegramTypingSink {
+    client: Client,
+    token: String,
+    chat_id: i64,
+}
+
+#[async_trait::async_trait]
+impl TypingSink for TelegramTypingSink {
+    async fn send_typing(&self) -> Result<(), String> {
+        send_chat_action(&self.client, &self.token, self.chat_id, "typing").await
+    }
+}
+
+async fn stop_typing(state: &TelegramGatewayState, chat_id: i64) {
+    let indicator = {
+        let mut gateway = state.lock().await;
+        gateway.typing.remove(&chat_id)
+    };
+    if let Some(mut indicator) = indicator {
+        indicator.stop().await;
+        log::debug!("[TelegramGateway] Typing stopped chat_id={}", chat_id);
+    }
+}
+
+/// Show `typing` in the chat until the first message is sent or typing is stopped
+#[tauri::command]
+pub async fn telegram_start_typing(
+    state: State<'_, TelegramGatewayState>,
+    chat_id: i64,
+) -> Result<(), String> {
+    let mut gateway = state.lock().await;
+    if gateway.config.token.is_empty() {
+        return Err("Telegram bot token is not configured".to_string());
+    }
+    if gateway
+        .typing
+        .get(&chat_id)
+        .is_some_and(|indicator| indicator.is_active())
+    {
+        return Ok(());
+    }
+
+    let client = crate::network_proxy::client_builder()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .map_err(|e| format!("Failed to build http client: {}", e))?;
+    let sink = TelegramTypingSink {
+        client,
+        token: gateway.config.token.clone(),
+        chat_id,
+    };
+    log::debug!("[TelegramGateway] Typing started chat_id={}", chat_id);
+    gateway.typing.insert(
+        chat_id,
+        TypingIndicator::start(Arc::new(sink), DEFAULT_TYPING_REFRESH),
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn telegram_stop_typing(
+    state: State<'_, TelegramGatewayState>,
+    chat_id: i64,
+) -> Result<(), String> {
+    stop_typing(state.inner(), chat_id).await;
+    Ok(())
+}
+
 pub fn default_state() -> TelegramGatewayState {
     Arc::new(Mutex::new(TelegramGateway::new()))
 }
@@ -1240,9 +1543,11 @@ pub fn default_state() -> TelegramGatewayState {
 #[cfg(test)]
 mod tests {
     use super::{
-        load_state, sanitize_allowed_chat_ids, save_state, TelegramGatewayStateSnapshot,
+        build_chat_action_payload, denial_text, is_sender_allowed, load_state,
+        sanitize_allowed_chat_ids, save_state, TelegramConfig, TelegramGatewayStateSnapshot,
         TELEGRAM_STATE_VERSION,
     };
+    use crate::integrations::commands::{route_inbound, GatewayCommand, InboundAction};
     use tauri::test::mock_app;
 
     /// This test uses Tauri test infrastructure that may not work on Windows CI
@@ -1272,4 +1577,88 @@ mod tests {
         sanitize_allowed_chat_ids(&mut ids);
         assert_eq!(ids, vec![123, 456]);
     }
+
+    #[test]
+    fn empty_allowlist_denies_unless_allow_all() {
+        let mut config = TelegramConfig::default();
+        assert!(!is_sender_allowed(&config, 123, Some(7)));
+
+        config.allow_all = true;
+        assert!(is_sender_allowed(&config, 123, Some(7)));
+    }
+
+    #[test]
+    fn non_allowlisted_sender_is_dropped() {
+        let config = TelegramConfig {
+            allowed_chat_ids: vec![100],
+            allowed_user_ids: vec![7],
+            ..TelegramConfig::default()
+        };
+        assert!(!is_sender_allowed(&config, 200, Some(8)));
+        assert!(!is_sender_allowed(&config, 200, None));
+    }
+
+    #[test]
+    fn allowlisted_sender_proceeds() {
+        let config = TelegramConfig {
+            allowed_chat_ids: vec![100],
+            allowed_user_ids: vec![7],
+            ..TelegramConfig::default()
+        };
+        assert!(is_sender_allowed(&config, 100, None));
+        assert!(is_sender_allowed(&config, 200, Some(7)));
+    }
+
+    #[test]
+    fn allow_all_is_ignored_when_allowlist_is_set() {
+        let config = TelegramConfig {
+            allowed_chat_ids: vec![100],
+            allow_all: true,
+            ..TelegramConfig::default()
+        };
+        assert!(!is_sender_allowed(&config, 200, None));
+    }
+
+    #[test]
+    fn denial_text_skips_blank_message() {
+        let mut config = TelegramConfig::default();
+        assert_eq!(denial_text(&config), None);
+
+        config.denial_message = Some("   ".to_string());
+        assert_eq!(denial_text(&config), None);
+
+        config.denial_message = Some("Access denied".to_string());
+        assert_eq!(denial_text(&config), Some("Access denied"));
+    }
+
+    #[test]
+    fn legacy_config_without_new_fields_parses() {
+        let config: TelegramConfig = serde_json::from_str(
+            r#"{"enabled":true,"token":"t","allowedChatIds":[1],"pollTimeoutSecs":25}"#,
+        )
+        .expect("legacy config should parse");
+        assert!(config.allowed_user_ids.is_empty());
+        assert!(!config.allow_all);
+        assert!(config.denial_message.is_none());
+    }
+
+    #[test]
+    fn legacy_config_gets_default_command_prefix_behaviour() {
+        let config: TelegramConfig = serde_json::from_str(
+            r#"{"enabled":true,"token":"t","allowedChatIds":[1],"pollTimeoutSecs":25}"#,
+        )
+        .expect("legacy config should parse");
+        assert!(config.command_prefix.is_empty());
+        assert_eq!(
+            route_inbound("/stop", &config.command_prefix),
+            InboundAction::Dispatch(GatewayCommand::Stop)
+        );
+    }
+
+    #[test]
+    fn chat_action_payload_uses_typing_action() {
+        let payload = build_chat_action_payload(42, "typing");
+        assert_eq!(payload["chat_id"], 42);
+        assert_eq!(payload["action"], "typing");
+    }
 }
@@ -1,13 +1,17 @@
 use log::{error, info, warn};
 use portable_pty::{native_pty_system, CommandBuilder, PtySize};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::io::{Read, Write};
 use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// Bytes of output kept per PTY so a reloaded frontend can reattach
+pub const DEFAULT_SCROLLBACK_BYTES: usize = 512 * 1024;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PtySpawnResult {
+    /// Stable id; PTYs outlive frontend reloads and can be reattached with it
     pub pty_id: String,
 }
 
@@ -15,6 +19,89 @@ pub struct PtySpawnResult {
 pub struct PtyOutput {
     pub pty_id: String,
     pub data: String,
+    /// Scrollback offset just past this chunk, used to dedupe after reattach
+    #[serde(default)]
+    pub offset: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PtyReattachResult {
+    pub pty_id: String,
+    /// Recent output to replay before live events resume
+    pub scrollback: String,
+    /// Live events with an offset at or below this are already in `scrollback`
+    pub offset: u64,
+    /// Older output was dropped from the bounded buffer
+    pub truncated: bool,
+}
+
+/// Upper bound on matches returned by a scrollback search
+const MAX_SEARCH_MATCHES: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PtySearchMatch {
+    /// Zero-based line index within the captured scrollback
+    pub line: usize,
+    /// Line text with terminal escape sequences removed
+    pub content: String,
+}
+
+lazy_static::lazy_static! {
+    /// CSI and OSC escape sequences emitted by shells and TUI programs
+    static ref ANSI_ESCAPE: regex::Regex =
+        regex::Regex::new(r"\x1b\[[0-?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)|\x1b[@-Z\\-_]")
+            .expect("valid ANSI escape regex");
+}
+
+/// Bounded buffer of the most recent PTY output
+struct Scrollback {
+    data: VecDeque<u8>,
+    capacity: usize,
+    total_written: u64,
+}
+
+impl Default for Scrollback {
+    fn default() -> Self {
+        Self::new(DEFAULT_SCROLLBACK_BYTES)
+    }
+}
+
+impl Scrollback {
+    fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            data: VecDeque::with_capacity(capacity.min(64 * 1024)),
+            capacity,
+            total_written: 0,
+        }
+    }
+
+    fn push(&mut self, bytes: &[u8]) {
+        self.total_written += bytes.len() as u64;
+        let bytes = &bytes[bytes.len().saturating_sub(self.capacity)..];
+        let overflow = (self.data.len() + bytes.len()).saturating_sub(self.capacity);
+        self.data.drain(..overflow);
+        self.data.extend(bytes);
+    }
+
+    fn end_offset(&self) -> u64 {
+        self.total_written
+    }
+
+    fn is_truncated(&self) -> bool {
+        self.total_written > self.data.len() as u64
+    }
+
+    /// The newest `max_bytes` (or everything kept), starting on a UTF-8 boundary
+    fn contents(&self, max_bytes: Option<usize>) -> String {
+        let take = max_bytes.unwrap_or(usize::MAX).min(self.data.len());
+        let mut start = self.data.len() - take;
+        while start < self.data.len() && (self.data[start] & 0b1100_0000) == 0b1000_0000 {
+            start += 1;
+        }
+        let bytes: Vec<u8> = self.data.range(start..).copied().collect();
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
 }
 
 struct PtySession {
@@ -23,6 +110,7 @@ struct PtySession {
     child: Box<dyn portable_pty::Child + Send + Sync>,
     #[allow(dead_code)]
     master: Box<dyn portable_pty::MasterPty + Send>,
+    scrollback: Arc<Mutex<Scrollback>>,
 }
 
 type PtyRegistry = Arc<Mutex<HashMap<String, PtySession>>>;
@@ -186,6 +274,16 @@ pub async fn pty_spawn(
     cols: Option<u16>,
     rows: Option<u16>,
     preferred_shell: Option<String>,
+) -> Result<PtySpawnResult, String> {
+    spawn_pty(app, cwd, cols, rows, preferred_shell)
+}
+
+fn spawn_pty<R: Runtime>(
+    app: AppHandle<R>,
+    cwd: Option<String>,
+    cols: Option<u16>,
+    rows: Option<u16>,
+    preferred_shell: Option<String>,
 ) -> Result<PtySpawnResult, String> {
     info!("Spawning new PTY session");
 
@@ -289,6 +387,8 @@ pub async fn pty_spawn(
         .try_clone_reader()
         .map_err(|e| format!("Failed to clone reader: {}", e))?;
 
+    let scrollback = Arc::new(Mutex::new(Scrollback::default()));
+
     // Store the session - keeping child and master alive is critical on Windows
     {
         let mut sessions = PTY_SESSIONS.lock().unwrap();
@@ -298,6 +398,7 @@ pub async fn pty_spawn(
                 writer,
                 child,
                 master: pair.master,
+                scrollback: scrollback.clone(),
             },
         );
     }
@@ -314,11 +415,13 @@ pub async fn pty_spawn(
                 Ok(0) => {
                     info!("PTY {} closed (read returned 0)", pty_id_clone);
                     // PTY closed
+                    let offset = scrollback.lock().unwrap().end_offset();
                     let _ = app_clone.emit(
                         "pty-output",
                         PtyOutput {
                             pty_id: pty_id_clone.clone(),
                             data: String::new(),
+                            offset,
                         },
                     );
                     break;
@@ -326,11 +429,18 @@ pub async fn pty_spawn(
                 Ok(n) => {
                     let data = String::from_utf8_lossy(&buffer[..n]).to_string();
                     info!("PTY {} read {} bytes", pty_id_clone, n);
+                    // Record before emitting so a concurrent reattach never misses a chunk
+                    let offset = {
+                        let mut scrollback = scrollback.lock().unwrap();
+                        scrollback.push(&buffer[..n]);
+                        scrollback.end_offset()
+                    };
                     let emit_result = app_clone.emit(
                         "pty-output",
                         PtyOutput {
                             pty_id: pty_id_clone.clone(),
                             data,
+                            offset,
                         },
                     );
                     if let Err(e) = emit_result {
@@ -429,6 +539,88 @@ pub fn pty_kill(pty_id: String) -> Result<(), String> {
     }
 }
 
+/// Ids of the PTYs that are still running, for reattaching after a reload
+#[tauri::command]
+pub fn pty_list_sessions() -> Vec<String> {
+    PTY_SESSIONS.lock().unwrap().keys().cloned().collect()
+}
+
+/// Reattach to a running PTY: returns its recent output to replay.
+/// Live output keeps arriving on `pty-output`; skip events up to `offset`.
+#[tauri::command]
+pub fn pty_reattach(pty_id: String) -> Result<PtyReattachResult, String> {
+    info!("Reattaching PTY session {}", pty_id);
+    let scrollback = session_scrollback(&pty_id)?;
+    let scrollback = scrollback.lock().unwrap();
+    Ok(PtyReattachResult {
+        pty_id,
+        scrollback: scrollback.contents(None),
+        offset: scrollback.end_offset(),
+        truncated: scrollback.is_truncated(),
+    })
+}
+
+/// Captured output of a PTY, limited to the newest `max_bytes` when given
+#[tauri::command]
+pub fn pty_get_scrollback(pty_id: String, max_bytes: Option<usize>) -> Result<String, String> {
+    let scrollback = session_scrollback(&pty_id)?;
+    let contents = scrollback.lock().unwrap().contents(max_bytes);
+    Ok(contents)
+}
+
+/// Write the captured output of a PTY to `path`, returning the bytes written
+#[tauri::command]
+pub fn pty_export_log(pty_id: String, path: String) -> Result<u64, String> {
+    let contents = pty_get_scrollback(pty_id.clone(), None)?;
+    let path = std::path::Path::new(&path);
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create log directory: {}", e))?;
+    }
+    std::fs::write(path, &contents).map_err(|e| {
+        error!("Failed to export PTY {} log: {}", pty_id, e);
+        format!("Failed to write terminal log: {}", e)
+    })?;
+    info!("Exported PTY {} log to {}", pty_id, path.display());
+    Ok(contents.len() as u64)
+}
+
+fn search_lines(text: &str, matcher: &regex::Regex) -> Vec<PtySearchMatch> {
+    let plain = ANSI_ESCAPE.replace_all(text, "");
+    plain
+        .split('\n')
+        .enumerate()
+        .filter_map(|(line, content)| {
+            let content = content.trim_end_matches('\r');
+            matcher.is_match(content).then(|| PtySearchMatch {
+                line,
+                content: content.to_string(),
+            })
+        })
+        .take(MAX_SEARCH_MATCHES)
+        .collect()
+}
+
+/// Find lines in the captured scrollback matching `pattern` (literal unless `regex`)
+#[tauri::command]
+pub fn pty_search_scrollback(
+    pty_id: String,
+    pattern: String,
+    regex: Option<bool>,
+) -> Result<Vec<PtySearchMatch>, String> {
+    let matcher = crate::search::build_text_regex(&pattern, regex.unwrap_or(false))?;
+    let text = pty_get_scrollback(pty_id, None)?;
+    Ok(search_lines(&text, &matcher))
+}
+
+fn session_scrollback(pty_id: &str) -> Result<Arc<Mutex<Scrollback>>, String> {
+    let sessions = PTY_SESSIONS.lock().unwrap();
+    sessions
+        .get(pty_id)
+        .map(|session| session.scrollback.clone())
+        .ok_or_else(|| format!("PTY session {} not found", pty_id))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -609,6 +801,7 @@ mod tests {
                         writer,
                         child,
                         master: pair.master,
+                        scrollback: Default::default(),
                     },
                 );
             }
@@ -685,6 +878,7 @@ mod tests {
                         writer,
                         child,
                         master: pair.master,
+                        scrollback: Default::default(),
                     },
                 );
             }
@@ -749,6 +943,7 @@ mod tests {
                         writer,
                         child,
                         master: pair.master,
+                        scrollback: Default::default(),
                     },
                 );
             }
@@ -811,6 +1006,7 @@ mod tests {
                         writer,
                         child,
                         master: pair.master,
+                        scrollback: Default::default(),
                     },
                 );
             }
@@ -908,6 +1104,7 @@ mod tests {
                         writer,
                         child,
                         master: pair.master,
+                        scrollback: Default::default(),
                     },
                 );
             }
@@ -985,6 +1182,7 @@ mod tests {
                             writer,
                             child,
                             master: pair.master,
+                            scrollback: Default::default(),
                         },
                     );
                 }
@@ -1066,6 +1264,7 @@ mod tests {
                         writer,
                         child,
                         master: pair.master,
+                        scrollback: Default::default(),
                     },
                 );
             }
@@ -1088,4 +1287,137 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_scrollback_is_bounded() {
+        let mut scrollback = Scrollback::new(8);
+        scrollback.push(b"0123456789");
+        scrollback.push(b"ab");
+        assert_eq!(scrollback.contents(None), "456789ab");
+        assert_eq!(scrollback.end_offset(), 12);
+        assert!(scrollback.is_truncated());
+        assert_eq!(scrollback.contents(Some(3)), "9ab");
+    }
+
+    #[test]
+    fn test_scrollback_starts_on_char_boundary() {
+        let mut scrollback = Scrollback::new(64);
+        scrollback.push("héllo".as_bytes());
+        // Cutting inside "é" skips its continuation byte
+        assert_eq!(scrollback.contents(Some(5)), "llo");
+    }
+
+    #[test]
+    fn test_search_scrollback_literal_and_regex() {
+        let mut scrollback = Scrollback::new(4096);
+        scrollback.push(b"$ cargo build\r\n   Compiling app v0.1.0\r\n");
+        scrollback.push(b"\x1b[31merror[E0308]\x1b[0m: mismatched types\r\n");
+        scrollback.push(b"warning: unused variable\r\nerror: aborting (1.5s)\r\n");
+        let text = scrollback.contents(None);
+
+        let literal = crate::search::build_text_regex("error", false).unwrap();
+        let matches = search_lines(&text, &literal);
+        assert_eq!(
+            matches.iter().map(|m| m.line).collect::<Vec<_>>(),
+            vec![2, 4]
+        );
+        assert_eq!(matches[0].content, "error[E0308]: mismatched types");
+
+        // Literal mode does not treat "." as a wildcard
+        let dot = crate::search::build_text_regex("1.5s", false).unwrap();
+        assert_eq!(search_lines(&text, &dot).len(), 1);
+        let dot = crate::search::build_text_regex("1x5s", false).unwrap();
+        assert!(search_lines(&text, &dot).is_empty());
+
+        let regex = crate::search::build_text_regex(r"^(warning|\s+compiling)", true).unwrap();
+        let matches = search_lines(&text, &regex);
+        assert_eq!(
+            matches.iter().map(|m| m.line).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+
+        assert!(crate::search::build_text_regex("(unclosed", true).is_err());
+    }
+
+    #[cfg(unix)]
+    mod reattach_tests {
+        use super::*;
+        use std::time::{Duration, Instant};
+
+        fn wait_for_scrollback(pty_id: &str, needle: &str) -> PtyReattachResult {
+            let deadline = Instant::now() + Duration::from_secs(10);
+            loop {
+                let result = pty_reattach(pty_id.to_string()).expect("session should exist");
+                if result.scrollback.contains(needle) || Instant::now() > deadline {
+                    return result;
+                }
+                std::thread::sleep(Duration::from_millis(50));
+            }
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn test_reattach_replays_scrollback() {
+            let app = tauri::test::mock_app();
+            let spawned = spawn_pty(
+                app.handle().clone(),
+                None,
+                Some(80),
+                Some(24),
+                Some("/bin/sh".to_string()),
+            )
+            .expect("spawn should succeed");
+
+            assert!(pty_list_sessions().contains(&spawned.pty_id));
+            pty_write(
+                spawned.pty_id.clone(),
+                "echo reattach-$((40+2))\n".to_string(),
+            )
+            .unwrap();
+
+            // Simulate a reloaded frontend asking for the session again
+            let result = wait_for_scrollback(&spawned.pty_id, "reattach-42");
+            assert!(result.scrollback.contains("reattach-42"));
+            assert_eq!(result.pty_id, spawned.pty_id);
+            assert!(result.offset >= result.scrollback.len() as u64);
+            assert!(!result.truncated);
+
+            pty_kill(spawned.pty_id.clone()).unwrap();
+            assert!(pty_reattach(spawned.pty_id).is_err());
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn test_scrollback_read_and_export() {
+            let app = tauri::test::mock_app();
+            let spawned = spawn_pty(
+                app.handle().clone(),
+                None,
+                Some(80),
+                Some(24),
+                Some("/bin/sh".to_string()),
+            )
+            .expect("spawn should succeed");
+            let pty_id = spawned.pty_id;
+
+            pty_write(pty_id.clone(), "echo scrollback-$((6*7))\n".to_string()).unwrap();
+            wait_for_scrollback(&pty_id, "scrollback-42");
+
+            let full = pty_get_scrollback(pty_id.clone(), None).unwrap();
+            assert!(full.contains("scrollback-42"));
+
+            let capped = pty_get_scrollback(pty_id.clone(), Some(8)).unwrap();
+            assert!(!capped.is_empty());
+            assert!(capped.len() <= 8);
+
+            let dir = tempfile::TempDir::new().unwrap();
+            let path = dir.path().join("logs").join("terminal.log");
+            let written =
+                pty_export_log(pty_id.clone(), path.to_string_lossy().to_string()).unwrap();
+            let exported = std::fs::read_to_string(&path).unwrap();
+            assert_eq!(written, exported.len() as u64);
+            assert!(exported.contains("scrollback-42"));
+
+            pty_kill(pty_id.clone()).unwrap();
+            assert!(pty_get_scrollback(pty_id, None).is_err());
+        }
+    }
 }
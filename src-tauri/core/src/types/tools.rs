@@ -268,7 +268,7 @@ async fn execute_tool_by_name(
                 .unwrap_or("");
             let platform_result = platform
                 .filesystem
-                .write_file(path, content, &platform_ctx)
+                .write_file(path, content, true, &platform_ctx)
                 .await;
             ToolExecutionOutput {
                 success: platform_result.success,
@@ -300,7 +300,7 @@ async fn execute_tool_by_name(
                         }
                         let write_result = platform
                             .filesystem
-                            .write_file(path, &new_content, &platform_ctx)
+                            .write_file(path, &new_content, false, &platform_ctx)
                             .await;
                         ToolExecutionOutput {
                             success: write_result.success,
@@ -423,7 +423,11 @@ async fn execute_tool_by_name(
         "webFetch" | "web_fetch" => {
             if let Some(url) = request.input.get("url").and_then(|v| v.as_str()) {
                 // Perform HTTP fetch
-                match reqwest::get(url).await {
+                match crate::network_proxy::shared_json_client()
+                    .get(url)
+                    .send()
+                    .await
+                {
                     Ok(response) => match response.text().await {
                         Ok(text) => ToolExecutionOutput {
                             success: true,
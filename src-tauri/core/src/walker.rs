@@ -9,7 +9,8 @@
 //! - **Configurable Presets**: Ready-to-use configurations for file search, content search, glob, and directory listing
 //! - **Shared Exclusion Logic**: Centralized directory exclusion handling
 
-use crate::constants::{should_exclude_dir, DEFAULT_MAX_DEPTH};
+use crate::constants::{is_vcs_dir, should_exclude_dir, DEFAULT_IGNORE_GLOBS, DEFAULT_MAX_DEPTH};
+use ignore::overrides::{Override, OverrideBuilder};
 use ignore::{Walk, WalkBuilder, WalkParallel};
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
@@ -31,6 +32,12 @@ pub struct WalkerConfig {
     pub workspace_root: Option<PathBuf>,
     /// Additional directories to exclude (on top of defaults)
     pub additional_excludes: Vec<String>,
+    /// Apply the default `DEFAULT_EXCLUDE_DIRS` list (build output, vendored deps)
+    /// and `ignore_globs`. VCS metadata directories are excluded regardless.
+    /// Default: `true`
+    pub use_default_excludes: bool,
+    /// File globs to skip (e.g. `*.min.js`). Default: empty
+    pub ignore_globs: Vec<String>,
 }
 
 impl Default for WalkerConfig {
@@ -43,6 +50,8 @@ impl Default for WalkerConfig {
             allow_github_dir: false,
             workspace_root: None,
             additional_excludes: Vec::new(),
+            use_default_excludes: true,
+            ignore_globs: Vec::new(),
         }
     }
 }
@@ -67,6 +76,8 @@ impl WalkerConfig {
             allow_github_dir: true, // Allow .github for CI/CD files
             workspace_root: None,
             additional_excludes: Vec::new(),
+            use_default_excludes: true,
+            ignore_globs: default_ignore_globs(),
         }
     }
 
@@ -83,6 +94,8 @@ impl WalkerConfig {
             allow_github_dir: false,
             workspace_root: None,
             additional_excludes: Vec::new(),
+            use_default_excludes: true,
+            ignore_globs: default_ignore_globs(),
         }
     }
 
@@ -99,6 +112,8 @@ impl WalkerConfig {
             allow_github_dir: false,
             workspace_root: Some(PathBuf::from(workspace_root)),
             additional_excludes: Vec::new(),
+            use_default_excludes: true,
+            ignore_globs: Vec::new(),
         }
     }
 
@@ -115,6 +130,8 @@ impl WalkerConfig {
             allow_github_dir: false,
             workspace_root: None,
             additional_excludes: Vec::new(),
+            use_default_excludes: true,
+            ignore_globs: Vec::new(),
         }
     }
 
@@ -167,6 +184,22 @@ impl WalkerConfig {
         self.additional_excludes = excludes;
         self
     }
+
+    /// Set file globs to skip.
+    pub fn with_ignore_globs(mut self, globs: Vec<String>) -> Self {
+        self.ignore_globs = globs;
+        self
+    }
+
+    /// Set use_default_excludes option.
+    pub fn with_default_excludes(mut self, enabled: bool) -> Self {
+        self.use_default_excludes = enabled;
+        self
+    }
+}
+
+fn default_ignore_globs() -> Vec<String> {
+    DEFAULT_IGNORE_GLOBS.iter().map(|g| g.to_string()).collect()
 }
 
 /// Wrapper around `ignore::WalkBuilder` with unified configuration.
@@ -200,6 +233,22 @@ impl WorkspaceWalker {
             builder.standard_filters(false);
         }
 
+        if config.use_default_excludes && !config.ignore_globs.is_empty() {
+            let mut overrides = OverrideBuilder::new(root_path);
+            for glob in &config.ignore_globs {
+                // Override globs whitelist by default; "!" turns them into ignores
+                if let Err(e) = overrides.add(&format!("!{}", glob)) {
+                    log::warn!("Invalid ignore glob '{}': {}", glob, e);
+                }
+            }
+            match overrides.build() {
+                Ok(overrides) => {
+                    builder.overrides(overrides);
+                }
+                Err(e) => log::warn!("Failed to build ignore globs: {}", e),
+            }
+        }
+
         Self { builder, config }
     }
 
@@ -208,10 +257,16 @@ impl WorkspaceWalker {
         let config = self.config;
         let additional_excludes = config.additional_excludes.clone();
         let allow_github = config.allow_github_dir;
+        let use_default_excludes = config.use_default_excludes;
 
         self.builder
             .filter_entry(move |entry| {
-                Self::should_include_entry(entry, allow_github, &additional_excludes)
+                Self::should_include_entry(
+                    entry,
+                    allow_github,
+                    use_default_excludes,
+                    &additional_excludes,
+                )
             })
             .build()
     }
@@ -221,10 +276,16 @@ impl WorkspaceWalker {
         let config = self.config;
         let additional_excludes = config.additional_excludes.clone();
         let allow_github = config.allow_github_dir;
+        let use_default_excludes = config.use_default_excludes;
 
         self.builder
             .filter_entry(move |entry| {
-                Self::should_include_entry(entry, allow_github, &additional_excludes)
+                Self::should_include_entry(
+                    entry,
+                    allow_github,
+                    use_default_excludes,
+                    &additional_excludes,
+                )
             })
             .build_parallel()
     }
@@ -238,6 +299,7 @@ impl WorkspaceWalker {
     fn should_include_entry(
         entry: &ignore::DirEntry,
         allow_github: bool,
+        use_default_excludes: bool,
         additional_excludes: &[String],
     ) -> bool {
         let path = entry.path();
@@ -258,14 +320,80 @@ impl WorkspaceWalker {
                 return false;
             }
 
+            if is_vcs_dir(name) {
+                return false;
+            }
+
             // Check default excluded directories
-            return !should_exclude_dir(name);
+            return !(use_default_excludes && should_exclude_dir(name));
         }
 
         true
     }
 }
 
+/// Include/exclude filter for individual paths, using the same gitignore-style
+/// globs as the walker (e.g. `src/**`, `*.rs`, `target/`).
+///
+/// A path passes when it, or one of its parent directories below `root`,
+/// matches an include glob (or no include globs were given), and neither it
+/// nor any parent matches an exclude glob.
+#[derive(Debug, Clone)]
+pub struct PathFilter {
+    root: PathBuf,
+    include: Option<Override>,
+    exclude: Option<Override>,
+}
+
+impl PathFilter {
+    pub fn new(root: &Path, include: &[String], exclude: &[String]) -> Result<Self, String> {
+        Ok(Self {
+            root: root.to_path_buf(),
+            include: Self::build_globs(root, include)?,
+            exclude: Self::build_globs(root, exclude)?,
+        })
+    }
+
+    fn build_globs(root: &Path, globs: &[String]) -> Result<Option<Override>, String> {
+        if globs.is_empty() {
+            return Ok(None);
+        }
+        let mut builder = OverrideBuilder::new(root);
+        for glob in globs {
+            builder
+                .add(glob)
+                .map_err(|e| format!("Invalid glob '{}': {}", glob, e))?;
+        }
+        builder
+            .build()
+            .map(Some)
+            .map_err(|e| format!("Failed to build globs: {}", e))
+    }
+
+    /// Whether `path` or any of its parents below the root matches `globs`
+    fn matches_self_or_parent(&self, globs: &Override, path: &Path) -> bool {
+        if globs.matched(path, path.is_dir()).is_whitelist() {
+            return true;
+        }
+        path.ancestors()
+            .skip(1)
+            .take_while(|ancestor| ancestor.starts_with(&self.root) && *ancestor != self.root)
+            .any(|ancestor| globs.matched(ancestor, true).is_whitelist())
+    }
+
+    pub fn is_match(&self, path: &Path) -> bool {
+        if let Some(ref include) = self.include {
+            if !self.matches_self_or_parent(include, path) {
+                return false;
+            }
+        }
+        match self.exclude {
+            Some(ref exclude) => !self.matches_self_or_parent(exclude, path),
+            None => true,
+        }
+    }
+}
+
 /// Validate that a path stays within the workspace root.
 ///
 /// This function canonicalizes the given path and checks if it starts with
@@ -327,6 +455,7 @@ mod tests {
         assert!(config.skip_hidden);
         assert_eq!(config.max_depth, Some(DEFAULT_MAX_DEPTH));
         assert!(!config.allow_github_dir);
+        assert!(config.use_default_excludes);
     }
 
     #[test]
@@ -335,6 +464,7 @@ mod tests {
         assert!(!config.follow_links);
         assert!(!config.skip_hidden); // Allow hidden files
         assert!(config.allow_github_dir); // Allow .github
+        assert_eq!(config.ignore_globs.len(), DEFAULT_IGNORE_GLOBS.len());
     }
 
     #[test]
@@ -456,7 +586,7 @@ mod tests {
             }
         }
 
-        // Note: .github is not in EXCLUDED_DIRS, so it should still be found
+        // Note: .github is not in DEFAULT_EXCLUDE_DIRS, so it should still be found
         // unless explicitly hidden. The skip_hidden setting controls this.
     }
 
@@ -542,4 +672,79 @@ mod tests {
 
         assert!(!found_custom, "custom_exclude directory should be excluded");
     }
+
+    #[test]
+    fn test_walker_without_default_excludes() {
+        let temp_dir = create_test_directory();
+        let config = WalkerConfig::for_file_search().with_default_excludes(false);
+        let walker = WorkspaceWalker::new(temp_dir.path().to_str().unwrap(), config);
+
+        let paths: Vec<String> = walker
+            .build()
+            .flatten()
+            .map(|entry| entry.path().to_string_lossy().to_string())
+            .collect();
+
+        assert!(paths.iter().any(|p| p.contains("node_modules")));
+        assert!(
+            !paths.iter().any(|p| p.contains(".git/objects")),
+            ".git should stay excluded"
+        );
+    }
+
+    #[test]
+    fn test_walker_skips_ignore_globs() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("app.js"), "").unwrap();
+        fs::write(temp_dir.path().join("app.min.js"), "").unwrap();
+        fs::write(temp_dir.path().join("app.js.map"), "").unwrap();
+
+        let config = WalkerConfig::for_content_search();
+        let walker = WorkspaceWalker::new(temp_dir.path().to_str().unwrap(), config);
+        let names: Vec<String> = walker
+            .build()
+            .flatten()
+            .filter(|entry| entry.path().is_file())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+
+        assert_eq!(names, vec!["app.js".to_string()]);
+    }
+
+    #[test]
+    fn test_path_filter_exclude_directory() {
+        let root = Path::new("/repo");
+        let filter = PathFilter::new(root, &[], &["target/".to_string()]).unwrap();
+
+        assert!(!filter.is_match(Path::new("/repo/target/debug/app.d")));
+        assert!(filter.is_match(Path::new("/repo/src/main.rs")));
+    }
+
+    #[test]
+    fn test_path_filter_include_and_exclude() {
+        let root = Path::new("/repo");
+        let filter = PathFilter::new(
+            root,
+            &["src/**".to_string(), "*.toml".to_string()],
+            &["*.snap".to_string()],
+        )
+        .unwrap();
+
+        assert!(filter.is_match(Path::new("/repo/src/lib.rs")));
+        assert!(filter.is_match(Path::new("/repo/Cargo.toml")));
+        assert!(!filter.is_match(Path::new("/repo/docs/guide.md")));
+        assert!(!filter.is_match(Path::new("/repo/src/__snapshots__/a.snap")));
+    }
+
+    #[test]
+    fn test_path_filter_empty_matches_everything() {
+        let filter = PathFilter::new(Path::new("/repo"), &[], &[]).unwrap();
+        assert!(filter.is_match(Path::new("/repo/anything/at/all.txt")));
+    }
+
+    #[test]
+    fn test_path_filter_invalid_glob() {
+        let result = PathFilter::new(Path::new("/repo"), &["src/[".to_string()], &[]);
+        assert!(result.is_err());
+    }
 }
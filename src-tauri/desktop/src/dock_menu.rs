@@ -3,9 +3,17 @@
 use crate::database::Database;
 use crate::window_manager::create_window;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::sync::Arc;
+use talkcody_core::storage::SettingsRepository;
 use tauri::Manager;
 
+/// Number of recent projects shown when no limit is configured
+pub const DEFAULT_RECENT_PROJECTS_LIMIT: usize = 10;
+
+/// Settings key overriding how many recent projects the menu shows
+pub const RECENT_PROJECTS_LIMIT_SETTING: &str = "dock_menu_recent_projects_limit";
+
 /// Common helper to create window from dock menu actions
 /// Uses spawn instead of block_on to avoid potential deadlocks when called from Cocoa main thread
 fn create_window_from_dock(
@@ -28,20 +36,34 @@ fn create_window_from_dock(
             project_id,
             root_path,
             is_new_window,
+            None,
         ) {
             log::error!("Failed to create window from dock menu: {}", e);
         }
     });
 }
 
+/// Read the configured recent-projects limit, falling back to the default
+async fn recent_projects_limit(db: &Arc<Database>) -> usize {
+    SettingsRepository::new(db.clone())
+        .get_setting_or_default(RECENT_PROJECTS_LIMIT_SETTING, DEFAULT_RECENT_PROJECTS_LIMIT)
+        .await
+        .unwrap_or_else(|e| {
+            log::warn!("Invalid recent projects limit setting: {}", e);
+            DEFAULT_RECENT_PROJECTS_LIMIT
+        })
+}
+
 /// Query recent projects from database
 /// Uses the recent_projects table which tracks actual project open times
 async fn query_recent_projects(db: &Arc<Database>) -> Vec<serde_json::Value> {
     // Query from recent_projects table which tracks when projects were actually opened
-    // This gives accurate "recent" ordering based on last open time, not update time
-    let sql = "SELECT project_id as id, project_name as name, root_path FROM recent_projects ORDER BY opened_at DESC LIMIT 10";
+    // This gives accurate "recent" ordering based on last open time, not update time.
+    // Several project records can share a root path, so dedupe before applying the limit.
+    let limit = recent_projects_limit(db).await;
+    let sql = "SELECT project_id as id, project_name as name, root_path, opened_at FROM recent_projects ORDER BY opened_at DESC";
     match db.query(sql, vec![]).await {
-        Ok(result) => result.rows,
+        Ok(result) => dedupe_recent_projects(result.rows, limit),
         Err(e) => {
             log::error!("Failed to query recent projects for dock menu: {}", e);
             vec![]
@@ -49,49 +71,89 @@ async fn query_recent_projects(db: &Arc<Database>) -> Vec<serde_json::Value> {
     }
 }
 
-/// Refresh the dock menu with the latest recent projects
-/// This function queries the database and updates the dock menu on the main thread
-#[cfg(target_os = "macos")]
-pub async fn refresh_dock_menu() {
-    let app_handle = crate::get_app_handle().clone();
+/// Order projects by last-opened time (newest first), keep only the most
+/// recent entry per root path, drop entries without a path and cap at `limit`
+fn dedupe_recent_projects(
+    mut projects: Vec<serde_json::Value>,
+    limit: usize,
+) -> Vec<serde_json::Value> {
+    projects.sort_by_key(|project| {
+        std::cmp::Reverse(
+            project
+                .get("opened_at")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(0),
+        )
+    });
 
-    // Try to get database with retry
-    let db = {
-        let mut retry_count = 0;
-        let max_retries = 5;
-        let mut backoff_ms: u64 = 50;
-
-        loop {
-            if let Some(state) = app_handle.try_state::<Arc<Database>>() {
-                let db = state.inner().clone();
-                if db.connect().await.is_ok() {
-                    break db;
-                }
-            }
+    let mut seen = HashSet::new();
+    projects
+        .into_iter()
+        .filter(
+            |project| match project.get("root_path").and_then(|v| v.as_str()) {
+                Some(path) if !path.is_empty() => seen.insert(path.to_string()),
+                _ => false,
+            },
+        )
+        .take(limit)
+        .collect()
+}
+
+/// Wait for the database to become available, then load the recent projects.
+/// Returns `None` if the database never connects within `max_retries` attempts.
+async fn load_recent_projects(max_retries: u32) -> Option<Vec<serde_json::Value>> {
+    let app_handle = crate::get_app_handle().clone();
 
-            retry_count += 1;
-            if retry_count >= max_retries {
-                log::error!(
-                    "Failed to get database for dock menu refresh after {} retries",
-                    max_retries
+    // Use retry loop instead of fixed delay to handle database initialization
+    // This is more reliable than hardcoded sleep
+    let mut retry_count = 0;
+    let mut backoff_ms: u64 = 50;
+    let max_backoff_ms: u64 = 500;
+
+    let db = loop {
+        if let Some(state) = app_handle.try_state::<Arc<Database>>() {
+            let db = state.inner().clone();
+            // Try to connect to database
+            if db.connect().await.is_ok() {
+                log::info!(
+                    "Database connected for recent projects menu after {} attempts",
+                    retry_count + 1
                 );
-                return;
+                break db;
             }
+        }
 
-            let sleep_ms = backoff_ms;
-            backoff_ms = (backoff_ms * 2).min(500);
-            tokio::time::sleep(tokio::time::Duration::from_millis(sleep_ms)).await;
+        retry_count += 1;
+        if retry_count >= max_retries {
+            log::error!(
+                "Failed to get database for recent projects menu after {} retries",
+                max_retries
+            );
+            return None;
         }
+
+        let sleep_ms = backoff_ms;
+        backoff_ms = (backoff_ms * 2).min(max_backoff_ms);
+        tokio::time::sleep(tokio::time::Duration::from_millis(sleep_ms)).await;
     };
 
-    let recent_projects = query_recent_projects(&db).await;
+    Some(query_recent_projects(&db).await)
+}
+
+/// Refresh the dock menu with the latest recent projects
+/// This function queries the database and updates the dock menu on the main thread
+#[cfg(target_os = "macos")]
+pub async fn refresh_dock_menu() {
+    let Some(recent_projects) = load_recent_projects(5).await else {
+        return;
+    };
 
     log::info!(
         "Refreshing dock menu with {} recent projects",
         recent_projects.len()
     );
 
-    if let Err(e) = app_handle.run_on_main_thread(move || {
+    if let Err(e) = crate::get_app_handle().run_on_main_thread(move || {
         create_native_dock_menu(&recent_projects);
     }) {
         log::error!("Failed to refresh dock menu on main thread: {}", e);
@@ -101,53 +163,19 @@ pub async fn refresh_dock_menu() {
 /// Setup dock menu on macOS using native Cocoa API
 #[cfg(target_os = "macos")]
 pub fn setup_dock_menu() {
-    let app_handle = crate::get_app_handle().clone();
-
     // Spawn async task to load data, then update UI on main thread
     tauri::async_runtime::spawn(async move {
-        // Use retry loop instead of fixed delay to handle database initialization
-        // This is more reliable than hardcoded sleep
-        let mut retry_count = 0;
-        let max_retries = 20; // max wait ~4 seconds with backoff
-        let mut backoff_ms: u64 = 50;
-        let max_backoff_ms: u64 = 500;
-
-        let db = loop {
-            if let Some(state) = app_handle.try_state::<Arc<Database>>() {
-                let db = state.inner().clone();
-                // Try to connect to database
-                if db.connect().await.is_ok() {
-                    log::info!(
-                        "Database connected for dock menu after {} attempts",
-                        retry_count + 1
-                    );
-                    break db;
-                }
-            }
-
-            retry_count += 1;
-            if retry_count >= max_retries {
-                log::error!(
-                    "Failed to get database for dock menu after {} retries",
-                    max_retries
-                );
-                return;
-            }
-
-            let sleep_ms = backoff_ms;
-            backoff_ms = (backoff_ms * 2).min(max_backoff_ms);
-            tokio::time::sleep(tokio::time::Duration::from_millis(sleep_ms)).await;
+        // max wait ~4 seconds with backoff
+        let Some(recent_projects) = load_recent_projects(20).await else {
+            return;
         };
 
-        let recent_projects = query_recent_projects(&db).await;
-
         log::info!(
             "Found {} recent projects for dock menu",
             recent_projects.len()
         );
 
-        let app_handle = app_handle.clone();
-        if let Err(e) = app_handle.run_on_main_thread(move || {
+        if let Err(e) = crate::get_app_handle().run_on_main_thread(move || {
             create_native_dock_menu(&recent_projects);
         }) {
             log::error!("Failed to update dock menu on main thread: {}", e);
@@ -162,12 +190,12 @@ struct DockMenuPayload {
 }
 
 #[derive(Debug)]
-struct DockMenuEntry {
-    title: String,
-    payload: String,
+pub(crate) struct DockMenuEntry {
+    pub(crate) title: String,
+    pub(crate) payload: String,
 }
 
-fn decode_dock_menu_payload(payload: &str) -> (Option<String>, String) {
+pub(crate) fn decode_dock_menu_payload(payload: &str) -> (Option<String>, String) {
     match serde_json::from_str::<DockMenuPayload>(payload) {
         Ok(decoded) => (decoded.id, decoded.path),
         Err(_) => {
@@ -180,7 +208,7 @@ fn decode_dock_menu_payload(payload: &str) -> (Option<String>, String) {
     }
 }
 
-fn build_dock_menu_entries(recent_projects: &[serde_json::Value]) -> Vec<DockMenuEntry> {
+pub(crate) fn build_dock_menu_entries(recent_projects: &[serde_json::Value]) -> Vec<DockMenuEntry> {
     let mut entries = Vec::new();
 
     for project in recent_projects {
@@ -230,7 +258,9 @@ fn create_native_dock_menu(recent_projects: &[serde_json::Value]) {
     use objc::{msg_send, sel, sel_impl};
     use std::sync::Once;
 
-    let menu_entries = build_dock_menu_entries(recent_projects);
+    use crate::tray_menu::MenuItemSpec;
+
+    let menu_items = crate::tray_menu::build_menu_items(recent_projects);
 
     // SAFETY: All unsafe operations in this function are necessary for Cocoa/Objective-C interop.
     // The cocoa crate provides safe abstractions over raw Objective-C calls, but they're marked
@@ -299,58 +329,51 @@ fn create_native_dock_menu(recent_projects: &[serde_json::Value]) {
         // SAFETY: Class was registered above in Once block, guaranteed to exist
         let target_class = Class::get("TalkCodyDockMenuTarget").unwrap();
 
-        // Add recent projects (with folder icon)
-        if !menu_entries.is_empty() {
-            for entry in menu_entries {
-                // SAFETY: Creating NSString from Rust &str - cocoa validates UTF-8
-                let title = NSString::alloc(nil).init_str(&entry.title);
-                let selector = Sel::register("openProjectAction:");
-                // SAFETY: Creating menu item with valid title, action, and empty key equivalent
-                let item: *mut Object = NSMenuItem::alloc(nil).initWithTitle_action_keyEquivalent_(
-                    title,
-                    selector,
-                    NSString::alloc(nil).init_str(""),
-                );
+        // Items come from the shared builder so the dock matches the tray menu:
+        // recent projects, a separator, then "New Window"
+        for menu_item in menu_items {
+            let (selector_name, payload) = match menu_item {
+                MenuItemSpec::Project { ref payload, .. } => {
+                    ("openProjectAction:", Some(payload.clone()))
+                }
+                MenuItemSpec::NewWindow => ("openNewWindowAction:", None),
+                MenuItemSpec::Separator => {
+                    // SAFETY: Adding system-provided separator menu item
+                    let _: () = msg_send![dock_menu, addItem: NSMenuItem::separatorItem(nil)];
+                    continue;
+                }
+            };
+
+            // SAFETY: Creating NSString from Rust &str - cocoa validates UTF-8
+            let title = NSString::alloc(nil).init_str(menu_item.title());
+            let selector = Sel::register(selector_name);
+            // SAFETY: Creating menu item with valid title, action, and empty key equivalent
+            let item: *mut Object = NSMenuItem::alloc(nil).initWithTitle_action_keyEquivalent_(
+                title,
+                selector,
+                NSString::alloc(nil).init_str(""),
+            );
 
-                // SAFETY: Creating new instance of our registered class
-                let target: *mut Object = msg_send![target_class, new];
-                // SAFETY: Setting the target for menu item action
-                let _: () = msg_send![item, setTarget: target];
+            // SAFETY: Creating new instance of our registered class
+            let target: *mut Object = msg_send![target_class, new];
+            // SAFETY: Setting the target for menu item action
+            let _: () = msg_send![item, setTarget: target];
 
+            if let Some(payload) = payload {
                 // SAFETY: Creating NSString from validated UTF-8 payload string
-                let payload_string = NSString::alloc(nil).init_str(&entry.payload);
+                let payload_string = NSString::alloc(nil).init_str(&payload);
                 // SAFETY: Storing NSString as representedObject (type-safe Objective-C property)
                 let _: () = msg_send![item, setRepresentedObject: payload_string];
-
-                // Note: Folder icon temporarily disabled due to compatibility issues
-                // TODO: Add folder icon when a stable solution is found
-
-                // SAFETY: Adding menu item to menu - standard Cocoa API
-                let _: () = msg_send![dock_menu, addItem: item];
             }
 
-            // Add separator after projects
-            // SAFETY: Adding system-provided separator menu item
-            let _: () = msg_send![dock_menu, addItem: NSMenuItem::separatorItem(nil)];
-        }
-
-        // Add "New Window" item (now at the bottom)
-        // SAFETY: Creating NSString from static string literal
-        let title = NSString::alloc(nil).init_str("New Window");
-        let selector = Sel::register("openNewWindowAction:");
-        // SAFETY: Creating menu item with valid parameters
-        let item: *mut Object = NSMenuItem::alloc(nil).initWithTitle_action_keyEquivalent_(
-            title,
-            selector,
-            NSString::alloc(nil).init_str(""),
-        );
+            // Note: Folder icon temporarily disabled due to compatibility issues
+            // TODO: Add folder icon when a stable solution is found
 
-        // SAFETY: Creating new instance of our registered target class
-        let target: *mut Object = msg_send![target_class, new];
-        // SAFETY: Setting target and adding to menu - standard Cocoa operations
-        let _: () = msg_send![item, setTarget: target];
-        let _: () = msg_send![dock_menu, addItem: item];
-        let _: () = msg_send![item, setEnabled: true];
+            // SAFETY: Adding menu item to menu - standard Cocoa API
+            let _: () = msg_send![dock_menu, addItem: item];
+            // SAFETY: Enabling a valid menu item
+            let _: () = msg_send![item, setEnabled: true];
+        }
 
         // Set the dock menu on the application
         // SAFETY: Setting dock menu on NSApp - standard macOS API, menu is retained by app
@@ -360,24 +383,56 @@ fn create_native_dock_menu(recent_projects: &[serde_json::Value]) {
     }
 }
 
-/// No-op for other platforms
+/// Other platforms have no dock; show the same menu from a tray icon instead
 #[cfg(not(target_os = "macos"))]
 pub fn setup_dock_menu() {
-    // Dock menu is only supported on macOS
+    tauri::async_runtime::spawn(async move {
+        let recent_projects = load_recent_projects(20).await.unwrap_or_default();
+        log::info!(
+            "Found {} recent projects for tray menu",
+            recent_projects.len()
+        );
+        if let Err(e) = crate::get_app_handle().run_on_main_thread(move || {
+            crate::tray_menu::setup_tray_menu(&recent_projects);
+        }) {
+            log::error!("Failed to create tray menu on main thread: {}", e);
+        }
+    });
 }
 
-/// No-op for other platforms
+/// Refresh the tray icon menu on platforms without a dock
 #[cfg(not(target_os = "macos"))]
 pub async fn refresh_dock_menu() {
-    // Dock menu is only supported on macOS
+    let Some(recent_projects) = load_recent_projects(5).await else {
+        return;
+    };
+    if let Err(e) = crate::get_app_handle().run_on_main_thread(move || {
+        crate::tray_menu::refresh_tray_menu(&recent_projects);
+    }) {
+        log::error!("Failed to refresh tray menu on main thread: {}", e);
+    }
 }
 
-/// Handle dock menu events (placeholder for future implementation)
+/// Handle tray menu events
+/// Dock menu events on macOS are handled by native Cocoa code above
 pub fn handle_dock_menu_event<R: tauri::Runtime>(
     _app: &tauri::AppHandle<R>,
-    _event: tauri::menu::MenuEvent,
+    event: tauri::menu::MenuEvent,
 ) {
-    // Dock menu events are handled by native Cocoa code above
+    match crate::tray_menu::parse_menu_id(event.id().as_ref()) {
+        Some(crate::tray_menu::MenuAction::NewWindow) => {
+            log::info!("Tray menu: New window action triggered");
+            create_window_from_dock(None, None, true);
+        }
+        Some(crate::tray_menu::MenuAction::OpenProject {
+            project_id,
+            root_path,
+        }) => {
+            log::info!("Tray menu: Open project action triggered: {}", root_path);
+            create_window_from_dock(project_id, Some(root_path), false);
+        }
+        None => {}
+    }
 }
 
 #[cfg(test)]
@@ -441,4 +496,51 @@ mod tests {
         assert!(has_first);
         assert!(has_second);
     }
+
+    fn recent(id: &str, root_path: &str, opened_at: i64) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "name": id,
+            "root_path": root_path,
+            "opened_at": opened_at
+        })
+    }
+
+    fn ids(projects: &[serde_json::Value]) -> Vec<&str> {
+        projects
+            .iter()
+            .map(|p| p.get("id").and_then(|v| v.as_str()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_dedupe_recent_projects_keeps_most_recent_per_path() {
+        let projects = vec![
+            recent("old-alpha", "/tmp/alpha", 100),
+            recent("beta", "/tmp/beta", 300),
+            recent("new-alpha", "/tmp/alpha", 400),
+            recent("gamma", "/tmp/gamma", 200),
+            recent("no-path", "", 500),
+        ];
+
+        let deduped = dedupe_recent_projects(projects, DEFAULT_RECENT_PROJECTS_LIMIT);
+        assert_eq!(ids(&deduped), vec!["new-alpha", "beta", "gamma"]);
+    }
+
+    #[test]
+    fn test_dedupe_recent_projects_caps_length() {
+        let projects: Vec<serde_json::Value> = (0..15)
+            .map(|i| recent(&format!("p{}", i), &format!("/tmp/p{}", i), i))
+            .collect();
+
+        let deduped = dedupe_recent_projects(projects.clone(), DEFAULT_RECENT_PROJECTS_LIMIT);
+        assert_eq!(deduped.len(), DEFAULT_RECENT_PROJECTS_LIMIT);
+        assert_eq!(ids(&deduped)[..3], ["p14", "p13", "p12"]);
+
+        let deduped = dedupe_recent_projects(projects, 3);
+        assert_eq!(ids(&deduped), vec!["p14", "p13", "p12"]);
+        let entries = build_dock_menu_entries(&deduped);
+        let titles: Vec<&str> = entries.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["p14", "p13", "p12"]);
+    }
 }
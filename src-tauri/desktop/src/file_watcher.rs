@@ -1,6 +1,10 @@
-use crate::constants::{BINARY_EXTENSIONS, EXCLUDED_DIRS};
-use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
-use std::path::Path;
+use crate::constants::{BINARY_EXTENSIONS, DEFAULT_EXCLUDE_DIRS};
+use crate::walker::PathFilter;
+use notify::event::{ModifyKind, RenameMode};
+use notify::{Config, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::{
     atomic::{AtomicBool, Ordering},
     mpsc, Arc,
@@ -9,6 +13,221 @@ use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
 
+/// Default quiet period before a burst of changes is emitted
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// How often the event loop wakes up to check for a ready batch
+const CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Options for `FileWatcher::watch_directory`
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    /// Trailing-edge debounce window: events are held until no new event has
+    /// arrived for this long, then emitted together as one batch
+    pub debounce: Duration,
+    /// Only paths matching one of these globs emit events (all paths when empty)
+    pub include_globs: Vec<String>,
+    /// Paths matching these globs never emit events, on top of the built-in exclusions
+    pub exclude_globs: Vec<String>,
+    /// Watch subdirectories too. Recursive watching costs one OS watch per
+    /// directory on Linux (inotify, capped by `fs.inotify.max_user_watches`,
+    /// often 8192) and a full tree scan at startup; macOS FSEvents and Windows
+    /// watch a tree natively. Non-recursive mode watches only the top level,
+    /// which is enough for e.g. a flat file list of a huge directory.
+    pub recursive: bool,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            debounce: DEFAULT_DEBOUNCE,
+            include_globs: Vec::new(),
+            exclude_globs: Vec::new(),
+            recursive: true,
+        }
+    }
+}
+
+/// What happened to a path
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum FileChangeKind {
+    Created,
+    Modified,
+    Removed,
+    Renamed { from: PathBuf, to: PathBuf },
+}
+
+/// A single coalesced change; for renames `path` is the new path
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileChangeEvent {
+    pub kind: FileChangeKind,
+    pub path: PathBuf,
+}
+
+impl FileChangeEvent {
+    /// Paths touched by this change (both sides of a rename)
+    pub fn affected_paths(&self) -> Vec<&PathBuf> {
+        match self.kind {
+            FileChangeKind::Renamed { ref from, ref to } => vec![from, to],
+            _ => vec![&self.path],
+        }
+    }
+
+    /// Translate a notify event into changes. Platforms report renames
+    /// differently: inotify sends From/To halves followed by a paired Both
+    /// event, FSEvents sends unpaired Name(Any) events resolved by existence.
+    fn from_notify(event: notify::Event) -> Vec<Self> {
+        let simple = |kind: FileChangeKind, paths: Vec<PathBuf>| {
+            paths
+                .into_iter()
+                .map(|path| FileChangeEvent {
+                    kind: kind.clone(),
+                    path,
+                })
+                .collect()
+        };
+
+        match event.kind {
+            EventKind::Create(_) => simple(FileChangeKind::Created, event.paths),
+            EventKind::Remove(_) => simple(FileChangeKind::Removed, event.paths),
+            EventKind::Modify(ModifyKind::Data(_)) => simple(FileChangeKind::Modified, event.paths),
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if event.paths.len() == 2 => {
+                let mut paths = event.paths.into_iter();
+                let (from, to) = (paths.next().unwrap(), paths.next().unwrap());
+                vec![FileChangeEvent {
+                    kind: FileChangeKind::Renamed {
+                        from,
+                        to: to.clone(),
+                    },
+                    path: to,
+                }]
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                simple(FileChangeKind::Removed, event.paths)
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+                simple(FileChangeKind::Created, event.paths)
+            }
+            EventKind::Modify(ModifyKind::Name(_)) => event
+                .paths
+                .into_iter()
+                .map(|path| FileChangeEvent {
+                    kind: if path.exists() {
+                        FileChangeKind::Created
+                    } else {
+                        FileChangeKind::Removed
+                    },
+                    path,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Payload of the `file-system-changed` event. `paths` lists every affected
+/// path for listeners that only need to know what to refresh.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileSystemChangedPayload {
+    pub paths: Vec<PathBuf>,
+    pub events: Vec<FileChangeEvent>,
+}
+
+impl FileSystemChangedPayload {
+    fn new(events: Vec<FileChangeEvent>) -> Self {
+        let paths = events
+            .iter()
+            .flat_map(|event| event.affected_paths())
+            .cloned()
+            .collect();
+        Self { paths, events }
+    }
+}
+
+/// Collects changes from watcher events and releases them as a single batch
+/// once the debounce window has passed without new events. Repeated changes
+/// to the same path within a window merge into one entry.
+struct EventCoalescer {
+    debounce: Duration,
+    /// Pending change per path, with its arrival order
+    pending: HashMap<PathBuf, (u64, FileChangeKind)>,
+    next_seq: u64,
+    last_event_time: Option<Instant>,
+}
+
+impl EventCoalescer {
+    fn new(debounce: Duration) -> Self {
+        Self {
+            debounce,
+            pending: HashMap::new(),
+            next_seq: 0,
+            last_event_time: None,
+        }
+    }
+
+    fn push(&mut self, changes: impl IntoIterator<Item = FileChangeEvent>, now: Instant) {
+        for change in changes {
+            self.last_event_time = Some(now);
+            // Keep a path's first position in the batch when it changes again
+            let seq = match self.pending.get(&change.path) {
+                Some((seq, _)) => *seq,
+                None => {
+                    self.next_seq += 1;
+                    self.next_seq
+                }
+            };
+
+            let kind = match change.kind {
+                FileChangeKind::Renamed { from, to } => {
+                    // A rename supersedes the From/To halves reported before it
+                    self.pending.remove(&to);
+                    match self.pending.remove(&from) {
+                        Some((_, FileChangeKind::Created)) => FileChangeKind::Created,
+                        _ => FileChangeKind::Renamed { from, to },
+                    }
+                }
+                next => match self.pending.get(&change.path) {
+                    Some((_, previous)) => Self::merge(previous, next),
+                    None => next,
+                },
+            };
+            self.pending.insert(change.path, (seq, kind));
+        }
+    }
+
+    /// Combine two changes to the same path within one window
+    fn merge(previous: &FileChangeKind, next: FileChangeKind) -> FileChangeKind {
+        match (previous, next) {
+            (FileChangeKind::Created, FileChangeKind::Modified) => FileChangeKind::Created,
+            (FileChangeKind::Renamed { .. }, FileChangeKind::Modified) => previous.clone(),
+            // Delete-then-create, e.g. an editor's atomic save
+            (FileChangeKind::Removed, FileChangeKind::Created) => FileChangeKind::Modified,
+            (_, next) => next,
+        }
+    }
+
+    /// Take the pending batch if the debounce window has elapsed
+    fn take_ready(&mut self, now: Instant) -> Option<Vec<FileChangeEvent>> {
+        let last_event_time = self.last_event_time?;
+        if now.duration_since(last_event_time) < self.debounce {
+            return None;
+        }
+        self.last_event_time = None;
+
+        let mut pending: Vec<_> = self.pending.drain().collect();
+        pending.sort_by_key(|(_, (seq, _))| *seq);
+        Some(
+            pending
+                .into_iter()
+                .map(|(path, (_, kind))| FileChangeEvent { kind, path })
+                .collect(),
+        )
+    }
+}
+
 pub struct FileWatcher {
     _watcher: RecommendedWatcher,
     _thread_handle: Option<JoinHandle<()>>,
@@ -50,11 +269,15 @@ impl FileWatcher {
         path: P,
         app_handle: AppHandle,
         window_label: Option<String>,
+        options: WatchOptions,
     ) -> notify::Result<()> {
         // Stop any existing watcher first
         self.stop();
 
         let repo_path = path.as_ref().to_path_buf();
+        let path_filter =
+            PathFilter::new(&repo_path, &options.include_globs, &options.exclude_globs)
+                .map_err(|e| notify::Error::generic(&e))?;
 
         let (sender, receiver) = mpsc::channel();
 
@@ -69,7 +292,7 @@ impl FileWatcher {
         )?;
 
         // Start watching
-        watcher.watch(path.as_ref(), RecursiveMode::Recursive)?;
+        watcher.watch(path.as_ref(), Self::recursive_mode(&options))?;
 
         // Replace the old watcher
         self._watcher = watcher;
@@ -81,90 +304,35 @@ impl FileWatcher {
         // Clone app_handle and window_label for the file watcher thread
         let file_app_handle = app_handle.clone();
         let file_window_label = window_label.clone();
+        let root = repo_path.clone();
 
-        // Spawn thread to handle events with proper trailing-edge debounce
         let thread_handle = thread::spawn(move || {
-            let debounce_duration = Duration::from_millis(500);
-            let check_interval = Duration::from_millis(100);
-
-            // Trailing-edge debounce state
-            let mut pending_emit = false;
-            let mut last_event_time = Instant::now();
-            let mut pending_paths: Vec<std::path::PathBuf> = Vec::new();
-
-            loop {
-                // Check stop flag first
-                if stop_flag.load(Ordering::Relaxed) {
-                    log::info!("File watcher thread stopping");
-                    break;
-                }
-
-                // Use short timeout to allow checking for pending events
-                match receiver.recv_timeout(check_interval) {
-                    Ok(Ok(event)) => {
-                        // Filter events we care about
-                        match event.kind {
-                            notify::EventKind::Create(_)
-                            | notify::EventKind::Remove(_)
-                            | notify::EventKind::Modify(notify::event::ModifyKind::Name(_))
-                            | notify::EventKind::Modify(notify::event::ModifyKind::Data(_)) => {
-                                // Check if the event is for files we care about
-                                let relevant_paths: Vec<_> = event
-                                    .paths
-                                    .iter()
-                                    .filter(|path| Self::should_watch_path(path))
-                                    .cloned()
-                                    .collect();
-
-                                if !relevant_paths.is_empty() {
-                                    // Mark pending and update last event time
-                                    pending_emit = true;
-                                    last_event_time = Instant::now();
-                                    // Collect paths for logging/debugging
-                                    pending_paths.extend(relevant_paths);
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
-                    Ok(Err(e)) => {
-                        log::error!("File watcher error: {}", e);
-                    }
-                    Err(mpsc::RecvTimeoutError::Timeout) => {
-                        // Normal timeout - check if we should emit pending event
-                    }
-                    Err(mpsc::RecvTimeoutError::Disconnected) => {
-                        log::info!("File watcher channel disconnected");
-                        break;
-                    }
-                }
-
-                // Check if we should emit the pending event (trailing-edge debounce)
-                // Emit after debounce_duration has passed since the last event
-                if pending_emit {
-                    let elapsed = Instant::now().duration_since(last_event_time);
-                    if elapsed >= debounce_duration {
-                        log::debug!(
-                            "Emitting debounced file-system-changed event for {} paths to {:?}",
-                            pending_paths.len(),
-                            file_window_label
-                        );
-
-                        // Emit to specific window if label provided, otherwise broadcast
-                        let result = if let Some(ref label) = file_window_label {
-                            file_app_handle.emit_to(label, "file-system-changed", &pending_paths)
-                        } else {
-                            file_app_handle.emit("file-system-changed", &pending_paths)
-                        };
-
-                        if let Err(e) = result {
-                            log::error!("Failed to emit file system change event: {}", e);
-                        }
-                        pending_emit = false;
-                        pending_paths.clear();
+            Self::run_event_loop(
+                receiver,
+                stop_flag,
+                root,
+                options.debounce,
+                path_filter,
+                move |events| {
+                    let payload = FileSystemChangedPayload::new(events);
+                    log::debug!(
+                        "Emitting debounced file-system-changed event for {} paths to {:?}",
+                        payload.paths.len(),
+                        file_window_label
+                    );
+
+                    // Emit to specific window if label provided, otherwise broadcast
+                    let result = if let Some(ref label) = file_window_label {
+                        file_app_handle.emit_to(label, "file-system-changed", &payload)
+                    } else {
+                        file_app_handle.emit("file-system-changed", &payload)
+                    };
+
+                    if let Err(e) = result {
+                        log::error!("Failed to emit file system change event: {}", e);
                     }
-                }
-            }
+                },
+            );
         });
 
         self._thread_handle = Some(thread_handle);
@@ -175,6 +343,69 @@ impl FileWatcher {
         Ok(())
     }
 
+    fn recursive_mode(options: &WatchOptions) -> RecursiveMode {
+        if options.recursive {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        }
+    }
+
+    /// Receive watcher events, filter and coalesce them, and hand each debounced
+    /// batch to `emit`. Runs until the stop flag is set or the channel closes.
+    fn run_event_loop<F>(
+        receiver: mpsc::Receiver<notify::Result<notify::Event>>,
+        stop_flag: Arc<AtomicBool>,
+        root: PathBuf,
+        debounce: Duration,
+        path_filter: PathFilter,
+        mut emit: F,
+    ) where
+        F: FnMut(Vec<FileChangeEvent>),
+    {
+        let check_interval = CHECK_INTERVAL.min(debounce).max(Duration::from_millis(10));
+        let mut coalescer = EventCoalescer::new(debounce);
+
+        loop {
+            // Check stop flag first
+            if stop_flag.load(Ordering::Relaxed) {
+                log::info!("File watcher thread stopping");
+                break;
+            }
+
+            // Use short timeout to allow checking for pending events
+            match receiver.recv_timeout(check_interval) {
+                Ok(Ok(event)) => {
+                    // Exclusions apply below the watched root only, so a project
+                    // that itself lives under e.g. /tmp or ~/build still emits
+                    let is_relevant = |path: &PathBuf| {
+                        Self::should_watch_path(path.strip_prefix(&root).unwrap_or(path))
+                            && path_filter.is_match(path)
+                    };
+                    let changes = FileChangeEvent::from_notify(event)
+                        .into_iter()
+                        .filter(|change| change.affected_paths().into_iter().any(is_relevant));
+                    coalescer.push(changes, Instant::now());
+                }
+                Ok(Err(e)) => {
+                    log::error!("File watcher error: {}", e);
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    // Normal timeout - check if we should emit pending event
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    log::info!("File watcher channel disconnected");
+                    break;
+                }
+            }
+
+            // Emit after the debounce window has passed since the last event
+            if let Some(events) = coalescer.take_ready(Instant::now()) {
+                emit(events);
+            }
+        }
+    }
+
     /// Watch the .git directory for git status changes
     /// If window_label is provided, events will be emitted only to that specific window
     fn watch_git_directory<P: AsRef<Path>>(
@@ -332,10 +563,10 @@ impl FileWatcher {
 
     /// Check if a path should be watched (not ignored)
     fn should_watch_path(path: &Path) -> bool {
-        // Check if any component of the path is in EXCLUDED_DIRS
+        // Check if any component of the path is in DEFAULT_EXCLUDE_DIRS
         for component in path.components() {
             if let Some(name) = component.as_os_str().to_str() {
-                if EXCLUDED_DIRS.contains(&name) {
+                if DEFAULT_EXCLUDE_DIRS.contains(&name) {
                     return false;
                 }
             }
@@ -647,6 +878,358 @@ mod tests {
         assert!(FileWatcher::should_watch_path(Path::new("/repo/README.md")));
     }
 
+    fn change(kind: FileChangeKind, path: &str) -> FileChangeEvent {
+        FileChangeEvent {
+            kind,
+            path: PathBuf::from(path),
+        }
+    }
+
+    #[test]
+    fn test_coalescer_merges_burst_for_same_path() {
+        let debounce = Duration::from_millis(200);
+        let mut coalescer = EventCoalescer::new(debounce);
+        let start = Instant::now();
+
+        for i in 0..50 {
+            coalescer.push(
+                [change(FileChangeKind::Modified, "/repo/src/main.rs")],
+                start + Duration::from_millis(i * 10),
+            );
+        }
+        let last = start + Duration::from_millis(490);
+
+        assert!(coalescer
+            .take_ready(last + Duration::from_millis(100))
+            .is_none());
+        assert_eq!(
+            coalescer.take_ready(last + debounce),
+            Some(vec![change(FileChangeKind::Modified, "/repo/src/main.rs")])
+        );
+        assert!(coalescer.take_ready(last + debounce * 2).is_none());
+    }
+
+    #[test]
+    fn test_coalescer_batches_distinct_paths_in_order() {
+        let mut coalescer = EventCoalescer::new(Duration::from_millis(100));
+        let now = Instant::now();
+        coalescer.push(
+            [
+                change(FileChangeKind::Modified, "/repo/b.rs"),
+                change(FileChangeKind::Created, "/repo/a.rs"),
+            ],
+            now,
+        );
+        coalescer.push(
+            [
+                change(FileChangeKind::Modified, "/repo/b.rs"),
+                change(FileChangeKind::Removed, "/repo/c.rs"),
+            ],
+            now,
+        );
+
+        let paths: Vec<PathBuf> = coalescer
+            .take_ready(now + Duration::from_millis(100))
+            .unwrap()
+            .into_iter()
+            .map(|event| event.path)
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/repo/b.rs"),
+                PathBuf::from("/repo/a.rs"),
+                PathBuf::from("/repo/c.rs"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coalescer_merges_kinds() {
+        let mut coalescer = EventCoalescer::new(Duration::ZERO);
+        let now = Instant::now();
+        coalescer.push(
+            [
+                change(FileChangeKind::Created, "/repo/new.rs"),
+                change(FileChangeKind::Modified, "/repo/new.rs"),
+                change(FileChangeKind::Removed, "/repo/saved.rs"),
+                change(FileChangeKind::Created, "/repo/saved.rs"),
+            ],
+            now,
+        );
+
+        assert_eq!(
+            coalescer.take_ready(now),
+            Some(vec![
+                change(FileChangeKind::Created, "/repo/new.rs"),
+                change(FileChangeKind::Modified, "/repo/saved.rs"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_coalescer_rename_replaces_halves() {
+        let mut coalescer = EventCoalescer::new(Duration::ZERO);
+        let now = Instant::now();
+        let renamed = FileChangeKind::Renamed {
+            from: PathBuf::from("/repo/old.rs"),
+            to: PathBuf::from("/repo/new.rs"),
+        };
+        // inotify order: From, To, then the paired event
+        coalescer.push(
+            [
+                change(FileChangeKind::Removed, "/repo/old.rs"),
+                change(FileChangeKind::Created, "/repo/new.rs"),
+                change(renamed.clone(), "/repo/new.rs"),
+            ],
+            now,
+        );
+
+        assert_eq!(
+            coalescer.take_ready(now),
+            Some(vec![change(renamed, "/repo/new.rs")])
+        );
+    }
+
+    #[test]
+    fn test_payload_lists_both_rename_paths() {
+        let payload = FileSystemChangedPayload::new(vec![
+            change(FileChangeKind::Modified, "/repo/a.rs"),
+            change(
+                FileChangeKind::Renamed {
+                    from: PathBuf::from("/repo/old.rs"),
+                    to: PathBuf::from("/repo/new.rs"),
+                },
+                "/repo/new.rs",
+            ),
+        ]);
+        assert_eq!(
+            payload.paths,
+            vec![
+                PathBuf::from("/repo/a.rs"),
+                PathBuf::from("/repo/old.rs"),
+                PathBuf::from("/repo/new.rs"),
+            ]
+        );
+
+        let json = serde_json::to_value(&payload).unwrap();
+        assert_eq!(json["events"][0]["kind"]["type"], "modified");
+        assert_eq!(json["events"][1]["kind"]["type"], "renamed");
+        assert_eq!(json["events"][1]["kind"]["from"], "/repo/old.rs");
+    }
+
+    /// Run the event loop on a real notify watcher, collecting emitted batches
+    fn spawn_test_loop(
+        root: &Path,
+        options: WatchOptions,
+    ) -> (
+        RecommendedWatcher,
+        Arc<AtomicBool>,
+        mpsc::Receiver<Vec<FileChangeEvent>>,
+        JoinHandle<()>,
+    ) {
+        let (event_sender, event_receiver) = mpsc::channel();
+        let mut watcher = RecommendedWatcher::new(
+            move |result| {
+                let _ = event_sender.send(result);
+            },
+            Config::default(),
+        )
+        .unwrap();
+        watcher
+            .watch(root, FileWatcher::recursive_mode(&options))
+            .unwrap();
+
+        let (batch_sender, batch_receiver) = mpsc::channel();
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let loop_stop_flag = Arc::clone(&stop_flag);
+        let path_filter =
+            PathFilter::new(root, &options.include_globs, &options.exclude_globs).unwrap();
+        let root = root.to_path_buf();
+        let handle = thread::spawn(move || {
+            FileWatcher::run_event_loop(
+                event_receiver,
+                loop_stop_flag,
+                root,
+                options.debounce,
+                path_filter,
+                |events| {
+                    let _ = batch_sender.send(events);
+                },
+            );
+        });
+
+        (watcher, stop_flag, batch_receiver, handle)
+    }
+
+    #[test]
+    fn test_event_loop_coalesces_burst_of_writes() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let file = root.join("output.txt");
+        let options = WatchOptions {
+            debounce: Duration::from_millis(300),
+            ..Default::default()
+        };
+        let (watcher, stop_flag, batches, handle) = spawn_test_loop(root, options);
+
+        for i in 0..20 {
+            std::fs::write(&file, format!("write {}", i)).unwrap();
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        let batch = batches.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(
+            batch,
+            vec![FileChangeEvent {
+                kind: FileChangeKind::Created,
+                path: file,
+            }]
+        );
+        assert!(batches.recv_timeout(Duration::from_millis(600)).is_err());
+
+        stop_flag.store(true, Ordering::Relaxed);
+        drop(watcher);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_event_loop_applies_exclude_globs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("generated/api")).unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        let options = WatchOptions {
+            debounce: Duration::from_millis(200),
+            exclude_globs: vec!["generated/".to_string()],
+            ..Default::default()
+        };
+        let (watcher, stop_flag, batches, handle) = spawn_test_loop(root, options);
+
+        std::fs::write(root.join("generated/api/client.ts"), "export {}").unwrap();
+        assert!(batches.recv_timeout(Duration::from_millis(800)).is_err());
+
+        std::fs::write(root.join("src/app.ts"), "export {}").unwrap();
+        let batch = batches.recv_timeout(Duration::from_secs(5)).unwrap();
+        let paths: Vec<_> = batch.into_iter().map(|event| event.path).collect();
+        assert_eq!(paths, vec![root.join("src/app.ts")]);
+
+        stop_flag.store(true, Ordering::Relaxed);
+        drop(watcher);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_event_loop_applies_include_globs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("docs")).unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        let options = WatchOptions {
+            debounce: Duration::from_millis(200),
+            include_globs: vec!["src/**".to_string()],
+            ..Default::default()
+        };
+        let (watcher, stop_flag, batches, handle) = spawn_test_loop(root, options);
+
+        std::fs::write(root.join("docs/guide.md"), "# Guide").unwrap();
+        std::fs::write(root.join("src/lib.rs"), "pub fn f() {}").unwrap();
+
+        let batch = batches.recv_timeout(Duration::from_secs(5)).unwrap();
+        let paths: Vec<_> = batch.into_iter().map(|event| event.path).collect();
+        assert_eq!(paths, vec![root.join("src/lib.rs")]);
+
+        stop_flag.store(true, Ordering::Relaxed);
+        drop(watcher);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_event_loop_reports_change_kinds() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let file = root.join("notes.md");
+        let options = WatchOptions {
+            debounce: Duration::from_millis(200),
+            ..Default::default()
+        };
+        let (watcher, stop_flag, batches, handle) = spawn_test_loop(root, options);
+        let next_kind = || {
+            let batch = batches.recv_timeout(Duration::from_secs(5)).unwrap();
+            assert_eq!(batch.len(), 1, "{:?}", batch);
+            assert_eq!(batch[0].path, file);
+            batch[0].kind.clone()
+        };
+
+        std::fs::write(&file, "one").unwrap();
+        assert_eq!(next_kind(), FileChangeKind::Created);
+
+        std::fs::write(&file, "two").unwrap();
+        assert_eq!(next_kind(), FileChangeKind::Modified);
+
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(next_kind(), FileChangeKind::Removed);
+
+        stop_flag.store(true, Ordering::Relaxed);
+        drop(watcher);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_event_loop_reports_rename() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("old.md"), "x").unwrap();
+        let options = WatchOptions {
+            debounce: Duration::from_millis(200),
+            ..Default::default()
+        };
+        let (watcher, stop_flag, batches, handle) = spawn_test_loop(root, options);
+
+        std::fs::rename(root.join("old.md"), root.join("new.md")).unwrap();
+        let batch = batches.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(
+            batch,
+            vec![FileChangeEvent {
+                kind: FileChangeKind::Renamed {
+                    from: root.join("old.md"),
+                    to: root.join("new.md"),
+                },
+                path: root.join("new.md"),
+            }]
+        );
+
+        stop_flag.store(true, Ordering::Relaxed);
+        drop(watcher);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_event_loop_non_recursive_ignores_subdirectories() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("nested")).unwrap();
+        let options = WatchOptions {
+            debounce: Duration::from_millis(200),
+            recursive: false,
+            ..Default::default()
+        };
+        let (watcher, stop_flag, batches, handle) = spawn_test_loop(root, options);
+
+        std::fs::write(root.join("nested/inner.txt"), "inner").unwrap();
+        assert!(batches.recv_timeout(Duration::from_millis(800)).is_err());
+
+        std::fs::write(root.join("top.txt"), "top").unwrap();
+        let batch = batches.recv_timeout(Duration::from_secs(5)).unwrap();
+        let paths: Vec<_> = batch.into_iter().map(|event| event.path).collect();
+        assert_eq!(paths, vec![root.join("top.txt")]);
+
+        stop_flag.store(true, Ordering::Relaxed);
+        drop(watcher);
+        handle.join().unwrap();
+    }
+
     // Test for trailing-edge debounce behavior simulation
     #[test]
     fn test_trailing_edge_debounce_logic() {
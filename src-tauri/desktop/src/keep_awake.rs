@@ -4,9 +4,11 @@
 // - Multiple tasks can request sleep prevention
 // - Sleep is prevented while any task is active
 // - Sleep is allowed when all tasks complete (refcount reaches 0)
+// - Task-keyed guards release their reference automatically when dropped
 
+use std::collections::HashMap;
 use std::process::{Child, Command, Stdio};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use tauri::State;
 
 #[cfg(target_os = "linux")]
@@ -19,14 +21,63 @@ use windows_sys::Win32::System::Power::{
 
 /// State wrapper for keep awake functionality
 pub struct KeepAwakeStateWrapper {
-    state: KeepAwakeState,
+    state: Arc<KeepAwakeState>,
+    /// Guards held on behalf of running tasks, keyed by task id
+    task_guards: Mutex<HashMap<String, KeepAwakeGuard>>,
 }
 
 impl KeepAwakeStateWrapper {
     pub fn new() -> Self {
+        Self::with_state(KeepAwakeState::new())
+    }
+
+    fn with_state(state: KeepAwakeState) -> Self {
         Self {
-            state: KeepAwakeState::new(),
+            state: Arc::new(state),
+            task_guards: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Acquire a keep-awake reference tied to a task.
+    /// Returns false if the task already holds one.
+    pub fn acquire_for_task(&self, task_id: &str) -> Result<bool, String> {
+        let mut guards = self
+            .task_guards
+            .lock()
+            .expect("KeepAwake guards lock poisoned");
+        if guards.contains_key(task_id) {
+            return Ok(false);
         }
+        let guard = KeepAwakeGuard::acquire(&self.state)?;
+        guards.insert(task_id.to_string(), guard);
+        log::info!("KeepAwake: acquired for task {}", task_id);
+        Ok(true)
+    }
+
+    /// Release the task's reference when it completes, errors, or is killed.
+    /// Returns false if the task held none.
+    pub fn release_for_task(&self, task_id: &str) -> bool {
+        let guard = self
+            .task_guards
+            .lock()
+            .expect("KeepAwake guards lock poisoned")
+            .remove(task_id);
+        // Dropping the guard releases the reference
+        let released = guard.is_some();
+        if released {
+            log::info!("KeepAwake: released for task {}", task_id);
+        }
+        released
+    }
+
+    /// Task ids currently holding a keep-awake reference
+    pub fn active_tasks(&self) -> Vec<String> {
+        self.task_guards
+            .lock()
+            .expect("KeepAwake guards lock poisoned")
+            .keys()
+            .cloned()
+            .collect()
     }
 }
 
@@ -259,6 +310,29 @@ enum KeepAwakeProcess {
     Child(Child),
 }
 
+/// RAII keep-awake reference; released when dropped, so a task that ends in any
+/// way (completion, error, kill, panic) cannot leave the machine awake.
+pub struct KeepAwakeGuard {
+    state: Arc<KeepAwakeState>,
+}
+
+impl KeepAwakeGuard {
+    pub fn acquire(state: &Arc<KeepAwakeState>) -> Result<Self, String> {
+        state.acquire()?;
+        Ok(Self {
+            state: Arc::clone(state),
+        })
+    }
+}
+
+impl Drop for KeepAwakeGuard {
+    fn drop(&mut self) {
+        if let Err(err) = self.state.release() {
+            log::warn!("KeepAwake: failed to release guard: {}", err);
+        }
+    }
+}
+
 /// Tauri command to acquire sleep prevention
 ///
 /// This command is called when a task starts and needs to prevent system sleep.
@@ -281,6 +355,24 @@ pub fn keep_awake_release(state: State<KeepAwakeStateWrapper>) -> Result<bool, S
     state.state.release()
 }
 
+/// Acquire sleep prevention for a task; released by `keep_awake_release_for_task`
+#[tauri::command]
+pub fn keep_awake_acquire_for_task(
+    state: State<KeepAwakeStateWrapper>,
+    task_id: String,
+) -> Result<bool, String> {
+    state.acquire_for_task(&task_id)
+}
+
+/// Release a task's sleep prevention when it completes, errors, or is killed
+#[tauri::command]
+pub fn keep_awake_release_for_task(
+    state: State<KeepAwakeStateWrapper>,
+    task_id: String,
+) -> Result<bool, String> {
+    Ok(state.release_for_task(&task_id))
+}
+
 /// Get current reference count (for debugging)
 #[tauri::command]
 pub fn keep_awake_get_ref_count(state: State<KeepAwakeStateWrapper>) -> Result<u32, String> {
@@ -362,4 +454,55 @@ mod tests {
         assert!(counts.iter().all(|&c| c >= 1));
         assert_eq!(state.ref_count(), 10);
     }
+
+    #[test]
+    fn test_guard_drop_decrements_ref_count() {
+        let state = Arc::new(KeepAwakeState::new_for_tests());
+        let first = KeepAwakeGuard::acquire(&state).unwrap();
+        let second = KeepAwakeGuard::acquire(&state).unwrap();
+        assert_eq!(state.ref_count(), 2);
+
+        drop(first);
+        assert_eq!(state.ref_count(), 1);
+        assert!(state.is_preventing_sleep());
+
+        drop(second);
+        assert_eq!(state.ref_count(), 0);
+        assert!(!state.is_preventing_sleep());
+    }
+
+    #[test]
+    fn test_task_guards_release_on_completion() {
+        let wrapper = KeepAwakeStateWrapper::with_state(KeepAwakeState::new_for_tests());
+        assert!(wrapper.acquire_for_task("task-1").unwrap());
+        assert!(wrapper.acquire_for_task("task-2").unwrap());
+        // Re-acquiring for the same task doesn't leak a reference
+        assert!(!wrapper.acquire_for_task("task-1").unwrap());
+        assert_eq!(wrapper.state.ref_count(), 2);
+
+        // Task completes
+        assert!(wrapper.release_for_task("task-1"));
+        assert_eq!(wrapper.state.ref_count(), 1);
+        assert!(!wrapper.release_for_task("task-1"));
+
+        // Task killed
+        assert!(wrapper.release_for_task("task-2"));
+        assert_eq!(wrapper.state.ref_count(), 0);
+        assert!(!wrapper.state.is_preventing_sleep());
+        assert!(wrapper.active_tasks().is_empty());
+    }
+
+    #[test]
+    fn test_guard_released_when_task_panics() {
+        let state = Arc::new(KeepAwakeState::new_for_tests());
+        let task_state = Arc::clone(&state);
+        let result = std::thread::spawn(move || {
+            let _guard = KeepAwakeGuard::acquire(&task_state).unwrap();
+            panic!("task crashed");
+        })
+        .join();
+
+        assert!(result.is_err());
+        assert_eq!(state.ref_count(), 0);
+    }
 }
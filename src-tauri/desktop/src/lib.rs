@@ -4,6 +4,7 @@ pub mod dock_menu;
 pub mod file_watcher;
 pub mod keep_awake;
 pub mod scheduled_tasks;
+pub mod tray_menu;
 pub mod window_manager;
 
 pub mod llm_commands;
@@ -16,14 +17,17 @@ pub use talkcody_core::core;
 pub use talkcody_core::database;
 pub use talkcody_core::device_id;
 pub use talkcody_core::directory_tree;
+pub use talkcody_core::discord_gateway;
 pub use talkcody_core::feishu_gateway;
 pub use talkcody_core::file_search;
+pub use talkcody_core::files;
 pub use talkcody_core::git;
 pub use talkcody_core::glob;
 pub use talkcody_core::http_proxy;
 pub use talkcody_core::integrations;
 pub use talkcody_core::list_files;
 pub use talkcody_core::llm;
+pub use talkcody_core::network_proxy;
 pub use talkcody_core::oauth_callback_server;
 pub use talkcody_core::platform;
 pub use talkcody_core::scheduler;
@@ -42,7 +46,9 @@ use analytics::AnalyticsState;
 use code_navigation::{CodeNavState, CodeNavigationService};
 use database::Database;
 use file_watcher::FileWatcher;
+use llm::token_estimation::{self, MessagesTokenEstimate, TokenEstimate};
 use llm::tracing::writer::TraceWriter;
+use llm::types::{Message, ToolDefinition};
 use scheduler::SchedulerService;
 use script_executor::{ScriptExecutionRequest, ScriptExecutionResult, ScriptExecutor};
 use serde::{Deserialize, Serialize};
@@ -126,9 +132,29 @@ fn should_exit_app<R: Runtime>(app_handle: &AppHandle<R>) -> bool {
     app_handle.webview_windows().len() <= 1
 }
 
+fn watch_options(
+    debounce_ms: Option<u64>,
+    include_globs: Option<Vec<String>>,
+    exclude_globs: Option<Vec<String>>,
+    recursive: Option<bool>,
+) -> file_watcher::WatchOptions {
+    let mut options = file_watcher::WatchOptions::default();
+    if let Some(ms) = debounce_ms {
+        options.debounce = Duration::from_millis(ms);
+    }
+    options.include_globs = include_globs.unwrap_or_default();
+    options.exclude_globs = exclude_globs.unwrap_or_default();
+    options.recursive = recursive.unwrap_or(true);
+    options
+}
+
 #[tauri::command]
 fn start_file_watching(
     path: String,
+    debounce_ms: Option<u64>,
+    include_globs: Option<Vec<String>>,
+    exclude_globs: Option<Vec<String>>,
+    recursive: Option<bool>,
     app_handle: AppHandle,
     state: State<AppState>,
 ) -> Result<(), String> {
@@ -145,7 +171,12 @@ fn start_file_watching(
 
     let mut watcher = FileWatcher::new().map_err(|e| e.to_string())?;
     watcher
-        .watch_directory(&path, app_handle, None)
+        .watch_directory(
+            &path,
+            app_handle,
+            None,
+            watch_options(debounce_ms, include_globs, exclude_globs, recursive),
+        )
         .map_err(|e| e.to_string())?;
 
     *watcher_guard = Some(watcher);
@@ -172,6 +203,7 @@ fn search_file_content(
     root_path: String,
     file_types: Option<Vec<String>>,
     exclude_dirs: Option<Vec<String>>,
+    include_ignored: Option<bool>,
 ) -> Result<Vec<search::SearchResult>, String> {
     let start_time = Instant::now();
     log::info!(
@@ -184,7 +216,8 @@ fn search_file_content(
         .with_max_results(50)
         .with_max_matches_per_file(10)
         .with_file_types(file_types)
-        .with_exclude_dirs(exclude_dirs);
+        .with_exclude_dirs(exclude_dirs)
+        .with_include_ignored(include_ignored.unwrap_or(false));
 
     let result = searcher.search_content(&query, &root_path).map_err(|e| {
         log::error!("Search error: {}", e);
@@ -205,11 +238,18 @@ fn search_file_content(
     result
 }
 
+#[tauri::command]
+fn get_search_defaults() -> search::SearchDefaults {
+    search::search_defaults()
+}
+
 #[tauri::command]
 fn search_files_fast(
     query: String,
     root_path: String,
     max_results: Option<usize>,
+    exclude_dirs: Option<Vec<String>>,
+    include_ignored: Option<bool>,
 ) -> Result<Vec<file_search::FileSearchResult>, String> {
     let start_time = Instant::now();
     log::info!(
@@ -218,8 +258,10 @@ fn search_files_fast(
         root_path
     );
 
-    let searcher =
-        file_search::HighPerformanceFileSearch::new().with_max_results(max_results.unwrap_or(200));
+    let searcher = file_search::HighPerformanceFileSearch::new()
+        .with_max_results(max_results.unwrap_or(200))
+        .with_exclude_dirs(exclude_dirs)
+        .with_include_ignored(include_ignored.unwrap_or(false));
 
     let result = searcher.search_files(&root_path, &query).map_err(|e| {
         log::error!("File search error: {}", e);
@@ -240,6 +282,45 @@ fn search_files_fast(
     result
 }
 
+#[tauri::command]
+fn search_unified(
+    query: String,
+    root_path: String,
+    max_results: Option<usize>,
+    exclude_dirs: Option<Vec<String>>,
+    include_ignored: Option<bool>,
+) -> Result<Vec<file_search::UnifiedSearchResult>, String> {
+    let start_time = Instant::now();
+    log::info!(
+        "Starting unified search for query: '{}' in path: {}",
+        query,
+        root_path
+    );
+
+    let searcher = file_search::HighPerformanceFileSearch::new()
+        .with_max_results(max_results.unwrap_or(50))
+        .with_exclude_dirs(exclude_dirs)
+        .with_include_ignored(include_ignored.unwrap_or(false));
+
+    let result = searcher.search_unified(&root_path, &query).map_err(|e| {
+        log::error!("Unified search error: {}", e);
+        format!("Unified search failed: {}", e)
+    });
+
+    let duration = start_time.elapsed();
+    if let Ok(ref results) = result {
+        log::info!(
+            "Unified search completed successfully with {} results in {}ms",
+            results.len(),
+            duration.as_millis()
+        );
+    } else {
+        log::error!("Unified search failed after {}ms", duration.as_millis());
+    }
+
+    result
+}
+
 #[tauri::command]
 fn create_project_window(
     app_handle: AppHandle,
@@ -255,6 +336,7 @@ fn create_project_window(
         project_id,
         root_path,
         is_new_window,
+        None,
     );
     if result.is_ok() {
         // Refresh dock menu to show the updated recent projects list
@@ -266,6 +348,52 @@ fn create_project_window(
     result
 }
 
+/// Reopen the project windows from the last session unless restore is disabled,
+/// then start recording the session. Called once by the main window on startup;
+/// tracking starts here so nothing overwrites the session file before it is read.
+#[tauri::command]
+async fn restore_windows(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    storage: State<'_, Storage>,
+) -> Result<Vec<String>, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let session_file = app_data_dir.join(window_manager::SESSION_FILE_NAME);
+    if let Err(e) = window_manager::migrate_legacy_session(
+        &app_data_dir.join(window_manager::LEGACY_STATE_FILE_NAME),
+        &session_file,
+    ) {
+        log::warn!("Failed to migrate legacy window state: {}", e);
+    }
+
+    let setting = storage
+        .settings
+        .get_setting(window_manager::RESTORE_WINDOWS_SETTING)
+        .await?;
+    let labels = if window_manager::restore_enabled(setting) {
+        window_manager::restore_windows(&app_handle, &state.window_registry, &session_file)?
+    } else {
+        log::info!("Window restore is disabled, skipping");
+        Vec::new()
+    };
+
+    state.window_registry.set_session_file(session_file)?;
+    state.window_registry.save_session()?;
+    Ok(labels)
+}
+
+#[tauri::command]
+fn reopen_last_closed_window(
+    app_handle: AppHandle,
+    state: State<AppState>,
+) -> Result<Option<String>, String> {
+    log::info!("Reopening last closed window");
+    window_manager::reopen_last_closed_window(&app_handle, &state.window_registry)
+}
+
 #[tauri::command]
 async fn refresh_dock_menu() {
     dock_menu::refresh_dock_menu().await;
@@ -352,7 +480,7 @@ fn close_project_window(
     label: String,
 ) -> Result<(), String> {
     log::info!("Closing window: {}", label);
-    state.window_registry.unregister_window(&label)?;
+    state.window_registry.forget_window(&label)?;
     if let Some(window) = app_handle.get_webview_window(&label) {
         window.close().map_err(|e| e.to_string())?;
     }
@@ -381,6 +509,10 @@ fn update_window_project(
 fn start_window_file_watching(
     window_label: String,
     path: String,
+    debounce_ms: Option<u64>,
+    include_globs: Option<Vec<String>>,
+    exclude_globs: Option<Vec<String>>,
+    recursive: Option<bool>,
     app_handle: AppHandle,
     state: State<AppState>,
 ) -> Result<(), String> {
@@ -391,7 +523,12 @@ fn start_window_file_watching(
     );
     let mut watcher = FileWatcher::new().map_err(|e| e.to_string())?;
     watcher
-        .watch_directory(&path, app_handle, Some(window_label.clone()))
+        .watch_directory(
+            &path,
+            app_handle,
+            Some(window_label.clone()),
+            watch_options(debounce_ms, include_globs, exclude_globs, recursive),
+        )
         .map_err(|e| e.to_string())?;
     state
         .window_registry
@@ -713,28 +850,24 @@ async fn execute_skill_script(
     ScriptExecutor::execute(request).await
 }
 
+/// Estimates tokens for `text`; a recognized OpenAI `model` enables the BPE approximation
 #[tauri::command]
-fn estimate_tokens(text: String) -> usize {
-    let mut cjk_count = 0;
-    let mut other_count = 0;
-    for c in text.chars() {
-        if is_cjk_char(c) {
-            cjk_count += 1;
-        } else {
-            other_count += 1;
-        }
-    }
-    let other_tokens = if other_count > 0 {
-        (other_count / 4).max(1)
-    } else {
-        0
-    };
-    (cjk_count + other_tokens).max(1)
+fn estimate_tokens(text: String, model: Option<String>) -> TokenEstimate {
+    token_estimation::estimate_tokens(&text, model.as_deref())
 }
 
-#[inline]
-fn is_cjk_char(c: char) -> bool {
-    matches!(c, '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' | '\u{F900}'..='\u{FAFF}' | '\u{3040}'..='\u{309F}' | '\u{30A0}'..='\u{30FF}' | '\u{AC00}'..='\u{D7AF}')
+/// Estimates a whole conversation's tokens, broken down by role and tool definitions
+#[tauri::command]
+fn estimate_messages_tokens(
+    messages: Vec<Message>,
+    tools: Option<Vec<ToolDefinition>>,
+    model: Option<String>,
+) -> MessagesTokenEstimate {
+    token_estimation::estimate_messages_tokens(
+        &messages,
+        tools.as_deref().unwrap_or_default(),
+        model.as_deref(),
+    )
 }
 
 fn cleanup_old_logs(log_dir: &std::path::Path, days_to_keep: u64) {
@@ -780,6 +913,7 @@ pub fn run() {
         .manage(AnalyticsState::new())
         .manage(telegram_gateway::default_state())
         .manage(feishu_gateway::default_state())
+        .manage(discord_gateway::default_state())
         .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_single_instance::init(|app, argv, cwd| {
             if let Err(e) = app.emit("single-instance", Payload { args: argv, cwd }) {
@@ -810,6 +944,9 @@ pub fn run() {
 
             app.manage(storage.clone());
 
+            // Apply the saved proxy before any HTTP client is built
+            tauri::async_runtime::block_on(network_proxy::load_proxy_setting(&storage.settings));
+
             // Get database reference for other components
             let database = storage.chat_history.get_db();
             app.manage(database.clone());
@@ -823,6 +960,12 @@ pub fn run() {
             tauri::async_runtime::spawn(async move {
                 match ServerStateFactory::create(server_config_clone, event_tx).await {
                     Ok(server_state) => {
+                        let webhook_state = integrations::webhook::WebhookState {
+                            runtime: server_state.runtime.clone(),
+                            storage: server_state.storage.clone(),
+                            events: server_state.event_broadcast.clone(),
+                        };
+
                         // Save server state so Storage is not dropped
                         server_handle.manage(server_state);
 
@@ -833,6 +976,11 @@ pub fn run() {
                                 let addr = listener.local_addr().unwrap_or(bind_addr);
                                 log::info!("Cloud backend server started on {}", addr);
                                 server_handle.manage(ServerInfo { addr });
+                                if let Err(e) =
+                                    integrations::webhook::serve(listener, webhook_state).await
+                                {
+                                    log::error!("{}", e);
+                                }
                             }
                             Err(e) => {
                                 log::error!("Failed to bind server: {}", e);
@@ -848,6 +996,14 @@ pub fn run() {
             // Initialize LLM tracing
             init_trace_writer_state(app, database.clone());
 
+            // Apply the opt-in trace retention settings; runs on a schedule, not at shutdown
+            let pruning_db = database.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = llm::tracing::retention::start_background_pruning(pruning_db).await {
+                    log::error!("Failed to schedule trace pruning: {}", e);
+                }
+            });
+
             let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
             let llm_state = llm::auth::api_key_manager::LlmState::new(
                 database.clone(),
@@ -858,6 +1014,7 @@ pub fn run() {
 
             let model_sync_handle = app.handle().clone();
             let model_sync_data_dir = app_data_dir.clone();
+            let usage_history_db = database.clone();
             tauri::async_runtime::spawn(async move {
                 if let Some(state) = model_sync_handle
                     .try_state::<llm::auth::api_key_manager::LlmState>()
@@ -868,9 +1025,33 @@ pub fn run() {
                     };
                     llm::models::model_sync::start_background_sync(
                         model_sync_handle.clone(),
-                        api_keys,
+                        api_keys.clone(),
                         model_sync_data_dir,
                     );
+                    if let Err(e) = llm::auth::openai_usage_history::start_background_polling(
+                        usage_history_db,
+                        api_keys.clone(),
+                    )
+                    .await
+                    {
+                        log::error!("Failed to schedule OpenAI usage polling: {}", e);
+                    }
+
+                    // Refresh OAuth tokens shortly before they expire
+                    let refresh_failed_handle = model_sync_handle.clone();
+                    let token_refresh = llm::auth::token_refresh::TokenRefreshScheduler::new(
+                        api_keys,
+                        llm::auth::token_refresh::refresh_provider,
+                        move |payload| {
+                            let _ = refresh_failed_handle.emit(
+                                llm::auth::token_refresh::OAUTH_REFRESH_FAILED_EVENT,
+                                payload,
+                            );
+                        },
+                    );
+                    if let Err(e) = token_refresh.start().await {
+                        log::error!("Failed to schedule OAuth token refresh: {}", e);
+                    }
                 }
             });
 
@@ -918,6 +1099,37 @@ pub fn run() {
                 }
             });
 
+            // Gateways share one lifecycle surface through the integration registry
+            let integrations = integrations::IntegrationRegistry::new();
+            let builtin_integrations: [Arc<dyn integrations::Integration>; 3] = [
+                Arc::new(telegram_gateway::TelegramIntegration::new(
+                    app.handle().clone(),
+                    app.state::<telegram_gateway::TelegramGatewayState>()
+                        .inner()
+                        .clone(),
+                )),
+                Arc::new(feishu_gateway::FeishuIntegration::new(
+                    app.handle().clone(),
+                    app.state::<feishu_gateway::FeishuGatewayState>()
+                        .inner()
+                        .clone(),
+                )),
+                Arc::new(discord_gateway::DiscordIntegration::new(
+                    app.handle().clone(),
+                    app.state::<discord_gateway::DiscordGatewayState>()
+                        .inner()
+                        .clone(),
+                )),
+            ];
+            tauri::async_runtime::block_on(async {
+                for integration in builtin_integrations {
+                    if let Err(e) = integrations.register(integration).await {
+                        log::warn!("Failed to register integration: {}", e);
+                    }
+                }
+            });
+            app.manage(integrations);
+
             let ws_state = Arc::new(TokioMutex::new(WebSocketState::new()));
             app.manage(ws_state);
             let code_nav_state = CodeNavState(RwLock::new(CodeNavigationService::new()));
@@ -927,7 +1139,9 @@ pub fn run() {
             let app_data_dir_clone = app_data_dir.clone();
             if let Some(analytics_state) = app.try_state::<AnalyticsState>() {
                 let state = analytics_state.inner().clone();
+                let analytics_storage = storage.clone();
                 tauri::async_runtime::spawn(async move {
+                    analytics::load_enabled_setting(&state, &analytics_storage.settings).await;
                     analytics::start_session(&state, &app_data_dir_clone, &app_version).await;
                 });
             }
@@ -943,17 +1157,15 @@ pub fn run() {
                     project_id: None,
                     root_path: None,
                     file_watcher: None,
+                    geometry: None,
                 };
                 let _ = app_state
                     .window_registry
                     .register_window("main".to_string(), state);
             }
 
-            // Initialize dock menu on macOS
-            #[cfg(target_os = "macos")]
-            {
-                dock_menu::setup_dock_menu();
-            }
+            // Initialize dock menu on macOS, tray icon menu elsewhere
+            dock_menu::setup_dock_menu();
 
             // Start scheduled task scheduler
             let scheduler_db = Arc::clone(&database);
@@ -1006,6 +1218,8 @@ pub fn run() {
             stop_file_watching,
             search_file_content,
             search_files_fast,
+            search_unified,
+            get_search_defaults,
             list_files::list_project_files,
             directory_tree::build_directory_tree,
             directory_tree::load_directory_children,
@@ -1014,6 +1228,8 @@ pub fn run() {
             glob::search_files_by_glob,
             create_project_window,
             get_all_project_windows,
+            restore_windows,
+            reopen_last_closed_window,
             get_current_window_label,
             get_window_info,
             check_project_window_exists,
@@ -1030,12 +1246,30 @@ pub fn run() {
             database::db_batch,
             http_proxy::proxy_fetch,
             http_proxy::stream_fetch,
+            http_proxy::proxy_download_to_file,
+            network_proxy::get_proxy_config,
+            network_proxy::set_proxy_config,
             git::git_get_status,
             git::git_is_repository,
             git::git_get_all_file_statuses,
             git::git_get_line_changes,
             git::git_get_all_file_diffs,
             git::git_get_raw_diff_text,
+            git::git_log,
+            git::git_show,
+            git::git_checkout_file,
+            git::git_stage_files,
+            git::git_unstage_files,
+            git::git_commit,
+            git::git_list_branches,
+            git::git_create_branch,
+            git::git_switch_branch,
+            git::git_delete_branch,
+            git::git_fetch,
+            git::git_pull,
+            git::git_push,
+            git::git_get_conflict_hunks,
+            git::git_get_word_diff,
             git::git_get_default_worktree_root,
             git::git_acquire_worktree,
             git::git_release_worktree,
@@ -1058,6 +1292,11 @@ pub fn run() {
             terminal::pty_write,
             terminal::pty_resize,
             terminal::pty_kill,
+            terminal::pty_list_sessions,
+            terminal::pty_reattach,
+            terminal::pty_get_scrollback,
+            terminal::pty_export_log,
+            terminal::pty_search_scrollback,
             code_navigation::code_nav_index_file,
             code_navigation::code_nav_index_files_batch,
             code_navigation::code_nav_find_definition,
@@ -1071,7 +1310,11 @@ pub fn run() {
             code_navigation::code_nav_get_indexed_files,
             code_navigation::summarize_code_content,
             estimate_tokens,
+            estimate_messages_tokens,
             background_tasks::spawn_background_task,
+            background_tasks::schedule_background_task,
+            background_tasks::set_background_task_concurrency,
+            background_tasks::report_background_task_progress,
             background_tasks::get_background_task_status,
             background_tasks::get_background_task_output,
             background_tasks::kill_background_task,
@@ -1086,15 +1329,21 @@ pub fn run() {
             llm_commands::llm_get_provider_configs,
             llm_commands::llm_get_models_config,
             llm_commands::llm_is_model_available,
+            llm::providers::provider_health::llm_check_providers_health,
             llm_commands::llm_transcribe_audio,
             llm_commands::llm_generate_image,
             llm_commands::llm_download_image,
             llm_commands::llm_calculate_cost,
+            llm_commands::llm_get_session_cost,
             llm_commands::llm_get_completion,
             llm_commands::llm_generate_commit_message,
             llm_commands::llm_generate_title,
             llm_commands::llm_compact_context,
+            llm_commands::llm_compact_context_streaming,
             llm_commands::llm_enhance_prompt,
+            llm::tracing::export::export_traces,
+            llm::tracing::retention::prune_traces,
+            llm::tracing::stats::query_trace_stats,
             llm::auth::api_key_manager::llm_set_setting,
             llm::auth::oauth::llm_openai_oauth_start,
             llm::auth::oauth::llm_openai_oauth_complete,
@@ -1102,6 +1351,7 @@ pub fn run() {
             llm::auth::oauth::llm_openai_oauth_refresh_from_store,
             llm::auth::oauth::llm_openai_oauth_disconnect,
             llm::auth::openai_usage::llm_openai_oauth_usage,
+            llm::auth::openai_usage_history::llm_openai_usage_history,
             llm::auth::oauth::llm_claude_oauth_start,
             llm::auth::oauth::llm_claude_oauth_complete,
             llm::auth::oauth::llm_claude_oauth_refresh,
@@ -1112,9 +1362,18 @@ pub fn run() {
             llm::auth::oauth::llm_github_copilot_oauth_disconnect,
             llm::auth::oauth::llm_github_copilot_oauth_tokens,
             llm::auth::oauth::llm_oauth_status,
+            llm::auth::oauth_accounts::llm_oauth_save_account,
+            llm::auth::oauth_accounts::llm_oauth_remove_account,
+            llm::auth::oauth_accounts::llm_oauth_set_active_account,
             device_id::get_device_id,
+            device_id::reset_device_id,
+            device_id::get_device_id_info,
+            analytics::get_analytics_enabled,
+            analytics::set_analytics_enabled,
             keep_awake::keep_awake_acquire,
             keep_awake::keep_awake_release,
+            keep_awake::keep_awake_acquire_for_task,
+            keep_awake::keep_awake_release_for_task,
             keep_awake::keep_awake_get_ref_count,
             keep_awake::keep_awake_is_preventing,
             telegram_gateway::telegram_get_config,
@@ -1125,6 +1384,8 @@ pub fn run() {
             telegram_gateway::telegram_is_running,
             telegram_gateway::telegram_send_message,
             telegram_gateway::telegram_edit_message,
+            telegram_gateway::telegram_start_typing,
+            telegram_gateway::telegram_stop_typing,
             feishu_gateway::feishu_get_config,
             feishu_gateway::feishu_set_config,
             feishu_gateway::feishu_start,
@@ -1133,6 +1394,27 @@ pub fn run() {
             feishu_gateway::feishu_is_running,
             feishu_gateway::feishu_send_message,
             feishu_gateway::feishu_edit_message,
+            feishu_gateway::feishu_start_typing,
+            feishu_gateway::feishu_stop_typing,
+            discord_gateway::discord_get_config,
+            discord_gateway::discord_set_config,
+            discord_gateway::discord_start,
+            discord_gateway::discord_stop,
+            discord_gateway::discord_get_status,
+            discord_gateway::discord_is_running,
+            discord_gateway::discord_send_message,
+            discord_gateway::discord_edit_message,
+            discord_gateway::discord_start_typing,
+            discord_gateway::discord_stop_typing,
+            integrations::registry::integration_list,
+            integrations::registry::integration_start,
+            integrations::registry::integration_stop,
+            integrations::registry::integration_get_status,
+            integrations::webhook::webhook_list_secrets,
+            integrations::webhook::webhook_create_secret,
+            integrations::webhook::webhook_revoke_secret,
+            files::attachment_get_thumbnail,
+            core::tool_definitions::list_available_tools,
             scheduler::create_scheduled_task,
             scheduler::update_scheduled_task,
             scheduler::delete_scheduled_task,
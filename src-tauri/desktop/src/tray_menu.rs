@@ -0,0 +1,220 @@
+//! Recent-projects menu shared by the macOS dock menu and the system tray
+//! icon used on Linux and Windows.
+//!
+//! The item list is built once here so both backends show the same entries;
+//! each backend only translates `MenuItemSpec`s into native menu items.
+
+use crate::dock_menu::{build_dock_menu_entries, decode_dock_menu_payload};
+
+/// Tray icon id, used to look the icon up again when refreshing its menu
+pub const TRAY_ID: &str = "talkcody-tray";
+
+/// Menu id of the "New Window" action
+pub const NEW_WINDOW_ITEM_ID: &str = "tray-new-window";
+
+/// Prefix of recent-project menu ids; the rest of the id is the project payload
+pub const PROJECT_ITEM_PREFIX: &str = "tray-project:";
+
+/// A platform-independent menu item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuItemSpec {
+    /// Open a recent project; `payload` is the encoded project id and path
+    Project {
+        title: String,
+        payload: String,
+    },
+    Separator,
+    NewWindow,
+}
+
+impl MenuItemSpec {
+    /// Menu id for backends that identify items by string id
+    pub fn id(&self) -> Option<String> {
+        match self {
+            MenuItemSpec::Project { payload, .. } => {
+                Some(format!("{}{}", PROJECT_ITEM_PREFIX, payload))
+            }
+            MenuItemSpec::Separator => None,
+            MenuItemSpec::NewWindow => Some(NEW_WINDOW_ITEM_ID.to_string()),
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        match self {
+            MenuItemSpec::Project { title, .. } => title,
+            MenuItemSpec::Separator => "",
+            MenuItemSpec::NewWindow => "New Window",
+        }
+    }
+}
+
+/// Action triggered by selecting a menu item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuAction {
+    NewWindow,
+    OpenProject {
+        project_id: Option<String>,
+        root_path: String,
+    },
+}
+
+/// Build the menu: recent projects, a separator, then "New Window"
+pub fn build_menu_items(recent_projects: &[serde_json::Value]) -> Vec<MenuItemSpec> {
+    let mut items: Vec<MenuItemSpec> = build_dock_menu_entries(recent_projects)
+        .into_iter()
+        .map(|entry| MenuItemSpec::Project {
+            title: entry.title,
+            payload: entry.payload,
+        })
+        .collect();
+    if !items.is_empty() {
+        items.push(MenuItemSpec::Separator);
+    }
+    items.push(MenuItemSpec::NewWindow);
+    items
+}
+
+/// Map a menu id back to its action; ids from other menus return `None`
+pub fn parse_menu_id(id: &str) -> Option<MenuAction> {
+    if id == NEW_WINDOW_ITEM_ID {
+        return Some(MenuAction::NewWindow);
+    }
+    id.strip_prefix(PROJECT_ITEM_PREFIX).map(|payload| {
+        let (project_id, root_path) = decode_dock_menu_payload(payload);
+        MenuAction::OpenProject {
+            project_id,
+            root_path,
+        }
+    })
+}
+
+#[cfg(not(target_os = "macos"))]
+fn build_tray_menu<R: tauri::Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+    recent_projects: &[serde_json::Value],
+) -> tauri::Result<tauri::menu::Menu<R>> {
+    use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+
+    let menu = Menu::new(app_handle)?;
+    for item in build_menu_items(recent_projects) {
+        match item.id() {
+            Some(id) => {
+                let menu_item =
+                    MenuItem::with_id(app_handle, id, item.title(), true, None::<&str>)?;
+                menu.append(&menu_item)?;
+            }
+            None => menu.append(&PredefinedMenuItem::separator(app_handle)?)?,
+        }
+    }
+    Ok(menu)
+}
+
+/// Create the tray icon with the recent-projects menu
+#[cfg(not(target_os = "macos"))]
+pub fn setup_tray_menu(recent_projects: &[serde_json::Value]) {
+    use tauri::tray::TrayIconBuilder;
+
+    let app_handle = crate::get_app_handle();
+    let result = build_tray_menu(app_handle, recent_projects).and_then(|menu| {
+        let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+            .tooltip("TalkCody")
+            .menu(&menu);
+        if let Some(icon) = app_handle.default_window_icon() {
+            builder = builder.icon(icon.clone());
+        }
+        builder.build(app_handle)
+    });
+
+    match result {
+        Ok(_) => log::info!("Tray menu successfully created"),
+        Err(e) => log::error!("Failed to create tray menu: {}", e),
+    }
+}
+
+/// Replace the tray icon's menu with the latest recent projects
+#[cfg(not(target_os = "macos"))]
+pub fn refresh_tray_menu(recent_projects: &[serde_json::Value]) {
+    let app_handle = crate::get_app_handle();
+    let Some(tray) = app_handle.tray_by_id(TRAY_ID) else {
+        log::warn!("Tray icon not found, skipping tray menu refresh");
+        return;
+    };
+
+    let result =
+        build_tray_menu(app_handle, recent_projects).and_then(|menu| tray.set_menu(Some(menu)));
+    if let Err(e) = result {
+        log::error!("Failed to refresh tray menu: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn projects() -> Vec<serde_json::Value> {
+        vec![
+            serde_json::json!({
+                "id": "1",
+                "name": "First",
+                "root_path": "/tmp/first"
+            }),
+            serde_json::json!({
+                "id": 2,
+                "name": "Second",
+                "root_path": "/tmp/second"
+            }),
+        ]
+    }
+
+    #[test]
+    fn test_build_menu_items_order() {
+        let items = build_menu_items(&projects());
+        let titles: Vec<&str> = items.iter().map(|item| item.title()).collect();
+        assert_eq!(titles, vec!["First", "Second", "", "New Window"]);
+        assert_eq!(items[2], MenuItemSpec::Separator);
+        assert_eq!(items[3], MenuItemSpec::NewWindow);
+    }
+
+    #[test]
+    fn test_build_menu_items_without_projects() {
+        let items = build_menu_items(&[]);
+        assert_eq!(items, vec![MenuItemSpec::NewWindow]);
+    }
+
+    #[test]
+    fn test_build_menu_items_is_deterministic() {
+        // Every backend builds from the same list, so repeated builds must match
+        assert_eq!(build_menu_items(&projects()), build_menu_items(&projects()));
+    }
+
+    #[test]
+    fn test_menu_ids_round_trip() {
+        let items = build_menu_items(&projects());
+        let actions: Vec<MenuAction> = items
+            .iter()
+            .filter_map(|item| item.id())
+            .map(|id| parse_menu_id(&id).expect("known menu id"))
+            .collect();
+
+        assert_eq!(
+            actions,
+            vec![
+                MenuAction::OpenProject {
+                    project_id: Some("1".to_string()),
+                    root_path: "/tmp/first".to_string(),
+                },
+                MenuAction::OpenProject {
+                    project_id: Some("2".to_string()),
+                    root_path: "/tmp/second".to_string(),
+                },
+                MenuAction::NewWindow,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown_menu_id() {
+        assert_eq!(parse_menu_id("quit"), None);
+        assert_eq!(parse_menu_id(""), None);
+    }
+}
@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tauri::{AppHandle, Manager, Runtime, WebviewUrl, WebviewWindowBuilder};
 
 use crate::file_watcher::FileWatcher;
@@ -13,17 +16,92 @@ pub struct WindowInfo {
     pub project_id: Option<String>,
     pub root_path: Option<String>,
     pub title: String,
+    /// Serialized as flat `x`, `y`, `width`, `height` fields when known
+    #[serde(flatten)]
+    pub geometry: Option<WindowGeometry>,
+}
+
+/// Outer position and inner size of a window, in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl WindowGeometry {
+    /// Read the current geometry of a window
+    pub fn from_window<R: Runtime>(window: &tauri::WebviewWindow<R>) -> Result<Self, String> {
+        let scale = window.scale_factor().map_err(|e| e.to_string())?;
+        let position = window
+            .outer_position()
+            .map_err(|e| e.to_string())?
+            .to_logical::<f64>(scale);
+        let size = window
+            .inner_size()
+            .map_err(|e| e.to_string())?
+            .to_logical::<f64>(scale);
+        Ok(Self {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        })
+    }
+}
+
+/// File in the app data dir recording the project windows open in the last session
+pub const SESSION_FILE_NAME: &str = "window-session.json";
+
+/// File the frontend recorded open windows in before the session file existed
+pub const LEGACY_STATE_FILE_NAME: &str = "windows-state.json";
+
+/// Quiet period after the last move or resize before geometry is written
+pub const GEOMETRY_SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Number of closed windows remembered for "reopen closed window"
+pub const MAX_CLOSED_WINDOW_HISTORY: usize = 10;
+
+/// Settings key that turns restoring the last session layout on or off
+pub const RESTORE_WINDOWS_SETTING: &str = "restore_windows_on_startup";
+
+/// On-disk layout of the session file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WindowSession {
+    pub windows: Vec<WindowInfo>,
+}
+
+/// Entry of the legacy frontend state file
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LegacyWindowState {
+    label: String,
+    project_id: Option<String>,
+    root_path: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct LegacyWindowsState {
+    #[serde(default)]
+    windows: Vec<LegacyWindowState>,
 }
 
 pub struct WindowState {
     pub project_id: Option<String>,
     pub root_path: Option<String>,
     pub file_watcher: Option<FileWatcher>,
+    pub geometry: Option<WindowGeometry>,
 }
 
 #[derive(Clone)]
 pub struct WindowRegistry {
     windows: Arc<Mutex<HashMap<String, WindowState>>>,
+    session_file: Arc<Mutex<Option<PathBuf>>>,
+    closed_windows: Arc<Mutex<VecDeque<WindowInfo>>>,
+    /// Bumped on every geometry save request; a debounced save only runs if
+    /// nothing newer was requested in the meantime
+    geometry_save_generation: Arc<AtomicU64>,
 }
 
 impl Default for WindowRegistry {
@@ -36,16 +114,31 @@ impl WindowRegistry {
     pub fn new() -> Self {
         Self {
             windows: Arc::new(Mutex::new(HashMap::new())),
+            session_file: Arc::new(Mutex::new(None)),
+            closed_windows: Arc::new(Mutex::new(VecDeque::new())),
+            geometry_save_generation: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Persist the open project windows to `path` whenever they change
+    pub fn set_session_file(&self, path: PathBuf) -> Result<(), String> {
+        let mut session_file = self.session_file.lock().map_err(|e| e.to_string())?;
+        *session_file = Some(path);
+        Ok(())
+    }
+
     pub fn register_window(&self, label: String, state: WindowState) -> Result<(), String> {
-        let mut windows = self.windows.lock().map_err(|e| e.to_string())?;
-        windows.insert(label, state);
+        {
+            let mut windows = self.windows.lock().map_err(|e| e.to_string())?;
+            windows.insert(label, state);
+        }
+        self.persist_session();
         Ok(())
     }
 
     pub fn unregister_window(&self, label: &str) -> Result<(), String> {
+        // A save firing mid-teardown would record a partial session
+        self.cancel_geometry_save();
         let mut windows = self.windows.lock().map_err(|e| e.to_string())?;
         if let Some(mut state) = windows.remove(label) {
             // Stop file watcher if exists
@@ -65,6 +158,7 @@ impl WindowRegistry {
                 project_id: state.project_id.clone(),
                 root_path: state.root_path.clone(),
                 title: build_window_title(state.root_path.as_ref()),
+                geometry: state.geometry,
             });
         }
         Ok(infos)
@@ -88,14 +182,119 @@ impl WindowRegistry {
         project_id: Option<String>,
         root_path: Option<String>,
     ) -> Result<(), String> {
-        let mut windows = self.windows.lock().map_err(|e| e.to_string())?;
-        if let Some(state) = windows.get_mut(label) {
-            state.project_id = project_id;
-            state.root_path = root_path;
+        {
+            let mut windows = self.windows.lock().map_err(|e| e.to_string())?;
+            if let Some(state) = windows.get_mut(label) {
+                state.project_id = project_id;
+                state.root_path = root_path;
+            }
         }
+        self.persist_session();
         Ok(())
     }
 
+    /// Record a window's new position or size; the session is written once
+    /// moves and resizes settle
+    pub fn update_window_geometry(
+        &self,
+        label: &str,
+        geometry: WindowGeometry,
+    ) -> Result<(), String> {
+        {
+            let mut windows = self.windows.lock().map_err(|e| e.to_string())?;
+            match windows.get_mut(label) {
+                Some(state) if state.geometry != Some(geometry) => {
+                    state.geometry = Some(geometry);
+                }
+                _ => return Ok(()),
+            }
+        }
+        self.schedule_geometry_save();
+        Ok(())
+    }
+
+    /// Write the session once moves and resizes have settled for
+    /// `GEOMETRY_SAVE_DEBOUNCE`
+    fn schedule_geometry_save(&self) {
+        let generation = self.geometry_save_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let registry = self.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(GEOMETRY_SAVE_DEBOUNCE).await;
+            if registry.geometry_save_generation.load(Ordering::SeqCst) == generation {
+                registry.persist_session();
+            }
+        });
+    }
+
+    fn cancel_geometry_save(&self) {
+        self.geometry_save_generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Write any geometry still waiting on the debounce
+    pub fn flush_session(&self) -> Result<(), String> {
+        self.cancel_geometry_save();
+        self.save_session()
+    }
+
+    /// Unregister a window the user closed and drop it from the saved session.
+    /// Plain `unregister_window` leaves the session alone so that windows torn
+    /// down while the app quits are restored on the next launch.
+    /// Project windows are also remembered so they can be reopened.
+    pub fn forget_window(&self, label: &str) -> Result<(), String> {
+        let closed = self
+            .get_all_windows()?
+            .into_iter()
+            .find(|w| w.label == label && w.label != "main" && w.root_path.is_some());
+        if let Some(info) = closed {
+            self.push_closed_window(info)?;
+        }
+        self.unregister_window(label)?;
+        self.save_session()
+    }
+
+    /// Remember a closed window, dropping the oldest beyond the history limit
+    pub fn push_closed_window(&self, info: WindowInfo) -> Result<(), String> {
+        let mut closed = self.closed_windows.lock().map_err(|e| e.to_string())?;
+        closed.push_back(info);
+        while closed.len() > MAX_CLOSED_WINDOW_HISTORY {
+            closed.pop_front();
+        }
+        Ok(())
+    }
+
+    /// Take the most recently closed window off the history
+    pub fn pop_closed_window(&self) -> Result<Option<WindowInfo>, String> {
+        let mut closed = self.closed_windows.lock().map_err(|e| e.to_string())?;
+        Ok(closed.pop_back())
+    }
+
+    /// Project windows worth restoring: the main window is created by the app
+    /// itself and windows without a project have nothing to reopen.
+    pub fn session_windows(&self) -> Result<Vec<WindowInfo>, String> {
+        let mut windows: Vec<WindowInfo> = self
+            .get_all_windows()?
+            .into_iter()
+            .filter(|w| w.label != "main" && w.root_path.is_some())
+            .collect();
+        windows.sort_by(|a, b| a.label.cmp(&b.label));
+        Ok(windows)
+    }
+
+    /// Write the current session to the session file, if one is configured
+    pub fn save_session(&self) -> Result<(), String> {
+        let path = self.session_file.lock().map_err(|e| e.to_string())?.clone();
+        match path {
+            Some(path) => save_session_to(&path, &self.session_windows()?),
+            None => Ok(()),
+        }
+    }
+
+    fn persist_session(&self) {
+        if let Err(e) = self.save_session() {
+            log::error!("Failed to save window session: {}", e);
+        }
+    }
+
     pub fn set_window_file_watcher(
         &self,
         label: &str,
@@ -165,16 +364,24 @@ fn try_focus_existing_window<R: Runtime>(
     Ok(None)
 }
 
+static LAST_WINDOW_ID: AtomicU64 = AtomicU64::new(0);
+
 /// Generate a unique window label
+/// Labels are timestamp based but strictly increasing, so windows created in
+/// the same millisecond (e.g. during session restore) don't collide.
 fn generate_window_label() -> Result<String, String> {
-    let label = format!(
-        "window-{}",
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|e| e.to_string())?
-            .as_millis()
-    );
-    Ok(label)
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_err(|e| e.to_string())?
+        .as_millis() as u64;
+    let mut last = LAST_WINDOW_ID.load(Ordering::SeqCst);
+    loop {
+        let next = now.max(last + 1);
+        match LAST_WINDOW_ID.compare_exchange(last, next, Ordering::SeqCst, Ordering::SeqCst) {
+            Ok(_) => return Ok(format!("window-{}", next)),
+            Err(actual) => last = actual,
+        }
+    }
 }
 
 /// Build window title based on root path
@@ -261,6 +468,33 @@ fn register_window_with_cleanup<R: Runtime>(
     let app_handle = window.app_handle().clone();
 
     window.on_window_event(move |event| {
+        if let tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_) = event {
+            if let Some(window) = app_handle.get_webview_window(&label_clone) {
+                match WindowGeometry::from_window(&window) {
+                    Ok(geometry) => {
+                        if let Err(e) =
+                            registry_clone.update_window_geometry(&label_clone, geometry)
+                        {
+                            log::error!("Failed to update geometry for {}: {}", label_clone, e);
+                        }
+                    }
+                    Err(e) => log::warn!("Failed to read geometry for {}: {}", label_clone, e),
+                }
+            }
+        }
+
+        // Closing one of several windows removes it from the saved session.
+        // When the last window closes the app is quitting, so the layout stays.
+        if let tauri::WindowEvent::CloseRequested { .. } = event {
+            if app_handle.webview_windows().len() > 1 {
+                if let Err(e) = registry_clone.forget_window(&label_clone) {
+                    log::error!("Failed to forget window {}: {}", label_clone, e);
+                }
+            } else if let Err(e) = registry_clone.flush_session() {
+                log::error!("Failed to save window session: {}", e);
+            }
+        }
+
         if let tauri::WindowEvent::Destroyed = event {
             log::info!(
                 "Window {} is being destroyed, cleaning up registry and state file",
@@ -293,6 +527,7 @@ pub fn create_window<R: Runtime>(
     project_id: Option<String>,
     root_path: Option<String>,
     is_new_window: bool,
+    geometry: Option<WindowGeometry>,
 ) -> Result<String, String> {
     // Only try to reuse existing window if not explicitly requesting a new window
     // When is_new_window is true, always create a new window even if project is already open
@@ -324,9 +559,14 @@ pub fn create_window<R: Runtime>(
         "/"
     };
 
-    let window = WebviewWindowBuilder::new(app_handle, &label, WebviewUrl::App(url_path.into()))
-        .title(&title)
-        .inner_size(1200.0, 800.0)
+    let mut builder =
+        WebviewWindowBuilder::new(app_handle, &label, WebviewUrl::App(url_path.into()))
+            .title(&title);
+    builder = match geometry {
+        Some(g) => builder.position(g.x, g.y).inner_size(g.width, g.height),
+        None => builder.inner_size(1200.0, 800.0),
+    };
+    let window = builder
         .build()
         .map_err(|e| format!("Failed to create window: {}", e))?;
 
@@ -335,12 +575,154 @@ pub fn create_window<R: Runtime>(
         project_id,
         root_path,
         file_watcher: None,
+        geometry,
     };
     register_window_with_cleanup(&window, window_registry, label.clone(), state)?;
 
     Ok(label)
 }
 
+/// Recreate the most recently closed project window.
+/// Returns the label of the reopened (or focused, if already open) window,
+/// or `None` when the history is empty.
+pub fn reopen_last_closed_window<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    window_registry: &WindowRegistry,
+) -> Result<Option<String>, String> {
+    let info = match window_registry.pop_closed_window()? {
+        Some(info) => info,
+        None => return Ok(None),
+    };
+    log::info!("Reopening closed window for {:?}", info.root_path);
+    create_window(
+        app_handle,
+        window_registry,
+        info.project_id,
+        info.root_path,
+        false,
+        info.geometry,
+    )
+    .map(Some)
+}
+
+/// Write the session file, creating the app data dir if needed
+pub fn save_session_to(path: &Path, windows: &[WindowInfo]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create session directory: {}", e))?;
+    }
+    let session = WindowSession {
+        windows: windows.to_vec(),
+    };
+    let content = serde_json::to_string_pretty(&session)
+        .map_err(|e| format!("Failed to serialize window session: {}", e))?;
+    fs::write(path, content).map_err(|e| format!("Failed to write window session: {}", e))
+}
+
+/// Read the session file; a missing file is an empty session
+pub fn load_session_from(path: &Path) -> Result<Vec<WindowInfo>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read window session: {}", e))?;
+    let session: WindowSession = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse window session: {}", e))?;
+    Ok(session.windows)
+}
+
+/// Move the project windows from the legacy frontend state file into
+/// `session_file`, then delete the legacy file. Does nothing once a session
+/// file exists. Geometry is not carried over: the legacy file stored physical
+/// outer sizes. Returns whether a migration happened.
+pub fn migrate_legacy_session(legacy_file: &Path, session_file: &Path) -> Result<bool, String> {
+    if session_file.exists() || !legacy_file.exists() {
+        return Ok(false);
+    }
+    let content = fs::read_to_string(legacy_file)
+        .map_err(|e| format!("Failed to read legacy window state: {}", e))?;
+    let legacy: LegacyWindowsState = serde_json::from_str(&content).unwrap_or_else(|e| {
+        log::warn!("Ignoring unreadable legacy window state: {}", e);
+        LegacyWindowsState::default()
+    });
+
+    let mut windows: Vec<WindowInfo> = Vec::new();
+    for entry in legacy.windows {
+        let Some(root_path) = entry.root_path.filter(|path| !path.trim().is_empty()) else {
+            continue;
+        };
+        if entry.label == "main"
+            || windows
+                .iter()
+                .any(|w| w.root_path.as_ref() == Some(&root_path))
+        {
+            continue;
+        }
+        windows.push(WindowInfo {
+            label: entry.label,
+            project_id: entry.project_id,
+            root_path: Some(root_path),
+            title: String::new(),
+            geometry: None,
+        });
+    }
+
+    save_session_to(session_file, &windows)?;
+    fs::remove_file(legacy_file)
+        .map_err(|e| format!("Failed to remove legacy window state: {}", e))?;
+    log::info!(
+        "Migrated {} window(s) from the legacy window state",
+        windows.len()
+    );
+    Ok(true)
+}
+
+/// Interpret the restore setting; restore is on unless explicitly disabled
+pub fn restore_enabled(value: Option<Value>) -> bool {
+    match value {
+        Some(Value::Bool(enabled)) => enabled,
+        Some(Value::String(text)) => text != "false",
+        _ => true,
+    }
+}
+
+/// Recreate the project windows recorded in `session_file`.
+/// Projects that are already open are left alone, so calling this twice is
+/// harmless. Returns the labels of the restored windows.
+pub fn restore_windows<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    window_registry: &WindowRegistry,
+    session_file: &Path,
+) -> Result<Vec<String>, String> {
+    let entries = load_session_from(session_file)?;
+    let mut labels = Vec::new();
+    for entry in entries {
+        let root_path = match entry.root_path {
+            Some(ref path) if entry.label != "main" => path.clone(),
+            _ => continue,
+        };
+        if window_registry
+            .find_window_by_project(&root_path)?
+            .is_some()
+        {
+            continue;
+        }
+        match create_window(
+            app_handle,
+            window_registry,
+            entry.project_id.clone(),
+            Some(root_path),
+            false,
+            entry.geometry,
+        ) {
+            Ok(label) => labels.push(label),
+            Err(e) => log::error!("Failed to restore window {:?}: {}", entry.root_path, e),
+        }
+    }
+    log::info!("Restored {} window(s) from last session", labels.len());
+    Ok(labels)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -360,6 +742,7 @@ mod tests {
             project_id: Some("project-1".to_string()),
             root_path: Some("/path/to/project".to_string()),
             file_watcher: None,
+            geometry: None,
         };
 
         let result = registry.register_window("window-1".to_string(), state);
@@ -380,6 +763,7 @@ mod tests {
             project_id: Some("project-1".to_string()),
             root_path: Some("/path/to/project".to_string()),
             file_watcher: None,
+            geometry: None,
         };
 
         registry
@@ -409,12 +793,14 @@ mod tests {
             project_id: Some("project-1".to_string()),
             root_path: Some("/path/to/project1".to_string()),
             file_watcher: None,
+            geometry: None,
         };
 
         let state2 = WindowState {
             project_id: Some("project-2".to_string()),
             root_path: Some("/path/to/project2".to_string()),
             file_watcher: None,
+            geometry: None,
         };
 
         registry
@@ -446,6 +832,7 @@ mod tests {
             project_id: Some("old-project".to_string()),
             root_path: Some("/old/path".to_string()),
             file_watcher: None,
+            geometry: None,
         };
 
         registry
@@ -488,6 +875,7 @@ mod tests {
                 project_id: Some(format!("project-{}", i)),
                 root_path: Some(format!("/path/to/project{}", i)),
                 file_watcher: None,
+                geometry: None,
             };
             registry
                 .register_window(format!("window-{}", i), state)
@@ -505,6 +893,7 @@ mod tests {
             project_id: Some("project-1".to_string()),
             root_path: Some("/path/to/project".to_string()),
             title: "Project - TalkCody".to_string(),
+            geometry: None,
         };
 
         let json = serde_json::to_string(&info).unwrap();
@@ -525,6 +914,7 @@ mod tests {
             project_id: None,
             root_path: None,
             title: "TalkCody".to_string(),
+            geometry: None,
         };
 
         let json = serde_json::to_string(&info).unwrap();
@@ -545,6 +935,7 @@ mod tests {
             project_id: None,
             root_path: Some("/path/to/project".to_string()),
             file_watcher: None,
+            geometry: None,
         };
         registry
             .register_window("window-1".to_string(), state_with_path)
@@ -555,6 +946,7 @@ mod tests {
             project_id: None,
             root_path: None,
             file_watcher: None,
+            geometry: None,
         };
         registry
             .register_window("window-2".to_string(), state_without_path)
@@ -615,6 +1007,7 @@ mod tests {
                     project_id: Some(format!("project-{}", i)),
                     root_path: Some(format!("/path/{}", i)),
                     file_watcher: None,
+                    geometry: None,
                 };
                 registry_clone
                     .register_window(format!("window-{}", i), state)
@@ -758,6 +1151,7 @@ mod tests {
             project_id: Some("project-1".to_string()),
             root_path: Some("/path/1".to_string()),
             file_watcher: None,
+            geometry: None,
         };
         registry
             .register_window("window-1".to_string(), state)
@@ -789,6 +1183,7 @@ mod tests {
                 project_id: Some(format!("project-{}", i)),
                 root_path: Some(format!("/path/to/project{}", i)),
                 file_watcher: None,
+                geometry: None,
             };
             registry
                 .register_window(format!("window-{}", i), state)
@@ -820,6 +1215,7 @@ mod tests {
                 project_id: Some(format!("project-{}", i)),
                 root_path: Some(format!("/path/{}", i)),
                 file_watcher: None,
+                geometry: None,
             };
             registry
                 .register_window(format!("window-{}", i), state)
@@ -852,6 +1248,7 @@ mod tests {
             project_id: Some("project-1".to_string()),
             root_path: Some("/path/1".to_string()),
             file_watcher: None,
+            geometry: None,
         };
         registry
             .register_window("window-1".to_string(), state)
@@ -879,6 +1276,7 @@ mod tests {
             project_id: Some("project-1".to_string()),
             root_path: Some("/path/1".to_string()),
             file_watcher: None,
+            geometry: None,
         };
         registry
             .register_window("window-1".to_string(), state)
@@ -916,6 +1314,7 @@ mod tests {
             project_id: Some("project-1".to_string()),
             root_path: Some("/path/1".to_string()),
             file_watcher: None,
+            geometry: None,
         };
         registry
             .register_window("window-1".to_string(), state)
@@ -945,6 +1344,7 @@ mod tests {
             project_id: Some("talkcody".to_string()),
             root_path: Some("/Users/kks/mygit/talkcody".to_string()),
             file_watcher: None,
+            geometry: None,
         };
         registry
             .register_window("window-talkcody".to_string(), state1)
@@ -959,6 +1359,7 @@ mod tests {
             project_id: Some("trader".to_string()),
             root_path: Some("/Users/kks/mygit/trader".to_string()),
             file_watcher: None,
+            geometry: None,
         };
         registry
             .register_window("window-trader".to_string(), state2)
@@ -987,4 +1388,382 @@ mod tests {
             Some("/Users/kks/mygit/trader".to_string())
         );
     }
+
+    fn project_info(label: &str, project: &str) -> WindowInfo {
+        WindowInfo {
+            label: label.to_string(),
+            project_id: Some(project.to_string()),
+            root_path: Some(format!("/path/to/{}", project)),
+            title: format!("{} - TalkCody", project),
+            geometry: None,
+        }
+    }
+
+    fn register_project(registry: &WindowRegistry, label: &str, project: &str) {
+        let state = WindowState {
+            project_id: Some(project.to_string()),
+            root_path: Some(format!("/path/to/{}", project)),
+            file_watcher: None,
+            geometry: None,
+        };
+        registry.register_window(label.to_string(), state).unwrap();
+    }
+
+    #[test]
+    fn test_session_round_trip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("nested").join(SESSION_FILE_NAME);
+        let windows = vec![
+            project_info("window-1", "alpha"),
+            project_info("window-2", "beta"),
+        ];
+
+        save_session_to(&path, &windows).unwrap();
+        let loaded = load_session_from(&path).unwrap();
+
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].root_path, Some("/path/to/alpha".to_string()));
+        assert_eq!(loaded[1].project_id, Some("beta".to_string()));
+    }
+
+    #[test]
+    fn test_migrates_legacy_state_once() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let legacy = temp_dir.path().join(LEGACY_STATE_FILE_NAME);
+        let session = temp_dir.path().join(SESSION_FILE_NAME);
+        fs::write(
+            &legacy,
+            r#"{"windows":[
+                {"label":"main","rootPath":"/path/to/main"},
+                {"label":"window-1","projectId":"alpha","rootPath":"/path/to/alpha","x":10,"y":20},
+                {"label":"window-2","rootPath":""},
+                {"label":"window-3","projectId":"alpha","rootPath":"/path/to/alpha"}
+            ],"lastActive":"window-1"}"#,
+        )
+        .unwrap();
+
+        assert!(migrate_legacy_session(&legacy, &session).unwrap());
+        assert!(!legacy.exists());
+        let loaded = load_session_from(&session).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].label, "window-1");
+        assert_eq!(loaded[0].project_id, Some("alpha".to_string()));
+        assert_eq!(loaded[0].geometry, None);
+
+        // A session file already exists, so a stale legacy file is left alone
+        fs::write(&legacy, r#"{"windows":[]}"#).unwrap();
+        assert!(!migrate_legacy_session(&legacy, &session).unwrap());
+        assert_eq!(load_session_from(&session).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_load_missing_session_is_empty() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let loaded = load_session_from(&temp_dir.path().join(SESSION_FILE_NAME)).unwrap();
+        assert!(loaded.is_empty());
+    }
+
+    #[test]
+    fn test_registry_persists_project_windows() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(SESSION_FILE_NAME);
+        let registry = WindowRegistry::new();
+        registry.set_session_file(path.clone()).unwrap();
+
+        // Main window and windows without a project are not part of the session
+        registry
+            .register_window(
+                "main".to_string(),
+                WindowState {
+                    project_id: Some("main-project".to_string()),
+                    root_path: Some("/path/to/main-project".to_string()),
+                    file_watcher: None,
+                    geometry: None,
+                },
+            )
+            .unwrap();
+        registry
+            .register_window(
+                "window-empty".to_string(),
+                WindowState {
+                    project_id: None,
+                    root_path: None,
+                    file_watcher: None,
+                    geometry: None,
+                },
+            )
+            .unwrap();
+        register_project(&registry, "window-1", "alpha");
+        register_project(&registry, "window-2", "beta");
+
+        let loaded = load_session_from(&path).unwrap();
+        let labels: Vec<&str> = loaded.iter().map(|w| w.label.as_str()).collect();
+        assert_eq!(labels, vec!["window-1", "window-2"]);
+
+        // Unregistering (app shutdown) keeps the session; forgetting drops it
+        registry.unregister_window("window-1").unwrap();
+        assert_eq!(load_session_from(&path).unwrap().len(), 2);
+        registry.forget_window("window-2").unwrap();
+        assert!(load_session_from(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_restore_enabled_setting() {
+        assert!(restore_enabled(None));
+        assert!(restore_enabled(Some(Value::Bool(true))));
+        assert!(restore_enabled(Some(Value::String("true".to_string()))));
+        assert!(!restore_enabled(Some(Value::Bool(false))));
+        assert!(!restore_enabled(Some(Value::String("false".to_string()))));
+    }
+
+    #[test]
+    fn test_generate_window_label_is_unique() {
+        let labels: std::collections::HashSet<String> =
+            (0..50).map(|_| generate_window_label().unwrap()).collect();
+        assert_eq!(labels.len(), 50);
+    }
+
+    /// This test uses Tauri test infrastructure that may not work on Windows CI
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_restore_windows_recreates_session() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(SESSION_FILE_NAME);
+        save_session_to(
+            &path,
+            &[
+                project_info("window-1", "alpha"),
+                project_info("window-2", "beta"),
+                project_info("main", "main-project"),
+            ],
+        )
+        .unwrap();
+
+        let app = tauri::test::mock_app();
+        let registry = WindowRegistry::new();
+        let labels = restore_windows(app.handle(), &registry, &path).unwrap();
+        assert_eq!(labels.len(), 2);
+
+        let mut restored: Vec<String> = registry
+            .get_all_windows()
+            .unwrap()
+            .into_iter()
+            .filter_map(|w| w.root_path)
+            .collect();
+        restored.sort();
+        assert_eq!(restored, vec!["/path/to/alpha", "/path/to/beta"]);
+
+        // Restoring again leaves the already open windows alone
+        let labels_again = restore_windows(app.handle(), &registry, &path).unwrap();
+        assert!(labels_again.is_empty());
+        assert_eq!(registry.get_all_windows().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_window_info_geometry_serialization() {
+        let mut info = project_info("window-1", "alpha");
+        let json = serde_json::to_string(&info).unwrap();
+        assert!(!json.contains("\"width\""));
+        assert!(serde_json::from_str::<WindowInfo>(&json)
+            .unwrap()
+            .geometry
+            .is_none());
+
+        info.geometry = Some(WindowGeometry {
+            x: 10.0,
+            y: 20.0,
+            width: 900.0,
+            height: 600.0,
+        });
+        let json = serde_json::to_string(&info).unwrap();
+        assert!(json.contains("\"x\":10.0"));
+        assert!(json.contains("\"height\":600.0"));
+        let parsed: WindowInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.geometry, info.geometry);
+    }
+
+    #[test]
+    fn test_update_window_geometry_is_persisted() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(SESSION_FILE_NAME);
+        let registry = WindowRegistry::new();
+        registry.set_session_file(path.clone()).unwrap();
+        register_project(&registry, "window-1", "alpha");
+
+        let geometry = WindowGeometry {
+            x: 100.0,
+            y: 50.0,
+            width: 1024.0,
+            height: 768.0,
+        };
+        registry
+            .update_window_geometry("window-1", geometry)
+            .unwrap();
+
+        // Written once the debounce settles, not on every event
+        assert_eq!(load_session_from(&path).unwrap()[0].geometry, None);
+        std::thread::sleep(GEOMETRY_SAVE_DEBOUNCE * 3);
+        let loaded = load_session_from(&path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].geometry, Some(geometry));
+
+        // Unknown windows are ignored
+        assert!(registry
+            .update_window_geometry("nonexistent", geometry)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_flush_session_writes_pending_geometry() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(SESSION_FILE_NAME);
+        let registry = WindowRegistry::new();
+        registry.set_session_file(path.clone()).unwrap();
+        register_project(&registry, "window-1", "alpha");
+        register_project(&registry, "window-2", "beta");
+
+        let geometry = WindowGeometry {
+            x: 0.0,
+            y: 0.0,
+            width: 640.0,
+            height: 480.0,
+        };
+        registry
+            .update_window_geometry("window-1", geometry)
+            .unwrap();
+        registry.flush_session().unwrap();
+        assert_eq!(
+            load_session_from(&path).unwrap()[0].geometry,
+            Some(geometry)
+        );
+
+        // Teardown cancels a pending save, so the session keeps both windows
+        registry
+            .update_window_geometry("window-2", geometry)
+            .unwrap();
+        registry.unregister_window("window-1").unwrap();
+        std::thread::sleep(GEOMETRY_SAVE_DEBOUNCE * 3);
+        assert_eq!(load_session_from(&path).unwrap().len(), 2);
+    }
+
+    /// This test uses Tauri test infrastructure that may not work on Windows CI
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_restore_windows_applies_geometry() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(SESSION_FILE_NAME);
+        let geometry = WindowGeometry {
+            x: 40.0,
+            y: 60.0,
+            width: 800.0,
+            height: 500.0,
+        };
+        let mut info = project_info("window-1", "alpha");
+        info.geometry = Some(geometry);
+        save_session_to(&path, &[info]).unwrap();
+
+        let app = tauri::test::mock_app();
+        let registry = WindowRegistry::new();
+        let labels = restore_windows(app.handle(), &registry, &path).unwrap();
+        assert_eq!(labels.len(), 1);
+
+        let windows = registry.get_all_windows().unwrap();
+        assert_eq!(windows[0].label, labels[0]);
+        assert_eq!(windows[0].geometry, Some(geometry));
+    }
+
+    #[test]
+    fn test_closed_window_history_is_bounded() {
+        let registry = WindowRegistry::new();
+        for i in 0..MAX_CLOSED_WINDOW_HISTORY + 3 {
+            registry
+                .push_closed_window(project_info(&format!("window-{}", i), &format!("p{}", i)))
+                .unwrap();
+        }
+
+        let mut popped = Vec::new();
+        while let Some(info) = registry.pop_closed_window().unwrap() {
+            popped.push(info.project_id.unwrap());
+        }
+        assert_eq!(popped.len(), MAX_CLOSED_WINDOW_HISTORY);
+        assert_eq!(
+            popped.first().unwrap(),
+            &format!("p{}", MAX_CLOSED_WINDOW_HISTORY + 2)
+        );
+        assert_eq!(popped.last().unwrap(), "p3");
+    }
+
+    #[test]
+    fn test_forget_window_records_project_windows_only() {
+        let registry = WindowRegistry::new();
+        register_project(&registry, "window-1", "alpha");
+        registry
+            .register_window(
+                "window-empty".to_string(),
+                WindowState {
+                    project_id: None,
+                    root_path: None,
+                    file_watcher: None,
+                    geometry: None,
+                },
+            )
+            .unwrap();
+
+        registry.forget_window("window-empty").unwrap();
+        registry.forget_window("window-1").unwrap();
+        // Forgetting an already closed window doesn't record it twice
+        registry.forget_window("window-1").unwrap();
+
+        let closed = registry.pop_closed_window().unwrap().unwrap();
+        assert_eq!(closed.root_path, Some("/path/to/alpha".to_string()));
+        assert!(registry.pop_closed_window().unwrap().is_none());
+    }
+
+    /// This test uses Tauri test infrastructure that may not work on Windows CI
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_reopen_last_closed_window_is_lifo() {
+        let app = tauri::test::mock_app();
+        let registry = WindowRegistry::new();
+        let first = create_window(
+            app.handle(),
+            &registry,
+            Some("alpha".to_string()),
+            Some("/path/to/alpha".to_string()),
+            false,
+            None,
+        )
+        .unwrap();
+        let second = create_window(
+            app.handle(),
+            &registry,
+            Some("beta".to_string()),
+            Some("/path/to/beta".to_string()),
+            false,
+            None,
+        )
+        .unwrap();
+
+        registry.forget_window(&first).unwrap();
+        registry.forget_window(&second).unwrap();
+        assert!(registry.get_all_windows().unwrap().is_empty());
+
+        let reopened = reopen_last_closed_window(app.handle(), &registry)
+            .unwrap()
+            .unwrap();
+        let windows = registry.get_all_windows().unwrap();
+        let info = windows.iter().find(|w| w.label == reopened).unwrap();
+        assert_eq!(info.root_path, Some("/path/to/beta".to_string()));
+
+        let reopened = reopen_last_closed_window(app.handle(), &registry)
+            .unwrap()
+            .unwrap();
+        let windows = registry.get_all_windows().unwrap();
+        let info = windows.iter().find(|w| w.label == reopened).unwrap();
+        assert_eq!(info.root_path, Some("/path/to/alpha".to_string()));
+
+        assert!(reopen_last_closed_window(app.handle(), &registry)
+            .unwrap()
+            .is_none());
+    }
 }
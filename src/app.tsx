@@ -309,25 +309,6 @@ function AppContent() {
     loadWindowProject();
   }, [isInitializing]);
 
-  // Save window state before closing
-  useEffect(() => {
-    const handleBeforeUnload = async () => {
-      try {
-        if (isMainWindow) {
-          await WindowRestoreService.saveAllWindowsState();
-        }
-      } catch (error) {
-        logger.error('Failed to save window state on close:', error);
-      }
-    };
-
-    window.addEventListener('beforeunload', handleBeforeUnload);
-
-    return () => {
-      window.removeEventListener('beforeunload', handleBeforeUnload);
-    };
-  }, [isMainWindow]);
-
   // Global drag/drop event handlers to prevent browser default behavior
   // This is required for Tauri's file-drop events to work properly
   useEffect(() => {
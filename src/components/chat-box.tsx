@@ -652,11 +652,12 @@ export const ChatBox = forwardRef<ChatBoxRef, ChatBoxProps>(
           conversationMessages,
           enableContextExtraction: payload.enableContextExtraction,
           model: payload.model,
+          targetLanguage: language || undefined,
         });
 
         return result.enhancedPrompt;
       },
-      [messages, repositoryPath]
+      [messages, repositoryPath, language]
     );
 
     return (
@@ -1,4 +1,5 @@
 import { useEffect } from 'react';
+import { DiscordChannelAdapter } from '@/services/remote/channels/discord-channel-adapter';
 import { FeishuChannelAdapter } from '@/services/remote/channels/feishu-channel-adapter';
 import { TelegramChannelAdapter } from '@/services/remote/channels/telegram-channel-adapter';
 import { WechatChannelAdapter } from '@/services/remote/channels/wechat-channel-adapter';
@@ -6,9 +7,11 @@ import { remoteChannelManager } from '@/services/remote/remote-channel-manager';
 import { remoteControlLifecycleService } from '@/services/remote/remote-control-lifecycle-service';
 
 const telegramAdapter = new TelegramChannelAdapter();
+const discordAdapter = new DiscordChannelAdapter();
 const feishuAdapter = new FeishuChannelAdapter();
 const wechatAdapter = new WechatChannelAdapter();
 remoteChannelManager.registerAdapter(telegramAdapter);
+remoteChannelManager.registerAdapter(discordAdapter);
 remoteChannelManager.registerAdapter(feishuAdapter);
 remoteChannelManager.registerAdapter(wechatAdapter);
 
@@ -97,6 +97,9 @@ export function RemoteControlSettings() {
   const [telegramAllowedChats, setTelegramAllowedChats] = useState(
     valueToString(settingsManager.get('telegram_remote_allowed_chats'))
   );
+  const [telegramAllowAll, setTelegramAllowAll] = useState(
+    toBoolean(settingsManager.get('telegram_remote_allow_all'))
+  );
   const [telegramPollTimeout, setTelegramPollTimeout] = useState(
     valueToString(settingsManager.get('telegram_remote_poll_timeout')) ||
       DEFAULT_TELEGRAM_POLL_TIMEOUT
@@ -120,6 +123,9 @@ export function RemoteControlSettings() {
   const [feishuAllowedOpenIdsValue, setFeishuAllowedOpenIdsValue] = useState(
     valueToString(settingsManager.get('feishu_remote_allowed_open_ids'))
   );
+  const [feishuAllowAll, setFeishuAllowAll] = useState(
+    toBoolean(settingsManager.get('feishu_remote_allow_all'))
+  );
 
   const [wechatEnabled, setWechatEnabled] = useState(
     toBoolean(settingsManager.get('wechat_remote_enabled'))
@@ -304,6 +310,7 @@ export function RemoteControlSettings() {
       await settingsManager.setTelegramRemoteEnabled(telegramEnabled);
       await settingsManager.set('telegram_remote_token', telegramToken.trim());
       await settingsManager.set('telegram_remote_allowed_chats', telegramAllowedChats.trim());
+      await settingsManager.setTelegramRemoteAllowAll(telegramAllowAll);
       await settingsManager.set(
         'telegram_remote_poll_timeout',
         telegramPollTimeout || DEFAULT_TELEGRAM_POLL_TIMEOUT
@@ -315,6 +322,7 @@ export function RemoteControlSettings() {
       await settingsManager.setFeishuRemoteEncryptKey(feishuEncryptKeyValue.trim());
       await settingsManager.setFeishuRemoteVerificationToken(feishuVerificationTokenValue.trim());
       await settingsManager.setFeishuRemoteAllowedOpenIds(feishuAllowedOpenIdsValue.trim());
+      await settingsManager.setFeishuRemoteAllowAll(feishuAllowAll);
 
       await persistWechatSettings();
 
@@ -366,6 +374,18 @@ export function RemoteControlSettings() {
             />
           </div>
 
+          <div className="flex items-center justify-between gap-3">
+            <div className="space-y-1">
+              <Label className="text-sm font-medium">
+                {t.Settings.remoteControl.allowAllLabel}
+              </Label>
+              <p className="text-xs text-muted-foreground">
+                {t.Settings.remoteControl.allowAllHint}
+              </p>
+            </div>
+            <Switch checked={telegramAllowAll} onCheckedChange={setTelegramAllowAll} />
+          </div>
+
           <div className="space-y-2">
             <Label htmlFor={pollTimeoutId}>{t.Settings.remoteControl.pollTimeoutLabel}</Label>
             <Input
@@ -456,6 +476,13 @@ export function RemoteControlSettings() {
             />
           </div>
 
+          <div className="flex items-center justify-between gap-3">
+            <Label className="text-sm font-medium">
+              {t.Settings.remoteControl.feishu.allowAllLabel}
+            </Label>
+            <Switch checked={feishuAllowAll} onCheckedChange={setFeishuAllowAll} />
+          </div>
+
           <p className="text-xs text-muted-foreground">
             {t.Settings.remoteControl.feishu.allowlistHint}
           </p>
@@ -67,6 +67,8 @@ vi.mock('@/stores/settings-store', () => ({
     setFeishuRemoteEncryptKey: vi.fn().mockResolvedValue(undefined),
     setFeishuRemoteVerificationToken: vi.fn().mockResolvedValue(undefined),
     setFeishuRemoteAllowedOpenIds: vi.fn().mockResolvedValue(undefined),
+    setTelegramRemoteAllowAll: vi.fn().mockResolvedValue(undefined),
+    setFeishuRemoteAllowAll: vi.fn().mockResolvedValue(undefined),
     setWechatRemoteEnabled: vi.fn().mockResolvedValue(true),
     setWechatRemoteBaseUrl: vi.fn().mockResolvedValue(undefined),
     setWechatRemoteAllowedUserIds: vi.fn().mockResolvedValue(undefined),
@@ -85,6 +87,7 @@ describe('RemoteControlSettings WeChat QR', () => {
         telegram_remote_enabled: 'false',
         telegram_remote_token: '',
         telegram_remote_allowed_chats: '',
+        telegram_remote_allow_all: 'false',
         telegram_remote_poll_timeout: '25',
         feishu_remote_enabled: 'false',
         feishu_remote_app_id: '',
@@ -92,6 +95,7 @@ describe('RemoteControlSettings WeChat QR', () => {
         feishu_remote_encrypt_key: '',
         feishu_remote_verification_token: '',
         feishu_remote_allowed_open_ids: '',
+        feishu_remote_allow_all: 'false',
         wechat_remote_enabled: 'true',
         wechat_remote_base_url: 'https://ilinkai.weixin.qq.com',
         wechat_remote_allowed_user_ids: '',
@@ -2,7 +2,6 @@ import { invoke } from '@tauri-apps/api/core';
 import type React from 'react';
 import { createContext, useContext, useEffect, useState } from 'react';
 import { logger } from '@/lib/logger';
-import { WindowStateStore } from '@/lib/window-state-store';
 
 interface WindowContextType {
   windowLabel: string;
@@ -49,6 +49,8 @@ export interface ProxyRequest {
   body?: string;
   request_id?: number;
   allow_private_ip?: boolean;
+  timeout_ms?: number;
+  max_response_bytes?: number;
 }
 
 export interface ProxyResponse {
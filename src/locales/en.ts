@@ -616,6 +616,9 @@ const en: LocaleDefinition = {
       tokenPlaceholder: 'Enter Telegram bot token',
       allowedChatsLabel: 'Allowed Chat IDs',
       allowedChatsPlaceholder: 'Comma-separated chat IDs (optional)',
+      allowAllLabel: 'Allow Everyone When No Chat IDs Are Set',
+      allowAllHint:
+        'Off by default: with no allowed chat IDs, messages from every chat are ignored.',
       pollTimeoutLabel: 'Polling Timeout (seconds)',
       pollTimeoutPlaceholder: '25',
       pollTimeoutHint: 'Recommended 10-30 seconds. Accepts 5-60 seconds.',
@@ -645,7 +648,9 @@ const en: LocaleDefinition = {
         verificationTokenPlaceholder: 'Optional verification token',
         allowedOpenIdsLabel: 'Allowed Open IDs',
         allowedOpenIdsPlaceholder: 'Comma-separated open IDs (optional)',
-        allowlistHint: 'Leave empty to allow all direct messages. Group chats are ignored.',
+        allowAllLabel: 'Allow Everyone When No Open IDs Are Set',
+        allowlistHint:
+          'With no allowed open IDs, direct messages are ignored unless Allow Everyone is on. Group chats are ignored.',
         errors: {
           appIdMissing: 'Feishu app ID is required when enabled',
           appSecretMissing: 'Feishu app secret is required when enabled',
@@ -583,6 +583,8 @@ export interface LocaleDefinition {
       tokenPlaceholder: string;
       allowedChatsLabel: string;
       allowedChatsPlaceholder: string;
+      allowAllLabel: string;
+      allowAllHint: string;
       pollTimeoutLabel: string;
       pollTimeoutPlaceholder: string;
       pollTimeoutHint: string;
@@ -611,6 +613,7 @@ export interface LocaleDefinition {
         verificationTokenPlaceholder: string;
         allowedOpenIdsLabel: string;
         allowedOpenIdsPlaceholder: string;
+        allowAllLabel: string;
         allowlistHint: string;
         errors: {
           appIdMissing: string;
@@ -589,6 +589,8 @@ const zh: LocaleDefinition = {
       tokenPlaceholder: '输入 Telegram bot token',
       allowedChatsLabel: '允许的 Chat ID',
       allowedChatsPlaceholder: '逗号分隔的 chat ID（可选）',
+      allowAllLabel: '未设置 Chat ID 时允许所有人',
+      allowAllHint: '默认关闭：未设置允许的 Chat ID 时，将忽略所有聊天的消息。',
       pollTimeoutLabel: '轮询超时（秒）',
       pollTimeoutPlaceholder: '25',
       pollTimeoutHint: '推荐 10-30 秒，可填写 5-60 秒。',
@@ -617,7 +619,8 @@ const zh: LocaleDefinition = {
         verificationTokenPlaceholder: '可选的 Verification Token',
         allowedOpenIdsLabel: '允许的 Open ID',
         allowedOpenIdsPlaceholder: '逗号分隔的 open_id（可选）',
-        allowlistHint: '留空将允许所有私聊消息，群聊会被忽略。',
+        allowAllLabel: '未设置 Open ID 时允许所有人',
+        allowlistHint: '未设置允许的 Open ID 时，除非开启“允许所有人”，否则会忽略私聊消息。群聊会被忽略。',
         errors: {
           appIdMissing: '启用后需要填写飞书 App ID',
           appSecretMissing: '启用后需要填写飞书 App Secret',
@@ -30,6 +30,8 @@ import type {
 const MAX_JSON_PREVIEW = 2000;
 const MAX_PAYLOAD_HEIGHT = '24rem';
 const TRACE_PAGE_SIZE = 10;
+/** Age cutoff for the "Delete Old Traces" action, matching the confirm text */
+const DELETE_OLD_TRACES_DAYS = 3;
 
 const formatTraceCount = (count: number) => `${count} trace${count !== 1 ? 's' : ''}`;
 
@@ -277,9 +279,7 @@ export function LLMTracingPage() {
     setIsDeleting(true);
     setDeleteError(null);
     try {
-      // Calculate cutoff timestamp (3 days ago)
-      const cutoffTimestamp = Date.now() - 3 * 24 * 60 * 60 * 1000;
-      await databaseService.deleteOldTraces(cutoffTimestamp);
+      await databaseService.deleteOldTraces(DELETE_OLD_TRACES_DAYS);
       logger.info('Deleted old traces successfully');
       setShowDeleteConfirm(false);
       // Reload traces after deletion
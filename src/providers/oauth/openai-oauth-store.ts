@@ -324,7 +324,10 @@ export const useOpenAIOAuthStore = create<OpenAIOAuthStore>((set, get) => ({
     set({ isLoading: true, error: null });
 
     try {
-      await llmClient.disconnectOpenAIOAuth();
+      const result = await llmClient.disconnectOpenAIOAuth();
+      if (result?.revocation === 'failed') {
+        logger.warn('[OpenAIOAuth] Token revocation failed, cleared locally:', result.error);
+      }
 
       logger.info('[OpenAIOAuth] Disconnected');
 
@@ -2,6 +2,7 @@
 // Unified state management for providers and models
 
 import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import { toast } from 'sonner';
 import { create } from 'zustand';
 import { logger } from '@/lib/logger';
 import { ensureModelsInitialized, refreshModelConfigs } from '@/providers/config/model-config';
@@ -24,6 +25,13 @@ import type { CustomProviderConfig } from '@/types/custom-provider';
 import type { ModelConfig } from '@/types/models';
 
 let modelsUpdateListener: Promise<UnlistenFn> | null = null;
+let oauthRefreshFailedListener: Promise<UnlistenFn> | null = null;
+
+const OAUTH_PROVIDER_NAMES: Record<string, string> = {
+  openai: 'OpenAI',
+  anthropic: 'Claude',
+  githubCopilot: 'GitHub Copilot',
+};
 let modelsUpdateReady = false;
 
 // ===== Types =====
@@ -267,6 +275,19 @@ export const useProviderStore = create<ProviderStore>((set, get) => ({
         });
       }
 
+      // Proactive token refresh failed in the backend; the user has to reconnect
+      if (!oauthRefreshFailedListener) {
+        oauthRefreshFailedListener = listen<{ provider: string; error: string }>(
+          'oauth-refresh-failed',
+          (event) => {
+            const { provider, error } = event.payload;
+            logger.warn('[ProviderStore] OAuth token refresh failed', { provider, error });
+            const name = OAUTH_PROVIDER_NAMES[provider] ?? provider;
+            toast.error(`${name} session expired. Please reconnect your account in settings.`);
+          }
+        );
+      }
+
       // Initialize remote skills sync service (non-blocking, for hot-reload)
       remoteSkillsSyncService.initialize().catch((err) => {
         logger.warn('[ProviderStore] Remote skills sync initialization failed:', err);
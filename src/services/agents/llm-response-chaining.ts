@@ -14,6 +14,7 @@ export type RequestPlanningContext = {
   fallbackModels?: string[] | null;
   iteration: number;
   messages: LlmMessage[];
+  sessionId?: StreamTextRequest['sessionId'];
   traceContext?: StreamTextRequest['traceContext'];
   tools?: StreamTextRequest['tools'];
   temperature?: number | null;
@@ -102,6 +103,7 @@ function buildRequest(
     topP: context.topP,
     topK: context.topK,
     providerOptions: context.providerOptions,
+    sessionId: context.sessionId,
     traceContext: context.traceContext,
     conversationMode:
       overrides.conversationMode ?? (chainState ? 'responses-chained' : 'stateless'),
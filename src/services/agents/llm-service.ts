@@ -34,6 +34,7 @@ import { useSettingsStore } from '@/stores/settings-store';
 import { useTaskStore } from '@/stores/task-store';
 import type { ToolSummary } from '@/types/completion-hooks';
 import { ModelType } from '@/types/model-types';
+import type { TaskSettings } from '@/types/task';
 import type {
   AgentLoopOptions,
   AgentLoopState,
@@ -142,6 +143,29 @@ class ModelFallbackSwitchError extends Error {
   }
 }
 
+// The backend walks the request's fallback models itself and reports this
+// prefix once every model in the chain has failed.
+const ALL_MODELS_FAILED_PREFIX = 'All models failed';
+
+function isAllModelsFailedError(error: unknown): boolean {
+  const message = error instanceof Error ? error.message : String(error ?? '');
+  return message.startsWith(ALL_MODELS_FAILED_PREFIX);
+}
+
+/** The task's `maxIterations` budget, when one is set */
+function getTaskMaxIterations(taskId: string | undefined): number | undefined {
+  if (!taskId || taskId === 'nested') return undefined;
+  const settings = useTaskStore.getState().getTask(taskId)?.settings;
+  if (!settings) return undefined;
+  try {
+    const { maxIterations } = JSON.parse(settings) as TaskSettings;
+    return typeof maxIterations === 'number' && maxIterations > 0 ? maxIterations : undefined;
+  } catch (error) {
+    logger.warn('[LLMService] Failed to parse task settings', { taskId, error });
+    return undefined;
+  }
+}
+
 function getTranslations() {
   const language = (useSettingsStore.getState().language || 'en') as SupportedLocale;
   return getLocale(language);
@@ -751,6 +775,10 @@ export class LLMService {
           rootPath: providedRootPath,
         } = options;
 
+        // The task's iteration budget can only tighten the caller's limit
+        const taskMaxIterations = isSubagent ? undefined : getTaskMaxIterations(this.taskId);
+        const iterationBudget = Math.min(maxIterations, taskMaxIterations ?? maxIterations);
+
         activeModel = model;
         activeFallbackModels = [...fallbackModels];
         activeProviderId = parseModelIdentifier(activeModel).providerId ?? undefined;
@@ -766,7 +794,7 @@ export class LLMService {
 
         logger.info('Starting agent loop with model', {
           model,
-          maxIterations: options.maxIterations,
+          maxIterations: iterationBudget,
           taskId: this.taskId,
           inputMessageCount: inputMessages.length,
           agentId: agentId || 'default',
@@ -912,7 +940,7 @@ export class LLMService {
         let autoCompactionAttempts = 0;
         let ralphIteration = 0; // Track Ralph loop iterations separately from agent steps
 
-        while (!loopState.isComplete && loopState.currentIteration < maxIterations) {
+        while (!loopState.isComplete && loopState.currentIteration < iterationBudget) {
           if (this.taskId && !isSubagent && !didRunSessionStart) {
             const sessionStartSummary = await hookService.runSessionStart(this.taskId, 'startup');
             hookService.applyHookSummary(sessionStartSummary);
@@ -1123,6 +1151,8 @@ export class LLMService {
                 topP,
                 topK,
                 providerOptions: providerOptions ?? undefined,
+                // Usage is recorded against the session even when tracing is off
+                sessionId: this.taskId && this.taskId !== 'nested' ? this.taskId : null,
                 traceContext,
               });
 
@@ -1217,6 +1247,44 @@ export class LLMService {
                     transportFallbackEvent = delta;
                     didFallbackToStateless = delta.to === 'stateless';
                     break;
+                  case 'model-fallback': {
+                    // The backend already switched models; only sync local state so the
+                    // frontend does not run a second fallback round over the same chain.
+                    const servedIndex = activeFallbackModels.indexOf(delta.to);
+                    if (servedIndex > 0) {
+                      activeFallbackModels = activeFallbackModels.slice(servedIndex);
+                    }
+                    await promoteFallbackModel(delta.to, delta.reason);
+                    break;
+                  }
+                  case 'context-compacted': {
+                    // The backend compacted the history to fit the context window and
+                    // retried with it; keep it so the next iteration doesn't overflow again.
+                    logger.info('[LLMService] Backend compacted the context', {
+                      iteration: loopState.currentIteration,
+                      tokensBefore: delta.tokensBefore,
+                      tokensAfter: delta.tokensAfter,
+                    });
+                    loopState.messages = convertToAnthropicFormat(
+                      delta.messages as unknown as ModelMessage[],
+                      { autoFix: true, trimAssistantWhitespace: true }
+                    );
+                    invalidateResponsesChain(loopState, 'history_rewritten');
+                    loopState.lastRequestTokens = delta.tokensAfter;
+                    if (this.taskId && !isSubagent) {
+                      const currentUIMessageCount = useTaskStore
+                        .getState()
+                        .getMessages(this.taskId).length;
+                      this.saveCompactedMessages(
+                        loopState.messages,
+                        currentUIMessageCount,
+                        loopState.lastRequestTokens
+                      ).catch((err) => {
+                        logger.warn('Failed to save compacted messages', err);
+                      });
+                    }
+                    break;
+                  }
                   case 'usage': {
                     const requestDuration = Date.now() - requestStartTime;
                     const normalizedUsage = UsageTokenUtils.normalizeUsageTokens(
@@ -1348,6 +1416,9 @@ export class LLMService {
                     if (delta.name) {
                       errorObj.name = delta.name;
                     }
+                    if (isAllModelsFailedError(errorObj)) {
+                      activeFallbackModels = [];
+                    }
 
                     if (isContextLengthExceededError(errorObj)) {
                       const MAX_AUTO_COMPACTIONS = 1;
@@ -1484,7 +1555,12 @@ export class LLMService {
                 continue;
               }
 
-              if (!visibleOutput && activeFallbackModels[0] && !this.isAbortError(streamError)) {
+              if (
+                !visibleOutput &&
+                activeFallbackModels[0] &&
+                !this.isAbortError(streamError) &&
+                !isAllModelsFailedError(streamError)
+              ) {
                 await promoteFallbackModel(activeFallbackModels[0], retryDecision.reason);
                 streamRetryCount = 0;
                 streamProcessor.resetState();
@@ -1987,6 +2063,15 @@ export class LLMService {
           }
         }
 
+        if (!loopState.isComplete) {
+          logger.warn('[LLMService] Agent loop stopped at its iteration budget', {
+            taskId: this.taskId,
+            iterations: loopState.currentIteration,
+            maxIterations: iterationBudget,
+          });
+          loopState.lastFinishReason = 'max-iterations';
+        }
+
         const totalDuration = Date.now() - totalStartTime;
         await this.closeResponsesChainSession(loopState);
         logger.info('Agent loop completed', {
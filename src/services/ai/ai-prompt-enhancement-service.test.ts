@@ -44,6 +44,7 @@ describe('AIPromptEnhancementService', () => {
         ],
         enableContextExtraction: true,
         model: 'gpt-4@openai',
+        targetLanguage: null,
       });
 
       expect(llmClient.enhancePrompt).toHaveBeenCalledWith({
@@ -110,6 +111,7 @@ describe('AIPromptEnhancementService', () => {
         conversationHistory: null,
         enableContextExtraction: false,
         model: null,
+        targetLanguage: null,
       });
     });
 
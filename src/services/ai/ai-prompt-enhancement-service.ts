@@ -11,12 +11,19 @@ export interface EnhancePromptParams {
   conversationMessages?: Array<{ role: string; content: string }>;
   enableContextExtraction: boolean;
   model?: string;
+  targetLanguage?: string;
 }
 
 class AIPromptEnhancementService {
   async enhancePrompt(params: EnhancePromptParams): Promise<PromptEnhancementResult> {
-    const { originalPrompt, projectPath, conversationMessages, enableContextExtraction, model } =
-      params;
+    const {
+      originalPrompt,
+      projectPath,
+      conversationMessages,
+      enableContextExtraction,
+      model,
+      targetLanguage,
+    } = params;
 
     if (!originalPrompt || originalPrompt.trim().length === 0) {
       throw new Error('No prompt provided for enhancement');
@@ -38,6 +45,7 @@ class AIPromptEnhancementService {
       conversationHistory: conversationHistory ?? null,
       enableContextExtraction,
       model: model ?? null,
+      targetLanguage: targetLanguage ?? null,
     });
 
     if (!result.enhancedPrompt || result.enhancedPrompt.trim().length === 0) {
@@ -191,19 +191,55 @@ export async function summarizeCodeContent(
 // Token Estimation for Message Compaction
 // ============================================================================
 
+export type TokenEstimationMethod = 'heuristic' | 'bpe_cl100k' | 'bpe_o200k';
+
+export interface TokenEstimate {
+  tokens: number;
+  method: TokenEstimationMethod;
+}
+
 /**
- * Estimate token count using character-based heuristics.
- * - CJK characters: 1 char ≈ 1 token
- * - Other characters: 4 chars ≈ 1 token
+ * Estimate token count.
+ * - With a recognized OpenAI model: BPE (tiktoken-style) approximation
+ * - Otherwise character-based heuristics:
+ *   - CJK characters: 1 char ≈ 1 token
+ *   - Other characters: 4 chars ≈ 1 token
  *
  * This is used to quickly check if tree-sitter compression has reduced
  * tokens enough to skip AI-based compression.
  *
  * @param text - The text to estimate tokens for
+ * @param model - Optional model id used to pick the estimation method
  * @returns Estimated token count
  */
-export async function estimateTokens(text: string): Promise<number> {
-  return invoke('estimate_tokens', { text });
+export async function estimateTokens(text: string, model?: string): Promise<number> {
+  const estimate = await invoke<TokenEstimate>('estimate_tokens', { text, model: model ?? null });
+  return estimate.tokens;
+}
+
+export interface MessagesTokenEstimate {
+  system: number;
+  user: number;
+  assistant: number;
+  tools: number;
+  total: number;
+  method: TokenEstimationMethod | null;
+}
+
+/**
+ * Estimate a whole conversation's token usage, including per-message overhead
+ * and tool definitions, so the UI can warn before sending.
+ */
+export async function estimateMessagesTokens(
+  messages: unknown[],
+  tools?: unknown[],
+  model?: string
+): Promise<MessagesTokenEstimate> {
+  return invoke('estimate_messages_tokens', {
+    messages,
+    tools: tools ?? null,
+    model: model ?? null,
+  });
 }
 
 /**
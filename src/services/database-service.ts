@@ -3,6 +3,7 @@
 import { logger } from '@/lib/logger';
 import { MCPServerService } from '@/lib/mcp/mcp-server-service';
 import type { MessageAttachment } from '@/types/agent';
+import type { TraceExportFormat, TracePruneResult } from '@/types/trace';
 import { ApiUsageService } from './database/api-usage-service';
 import { ProjectService } from './database/project-service';
 import { type RecentFile, RecentFilesService } from './database/recent-files-service';
@@ -278,10 +279,16 @@ export class DatabaseService {
     return this.traceService.endSpan(spanId, endedAt);
   }
 
-  async deleteOldTraces(cutoffTimestamp: number): Promise<void> {
+  async exportTrace(traceId: string, format?: TraceExportFormat): Promise<string> {
     await this.ensureInitialized();
     if (!this.traceService) throw new Error('Trace service not initialized');
-    return this.traceService.deleteOldTraces(cutoffTimestamp);
+    return this.traceService.exportTrace(traceId, format);
+  }
+
+  async deleteOldTraces(olderThanDays: number): Promise<TracePruneResult> {
+    await this.ensureInitialized();
+    if (!this.traceService) throw new Error('Trace service not initialized');
+    return this.traceService.deleteOldTraces(olderThanDays);
   }
 
   async getAttachmentsForMessage(messageId: string): Promise<MessageAttachment[]> {
@@ -1,8 +1,12 @@
+import { invoke } from '@tauri-apps/api/core';
 import type {
+  ModelLatencyStats,
   OpenAiSubscriptionTraceMetrics,
   SpanEventRecord,
   SpanRecord,
   TraceDetail,
+  TraceExportFormat,
+  TracePruneResult,
   TraceSummary,
 } from '@/types/trace';
 import type { TursoClient } from './turso-client';
@@ -357,27 +361,30 @@ export class TraceService {
     };
   }
 
-  async deleteOldTraces(cutoffTimestamp: number): Promise<void> {
-    // Delete in proper order to respect foreign key constraints
-    // 1. First delete span_events for spans belonging to old traces
-    await this.db.execute(
-      `DELETE FROM span_events WHERE span_id IN (
-        SELECT id FROM spans WHERE trace_id IN (
-          SELECT id FROM traces WHERE started_at < $1
-        )
-      )`,
-      [cutoffTimestamp]
-    );
+  /**
+   * Export a trace as OTLP/JSON or Jaeger JSON for external tracing backends
+   */
+  async exportTrace(traceId: string, format: TraceExportFormat = 'otlp'): Promise<string> {
+    return invoke<string>('export_traces', { traceId, format });
+  }
 
-    // 2. Then delete spans for old traces
-    await this.db.execute(
-      `DELETE FROM spans WHERE trace_id IN (
-        SELECT id FROM traces WHERE started_at < $1
-      )`,
-      [cutoffTimestamp]
-    );
+  /**
+   * TTFT and total latency percentiles per model, optionally filtered by start time
+   */
+  async getLatencyStats(
+    filter: { model?: string; since?: number; until?: number } = {}
+  ): Promise<ModelLatencyStats[]> {
+    return invoke<ModelLatencyStats[]>('query_trace_stats', {
+      model: filter.model ?? null,
+      since: filter.since ?? null,
+      until: filter.until ?? null,
+    });
+  }
 
-    // 3. Finally delete the old traces
-    await this.db.execute('DELETE FROM traces WHERE started_at < $1', [cutoffTimestamp]);
+  /**
+   * Delete traces (with their spans and events) older than the given number of days
+   */
+  async deleteOldTraces(olderThanDays: number): Promise<TracePruneResult> {
+    return invoke<TracePruneResult>('prune_traces', { olderThanDays });
   }
 }
@@ -6,23 +6,24 @@ import { keepAwakeService } from './keep-awake-service';
 
 vi.mock('./keep-awake-service', () => ({
   keepAwakeService: {
-    acquireWithResult: vi.fn(),
-    releaseWithResult: vi.fn(),
+    acquireForTask: vi.fn(),
+    releaseForTask: vi.fn(),
   },
 }));
 
 const { executionState, listeners, mockExecutionStore } = vi.hoisted(() => {
   const executionState = {
-    runningCount: 0,
+    runningTaskIds: [] as string[],
   };
 
-  const listeners = new Set<(state: { getRunningCount: () => number }) => void>();
+  type MockState = { getRunningTaskIds: () => string[] };
+  const listeners = new Set<(state: MockState) => void>();
 
   const mockExecutionStore = {
-    getState: () => ({
-      getRunningCount: () => executionState.runningCount,
+    getState: (): MockState => ({
+      getRunningTaskIds: () => executionState.runningTaskIds,
     }),
-    subscribe: (listener: (state: { getRunningCount: () => number }) => void) => {
+    subscribe: (listener: (state: MockState) => void) => {
       listeners.add(listener);
       return () => {
         listeners.delete(listener);
@@ -39,16 +40,10 @@ vi.mock('@/stores/execution-store', () => ({
 
 describe('keepAwakeManager', () => {
   beforeEach(() => {
-    executionState.runningCount = 0;
+    executionState.runningTaskIds = [];
     listeners.clear();
-    vi.mocked(keepAwakeService.acquireWithResult).mockResolvedValue({
-      success: true,
-      wasFirst: true,
-    });
-    vi.mocked(keepAwakeService.releaseWithResult).mockResolvedValue({
-      success: true,
-      wasLast: true,
-    });
+    vi.mocked(keepAwakeService.acquireForTask).mockResolvedValue(true);
+    vi.mocked(keepAwakeService.releaseForTask).mockResolvedValue(true);
     keepAwakeManager.stop();
     vi.clearAllMocks();
   });
@@ -58,48 +53,50 @@ describe('keepAwakeManager', () => {
     listeners.forEach((listener) => listener(state));
   };
 
-  it('should acquire once on start when tasks are already running', async () => {
-    executionState.runningCount = 2;
+  it('should acquire for each task already running on start', async () => {
+    executionState.runningTaskIds = ['task-1', 'task-2'];
 
     keepAwakeManager.start();
     await keepAwakeManager.waitForIdle();
 
-    expect(keepAwakeService.acquireWithResult).toHaveBeenCalledTimes(1);
-    expect(keepAwakeService.releaseWithResult).not.toHaveBeenCalled();
+    expect(keepAwakeService.acquireForTask).toHaveBeenCalledWith('task-1');
+    expect(keepAwakeService.acquireForTask).toHaveBeenCalledWith('task-2');
+    expect(keepAwakeService.releaseForTask).not.toHaveBeenCalled();
     expect(keepAwakeManager.getSnapshot()).toMatchObject({
       runningCount: 2,
-      refCount: 1,
+      refCount: 2,
       isPreventing: true,
     });
   });
 
-  it('should only toggle keep-awake on zero-to-nonzero transitions', async () => {
+  it('should acquire when a task starts and release when it finishes', async () => {
     keepAwakeManager.start();
     await keepAwakeManager.waitForIdle();
 
-    executionState.runningCount = 1;
+    executionState.runningTaskIds = ['task-1'];
     emit();
     await keepAwakeManager.waitForIdle();
 
-    expect(keepAwakeService.acquireWithResult).toHaveBeenCalledTimes(1);
-    expect(keepAwakeService.releaseWithResult).not.toHaveBeenCalled();
+    expect(keepAwakeService.acquireForTask).toHaveBeenCalledTimes(1);
+    expect(keepAwakeService.acquireForTask).toHaveBeenCalledWith('task-1');
 
-    executionState.runningCount = 3;
+    executionState.runningTaskIds = ['task-1', 'task-2'];
     emit();
     await keepAwakeManager.waitForIdle();
 
-    executionState.runningCount = 1;
+    executionState.runningTaskIds = ['task-2'];
     emit();
     await keepAwakeManager.waitForIdle();
 
-    expect(keepAwakeService.acquireWithResult).toHaveBeenCalledTimes(1);
-    expect(keepAwakeService.releaseWithResult).not.toHaveBeenCalled();
+    expect(keepAwakeService.acquireForTask).toHaveBeenCalledTimes(2);
+    expect(keepAwakeService.releaseForTask).toHaveBeenCalledTimes(1);
+    expect(keepAwakeService.releaseForTask).toHaveBeenCalledWith('task-1');
 
-    executionState.runningCount = 0;
+    executionState.runningTaskIds = [];
     emit();
     await keepAwakeManager.waitForIdle();
 
-    expect(keepAwakeService.releaseWithResult).toHaveBeenCalledTimes(1);
+    expect(keepAwakeService.releaseForTask).toHaveBeenCalledWith('task-2');
     expect(keepAwakeManager.getSnapshot()).toMatchObject({
       runningCount: 0,
       refCount: 0,
@@ -111,22 +108,22 @@ describe('keepAwakeManager', () => {
     keepAwakeManager.start();
     await keepAwakeManager.waitForIdle();
 
-    expect(keepAwakeService.acquireWithResult).not.toHaveBeenCalled();
-    expect(keepAwakeService.releaseWithResult).not.toHaveBeenCalled();
+    expect(keepAwakeService.acquireForTask).not.toHaveBeenCalled();
+    expect(keepAwakeService.releaseForTask).not.toHaveBeenCalled();
   });
 
   it('should be idempotent on start', async () => {
-    executionState.runningCount = 1;
+    executionState.runningTaskIds = ['task-1'];
     keepAwakeManager.start();
     keepAwakeManager.start();
     await keepAwakeManager.waitForIdle();
 
     expect(listeners.size).toBe(1);
-    expect(keepAwakeService.acquireWithResult).toHaveBeenCalledTimes(1);
+    expect(keepAwakeService.acquireForTask).toHaveBeenCalledTimes(1);
   });
 
   it('should stop and reset state', () => {
-    executionState.runningCount = 1;
+    executionState.runningTaskIds = ['task-1'];
     keepAwakeManager.start();
 
     keepAwakeManager.stop();
@@ -139,8 +136,8 @@ describe('keepAwakeManager', () => {
     });
   });
 
-  it('should release after an in-flight startup acquire when tasks stop', async () => {
-    executionState.runningCount = 1;
+  it('should release after an in-flight startup acquire when the task stops', async () => {
+    executionState.runningTaskIds = ['task-1'];
 
     let resolveAcquire: (() => void) | null = null;
     const acquirePromise = new Promise<void>((resolve) => {
@@ -152,23 +149,23 @@ describe('keepAwakeManager', () => {
       notifyAcquireStarted = resolve;
     });
 
-    vi.mocked(keepAwakeService.acquireWithResult).mockImplementation(async () => {
+    vi.mocked(keepAwakeService.acquireForTask).mockImplementation(async () => {
       notifyAcquireStarted?.();
       await acquirePromise;
-      return { success: true, wasFirst: true };
+      return true;
     });
 
     keepAwakeManager.start();
 
     await acquireStarted;
-    executionState.runningCount = 0;
+    executionState.runningTaskIds = [];
     emit();
 
     resolveAcquire?.();
     await keepAwakeManager.waitForIdle();
 
-    expect(keepAwakeService.acquireWithResult).toHaveBeenCalledTimes(1);
-    expect(keepAwakeService.releaseWithResult).toHaveBeenCalledTimes(1);
+    expect(keepAwakeService.acquireForTask).toHaveBeenCalledTimes(1);
+    expect(keepAwakeService.releaseForTask).toHaveBeenCalledWith('task-1');
     expect(keepAwakeManager.getSnapshot()).toMatchObject({
       runningCount: 0,
       refCount: 0,
@@ -177,11 +174,8 @@ describe('keepAwakeManager', () => {
   });
 
   it('should keep state cleared when acquire fails', async () => {
-    executionState.runningCount = 1;
-    vi.mocked(keepAwakeService.acquireWithResult).mockResolvedValue({
-      success: false,
-      wasFirst: false,
-    });
+    executionState.runningTaskIds = ['task-1'];
+    vi.mocked(keepAwakeService.acquireForTask).mockResolvedValue(false);
 
     keepAwakeManager.start();
     await keepAwakeManager.waitForIdle();
@@ -191,6 +185,6 @@ describe('keepAwakeManager', () => {
       refCount: 0,
       isPreventing: false,
     });
-    expect(keepAwakeService.releaseWithResult).not.toHaveBeenCalled();
+    expect(keepAwakeService.releaseForTask).not.toHaveBeenCalled();
   });
 });
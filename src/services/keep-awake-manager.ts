@@ -1,8 +1,9 @@
 // keep-awake-manager.ts - Orchestrates keep-awake state from execution store
 //
 // This manager reacts to task activity in the current window only.
-// Each window owns at most one keep-awake reference while it has running tasks,
-// which avoids releasing references held by other windows or subsystems.
+// Each running task holds its own backend keep-awake reference, keyed by task id,
+// which is released when the task completes, errors, or is stopped. References
+// held by other windows or subsystems are never touched.
 
 import { logger } from '@/lib/logger';
 import { keepAwakeService } from '@/services/keep-awake-service';
@@ -16,12 +17,12 @@ export type KeepAwakeSnapshot = {
 
 class KeepAwakeManager {
   private isStarted = false;
-  private runningCount = 0;
-  private refCount = 0;
-  private isPreventing = false;
+  private runningTaskIds = new Set<string>();
+  private heldTaskIds = new Set<string>();
   private unsubscribe: (() => void) | null = null;
   private listeners = new Set<() => void>();
   private operationQueue: Promise<void> = Promise.resolve();
+  private snapshot: KeepAwakeSnapshot = { isPreventing: false, refCount: 0, runningCount: 0 };
 
   public start = (): void => {
     if (this.isStarted) {
@@ -29,41 +30,19 @@ class KeepAwakeManager {
     }
 
     this.isStarted = true;
-    this.runningCount = useExecutionStore.getState().getRunningCount();
-    this.emit();
+    this.syncRunningTasks(useExecutionStore.getState().getRunningTaskIds());
 
     this.unsubscribe = useExecutionStore.subscribe((state) => {
-      const nextRunningCount = state.getRunningCount();
-      if (nextRunningCount === this.runningCount) {
-        return;
-      }
-
-      const previousRunningCount = this.runningCount;
-      this.runningCount = nextRunningCount;
-      this.emit();
-
-      if (previousRunningCount === 0 && nextRunningCount > 0) {
-        this.enqueue(() => this.acquireForRunningTasks());
-        return;
-      }
-
-      if (previousRunningCount > 0 && nextRunningCount === 0) {
-        this.enqueue(() => this.releaseForRunningTasks());
-      }
+      this.syncRunningTasks(state.getRunningTaskIds());
     });
-
-    if (this.runningCount > 0) {
-      this.enqueue(() => this.acquireForRunningTasks());
-    }
   };
 
   public stop = (): void => {
     this.unsubscribe?.();
     this.unsubscribe = null;
     this.isStarted = false;
-    this.runningCount = 0;
-    this.refCount = 0;
-    this.isPreventing = false;
+    this.runningTaskIds = new Set();
+    this.heldTaskIds = new Set();
     this.operationQueue = Promise.resolve();
     this.emit();
   };
@@ -75,30 +54,37 @@ class KeepAwakeManager {
     };
   };
 
-  public getSnapshot = (): KeepAwakeSnapshot => ({
-    isPreventing: this.isPreventing,
-    refCount: this.refCount,
-    runningCount: this.runningCount,
-  });
+  public getSnapshot = (): KeepAwakeSnapshot => this.snapshot;
 
   public waitForIdle = (): Promise<void> => this.operationQueue;
 
   private emit(): void {
+    this.snapshot = {
+      isPreventing: this.heldTaskIds.size > 0,
+      refCount: this.heldTaskIds.size,
+      runningCount: this.runningTaskIds.size,
+    };
     for (const listener of this.listeners) {
       listener();
     }
   }
 
-  private setHeldReference(held: boolean): void {
-    const nextRefCount = held ? 1 : 0;
-    const nextPreventing = held;
-    const changed = nextRefCount !== this.refCount || nextPreventing !== this.isPreventing;
+  private syncRunningTasks(taskIds: string[]): void {
+    const next = new Set(taskIds);
+    const started = taskIds.filter((taskId) => !this.runningTaskIds.has(taskId));
+    const finished = Array.from(this.runningTaskIds).filter((taskId) => !next.has(taskId));
+    if (started.length === 0 && finished.length === 0) {
+      return;
+    }
 
-    this.refCount = nextRefCount;
-    this.isPreventing = nextPreventing;
+    this.runningTaskIds = next;
+    this.emit();
 
-    if (changed) {
-      this.emit();
+    for (const taskId of started) {
+      this.enqueue(() => this.acquireForTask(taskId));
+    }
+    for (const taskId of finished) {
+      this.enqueue(() => this.releaseForTask(taskId));
     }
   }
 
@@ -108,30 +94,30 @@ class KeepAwakeManager {
     });
   }
 
-  private async acquireForRunningTasks(): Promise<void> {
-    if (this.refCount > 0 || this.runningCount === 0) {
+  private async acquireForTask(taskId: string): Promise<void> {
+    if (this.heldTaskIds.has(taskId) || !this.runningTaskIds.has(taskId)) {
       return;
     }
 
-    const result = await keepAwakeService.acquireWithResult();
-    if (!result.success) {
+    if (!(await keepAwakeService.acquireForTask(taskId))) {
       return;
     }
 
-    this.setHeldReference(true);
+    this.heldTaskIds.add(taskId);
+    this.emit();
   }
 
-  private async releaseForRunningTasks(): Promise<void> {
-    if (this.refCount === 0) {
+  private async releaseForTask(taskId: string): Promise<void> {
+    if (!this.heldTaskIds.has(taskId) || this.runningTaskIds.has(taskId)) {
       return;
     }
 
-    const result = await keepAwakeService.releaseWithResult();
-    if (!result.success) {
+    if (!(await keepAwakeService.releaseForTask(taskId))) {
       return;
     }
 
-    this.setHeldReference(false);
+    this.heldTaskIds.delete(taskId);
+    this.emit();
   }
 }
 
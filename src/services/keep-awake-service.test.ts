@@ -152,6 +152,26 @@ describe('KeepAwakeService', () => {
     });
   });
 
+  describe('task references', () => {
+    it('should acquire and release by task id', async () => {
+      vi.mocked(invoke).mockResolvedValue(true);
+      vi.mocked(platform).mockResolvedValue('macos');
+
+      expect(await service.acquireForTask('task-1')).toBe(true);
+      expect(invoke).toHaveBeenCalledWith('keep_awake_acquire_for_task', { taskId: 'task-1' });
+
+      expect(await service.releaseForTask('task-1')).toBe(true);
+      expect(invoke).toHaveBeenCalledWith('keep_awake_release_for_task', { taskId: 'task-1' });
+    });
+
+    it('should report a failed task acquire', async () => {
+      vi.mocked(invoke).mockRejectedValue(new Error('Plugin error'));
+      vi.mocked(platform).mockResolvedValue('macos');
+
+      expect(await service.acquireForTask('task-1')).toBe(false);
+    });
+  });
+
   describe('release', () => {
     it('should call keep_awake_release and return true on last release', async () => {
       vi.mocked(invoke).mockResolvedValue(true);
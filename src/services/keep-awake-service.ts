@@ -197,6 +197,56 @@ export class KeepAwakeService {
     return wasLast;
   }
 
+  /**
+   * Acquire sleep prevention on behalf of a task. The backend keys the
+   * reference by task id, so a task never holds more than one.
+   *
+   * Returns true if the task holds a reference afterwards
+   */
+  public async acquireForTask(taskId: string): Promise<boolean> {
+    try {
+      if (!(await this.isSupported())) {
+        logger.warn('[KeepAwakeService] Platform not supported:', this.currentPlatform);
+        return false;
+      }
+
+      const acquired = await invoke<boolean>('keep_awake_acquire_for_task', { taskId });
+      if (acquired) {
+        this.refCount += 1;
+        this.isPreventing = true;
+      }
+      return true;
+    } catch (error) {
+      const t = this.getTranslations().KeepAwake;
+      logger.error('[KeepAwakeService] Failed to acquire sleep prevention for task:', error);
+      this.showToast('error', t.error);
+      return false;
+    }
+  }
+
+  /**
+   * Release the sleep prevention held by a task when it completes, errors, or is stopped
+   *
+   * Returns true if the task no longer holds a reference
+   */
+  public async releaseForTask(taskId: string): Promise<boolean> {
+    try {
+      if (!(await this.isSupported())) {
+        return false;
+      }
+
+      const released = await invoke<boolean>('keep_awake_release_for_task', { taskId });
+      if (released && this.refCount > 0) {
+        this.refCount -= 1;
+      }
+      this.isPreventing = this.refCount > 0;
+      return true;
+    } catch (error) {
+      logger.error('[KeepAwakeService] Failed to release sleep prevention for task:', error);
+      return false;
+    }
+  }
+
   /**
    * Get current reference count
    */
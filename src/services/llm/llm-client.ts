@@ -26,6 +26,7 @@ import type {
   PromptEnhancementRequest,
   PromptEnhancementResult,
   ProviderConfig,
+  SessionCost,
   StreamEvent,
   StreamResponse,
   StreamTextRequest,
@@ -35,6 +36,29 @@ import type {
   TranscriptionResponse,
 } from './types';
 
+/** Outcome of an OAuth disconnect; local tokens are cleared in every case */
+export type OAuthDisconnectResult = {
+  revocation: 'revoked' | 'unsupported' | 'noToken' | 'failed';
+  error?: string;
+};
+
+export type ProviderHealth = {
+  providerId: string;
+  reachable: boolean;
+  authenticated: boolean;
+  latencyMs: number;
+  error?: string;
+};
+
+/** A connected OAuth account; `default` is the provider's regular connection */
+export type OAuthAccountStatus = {
+  label: string;
+  expiresAt?: number;
+  accountId?: string;
+  hasRefreshToken: boolean;
+  isActive: boolean;
+};
+
 export type StreamTextResult = {
   requestId: string;
   events: AsyncGenerator<StreamEvent, void, unknown>;
@@ -184,6 +208,11 @@ export class LlmClient {
     return invoke<boolean>('llm_is_model_available', { modelIdentifier });
   }
 
+  /** Ping every configured provider with its credentials */
+  async checkProvidersHealth(): Promise<ProviderHealth[]> {
+    return invoke<ProviderHealth[]>('llm_check_providers_health');
+  }
+
   async transcribeAudio(request: TranscriptionRequest): Promise<TranscriptionResponse> {
     return invoke<TranscriptionResponse>('llm_transcribe_audio', { request });
   }
@@ -194,6 +223,10 @@ export class LlmClient {
     return invoke<CalculateCostResult>('llm_calculate_cost', { request });
   }
 
+  async getSessionCost(sessionId: string): Promise<SessionCost> {
+    return invoke<SessionCost>('llm_get_session_cost', { sessionId });
+  }
+
   async getCompletion(context: CompletionContext): Promise<CompletionResult> {
     return invoke<CompletionResult>('llm_get_completion', { context });
   }
@@ -301,12 +334,12 @@ export class LlmClient {
     return invoke('llm_openai_oauth_refresh_from_store');
   }
 
-  async disconnectClaudeOAuth(): Promise<void> {
-    await invoke('llm_claude_oauth_disconnect');
+  async disconnectClaudeOAuth(): Promise<OAuthDisconnectResult> {
+    return invoke('llm_claude_oauth_disconnect');
   }
 
-  async disconnectOpenAIOAuth(): Promise<void> {
-    await invoke('llm_openai_oauth_disconnect');
+  async disconnectOpenAIOAuth(): Promise<OAuthDisconnectResult> {
+    return invoke('llm_openai_oauth_disconnect');
   }
 
   async startGitHubCopilotOAuthDeviceCode(params: { enterpriseUrl?: string }): Promise<{
@@ -344,8 +377,8 @@ export class LlmClient {
     return invoke('llm_github_copilot_oauth_refresh');
   }
 
-  async disconnectGitHubCopilotOAuth(): Promise<void> {
-    await invoke('llm_github_copilot_oauth_disconnect');
+  async disconnectGitHubCopilotOAuth(): Promise<OAuthDisconnectResult> {
+    return invoke('llm_github_copilot_oauth_disconnect');
   }
 
   async getGitHubCopilotOAuthTokens(): Promise<{
@@ -361,12 +394,14 @@ export class LlmClient {
     anthropic?: {
       expiresAt?: number | null;
       isConnected?: boolean | null;
+      accounts?: OAuthAccountStatus[] | null;
     } | null;
     openai?: {
       expiresAt?: number | null;
       accountId?: string | null;
       isConnected?: boolean | null;
       hasRefreshToken?: boolean | null;
+      accounts?: OAuthAccountStatus[] | null;
     } | null;
     githubCopilot?: {
       isConnected?: boolean | null;
@@ -375,6 +410,23 @@ export class LlmClient {
     return invoke('llm_oauth_status');
   }
 
+  /** Save the current OAuth connection under `label` so another account can be connected */
+  async saveOAuthAccount(providerId: string, label: string): Promise<OAuthAccountStatus[]> {
+    return invoke('llm_oauth_save_account', { providerId, label });
+  }
+
+  async removeOAuthAccount(providerId: string, label: string): Promise<OAuthAccountStatus[]> {
+    return invoke('llm_oauth_remove_account', { providerId, label });
+  }
+
+  /** Account used by requests without `oauthAccount`; null selects the default account */
+  async setActiveOAuthAccount(
+    providerId: string,
+    label: string | null
+  ): Promise<OAuthAccountStatus[]> {
+    return invoke('llm_oauth_set_active_account', { providerId, label });
+  }
+
   async listMcpTools(): Promise<
     Array<{
       id: string;
@@ -95,12 +95,17 @@ export type StreamTextRequest = {
   topK?: number | null;
   providerOptions?: ProviderOptions;
   requestId?: string | null;
+  sessionId?: string | null;
   traceContext?: TraceContext | null;
   conversationMode?: ConversationMode | null;
   inputMode?: InputMode | null;
   previousResponseId?: string | null;
   transportSessionId?: string | null;
   allowTransportFallback?: boolean | null;
+  oauthAccount?: string | null;
+  disableGlobalSystemPrompt?: boolean | null;
+  seed?: number | null;
+  stop?: string[] | null;
   continuationContext?: ContinuationContext | null;
 };
 
@@ -147,6 +152,8 @@ export type StreamEvent =
       from: 'websocket' | 'responses-chained';
       to: 'http-sse' | 'stateless' | 'fresh-websocket-baseline';
     }
+  | { type: 'model-fallback'; from: string; to: string; reason: string }
+  | { type: 'context-compacted'; messages: Message[]; tokensBefore: number; tokensAfter: number }
   | {
       type: 'usage';
       input_tokens: number;
@@ -262,6 +269,10 @@ export type GitMessageContext = {
   diffText: string;
   model?: string | null;
   fallbackModels?: string[] | null;
+  style?: 'plain' | 'conventional';
+  maxSubjectLength?: number | null;
+  includeBody?: boolean;
+  language?: string | null;
 };
 
 export type GitMessageResult = {
@@ -286,9 +297,32 @@ export type CalculateCostResult = {
   cost: number;
 };
 
+export type ModelCost = {
+  model: string;
+  requestCount: number;
+  inputTokens: number;
+  outputTokens: number;
+  /** Spend of the priced requests */
+  cost: number;
+  /** Requests whose cost is unknown because the model had no pricing */
+  unpricedRequestCount: number;
+};
+
+export type SessionCost = {
+  sessionId: string;
+  requestCount: number;
+  inputTokens: number;
+  outputTokens: number;
+  cost: number;
+  /** When non-zero, `cost` is a lower bound */
+  unpricedRequestCount: number;
+  byModel: ModelCost[];
+};
+
 export type TitleGenerationRequest = {
   userInput: string;
   language?: string | null;
+  maxLength?: number | null;
   model?: string | null;
   fallbackModels?: string[] | null;
 };
@@ -317,6 +351,16 @@ export type ModelConfig = {
   providerMappings?: Record<string, string> | null;
   pricing?: ModelPricing | null;
   contextLength?: number | null;
+  capabilities?: ModelCapabilities | null;
+  contextWindow?: number | null;
+};
+
+export type ModelCapabilities = {
+  vision: boolean;
+  tools: boolean;
+  reasoning: boolean;
+  audio: boolean;
+  jsonMode: boolean;
 };
 
 export type ModelPricing = {
@@ -332,6 +376,7 @@ export type PromptEnhancementRequest = {
   conversationHistory?: string | null;
   enableContextExtraction: boolean;
   model?: string | null;
+  targetLanguage?: string | null;
 };
 
 export type PromptEnhancementResult = {
@@ -11,6 +11,7 @@ import {
 } from '@/lib/usage-utils';
 
 const OPENAI_USAGE_COMMAND = 'llm_openai_oauth_usage';
+const OPENAI_USAGE_HISTORY_COMMAND = 'llm_openai_usage_history';
 
 /**
  * API response structure for rate limit window
@@ -146,5 +147,27 @@ export async function fetchOpenAIUsage(): Promise<OpenAIUsageData> {
   }
 }
 
+/**
+ * Daily usage snapshot persisted by the backend (latest poll of the UTC day)
+ */
+export interface OpenAIUsageSnapshot {
+  day: string; // YYYY-MM-DD (UTC)
+  capturedAt: number; // Unix timestamp (ms)
+  usage: OpenAIApiResponse;
+}
+
+/**
+ * Fetch daily usage snapshots for the last `days` days, oldest first.
+ * The backend refreshes today's snapshot before returning.
+ */
+export async function fetchOpenAIUsageHistory(days: number): Promise<OpenAIUsageSnapshot[]> {
+  try {
+    return await invoke<OpenAIUsageSnapshot[]>(OPENAI_USAGE_HISTORY_COMMAND, { days });
+  } catch (error) {
+    logger.error('[OpenAIUsage] Failed to fetch usage history:', error);
+    throw error;
+  }
+}
+
 // Re-export utility functions for backward compatibility
 export { getTimeUntilReset, getWeeklyResetDisplay, getUsageLevel, getRemainingPercentage };
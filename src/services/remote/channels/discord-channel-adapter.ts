@@ -0,0 +1,184 @@
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import { logger } from '@/lib/logger';
+import type {
+  RemoteChannelAdapter,
+  RemoteChannelCapabilities,
+  RemoteChannelStatus,
+} from '@/services/remote/remote-channel-types';
+import { gatewayCommandText } from '@/services/remote/remote-text-utils';
+import type {
+  DiscordEditMessageRequest,
+  DiscordGatewayStatus,
+  DiscordInboundCommand,
+  DiscordInboundMessage,
+  DiscordRemoteConfig,
+  DiscordSendMessageRequest,
+  DiscordSendMessageResponse,
+  RemoteEditMessageRequest,
+  RemoteInboundMessage,
+  RemoteSendMessageRequest,
+  RemoteSendMessageResponse,
+} from '@/types/remote-control';
+
+function commandToRemoteInboundMessage(command: DiscordInboundCommand): RemoteInboundMessage {
+  return {
+    channelId: 'discord',
+    chatId: command.channelId,
+    messageId: command.messageId,
+    text: gatewayCommandText(command.command),
+    username: null,
+    firstName: null,
+    lastName: null,
+    date: Date.now(),
+    attachments: [],
+  };
+}
+
+function toRemoteInboundMessage(message: DiscordInboundMessage): RemoteInboundMessage {
+  const date = Date.parse(message.timestamp);
+  return {
+    channelId: 'discord',
+    chatId: message.channelId,
+    messageId: message.messageId,
+    text: message.text,
+    username: message.username,
+    firstName: message.displayName ?? null,
+    lastName: null,
+    date: Number.isNaN(date) ? Date.now() : date,
+    attachments: [],
+  };
+}
+
+function toDiscordSendMessageRequest(request: RemoteSendMessageRequest): DiscordSendMessageRequest {
+  return {
+    channelId: request.chatId,
+    text: request.text,
+    replyToMessageId: request.replyToMessageId ?? null,
+  };
+}
+
+function toDiscordEditMessageRequest(request: RemoteEditMessageRequest): DiscordEditMessageRequest {
+  return {
+    channelId: request.chatId,
+    messageId: request.messageId,
+    text: request.text,
+  };
+}
+
+export class DiscordChannelAdapter implements RemoteChannelAdapter {
+  readonly channelId = 'discord' as const;
+  readonly capabilities: RemoteChannelCapabilities = {
+    supportsEdit: true,
+    supportsReply: true,
+    supportsMediaSend: false,
+    supportsVoiceInput: false,
+    supportsProactiveMessage: true,
+    maxMessageLength: 2000,
+    streamMode: 'edit',
+  };
+  private inboundUnlisten: UnlistenFn | null = null;
+  private commandUnlisten: UnlistenFn | null = null;
+
+  async start(): Promise<void> {
+    // The Discord config is persisted by the gateway itself
+    const config = await this.getConfig();
+    if (!config.enabled || !config.token) {
+      logger.info('[DiscordChannelAdapter] Remote control disabled or missing token');
+      return;
+    }
+
+    logger.info('[DiscordChannelAdapter] Starting gateway');
+    await invoke('discord_start');
+  }
+
+  async stop(): Promise<void> {
+    logger.info('[DiscordChannelAdapter] Stopping gateway');
+    await invoke('discord_stop');
+  }
+
+  onInbound(handler: (message: RemoteInboundMessage) => void): () => void {
+    listen<DiscordInboundMessage>('discord-inbound-message', (event) => {
+      logger.debug('[DiscordChannelAdapter] Inbound event received', event.payload);
+      handler(toRemoteInboundMessage(event.payload));
+    })
+      .then((unlisten) => {
+        this.inboundUnlisten = unlisten;
+      })
+      .catch((error) => {
+        logger.warn('[DiscordChannelAdapter] Failed to listen inbound', error);
+      });
+
+    // Control commands parsed by the gateway go through the same command handler
+    listen<DiscordInboundCommand>('discord-inbound-command', (event) => {
+      logger.debug('[DiscordChannelAdapter] Inbound command received', event.payload);
+      handler(commandToRemoteInboundMessage(event.payload));
+    })
+      .then((unlisten) => {
+        this.commandUnlisten = unlisten;
+      })
+      .catch((error) => {
+        logger.warn('[DiscordChannelAdapter] Failed to listen inbound commands', error);
+      });
+
+    return () => {
+      if (this.inboundUnlisten) {
+        this.inboundUnlisten();
+        this.inboundUnlisten = null;
+      }
+      if (this.commandUnlisten) {
+        this.commandUnlisten();
+        this.commandUnlisten = null;
+      }
+    };
+  }
+
+  async sendMessage(request: RemoteSendMessageRequest): Promise<RemoteSendMessageResponse> {
+    logger.debug('[DiscordChannelAdapter] sendMessage', {
+      chatId: request.chatId,
+      textLen: request.text.length,
+    });
+    const response = await invoke<DiscordSendMessageResponse>('discord_send_message', {
+      request: toDiscordSendMessageRequest(request),
+    });
+    return { messageId: response.messageId };
+  }
+
+  async editMessage(request: RemoteEditMessageRequest): Promise<void> {
+    logger.debug('[DiscordChannelAdapter] editMessage', {
+      chatId: request.chatId,
+      messageId: request.messageId,
+      textLen: request.text.length,
+    });
+    await invoke('discord_edit_message', {
+      request: toDiscordEditMessageRequest(request),
+    });
+  }
+
+  async startTyping(chatId: string, _messageId: string): Promise<void> {
+    await invoke('discord_start_typing', { channelId: chatId });
+  }
+
+  async stopTyping(chatId: string): Promise<void> {
+    await invoke('discord_stop_typing', { channelId: chatId });
+  }
+
+  async getStatus(): Promise<RemoteChannelStatus> {
+    const status = await invoke<DiscordGatewayStatus>('discord_get_status');
+    return {
+      running: status.running,
+      lastPollAtMs: status.lastEventAtMs ?? null,
+      lastError: status.lastError ?? null,
+      lastErrorAtMs: status.lastErrorAtMs ?? null,
+      details: {
+        connected: status.connected,
+        botUserId: status.botUserId ?? null,
+        backoffMs: status.backoffMs,
+      },
+    };
+  }
+
+  async getConfig(): Promise<DiscordRemoteConfig> {
+    return invoke('discord_get_config');
+  }
+}
@@ -6,10 +6,12 @@ import type {
   RemoteChannelCapabilities,
   RemoteChannelStatus,
 } from '@/services/remote/remote-channel-types';
+import { gatewayCommandText } from '@/services/remote/remote-text-utils';
 import { useSettingsStore } from '@/stores/settings-store';
 import type {
   FeishuEditMessageRequest,
   FeishuGatewayStatus,
+  FeishuInboundCommand,
   FeishuInboundMessage,
   FeishuRemoteAttachment,
   FeishuRemoteConfig,
@@ -35,6 +37,20 @@ function toRemoteAttachment(attachment: FeishuRemoteAttachment): RemoteAttachmen
   };
 }
 
+function commandToRemoteInboundMessage(command: FeishuInboundCommand): RemoteInboundMessage {
+  return {
+    channelId: 'feishu',
+    chatId: command.openId,
+    messageId: command.messageId,
+    text: gatewayCommandText(command.command),
+    username: null,
+    firstName: null,
+    lastName: null,
+    date: Date.now(),
+    attachments: [],
+  };
+}
+
 function toRemoteInboundMessage(message: FeishuInboundMessage): RemoteInboundMessage {
   return {
     channelId: 'feishu',
@@ -77,6 +93,7 @@ export class FeishuChannelAdapter implements RemoteChannelAdapter {
     streamMode: 'append',
   };
   private inboundUnlisten: UnlistenFn | null = null;
+  private commandUnlisten: UnlistenFn | null = null;
 
   async start(): Promise<void> {
     const settings = useSettingsStore.getState();
@@ -109,11 +126,27 @@ export class FeishuChannelAdapter implements RemoteChannelAdapter {
         logger.warn('[FeishuChannelAdapter] Failed to listen inbound', error);
       });
 
+    // Control commands parsed by the gateway go through the same command handler
+    listen<FeishuInboundCommand>('feishu-inbound-command', (event) => {
+      logger.debug('[FeishuChannelAdapter] Inbound command received', event.payload);
+      handler(commandToRemoteInboundMessage(event.payload));
+    })
+      .then((unlisten) => {
+        this.commandUnlisten = unlisten;
+      })
+      .catch((error) => {
+        logger.warn('[FeishuChannelAdapter] Failed to listen inbound commands', error);
+      });
+
     return () => {
       if (this.inboundUnlisten) {
         this.inboundUnlisten();
         this.inboundUnlisten = null;
       }
+      if (this.commandUnlisten) {
+        this.commandUnlisten();
+        this.commandUnlisten = null;
+      }
     };
   }
 
@@ -139,6 +172,14 @@ export class FeishuChannelAdapter implements RemoteChannelAdapter {
     });
   }
 
+  async startTyping(chatId: string, messageId: string): Promise<void> {
+    await invoke('feishu_start_typing', { openId: chatId, messageId });
+  }
+
+  async stopTyping(chatId: string): Promise<void> {
+    await invoke('feishu_stop_typing', { openId: chatId });
+  }
+
   async getStatus(): Promise<RemoteChannelStatus> {
     const status = await invoke<FeishuGatewayStatus>('feishu_get_status');
     return {
@@ -167,6 +208,7 @@ export class FeishuChannelAdapter implements RemoteChannelAdapter {
         .split(',')
         .map((id) => id.trim())
         .filter((id) => id.length > 0),
+      allowAll: settings.feishu_remote_allow_all,
     };
   }
 }
@@ -38,6 +38,7 @@ describe('telegram-channel-adapter', () => {
       telegram_remote_enabled: false,
       telegram_remote_token: ' bot-token ',
       telegram_remote_allowed_chats: '123, 456',
+      telegram_remote_allow_all: false,
       telegram_remote_poll_timeout: '25',
     });
 
@@ -49,6 +50,7 @@ describe('telegram-channel-adapter', () => {
         enabled: false,
         token: 'bot-token',
         allowedChatIds: [123, 456],
+        allowAll: false,
         pollTimeoutSecs: 25,
       },
     });
@@ -61,6 +63,7 @@ describe('telegram-channel-adapter', () => {
       telegram_remote_enabled: true,
       telegram_remote_token: ' bot-token ',
       telegram_remote_allowed_chats: '',
+      telegram_remote_allow_all: true,
       telegram_remote_poll_timeout: '30',
     });
 
@@ -72,6 +75,7 @@ describe('telegram-channel-adapter', () => {
         enabled: true,
         token: 'bot-token',
         allowedChatIds: [],
+        allowAll: true,
         pollTimeoutSecs: 30,
       },
     });
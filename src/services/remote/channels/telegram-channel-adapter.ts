@@ -6,6 +6,7 @@ import type {
   RemoteChannelCapabilities,
   RemoteChannelStatus,
 } from '@/services/remote/remote-channel-types';
+import { gatewayCommandText } from '@/services/remote/remote-text-utils';
 import { parseAllowedChatIds } from '@/services/remote/telegram-remote-utils';
 import { useSettingsStore } from '@/stores/settings-store';
 import type {
@@ -16,6 +17,7 @@ import type {
   RemoteSendMessageResponse,
   TelegramEditMessageRequest,
   TelegramGatewayStatus,
+  TelegramInboundCommand,
   TelegramInboundMessage,
   TelegramRemoteAttachment,
   TelegramRemoteConfig,
@@ -36,6 +38,20 @@ function toRemoteAttachment(attachment: TelegramRemoteAttachment): RemoteAttachm
   };
 }
 
+function commandToRemoteInboundMessage(command: TelegramInboundCommand): RemoteInboundMessage {
+  return {
+    channelId: 'telegram',
+    chatId: String(command.chatId),
+    messageId: String(command.messageId),
+    text: gatewayCommandText(command.command),
+    username: null,
+    firstName: null,
+    lastName: null,
+    date: Date.now(),
+    attachments: [],
+  };
+}
+
 function toRemoteInboundMessage(message: TelegramInboundMessage): RemoteInboundMessage {
   return {
     channelId: 'telegram',
@@ -88,6 +104,7 @@ export class TelegramChannelAdapter implements RemoteChannelAdapter {
     streamMode: 'edit',
   };
   private inboundUnlisten: UnlistenFn | null = null;
+  private commandUnlisten: UnlistenFn | null = null;
 
   async start(): Promise<void> {
     const settings = useSettingsStore.getState();
@@ -125,11 +142,27 @@ export class TelegramChannelAdapter implements RemoteChannelAdapter {
         logger.warn('[TelegramChannelAdapter] Failed to listen inbound', error);
       });
 
+    // Control commands parsed by the gateway go through the same command handler
+    listen<TelegramInboundCommand>('telegram-inbound-command', (event) => {
+      logger.debug('[TelegramChannelAdapter] Inbound command received', event.payload);
+      handler(commandToRemoteInboundMessage(event.payload));
+    })
+      .then((unlisten) => {
+        this.commandUnlisten = unlisten;
+      })
+      .catch((error) => {
+        logger.warn('[TelegramChannelAdapter] Failed to listen inbound commands', error);
+      });
+
     return () => {
       if (this.inboundUnlisten) {
         this.inboundUnlisten();
         this.inboundUnlisten = null;
       }
+      if (this.commandUnlisten) {
+        this.commandUnlisten();
+        this.commandUnlisten = null;
+      }
     };
   }
 
@@ -156,6 +189,14 @@ export class TelegramChannelAdapter implements RemoteChannelAdapter {
     });
   }
 
+  async startTyping(chatId: string): Promise<void> {
+    await invoke('telegram_start_typing', { chatId: Number(chatId) });
+  }
+
+  async stopTyping(chatId: string): Promise<void> {
+    await invoke('telegram_stop_typing', { chatId: Number(chatId) });
+  }
+
   async getStatus(): Promise<RemoteChannelStatus> {
     const status = await invoke<TelegramGatewayStatus>('telegram_get_status');
     return {
@@ -181,6 +222,7 @@ export class TelegramChannelAdapter implements RemoteChannelAdapter {
       enabled: settings.telegram_remote_enabled,
       token: settings.telegram_remote_token.trim(),
       allowedChatIds: parseAllowedChatIds(settings.telegram_remote_allowed_chats),
+      allowAll: settings.telegram_remote_allow_all,
       pollTimeoutSecs: Number(settings.telegram_remote_poll_timeout || '25'),
     };
   }
@@ -128,6 +128,30 @@ class RemoteChannelManager {
     await adapter.editMessage(request);
   }
 
+  async startTyping(channelId: RemoteChannelId, chatId: string, messageId: string): Promise<void> {
+    const adapter = this.adapters.get(channelId);
+    if (!adapter?.startTyping) {
+      return;
+    }
+    try {
+      await adapter.startTyping(chatId, messageId);
+    } catch (error) {
+      logger.warn('[RemoteChannelManager] Failed to start typing', { channelId, chatId, error });
+    }
+  }
+
+  async stopTyping(channelId: RemoteChannelId, chatId: string): Promise<void> {
+    const adapter = this.adapters.get(channelId);
+    if (!adapter?.stopTyping) {
+      return;
+    }
+    try {
+      await adapter.stopTyping(chatId);
+    } catch (error) {
+      logger.warn('[RemoteChannelManager] Failed to stop typing', { channelId, chatId, error });
+    }
+  }
+
   getRegisteredChannels(): RemoteChannelId[] {
     return Array.from(this.adapters.keys());
   }
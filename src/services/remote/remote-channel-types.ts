@@ -33,5 +33,8 @@ export interface RemoteChannelAdapter {
   onInbound: (handler: (message: RemoteInboundMessage) => void) => () => void;
   sendMessage: (request: RemoteSendMessageRequest) => Promise<RemoteSendMessageResponse>;
   editMessage: (request: RemoteEditMessageRequest) => Promise<void>;
+  /** Show a typing indicator in reply to `messageId` until stopped or a message is sent */
+  startTyping?: (chatId: string, messageId: string) => Promise<void>;
+  stopTyping?: (chatId: string) => Promise<void>;
   getStatus?: () => Promise<RemoteChannelStatus>;
 }
@@ -10,6 +10,8 @@ const mocks = vi.hoisted(() => {
   const onInbound = vi.fn().mockReturnValue(inboundUnsubscribe);
   const sendMessage = vi.fn().mockResolvedValue({ messageId: '1' });
   const editMessage = vi.fn().mockResolvedValue(undefined);
+  const startTyping = vi.fn().mockResolvedValue(undefined);
+  const stopTyping = vi.fn().mockResolvedValue(undefined);
   const getStatus = vi.fn().mockResolvedValue(null);
   const getCapabilities = vi.fn().mockImplementation((channelId: string) => {
     if (channelId === 'feishu' || channelId === 'wechat') {
@@ -103,6 +105,8 @@ const mocks = vi.hoisted(() => {
     onInbound,
     sendMessage,
     editMessage,
+    startTyping,
+    stopTyping,
     getStatus,
     getCapabilities,
     executionSubscribe,
@@ -128,6 +132,8 @@ vi.mock('@/services/remote/remote-channel-manager', () => ({
     onInbound: mocks.onInbound,
     sendMessage: mocks.sendMessage,
     editMessage: mocks.editMessage,
+    startTyping: mocks.startTyping,
+    stopTyping: mocks.stopTyping,
     getStatus: mocks.getStatus,
     getCapabilities: mocks.getCapabilities,
   },
@@ -321,6 +327,7 @@ describe('remote-chat-service', () => {
     await Promise.resolve();
 
     expect(mocks.sendMessage).toHaveBeenCalled();
+    expect(mocks.stopTyping).toHaveBeenCalledWith('telegram', '1');
     vi.useRealTimers();
   });
 
@@ -399,6 +406,26 @@ describe('remote-chat-service', () => {
     );
   });
 
+  it('handles /stop without an active task', async () => {
+    await remoteChatService.start();
+
+    await remoteChatService.handleInboundMessage({
+      channelId: 'telegram',
+      chatId: '1',
+      messageId: 'm-stop',
+      text: '/stop',
+      date: Date.now(),
+    });
+
+    expect(mocks.sendMessage).toHaveBeenCalledWith(
+      expect.objectContaining({
+        channelId: 'telegram',
+        chatId: '1',
+        text: 'noActiveTask',
+      })
+    );
+  });
+
   it('switches project with /project', async () => {
     mocks.databaseService.getProject.mockResolvedValueOnce({
       id: 'project-2',
@@ -187,6 +187,12 @@ class RemoteChatService {
       return;
     }
 
+    if (command === '/stop') {
+      logger.info('[RemoteChatService] Stop command');
+      await this.handleStop(message);
+      return;
+    }
+
     if (command === '/status') {
       logger.info('[RemoteChatService] Status command');
       await this.handleStatus(message);
@@ -330,17 +336,24 @@ class RemoteChatService {
 
     const systemPrompt = typeof agent?.systemPrompt === 'string' ? agent.systemPrompt : undefined;
 
-    await executionService.startExecution({
-      taskId: session.taskId,
-      messages,
-      model,
-      fallbackModels: resolvedFallbackModels,
-      systemPrompt,
-      tools: agent?.tools,
-      agentId,
-      isNewTask: false,
-      userMessage: promptText,
-    });
+    // Typing stops once the execution leaves the running state
+    await remoteChannelManager.startTyping(message.channelId, message.chatId, message.messageId);
+    try {
+      await executionService.startExecution({
+        taskId: session.taskId,
+        messages,
+        model,
+        fallbackModels: resolvedFallbackModels,
+        systemPrompt,
+        tools: agent?.tools,
+        agentId,
+        isNewTask: false,
+        userMessage: promptText,
+      });
+    } catch (error) {
+      await remoteChannelManager.stopTyping(message.channelId, message.chatId);
+      throw error;
+    }
 
     logger.info('[RemoteChatService] Execution started', {
       taskId: session.taskId,
@@ -737,6 +750,7 @@ class RemoteChatService {
         if (execution.status !== 'running') {
           if (session.lastStreamStatus !== execution.status) {
             session.lastStreamStatus = execution.status;
+            remoteChannelManager.stopTyping(session.channelId, session.chatId).catch(console.error);
             this.flushFinalStream(sessionKey, session).catch(console.error);
           }
           continue;
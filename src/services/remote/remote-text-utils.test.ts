@@ -1,5 +1,6 @@
 import { describe, expect, it } from 'vitest';
 import {
+  gatewayCommandText,
   getRemoteMessageLimit,
   isDuplicateRemoteMessage,
   normalizeRemoteCommand,
@@ -10,6 +11,7 @@ describe('remote-text-utils', () => {
   it('returns per-channel limits', () => {
     expect(getRemoteMessageLimit('telegram')).toBe(4096);
     expect(getRemoteMessageLimit('feishu')).toBe(4000);
+    expect(getRemoteMessageLimit('discord')).toBe(2000);
   });
 
   it('splits text using channel limit', () => {
@@ -39,4 +41,11 @@ describe('remote-text-utils', () => {
   it('passes through non-commands', () => {
     expect(normalizeRemoteCommand('hello')).toBe('hello');
   });
+
+  it('converts gateway commands to command text', () => {
+    expect(gatewayCommandText({ type: 'new', prompt: 'fix the build' })).toBe('/new fix the build');
+    expect(gatewayCommandText({ type: 'new', prompt: null })).toBe('/new');
+    expect(gatewayCommandText({ type: 'stop' })).toBe('/stop');
+    expect(gatewayCommandText({ type: 'model', modelId: 'gpt-4o' })).toBe('/model gpt-4o');
+  });
 });
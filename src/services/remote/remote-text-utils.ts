@@ -1,8 +1,9 @@
-import type { RemoteChannelId } from '@/types/remote-control';
+import type { GatewayCommand, RemoteChannelId } from '@/types/remote-control';
 
 const DEFAULT_CHUNK_LIMIT = 4096;
 const DEFAULT_DEDUP_TTL_MS = 5 * 60 * 1000;
 
+const DISCORD_MESSAGE_LIMIT = 2000;
 const FEISHU_MESSAGE_LIMIT = 4000;
 const TELEGRAM_MESSAGE_LIMIT = 4096;
 const WECHAT_MESSAGE_LIMIT = 2000;
@@ -71,6 +72,9 @@ function splitByPreference(text: string, limit: number): string[] {
 }
 
 export function getRemoteMessageLimit(channelId: RemoteChannelId): number {
+  if (channelId === 'discord') {
+    return DISCORD_MESSAGE_LIMIT;
+  }
   if (channelId === 'feishu') {
     return FEISHU_MESSAGE_LIMIT;
   }
@@ -103,3 +107,15 @@ export function normalizeRemoteCommand(input: string): string {
   const rest = trimmed.slice(match[0]?.length ?? 0).trim();
   return rest ? `/${command} ${rest}` : `/${command}`;
 }
+
+/** Slash-command text handled by the remote chat service for a gateway command */
+export function gatewayCommandText(command: GatewayCommand): string {
+  switch (command.type) {
+    case 'new':
+      return command.prompt ? `/new ${command.prompt}` : '/new';
+    case 'stop':
+      return '/stop';
+    case 'model':
+      return `/model ${command.modelId}`;
+  }
+}
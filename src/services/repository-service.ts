@@ -37,6 +37,12 @@ interface SearchResult {
   matches: SearchMatch[];
 }
 
+export interface SearchDefaults {
+  exclude_dirs: string[];
+  ignore_globs: string[];
+  languages: { language: string; extensions: string[] }[];
+}
+
 interface CachedFile {
   content: string;
   modifiedTime: number;
@@ -393,6 +399,10 @@ export class RepositoryService {
     }
   }
 
+  async getSearchDefaults(): Promise<SearchDefaults> {
+    return invoke<SearchDefaults>('get_search_defaults');
+  }
+
   async writeFile(filePath: string, content: string): Promise<void> {
     try {
       // Ensure directory exists
@@ -13,6 +13,7 @@ interface PtySpawnResult {
 interface PtyOutput {
   pty_id: string;
   data: string;
+  offset?: number;
 }
 
 interface PtyCloseEvent {
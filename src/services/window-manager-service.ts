@@ -23,6 +23,10 @@ export interface WindowInfo {
   project_id?: string;
   root_path?: string;
   title: string;
+  x?: number;
+  y?: number;
+  width?: number;
+  height?: number;
 }
 
 export class WindowManagerService {
@@ -89,6 +93,32 @@ export class WindowManagerService {
     }
   }
 
+  /**
+   * Reopen the project windows from the last session (skipped when restore is
+   * disabled in settings). Returns the labels of the restored windows.
+   */
+  static async restoreWindows(): Promise<string[]> {
+    try {
+      return await invoke<string[]>('restore_windows');
+    } catch (error) {
+      logger.error('Failed to restore windows:', error);
+      return [];
+    }
+  }
+
+  /**
+   * Reopen the most recently closed project window
+   * Returns the window label, or null when there is nothing to reopen
+   */
+  static async reopenLastClosedWindow(): Promise<string | null> {
+    try {
+      return await invoke<string | null>('reopen_last_closed_window');
+    } catch (error) {
+      logger.error('Failed to reopen closed window:', error);
+      return null;
+    }
+  }
+
   /**
    * Get current window label
    */
@@ -298,5 +298,38 @@ describe('BackgroundTaskStore', () => {
       ).rejects.toThrow('Boom');
       expect(store.getAllTasks().length).toBe(0);
     });
+
+    it('should store tasks the backend queued as queued', async () => {
+      const store = useBackgroundTaskStore.getState();
+      vi.mocked(invoke).mockResolvedValue({
+        taskId: 'bg-3',
+        pid: 0,
+        outputFile: '/tmp/stdout.log',
+        errorFile: '/tmp/stderr.log',
+        success: true,
+        queued: true,
+      } satisfies SpawnBackgroundTaskResponse);
+
+      await store.spawnTask('sleep 1', 'conv-3', 'tool-3');
+
+      expect(store.getTask('bg-3')?.status).toBe('queued');
+    });
+  });
+
+  describe('refreshTaskStatus', () => {
+    it('should only set endTime once the task has finished', async () => {
+      const store = useBackgroundTaskStore.getState();
+      const invokeMock = vi.mocked(invoke);
+      store.addTask(createMockTask({ taskId: 'bg-4', status: 'queued' }));
+
+      invokeMock.mockResolvedValue({ taskId: 'bg-4', status: 'queued' });
+      await store.refreshTaskStatus('bg-4');
+      expect(store.getTask('bg-4')?.endTime).toBeUndefined();
+
+      invokeMock.mockResolvedValue({ taskId: 'bg-4', status: 'completed', exitCode: 0 });
+      await store.refreshTaskStatus('bg-4');
+      expect(store.getTask('bg-4')?.status).toBe('completed');
+      expect(store.getTask('bg-4')?.endTime).toBeDefined();
+    });
   });
 });
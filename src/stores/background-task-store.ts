@@ -8,8 +8,8 @@ import {
   type BackgroundTask,
   type GetIncrementalOutputResponse,
   type GetTaskStatusResponse,
+  isTerminalTaskStatus,
   type ListTasksResponse,
-  MAX_CONCURRENT_TASKS,
   POLLING_INTERVAL_MS,
   type SpawnBackgroundTaskResponse,
 } from '@/types/background-task';
@@ -104,15 +104,7 @@ export const useBackgroundTaskStore = create<BackgroundTaskState>((set, get) =>
   spawnTask: async (command, conversationTaskId, toolId, cwd, maxTimeoutMs) => {
     logger.info('Spawning background task:', command);
 
-    // Check concurrent task limit
-    const runningTasks = get().getRunningTasks();
-    if (runningTasks.length >= MAX_CONCURRENT_TASKS) {
-      throw new Error(
-        `Maximum concurrent tasks limit reached (${MAX_CONCURRENT_TASKS}). ` +
-          'Please stop some running tasks before starting new ones.'
-      );
-    }
-
+    // The backend enforces MAX_CONCURRENT_TASKS and queues tasks past it
     try {
       const result = await invoke<SpawnBackgroundTaskResponse>('spawn_background_task', {
         request: {
@@ -130,7 +122,7 @@ export const useBackgroundTaskStore = create<BackgroundTaskState>((set, get) =>
         taskId: result.taskId,
         pid: result.pid,
         command,
-        status: 'running',
+        status: result.queued ? 'queued' : 'running',
         startTime: Date.now(),
         outputFile: result.outputFile,
         errorFile: result.errorFile,
@@ -210,7 +202,7 @@ export const useBackgroundTaskStore = create<BackgroundTaskState>((set, get) =>
         exitCode: status.exitCode,
       };
 
-      if (status.status !== 'running') {
+      if (isTerminalTaskStatus(status.status)) {
         updateData.endTime = Date.now();
       }
 
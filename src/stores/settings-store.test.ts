@@ -226,6 +226,62 @@ describe('useSettingsStore.initialize', () => {
     expect(useSettingsStore.getState().memory_global_enabled).toBe(false);
     expect(useSettingsStore.getState().memory_project_enabled).toBe(false);
   });
+
+  it('keeps allow-all for existing remote control installs without an allowlist', async () => {
+    settingsRows.set('telegram_remote_enabled', 'true');
+    settingsRows.set('telegram_remote_allowed_chats', '');
+    settingsRows.set('feishu_remote_enabled', 'true');
+    settingsRows.set('feishu_remote_allowed_open_ids', 'ou_1');
+    vi.resetModules();
+    vi.unmock('@/stores/settings-store');
+
+    const { databaseService } = await import('@/services/database-service');
+    vi.mocked(databaseService.initialize).mockResolvedValue(undefined);
+    vi.mocked(databaseService.getDb).mockResolvedValue(mockDb);
+
+    const { useSettingsStore } = await import('./settings-store');
+
+    await useSettingsStore.getState().initialize();
+
+    expect(useSettingsStore.getState().telegram_remote_allow_all).toBe(true);
+    expect(useSettingsStore.getState().feishu_remote_allow_all).toBe(false);
+  });
+
+  it('does not enable allow-all for installs with remote control disabled', async () => {
+    settingsRows.set('telegram_remote_enabled', 'false');
+    settingsRows.set('telegram_remote_allowed_chats', '');
+    settingsRows.set('feishu_remote_enabled', 'false');
+    settingsRows.set('feishu_remote_allowed_open_ids', '');
+    vi.resetModules();
+    vi.unmock('@/stores/settings-store');
+
+    const { databaseService } = await import('@/services/database-service');
+    vi.mocked(databaseService.initialize).mockResolvedValue(undefined);
+    vi.mocked(databaseService.getDb).mockResolvedValue(mockDb);
+
+    const { useSettingsStore } = await import('./settings-store');
+
+    await useSettingsStore.getState().initialize();
+
+    expect(useSettingsStore.getState().telegram_remote_allow_all).toBe(false);
+    expect(useSettingsStore.getState().feishu_remote_allow_all).toBe(false);
+  });
+
+  it('defaults remote control allow-all to disabled for new installs', async () => {
+    vi.resetModules();
+    vi.unmock('@/stores/settings-store');
+
+    const { databaseService } = await import('@/services/database-service');
+    vi.mocked(databaseService.initialize).mockResolvedValue(undefined);
+    vi.mocked(databaseService.getDb).mockResolvedValue(mockDb);
+
+    const { useSettingsStore } = await import('./settings-store');
+
+    await useSettingsStore.getState().initialize();
+
+    expect(useSettingsStore.getState().telegram_remote_allow_all).toBe(false);
+    expect(useSettingsStore.getState().feishu_remote_allow_all).toBe(false);
+  });
 });
 
 describe('settingsManager.getBatch', () => {
@@ -51,6 +51,7 @@ interface SettingsState {
   telegram_remote_enabled: boolean;
   telegram_remote_token: string;
   telegram_remote_allowed_chats: string;
+  telegram_remote_allow_all: boolean;
   telegram_remote_poll_timeout: string;
   feishu_remote_enabled: boolean;
   feishu_remote_app_id: string;
@@ -58,6 +59,7 @@ interface SettingsState {
   feishu_remote_encrypt_key: string;
   feishu_remote_verification_token: string;
   feishu_remote_allowed_open_ids: string;
+  feishu_remote_allow_all: boolean;
   wechat_remote_enabled: boolean;
   wechat_remote_base_url: string;
   wechat_remote_bot_token: string;
@@ -159,6 +161,8 @@ interface SettingsActions {
   setFeishuRemoteEncryptKey: (value: string) => Promise<void>;
   setFeishuRemoteVerificationToken: (value: string) => Promise<void>;
   setFeishuRemoteAllowedOpenIds: (value: string) => Promise<void>;
+  setTelegramRemoteAllowAll: (enabled: boolean) => Promise<void>;
+  setFeishuRemoteAllowAll: (enabled: boolean) => Promise<void>;
   setWechatRemoteEnabled: (enabled: boolean) => Promise<boolean>;
   setWechatRemoteBaseUrl: (value: string) => Promise<void>;
   setWechatRemoteBotToken: (value: string) => Promise<void>;
@@ -300,6 +304,7 @@ const DEFAULT_SETTINGS: Omit<SettingsState, 'loading' | 'error' | 'isInitialized
   telegram_remote_enabled: false,
   telegram_remote_token: '',
   telegram_remote_allowed_chats: '',
+  telegram_remote_allow_all: false,
   telegram_remote_poll_timeout: '25',
   feishu_remote_enabled: false,
   feishu_remote_app_id: '',
@@ -307,6 +312,7 @@ const DEFAULT_SETTINGS: Omit<SettingsState, 'loading' | 'error' | 'isInitialized
   feishu_remote_encrypt_key: '',
   feishu_remote_verification_token: '',
   feishu_remote_allowed_open_ids: '',
+  feishu_remote_allow_all: false,
   wechat_remote_enabled: false,
   wechat_remote_base_url: 'https://ilinkai.weixin.qq.com',
   wechat_remote_bot_token: '',
@@ -357,6 +363,7 @@ class SettingsDatabase {
       try {
         await databaseService.initialize();
         this.db = await databaseService.getDb();
+        await this.migrateRemoteAllowAll();
         await this.ensureDefaults();
         this.initialized = true;
         logger.info('Settings Database initialized');
@@ -370,6 +377,37 @@ class SettingsDatabase {
     return this.initPromise;
   }
 
+  /**
+   * Gateways used to admit everyone when their allowlist was empty. Installs that
+   * predate the explicit allow-all setting and have the gateway enabled keep that
+   * behavior for an empty allowlist.
+   */
+  private async migrateRemoteAllowAll(): Promise<void> {
+    if (!this.db) return;
+
+    const existing = await this.getBatch([
+      'telegram_remote_enabled',
+      'telegram_remote_allowed_chats',
+      'telegram_remote_allow_all',
+      'feishu_remote_enabled',
+      'feishu_remote_allowed_open_ids',
+      'feishu_remote_allow_all',
+    ]);
+    const migrations: Record<string, string> = {};
+    if (existing.telegram_remote_enabled === 'true' && !existing.telegram_remote_allow_all) {
+      const allowlist = existing.telegram_remote_allowed_chats?.trim() ?? '';
+      migrations.telegram_remote_allow_all = (allowlist === '').toString();
+    }
+    if (existing.feishu_remote_enabled === 'true' && !existing.feishu_remote_allow_all) {
+      const allowlist = existing.feishu_remote_allowed_open_ids?.trim() ?? '';
+      migrations.feishu_remote_allow_all = (allowlist === '').toString();
+    }
+    if (Object.keys(migrations).length > 0) {
+      logger.info('Migrating remote control allow-all settings', migrations);
+      await this.setBatch(migrations);
+    }
+  }
+
   private async ensureDefaults(): Promise<void> {
     if (!this.db) return;
 
@@ -396,6 +434,7 @@ class SettingsDatabase {
       telegram_remote_enabled: 'false',
       telegram_remote_token: '',
       telegram_remote_allowed_chats: '',
+      telegram_remote_allow_all: 'false',
       telegram_remote_poll_timeout: '25',
       feishu_remote_enabled: 'false',
       feishu_remote_app_id: '',
@@ -403,6 +442,7 @@ class SettingsDatabase {
       feishu_remote_encrypt_key: '',
       feishu_remote_verification_token: '',
       feishu_remote_allowed_open_ids: '',
+      feishu_remote_allow_all: 'false',
       wechat_remote_enabled: 'false',
       wechat_remote_base_url: 'https://ilinkai.weixin.qq.com',
       wechat_remote_bot_token: '',
@@ -550,6 +590,7 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
         'telegram_remote_enabled',
         'telegram_remote_token',
         'telegram_remote_allowed_chats',
+        'telegram_remote_allow_all',
         'telegram_remote_poll_timeout',
         'feishu_remote_enabled',
         'feishu_remote_app_id',
@@ -557,6 +598,7 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
         'feishu_remote_encrypt_key',
         'feishu_remote_verification_token',
         'feishu_remote_allowed_open_ids',
+        'feishu_remote_allow_all',
         'wechat_remote_enabled',
         'wechat_remote_base_url',
         'wechat_remote_bot_token',
@@ -654,6 +696,7 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
         telegram_remote_enabled: rawSettings.telegram_remote_enabled === 'true',
         telegram_remote_token: rawSettings.telegram_remote_token || '',
         telegram_remote_allowed_chats: rawSettings.telegram_remote_allowed_chats || '',
+        telegram_remote_allow_all: rawSettings.telegram_remote_allow_all === 'true',
         telegram_remote_poll_timeout: rawSettings.telegram_remote_poll_timeout || '25',
         feishu_remote_enabled: rawSettings.feishu_remote_enabled === 'true',
         feishu_remote_app_id: rawSettings.feishu_remote_app_id || '',
@@ -661,6 +704,7 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
         feishu_remote_encrypt_key: rawSettings.feishu_remote_encrypt_key || '',
         feishu_remote_verification_token: rawSettings.feishu_remote_verification_token || '',
         feishu_remote_allowed_open_ids: rawSettings.feishu_remote_allowed_open_ids || '',
+        feishu_remote_allow_all: rawSettings.feishu_remote_allow_all === 'true',
         wechat_remote_enabled: rawSettings.wechat_remote_enabled === 'true',
         wechat_remote_base_url:
           rawSettings.wechat_remote_base_url || 'https://ilinkai.weixin.qq.com',
@@ -892,6 +936,16 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
     set({ feishu_remote_allowed_open_ids: value });
   },
 
+  setTelegramRemoteAllowAll: async (enabled: boolean) => {
+    await settingsDb.set('telegram_remote_allow_all', enabled.toString());
+    set({ telegram_remote_allow_all: enabled });
+  },
+
+  setFeishuRemoteAllowAll: async (enabled: boolean) => {
+    await settingsDb.set('feishu_remote_allow_all', enabled.toString());
+    set({ feishu_remote_allow_all: enabled });
+  },
+
   setWechatRemoteEnabled: async (enabled: boolean) => {
     await settingsDb.set('wechat_remote_enabled', enabled.toString());
     const updated = { ...get(), wechat_remote_enabled: enabled };
@@ -1439,6 +1493,10 @@ export const settingsManager = {
     useSettingsStore.getState().setFeishuRemoteVerificationToken(value),
   setFeishuRemoteAllowedOpenIds: (value: string) =>
     useSettingsStore.getState().setFeishuRemoteAllowedOpenIds(value),
+  setTelegramRemoteAllowAll: (enabled: boolean) =>
+    useSettingsStore.getState().setTelegramRemoteAllowAll(enabled),
+  setFeishuRemoteAllowAll: (enabled: boolean) =>
+    useSettingsStore.getState().setFeishuRemoteAllowAll(enabled),
   setWechatRemoteEnabled: (enabled: boolean) =>
     useSettingsStore.getState().setWechatRemoteEnabled(enabled),
   setWechatRemoteBaseUrl: (value: string) =>
@@ -8,7 +8,6 @@ import { databaseService } from '@/services/database-service';
 import { fastDirectoryTreeService } from '@/services/fast-directory-tree-service';
 import { repositoryService } from '@/services/repository-service';
 import { WindowManagerService } from '@/services/window-manager-service';
-import { WindowRestoreService } from '@/services/window-restore-service';
 import { settingsManager, useSettingsStore } from '@/stores/settings-store';
 
 function getTranslations() {
@@ -242,17 +241,6 @@ function createRepositoryStore() {
           logger.error('Failed to update window project:', error);
         }
 
-        // Save window state for restoration
-        try {
-          if (get().openRepositoryRequestId !== requestId) {
-            return;
-          }
-          await WindowRestoreService.saveCurrentWindowState(projectId, path);
-          logger.info('[openRepository] Window state saved');
-        } catch (error) {
-          logger.error('Failed to save window state:', error);
-        }
-
         // Track project opened for dock menu (non-blocking)
         databaseService
           .getProject(projectId)
@@ -4,7 +4,22 @@
 /**
  * Background task status
  */
-export type BackgroundTaskStatus = 'running' | 'completed' | 'failed' | 'killed' | 'timeout';
+export type BackgroundTaskStatus =
+  | 'running'
+  | 'completed'
+  | 'failed'
+  | 'killed'
+  | 'timeout'
+  | 'scheduled'
+  | 'queued';
+
+/**
+ * Recurrence for scheduled background tasks
+ */
+export type BackgroundTaskSchedule =
+  | { kind: 'every'; everyMs: number }
+  | { kind: 'cron'; expr: string; tz?: string }
+  | { kind: 'at'; at: string };
 
 /**
  * Background task information (camelCase naming)
@@ -45,6 +60,36 @@ export interface RustBackgroundTaskInfo {
   errorFile: string;
   maxTimeoutMs?: number;
   isTimedOut: boolean;
+  priority: number;
+  nextRunTime?: number;
+  schedule?: BackgroundTaskSchedule;
+}
+
+export interface RustScheduleBackgroundTaskRequest {
+  command: string;
+  cwd?: string;
+  maxTimeoutMs?: number;
+  maxBufferBytes?: number;
+  priority?: number;
+  cron?: string;
+  timezone?: string;
+  intervalMs?: number;
+}
+
+export interface RustScheduleBackgroundTaskResponse {
+  taskId: string;
+  nextRunTime: number;
+  schedule: BackgroundTaskSchedule;
+}
+
+/**
+ * Structured progress reported by a task (also emitted as
+ * `background-task-progress-{taskId}`)
+ */
+export interface BackgroundTaskProgress {
+  percent?: number;
+  stage?: string;
+  message?: string;
 }
 
 export interface RustGetTaskStatusResponse {
@@ -54,6 +99,7 @@ export interface RustGetTaskStatusResponse {
   runningTimeMs: number;
   outputBytes: number;
   errorBytes: number;
+  progress?: BackgroundTaskProgress;
 }
 
 export interface RustGetIncrementalOutputResponse {
@@ -63,12 +109,16 @@ export interface RustGetIncrementalOutputResponse {
   stdoutBytesRead: number;
   stderrBytesRead: number;
   isComplete: boolean;
+  stdoutTruncated?: boolean;
+  stderrTruncated?: boolean;
 }
 
 export interface RustListTasksResponse {
   tasks: RustBackgroundTaskInfo[];
   runningCount: number;
   completedCount: number;
+  scheduledCount: number;
+  queuedCount: number;
 }
 
 /**
@@ -103,6 +153,8 @@ export interface SpawnBackgroundTaskRequest {
   command: string;
   cwd?: string;
   maxTimeoutMs?: number;
+  maxBufferBytes?: number;
+  priority?: number;
 }
 
 /**
@@ -115,6 +167,7 @@ export interface SpawnBackgroundTaskResponse {
   errorFile: string;
   success: boolean;
   error?: string;
+  queued?: boolean;
 }
 
 /**
@@ -127,6 +180,7 @@ export interface GetTaskStatusResponse {
   runningTimeMs: number;
   outputBytes: number;
   errorBytes: number;
+  progress?: BackgroundTaskProgress;
 }
 
 /**
@@ -139,6 +193,8 @@ export interface GetIncrementalOutputResponse {
   stdoutBytesRead: number;
   stderrBytesRead: number;
   isComplete: boolean;
+  stdoutTruncated?: boolean;
+  stderrTruncated?: boolean;
 }
 
 /**
@@ -181,10 +237,20 @@ export interface BackgroundExecutionResult {
 }
 
 /**
- * Maximum concurrent tasks limit
+ * Maximum concurrent tasks limit, mirroring DEFAULT_MAX_CONCURRENT_TASKS in
+ * background_tasks.rs. Tasks spawned past it are queued by the backend.
  */
 export const MAX_CONCURRENT_TASKS = 10;
 
+/**
+ * Whether a task has finished; queued and scheduled tasks have not
+ */
+export function isTerminalTaskStatus(status: BackgroundTaskStatus): boolean {
+  return (
+    status === 'completed' || status === 'failed' || status === 'killed' || status === 'timeout'
+  );
+}
+
 /**
  * Polling interval configuration
  */
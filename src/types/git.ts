@@ -33,6 +33,15 @@ export interface GitStatus {
   untracked: string[];
   conflicted: string[];
   changesCount: number;
+  submodules: SubmoduleStatus[];
+}
+
+export interface SubmoduleStatus {
+  path: string;
+  head: string | null;
+  recordedHead: string | null;
+  isModified: boolean;
+  isUninitialized: boolean;
 }
 
 export enum DiffLineType {
@@ -64,15 +73,78 @@ export interface FileDiff {
   hunks: DiffHunk[];
   additions: number;
   deletions: number;
+  isBinary: boolean;
+  oldSize: number | null;
+  newSize: number | null;
 }
 
 export interface CommitInfo {
   hash: string;
   shortHash: string;
+  author: string;
+  email: string;
+  time: number;
+  summary: string;
+  body: string | null;
+}
+
+export interface CommitDetail {
+  commit: CommitInfo;
+  files: FileDiff[];
+  diffText: string;
+}
+
+export interface BranchEntry {
+  name: string;
+  isCurrent: boolean;
+  isRemote: boolean;
+  upstream: string | null;
+  commitHash: string | null;
+}
+
+export interface WordSpan {
+  start: number;
+  end: number;
+}
+
+export interface WordDiffLine {
+  lineType: DiffLineType;
+  oldLineNumber: number | null;
+  newLineNumber: number | null;
+  content: string;
+  spans: WordSpan[];
+}
+
+export interface ConflictHunk {
+  ours: string;
+  theirs: string;
+  base: string | null;
+  oursLabel: string | null;
+  theirsLabel: string | null;
+  startLine: number;
+  endLine: number;
+}
+
+export interface FetchResult {
+  remote: string;
+  updatedRefs: string[];
+  message: string;
+}
+
+export interface PullResult {
+  success: boolean;
+  fastForward: boolean;
+  upToDate: boolean;
+  headCommit: string | null;
+  hasConflicts: boolean;
+  conflictedFiles: string[];
+  message: string;
+}
+
+export interface PushResult {
+  success: boolean;
+  rejected: boolean;
   message: string;
-  authorName: string;
-  authorEmail: string;
-  timestamp: number;
 }
 
 // Helper types for UI components
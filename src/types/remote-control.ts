@@ -36,6 +36,12 @@ export interface RemoteInboundMessage {
   attachments?: RemoteAttachment[];
 }
 
+/** Control command parsed by a Rust gateway */
+export type GatewayCommand =
+  | { type: 'new'; prompt?: string | null }
+  | { type: 'stop' }
+  | { type: 'model'; modelId: string };
+
 export type MessageParseMode = 'HTML' | 'MarkdownV2' | 'plain';
 
 export interface RemoteSendMessageRequest {
@@ -78,6 +84,7 @@ export interface FeishuRemoteConfig {
   encryptKey: string;
   verificationToken: string;
   allowedOpenIds: string[];
+  allowAll: boolean;
 }
 
 export interface FeishuInboundMessage {
@@ -89,6 +96,13 @@ export interface FeishuInboundMessage {
   attachments?: FeishuRemoteAttachment[];
 }
 
+export interface FeishuInboundCommand {
+  chatId: string;
+  messageId: string;
+  openId: string;
+  command: GatewayCommand;
+}
+
 export interface FeishuSendMessageRequest {
   openId: string;
   text: string;
@@ -115,6 +129,7 @@ export interface TelegramRemoteConfig {
   enabled: boolean;
   token: string;
   allowedChatIds: number[];
+  allowAll: boolean;
   pollTimeoutSecs: number;
 }
 
@@ -129,6 +144,12 @@ export interface TelegramInboundMessage {
   attachments?: TelegramRemoteAttachment[];
 }
 
+export interface TelegramInboundCommand {
+  chatId: number;
+  messageId: number;
+  command: GatewayCommand;
+}
+
 export interface TelegramSendMessageRequest {
   chatId: number;
   text: string;
@@ -158,6 +179,62 @@ export interface TelegramEditMessageRequest {
   parseMode?: 'HTML' | 'MarkdownV2' | 'plain';
 }
 
+export interface DiscordRemoteConfig {
+  enabled: boolean;
+  token: string;
+  allowedChannelIds: string[];
+  allowedUserIds: string[];
+  allowAll: boolean;
+  denialMessage?: string | null;
+  commandPrefix: string;
+}
+
+export interface DiscordInboundMessage {
+  channelId: string;
+  guildId?: string | null;
+  isDm: boolean;
+  sessionKey: string;
+  messageId: string;
+  text: string;
+  userId: string;
+  username: string;
+  displayName?: string | null;
+  timestamp: string;
+}
+
+export interface DiscordInboundCommand {
+  channelId: string;
+  sessionKey: string;
+  messageId: string;
+  command: GatewayCommand;
+}
+
+export interface DiscordSendMessageRequest {
+  channelId: string;
+  text: string;
+  replyToMessageId?: string | null;
+}
+
+export interface DiscordSendMessageResponse {
+  messageId: string;
+}
+
+export interface DiscordEditMessageRequest {
+  channelId: string;
+  messageId: string;
+  text: string;
+}
+
+export interface DiscordGatewayStatus {
+  running: boolean;
+  connected: boolean;
+  botUserId?: string | null;
+  lastEventAtMs?: number | null;
+  lastError?: string | null;
+  lastErrorAtMs?: number | null;
+  backoffMs: number;
+}
+
 export interface WechatRemoteConfig {
   enabled: boolean;
   baseUrl: string;
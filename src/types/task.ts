@@ -30,6 +30,8 @@ export interface TaskSettings {
   autoApproveEdits?: boolean; // When true, skip review dialog for file edits in this task
   autoApprovePlan?: boolean; // When true, auto-approve plan for this task
   autoCodeReview?: boolean; // When true, auto-run code review for this task
+  maxIterations?: number; // Agent loop iteration budget; the loop stops once it is reached
+  disableGlobalSystemPrompt?: boolean; // Leave out the global system prompt prefix/suffix
   autoGitCommit?: boolean; // When true, auto-commit changes with AI message after task completes
   autoCheckFinish?: boolean; // When true, auto-check if task is truly complete after git commit
   planModeEnabled?: boolean; // Task-scoped plan mode override for prompt/environment generation
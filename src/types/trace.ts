@@ -6,6 +6,32 @@ export type TraceSummary = {
   spanCount: number;
 };
 
+/** Formats supported by the `export_traces` command */
+export type TraceExportFormat = 'otlp' | 'jaeger';
+
+/** p50/p95/p99 latency in milliseconds */
+export type LatencyPercentiles = {
+  count: number;
+  p50: number;
+  p95: number;
+  p99: number;
+};
+
+/** Per-model latency returned by the `query_trace_stats` command */
+export type ModelLatencyStats = {
+  model: string;
+  spanCount: number;
+  ttftMs: LatencyPercentiles | null;
+  totalMs: LatencyPercentiles | null;
+};
+
+/** Rows removed by the `prune_traces` command */
+export type TracePruneResult = {
+  traces: number;
+  spans: number;
+  events: number;
+};
+
 export type OpenAiSubscriptionTraceMetrics = {
   websocketTurnCount: number;
   incrementalTurnCount: number;